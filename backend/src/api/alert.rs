@@ -0,0 +1,99 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+use super::approval::current_user;
+use super::auth::Operator;
+use super::connection::AppError;
+use crate::db::AlertRepository;
+use crate::models::{AlertQuery, AlertRecord, PageQuery, PaginatedResponse, PipelineDetail};
+use crate::services::IncidentService;
+
+/// 查询告警历史，支持按状态、级别和 fingerprint 过滤
+#[utoipa::path(
+    get,
+    path = "/api/alerts",
+    tag = "alert",
+    params(AlertQuery, PageQuery),
+    responses((status = 200, body = PaginatedResponse<AlertRecord>))
+)]
+pub async fn list_alerts(
+    State(pool): State<MySqlPool>,
+    Query(query): Query<AlertQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Json<PaginatedResponse<AlertRecord>>, AppError> {
+    let repo = AlertRepository::new(&pool);
+    let limit = page.limit_within(AlertQuery::DEFAULT_LIMIT, AlertQuery::MAX_LIMIT);
+    let offset = page.get_offset();
+    let alerts = repo.find(&query, limit, offset).await?;
+    let total = repo.count(&query).await?;
+    Ok(Json(PaginatedResponse::new(alerts, total, limit, offset)))
+}
+
+/// 获取单条告警
+#[utoipa::path(
+    get,
+    path = "/api/alerts/{id}",
+    tag = "alert",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = AlertRecord))
+)]
+pub async fn get_alert(
+    State(pool): State<MySqlPool>,
+    Path(id): Path<i64>,
+) -> Result<Json<AlertRecord>, AppError> {
+    Ok(Json(AlertRepository::new(&pool).find_by_id(id).await?))
+}
+
+/// 手动解决告警，X-User 请求头记录为解决人
+#[utoipa::path(
+    post,
+    path = "/api/alerts/{id}/resolve",
+    tag = "alert",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn resolve_alert(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let user = current_user(&headers)?;
+    AlertRepository::new(&pool).resolve(id, &user).await?;
+    Ok(Json(json!({ "success": true })))
+}
+
+/// 关联到任务的告警（包括已解决的）
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/alerts",
+    tag = "alert",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = Vec<AlertRecord>))
+)]
+pub async fn list_task_alerts(
+    State(pool): State<MySqlPool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<AlertRecord>>, AppError> {
+    Ok(Json(IncidentService::task_alerts(&pool, id).await?))
+}
+
+/// 管道详情，target 为目标表 `database.table`，包含仍在触发的关联告警
+#[utoipa::path(
+    get,
+    path = "/api/pipelines/{target}",
+    tag = "alert",
+    params(("target" = String, Path)),
+    responses((status = 200, body = PipelineDetail))
+)]
+pub async fn get_pipeline_detail(
+    State(pool): State<MySqlPool>,
+    Path(target): Path<String>,
+) -> Result<Json<PipelineDetail>, AppError> {
+    Ok(Json(IncidentService::pipeline_detail(&pool, &target).await?))
+}
@@ -0,0 +1,191 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::HeaderMap,
+};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+use super::approval::current_user;
+use super::auth::Operator;
+use super::connection::AppError;
+use crate::db::AlertRoutingRepository;
+use crate::models::{AlertRoute, AlertRouteRequest, AlertSilence, AlertSilenceRequest};
+
+/// 列出静默窗口时返回的最大条数
+const SILENCE_LIST_LIMIT: i64 = 200;
+
+fn invalid(message: String) -> AppError {
+    crate::utils::error::AppError::Validation(message).into()
+}
+
+fn validate_route(request: &AlertRouteRequest) -> Result<(), AppError> {
+    request.validate().map_err(invalid)
+}
+
+fn validate_silence(request: &AlertSilenceRequest) -> Result<(), AppError> {
+    request.validate().map_err(invalid)
+}
+
+/// 列出告警路由规则，按匹配顺序排列
+#[utoipa::path(
+    get,
+    path = "/api/alert_routes",
+    tag = "alert_routing",
+    responses((status = 200, body = Vec<AlertRoute>))
+)]
+pub async fn list_routes(State(pool): State<MySqlPool>) -> Result<Json<Vec<AlertRoute>>, AppError> {
+    Ok(Json(
+        AlertRoutingRepository::new(&pool).find_routes().await?,
+    ))
+}
+
+/// 创建告警路由规则
+#[utoipa::path(
+    post,
+    path = "/api/alert_routes",
+    tag = "alert_routing",
+    request_body = AlertRouteRequest,
+    responses((status = 200, body = AlertRoute))
+)]
+pub async fn create_route(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Json(request): Json<AlertRouteRequest>,
+) -> Result<Json<AlertRoute>, AppError> {
+    validate_route(&request)?;
+    let repo = AlertRoutingRepository::new(&pool);
+    let id = repo.create_route(&request).await?;
+    Ok(Json(repo.find_route(id).await?))
+}
+
+/// 更新告警路由规则
+#[utoipa::path(
+    put,
+    path = "/api/alert_routes/{id}",
+    tag = "alert_routing",
+    params(("id" = i64, Path)),
+    request_body = AlertRouteRequest,
+    responses((status = 200, body = AlertRoute))
+)]
+pub async fn update_route(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+    Json(request): Json<AlertRouteRequest>,
+) -> Result<Json<AlertRoute>, AppError> {
+    validate_route(&request)?;
+    let repo = AlertRoutingRepository::new(&pool);
+    repo.update_route(id, &request).await?;
+    Ok(Json(repo.find_route(id).await?))
+}
+
+/// 删除告警路由规则
+#[utoipa::path(
+    delete,
+    path = "/api/alert_routes/{id}",
+    tag = "alert_routing",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn delete_route(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    AlertRoutingRepository::new(&pool).delete_route(id).await?;
+    Ok(Json(json!({ "success": true })))
+}
+
+/// 列出最近的静默窗口
+#[utoipa::path(
+    get,
+    path = "/api/alert_silences",
+    tag = "alert_routing",
+    responses((status = 200, body = Vec<AlertSilence>))
+)]
+pub async fn list_silences(
+    State(pool): State<MySqlPool>,
+) -> Result<Json<Vec<AlertSilence>>, AppError> {
+    Ok(Json(
+        AlertRoutingRepository::new(&pool)
+            .find_silences(SILENCE_LIST_LIMIT)
+            .await?,
+    ))
+}
+
+/// 创建静默窗口，X-User 请求头记录为创建人
+#[utoipa::path(
+    post,
+    path = "/api/alert_silences",
+    tag = "alert_routing",
+    request_body = AlertSilenceRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn create_silence(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<AlertSilenceRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    validate_silence(&request)?;
+    let created_by = current_user(&headers).ok();
+    let id = AlertRoutingRepository::new(&pool)
+        .create_silence(&request, created_by.as_deref())
+        .await?;
+    Ok(Json(json!({ "success": true, "id": id })))
+}
+
+/// 删除静默窗口，立即恢复告警发送
+#[utoipa::path(
+    delete,
+    path = "/api/alert_silences/{id}",
+    tag = "alert_routing",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn delete_silence(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    AlertRoutingRepository::new(&pool)
+        .delete_silence(id)
+        .await?;
+    Ok(Json(json!({ "success": true })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_alert_routing_requests() {
+        let request: AlertRouteRequest = serde_json::from_str(
+            r#"{"name": "sink", "matchers": [{"label": "component", "value": "sink"}], "channel_ids": [1]}"#,
+        )
+        .unwrap();
+        assert!(validate_route(&request).is_ok());
+        assert!(request.enabled);
+
+        let request: AlertRouteRequest = serde_json::from_str(
+            r#"{"name": "bad", "matchers": [{"label": "severity", "op": "=~", "value": "(critical"}], "channel_ids": [1]}"#,
+        )
+        .unwrap();
+        assert!(validate_route(&request).is_err());
+
+        let request: AlertRouteRequest =
+            serde_json::from_str(r#"{"name": "empty", "channel_ids": []}"#).unwrap();
+        assert!(validate_route(&request).is_err());
+
+        let request: AlertSilenceRequest = serde_json::from_str(
+            r#"{"matchers": [{"label": "alertname", "value": "SinkLag"}], "ends_at": "2000-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        assert!(validate_silence(&request).is_err());
+
+        let request: AlertSilenceRequest =
+            serde_json::from_str(r#"{"matchers": [], "ends_at": "2999-01-01T00:00:00Z"}"#).unwrap();
+        assert!(validate_silence(&request).is_err());
+    }
+}
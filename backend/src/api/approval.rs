@@ -0,0 +1,106 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+};
+use sqlx::MySqlPool;
+
+use super::auth::Operator;
+use super::connection::AppError;
+use crate::models::{ApprovalDecision, ApprovalQuery, ApprovalRequest};
+use crate::services::ApprovalService;
+
+/// 标识当前操作用户的请求头，用于记录审批的申请人和审批人
+pub(crate) const USER_HEADER: &str = "x-user";
+
+/// 读取当前操作用户
+pub fn current_user(headers: &HeaderMap) -> Result<String, AppError> {
+    request_user(headers).ok_or_else(|| {
+        AppError(crate::utils::error::AppError::Validation(
+            "Missing X-User header".to_string(),
+        ))
+    })
+}
+
+/// 读取操作用户，未提供时返回 None，用于记录审计日志
+pub fn request_user(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(USER_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty() && v.len() <= 100)
+        .map(str::to_string)
+}
+
+/// 列出审批单
+#[utoipa::path(
+    get,
+    path = "/api/approvals",
+    tag = "approval",
+    params(ApprovalQuery),
+    responses((status = 200, body = Vec<ApprovalRequest>))
+)]
+pub async fn list_approvals(
+    State(pool): State<MySqlPool>,
+    Query(params): Query<ApprovalQuery>,
+) -> Result<Json<Vec<ApprovalRequest>>, AppError> {
+    let approvals = ApprovalService::list(&pool, params.status).await?;
+    Ok(Json(approvals))
+}
+
+/// 批准审批单并提交同步任务
+#[utoipa::path(
+    post,
+    path = "/api/approvals/{id}/approve",
+    tag = "approval",
+    params(("id" = i64, Path)),
+    request_body = Option<ApprovalDecision>,
+    responses((status = 200, body = ApprovalRequest))
+)]
+pub async fn approve(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+    decision: Option<Json<ApprovalDecision>>,
+) -> Result<Json<ApprovalRequest>, AppError> {
+    let user = current_user(&headers)?;
+    let comment = decision.and_then(|Json(d)| d.comment);
+    let approval = ApprovalService::approve(&pool, id, &user, comment.as_deref()).await?;
+    Ok(Json(approval))
+}
+
+/// 拒绝审批单
+#[utoipa::path(
+    post,
+    path = "/api/approvals/{id}/reject",
+    tag = "approval",
+    params(("id" = i64, Path)),
+    request_body = Option<ApprovalDecision>,
+    responses((status = 200, body = ApprovalRequest))
+)]
+pub async fn reject(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+    decision: Option<Json<ApprovalDecision>>,
+) -> Result<Json<ApprovalRequest>, AppError> {
+    let user = current_user(&headers)?;
+    let comment = decision.and_then(|Json(d)| d.comment);
+    let approval = ApprovalService::reject(&pool, id, &user, comment.as_deref()).await?;
+    Ok(Json(approval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_user() {
+        let mut headers = HeaderMap::new();
+        assert!(current_user(&headers).is_err());
+        headers.insert(USER_HEADER, " alice ".parse().unwrap());
+        assert_eq!(current_user(&headers).ok().as_deref(), Some("alice"));
+    }
+}
@@ -0,0 +1,32 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use sqlx::MySqlPool;
+
+use super::auth::Admin;
+use super::connection::AppError;
+use crate::db::AuditRepository;
+use crate::models::{AuditLog, AuditQuery, PageQuery, PaginatedResponse};
+
+/// 查询审计日志，支持按操作人、操作类型、目标对象、任务和时间范围过滤
+#[utoipa::path(
+    get,
+    path = "/api/audit",
+    tag = "audit",
+    params(AuditQuery, PageQuery),
+    responses((status = 200, body = PaginatedResponse<AuditLog>))
+)]
+pub async fn list_audit_logs(
+    State(pool): State<MySqlPool>,
+    _: Admin,
+    Query(query): Query<AuditQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Json<PaginatedResponse<AuditLog>>, AppError> {
+    let repo = AuditRepository::new(&pool);
+    let limit = page.limit_within(AuditQuery::DEFAULT_LIMIT, AuditQuery::MAX_LIMIT);
+    let offset = page.get_offset();
+    let logs = repo.find(&query, limit, offset).await?;
+    let total = repo.count(&query).await?;
+    Ok(Json(PaginatedResponse::new(logs, total, limit, offset)))
+}
@@ -0,0 +1,431 @@
+use axum::{
+    Extension, Json,
+    extract::{FromRequestParts, Path, Request, State},
+    http::{HeaderValue, Method, StatusCode, header, request::Parts},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+use super::approval::USER_HEADER;
+use super::connection::AppError;
+use crate::db::{ApiTokenRepository, UserRepository};
+use crate::models::{
+    ApiToken, AuthUser, ChangePasswordRequest, CreateApiTokenRequest, CreateUserRequest,
+    CreatedApiToken, LoginRequest, LoginResponse, Role, UpdateUserRoleRequest, User,
+};
+use crate::services::AuthService;
+use crate::utils::error::AppError as CoreError;
+
+/// 不需要登录即可访问的路径：健康检查、登录接口、接口文档，以及 /api 之外的前端页面和公开状态页
+fn is_public(method: &Method, path: &str) -> bool {
+    // CORS 预检请求不带 Authorization
+    if method == Method::OPTIONS {
+        return true;
+    }
+    !(path == "/api" || path.starts_with("/api/"))
+        || path == "/api/health"
+        || path == "/api/webhook/health"
+        || path == "/api/auth/login"
+        || path == super::openapi::SPEC_PATH
+        || path == super::openapi::DOCS_PATH
+        || path.starts_with(&format!("{}/", super::openapi::DOCS_PATH))
+}
+
+fn bearer_token(request: &Request) -> Option<&str> {
+    request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| {
+            v.strip_prefix("Bearer ")
+                .or_else(|| v.strip_prefix("bearer "))
+        })
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+fn unauthorized(message: &str) -> Response {
+    (StatusCode::UNAUTHORIZED, Json(json!({ "error": message }))).into_response()
+}
+
+/// 认证中间件：/api 下的接口需要 `Authorization: Bearer <token>`，token 为登录返回的 JWT 或 API Token
+/// 认证通过后用登录用户覆盖 X-User 请求头，审批、告警等记录操作人的接口不再信任客户端传入的值
+pub async fn require_auth(
+    State(pool): State<MySqlPool>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if !AuthService::is_enabled() || is_public(request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let Some(token) = bearer_token(&request) else {
+        return unauthorized("Missing bearer token");
+    };
+    let user = match AuthService::authenticate(&pool, token).await {
+        Ok(user) => user,
+        Err(CoreError::Unauthorized(msg)) => return unauthorized(&msg),
+        Err(e) => return AppError(e).into_response(),
+    };
+
+    match HeaderValue::from_str(&user.username) {
+        Ok(value) => {
+            request.headers_mut().insert(USER_HEADER, value);
+        }
+        Err(_) => {
+            request.headers_mut().remove(USER_HEADER);
+        }
+    }
+    request.extensions_mut().insert(user);
+    next.run(request).await
+}
+
+/// 读取当前登录用户，认证关闭时返回 401
+fn auth_user(user: Option<Extension<AuthUser>>) -> Result<AuthUser, AppError> {
+    user.map(|Extension(user)| user).ok_or_else(|| {
+        AppError(CoreError::Unauthorized(
+            "Authentication is disabled or the request is not authenticated".to_string(),
+        ))
+    })
+}
+
+/// 检查当前用户是否至少具有 required 角色，返回当前用户；认证关闭时不检查
+pub(crate) fn authorize(user: Option<&AuthUser>, required: Role) -> Result<Option<AuthUser>, AppError> {
+    if !AuthService::is_enabled() {
+        return Ok(user.cloned());
+    }
+    match user {
+        Some(user) if user.role >= required => Ok(Some(user.clone())),
+        Some(user) => Err(AppError(CoreError::Forbidden(format!(
+            "Role {} is required, current role is {}",
+            required.as_str(),
+            user.role.as_str()
+        )))),
+        None => Err(AppError(CoreError::Unauthorized(
+            "The request is not authenticated".to_string(),
+        ))),
+    }
+}
+
+/// 角色提取器：handler 参数中声明后，当前用户低于 Operator 时返回 403
+/// 执行同步、删除对象、修改连接和告警配置等写操作都需要 Operator
+pub struct Operator(pub Option<AuthUser>);
+
+/// 角色提取器：要求 Admin，用于用户管理、运行时设置和功能开关
+pub struct Admin(pub Option<AuthUser>);
+
+#[axum::async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Operator {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        authorize(parts.extensions.get::<AuthUser>(), Role::Operator).map(Operator)
+    }
+}
+
+#[axum::async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Admin {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        authorize(parts.extensions.get::<AuthUser>(), Role::Admin).map(Admin)
+    }
+}
+
+/// 用户名密码登录，返回会话 token
+#[utoipa::path(
+    post,
+    path = "/api/auth/login",
+    tag = "auth",
+    security(()),
+    request_body = LoginRequest,
+    responses((status = 200, body = LoginResponse))
+)]
+pub async fn login(
+    State(pool): State<MySqlPool>,
+    Json(request): Json<LoginRequest>,
+) -> Result<Json<LoginResponse>, AppError> {
+    Ok(Json(
+        AuthService::login(&pool, &request.username, &request.password).await?,
+    ))
+}
+
+/// 当前登录用户
+#[utoipa::path(
+    get,
+    path = "/api/auth/me",
+    tag = "auth",
+    responses((status = 200, body = User))
+)]
+pub async fn me(
+    State(pool): State<MySqlPool>,
+    user: Option<Extension<AuthUser>>,
+) -> Result<Json<User>, AppError> {
+    let user = auth_user(user)?;
+    Ok(Json(UserRepository::new(&pool).find_by_id(user.id).await?))
+}
+
+/// 修改自己的密码
+#[utoipa::path(
+    post,
+    path = "/api/auth/password",
+    tag = "auth",
+    request_body = ChangePasswordRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn change_password(
+    State(pool): State<MySqlPool>,
+    user: Option<Extension<AuthUser>>,
+    Json(request): Json<ChangePasswordRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let user = auth_user(user)?;
+    let repo = UserRepository::new(&pool);
+    let current = repo.find_by_id(user.id).await?;
+    if !AuthService::verify_password(&request.old_password, &current.password_hash) {
+        return Err(AppError(CoreError::Validation(
+            "Current password is incorrect".to_string(),
+        )));
+    }
+    AuthService::validate_password(&request.new_password)?;
+    repo.update_password(user.id, &AuthService::hash_password(&request.new_password)?)
+        .await?;
+    Ok(Json(json!({ "success": true })))
+}
+
+/// 列出自己的 API Token（不返回明文）
+#[utoipa::path(
+    get,
+    path = "/api/auth/tokens",
+    tag = "auth",
+    responses((status = 200, body = Vec<ApiToken>))
+)]
+pub async fn list_tokens(
+    State(pool): State<MySqlPool>,
+    user: Option<Extension<AuthUser>>,
+) -> Result<Json<Vec<ApiToken>>, AppError> {
+    let user = auth_user(user)?;
+    Ok(Json(
+        ApiTokenRepository::new(&pool).find_by_user(user.id).await?,
+    ))
+}
+
+/// 创建 API Token，明文只在响应中出现一次
+#[utoipa::path(
+    post,
+    path = "/api/auth/tokens",
+    tag = "auth",
+    request_body = CreateApiTokenRequest,
+    responses((status = 200, body = CreatedApiToken))
+)]
+pub async fn create_token(
+    State(pool): State<MySqlPool>,
+    user: Option<Extension<AuthUser>>,
+    Json(request): Json<CreateApiTokenRequest>,
+) -> Result<Json<CreatedApiToken>, AppError> {
+    let user = auth_user(user)?;
+    let name = request.name.trim();
+    if name.is_empty() || name.len() > 100 {
+        return Err(AppError(CoreError::Validation(
+            "Token name must be 1-100 characters".to_string(),
+        )));
+    }
+    if request.expires_in_days.is_some_and(|days| days <= 0) {
+        return Err(AppError(CoreError::Validation(
+            "expires_in_days must be positive".to_string(),
+        )));
+    }
+    Ok(Json(
+        AuthService::create_api_token(&pool, user.id, name, request.expires_in_days).await?,
+    ))
+}
+
+/// 吊销自己的 API Token
+#[utoipa::path(
+    delete,
+    path = "/api/auth/tokens/{id}",
+    tag = "auth",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn delete_token(
+    State(pool): State<MySqlPool>,
+    user: Option<Extension<AuthUser>>,
+    Path(id): Path<i64>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let user = auth_user(user)?;
+    ApiTokenRepository::new(&pool).delete(id, user.id).await?;
+    Ok(Json(json!({ "success": true })))
+}
+
+/// 列出所有用户
+#[utoipa::path(
+    get,
+    path = "/api/users",
+    tag = "auth",
+    responses((status = 200, body = Vec<User>))
+)]
+pub async fn list_users(
+    State(pool): State<MySqlPool>,
+    _: Admin,
+) -> Result<Json<Vec<User>>, AppError> {
+    Ok(Json(UserRepository::new(&pool).find_all().await?))
+}
+
+/// 创建用户
+#[utoipa::path(
+    post,
+    path = "/api/users",
+    tag = "auth",
+    request_body = CreateUserRequest,
+    responses((status = 200, body = User))
+)]
+pub async fn create_user(
+    State(pool): State<MySqlPool>,
+    _: Admin,
+    Json(request): Json<CreateUserRequest>,
+) -> Result<Json<User>, AppError> {
+    let username = request.username.trim();
+    if username.is_empty()
+        || username.len() > 100
+        || !username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '@'))
+    {
+        return Err(AppError(CoreError::Validation(
+            "Username must be 1-100 characters of letters, digits, '_', '-', '.' or '@'"
+                .to_string(),
+        )));
+    }
+    AuthService::validate_password(&request.password)?;
+
+    let repo = UserRepository::new(&pool);
+    let id = repo
+        .create(
+            username,
+            &AuthService::hash_password(&request.password)?,
+            request.role,
+        )
+        .await?;
+    Ok(Json(repo.find_by_id(id).await?))
+}
+
+/// 修改用户角色，不能修改自己的角色
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}/role",
+    tag = "auth",
+    params(("id" = i64, Path)),
+    request_body = UpdateUserRoleRequest,
+    responses((status = 200, body = User))
+)]
+pub async fn update_user_role(
+    State(pool): State<MySqlPool>,
+    Admin(user): Admin,
+    Path(id): Path<i64>,
+    Json(request): Json<UpdateUserRoleRequest>,
+) -> Result<Json<User>, AppError> {
+    if user.is_some_and(|user| user.id == id) {
+        return Err(AppError(CoreError::Validation(
+            "Cannot change the role of the current user".to_string(),
+        )));
+    }
+    let repo = UserRepository::new(&pool);
+    repo.update_role(id, request.role).await?;
+    Ok(Json(repo.find_by_id(id).await?))
+}
+
+/// 删除用户，不能删除自己
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}",
+    tag = "auth",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn delete_user(
+    State(pool): State<MySqlPool>,
+    Admin(user): Admin,
+    Path(id): Path<i64>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    if user.is_some_and(|user| user.id == id) {
+        return Err(AppError(CoreError::Validation(
+            "Cannot delete the current user".to_string(),
+        )));
+    }
+    UserRepository::new(&pool).delete(id).await?;
+    Ok(Json(json!({ "success": true })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_public() {
+        assert!(is_public(&Method::GET, "/api/health"));
+        assert!(is_public(&Method::POST, "/api/auth/login"));
+        assert!(is_public(&Method::GET, "/status.json"));
+        assert!(is_public(&Method::GET, "/connections"));
+        assert!(is_public(&Method::OPTIONS, "/api/connections"));
+        assert!(is_public(&Method::GET, "/api/openapi.json"));
+        assert!(is_public(&Method::GET, "/api/docs/index.html"));
+        assert!(!is_public(&Method::GET, "/api/docsx"));
+        assert!(!is_public(&Method::GET, "/api"));
+        assert!(!is_public(&Method::GET, "/api/connections"));
+        assert!(!is_public(&Method::GET, "/api/auth/me"));
+        assert!(!is_public(&Method::POST, "/api/webhook/alertmanager"));
+        assert!(!is_public(&Method::POST, "/api/risingwave/sinks/delete"));
+    }
+
+    #[test]
+    fn test_authorize_roles() {
+        let user = |role| AuthUser {
+            id: 1,
+            username: "alice".to_string(),
+            role,
+            token_id: None,
+        };
+        assert!(Role::Viewer < Role::Operator && Role::Operator < Role::Admin);
+        assert!(authorize(Some(&user(Role::Operator)), Role::Operator).is_ok());
+        assert!(authorize(Some(&user(Role::Admin)), Role::Operator).is_ok());
+        assert!(matches!(
+            authorize(Some(&user(Role::Viewer)), Role::Operator),
+            Err(AppError(CoreError::Forbidden(_)))
+        ));
+        assert!(matches!(
+            authorize(Some(&user(Role::Operator)), Role::Admin),
+            Err(AppError(CoreError::Forbidden(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_viewer_cannot_preview_rows() {
+        use axum::{body::Body, http::Request, routing::post, Extension, Router};
+        use tower::Service;
+
+        let pool = sqlx::MySqlPool::connect_lazy("mysql://localhost/unused").unwrap();
+        let mut app = Router::new()
+            .route("/api/metadata/preview", post(super::super::metadata::preview_table))
+            .layer(Extension(AuthUser {
+                id: 1,
+                username: "viewer".to_string(),
+                role: Role::Viewer,
+                token_id: None,
+            }))
+            .with_state(pool);
+        let response = app
+            .call(
+                Request::post("/api/metadata/preview")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"config_id": 1, "database": "mysql", "table": "user"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
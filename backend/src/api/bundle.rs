@@ -0,0 +1,132 @@
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+};
+use sqlx::MySqlPool;
+
+use super::approval::request_user;
+use super::auth::Operator;
+use super::connection::AppError;
+use crate::db::ConfigRepository;
+use crate::models::{ExportRequest, ImportResult, MonitoringImportResult};
+use crate::services::{AuditService, BundleService};
+
+/// 导入时传入导出口令的请求头
+const PASSPHRASE_HEADER: &str = "x-bundle-passphrase";
+
+/// 导出连接配置和同步定义（YAML）
+#[utoipa::path(
+    post,
+    path = "/api/export",
+    tag = "bundle",
+    request_body = Option<ExportRequest>,
+    responses((status = 200, description = "YAML 导出文件", content_type = "application/yaml", body = String))
+)]
+pub async fn export_bundle(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    request: Option<Json<ExportRequest>>,
+) -> Result<Response, AppError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let bundle = BundleService::export(&pool, &request).await?;
+    let yaml = BundleService::to_yaml(&bundle)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/yaml; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"rw-cdc-sr-bundle.yaml\"",
+            ),
+        ],
+        yaml,
+    )
+        .into_response())
+}
+
+/// 导入 YAML 导出文件，请求体为 YAML 文本
+#[utoipa::path(
+    post,
+    path = "/api/import",
+    tag = "bundle",
+    request_body(content = String, content_type = "application/yaml", description = "导出的 YAML 文本"),
+    responses((status = 200, body = ImportResult))
+)]
+pub async fn import_bundle(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<ImportResult>, AppError> {
+    let bundle = BundleService::from_yaml(&body)?;
+    let passphrase = headers.get(PASSPHRASE_HEADER).and_then(|v| v.to_str().ok());
+    let result = BundleService::import(&pool, bundle, passphrase).await?;
+
+    let actor = request_user(&headers);
+    for config in ConfigRepository::new(&pool).find_all().await? {
+        if result.created.contains(&config.name) {
+            AuditService::record_connection(
+                &pool,
+                actor.as_deref(),
+                "connection_import",
+                config.id,
+                &config.name,
+            )
+            .await;
+        }
+    }
+    Ok(Json(result))
+}
+
+/// 导出通知渠道、告警路由规则和静默窗口（YAML）
+#[utoipa::path(
+    post,
+    path = "/api/export/monitoring",
+    tag = "bundle",
+    request_body = Option<ExportRequest>,
+    responses((status = 200, description = "YAML 导出文件", content_type = "application/yaml", body = String))
+)]
+pub async fn export_monitoring(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    request: Option<Json<ExportRequest>>,
+) -> Result<Response, AppError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let bundle = BundleService::export_monitoring(&pool, &request).await?;
+    let yaml = BundleService::to_yaml(&bundle)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/yaml; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"rw-cdc-sr-monitoring.yaml\"",
+            ),
+        ],
+        yaml,
+    )
+        .into_response())
+}
+
+/// 导入监控配置的 YAML 导出文件，重复导入同一文件不会产生重复记录
+#[utoipa::path(
+    post,
+    path = "/api/import/monitoring",
+    tag = "bundle",
+    request_body(content = String, content_type = "application/yaml", description = "导出的 YAML 文本"),
+    responses((status = 200, body = MonitoringImportResult))
+)]
+pub async fn import_monitoring(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<MonitoringImportResult>, AppError> {
+    let bundle = BundleService::from_yaml(&body)?;
+    let passphrase = headers.get(PASSPHRASE_HEADER).and_then(|v| v.to_str().ok());
+    Ok(Json(
+        BundleService::import_monitoring(&pool, bundle, passphrase).await?,
+    ))
+}
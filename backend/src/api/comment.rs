@@ -0,0 +1,116 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::HeaderMap,
+};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+use super::approval::current_user;
+use super::connection::AppError;
+use crate::models::{Comment, CommentSubject, CreateCommentRequest};
+use crate::services::CommentService;
+
+/// 任务评论
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/comments",
+    tag = "comment",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = Vec<Comment>))
+)]
+pub async fn list_task_comments(
+    State(pool): State<MySqlPool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<Comment>>, AppError> {
+    let comments = CommentService::list(&pool, CommentSubject::Task, &id.to_string()).await?;
+    Ok(Json(comments))
+}
+
+/// 添加任务评论
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/comments",
+    tag = "comment",
+    params(("id" = i64, Path)),
+    request_body = CreateCommentRequest,
+    responses((status = 200, body = Comment))
+)]
+pub async fn add_task_comment(
+    State(pool): State<MySqlPool>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+    Json(request): Json<CreateCommentRequest>,
+) -> Result<Json<Comment>, AppError> {
+    let author = current_user(&headers)?;
+    let comment = CommentService::add(
+        &pool,
+        CommentSubject::Task,
+        &id.to_string(),
+        &author,
+        &request.body,
+    )
+    .await?;
+    Ok(Json(comment))
+}
+
+/// 管道评论，target 为目标表 `database.table`
+#[utoipa::path(
+    get,
+    path = "/api/pipelines/{target}/comments",
+    tag = "comment",
+    params(("target" = String, Path)),
+    responses((status = 200, body = Vec<Comment>))
+)]
+pub async fn list_pipeline_comments(
+    State(pool): State<MySqlPool>,
+    Path(target): Path<String>,
+) -> Result<Json<Vec<Comment>>, AppError> {
+    let comments = CommentService::list(&pool, CommentSubject::Pipeline, &target).await?;
+    Ok(Json(comments))
+}
+
+/// 添加管道评论
+#[utoipa::path(
+    post,
+    path = "/api/pipelines/{target}/comments",
+    tag = "comment",
+    params(("target" = String, Path)),
+    request_body = CreateCommentRequest,
+    responses((status = 200, body = Comment))
+)]
+pub async fn add_pipeline_comment(
+    State(pool): State<MySqlPool>,
+    Path(target): Path<String>,
+    headers: HeaderMap,
+    Json(request): Json<CreateCommentRequest>,
+) -> Result<Json<Comment>, AppError> {
+    let author = current_user(&headers)?;
+    let comment = CommentService::add(
+        &pool,
+        CommentSubject::Pipeline,
+        &target,
+        &author,
+        &request.body,
+    )
+    .await?;
+    Ok(Json(comment))
+}
+
+/// 删除评论（仅作者本人）
+#[utoipa::path(
+    delete,
+    path = "/api/comments/{id}",
+    tag = "comment",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn delete_comment(
+    State(pool): State<MySqlPool>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let user = current_user(&headers)?;
+    CommentService::delete(&pool, id, &user).await?;
+    Ok(Json(json!({ "success": true })))
+}
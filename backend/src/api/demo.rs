@@ -0,0 +1,186 @@
+use axum::{
+    Json,
+    extract::Request,
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+
+/// 演示模式下仍然开放的 POST 接口：登录、连接测试、元数据浏览、预览和只读查询
+/// 其余 POST/PUT/DELETE 一律拒绝，新增的写接口默认在演示模式下不可用
+const ALLOWED_POSTS: &[&str] = &[
+    "/api/auth/login",
+    "/api/connections/test/mysql",
+    "/api/connections/test/risingwave",
+    "/api/connections/test/starrocks",
+    "/api/connections/test/doris",
+    "/api/connections/parse_url",
+    "/api/metadata/databases",
+    "/api/metadata/tables",
+    "/api/metadata/schema",
+    "/api/metadata/preview",
+    "/api/sync/preview",
+    "/api/schedules/preview",
+    "/api/export",
+    "/api/export/monitoring",
+    "/api/risingwave/query",
+];
+
+/// 演示模式下拒绝删除数据或对外部系统执行 DDL 的请求
+pub async fn reject_destructive(request: Request, next: Next) -> Response {
+    if !is_allowed(request.method(), request.uri().path()) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "This endpoint is disabled in demo mode" })),
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
+fn is_allowed(method: &Method, path: &str) -> bool {
+    match *method {
+        Method::GET | Method::HEAD | Method::OPTIONS => true,
+        Method::POST => ALLOWED_POSTS.contains(&path),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 从 create_router 的定义中读出所有 (方法, 路径)
+    fn declared_routes() -> Vec<(Method, String)> {
+        let source = include_str!("mod.rs");
+        let methods = regex::Regex::new(r"\b(get|post|put|delete)\(").unwrap();
+        let mut routes = Vec::new();
+        for (start, _) in source.match_indices(".route(") {
+            // 取出 .route( 对应的完整参数
+            let body = &source[start + ".route(".len()..];
+            let mut depth = 1;
+            let end = body
+                .char_indices()
+                .find(|&(_, c)| {
+                    match c {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                    depth == 0
+                })
+                .map(|(i, _)| i)
+                .unwrap();
+            let body = &body[..end];
+            let path = body.split('"').nth(1).unwrap().to_string();
+            for m in methods.captures_iter(body) {
+                let method = Method::from_bytes(m[1].to_uppercase().as_bytes()).unwrap();
+                routes.push((method, path.clone()));
+            }
+        }
+        routes
+    }
+
+    /// 把 :id 之类的路径参数替换为示例值
+    fn sample_path(path: &str) -> String {
+        path.split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some("target") => "shop.orders",
+                Some("name") => "orders_sink",
+                Some(_) => "1",
+                None => segment,
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    #[test]
+    fn test_is_allowed() {
+        assert!(!is_allowed(&Method::DELETE, "/api/connections/1"));
+        assert!(!is_allowed(&Method::PUT, "/api/sync_mappings/1"));
+        assert!(!is_allowed(&Method::POST, "/api/risingwave/sinks/delete"));
+        assert!(!is_allowed(&Method::POST, "/api/tasks/3/cancel"));
+        assert!(!is_allowed(&Method::POST, "/api/sync/multiple"));
+        assert!(!is_allowed(&Method::POST, "/api/risingwave/cleanup"));
+        assert!(!is_allowed(&Method::POST, "/api/risingwave/sources/kafka"));
+        assert!(!is_allowed(&Method::POST, "/api/tasks/retry-batch"));
+        assert!(is_allowed(&Method::GET, "/api/tasks/history"));
+        assert!(is_allowed(&Method::GET, "/api/risingwave/cleanup"));
+        assert!(is_allowed(&Method::POST, "/api/connections/test/mysql"));
+        assert!(is_allowed(&Method::POST, "/api/export"));
+    }
+
+    #[test]
+    fn test_every_write_route_is_blocked_or_allowlisted() {
+        let routes = declared_routes();
+        assert!(routes.len() > 100, "only found {} routes", routes.len());
+
+        // 白名单中的路径必须是真实存在的 POST 路由
+        for allowed in ALLOWED_POSTS {
+            assert!(
+                routes
+                    .iter()
+                    .any(|(m, p)| m == Method::POST && p == allowed),
+                "{} is allowlisted but not routed",
+                allowed
+            );
+        }
+
+        let mut blocked = Vec::new();
+        for (method, path) in &routes {
+            if *method == Method::GET {
+                continue;
+            }
+            let sample = sample_path(path);
+            if is_allowed(method, &sample) {
+                assert!(
+                    *method == Method::POST && ALLOWED_POSTS.contains(&path.as_str()),
+                    "{} {} passes the demo guard without being allowlisted",
+                    method,
+                    path
+                );
+            } else {
+                blocked.push(format!("{} {}", method, path));
+            }
+        }
+
+        for route in [
+            "POST /api/risingwave/cleanup",
+            "POST /api/risingwave/managed_objects/gc",
+            "POST /api/risingwave/secrets/delete_unused",
+            "POST /api/pipelines/cleanup",
+            "POST /api/pipelines/:id/pause",
+            "POST /api/pipelines/:id/resume",
+            "POST /api/sync_mappings/:id/run",
+            "POST /api/tasks/retry-batch",
+            "POST /api/connections/:id/rotate-password",
+            "POST /api/risingwave/sinks/batch_create",
+            "POST /api/risingwave/sinks/recreate",
+            "POST /api/risingwave/sources/kafka",
+            "POST /api/import/monitoring",
+            "PUT /api/sync_mappings/:id",
+            "DELETE /api/connections/:id",
+        ] {
+            assert!(blocked.iter().any(|b| b == route), "{} is not blocked", route);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_demo_layer_rejects_writes() {
+        use axum::{Router, body::Body, routing::post};
+        use tower::Service;
+
+        let mut app = Router::new()
+            .route("/api/tasks/retry-batch", post(|| async { "retried" }))
+            .route("/api/sync/preview", post(|| async { "preview" }))
+            .layer(axum::middleware::from_fn(reject_destructive));
+        let call = |path: &str| Request::post(path).body(Body::empty()).unwrap();
+
+        let response = app.call(call("/api/tasks/retry-batch")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = app.call(call("/api/sync/preview")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,46 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use sqlx::MySqlPool;
+
+use super::auth::Admin;
+use super::connection::AppError;
+use crate::models::{FeatureFlag, FeatureFlagQuery, UpdateFeatureFlagRequest};
+use crate::services::FeatureFlagService;
+
+/// 列出工作区的功能开关
+#[utoipa::path(
+    get,
+    path = "/api/feature_flags",
+    tag = "feature_flag",
+    params(FeatureFlagQuery),
+    responses((status = 200, body = Vec<FeatureFlag>))
+)]
+pub async fn list_feature_flags(
+    State(pool): State<MySqlPool>,
+    Query(params): Query<FeatureFlagQuery>,
+) -> Result<Json<Vec<FeatureFlag>>, AppError> {
+    let flags = FeatureFlagService::list(&pool, params.workspace.as_deref()).await?;
+    Ok(Json(flags))
+}
+
+/// 开启或关闭功能
+#[utoipa::path(
+    put,
+    path = "/api/feature_flags/{name}",
+    tag = "feature_flag",
+    params(("name" = String, Path)),
+    request_body = UpdateFeatureFlagRequest,
+    responses((status = 200, body = Vec<FeatureFlag>))
+)]
+pub async fn update_feature_flag(
+    State(pool): State<MySqlPool>,
+    _: Admin,
+    Path(name): Path<String>,
+    Json(request): Json<UpdateFeatureFlagRequest>,
+) -> Result<Json<Vec<FeatureFlag>>, AppError> {
+    FeatureFlagService::set(&pool, request.workspace.as_deref(), &name, request.enabled).await?;
+    let flags = FeatureFlagService::list(&pool, request.workspace.as_deref()).await?;
+    Ok(Json(flags))
+}
@@ -2,61 +2,113 @@ use axum::{extract::State, Json};
 use sqlx::MySqlPool;
 
 use crate::db::ConfigRepository;
-use crate::models::TableSchema;
-use crate::services::MetadataService;
-use serde::{Deserialize};
+use crate::models::{TablePreview, TableSchema};
+use crate::services::{ConnectionService, MetadataService};
+use serde::{Deserialize, Serialize};
 
+use super::auth::Operator;
 use super::connection::AppError;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 pub struct ListDatabasesRequest {
     pub config_id: i64,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 pub struct ListTablesRequest {
     pub config_id: i64,
     pub database: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 pub struct GetSchemaRequest {
     pub config_id: i64,
     pub database: String,
     pub table: String,
 }
 
+/// 预览默认和最多返回的行数
+const PREVIEW_DEFAULT_ROWS: usize = 20;
+const PREVIEW_MAX_ROWS: usize = 200;
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct PreviewRequest {
+    pub config_id: i64,
+    pub database: String,
+    pub table: String,
+    pub limit: Option<usize>,
+    /// 同时预览的 StarRocks 目标表，库名和表名默认与源表相同
+    pub sr_config_id: Option<i64>,
+    pub target_database: Option<String>,
+    pub target_table: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct PreviewResponse {
+    pub source: TablePreview,
+    pub target: Option<TablePreview>,
+}
+
 /// 获取 MySQL 数据库列表
+#[utoipa::path(
+    post,
+    path = "/api/metadata/databases",
+    operation_id = "metadata_list_databases",
+    tag = "metadata",
+    request_body = ListDatabasesRequest,
+    responses((status = 200, body = Vec<String>))
+)]
 pub async fn list_databases(
     State(pool): State<MySqlPool>,
     Json(request): Json<ListDatabasesRequest>,
 ) -> Result<Json<Vec<String>>, AppError> {
     let repo = ConfigRepository::new(&pool);
     let config = repo.find_by_id(request.config_id).await?;
+    // SRV 名称解析为实际主机和端口
+    let config = ConnectionService::resolve_config(config).await?;
 
     let databases = MetadataService::list_mysql_databases(&config).await?;
     Ok(Json(databases))
 }
 
 /// 获取数据库表列表
+#[utoipa::path(
+    post,
+    path = "/api/metadata/tables",
+    operation_id = "metadata_list_tables",
+    tag = "metadata",
+    request_body = ListTablesRequest,
+    responses((status = 200, body = Vec<String>))
+)]
 pub async fn list_tables(
     State(pool): State<MySqlPool>,
     Json(request): Json<ListTablesRequest>,
 ) -> Result<Json<Vec<String>>, AppError> {
     let repo = ConfigRepository::new(&pool);
     let config = repo.find_by_id(request.config_id).await?;
+    // SRV 名称解析为实际主机和端口
+    let config = ConnectionService::resolve_config(config).await?;
 
     let tables = MetadataService::list_mysql_tables(&config, &request.database).await?;
     Ok(Json(tables))
 }
 
 /// 获取表结构
+#[utoipa::path(
+    post,
+    path = "/api/metadata/schema",
+    tag = "metadata",
+    request_body = GetSchemaRequest,
+    responses((status = 200, body = TableSchema))
+)]
 pub async fn get_schema(
     State(pool): State<MySqlPool>,
     Json(request): Json<GetSchemaRequest>,
 ) -> Result<Json<TableSchema>, AppError> {
     let repo = ConfigRepository::new(&pool);
     let config = repo.find_by_id(request.config_id).await?;
+    // SRV 名称解析为实际主机和端口
+    let config = ConnectionService::resolve_config(config).await?;
 
     let schema = MetadataService::get_mysql_table_schema(
         &config,
@@ -66,3 +118,51 @@ pub async fn get_schema(
 
     Ok(Json(schema))
 }
+
+/// 预览源表的前 N 行，可同时预览 StarRocks 目标表（按源表主键排序以便逐行对照）
+/// 返回的是业务数据，需要 Operator 角色
+#[utoipa::path(
+    post,
+    path = "/api/metadata/preview",
+    tag = "metadata",
+    request_body = PreviewRequest,
+    responses((status = 200, body = PreviewResponse))
+)]
+pub async fn preview_table(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Json(request): Json<PreviewRequest>,
+) -> Result<Json<PreviewResponse>, AppError> {
+    let limit = request
+        .limit
+        .unwrap_or(PREVIEW_DEFAULT_ROWS)
+        .clamp(1, PREVIEW_MAX_ROWS);
+    let repo = ConfigRepository::new(&pool);
+    let config = repo.find_by_id(request.config_id).await?;
+    // SRV 名称解析为实际主机和端口
+    let config = ConnectionService::resolve_config(config).await?;
+
+    let (source, primary_keys) = MetadataService::preview_mysql_table(
+        &config,
+        &request.database,
+        &request.table,
+        limit,
+    ).await?;
+
+    let target = match request.sr_config_id {
+        Some(sr_config_id) => {
+            let sr_config = repo.find_by_id(sr_config_id).await?;
+            let sr_config = ConnectionService::resolve_config(sr_config).await?;
+            Some(MetadataService::preview_starrocks_table(
+                &sr_config,
+                request.target_database.as_deref().unwrap_or(&request.database),
+                request.target_table.as_deref().unwrap_or(&request.table),
+                &primary_keys,
+                limit,
+            ).await?)
+        }
+        None => None,
+    };
+
+    Ok(Json(PreviewResponse { source, target }))
+}
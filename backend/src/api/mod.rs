@@ -1,8 +1,28 @@
+pub mod alert;
+pub mod alert_routing;
+pub mod approval;
+pub mod audit;
+pub mod auth;
+pub mod bundle;
+pub mod comment;
 pub mod connection;
+pub mod demo;
+pub mod feature_flag;
 pub mod metadata;
+pub mod notification;
+pub mod openapi;
 pub mod sync;
+pub mod sync_mapping;
+pub mod sync_metrics;
 pub mod task;
 pub mod risingwave;
+pub mod schedule;
+pub mod search;
+pub mod settings;
+pub mod sla;
+pub mod starrocks;
+pub mod status;
+pub mod validation;
 pub mod webhook;
 
 use axum::{
@@ -11,9 +31,16 @@ use axum::{
 };
 use serde_json::json;
 use sqlx::MySqlPool;
-use tower_http::cors::CorsLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
 
 /// Health check endpoint
+#[utoipa::path(
+    get,
+    path = "/api/health",
+    tag = "health",
+    security(()),
+    responses((status = 200, body = serde_json::Value))
+)]
 async fn health_check() -> Json<serde_json::Value> {
     Json(json!({
         "status": "healthy",
@@ -21,57 +48,236 @@ async fn health_check() -> Json<serde_json::Value> {
     }))
 }
 
+/// CORS 配置：CORS_ALLOWED_ORIGINS（逗号分隔）限制允许的来源，未配置时不限制
+fn cors_layer() -> CorsLayer {
+    let origins: Vec<_> = std::env::var("CORS_ALLOWED_ORIGINS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|o| !o.is_empty())
+        .filter_map(|o| o.parse::<axum::http::HeaderValue>().ok())
+        .collect();
+    if origins.is_empty() {
+        return CorsLayer::permissive();
+    }
+
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods(tower_http::cors::Any)
+        .allow_headers(tower_http::cors::Any)
+}
+
 /// 创建 API 路由
 pub fn create_router(pool: MySqlPool) -> Router {
     Router::new()
         // Health check
         .route("/api/health", get(health_check))
 
+        // OpenAPI 文档和 Swagger UI
+        .merge(openapi::router())
+
+        // 登录认证
+        .route("/api/auth/login", post(auth::login))
+        .route("/api/auth/me", get(auth::me))
+        .route("/api/auth/password", post(auth::change_password))
+        .route("/api/auth/tokens", get(auth::list_tokens).post(auth::create_token))
+        .route("/api/auth/tokens/:id", delete(auth::delete_token))
+        .route("/api/users", get(auth::list_users).post(auth::create_user))
+        .route("/api/users/:id", delete(auth::delete_user))
+        .route("/api/users/:id/role", put(auth::update_user_role))
+
+        // 公开状态页（只读，不包含连接信息）
+        .route("/status", get(status::status_page))
+        .route("/status.json", get(status::status_json))
+
+        // SLA / 可用率
+        .route("/api/sla", get(sla::get_sla_report))
+        .route("/api/sla/breaches", get(sla::get_sla_breaches))
+
+        // 三端行数采样趋势
+        .route("/api/sync_metrics", get(sync_metrics::get_sync_metrics))
+
+        // 功能开关
+        .route("/api/feature_flags", get(feature_flag::list_feature_flags))
+        .route("/api/feature_flags/:name", put(feature_flag::update_feature_flag))
+
+        // 运行时设置
+        .route("/api/settings", get(settings::list_settings))
+        .route("/api/settings/:name", put(settings::update_setting))
+        .route("/api/settings/:name", delete(settings::reset_setting))
+
         // 连接管理路由
         .route("/api/connections/test/mysql", post(connection::test_mysql))
         .route("/api/connections/test/risingwave", post(connection::test_risingwave))
         .route("/api/connections/test/starrocks", post(connection::test_starrocks))
+        .route("/api/connections/test/doris", post(connection::test_starrocks))
         .route("/api/connections", post(connection::save_connection))
         .route("/api/connections", get(connection::list_connections))
+        .route("/api/connections/parse_url", post(connection::parse_connection_url))
         .route("/api/connections/:id", put(connection::update_connection))
         .route("/api/connections/:id", delete(connection::delete_connection))
+        .route("/api/connections/:id/clone", post(connection::clone_connection))
+        .route("/api/connections/:id/rotate-password", post(connection::rotate_password))
+
+        // 导出 / 导入连接配置和同步定义
+        .route("/api/export", post(bundle::export_bundle))
+        .route("/api/import", post(bundle::import_bundle))
+        .route("/api/export/monitoring", post(bundle::export_monitoring))
+        .route("/api/import/monitoring", post(bundle::import_monitoring))
 
         // 元数据路由
         .route("/api/metadata/databases", post(metadata::list_databases))
         .route("/api/metadata/tables", post(metadata::list_tables))
         .route("/api/metadata/schema", post(metadata::get_schema))
+        .route("/api/metadata/preview", post(metadata::preview_table))
+
+        // 同步审批
+        .route("/api/approvals", get(approval::list_approvals))
+        .route("/api/approvals/:id/approve", post(approval::approve))
+        .route("/api/approvals/:id/reject", post(approval::reject))
 
         // 同步任务路由
         .route("/api/sync/single", post(sync::sync_single_table))
         .route("/api/sync/multiple", post(sync::sync_multiple_tables))
+        .route("/api/sync/preview", post(sync::preview_sync))
         .route("/api/sync/progress/:id", get(sync::get_progress))
         .route("/api/sync/retry/:id", post(sync::retry_task))
+        .route("/api/sync/clone/:id", post(sync::clone_pipeline))
+        .route("/api/sync/:task_id/teardown", post(sync::teardown_task))
+
+        // 定时同步
+        .route(
+            "/api/schedules",
+            get(schedule::list_schedules).post(schedule::create_schedule),
+        )
+        .route("/api/schedules/preview", post(schedule::preview_schedule))
+        .route(
+            "/api/schedules/:id",
+            get(schedule::get_schedule)
+                .put(schedule::update_schedule)
+                .delete(schedule::delete_schedule),
+        )
+
+        // 同步映射
+        .route(
+            "/api/sync_mappings",
+            get(sync_mapping::list_mappings).post(sync_mapping::create_mapping),
+        )
+        .route(
+            "/api/sync_mappings/:id",
+            get(sync_mapping::get_mapping)
+                .put(sync_mapping::update_mapping)
+                .delete(sync_mapping::delete_mapping),
+        )
+        .route("/api/sync_mappings/:id/run", post(sync_mapping::run_mapping))
 
         // 任务管理路由
         .route("/api/tasks/history", get(task::get_history))
+        .route("/api/tasks/export", get(task::export_history))
+        .route("/api/tasks/stats/daily", get(task::get_daily_stats))
+        .route("/api/stats/errors", get(task::get_error_stats))
+        .route("/api/tasks/retry-batch", post(sync::retry_batch))
         .route("/api/tasks/:id", get(task::get_detail))
         .route("/api/tasks/:id/logs", get(task::get_logs))
         .route("/api/tasks/:id/cancel", post(task::cancel_task))
+        .route("/api/tasks/:id/row_counts", get(sync_metrics::get_task_row_counts))
+        .route("/api/tasks/:id/comments", get(comment::list_task_comments))
+        .route("/api/tasks/:id/comments", post(comment::add_task_comment))
+
+        // 管道评论（target 为目标表 database.table）
+        .route("/api/pipelines/:target/comments", get(comment::list_pipeline_comments))
+        .route("/api/pipelines/:target/comments", post(comment::add_pipeline_comment))
+        .route("/api/comments/:id", delete(comment::delete_comment))
+
+        // 清理已下线管道的目标对象
+        .route("/api/pipelines/cleanup", post(sync::cleanup_pipeline))
+
+        // 整体暂停 / 恢复管道（id 为同步映射 ID）
+        .route("/api/pipelines/:id/pause", post(sync_mapping::pause_pipeline))
+        .route("/api/pipelines/:id/resume", post(sync_mapping::resume_pipeline))
+
+        // 数据一致性校验路由
+        .route("/api/validate/:task_id", post(validation::validate_task))
+        .route("/api/validate/:task_id", get(validation::get_validation_results))
 
         // RisingWave 对象管理路由
         .route("/api/risingwave/schemas", get(risingwave::list_schemas))
         .route("/api/risingwave/sources", get(risingwave::list_sources))
+        .route("/api/risingwave/sources/kafka", post(risingwave::create_kafka_source))
+        .route("/api/risingwave/managed_objects", get(risingwave::list_managed_objects))
+        .route("/api/risingwave/managed_objects/gc", post(risingwave::collect_managed_objects))
+        .route("/api/risingwave/cleanup", get(risingwave::list_orphans).post(risingwave::cleanup_orphans))
         .route("/api/risingwave/tables", get(risingwave::list_tables))
         .route("/api/risingwave/materialized_views", get(risingwave::list_materialized_views))
         .route("/api/risingwave/sinks", get(risingwave::list_sinks))
+        .route("/api/risingwave/views", get(risingwave::list_views))
+        .route("/api/risingwave/indexes", get(risingwave::list_indexes))
+        .route("/api/risingwave/secrets", get(risingwave::list_secrets))
+        .route("/api/risingwave/secrets/usage", get(risingwave::list_secret_usage))
+        .route("/api/risingwave/secrets/delete", post(risingwave::delete_secret))
+        .route("/api/risingwave/secrets/delete_unused", post(risingwave::delete_unused_secrets))
         .route("/api/risingwave/sources/delete", post(risingwave::delete_source))
         .route("/api/risingwave/tables/delete", post(risingwave::delete_table))
         .route("/api/risingwave/materialized_views/delete", post(risingwave::delete_materialized_view))
+        .route("/api/risingwave/sinks/:name/status", get(risingwave::get_sink_status))
         .route("/api/risingwave/sinks/delete", post(risingwave::delete_sink))
+        .route("/api/risingwave/query", post(risingwave::query_sql))
+        .route("/api/risingwave/objects/keys", get(risingwave::get_object_keys))
+        .route("/api/risingwave/objects/dependencies", get(risingwave::get_object_dependencies))
         .route("/api/risingwave/objects/batch_delete", post(risingwave::batch_delete_objects))
         .route("/api/risingwave/sinks/create", post(risingwave::create_sink))
+        .route("/api/risingwave/sinks/batch_create", post(risingwave::batch_create_sinks))
+        .route("/api/risingwave/sinks/recreate", post(risingwave::recreate_sinks))
+
+        // StarRocks 目录浏览
+        .route("/api/starrocks/databases", get(starrocks::list_databases))
+        .route("/api/starrocks/tables", get(starrocks::list_tables))
+        .route("/api/starrocks/tables/schema", get(starrocks::get_table_schema))
+        .route("/api/starrocks/tables/partitions", get(starrocks::list_partitions))
 
         // Webhook 路由 - 用于接收 Alertmanager 告警
         .route("/api/webhook/alertmanager", post(webhook::receive_alertmanager_webhook))
         .route("/api/webhook/health", get(webhook::webhook_health))
+        // 通知渠道
+        .route(
+            "/api/notification_channels",
+            get(notification::list_channels).post(notification::create_channel),
+        )
+        .route(
+            "/api/notification_channels/:id",
+            put(notification::update_channel).delete(notification::delete_channel),
+        )
+        .route("/api/notification_channels/:id/test", post(notification::test_channel))
+        // 告警历史
+        .route("/api/alerts", get(alert::list_alerts))
+        .route("/api/alerts/:id", get(alert::get_alert))
+        .route("/api/alerts/:id/resolve", post(alert::resolve_alert))
+        .route("/api/tasks/:id/alerts", get(alert::list_task_alerts))
+        .route("/api/pipelines/:target", get(alert::get_pipeline_detail))
+        // 告警路由规则和静默窗口
+        .route(
+            "/api/alert_routes",
+            get(alert_routing::list_routes).post(alert_routing::create_route),
+        )
+        .route(
+            "/api/alert_routes/:id",
+            put(alert_routing::update_route).delete(alert_routing::delete_route),
+        )
+        .route(
+            "/api/alert_silences",
+            get(alert_routing::list_silences).post(alert_routing::create_silence),
+        )
+        .route("/api/alert_silences/:id", delete(alert_routing::delete_silence))
+        // 审计日志
+        .route("/api/audit", get(audit::list_audit_logs))
+        // 全局搜索
+        .route("/api/search", get(search::search))
+
+        // 认证（/api 下除健康检查和登录外都需要 Bearer token）
+        .layer(axum::middleware::from_fn_with_state(pool.clone(), auth::require_auth))
 
         // CORS 配置
-        .layer(CorsLayer::permissive())
+        .layer(cors_layer())
 
         // 共享状态
         .with_state(pool)
@@ -0,0 +1,147 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+use super::auth::Operator;
+use super::connection::AppError;
+use crate::db::NotificationChannelRepository;
+use crate::models::{
+    Notification, NotificationChannelConfig, NotificationChannelRequest, NotificationEvent,
+};
+use crate::services::NotificationService;
+
+/// 校验渠道配置
+fn validate(request: &NotificationChannelRequest) -> Result<(), AppError> {
+    request
+        .validate()
+        .map_err(|message| crate::utils::error::AppError::Validation(message).into())
+}
+
+/// 列出通知渠道
+#[utoipa::path(
+    get,
+    path = "/api/notification_channels",
+    tag = "notification",
+    responses((status = 200, body = Vec<NotificationChannelConfig>))
+)]
+pub async fn list_channels(
+    State(pool): State<MySqlPool>,
+) -> Result<Json<Vec<NotificationChannelConfig>>, AppError> {
+    Ok(Json(
+        NotificationChannelRepository::new(&pool).find_all().await?,
+    ))
+}
+
+/// 创建通知渠道
+#[utoipa::path(
+    post,
+    path = "/api/notification_channels",
+    tag = "notification",
+    request_body = NotificationChannelRequest,
+    responses((status = 200, body = NotificationChannelConfig))
+)]
+pub async fn create_channel(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Json(request): Json<NotificationChannelRequest>,
+) -> Result<Json<NotificationChannelConfig>, AppError> {
+    validate(&request)?;
+    let repo = NotificationChannelRepository::new(&pool);
+    let id = repo.create(&request).await?;
+    Ok(Json(repo.find_by_id(id).await?))
+}
+
+/// 更新通知渠道
+#[utoipa::path(
+    put,
+    path = "/api/notification_channels/{id}",
+    tag = "notification",
+    params(("id" = i64, Path)),
+    request_body = NotificationChannelRequest,
+    responses((status = 200, body = NotificationChannelConfig))
+)]
+pub async fn update_channel(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+    Json(request): Json<NotificationChannelRequest>,
+) -> Result<Json<NotificationChannelConfig>, AppError> {
+    validate(&request)?;
+    let repo = NotificationChannelRepository::new(&pool);
+    repo.update(id, &request).await?;
+    Ok(Json(repo.find_by_id(id).await?))
+}
+
+/// 删除通知渠道
+#[utoipa::path(
+    delete,
+    path = "/api/notification_channels/{id}",
+    tag = "notification",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn delete_channel(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    NotificationChannelRepository::new(&pool).delete(id).await?;
+    Ok(Json(json!({ "success": true })))
+}
+
+/// 向渠道发送一条测试消息
+#[utoipa::path(
+    post,
+    path = "/api/notification_channels/{id}/test",
+    tag = "notification",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn test_channel(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let channel = NotificationChannelRepository::new(&pool)
+        .find_by_id(id)
+        .await?;
+    let notification = Notification {
+        event: NotificationEvent::Alert,
+        title: "🔔 测试通知".to_string(),
+        text: format!("来自 rw_cdc_sr 的测试消息，渠道: {}", channel.name),
+        fields: Default::default(),
+    };
+    NotificationService::deliver(&channel, &notification).await?;
+    Ok(Json(json!({ "success": true })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_channel_request() {
+        let request: NotificationChannelRequest = serde_json::from_str(
+            r#"{"name": "ops", "settings": {"kind": "dingtalk", "webhook_url": "https://oapi.dingtalk.com/robot/send?access_token=x"}}"#,
+        )
+        .unwrap();
+        assert!(validate(&request).is_ok());
+        assert_eq!(request.events, NotificationEvent::ALL.to_vec());
+        assert!(request.enabled);
+
+        let request: NotificationChannelRequest = serde_json::from_str(
+            r#"{"name": "hook", "events": ["task_failed"], "settings": {"kind": "webhook", "url": "ftp://example.com"}}"#,
+        )
+        .unwrap();
+        assert!(validate(&request).is_err());
+
+        let request: NotificationChannelRequest = serde_json::from_str(
+            r#"{"name": "mail", "settings": {"kind": "email", "smtp_host": "smtp.example.com", "smtp_port": 465, "tls": "tls", "from": "a@example.com", "to": []}}"#,
+        )
+        .unwrap();
+        assert!(validate(&request).is_err());
+    }
+}
@@ -0,0 +1,274 @@
+//! OpenAPI 文档：/api/openapi.json 返回接口描述，/api/docs 为内置的 Swagger UI
+
+use axum::Router;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::{ContentBuilder, Ref, ResponseBuilder};
+use utoipa::{Modify, OpenApi, ToSchema};
+use utoipa_swagger_ui::SwaggerUi;
+
+use super::{
+    alert, alert_routing, approval, audit, auth, bundle, comment, connection, feature_flag, metadata, notification, risingwave, schedule, search, settings, sla, starrocks, status, sync, sync_mapping, sync_metrics, task, validation, webhook,
+};
+
+/// Swagger UI 的路径
+pub const DOCS_PATH: &str = "/api/docs";
+/// OpenAPI 文档的路径
+pub const SPEC_PATH: &str = "/api/openapi.json";
+
+/// 接口出错时的响应体
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "rw-cdc-sr",
+        description = "MySQL → RisingWave CDC → StarRocks 同步工具的 REST API。开启登录认证后，除健康检查、登录和状态页外都需要 `Authorization: Bearer <token>`"
+    ),
+    paths(
+        super::health_check,
+        auth::login,
+        auth::me,
+        auth::change_password,
+        auth::list_tokens,
+        auth::create_token,
+        auth::delete_token,
+        auth::list_users,
+        auth::create_user,
+        auth::delete_user,
+        auth::update_user_role,
+        status::status_page,
+        status::status_json,
+        sla::get_sla_report,
+        sla::get_sla_breaches,
+        sync_metrics::get_sync_metrics,
+        sync_metrics::get_task_row_counts,
+        feature_flag::list_feature_flags,
+        feature_flag::update_feature_flag,
+        settings::list_settings,
+        settings::update_setting,
+        settings::reset_setting,
+        connection::test_mysql,
+        connection::test_risingwave,
+        connection::test_starrocks,
+        connection::save_connection,
+        connection::list_connections,
+        connection::parse_connection_url,
+        connection::update_connection,
+        connection::delete_connection,
+        connection::clone_connection,
+        connection::rotate_password,
+        bundle::export_bundle,
+        bundle::import_bundle,
+        bundle::export_monitoring,
+        bundle::import_monitoring,
+        metadata::list_databases,
+        metadata::list_tables,
+        metadata::get_schema,
+        metadata::preview_table,
+        approval::list_approvals,
+        approval::approve,
+        approval::reject,
+        sync::sync_single_table,
+        sync::sync_multiple_tables,
+        sync::preview_sync,
+        sync::get_progress,
+        sync::retry_task,
+        sync::clone_pipeline,
+        sync::teardown_task,
+        sync::retry_batch,
+        sync::cleanup_pipeline,
+        schedule::list_schedules,
+        schedule::create_schedule,
+        schedule::preview_schedule,
+        schedule::get_schedule,
+        schedule::update_schedule,
+        schedule::delete_schedule,
+        sync_mapping::list_mappings,
+        sync_mapping::create_mapping,
+        sync_mapping::get_mapping,
+        sync_mapping::update_mapping,
+        sync_mapping::delete_mapping,
+        sync_mapping::run_mapping,
+        sync_mapping::pause_pipeline,
+        sync_mapping::resume_pipeline,
+        task::get_history,
+        task::export_history,
+        task::get_daily_stats,
+        task::get_error_stats,
+        task::get_detail,
+        task::get_logs,
+        task::cancel_task,
+        comment::list_task_comments,
+        comment::add_task_comment,
+        comment::list_pipeline_comments,
+        comment::add_pipeline_comment,
+        comment::delete_comment,
+        validation::validate_task,
+        validation::get_validation_results,
+        risingwave::list_schemas,
+        risingwave::list_sources,
+        risingwave::create_kafka_source,
+        risingwave::list_managed_objects,
+        risingwave::collect_managed_objects,
+        risingwave::list_orphans,
+        risingwave::cleanup_orphans,
+        risingwave::list_tables,
+        risingwave::list_materialized_views,
+        risingwave::list_sinks,
+        risingwave::list_views,
+        risingwave::list_indexes,
+        risingwave::list_secrets,
+        risingwave::list_secret_usage,
+        risingwave::delete_secret,
+        risingwave::delete_unused_secrets,
+        risingwave::delete_source,
+        risingwave::delete_table,
+        risingwave::delete_materialized_view,
+        risingwave::get_sink_status,
+        risingwave::delete_sink,
+        risingwave::query_sql,
+        risingwave::get_object_keys,
+        risingwave::get_object_dependencies,
+        risingwave::batch_delete_objects,
+        risingwave::create_sink,
+        risingwave::batch_create_sinks,
+        risingwave::recreate_sinks,
+        starrocks::list_databases,
+        starrocks::list_tables,
+        starrocks::get_table_schema,
+        starrocks::list_partitions,
+        webhook::receive_alertmanager_webhook,
+        webhook::webhook_health,
+        notification::list_channels,
+        notification::create_channel,
+        notification::update_channel,
+        notification::delete_channel,
+        notification::test_channel,
+        alert::list_alerts,
+        alert::get_alert,
+        alert::resolve_alert,
+        alert::list_task_alerts,
+        alert::get_pipeline_detail,
+        alert_routing::list_routes,
+        alert_routing::create_route,
+        alert_routing::update_route,
+        alert_routing::delete_route,
+        alert_routing::list_silences,
+        alert_routing::create_silence,
+        alert_routing::delete_silence,
+        audit::list_audit_logs,
+        search::search,
+    ),
+    components(schemas(ErrorResponse)),
+    modifiers(&ApiModifier),
+    security(("bearer" = [])),
+    tags(
+        (name = "health", description = "健康检查"),
+        (name = "auth", description = "登录认证、API Token 和用户管理"),
+        (name = "status", description = "公开状态页"),
+        (name = "sla", description = "SLA / 可用率"),
+        (name = "sync_metrics", description = "三端行数采样"),
+        (name = "feature_flag", description = "功能开关"),
+        (name = "settings", description = "运行时设置"),
+        (name = "connection", description = "连接管理"),
+        (name = "bundle", description = "导出 / 导入连接配置、同步定义和监控配置"),
+        (name = "metadata", description = "MySQL 元数据"),
+        (name = "approval", description = "同步审批"),
+        (name = "sync", description = "同步任务"),
+        (name = "schedule", description = "定时同步"),
+        (name = "sync_mapping", description = "同步映射和管道暂停 / 恢复"),
+        (name = "task", description = "任务历史、日志和统计"),
+        (name = "comment", description = "任务和管道评论"),
+        (name = "validation", description = "数据一致性校验"),
+        (name = "risingwave", description = "RisingWave 对象管理"),
+        (name = "starrocks", description = "StarRocks 目录浏览"),
+        (name = "webhook", description = "Alertmanager Webhook"),
+        (name = "notification", description = "通知渠道"),
+        (name = "alert", description = "告警历史和关联到管道的告警"),
+        (name = "alert_routing", description = "告警路由规则和静默窗口"),
+        (name = "audit", description = "审计日志"),
+        (name = "search", description = "全局搜索"),
+    )
+)]
+pub struct ApiDoc;
+
+/// 添加 Bearer 认证方式，并给每个接口补充出错时的响应
+struct ApiModifier;
+
+impl Modify for ApiModifier {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "bearer",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .description(Some("登录返回的 JWT 或 API Token"))
+                        .build(),
+                ),
+            );
+
+        let error = ResponseBuilder::new()
+            .description("错误信息")
+            .content(
+                "application/json",
+                ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("ErrorResponse")))
+                    .build(),
+            )
+            .build();
+        for item in openapi.paths.paths.values_mut() {
+            for operation in [
+                &mut item.get,
+                &mut item.post,
+                &mut item.put,
+                &mut item.delete,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                operation
+                    .responses
+                    .responses
+                    .entry("default".to_string())
+                    .or_insert_with(|| error.clone().into());
+            }
+        }
+    }
+}
+
+/// OpenAPI 文档和 Swagger UI 的路由
+pub fn router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    SwaggerUi::new(DOCS_PATH)
+        .url(SPEC_PATH, ApiDoc::openapi())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openapi_covers_routes() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let paths = spec["paths"].as_object().unwrap();
+        assert!(paths.contains_key("/api/health"));
+        assert!(paths["/api/pipelines/{id}/pause"]["post"].is_object());
+        assert_eq!(
+            paths["/api/tasks/{id}"]["get"]["parameters"][0]["name"],
+            "id"
+        );
+        assert!(paths["/api/sync/multiple"]["post"]["responses"]["default"].is_object());
+        assert!(spec["components"]["schemas"]["SyncRequest"].is_object());
+        assert!(spec["components"]["securitySchemes"]["bearer"].is_object());
+    }
+}
@@ -1,70 +1,150 @@
 use axum::{
     Json,
-    extract::{State, Query},
+    extract::{Path, State, Query},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
 };
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, Row};
-use sqlx::postgres::{PgConnectOptions, PgPoolOptions, PgSslMode};
-use mysql_async::prelude::*;
+use std::collections::{BTreeMap, HashSet};
+use std::time::Instant;
 
+use super::approval::request_user;
+use super::auth::Operator;
 use super::connection::AppError;
-use crate::db::ConfigRepository;
-use crate::models::{TableSchema, Column, PaginatedResponse};
+use super::sync::set_created_by;
+use crate::db::{ConfigRepository, ManagedObjectRepository, TaskRepository};
+use crate::services::{
+    AuditService, ConnectionPoolManager, ConnectionService, DdlExecutor, ManagedObjectService,
+    NotificationService, OrphanCleanupService, RwSecretService, SinkMaintenanceService, SqlConsoleService, TaskLogger,
+};
+use crate::models::{
+    TableSchema, Column, ColumnTypeSource, CreateKafkaSourceRequest, DatabaseConfig, DeleteUnusedSecretsRequest,
+    DeleteUnusedSecretsResult, IcebergSinkOptions, ManagedObject, ManagedObjectQuery, ManagedObjectType,
+    NestedColumnMode, OrphanCleanupRequest, OrphanCleanupResult, OrphanObject, OrphanQuery, PageQuery, PaginatedResponse, RecreateSinksRequest, RecreateSinksResult, RetryPolicy, RwSecretQuery,
+    RwSecretUsage, Setting, SqlQueryRequest, SqlQueryResult, StarRocksTableOptions,
+    SyncOptions, SyncTask, TaskLogLevel, TaskStatus,
+};
 use crate::generators::{RisingWaveDDLGenerator, StarRocksDDLGenerator};
+use crate::utils::crypto;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RwObjectQuery {
     pub config_id: i64,
     pub schema: Option<String>,
     pub search: Option<String>,
-    pub limit: Option<i64>,
-    pub offset: Option<i64>,
-}
-
-impl RwObjectQuery {
-    /// 获取有效的 limit 值（默认20，范围1-100）
-    fn get_limit(&self) -> i64 {
-        self.limit.unwrap_or(20).max(1).min(100)
-    }
-
-    /// 获取有效的 offset 值（默认0，最小0）
-    fn get_offset(&self) -> i64 {
-        self.offset.unwrap_or(0).max(0)
-    }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 pub struct DeleteObjectRequest {
     pub config_id: i64,
     pub schema: String,
     pub name: String,
+    /// 删除仍被其他目标表引用的共享 Source
+    #[serde(default)]
+    pub force: bool,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::ToSchema)]
 pub struct BatchDeleteObjectRequest {
     pub config_id: i64,
     pub schema: String,
     pub object_type: String,  // "source", "table", "materialized_view", "sink"
     pub names: Vec<String>,
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct CollectGarbageRequest {
+    pub rw_config_id: i64,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, utoipa::ToSchema)]
 pub struct CreateSinkRequest {
     pub rw_config_id: i64,
+    /// 写入 Iceberg 时不需要
+    #[serde(default)]
     pub sr_config_id: i64,
     pub schema: String,
     pub source_object: String,  // table name or materialized view name
     pub source_type: String,     // "table" or "materialized_view"
     pub target_database: String,
     pub target_table: String,
+    /// StarRocks 建表属性，为空时使用全局默认值
+    #[serde(default)]
+    pub starrocks_table: Option<StarRocksTableOptions>,
+    /// sink 和 StarRocks 表的主键列，为空时使用 RisingWave 目录中的主键
+    #[serde(default)]
+    pub primary_keys: Option<Vec<String>>,
+    /// 设置后写入 Iceberg 表（target_database.target_table）而不是 StarRocks
+    #[serde(default)]
+    pub iceberg: Option<IcebergSinkOptions>,
+    /// STRUCT / 数组 / JSON 列的写入方式：列名 -> flatten / json
+    #[serde(default)]
+    pub nested_columns: BTreeMap<String, NestedColumnMode>,
+}
+
+/// 批量创建 StarRocks sink 的请求
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct BatchCreateSinkRequest {
+    pub rw_config_id: i64,
+    pub sr_config_id: i64,
+    pub sinks: Vec<BatchSinkItem>,
+}
+
+/// 批量创建中的单个对象，字段含义同 CreateSinkRequest
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct BatchSinkItem {
+    pub schema: String,
+    pub source_object: String,
+    pub source_type: String,
+    pub target_database: String,
+    pub target_table: String,
+    #[serde(default)]
+    pub starrocks_table: Option<StarRocksTableOptions>,
+    #[serde(default)]
+    pub primary_keys: Option<Vec<String>>,
+    #[serde(default)]
+    pub nested_columns: BTreeMap<String, NestedColumnMode>,
+}
+
+impl BatchSinkItem {
+    fn into_request(self, rw_config_id: i64, sr_config_id: i64) -> CreateSinkRequest {
+        CreateSinkRequest {
+            rw_config_id,
+            sr_config_id,
+            schema: self.schema,
+            source_object: self.source_object,
+            source_type: self.source_type,
+            target_database: self.target_database,
+            target_table: self.target_table,
+            starrocks_table: self.starrocks_table,
+            primary_keys: self.primary_keys,
+            iceberg: None,
+            nested_columns: self.nested_columns,
+        }
+    }
+}
+
+/// 批量创建中单个 sink 的结果
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct SinkCreateResult {
+    /// RisingWave 中的来源对象 schema.name
+    pub source: String,
+    /// StarRocks 目标表 database.table
+    pub target: String,
+    pub success: bool,
+    pub error: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub struct RwSchema {
     pub schema_name: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub struct RwSource {
     pub id: i32,
     pub name: String,
@@ -75,7 +155,7 @@ pub struct RwSource {
     pub definition: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub struct RwTable {
     pub id: i32,
     pub name: String,
@@ -84,7 +164,7 @@ pub struct RwTable {
     pub definition: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub struct RwMaterializedView {
     pub id: i32,
     pub name: String,
@@ -93,7 +173,7 @@ pub struct RwMaterializedView {
     pub definition: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub struct RwSink {
     pub id: i32,
     pub name: String,
@@ -103,35 +183,255 @@ pub struct RwSink {
     pub definition: Option<String>,
 }
 
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct RwView {
+    pub id: i32,
+    pub name: String,
+    pub schema_name: String,
+    pub owner: i32,
+    pub definition: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct RwIndex {
+    pub id: i32,
+    pub name: String,
+    pub schema_name: String,
+    pub owner: i32,
+    /// 索引所在的表或物化视图
+    pub table_name: Option<String>,
+    pub definition: Option<String>,
+}
+
+/// RisingWave SECRET，只返回名称，不返回内容
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct RwSecret {
+    pub id: i32,
+    pub name: String,
+    pub schema_name: String,
+    pub owner: i32,
+}
+
+/// Sink 最近的错误事件（来自 rw_catalog.rw_event_logs）
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct RwSinkError {
+    pub timestamp: String,
+    pub event_type: String,
+    pub info: String,
+}
+
+/// Sink 运行状态
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct RwSinkStatus {
+    pub id: i32,
+    pub name: String,
+    pub schema_name: String,
+    pub connector: String,
+    /// running / creating / error
+    pub state: String,
+    /// 是否由本工具创建（{target_table}_to_sr_sink）
+    pub managed: bool,
+    /// 是否开启 sink decouple，旧版本 RisingWave 不支持时为空
+    pub is_decoupled: Option<bool>,
+    /// 创建中的回填进度
+    pub creating_progress: Option<String>,
+    /// 最近一小时内的错误数
+    pub recent_error_count: usize,
+    pub recent_errors: Vec<RwSinkError>,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SinkStatusQuery {
+    pub config_id: i64,
+    pub schema: Option<String>,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RwObjectNameQuery {
+    pub config_id: i64,
+    pub schema: String,
+    pub name: String,
+}
+
+/// 表或物化视图的列和主键，ambiguous 时创建 sink 需要指定 primary_keys
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RwRelationKeys {
+    /// 可见列
+    pub columns: Vec<String>,
+    /// 目录中的可见主键列
+    pub primary_keys: Vec<String>,
+    pub distribution_keys: Vec<String>,
+    /// 没有主键或主键包含隐藏列
+    pub ambiguous: bool,
+}
+
+/// 依赖某个对象的下游对象，DROP ... CASCADE 时会被一起删除
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RwDependentObject {
+    pub id: i32,
+    pub name: String,
+    pub schema_name: String,
+    /// rw_relations.relation_type，例如 materialized view、sink
+    pub object_type: String,
+}
+
 /// 获取 RisingWave 连接池
+/// 未指定 schema 时使用的 RisingWave schema（设置 rw.default_schema）
+fn default_schema() -> String {
+    Setting::RwDefaultSchema
+        .current()
+        .unwrap_or_else(|| "public".to_string())
+}
+
 async fn get_rw_pool(pool: &sqlx::MySqlPool, config_id: i64) -> Result<PgPool, AppError> {
-    let config_repo = ConfigRepository::new(pool);
-    let rw_config = config_repo.find_by_id(config_id).await?;
-
-    let rw_opts = PgConnectOptions::new()
-        .host(&rw_config.host)
-        .port(rw_config.port)
-        .username(&rw_config.username)
-        .password(&rw_config.password)
-        .database(rw_config.database_name.as_deref().unwrap_or("dev"))
-        .ssl_mode(PgSslMode::Prefer);
-
-    let rw_pool = PgPoolOptions::new()
-        .max_connections(5)
-        .connect_with(rw_opts)
-        .await
-        .map_err(|e| crate::utils::error::AppError::Connection(format!("Failed to connect to RisingWave: {}", e)))?;
+    let rw_config = ConfigRepository::new(pool).find_by_id(config_id).await?;
+    Ok(ConnectionPoolManager::postgres(&rw_config).await?)
+}
+
+/// 按 rw_depend 查找对象的所有下游对象（包括间接依赖），按依赖层级排序
+async fn fetch_dependents(
+    rw_pool: &PgPool,
+    schema: &str,
+    name: &str,
+) -> Result<Vec<RwDependentObject>, AppError> {
+    let root: Option<i32> = sqlx::query_scalar(
+        "SELECT r.id FROM rw_catalog.rw_relations r
+         JOIN rw_catalog.rw_schemas s ON r.schema_id = s.id
+         WHERE s.name = $1 AND r.name = $2",
+    )
+    .bind(schema)
+    .bind(name)
+    .fetch_optional(rw_pool)
+    .await?;
+    let Some(root) = root else {
+        return Ok(Vec::new());
+    };
+
+    let edges: Vec<(i32, i32)> =
+        sqlx::query_as("SELECT objid, refobjid FROM rw_catalog.rw_depend")
+            .fetch_all(rw_pool)
+            .await?;
+    let ids = collect_dependents(root, &edges);
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query(
+        "SELECT r.id, r.name, s.name AS schema_name, r.relation_type
+         FROM rw_catalog.rw_relations r
+         JOIN rw_catalog.rw_schemas s ON r.schema_id = s.id
+         WHERE r.id = ANY($1)",
+    )
+    .bind(&ids)
+    .fetch_all(rw_pool)
+    .await?;
+    let mut dependents: Vec<RwDependentObject> = rows
+        .iter()
+        .map(|row| RwDependentObject {
+            id: row.get("id"),
+            name: row.get("name"),
+            schema_name: row.get("schema_name"),
+            object_type: row.get("relation_type"),
+        })
+        .collect();
+    dependents.sort_by_key(|d| ids.iter().position(|id| *id == d.id));
+    Ok(dependents)
+}
+
+/// 从依赖边 (objid, refobjid) 中按广度优先收集 root 的下游对象
+fn collect_dependents(root: i32, edges: &[(i32, i32)]) -> Vec<i32> {
+    let mut dependents = Vec::new();
+    let mut queue = std::collections::VecDeque::from([root]);
+    while let Some(current) = queue.pop_front() {
+        for (objid, _) in edges.iter().filter(|(_, refobjid)| *refobjid == current) {
+            if *objid != root && !dependents.contains(objid) {
+                dependents.push(*objid);
+                queue.push_back(*objid);
+            }
+        }
+    }
+    dependents
+}
+
+/// 删除前检查下游对象：没有 force 且存在下游对象时返回 409 和会被一起删除的对象
+async fn check_dependents(
+    rw_pool: &PgPool,
+    schema: &str,
+    name: &str,
+    force: bool,
+) -> Result<Option<Response>, AppError> {
+    if force {
+        return Ok(None);
+    }
+    let dependents = fetch_dependents(rw_pool, schema, name).await?;
+    if dependents.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(
+        (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "error": format!(
+                    "{}.{} has {} dependent objects that would be dropped, set force to drop them too",
+                    schema,
+                    name,
+                    dependents.len()
+                ),
+                "dependents": dependents,
+            })),
+        )
+            .into_response(),
+    ))
+}
 
-    Ok(rw_pool)
+/// 执行删除等需要审计的语句并记录审计日志，操作人取 X-User
+async fn execute_audited(
+    pool: &sqlx::MySqlPool,
+    rw_pool: &PgPool,
+    headers: &HeaderMap,
+    sql: &str,
+) -> Result<(), sqlx::Error> {
+    let result = sqlx::query(sql).execute(rw_pool).await.map(|_| ());
+    let error = result.as_ref().err().map(|e| e.to_string());
+    AuditService::record_ddl(
+        pool,
+        request_user(headers).as_deref(),
+        None,
+        "RisingWave",
+        sql,
+        error.as_deref(),
+    )
+    .await;
+    result
+}
+
+/// 删除语句，force 时使用 CASCADE 一起删除下游对象
+fn drop_sql(object_type: &str, schema: &str, name: &str, force: bool) -> String {
+    format!(
+        "DROP {} IF EXISTS {}.{}{}",
+        object_type,
+        RisingWaveDDLGenerator::force_quote_identifier(schema),
+        RisingWaveDDLGenerator::force_quote_identifier(name),
+        if force { " CASCADE" } else { "" }
+    )
 }
 
 /// 列出所有 schemas
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/schemas",
+    tag = "risingwave",
+    params(RwObjectQuery),
+    responses((status = 200, body = Vec<RwSchema>))
+)]
 pub async fn list_schemas(
     State(pool): State<sqlx::MySqlPool>,
     Query(params): Query<RwObjectQuery>,
 ) -> Result<Json<Vec<RwSchema>>, AppError> {
     let rw_pool = get_rw_pool(&pool, params.config_id).await?;
-    let internal_schema = vec!["rw_catalog","information_schema", "pg_catalog"];
+    let internal_schema = ["rw_catalog","information_schema", "pg_catalog"];
 
     let schemas: Vec<RwSchema> = sqlx::query(
         "SELECT name as schema_name FROM rw_catalog.rw_schemas
@@ -150,14 +450,22 @@ pub async fn list_schemas(
 }
 
 /// 列出 sources
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/sources",
+    tag = "risingwave",
+    params(RwObjectQuery, PageQuery),
+    responses((status = 200, body = PaginatedResponse<RwSource>))
+)]
 pub async fn list_sources(
     State(pool): State<sqlx::MySqlPool>,
     Query(params): Query<RwObjectQuery>,
+    Query(page): Query<PageQuery>,
 ) -> Result<Json<PaginatedResponse<RwSource>>, AppError> {
     let rw_pool = get_rw_pool(&pool, params.config_id).await?;
-    let schema = params.schema.clone().unwrap_or_else(|| "public".to_string());
-    let limit = params.get_limit();
-    let offset = params.get_offset();
+    let schema = params.schema.clone().unwrap_or_else(default_schema);
+    let limit = page.get_limit();
+    let offset = page.get_offset();
 
     // 构建查询条件
     let where_clause = if params.search.is_some() {
@@ -228,14 +536,23 @@ pub async fn list_sources(
 }
 
 /// 列出 tables
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/tables",
+    operation_id = "risingwave_list_tables",
+    tag = "risingwave",
+    params(RwObjectQuery, PageQuery),
+    responses((status = 200, body = PaginatedResponse<RwTable>))
+)]
 pub async fn list_tables(
     State(pool): State<sqlx::MySqlPool>,
     Query(params): Query<RwObjectQuery>,
+    Query(page): Query<PageQuery>,
 ) -> Result<Json<PaginatedResponse<RwTable>>, AppError> {
     let rw_pool = get_rw_pool(&pool, params.config_id).await?;
-    let schema = params.schema.clone().unwrap_or_else(|| "public".to_string());
-    let limit = params.get_limit();
-    let offset = params.get_offset();
+    let schema = params.schema.clone().unwrap_or_else(default_schema);
+    let limit = page.get_limit();
+    let offset = page.get_offset();
 
     // 构建查询条件
     let where_clause = if params.search.is_some() {
@@ -294,14 +611,22 @@ pub async fn list_tables(
 }
 
 /// 列出 materialized views
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/materialized_views",
+    tag = "risingwave",
+    params(RwObjectQuery, PageQuery),
+    responses((status = 200, body = PaginatedResponse<RwMaterializedView>))
+)]
 pub async fn list_materialized_views(
     State(pool): State<sqlx::MySqlPool>,
     Query(params): Query<RwObjectQuery>,
+    Query(page): Query<PageQuery>,
 ) -> Result<Json<PaginatedResponse<RwMaterializedView>>, AppError> {
     let rw_pool = get_rw_pool(&pool, params.config_id).await?;
-    let schema = params.schema.clone().unwrap_or_else(|| "public".to_string());
-    let limit = params.get_limit();
-    let offset = params.get_offset();
+    let schema = params.schema.clone().unwrap_or_else(default_schema);
+    let limit = page.get_limit();
+    let offset = page.get_offset();
 
     // 构建查询条件
     let where_clause = if params.search.is_some() {
@@ -360,14 +685,22 @@ pub async fn list_materialized_views(
 }
 
 /// 列出 sinks
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/sinks",
+    tag = "risingwave",
+    params(RwObjectQuery, PageQuery),
+    responses((status = 200, body = PaginatedResponse<RwSink>))
+)]
 pub async fn list_sinks(
     State(pool): State<sqlx::MySqlPool>,
     Query(params): Query<RwObjectQuery>,
+    Query(page): Query<PageQuery>,
 ) -> Result<Json<PaginatedResponse<RwSink>>, AppError> {
     let rw_pool = get_rw_pool(&pool, params.config_id).await?;
-    let schema = params.schema.clone().unwrap_or_else(|| "public".to_string());
-    let limit = params.get_limit();
-    let offset = params.get_offset();
+    let schema = params.schema.clone().unwrap_or_else(default_schema);
+    let limit = page.get_limit();
+    let offset = page.get_offset();
 
     // 构建查询条件
     let where_clause = if params.search.is_some() {
@@ -426,93 +759,748 @@ pub async fn list_sinks(
     Ok(Json(PaginatedResponse::new(sinks, total, limit, offset)))
 }
 
-/// 删除 source
-pub async fn delete_source(
+/// 列出 views
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/views",
+    tag = "risingwave",
+    params(RwObjectQuery, PageQuery),
+    responses((status = 200, body = PaginatedResponse<RwView>))
+)]
+pub async fn list_views(
     State(pool): State<sqlx::MySqlPool>,
-    Json(request): Json<DeleteObjectRequest>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let rw_pool = get_rw_pool(&pool, request.config_id).await?;
+    Query(params): Query<RwObjectQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Json<PaginatedResponse<RwView>>, AppError> {
+    let rw_pool = get_rw_pool(&pool, params.config_id).await?;
+    let schema = params.schema.clone().unwrap_or_else(default_schema);
+    let limit = page.get_limit();
+    let offset = page.get_offset();
 
-    let drop_sql = format!("DROP SOURCE IF EXISTS \"{}\".\"{}\"", request.schema, request.name);
-    sqlx::query(&drop_sql)
-        .execute(&rw_pool)
-        .await?;
+    // 构建查询条件
+    let where_clause = if params.search.is_some() {
+        "WHERE sch.name = $1 AND v.name ILIKE '%' || $2 || '%'"
+    } else {
+        "WHERE sch.name = $1"
+    };
 
-    Ok(Json(serde_json::json!({ "success": true })))
-}
+    // 数据查询 - LIMIT 和 OFFSET 必须直接在 SQL 中格式化，不能使用参数化查询
+    let query_str = format!(
+        "SELECT v.id, v.name, sch.name as schema_name, v.owner, v.definition
+         FROM rw_catalog.rw_views v
+         JOIN rw_catalog.rw_schemas sch ON v.schema_id = sch.id
+         {}
+         ORDER BY v.name
+         LIMIT {} OFFSET {}", where_clause, limit, offset
+    );
 
-/// 删除 table
-pub async fn delete_table(
-    State(pool): State<sqlx::MySqlPool>,
-    Json(request): Json<DeleteObjectRequest>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let rw_pool = get_rw_pool(&pool, request.config_id).await?;
+    let mut query = sqlx::query(&query_str).bind(&schema);
+    if let Some(search) = &params.search {
+        query = query.bind(search);
+    }
 
-    let drop_sql = format!("DROP TABLE IF EXISTS \"{}\".\"{}\"", request.schema, request.name);
-    sqlx::query(&drop_sql)
-        .execute(&rw_pool)
-        .await?;
+    let views: Vec<RwView> = query
+        .fetch_all(&rw_pool)
+        .await?
+        .iter()
+        .map(|row| RwView {
+            id: row.get("id"),
+            name: row.get("name"),
+            schema_name: row.get("schema_name"),
+            owner: row.get("owner"),
+            definition: row.get("definition"),
+        })
+        .collect();
 
-    Ok(Json(serde_json::json!({ "success": true })))
-}
+    // COUNT 查询
+    let count_str = format!(
+        "SELECT COUNT(*) as total
+         FROM rw_catalog.rw_views v
+         JOIN rw_catalog.rw_schemas sch ON v.schema_id = sch.id
+         {}", where_clause
+    );
 
-/// 删除 materialized view
-pub async fn delete_materialized_view(
-    State(pool): State<sqlx::MySqlPool>,
-    Json(request): Json<DeleteObjectRequest>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let rw_pool = get_rw_pool(&pool, request.config_id).await?;
+    let mut count_query = sqlx::query(&count_str).bind(&schema);
+    if let Some(search) = &params.search {
+        count_query = count_query.bind(search);
+    }
 
-    let drop_sql = format!("DROP MATERIALIZED VIEW IF EXISTS \"{}\".\"{}\"", request.schema, request.name);
-    sqlx::query(&drop_sql)
-        .execute(&rw_pool)
-        .await?;
+    let total: i64 = count_query
+        .fetch_one(&rw_pool)
+        .await?
+        .get("total");
 
-    Ok(Json(serde_json::json!({ "success": true })))
+    Ok(Json(PaginatedResponse::new(views, total, limit, offset)))
 }
 
-/// 删除 sink
-pub async fn delete_sink(
+/// 列出 indexes
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/indexes",
+    tag = "risingwave",
+    params(RwObjectQuery, PageQuery),
+    responses((status = 200, body = PaginatedResponse<RwIndex>))
+)]
+pub async fn list_indexes(
     State(pool): State<sqlx::MySqlPool>,
-    Json(request): Json<DeleteObjectRequest>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let rw_pool = get_rw_pool(&pool, request.config_id).await?;
+    Query(params): Query<RwObjectQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Json<PaginatedResponse<RwIndex>>, AppError> {
+    let rw_pool = get_rw_pool(&pool, params.config_id).await?;
+    let schema = params.schema.clone().unwrap_or_else(default_schema);
+    let limit = page.get_limit();
+    let offset = page.get_offset();
 
-    let drop_sql = format!("DROP SINK IF EXISTS \"{}\".\"{}\"", request.schema, request.name);
-    sqlx::query(&drop_sql)
-        .execute(&rw_pool)
-        .await?;
+    // 构建查询条件
+    let where_clause = if params.search.is_some() {
+        "WHERE sch.name = $1 AND i.name ILIKE '%' || $2 || '%'"
+    } else {
+        "WHERE sch.name = $1"
+    };
 
-    Ok(Json(serde_json::json!({ "success": true })))
+    // 数据查询 - LIMIT 和 OFFSET 必须直接在 SQL 中格式化，不能使用参数化查询
+    let query_str = format!(
+        "SELECT i.id, i.name, sch.name as schema_name, i.owner, rel.name as table_name, i.definition
+         FROM rw_catalog.rw_indexes i
+         JOIN rw_catalog.rw_schemas sch ON i.schema_id = sch.id
+         LEFT JOIN rw_catalog.rw_relations rel ON i.primary_table_id = rel.id
+         {}
+         ORDER BY i.name
+         LIMIT {} OFFSET {}", where_clause, limit, offset
+    );
+
+    let mut query = sqlx::query(&query_str).bind(&schema);
+    if let Some(search) = &params.search {
+        query = query.bind(search);
+    }
+
+    let indexes: Vec<RwIndex> = query
+        .fetch_all(&rw_pool)
+        .await?
+        .iter()
+        .map(|row| RwIndex {
+            id: row.get("id"),
+            name: row.get("name"),
+            schema_name: row.get("schema_name"),
+            owner: row.get("owner"),
+            table_name: row.get("table_name"),
+            definition: row.get("definition"),
+        })
+        .collect();
+
+    // COUNT 查询
+    let count_str = format!(
+        "SELECT COUNT(*) as total
+         FROM rw_catalog.rw_indexes i
+         JOIN rw_catalog.rw_schemas sch ON i.schema_id = sch.id
+         {}", where_clause
+    );
+
+    let mut count_query = sqlx::query(&count_str).bind(&schema);
+    if let Some(search) = &params.search {
+        count_query = count_query.bind(search);
+    }
+
+    let total: i64 = count_query
+        .fetch_one(&rw_pool)
+        .await?
+        .get("total");
+
+    Ok(Json(PaginatedResponse::new(indexes, total, limit, offset)))
 }
 
-/// 批量删除对象
-pub async fn batch_delete_objects(
+/// 列出 secrets（不包含内容）
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/secrets",
+    tag = "risingwave",
+    params(RwObjectQuery, PageQuery),
+    responses((status = 200, body = PaginatedResponse<RwSecret>))
+)]
+pub async fn list_secrets(
     State(pool): State<sqlx::MySqlPool>,
-    Json(request): Json<BatchDeleteObjectRequest>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let rw_pool = get_rw_pool(&pool, request.config_id).await?;
+    Query(params): Query<RwObjectQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Json<PaginatedResponse<RwSecret>>, AppError> {
+    let rw_pool = get_rw_pool(&pool, params.config_id).await?;
+    let schema = params.schema.clone().unwrap_or_else(default_schema);
+    let limit = page.get_limit();
+    let offset = page.get_offset();
 
-    let object_type_sql = match request.object_type.as_str() {
-        "source" => "SOURCE",
-        "table" => "TABLE",
-        "materialized_view" => "MATERIALIZED VIEW",
-        "sink" => "SINK",
-        _ => {
-            return Err(crate::utils::error::AppError::InvalidInput(
-                format!("Invalid object type: {}", request.object_type)
-            ).into())
-        }
+    // 构建查询条件
+    let where_clause = if params.search.is_some() {
+        "WHERE sch.name = $1 AND sec.name ILIKE '%' || $2 || '%'"
+    } else {
+        "WHERE sch.name = $1"
     };
 
-    let mut success_count = 0;
-    let mut failed = Vec::new();
-
-    for name in &request.names {
-        let drop_sql = format!("DROP {} IF EXISTS \"{}\".\"{}\"", object_type_sql, request.schema, name);
-        tracing::debug!("Executing: {}", drop_sql);
+    // 数据查询 - LIMIT 和 OFFSET 必须直接在 SQL 中格式化，不能使用参数化查询
+    let query_str = format!(
+        "SELECT sec.id, sec.name, sch.name as schema_name, sec.owner
+         FROM rw_catalog.rw_secrets sec
+         JOIN rw_catalog.rw_schemas sch ON sec.schema_id = sch.id
+         {}
+         ORDER BY sec.name
+         LIMIT {} OFFSET {}", where_clause, limit, offset
+    );
 
-        match sqlx::query(&drop_sql).execute(&rw_pool).await {
+    let mut query = sqlx::query(&query_str).bind(&schema);
+    if let Some(search) = &params.search {
+        query = query.bind(search);
+    }
+
+    let secrets: Vec<RwSecret> = query
+        .fetch_all(&rw_pool)
+        .await?
+        .iter()
+        .map(|row| RwSecret {
+            id: row.get("id"),
+            name: row.get("name"),
+            schema_name: row.get("schema_name"),
+            owner: row.get("owner"),
+        })
+        .collect();
+
+    // COUNT 查询
+    let count_str = format!(
+        "SELECT COUNT(*) as total
+         FROM rw_catalog.rw_secrets sec
+         JOIN rw_catalog.rw_schemas sch ON sec.schema_id = sch.id
+         {}", where_clause
+    );
+
+    let mut count_query = sqlx::query(&count_str).bind(&schema);
+    if let Some(search) = &params.search {
+        count_query = count_query.bind(search);
+    }
+
+    let total: i64 = count_query
+        .fetch_one(&rw_pool)
+        .await?
+        .get("total");
+
+    Ok(Json(PaginatedResponse::new(secrets, total, limit, offset)))
+}
+
+/// 列出同步目标 schema 中的 secrets 及引用它们的 Source / Sink
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/secrets/usage",
+    tag = "risingwave",
+    params(RwSecretQuery),
+    responses((status = 200, body = Vec<RwSecretUsage>))
+)]
+pub async fn list_secret_usage(
+    State(pool): State<sqlx::MySqlPool>,
+    Query(params): Query<RwSecretQuery>,
+) -> Result<Json<Vec<RwSecretUsage>>, AppError> {
+    Ok(Json(RwSecretService::list(&pool, params.config_id).await?))
+}
+
+/// 删除 secret，仍被 Source / Sink 引用时返回 409
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/secrets/delete",
+    tag = "risingwave",
+    request_body = DeleteObjectRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn delete_secret(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<DeleteObjectRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    RwSecretService::delete(
+        &pool,
+        request.config_id,
+        &request.schema,
+        &request.name,
+        request.force,
+        request_user(&headers).as_deref(),
+    )
+    .await?;
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// 删除同步目标 schema 中没有被引用的 secrets
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/secrets/delete_unused",
+    tag = "risingwave",
+    request_body = DeleteUnusedSecretsRequest,
+    responses((status = 200, body = DeleteUnusedSecretsResult))
+)]
+pub async fn delete_unused_secrets(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<DeleteUnusedSecretsRequest>,
+) -> Result<Json<DeleteUnusedSecretsResult>, AppError> {
+    Ok(Json(
+        RwSecretService::delete_unused(
+            &pool,
+            request.config_id,
+            request.dry_run,
+            request_user(&headers).as_deref(),
+        )
+        .await?,
+    ))
+}
+
+/// 列出同步目标 schema 中没有对应同步任务的遗留对象（按本工具的命名规则识别）
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/cleanup",
+    tag = "risingwave",
+    params(OrphanQuery),
+    responses((status = 200, body = Vec<OrphanObject>))
+)]
+pub async fn list_orphans(
+    State(pool): State<sqlx::MySqlPool>,
+    Query(params): Query<OrphanQuery>,
+) -> Result<Json<Vec<OrphanObject>>, AppError> {
+    Ok(Json(OrphanCleanupService::scan(&pool, params.config_id).await?))
+}
+
+/// 批量删除遗留对象，dry_run 时只返回语句
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/cleanup",
+    tag = "risingwave",
+    request_body = OrphanCleanupRequest,
+    responses((status = 200, body = OrphanCleanupResult))
+)]
+pub async fn cleanup_orphans(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<OrphanCleanupRequest>,
+) -> Result<Json<OrphanCleanupResult>, AppError> {
+    Ok(Json(
+        OrphanCleanupService::cleanup(&pool, &request, request_user(&headers).as_deref()).await?,
+    ))
+}
+
+/// 列出共享的 Source / Secret 及引用它们的目标表数量
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/managed_objects",
+    tag = "risingwave",
+    params(ManagedObjectQuery),
+    responses((status = 200, body = Vec<ManagedObject>))
+)]
+pub async fn list_managed_objects(
+    State(pool): State<sqlx::MySqlPool>,
+    Query(params): Query<ManagedObjectQuery>,
+) -> Result<Json<Vec<ManagedObject>>, AppError> {
+    let objects = ManagedObjectRepository::new(&pool)
+        .list(params.rw_config_id)
+        .await?;
+    Ok(Json(objects))
+}
+
+/// 删除不再被任何目标表引用的共享 Source / Secret
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/managed_objects/gc",
+    tag = "risingwave",
+    request_body = CollectGarbageRequest,
+    responses((status = 200, body = Vec<ManagedObject>))
+)]
+pub async fn collect_managed_objects(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    Json(request): Json<CollectGarbageRequest>,
+) -> Result<Json<Vec<ManagedObject>>, AppError> {
+    let rw_pool = get_rw_pool(&pool, request.rw_config_id).await?;
+    let dropped =
+        ManagedObjectService::collect_garbage(&pool, &rw_pool, request.rw_config_id).await?;
+    Ok(Json(dropped))
+}
+
+/// 对比同步任务创建的 sink 与当前生成器输出，重建属性不一致的 sink（RisingWave 升级后使用）
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/sinks/recreate",
+    tag = "risingwave",
+    request_body = RecreateSinksRequest,
+    responses((status = 200, body = RecreateSinksResult))
+)]
+pub async fn recreate_sinks(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<RecreateSinksRequest>,
+) -> Result<Json<RecreateSinksResult>, AppError> {
+    let result = SinkMaintenanceService::recreate(&pool, &request).await?;
+    if let Some(task_id) = result.task_id {
+        set_created_by(&pool, task_id, &headers).await?;
+    }
+    Ok(Json(result))
+}
+
+/// SQL 控制台：在 RisingWave 上执行单条只读语句，限制返回行数和执行时间
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/query",
+    tag = "risingwave",
+    request_body = SqlQueryRequest,
+    responses((status = 200, body = SqlQueryResult))
+)]
+pub async fn query_sql(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    Json(request): Json<SqlQueryRequest>,
+) -> Result<Json<SqlQueryResult>, AppError> {
+    Ok(Json(SqlConsoleService::query(&pool, &request).await?))
+}
+
+/// 列出删除对象时会被 CASCADE 一起删除的下游对象
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/objects/dependencies",
+    tag = "risingwave",
+    params(RwObjectNameQuery),
+    responses((status = 200, body = Vec<RwDependentObject>))
+)]
+pub async fn get_object_dependencies(
+    State(pool): State<sqlx::MySqlPool>,
+    Query(params): Query<RwObjectNameQuery>,
+) -> Result<Json<Vec<RwDependentObject>>, AppError> {
+    let rw_pool = get_rw_pool(&pool, params.config_id).await?;
+    Ok(Json(fetch_dependents(&rw_pool, &params.schema, &params.name).await?))
+}
+
+/// 删除 source
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/sources/delete",
+    tag = "risingwave",
+    request_body = DeleteObjectRequest,
+    responses(
+        (status = 200, body = serde_json::Value),
+        (status = 409, description = "存在下游依赖对象且未指定 force，返回依赖对象列表", body = serde_json::Value)
+    )
+)]
+pub async fn delete_source(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<DeleteObjectRequest>,
+) -> Result<Response, AppError> {
+    // 共享 Source 仍被引用时需要 force
+    let managed = ManagedObjectService::check_drop(
+        &pool,
+        request.config_id,
+        ManagedObjectType::Source,
+        &ManagedObject::qualified_name(&request.schema, &request.name),
+        request.force,
+    )
+    .await?;
+    let rw_pool = get_rw_pool(&pool, request.config_id).await?;
+    if let Some(conflict) =
+        check_dependents(&rw_pool, &request.schema, &request.name, request.force).await?
+    {
+        return Ok(conflict);
+    }
+
+    let drop_sql = drop_sql("SOURCE", &request.schema, &request.name, request.force);
+    execute_audited(&pool, &rw_pool, &headers, &drop_sql).await?;
+    if let Some(object) = managed {
+        ManagedObjectService::forget(&pool, &object).await?;
+    }
+
+    Ok(Json(serde_json::json!({ "success": true })).into_response())
+}
+
+/// 删除 table
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/tables/delete",
+    tag = "risingwave",
+    request_body = DeleteObjectRequest,
+    responses(
+        (status = 200, body = serde_json::Value),
+        (status = 409, description = "存在下游依赖对象且未指定 force，返回依赖对象列表", body = serde_json::Value)
+    )
+)]
+pub async fn delete_table(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<DeleteObjectRequest>,
+) -> Result<Response, AppError> {
+    let rw_pool = get_rw_pool(&pool, request.config_id).await?;
+    if let Some(conflict) =
+        check_dependents(&rw_pool, &request.schema, &request.name, request.force).await?
+    {
+        return Ok(conflict);
+    }
+
+    let drop_sql = drop_sql("TABLE", &request.schema, &request.name, request.force);
+    execute_audited(&pool, &rw_pool, &headers, &drop_sql).await?;
+    // 释放对共享 Source 的引用，最后一张表删除时清理 Source 和 Secret
+    let released = ManagedObjectService::release_table(
+        &pool,
+        &rw_pool,
+        request.config_id,
+        &request.schema,
+        &request.name,
+    )
+    .await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "released": released.iter().map(|o| &o.object_name).collect::<Vec<_>>(),
+    }))
+    .into_response())
+}
+
+/// 删除 materialized view
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/materialized_views/delete",
+    tag = "risingwave",
+    request_body = DeleteObjectRequest,
+    responses(
+        (status = 200, body = serde_json::Value),
+        (status = 409, description = "存在下游依赖对象且未指定 force，返回依赖对象列表", body = serde_json::Value)
+    )
+)]
+pub async fn delete_materialized_view(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<DeleteObjectRequest>,
+) -> Result<Response, AppError> {
+    let rw_pool = get_rw_pool(&pool, request.config_id).await?;
+    if let Some(conflict) =
+        check_dependents(&rw_pool, &request.schema, &request.name, request.force).await?
+    {
+        return Ok(conflict);
+    }
+
+    let drop_sql = drop_sql("MATERIALIZED VIEW", &request.schema, &request.name, request.force);
+    execute_audited(&pool, &rw_pool, &headers, &drop_sql).await?;
+
+    Ok(Json(serde_json::json!({ "success": true })).into_response())
+}
+
+/// 查询 sink 状态：是否在创建中、decouple 状态和最近的错误事件
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/sinks/{name}/status",
+    tag = "risingwave",
+    params(("name" = String, Path), SinkStatusQuery),
+    responses((status = 200, body = RwSinkStatus))
+)]
+pub async fn get_sink_status(
+    State(pool): State<sqlx::MySqlPool>,
+    Path(name): Path<String>,
+    Query(params): Query<SinkStatusQuery>,
+) -> Result<Json<RwSinkStatus>, AppError> {
+    let rw_pool = get_rw_pool(&pool, params.config_id).await?;
+    let schema = params.schema.unwrap_or_else(default_schema);
+
+    let row = sqlx::query(
+        "SELECT s.id, s.name, sch.name as schema_name, s.connector
+         FROM rw_catalog.rw_sinks s
+         JOIN rw_catalog.rw_schemas sch ON s.schema_id = sch.id
+         WHERE sch.name = $1 AND s.name = $2",
+    )
+    .bind(&schema)
+    .bind(&name)
+    .fetch_optional(&rw_pool)
+    .await?
+    .ok_or_else(|| {
+        crate::utils::error::AppError::NotFound(format!("Sink {}.{} not found", schema, name))
+    })?;
+    let sink_id: i32 = row.get("id");
+
+    // 以下系统表在旧版本 RisingWave 中可能不存在，查询失败时忽略
+    let is_decoupled = sqlx::query("SELECT is_decouple FROM rw_catalog.rw_sink_decouple WHERE sink_id = $1")
+        .bind(sink_id)
+        .fetch_optional(&rw_pool)
+        .await
+        .map_err(|e| tracing::debug!("Failed to query rw_sink_decouple: {}", e))
+        .ok()
+        .flatten()
+        .and_then(|row| row.try_get::<bool, _>("is_decouple").ok());
+
+    let creating_progress = sqlx::query("SELECT progress FROM rw_catalog.rw_ddl_progress WHERE ddl_id = $1")
+        .bind(sink_id as i64)
+        .fetch_optional(&rw_pool)
+        .await
+        .map_err(|e| tracing::debug!("Failed to query rw_ddl_progress: {}", e))
+        .ok()
+        .flatten()
+        .and_then(|row| row.try_get::<String, _>("progress").ok());
+
+    let error_rows = sqlx::query(
+        "SELECT timestamp::varchar AS ts, event_type, info::varchar AS info,
+                timestamp > now() - interval '1 hour' AS recent
+         FROM rw_catalog.rw_event_logs
+         WHERE event_type = 'SINK_FAIL' AND info::varchar LIKE '%' || $1 || '%'
+         ORDER BY timestamp DESC
+         LIMIT 20",
+    )
+    .bind(&name)
+    .fetch_all(&rw_pool)
+    .await
+    .unwrap_or_else(|e| {
+        tracing::debug!("Failed to query rw_event_logs: {}", e);
+        vec![]
+    });
+
+    let recent_error_count = error_rows
+        .iter()
+        .filter(|row| row.try_get::<bool, _>("recent").unwrap_or(false))
+        .count();
+    let recent_errors = error_rows
+        .iter()
+        .map(|row| RwSinkError {
+            timestamp: row.try_get("ts").unwrap_or_default(),
+            event_type: row.try_get("event_type").unwrap_or_default(),
+            info: row.try_get("info").unwrap_or_default(),
+        })
+        .collect();
+
+    let state = if creating_progress.is_some() {
+        "creating"
+    } else if recent_error_count > 0 {
+        "error"
+    } else {
+        "running"
+    };
+
+    Ok(Json(RwSinkStatus {
+        id: sink_id,
+        managed: name.ends_with("_to_sr_sink"),
+        name: row.get("name"),
+        schema_name: row.get("schema_name"),
+        connector: row.get("connector"),
+        state: state.to_string(),
+        is_decoupled,
+        creating_progress,
+        recent_error_count,
+        recent_errors,
+    }))
+}
+
+/// 删除 sink
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/sinks/delete",
+    tag = "risingwave",
+    request_body = DeleteObjectRequest,
+    responses(
+        (status = 200, body = serde_json::Value),
+        (status = 409, description = "存在下游依赖对象且未指定 force，返回依赖对象列表", body = serde_json::Value)
+    )
+)]
+pub async fn delete_sink(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<DeleteObjectRequest>,
+) -> Result<Response, AppError> {
+    let rw_pool = get_rw_pool(&pool, request.config_id).await?;
+    if let Some(conflict) =
+        check_dependents(&rw_pool, &request.schema, &request.name, request.force).await?
+    {
+        return Ok(conflict);
+    }
+
+    let drop_sql = drop_sql("SINK", &request.schema, &request.name, request.force);
+    execute_audited(&pool, &rw_pool, &headers, &drop_sql).await?;
+
+    Ok(Json(serde_json::json!({ "success": true })).into_response())
+}
+
+/// 批量删除对象
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/objects/batch_delete",
+    tag = "risingwave",
+    request_body = BatchDeleteObjectRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn batch_delete_objects(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<BatchDeleteObjectRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let rw_pool = get_rw_pool(&pool, request.config_id).await?;
+
+    let object_type_sql = match request.object_type.as_str() {
+        "source" => "SOURCE",
+        "table" => "TABLE",
+        "materialized_view" => "MATERIALIZED VIEW",
+        "sink" => "SINK",
+        _ => {
+            return Err(crate::utils::error::AppError::InvalidInput(
+                format!("Invalid object type: {}", request.object_type)
+            ).into())
+        }
+    };
+
+    let mut success_count = 0;
+    let mut failed = Vec::new();
+    let mut released = Vec::new();
+    // 因存在下游对象而跳过的对象
+    let mut blocked = std::collections::BTreeMap::new();
+
+    for name in &request.names {
+        // 共享 Source 仍被引用时需要 force
+        let managed = if object_type_sql == "SOURCE" {
+            match ManagedObjectService::check_drop(
+                &pool,
+                request.config_id,
+                ManagedObjectType::Source,
+                &ManagedObject::qualified_name(&request.schema, name),
+                request.force,
+            )
+            .await
+            {
+                Ok(managed) => managed,
+                Err(e) => {
+                    tracing::error!("Refusing to delete source {}: {}", name, e);
+                    failed.push(name.clone());
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+
+        if !request.force {
+            match fetch_dependents(&rw_pool, &request.schema, name).await {
+                Ok(dependents) if dependents.is_empty() => {}
+                Ok(dependents) => {
+                    tracing::warn!(
+                        "Refusing to delete {} {} with {} dependent objects",
+                        object_type_sql,
+                        name,
+                        dependents.len()
+                    );
+                    failed.push(name.clone());
+                    blocked.insert(name.clone(), dependents);
+                    continue;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to check dependents of {}: {}", name, e.0);
+                    failed.push(name.clone());
+                    continue;
+                }
+            }
+        }
+
+        let drop_sql = drop_sql(object_type_sql, &request.schema, name, request.force);
+        tracing::debug!("Executing: {}", drop_sql);
+
+        match execute_audited(&pool, &rw_pool, &headers, &drop_sql).await {
             Ok(_) => {
                 tracing::info!("Successfully deleted {} {}", object_type_sql, name);
                 success_count += 1;
@@ -520,8 +1508,21 @@ pub async fn batch_delete_objects(
             Err(e) => {
                 tracing::error!("Failed to delete {} {}: {}", object_type_sql, name, e);
                 failed.push(name.clone());
+                continue;
             }
         }
+
+        if let Some(object) = managed {
+            ManagedObjectService::forget(&pool, &object).await?;
+        }
+        if object_type_sql == "TABLE" {
+            released.extend(
+                ManagedObjectService::release_table(&pool, &rw_pool, request.config_id, &request.schema, name)
+                    .await?
+                    .into_iter()
+                    .map(|o| o.object_name),
+            );
+        }
     }
 
     Ok(Json(serde_json::json!({
@@ -529,25 +1530,209 @@ pub async fn batch_delete_objects(
         "deleted_count": success_count,
         "total_count": request.names.len(),
         "failed": failed,
+        "released": released,
+        "dependents": blocked,
     })))
 }
 
+/// 将 Kafka topic 注册为 RisingWave 表，之后可以通过 /sinks/create 写入 StarRocks
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/sources/kafka",
+    tag = "risingwave",
+    request_body = CreateKafkaSourceRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn create_kafka_source(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<CreateKafkaSourceRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    tracing::info!(
+        "Creating Kafka table {}.{} from topic {}",
+        request.schema,
+        request.name,
+        request.topic
+    );
+
+    let ddl = RisingWaveDDLGenerator::generate_kafka_table_ddl(&request)?;
+    let task_id = KafkaTask::start(&pool, &request).await?;
+    set_created_by(&pool, task_id, &headers).await?;
+    let logger = TaskLogger::new(&pool, task_id, TaskLogLevel::default());
+    let result = create_kafka_table(&logger, &pool, &request, &ddl).await;
+    if let Err(e) = logger.finish().await {
+        tracing::warn!("Failed to flush logs for task {}: {}", task_id, e);
+    }
+    KafkaTask::finish(&pool, task_id, &request, &result).await?;
+    result?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "task_id": task_id,
+        "schema": request.schema,
+        "source_object": request.name,
+        "source_type": "table",
+        "ddl": ddl,
+    })))
+}
+
+/// 在 RisingWave 中创建 schema 和 Kafka 表，语句经 DDL 执行器记录到任务日志和审计日志
+async fn create_kafka_table(
+    logger: &TaskLogger<'_>,
+    pool: &sqlx::MySqlPool,
+    request: &CreateKafkaSourceRequest,
+    ddl: &str,
+) -> Result<(), AppError> {
+    let rw_config = ConnectionService::resolve_config(
+        ConfigRepository::new(pool).find_by_id(request.rw_config_id).await?,
+    )
+    .await?;
+    let executor = DdlExecutor::connect_rw(logger, RetryPolicy::global_defaults(), &rw_config).await?;
+
+    logger
+        .step(&format!(
+            "Creating Kafka table {}.{} from topic {}",
+            request.schema, request.name, request.topic
+        ))
+        .await?;
+    let schema_ddl = RisingWaveDDLGenerator::generate_create_schema_ddl(&request.schema);
+    executor.rw("create schema", &schema_ddl, true).await?;
+    executor.rw("create Kafka table", ddl, true).await?;
+    executor.close().await;
+    Ok(())
+}
+
+/// 注册 Kafka topic 的任务记录
+struct KafkaTask;
+
+impl KafkaTask {
+    /// 创建运行中的任务。与创建 sink 的任务一样没有同步请求，sink_requests 记录为空列表，
+    /// 不参与重跑、拆除和遗留对象扫描；没有 MySQL 和 StarRocks 连接，均记录 RisingWave 连接
+    async fn start(pool: &sqlx::MySqlPool, request: &CreateKafkaSourceRequest) -> Result<i64, AppError> {
+        let task = SyncTask {
+            id: 0,
+            task_name: format!("Create Kafka table {}.{}", request.schema, request.name),
+            mysql_config_id: request.rw_config_id,
+            rw_config_id: request.rw_config_id,
+            sr_config_id: request.rw_config_id,
+            mysql_database: request.schema.clone(),
+            mysql_table: request.topic.clone(),
+            target_database: request.schema.clone(),
+            target_table: request.name.clone(),
+            status: TaskStatus::Running,
+            started_at: chrono::Utc::now(),
+            completed_at: None,
+            error_message: None,
+            options: serde_json::to_string(&SyncOptions::default())?,
+            preflight_result: None,
+            requests: None,
+            sink_requests: Some(crypto::encrypt("[]")?),
+        };
+        Ok(TaskRepository::new(pool)
+            .create_with_log(&task, &format!("Task created for registering Kafka topic {}", request.topic))
+            .await?)
+    }
+
+    /// 按结果结束任务并发送通知
+    async fn finish(
+        pool: &sqlx::MySqlPool,
+        task_id: i64,
+        request: &CreateKafkaSourceRequest,
+        result: &Result<(), AppError>,
+    ) -> Result<(), AppError> {
+        let task_repo = TaskRepository::new(pool);
+        match result {
+            Ok(()) => {
+                task_repo
+                    .finish_with_log(
+                        task_id,
+                        TaskStatus::Completed,
+                        None,
+                        "info",
+                        &format!("Created Kafka table {}.{}", request.schema, request.name),
+                    )
+                    .await?
+            }
+            Err(e) => {
+                let error_msg = format!("Failed to create Kafka table: {}", e.0);
+                tracing::error!("Kafka table task {} failed: {}", task_id, error_msg);
+                task_repo
+                    .finish_with_log(task_id, TaskStatus::Failed, Some(error_msg.clone()), "error", &error_msg)
+                    .await?
+            }
+        }
+        NotificationService::notify_task_finished(pool, task_id);
+        Ok(())
+    }
+}
+
 /// 从 RisingWave 获取表或物化视图的 schema
 async fn get_rw_table_schema(
     rw_pool: &PgPool,
     schema: &str,
     object_name: &str,
-    object_type: &str, // "table" or "materialized_view"
+    primary_keys: Option<&[String]>,
 ) -> Result<TableSchema, AppError> {
-    // 查询列信息
-    let columns: Vec<Column> = sqlx::query(
+    let columns = fetch_rw_columns(rw_pool, schema, object_name).await?;
+    let keys = resolve_keys(&columns);
+
+    // 用户选择的主键列优先，否则使用目录中的主键；主键不明确时为空
+    let primary_keys = match primary_keys.filter(|keys| !keys.is_empty()) {
+        Some(chosen) => {
+            if let Some(missing) = chosen.iter().find(|k| !keys.columns.contains(k)) {
+                return Err(crate::utils::error::AppError::Validation(format!(
+                    "Key column {} not found in {}.{}",
+                    missing, schema, object_name
+                ))
+                .into());
+            }
+            chosen.to_vec()
+        }
+        None if keys.ambiguous => Vec::new(),
+        None => keys.primary_keys,
+    };
+
+    Ok(TableSchema {
+        database: schema.to_string(),
+        table_name: object_name.to_string(),
+        columns: columns
+            .into_iter()
+            .filter(|c| !c.is_hidden)
+            .map(|c| c.column)
+            .collect(),
+        primary_keys,
+        indexes: vec![],
+        type_source: ColumnTypeSource::RisingWave,
+        comment: None,
+    })
+}
+
+/// rw_columns 中的一列及其在主键 / 分布键中的角色
+struct RwColumnInfo {
+    column: Column,
+    is_hidden: bool,
+    is_primary_key: bool,
+    is_distribution_key: bool,
+}
+
+/// 查询表或物化视图的全部列（包括隐藏列），按位置排序
+async fn fetch_rw_columns(
+    rw_pool: &PgPool,
+    schema: &str,
+    object_name: &str,
+) -> Result<Vec<RwColumnInfo>, AppError> {
+    let columns: Vec<RwColumnInfo> = sqlx::query(
         r#"
         SELECT
             c.name as column_name,
             c.data_type,
-            c.is_nullable
+            c.is_nullable,
+            c.is_hidden,
+            c.is_primary_key,
+            c.is_distribution_key
         FROM rw_catalog.rw_columns c
-        WHERE 
+        WHERE
         c.relation_id IN (
             SELECT t.id FROM rw_catalog.rw_tables t
             JOIN rw_catalog.rw_schemas sch ON t.schema_id = sch.id
@@ -565,60 +1750,81 @@ async fn get_rw_table_schema(
     .fetch_all(rw_pool)
     .await?
     .iter()
-    .map(|row| {
-        let data_type: String = row.get("data_type");
-        Column {
+    .map(|row| RwColumnInfo {
+        column: Column {
             name: row.get("column_name"),
-            data_type,
+            data_type: row.get("data_type"),
             is_nullable: row.get("is_nullable"),
             default_value: None,
             comment: None,
             character_maximum_length: None,
             numeric_precision: None,
             numeric_scale: None,
-        }
+        },
+        is_hidden: row.get("is_hidden"),
+        is_primary_key: row.get("is_primary_key"),
+        is_distribution_key: row.get("is_distribution_key"),
     })
     .collect();
 
-    if columns.is_empty() {
+    if columns.iter().all(|c| c.is_hidden) {
         return Err(crate::utils::error::AppError::NotFound(
             format!("No columns found for {}.{}", schema, object_name)
         ).into());
     }
+    Ok(columns)
+}
 
-    // 查询主键信息（只有表有主键，物化视图没有主键约束）
-    let primary_keys: Vec<String> = if object_type == "table" {
-        sqlx::query_scalar(
-            r#"
-            SELECT c.name
-            FROM rw_catalog.rw_columns c
-            JOIN rw_catalog.rw_tables t ON c.relation_id = t.id
-            JOIN rw_catalog.rw_schemas s ON t.schema_id = s.id
-            WHERE s.name = $1 AND t.name = $2 AND c.is_primary_key = true
-            ORDER BY c.position
-            "#
-        )
-        .bind(schema)
-        .bind(object_name)
-        .fetch_all(rw_pool)
-        .await?
-    } else {
-        // 物化视图没有主键，使用第一列作为主键
-        vec![columns[0].name.clone()]
+/// 按目录中的主键确定 sink 主键：主键为空或包含隐藏列（例如没有主键的表的 _row_id、
+/// 没有 GROUP BY 的物化视图的隐藏流键）时无法用可见列表示，需要用户选择
+fn resolve_keys(columns: &[RwColumnInfo]) -> RwRelationKeys {
+    let visible = |f: fn(&RwColumnInfo) -> bool| -> Vec<String> {
+        columns
+            .iter()
+            .filter(|c| !c.is_hidden && f(c))
+            .map(|c| c.column.name.clone())
+            .collect()
     };
-
-    Ok(TableSchema {
-        database: schema.to_string(),
-        table_name: object_name.to_string(),
-        columns,
+    let primary_keys = visible(|c| c.is_primary_key);
+    let hidden_key = columns.iter().any(|c| c.is_hidden && c.is_primary_key);
+    RwRelationKeys {
+        columns: visible(|_| true),
+        ambiguous: hidden_key || primary_keys.is_empty(),
         primary_keys,
-        indexes: vec![],
-    })
+        distribution_keys: visible(|c| c.is_distribution_key),
+    }
+}
+
+/// 表或物化视图的列和主键信息，创建 sink 前用于确定 StarRocks 主键
+#[utoipa::path(
+    get,
+    path = "/api/risingwave/objects/keys",
+    tag = "risingwave",
+    params(RwObjectNameQuery),
+    responses((status = 200, body = RwRelationKeys))
+)]
+pub async fn get_object_keys(
+    State(pool): State<sqlx::MySqlPool>,
+    Query(params): Query<RwObjectNameQuery>,
+) -> Result<Json<RwRelationKeys>, AppError> {
+    let rw_pool = get_rw_pool(&pool, params.config_id).await?;
+    let columns = fetch_rw_columns(&rw_pool, &params.schema, &params.name).await?;
+    Ok(Json(resolve_keys(&columns)))
 }
 
 /// 创建 Sink 到 StarRocks
+/// 创建过程记录为任务，可在任务历史中查看日志，失败时发送通知
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/sinks/create",
+    tag = "risingwave",
+    request_body = CreateSinkRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn create_sink(
     State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
     Json(request): Json<CreateSinkRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     tracing::info!(
@@ -630,101 +1836,490 @@ pub async fn create_sink(
         request.target_table
     );
 
-    // 获取配置
-    let config_repo = ConfigRepository::new(&pool);
-    let sr_config = config_repo.find_by_id(request.sr_config_id).await?;
+    let requests = vec![request];
+    let (task_id, mut results) = run_sink_task(&pool, &requests, &headers).await?;
+    let request = &requests[0];
+    results.pop().unwrap_or(Ok(()))?;
 
-    // 连接到 RisingWave
-    let rw_pool = get_rw_pool(&pool, request.rw_config_id).await?;
+    let target = if request.iceberg.is_some() { "Iceberg sink" } else { "sink" };
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "task_id": task_id,
+        "message": format!("Successfully created {} from {}.{} to {}.{}",
+            target, request.schema, request.source_object,
+            request.target_database, request.target_table)
+    })))
+}
 
-    // 获取表结构
-    let schema = get_rw_table_schema(
-        &rw_pool,
-        &request.schema,
-        &request.source_object,
-        &request.source_type
-    ).await?;
+/// 批量创建 Sink 到 StarRocks，所有对象共用一个 RisingWave 连接池和 StarRocks 连接，
+/// 单个对象失败不影响其他对象
+#[utoipa::path(
+    post,
+    path = "/api/risingwave/sinks/batch_create",
+    tag = "risingwave",
+    request_body = BatchCreateSinkRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn batch_create_sinks(
+    State(pool): State<sqlx::MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<BatchCreateSinkRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    tracing::info!(
+        "Creating {} sinks to StarRocks (config {})",
+        request.sinks.len(),
+        request.sr_config_id
+    );
+    if request.sinks.is_empty() {
+        return Err(crate::utils::error::AppError::Validation("No sinks to create".to_string()).into());
+    }
 
-    // 连接到 StarRocks
-    let mut sr_opts_builder = mysql_async::OptsBuilder::default()
-        .ip_or_hostname(&sr_config.host)
-        .tcp_port(sr_config.port)
-        .user(Some(&sr_config.username))
-        .pass(Some(&sr_config.password))
-        .prefer_socket(false);
+    let requests: Vec<CreateSinkRequest> = request
+        .sinks
+        .into_iter()
+        .map(|item| item.into_request(request.rw_config_id, request.sr_config_id))
+        .collect();
+    let (task_id, outcomes) = run_sink_task(&pool, &requests, &headers).await?;
+    Ok(Json(batch_summary(task_id, &requests, outcomes)))
+}
 
-    if let Some(db) = &sr_config.database_name {
-        sr_opts_builder = sr_opts_builder.db_name(Some(db));
+/// 重试创建 sink 的任务：重新执行记录的全部请求，语句均为 IF NOT EXISTS，已创建的对象不受影响
+pub(super) async fn retry_sink_task(
+    pool: &sqlx::MySqlPool,
+    task: &SyncTask,
+    headers: &HeaderMap,
+) -> Result<serde_json::Value, AppError> {
+    let encrypted = task.sink_requests.as_deref().unwrap_or_default();
+    let requests: Vec<CreateSinkRequest> = serde_json::from_str(&crypto::decrypt(encrypted)?)?;
+    if requests.is_empty() {
+        return Err(crate::utils::error::AppError::Validation(format!(
+            "Task {} does not record its sinks",
+            task.id
+        ))
+        .into());
     }
 
-    let sr_opts = mysql_async::Opts::from(sr_opts_builder);
-    let mut sr_conn = mysql_async::Conn::new(sr_opts).await.map_err(|e| {
-        crate::utils::error::AppError::Connection(format!("StarRocks connection failed: {}", e))
-    })?;
+    let (task_id, outcomes) = run_sink_task(pool, &requests, headers).await?;
+    Ok(batch_summary(task_id, &requests, outcomes))
+}
 
-    // 创建 StarRocks 数据库
-    let create_db_ddl = StarRocksDDLGenerator::generate_create_database_ddl(&request.target_database);
-    sr_conn.query_drop(&create_db_ddl).await.map_err(|e| {
-        crate::utils::error::AppError::Unknown(format!("Failed to create database: {}", e))
-    })?;
+fn batch_summary(
+    task_id: i64,
+    requests: &[CreateSinkRequest],
+    outcomes: Vec<Result<(), AppError>>,
+) -> serde_json::Value {
+    let results: Vec<SinkCreateResult> = requests
+        .iter()
+        .zip(outcomes)
+        .map(|(request, outcome)| {
+            let error = outcome.err().map(|e| e.0.to_string());
+            SinkCreateResult {
+                source: format!("{}.{}", request.schema, request.source_object),
+                target: format!("{}.{}", request.target_database, request.target_table),
+                success: error.is_none(),
+                error,
+            }
+        })
+        .collect();
+
+    let total_count = results.len();
+    let created_count = results.iter().filter(|r| r.success).count();
+    serde_json::json!({
+        "success": created_count == total_count,
+        "task_id": task_id,
+        "created_count": created_count,
+        "total_count": total_count,
+        "results": results,
+    })
+}
+
+/// 以任务形式创建 sink，返回 task_id 和每个请求的结果
+/// 连接失败等影响全部对象的错误直接返回，任务同样记为失败
+async fn run_sink_task(
+    pool: &sqlx::MySqlPool,
+    requests: &[CreateSinkRequest],
+    headers: &HeaderMap,
+) -> Result<(i64, Vec<Result<(), AppError>>), AppError> {
+    let task_id = SinkTask::start(pool, requests).await?;
+    set_created_by(pool, task_id, headers).await?;
+    let logger = TaskLogger::new(pool, task_id, TaskLogLevel::default());
+
+    let outcome = match requests.first().and_then(|r| r.iceberg.as_ref()) {
+        Some(iceberg) => {
+            let result = create_iceberg_sink(&logger, pool, &requests[0], iceberg).await;
+            if let Err(e) = &result {
+                let _ = logger.error(&format!("Failed to create Iceberg sink: {}", e.0)).await;
+            }
+            Ok(vec![result])
+        }
+        None => create_sinks(&logger, pool, requests).await,
+    };
+    if let Err(e) = logger.finish().await {
+        tracing::warn!("Failed to flush logs for task {}: {}", task_id, e);
+    }
+
+    SinkTask::finish(pool, task_id, requests, &outcome).await?;
+    outcome.map(|results| (task_id, results))
+}
+
+/// 创建 sink 的任务记录
+struct SinkTask;
+
+impl SinkTask {
+    /// 创建运行中的任务。sink 的来源是 RisingWave，mysql_config_id 记录 RisingWave 连接；
+    /// 写入 Iceberg 时没有 StarRocks 连接，sr_config_id 同样记录 RisingWave 连接
+    async fn start(pool: &sqlx::MySqlPool, requests: &[CreateSinkRequest]) -> Result<i64, AppError> {
+        let first = &requests[0];
+        let (task_name, source, target) = if requests.len() == 1 {
+            (
+                format!("Create sink {}.{}", first.schema, first.source_object),
+                first.source_object.clone(),
+                first.target_table.clone(),
+            )
+        } else {
+            (
+                format!("Create {} sinks", requests.len()),
+                format!("[Batch: {} objects]", requests.len()),
+                format!("[Batch: {} sinks]", requests.len()),
+            )
+        };
+
+        let task = SyncTask {
+            id: 0,
+            task_name,
+            mysql_config_id: first.rw_config_id,
+            rw_config_id: first.rw_config_id,
+            sr_config_id: if first.iceberg.is_some() { first.rw_config_id } else { first.sr_config_id },
+            mysql_database: first.schema.clone(),
+            mysql_table: source,
+            target_database: first.target_database.clone(),
+            target_table: target,
+            status: TaskStatus::Running,
+            started_at: chrono::Utc::now(),
+            completed_at: None,
+            error_message: None,
+            options: serde_json::to_string(&SyncOptions::default())?,
+            preflight_result: None,
+            requests: None,
+            sink_requests: Some(crypto::encrypt(&serde_json::to_string(requests)?)?),
+        };
+        Ok(TaskRepository::new(pool)
+            .create_with_log(&task, &format!("Task created for creating {} sinks", requests.len()))
+            .await?)
+    }
+
+    /// 按结果结束任务并发送通知
+    async fn finish(
+        pool: &sqlx::MySqlPool,
+        task_id: i64,
+        requests: &[CreateSinkRequest],
+        outcome: &Result<Vec<Result<(), AppError>>, AppError>,
+    ) -> Result<(), AppError> {
+        let failure = match outcome {
+            Ok(results) => {
+                let failed: Vec<_> = requests
+                    .iter()
+                    .zip(results)
+                    .filter_map(|(request, result)| result.as_ref().err().map(|e| (request, e)))
+                    .collect();
+                match failed.as_slice() {
+                    [] => None,
+                    [(request, e)] => Some(format!(
+                        "Failed to create sink from {}.{}: {}",
+                        request.schema, request.source_object, e.0
+                    )),
+                    _ => Some(format!("Failed to create {} of {} sinks", failed.len(), requests.len())),
+                }
+            }
+            Err(e) => Some(format!("Sink creation failed: {}", e.0)),
+        };
+
+        let task_repo = TaskRepository::new(pool);
+        match failure {
+            None => {
+                task_repo
+                    .finish_with_log(
+                        task_id,
+                        TaskStatus::Completed,
+                        None,
+                        "info",
+                        &format!("Created {} sinks", requests.len()),
+                    )
+                    .await?
+            }
+            Some(error_msg) => {
+                tracing::error!("Sink creation task {} failed: {}", task_id, error_msg);
+                task_repo
+                    .finish_with_log(task_id, TaskStatus::Failed, Some(error_msg.clone()), "error", &error_msg)
+                    .await?
+            }
+        }
+        NotificationService::notify_task_finished(pool, task_id);
+        Ok(())
+    }
+}
+
+/// 逐个创建 StarRocks sink，所有对象共用一个 DDL 执行器，单个对象失败时记录后继续
+async fn create_sinks(
+    logger: &TaskLogger<'_>,
+    pool: &sqlx::MySqlPool,
+    requests: &[CreateSinkRequest],
+) -> Result<Vec<Result<(), AppError>>, AppError> {
+    let first = &requests[0];
+    let mut setup = SinkSetup::connect(logger, pool, first.rw_config_id, first.sr_config_id).await?;
+    let mut results = Vec::with_capacity(requests.len());
+    for request in requests {
+        logger
+            .step(&format!(
+                "Creating sink {}.{} -> {}.{}",
+                request.schema, request.source_object, request.target_database, request.target_table
+            ))
+            .await?;
+        let result = setup.create(request).await;
+        if let Err(e) = &result {
+            tracing::error!(
+                "Failed to create sink from {}.{}: {}",
+                request.schema,
+                request.source_object,
+                e.0
+            );
+            logger
+                .error(&format!(
+                    "Failed to create sink from {}.{}: {}",
+                    request.schema, request.source_object, e.0
+                ))
+                .await?;
+        }
+        results.push(result);
+    }
+    setup.close().await;
+    Ok(results)
+}
+
+/// 创建 StarRocks sink 共用的执行器，记录已创建的数据库和 secret
+struct SinkSetup<'a> {
+    logger: &'a TaskLogger<'a>,
+    sr_config: DatabaseConfig,
+    executor: DdlExecutor<'a>,
+    databases_created: HashSet<String>,
+    secrets_created: HashSet<String>,
+}
+
+impl<'a> SinkSetup<'a> {
+    async fn connect(
+        logger: &'a TaskLogger<'a>,
+        pool: &sqlx::MySqlPool,
+        rw_config_id: i64,
+        sr_config_id: i64,
+    ) -> Result<Self, AppError> {
+        let config_repo = ConfigRepository::new(pool);
+        // SRV 名称解析为实际主机和端口
+        let rw_config =
+            ConnectionService::resolve_config(config_repo.find_by_id(rw_config_id).await?).await?;
+        let sr_config =
+            ConnectionService::resolve_config(config_repo.find_by_id(sr_config_id).await?).await?;
+        // DDL 遇到瞬时错误时按全局重试策略重试
+        let executor =
+            DdlExecutor::connect(logger, RetryPolicy::global_defaults(), &rw_config, &sr_config)
+                .await?;
+        Ok(Self {
+            logger,
+            sr_config,
+            executor,
+            databases_created: HashSet::new(),
+            secrets_created: HashSet::new(),
+        })
+    }
+
+    /// 创建 StarRocks 表和 sink，目标库和 RisingWave 中的 secret 每个目标库只创建一次
+    /// 创建 sink 失败时删除本次新建的 StarRocks 表
+    async fn create(&mut self, request: &CreateSinkRequest) -> Result<(), AppError> {
+        // 获取表结构
+        let schema = get_rw_table_schema(
+            self.executor.rw_pool(),
+            &request.schema,
+            &request.source_object,
+            request.primary_keys.as_deref(),
+        )
+        .await?;
+        if schema.primary_keys.is_empty() {
+            return Err(crate::utils::error::AppError::Validation(format!(
+                "Cannot determine the primary key of {}.{}, choose key columns (primary_keys) for the sink",
+                request.schema, request.source_object
+            ))
+            .into());
+        }
+
+        // 创建 Sink - 需要构建一个临时的 SyncRequest
+        let sync_request = crate::models::SyncRequest {
+            mysql_config_id: 0, // 不需要
+            rw_config_id: request.rw_config_id,
+            sr_config_id: request.sr_config_id,
+            mysql_database: request.schema.clone(), // 仅用于错误信息
+            mysql_table: request.source_object.clone(), // 仅用于错误信息
+            target_database: request.target_database.clone(),
+            target_table: request.target_table.clone(),
+            options: crate::models::SyncOptions::default(),
+            row_filter: None,
+            partition: None,
+            column_mapping: Default::default(),
+            nested_columns: request.nested_columns.clone(),
+            spatial_columns: Default::default(),
+        };
+        RisingWaveDDLGenerator::validate_column_mapping(&sync_request, &schema)?;
+
+        // 创建 StarRocks 数据库
+        if !self.databases_created.contains(&request.target_database) {
+            let create_db_ddl =
+                StarRocksDDLGenerator::generate_create_database_ddl(&request.target_database);
+            self.executor.sr("create database", &create_db_ddl, true).await?;
+            self.databases_created.insert(request.target_database.clone());
+        }
+
+        // 创建 StarRocks 表
+        let table_existed = self
+            .executor
+            .sr_query_first::<i32>(
+                "check table existence",
+                &format!(
+                    "SELECT 1 FROM information_schema.tables WHERE table_schema = '{}' AND table_name = '{}' LIMIT 1",
+                    request.target_database, request.target_table
+                ),
+            )
+            .await?
+            .is_some();
+        let table_options = request
+            .starrocks_table
+            .clone()
+            .unwrap_or_else(StarRocksTableOptions::global_defaults);
+        let sr_table_ddl = StarRocksDDLGenerator::generate_table_ddl(
+            &sync_request.target_schema(&schema),
+            &request.target_database,
+            &request.target_table,
+            &table_options,
+            None,
+        )?;
+        self.executor.sr("create StarRocks table", &sr_table_ddl, true).await?;
+
+        if let Err(e) = self.create_rw_sink(request, &sync_request, &schema).await {
+            if !table_existed {
+                self.rollback_table(request).await;
+            }
+            return Err(e);
+        }
+
+        tracing::info!(
+            "Successfully created sink from {}.{} to {}.{}",
+            request.schema,
+            request.source_object,
+            request.target_database,
+            request.target_table
+        );
+        Ok(())
+    }
+
+    /// 创建 sink，StarRocks Secret 位于与目标库同名的 schema 中
+    async fn create_rw_sink(
+        &mut self,
+        request: &CreateSinkRequest,
+        sync_request: &crate::models::SyncRequest,
+        schema: &TableSchema,
+    ) -> Result<(), AppError> {
+        if !self.secrets_created.contains(&request.target_database) {
+            let schema_ddl =
+                RisingWaveDDLGenerator::generate_create_schema_ddl(&request.target_database);
+            self.executor.rw("create schema", &schema_ddl, true).await?;
+            let sr_secret_ddl = RisingWaveDDLGenerator::generate_starrocks_secret_ddl(
+                &self.sr_config,
+                &request.target_database,
+            )?;
+            self.executor.rw("create StarRocks secret", &sr_secret_ddl, false).await?;
+            self.secrets_created.insert(request.target_database.clone());
+        }
 
-    // 创建 StarRocks 表
-    let sr_table_ddl = StarRocksDDLGenerator::generate_table_ddl(
+        let sink_ddl =
+            RisingWaveDDLGenerator::generate_sink_ddl(&self.sr_config, sync_request, schema)?;
+        self.executor.rw("create RisingWave sink", &sink_ddl, true).await.map_err(|e| {
+            crate::utils::error::AppError::SqlGeneration(format!("Failed to create sink: {}", e))
+        })?;
+        Ok(())
+    }
+
+    /// 删除创建 sink 失败时遗留的 StarRocks 表，删除失败只记录警告
+    async fn rollback_table(&mut self, request: &CreateSinkRequest) {
+        let drop_ddl = StarRocksDDLGenerator::generate_drop_table_ddl(
+            &request.target_database,
+            &request.target_table,
+        );
+        let message = match self.executor.sr("drop StarRocks table", &drop_ddl, true).await {
+            Ok(()) => format!(
+                "Rolled back StarRocks table {}.{}",
+                request.target_database, request.target_table
+            ),
+            Err(e) => format!(
+                "Failed to roll back StarRocks table {}.{}: {}",
+                request.target_database, request.target_table, e
+            ),
+        };
+        let _ = self.logger.warn(&message).await;
+    }
+
+    /// 关闭 StarRocks 连接
+    async fn close(self) {
+        self.executor.close().await;
+    }
+}
+
+/// 创建 Sink 到 Iceberg
+async fn create_iceberg_sink(
+    logger: &TaskLogger<'_>,
+    pool: &sqlx::MySqlPool,
+    request: &CreateSinkRequest,
+    iceberg: &IcebergSinkOptions,
+) -> Result<(), AppError> {
+    let rw_pool = get_rw_pool(pool, request.rw_config_id).await?;
+
+    let schema = get_rw_table_schema(
+        &rw_pool,
+        &request.schema,
+        &request.source_object,
+        request.primary_keys.as_deref(),
+    ).await?;
+
+    let sink_ddl = RisingWaveDDLGenerator::generate_iceberg_sink_ddl(
+        iceberg,
         &schema,
         &request.target_database,
         &request.target_table,
     )?;
-    sr_conn.query_drop(&sr_table_ddl).await.map_err(|e| {
-        crate::utils::error::AppError::Unknown(format!("Failed to create StarRocks table: {}", e))
-    })?;
 
-    // 创建 StarRocks Secret
-    let sr_secret_ddl = RisingWaveDDLGenerator::generate_starrocks_secret_ddl(&sr_config, &request.schema)?;
-    let _ = sqlx::query(&sr_secret_ddl).execute(&rw_pool).await; // 忽略错误（可能已存在）
-
-    // 创建 Sink - 需要构建一个临时的 SyncRequest
-    let sync_request = crate::models::SyncRequest {
-        mysql_config_id: 0, // 不需要
-        rw_config_id: request.rw_config_id,
-        sr_config_id: request.sr_config_id,
-        mysql_database: String::new(), // 不需要
-        mysql_table: String::new(), // 不需要
-        target_database: request.target_database.clone(),
-        target_table: request.target_table.clone(),
-        options: crate::models::SyncOptions {
-            recreate_rw_source: false,
-            recreate_sr_table: false,
-            truncate_sr_table: false,
-        },
-    };
-
-    let sink_ddl = RisingWaveDDLGenerator::generate_sink_ddl(
-        &sr_config,
-        &sync_request,
-        &schema
-    )?;
+    let sink_name = format!("{}_to_iceberg_sink", request.target_table);
+    if let Some(secret_ddl) =
+        RisingWaveDDLGenerator::generate_iceberg_secret_ddl(iceberg, &request.schema, &sink_name)
+    {
+        // 语句包含密钥，不记录到日志
+        sqlx::query(&secret_ddl).execute(&rw_pool).await?;
+        logger.info("Created secret for Iceberg storage").await?;
+    }
 
-    sqlx::query(&sink_ddl).execute(&rw_pool).await.map_err(|e| {
-        crate::utils::error::AppError::SqlGeneration(format!("Failed to create sink: {}", e))
+    let started = Instant::now();
+    let result = sqlx::query(&sink_ddl).execute(&rw_pool).await;
+    let error = result.as_ref().err().map(|e| e.to_string());
+    AuditService::record_task_ddl(logger, "RisingWave", &sink_ddl, error.as_deref()).await;
+    result.map_err(|e| {
+        crate::utils::error::AppError::SqlGeneration(format!("Failed to create Iceberg sink: {}", e))
     })?;
-
-    // 关闭连接
-    let _ = sr_conn.disconnect().await;
+    logger.ddl("RisingWave", "create Iceberg sink", &sink_ddl, started).await?;
 
     tracing::info!(
-        "Successfully created sink from {}.{} to {}.{}",
+        "Successfully created Iceberg sink from {}.{} to {}.{}",
         request.schema,
         request.source_object,
         request.target_database,
         request.target_table
     );
-
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "message": format!("Successfully created sink from {}.{} to {}.{}",
-            request.schema, request.source_object,
-            request.target_database, request.target_table)
-    })))
+    Ok(())
 }
 
 #[cfg(test)]
@@ -732,67 +2327,49 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_rw_object_query_defaults() {
-        let query = RwObjectQuery {
-            config_id: 1,
-            schema: Some("public".to_string()),
-            search: None,
-            limit: None,
-            offset: None,
-        };
-
-        assert_eq!(query.get_limit(), 20);
-        assert_eq!(query.get_offset(), 0);
+    fn test_collect_dependents() {
+        // 1 <- 2 (mv) <- 3 (sink), 1 <- 4 (sink), 2 <- 5 (mv) <- 3
+        let edges = vec![(2, 1), (3, 2), (4, 1), (5, 2), (3, 5), (6, 7)];
+        assert_eq!(collect_dependents(1, &edges), vec![2, 4, 3, 5]);
+        assert_eq!(collect_dependents(3, &edges), Vec::<i32>::new());
+        // 依赖环不会死循环
+        assert_eq!(collect_dependents(8, &[(9, 8), (8, 9)]), vec![9]);
     }
 
     #[test]
-    fn test_rw_object_query_validation() {
-        // Test limit boundary conditions
-        let query_low_limit = RwObjectQuery {
-            config_id: 1,
-            schema: None,
-            search: None,
-            limit: Some(0),
-            offset: None,
-        };
-        assert_eq!(query_low_limit.get_limit(), 1); // Should clamp to min 1
-
-        let query_high_limit = RwObjectQuery {
-            config_id: 1,
-            schema: None,
-            search: None,
-            limit: Some(200),
-            offset: None,
-        };
-        assert_eq!(query_high_limit.get_limit(), 100); // Should clamp to max 100
-
-        let query_negative_limit = RwObjectQuery {
-            config_id: 1,
-            schema: None,
-            search: None,
-            limit: Some(-10),
-            offset: None,
-        };
-        assert_eq!(query_negative_limit.get_limit(), 1); // Negative should clamp to 1
-
-        // Test offset boundary conditions
-        let query_negative_offset = RwObjectQuery {
-            config_id: 1,
-            schema: None,
-            search: None,
-            limit: None,
-            offset: Some(-10),
-        };
-        assert_eq!(query_negative_offset.get_offset(), 0); // Should clamp to min 0
-
-        let query_valid_offset = RwObjectQuery {
-            config_id: 1,
-            schema: None,
-            search: None,
-            limit: None,
-            offset: Some(100),
+    fn test_resolve_keys() {
+        let column = |name: &str, is_hidden: bool, is_primary_key: bool| RwColumnInfo {
+            column: Column {
+                name: name.to_string(),
+                data_type: "bigint".to_string(),
+                is_nullable: true,
+                default_value: None,
+                comment: None,
+                character_maximum_length: None,
+                numeric_precision: None,
+                numeric_scale: None,
+            },
+            is_hidden,
+            is_primary_key,
+            is_distribution_key: is_primary_key,
         };
-        assert_eq!(query_valid_offset.get_offset(), 100);
+
+        // GROUP BY 物化视图：主键为可见的分组列
+        let keys = resolve_keys(&[column("region", false, true), column("total", false, false)]);
+        assert_eq!(keys.primary_keys, vec!["region"]);
+        assert_eq!(keys.distribution_keys, vec!["region"]);
+        assert!(!keys.ambiguous);
+
+        // 流键包含隐藏列
+        let keys = resolve_keys(&[
+            column("id", false, true),
+            column("name", false, false),
+            column("_row_id", true, true),
+        ]);
+        assert_eq!(keys.columns, vec!["id", "name"]);
+        assert!(keys.ambiguous);
+
+        assert!(resolve_keys(&[column("id", false, false)]).ambiguous);
     }
 
     #[test]
@@ -822,6 +2399,10 @@ mod tests {
             source_type: "table".to_string(),
             target_database: "test_db".to_string(),
             target_table: "test_table_sr".to_string(),
+            starrocks_table: None,
+            primary_keys: None,
+            iceberg: None,
+            nested_columns: BTreeMap::new(),
         };
 
         // Test that the struct can be serialized
@@ -854,6 +2435,33 @@ mod tests {
         assert_eq!(request.target_table, "test_mv_sr");
     }
 
+    #[test]
+    fn test_batch_create_sink_request_deserialization() {
+        let json = r#"{
+            "rw_config_id": 1,
+            "sr_config_id": 2,
+            "sinks": [
+                {
+                    "schema": "public",
+                    "source_object": "orders_mv",
+                    "source_type": "materialized_view",
+                    "target_database": "analytics",
+                    "target_table": "orders",
+                    "nested_columns": {"addr": "flatten"}
+                }
+            ]
+        }"#;
+
+        let request: BatchCreateSinkRequest = serde_json::from_str(json).unwrap();
+        let item = request.sinks.into_iter().next().unwrap();
+        let request = item.into_request(request.rw_config_id, request.sr_config_id);
+        assert_eq!(request.rw_config_id, 1);
+        assert_eq!(request.sr_config_id, 2);
+        assert_eq!(request.target_table, "orders");
+        assert!(request.iceberg.is_none());
+        assert_eq!(request.nested_columns.get("addr"), Some(&NestedColumnMode::Flatten));
+    }
+
     #[test]
     fn test_batch_delete_object_types() {
         // Test valid object types
@@ -0,0 +1,141 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::HeaderMap,
+};
+use chrono::Utc;
+use serde_json::json;
+use sqlx::MySqlPool;
+
+use super::approval::request_user;
+use super::auth::Operator;
+use super::connection::AppError;
+use crate::db::ScheduleRepository;
+use crate::models::{ScheduleDetail, SchedulePreviewRequest, ScheduleRequest, ScheduledRun};
+use crate::services::{NEXT_RUNS_PREVIEW, SchedulerService};
+
+/// 列出定时同步及接下来的运行时间
+#[utoipa::path(
+    get,
+    path = "/api/schedules",
+    tag = "schedule",
+    responses((status = 200, body = Vec<ScheduleDetail>))
+)]
+pub async fn list_schedules(
+    State(pool): State<MySqlPool>,
+) -> Result<Json<Vec<ScheduleDetail>>, AppError> {
+    let schedules = ScheduleRepository::new(&pool).find_all().await?;
+    Ok(Json(
+        schedules
+            .into_iter()
+            .map(SchedulerService::detail)
+            .collect(),
+    ))
+}
+
+/// 获取定时同步
+#[utoipa::path(
+    get,
+    path = "/api/schedules/{id}",
+    tag = "schedule",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = ScheduleDetail))
+)]
+pub async fn get_schedule(
+    State(pool): State<MySqlPool>,
+    Path(id): Path<i64>,
+) -> Result<Json<ScheduleDetail>, AppError> {
+    let schedule = ScheduleRepository::new(&pool).find_by_id(id).await?;
+    Ok(Json(SchedulerService::detail(schedule)))
+}
+
+/// 创建定时同步，X-User 请求头记录为创建人，定时提交的任务以其作为提交人
+#[utoipa::path(
+    post,
+    path = "/api/schedules",
+    tag = "schedule",
+    request_body = ScheduleRequest,
+    responses((status = 200, body = ScheduleDetail))
+)]
+pub async fn create_schedule(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<ScheduleRequest>,
+) -> Result<Json<ScheduleDetail>, AppError> {
+    SchedulerService::validate(&request)?;
+    let next_run_at = SchedulerService::next_run_at(
+        &request.cron,
+        &request.timezone,
+        request.enabled,
+        Utc::now(),
+    )?;
+    let repo = ScheduleRepository::new(&pool);
+    let id = repo
+        .create(&request, next_run_at, request_user(&headers).as_deref())
+        .await?;
+    Ok(Json(SchedulerService::detail(repo.find_by_id(id).await?)))
+}
+
+/// 更新定时同步，从当前时间重新计算下一次运行时间
+#[utoipa::path(
+    put,
+    path = "/api/schedules/{id}",
+    tag = "schedule",
+    params(("id" = i64, Path)),
+    request_body = ScheduleRequest,
+    responses((status = 200, body = ScheduleDetail))
+)]
+pub async fn update_schedule(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+    Json(request): Json<ScheduleRequest>,
+) -> Result<Json<ScheduleDetail>, AppError> {
+    SchedulerService::validate(&request)?;
+    let next_run_at = SchedulerService::next_run_at(
+        &request.cron,
+        &request.timezone,
+        request.enabled,
+        Utc::now(),
+    )?;
+    let repo = ScheduleRepository::new(&pool);
+    repo.update(id, &request, next_run_at).await?;
+    Ok(Json(SchedulerService::detail(repo.find_by_id(id).await?)))
+}
+
+/// 删除定时同步，已提交的任务不受影响
+#[utoipa::path(
+    delete,
+    path = "/api/schedules/{id}",
+    tag = "schedule",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn delete_schedule(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    ScheduleRepository::new(&pool).delete(id).await?;
+    Ok(Json(json!({ "success": true })))
+}
+
+/// 预览 cron 表达式在指定时区接下来的运行时间
+#[utoipa::path(
+    post,
+    path = "/api/schedules/preview",
+    tag = "schedule",
+    request_body = SchedulePreviewRequest,
+    responses((status = 200, body = Vec<ScheduledRun>))
+)]
+pub async fn preview_schedule(
+    Json(request): Json<SchedulePreviewRequest>,
+) -> Result<Json<Vec<ScheduledRun>>, AppError> {
+    Ok(Json(SchedulerService::next_runs(
+        &request.cron,
+        &request.timezone,
+        Utc::now(),
+        NEXT_RUNS_PREVIEW,
+    )?))
+}
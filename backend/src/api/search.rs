@@ -0,0 +1,24 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use sqlx::MySqlPool;
+
+use super::connection::AppError;
+use crate::models::{SearchQuery, SearchResponse};
+use crate::services::SearchService;
+
+/// 全局搜索：按名称搜索连接、同步映射、任务、RisingWave 对象和 StarRocks 表
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    tag = "search",
+    params(SearchQuery),
+    responses((status = 200, body = SearchResponse))
+)]
+pub async fn search(
+    State(pool): State<MySqlPool>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<SearchResponse>, AppError> {
+    Ok(Json(SearchService::search(&pool, &query).await?))
+}
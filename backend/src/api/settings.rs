@@ -0,0 +1,59 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use sqlx::MySqlPool;
+
+use super::auth::Admin;
+use super::connection::AppError;
+use crate::models::{SettingValue, UpdateSettingRequest};
+use crate::services::SettingsService;
+
+/// 列出所有设置及当前生效的值
+#[utoipa::path(
+    get,
+    path = "/api/settings",
+    tag = "settings",
+    responses((status = 200, body = Vec<SettingValue>))
+)]
+pub async fn list_settings(
+    State(pool): State<MySqlPool>,
+) -> Result<Json<Vec<SettingValue>>, AppError> {
+    Ok(Json(SettingsService::list(&pool).await?))
+}
+
+/// 修改设置
+#[utoipa::path(
+    put,
+    path = "/api/settings/{name}",
+    tag = "settings",
+    params(("name" = String, Path)),
+    request_body = UpdateSettingRequest,
+    responses((status = 200, body = Vec<SettingValue>))
+)]
+pub async fn update_setting(
+    State(pool): State<MySqlPool>,
+    _: Admin,
+    Path(name): Path<String>,
+    Json(request): Json<UpdateSettingRequest>,
+) -> Result<Json<Vec<SettingValue>>, AppError> {
+    SettingsService::set(&pool, &name, &request.value).await?;
+    Ok(Json(SettingsService::list(&pool).await?))
+}
+
+/// 删除设置，恢复为环境变量或内置默认值
+#[utoipa::path(
+    delete,
+    path = "/api/settings/{name}",
+    tag = "settings",
+    params(("name" = String, Path)),
+    responses((status = 200, body = Vec<SettingValue>))
+)]
+pub async fn reset_setting(
+    State(pool): State<MySqlPool>,
+    _: Admin,
+    Path(name): Path<String>,
+) -> Result<Json<Vec<SettingValue>>, AppError> {
+    SettingsService::reset(&pool, &name).await?;
+    Ok(Json(SettingsService::list(&pool).await?))
+}
@@ -0,0 +1,40 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use sqlx::MySqlPool;
+
+use super::connection::AppError;
+use crate::models::{PipelineSla, SlaBreach, SlaBreachQuery};
+use crate::services::SlaService;
+
+/// 各管道 7 天 / 30 天可用率
+#[utoipa::path(
+    get,
+    path = "/api/sla",
+    tag = "sla",
+    responses((status = 200, body = Vec<PipelineSla>))
+)]
+pub async fn get_sla_report(
+    State(pool): State<MySqlPool>,
+) -> Result<Json<Vec<PipelineSla>>, AppError> {
+    let report = SlaService::report(&pool).await?;
+    Ok(Json(report))
+}
+
+/// SLA 违约历史
+#[utoipa::path(
+    get,
+    path = "/api/sla/breaches",
+    tag = "sla",
+    params(SlaBreachQuery),
+    responses((status = 200, body = Vec<SlaBreach>))
+)]
+pub async fn get_sla_breaches(
+    State(pool): State<MySqlPool>,
+    Query(params): Query<SlaBreachQuery>,
+) -> Result<Json<Vec<SlaBreach>>, AppError> {
+    let breaches =
+        SlaService::breaches(&pool, params.days.unwrap_or(7), params.target.as_deref()).await?;
+    Ok(Json(breaches))
+}
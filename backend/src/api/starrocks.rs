@@ -0,0 +1,323 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use mysql_async::Row;
+use mysql_async::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::connection::AppError;
+use crate::db::ConfigRepository;
+use crate::generators::StarRocksDDLGenerator;
+use crate::models::{PageQuery, PaginatedResponse};
+use crate::services::{ConnectionService, MetadataService};
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SrDatabaseQuery {
+    pub config_id: i64,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SrTableQuery {
+    pub config_id: i64,
+    pub database: String,
+    pub search: Option<String>,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SrTableDetailQuery {
+    pub config_id: i64,
+    pub database: String,
+    pub table: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct SrTable {
+    pub name: String,
+    pub table_type: Option<String>,
+    pub engine: Option<String>,
+    pub table_rows: Option<String>,
+    pub create_time: Option<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct SrColumn {
+    pub name: String,
+    pub data_type: String,
+    pub is_nullable: bool,
+    /// 排序键 / 主键列
+    pub is_key: bool,
+    pub default_value: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// StarRocks 表结构，包括 SHOW CREATE TABLE 的完整建表语句
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct SrTableSchema {
+    pub database: String,
+    pub table: String,
+    pub columns: Vec<SrColumn>,
+    pub create_table: String,
+}
+
+/// SHOW PARTITIONS 的一行，未分区的表只有一个与表同名的分区
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct SrPartition {
+    pub name: String,
+    pub state: Option<String>,
+    pub partition_key: Option<String>,
+    /// 范围分区的区间或列表分区的取值
+    pub range: Option<String>,
+    pub distribution_key: Option<String>,
+    pub buckets: Option<String>,
+    pub data_size: Option<String>,
+    pub row_count: Option<String>,
+}
+
+/// 连接 StarRocks / Doris
+async fn get_sr_conn(
+    pool: &sqlx::MySqlPool,
+    config_id: i64,
+) -> Result<mysql_async::Conn, AppError> {
+    let config = ConfigRepository::new(pool).find_by_id(config_id).await?;
+    if !config.db_type.is_sink_target() {
+        return Err(crate::utils::error::AppError::InvalidInput(format!(
+            "Connection {} is not a StarRocks or Doris connection",
+            config.name
+        ))
+        .into());
+    }
+    let config = ConnectionService::resolve_config(config).await?;
+    Ok(ConnectionService::connect_starrocks(&config).await?)
+}
+
+/// 执行查询并断开连接
+async fn sr_query(mut conn: mysql_async::Conn, sql: &str) -> Result<Vec<Row>, AppError> {
+    let result = conn.query::<Row, _>(sql).await;
+    let _ = conn.disconnect().await;
+    Ok(result.map_err(|e| {
+        crate::utils::error::AppError::Unknown(format!("StarRocks query failed: {}", e))
+    })?)
+}
+
+/// 按列名读取文本值，列不存在或为 NULL 时返回 None
+fn text(row: &Row, column: &str) -> Option<String> {
+    let index = row
+        .columns_ref()
+        .iter()
+        .position(|c| c.name_str().eq_ignore_ascii_case(column))?;
+    MetadataService::sr_value_text(row.as_ref(index)?)
+}
+
+/// LIKE 子串匹配的字符串字面量，搜索词中的通配符按普通字符匹配
+fn like_literal(search: &str) -> String {
+    let escaped = search
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    StarRocksDDLGenerator::quote_string(&format!("%{}%", escaped))
+}
+
+/// 列出数据库
+#[utoipa::path(
+    get,
+    path = "/api/starrocks/databases",
+    operation_id = "starrocks_list_databases",
+    tag = "starrocks",
+    params(SrDatabaseQuery),
+    responses((status = 200, body = Vec<String>))
+)]
+pub async fn list_databases(
+    State(pool): State<sqlx::MySqlPool>,
+    Query(params): Query<SrDatabaseQuery>,
+) -> Result<Json<Vec<String>>, AppError> {
+    let conn = get_sr_conn(&pool, params.config_id).await?;
+    let databases = sr_query(conn, "SHOW DATABASES")
+        .await?
+        .iter()
+        .filter_map(|row| text(row, "Database"))
+        .collect();
+    Ok(Json(databases))
+}
+
+/// 列出数据库中的表（分页，支持按名称搜索）
+#[utoipa::path(
+    get,
+    path = "/api/starrocks/tables",
+    operation_id = "starrocks_list_tables",
+    tag = "starrocks",
+    params(SrTableQuery, PageQuery),
+    responses((status = 200, body = PaginatedResponse<SrTable>))
+)]
+pub async fn list_tables(
+    State(pool): State<sqlx::MySqlPool>,
+    Query(params): Query<SrTableQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Json<PaginatedResponse<SrTable>>, AppError> {
+    let limit = page.get_limit();
+    let offset = page.get_offset();
+
+    // StarRocks 旧版本不支持服务端预处理语句，条件中的值直接转义为字面量
+    let mut where_clause = format!(
+        "WHERE TABLE_SCHEMA = {}",
+        StarRocksDDLGenerator::quote_string(&params.database)
+    );
+    if let Some(search) = params.search.as_deref().filter(|s| !s.is_empty()) {
+        where_clause.push_str(&format!(" AND TABLE_NAME LIKE {}", like_literal(search)));
+    }
+
+    let mut conn = get_sr_conn(&pool, params.config_id).await?;
+    let count: Option<i64> = conn
+        .query_first(format!(
+            "SELECT COUNT(*) FROM information_schema.tables {}",
+            where_clause
+        ))
+        .await
+        .map_err(|e| {
+            crate::utils::error::AppError::Unknown(format!("StarRocks query failed: {}", e))
+        })?;
+
+    let query_str = format!(
+        "SELECT TABLE_NAME, TABLE_TYPE, ENGINE, TABLE_ROWS, CREATE_TIME, TABLE_COMMENT
+         FROM information_schema.tables
+         {}
+         ORDER BY TABLE_NAME
+         LIMIT {} OFFSET {}",
+        where_clause, limit, offset
+    );
+    let tables = sr_query(conn, &query_str)
+        .await?
+        .iter()
+        .map(|row| SrTable {
+            name: text(row, "TABLE_NAME").unwrap_or_default(),
+            table_type: text(row, "TABLE_TYPE"),
+            engine: text(row, "ENGINE"),
+            table_rows: text(row, "TABLE_ROWS"),
+            create_time: text(row, "CREATE_TIME"),
+            comment: text(row, "TABLE_COMMENT").filter(|c| !c.is_empty()),
+        })
+        .collect();
+
+    Ok(Json(PaginatedResponse::new(
+        tables,
+        count.unwrap_or(0),
+        limit,
+        offset,
+    )))
+}
+
+/// 获取表结构和建表语句
+#[utoipa::path(
+    get,
+    path = "/api/starrocks/tables/schema",
+    tag = "starrocks",
+    params(SrTableDetailQuery),
+    responses((status = 200, body = SrTableSchema))
+)]
+pub async fn get_table_schema(
+    State(pool): State<sqlx::MySqlPool>,
+    Query(params): Query<SrTableDetailQuery>,
+) -> Result<Json<SrTableSchema>, AppError> {
+    let mut conn = get_sr_conn(&pool, params.config_id).await?;
+
+    let create_sql = format!(
+        "SHOW CREATE TABLE {}.{}",
+        StarRocksDDLGenerator::quote_identifier(&params.database),
+        StarRocksDDLGenerator::quote_identifier(&params.table)
+    );
+    let create_row: Option<Row> = conn.query_first(create_sql).await.map_err(|e| {
+        crate::utils::error::AppError::Unknown(format!("StarRocks query failed: {}", e))
+    })?;
+    let Some(create_table) = create_row
+        .as_ref()
+        .and_then(|row| text(row, "Create Table").or_else(|| text(row, "Create View")))
+    else {
+        let _ = conn.disconnect().await;
+        return Err(crate::utils::error::AppError::NotFound(format!(
+            "Table {}.{} not found",
+            params.database, params.table
+        ))
+        .into());
+    };
+
+    let columns_sql = format!(
+        "SELECT COLUMN_NAME, COLUMN_TYPE, IS_NULLABLE, COLUMN_KEY, COLUMN_DEFAULT, COLUMN_COMMENT
+         FROM information_schema.columns
+         WHERE TABLE_SCHEMA = {} AND TABLE_NAME = {}
+         ORDER BY ORDINAL_POSITION",
+        StarRocksDDLGenerator::quote_string(&params.database),
+        StarRocksDDLGenerator::quote_string(&params.table)
+    );
+    let columns = sr_query(conn, &columns_sql)
+        .await?
+        .iter()
+        .map(|row| SrColumn {
+            name: text(row, "COLUMN_NAME").unwrap_or_default(),
+            data_type: text(row, "COLUMN_TYPE").unwrap_or_default(),
+            is_nullable: text(row, "IS_NULLABLE").is_some_and(|v| v.eq_ignore_ascii_case("YES")),
+            is_key: text(row, "COLUMN_KEY").is_some_and(|v| !v.is_empty()),
+            default_value: text(row, "COLUMN_DEFAULT"),
+            comment: text(row, "COLUMN_COMMENT").filter(|c| !c.is_empty()),
+        })
+        .collect();
+
+    Ok(Json(SrTableSchema {
+        database: params.database,
+        table: params.table,
+        columns,
+        create_table,
+    }))
+}
+
+/// 获取表的分区
+#[utoipa::path(
+    get,
+    path = "/api/starrocks/tables/partitions",
+    tag = "starrocks",
+    params(SrTableDetailQuery),
+    responses((status = 200, body = Vec<SrPartition>))
+)]
+pub async fn list_partitions(
+    State(pool): State<sqlx::MySqlPool>,
+    Query(params): Query<SrTableDetailQuery>,
+) -> Result<Json<Vec<SrPartition>>, AppError> {
+    let conn = get_sr_conn(&pool, params.config_id).await?;
+    let sql = format!(
+        "SHOW PARTITIONS FROM {}.{}",
+        StarRocksDDLGenerator::quote_identifier(&params.database),
+        StarRocksDDLGenerator::quote_identifier(&params.table)
+    );
+    let partitions = sr_query(conn, &sql)
+        .await?
+        .iter()
+        .map(|row| SrPartition {
+            name: text(row, "PartitionName").unwrap_or_default(),
+            state: text(row, "State"),
+            partition_key: text(row, "PartitionKey").filter(|k| !k.is_empty()),
+            range: text(row, "Range").or_else(|| text(row, "List")),
+            distribution_key: text(row, "DistributionKey"),
+            buckets: text(row, "Buckets"),
+            data_size: text(row, "DataSize"),
+            row_count: text(row, "RowCount"),
+        })
+        .collect();
+    Ok(Json(partitions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_like_literal() {
+        assert_eq!(like_literal("orders"), "'%orders%'");
+        // 通配符按普通字符匹配，引号和反斜杠转义
+        assert_eq!(like_literal("a_b%"), "'%a\\\\_b\\\\%%'");
+        assert_eq!(like_literal("it's"), "'%it''s%'");
+        assert_eq!(like_literal("a\\b"), "'%a\\\\\\\\b%'");
+    }
+}
@@ -0,0 +1,50 @@
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+};
+use sqlx::MySqlPool;
+
+use super::connection::AppError;
+use crate::services::StatusService;
+
+/// 公开状态页（HTML）
+#[utoipa::path(
+    get,
+    path = "/status",
+    tag = "status",
+    security(()),
+    responses(
+        (status = 200, description = "状态页 HTML", content_type = "text/html", body = String),
+        (status = 404, description = "未开启状态页")
+    )
+)]
+pub async fn status_page(State(pool): State<MySqlPool>) -> Result<Response, AppError> {
+    if !StatusService::is_enabled() {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    let summary = StatusService::summarize(&pool).await?;
+    Ok(Html(StatusService::render_html(&summary)).into_response())
+}
+
+/// 公开状态页（JSON）
+#[utoipa::path(
+    get,
+    path = "/status.json",
+    tag = "status",
+    security(()),
+    responses(
+        (status = 200, body = crate::models::StatusSummary),
+        (status = 404, description = "未开启状态页")
+    )
+)]
+pub async fn status_json(State(pool): State<MySqlPool>) -> Result<Response, AppError> {
+    if !StatusService::is_enabled() {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    let summary = StatusService::summarize(&pool).await?;
+    Ok(Json(summary).into_response())
+}
@@ -1,69 +1,458 @@
-use axum::{extract::{Path, State}, Json};
+use axum::{extract::{Path, Query, State}, http::HeaderMap, Json};
 use sqlx::MySqlPool;
 use serde_json::json;
+use std::collections::{HashMap, HashSet};
 
-use crate::models::{SyncRequest, SyncTask};
-use crate::services::SyncEngine;
+use crate::models::{
+    BatchSyncQuery, ClonePipelineRequest, DdlPreviewQuery, MAX_RETRY_BATCH, PipelineCleanupRequest,
+    PipelineCleanupResult, RetriedTask, RetryBatchRequest, RetryBatchResult, SyncProgress,
+    SyncQuery, SyncRequest, SyncTask, TableDdlPreview, TaskFilter, TaskStatus,
+    TaskTeardownRequest, TaskTeardownResult,
+};
+use crate::services::{ApprovalPolicy, ApprovalService, PipelineCleanupService, SyncEngine};
+use crate::utils::naming::TableNameTemplate;
+use super::approval::{current_user, request_user};
+use super::auth::Operator;
 use super::connection::AppError;
 
+/// 提交同步任务，需要审批时只创建审批单，返回 approval_id
+/// dedupe 为 true 且相同的请求已在等待或执行时返回已有任务（`deduplicated: true`）
+pub(super) async fn submit(
+    pool: MySqlPool,
+    headers: &HeaderMap,
+    requests: Vec<SyncRequest>,
+    dedupe: bool,
+) -> Result<serde_json::Value, AppError> {
+    let engine = SyncEngine::new(pool.clone());
+    if dedupe && let Some(task_id) = engine.find_duplicate(&requests).await? {
+        tracing::info!("Same sync request is already in task {}, not submitting again", task_id);
+        return Ok(json!({ "task_id": task_id, "deduplicated": true }));
+    }
+
+    if let Some(reason) = ApprovalPolicy::from_env().reason(&requests) {
+        let user = current_user(headers)?;
+        let approval = ApprovalService::request(&pool, &requests, &reason, &user).await?;
+        return Ok(json!({
+            "approval_required": true,
+            "approval_id": approval.id,
+            "reason": approval.reason,
+        }));
+    }
+
+    let task_id = engine.sync_multiple_tables(requests).await?;
+    set_created_by(&pool, task_id, headers).await?;
+    Ok(json!({ "task_id": task_id }))
+}
+
+/// 记录任务的提交人，用于审计日志
+pub(super) async fn set_created_by(
+    pool: &MySqlPool,
+    task_id: i64,
+    headers: &HeaderMap,
+) -> Result<(), AppError> {
+    if let Some(user) = request_user(headers) {
+        crate::db::TaskRepository::new(pool)
+            .set_created_by(task_id, &user)
+            .await?;
+    }
+    Ok(())
+}
+
 /// 同步单个表
+#[utoipa::path(
+    post,
+    path = "/api/sync/single",
+    tag = "sync",
+    params(SyncQuery),
+    request_body = SyncRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn sync_single_table(
     State(pool): State<MySqlPool>,
+    _: Operator,
+    Query(params): Query<SyncQuery>,
+    headers: HeaderMap,
     Json(request): Json<SyncRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    let engine = SyncEngine::new(pool);
-    let task_id = engine.sync_table(request).await?;
-    Ok(Json(json!({ "task_id": task_id })))
+    Ok(Json(submit(pool, &headers, vec![request], params.dedupe).await?))
 }
 
 /// 同步多个表
+/// 传入 target_table_template 时按模板生成所有目标表名
+#[utoipa::path(
+    post,
+    path = "/api/sync/multiple",
+    tag = "sync",
+    params(BatchSyncQuery),
+    request_body = Vec<SyncRequest>,
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn sync_multiple_tables(
     State(pool): State<MySqlPool>,
-    Json(requests): Json<Vec<SyncRequest>>,
+    _: Operator,
+    Query(params): Query<BatchSyncQuery>,
+    headers: HeaderMap,
+    Json(mut requests): Json<Vec<SyncRequest>>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     if requests.is_empty() {
         return Err(AppError(crate::utils::error::AppError::Validation("No tables to sync".to_string())));
     }
+    if let Some(template) = params.target_table_template.as_deref().filter(|t| !t.is_empty()) {
+        TableNameTemplate::parse(template)?.apply(&mut requests)?;
+    }
 
+    Ok(Json(submit(pool, &headers, requests, params.dedupe).await?))
+}
+
+/// 预览同步将执行的 DDL，不创建任务
+/// 配置了预发 RisingWave 连接时在 staging_validation 中返回试运行结果
+#[utoipa::path(
+    post,
+    path = "/api/sync/preview",
+    tag = "sync",
+    params(DdlPreviewQuery),
+    request_body = Vec<SyncRequest>,
+    responses((status = 200, body = Vec<TableDdlPreview>))
+)]
+pub async fn preview_sync(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Query(params): Query<DdlPreviewQuery>,
+    Json(requests): Json<Vec<SyncRequest>>,
+) -> Result<Json<Vec<TableDdlPreview>>, AppError> {
     let engine = SyncEngine::new(pool);
-    let task_id = engine.sync_multiple_tables(requests).await?;
-    Ok(Json(json!({ "task_id": task_id })))
+    Ok(Json(
+        engine
+            .preview_ddl(&requests, params.staging_rw_config_id)
+            .await?,
+    ))
 }
 
-/// 获取同步进度
+/// 获取同步进度，按已完成的步骤计算百分比
+#[utoipa::path(
+    get,
+    path = "/api/sync/progress/{id}",
+    tag = "sync",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = SyncProgress))
+)]
 pub async fn get_progress(
     State(pool): State<MySqlPool>,
     Path(id): Path<i64>,
-) -> Result<Json<SyncTask>, AppError> {
+) -> Result<Json<SyncProgress>, AppError> {
     use crate::db::TaskRepository;
     let repo = TaskRepository::new(&pool);
     let task = repo.find_by_id(id).await?;
-    Ok(Json(task))
+    let steps = repo.get_steps(id).await?;
+    let mut progress = SyncProgress::new(&task, steps);
+    if let Some(outcomes) = repo.find_table_outcomes(id).await? {
+        progress.tables = serde_json::from_str(&outcomes)?;
+    }
+    Ok(Json(progress))
 }
 
-/// 重试失败的任务
+/// 重试失败的任务，创建 sink 的任务重新创建记录的 sink
+#[utoipa::path(
+    post,
+    path = "/api/sync/retry/{id}",
+    tag = "sync",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn retry_task(
     State(pool): State<MySqlPool>,
+    _: Operator,
     Path(id): Path<i64>,
+    headers: HeaderMap,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    use crate::db::TaskRepository;
+    let task = TaskRepository::new(&pool).find_by_id(id).await?;
+    Ok(Json(retry(pool, task, &headers).await?))
+}
+
+/// 批量重试失败任务：逐个按单个重试的方式提交（需要审批的只创建审批单）
+/// 同一任务多次失败时只重试第一个，返回原任务 id 到新任务 id 的映射
+#[utoipa::path(
+    post,
+    path = "/api/tasks/retry-batch",
+    tag = "sync",
+    request_body = RetryBatchRequest,
+    responses((status = 200, body = RetryBatchResult))
+)]
+pub async fn retry_batch(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<RetryBatchRequest>,
+) -> Result<Json<RetryBatchResult>, AppError> {
     use crate::db::TaskRepository;
     let repo = TaskRepository::new(&pool);
-    let task = repo.find_by_id(id).await?;
+    let mut tasks = Vec::new();
+    for id in &request.task_ids {
+        tasks.push(repo.find_by_id(*id).await?);
+    }
+    if let Some(hours) = request.failed_within_hours {
+        if hours <= 0 {
+            return Err(validation("failed_within_hours must be positive"));
+        }
+        let filter = TaskFilter {
+            status: Some(TaskStatus::Failed),
+            started_after: Some(chrono::Utc::now() - chrono::Duration::hours(hours)),
+            ..Default::default()
+        };
+        tasks.extend(
+            repo.find_history(&filter, MAX_RETRY_BATCH as i64 + 1, 0)
+                .await?,
+        );
+    } else if tasks.is_empty() {
+        return Err(validation("task_ids or failed_within_hours is required"));
+    }
+    if tasks.len() > MAX_RETRY_BATCH {
+        return Err(validation(&format!(
+            "At most {} tasks can be retried at once",
+            MAX_RETRY_BATCH
+        )));
+    }
 
-    // 创建新的同步请求
-    let request = SyncRequest {
-        mysql_config_id: task.mysql_config_id,
-        rw_config_id: task.rw_config_id,
-        sr_config_id: task.sr_config_id,
-        mysql_database: task.mysql_database,
-        mysql_table: task.mysql_table,
-        target_database: task.target_database,
-        target_table: task.target_table,
-        options: serde_json::from_str(&task.options).unwrap_or_default(),
+    let mut result = RetryBatchResult {
+        mapping: Default::default(),
+        results: vec![],
     };
+    let mut seen_ids = HashSet::new();
+    let mut seen_keys = HashMap::new();
+    for task in tasks {
+        if !seen_ids.insert(task.id) {
+            continue;
+        }
+        let task_id = task.id;
+        let outcome = if !matches!(task.status, TaskStatus::Failed | TaskStatus::Interrupted) {
+            Err(format!(
+                "Task is {}, only failed or interrupted tasks can be retried",
+                task.status.as_str()
+            ))
+        } else if let Some(first) = seen_keys.get(&task.retry_key()) {
+            Err(format!("Same task as #{}, retried once", first))
+        } else {
+            seen_keys.insert(task.retry_key(), task_id);
+            retry(pool.clone(), task, &headers)
+                .await
+                .map_err(|e| e.0.to_string())
+        };
 
-    let engine = SyncEngine::new(pool);
-    let new_task_id = engine.sync_table(request).await?;
+        let retried = match outcome {
+            Ok(value) => RetriedTask {
+                task_id,
+                new_task_id: value["task_id"].as_i64(),
+                approval_id: value["approval_id"].as_i64(),
+                error: None,
+            },
+            Err(error) => RetriedTask {
+                task_id,
+                new_task_id: None,
+                approval_id: None,
+                error: Some(error),
+            },
+        };
+        if let Some(new_task_id) = retried.new_task_id {
+            result.mapping.insert(task_id, new_task_id);
+        }
+        result.results.push(retried);
+    }
+
+    tracing::info!(
+        "Retried {} of {} tasks in batch",
+        result.mapping.len(),
+        result.results.len()
+    );
+    Ok(Json(result))
+}
 
-    Ok(Json(json!({ "task_id": new_task_id })))
+fn validation(message: &str) -> AppError {
+    AppError(crate::utils::error::AppError::Validation(message.to_string()))
+}
+
+/// 按任务记录的请求重新提交：创建 sink 的任务重新创建 sink，同步任务重新提交同步
+async fn retry(
+    pool: MySqlPool,
+    task: SyncTask,
+    headers: &HeaderMap,
+) -> Result<serde_json::Value, AppError> {
+    if task.is_sink_task() {
+        return super::risingwave::retry_sink_task(&pool, &task, headers).await;
+    }
+    if task.is_cleanup_task() {
+        return Err(AppError(crate::utils::error::AppError::Validation(
+            "Cleanup tasks cannot be retried, submit a new cleanup instead".to_string(),
+        )));
+    }
+
+    let requests = retry_requests(&task)?;
+    submit(pool, headers, requests, false).await
+}
+
+/// 重试时重新提交的同步请求，使用任务记录的原始请求（保留行过滤、分区、列映射等配置）
+fn retry_requests(task: &SyncTask) -> crate::utils::error::Result<Vec<SyncRequest>> {
+    let requests = task.sync_requests()?;
+    if requests.is_empty() {
+        return Err(crate::utils::error::AppError::Validation(format!(
+            "Task {} does not record its table list, submit a new sync instead",
+            task.id
+        )));
+    }
+    Ok(requests)
+}
+
+/// 克隆已有任务的同步定义到其他目标库或集群
+/// execute 为 false 时只返回新名称下生成的 DDL 预览
+#[utoipa::path(
+    post,
+    path = "/api/sync/clone/{id}",
+    tag = "sync",
+    params(("id" = i64, Path)),
+    request_body = ClonePipelineRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn clone_pipeline(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+    Json(request): Json<ClonePipelineRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    use crate::db::TaskRepository;
+    let repo = TaskRepository::new(&pool);
+    let task = repo.find_by_id(id).await?;
+
+    let requests: Vec<SyncRequest> = task
+        .sync_requests()?
+        .iter()
+        .map(|r| r.retarget(&request))
+        .collect();
+    if requests.is_empty() {
+        return Err(AppError(crate::utils::error::AppError::Validation(format!(
+            "Task {} does not record its table list",
+            id
+        ))));
+    }
+
+    if request.execute {
+        let mut result = submit(pool, &headers, requests.clone(), false).await?;
+        result["requests"] = json!(requests);
+        Ok(Json(result))
+    } else {
+        let engine = SyncEngine::new(pool);
+        let preview = engine
+            .preview_ddl(&requests, request.staging_rw_config_id)
+            .await?;
+        Ok(Json(json!({ "requests": requests, "preview": preview })))
+    }
+}
+
+/// 拆除同步任务创建的管道：RisingWave sink、表，可选的 CDC Source，以及 StarRocks 目标表
+/// 需要在 confirm 中输入任务 ID，dry_run 时只返回将执行的语句
+#[utoipa::path(
+    post,
+    path = "/api/sync/{task_id}/teardown",
+    tag = "sync",
+    params(("task_id" = i64, Path)),
+    request_body = TaskTeardownRequest,
+    responses((status = 200, body = TaskTeardownResult))
+)]
+pub async fn teardown_task(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Path(task_id): Path<i64>,
+    Json(request): Json<TaskTeardownRequest>,
+) -> Result<Json<TaskTeardownResult>, AppError> {
+    let result = PipelineCleanupService::teardown(&pool, task_id, &request).await?;
+    if let Some(task_id) = result.task_id {
+        set_created_by(&pool, task_id, &headers).await?;
+    }
+    Ok(Json(result))
+}
+
+/// 清理已下线管道的目标对象（RisingWave sink / 表和 StarRocks 目标表）
+/// 需要在 confirm 中输入 `database.table`，dry_run 时只返回将执行的语句
+#[utoipa::path(
+    post,
+    path = "/api/pipelines/cleanup",
+    tag = "sync",
+    request_body = PipelineCleanupRequest,
+    responses((status = 200, body = PipelineCleanupResult))
+)]
+pub async fn cleanup_pipeline(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<PipelineCleanupRequest>,
+) -> Result<Json<PipelineCleanupResult>, AppError> {
+    let result = PipelineCleanupService::cleanup(&pool, &request).await?;
+    if let Some(task_id) = result.task_id {
+        set_created_by(&pool, task_id, &headers).await?;
+    }
+    Ok(Json(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{PartitionGranularity, StarRocksPartition};
+
+    fn task(requests: Option<Vec<SyncRequest>>, mysql_table: &str) -> SyncTask {
+        SyncTask {
+            id: 7,
+            task_name: "t".to_string(),
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: mysql_table.to_string(),
+            target_database: "ods".to_string(),
+            target_table: "orders".to_string(),
+            status: TaskStatus::Failed,
+            started_at: chrono::Utc::now(),
+            completed_at: None,
+            error_message: None,
+            options: "{}".to_string(),
+            preflight_result: None,
+            requests: requests.map(|r| serde_json::to_string(&r).unwrap()),
+            sink_requests: None,
+        }
+    }
+
+    #[test]
+    fn test_retry_preserves_request() {
+        let request = SyncRequest {
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: "orders".to_string(),
+            target_database: "ods".to_string(),
+            target_table: "orders".to_string(),
+            options: Default::default(),
+            row_filter: Some("deleted_at IS NULL".to_string()),
+            partition: Some(StarRocksPartition::Expression {
+                column: "created_at".to_string(),
+                granularity: PartitionGranularity::Day,
+                derived_column: None,
+            }),
+            column_mapping: [("amt".to_string(), "amount".to_string())].into(),
+            nested_columns: Default::default(),
+            spatial_columns: Default::default(),
+        };
+
+        let requests = retry_requests(&task(Some(vec![request.clone()]), "orders")).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].row_filter, request.row_filter);
+        assert_eq!(requests[0].column_mapping, request.column_mapping);
+        assert!(matches!(
+            requests[0].partition,
+            Some(StarRocksPartition::Expression { ref column, .. }) if column == "created_at"
+        ));
+
+        // 没有记录请求的旧批量任务不能按任务列还原
+        assert!(retry_requests(&task(None, "[Batch: 3 tables]")).is_err());
+    }
 }
@@ -0,0 +1,201 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+use super::approval::request_user;
+use super::auth::Operator;
+use super::connection::{AppError, parse_if_match};
+use crate::db::SyncMappingRepository;
+use crate::models::{PipelinePauseResult, SyncMapping, SyncMappingRequest, SyncQuery};
+use crate::services::SyncMappingService;
+
+/// 列出同步映射
+#[utoipa::path(
+    get,
+    path = "/api/sync_mappings",
+    tag = "sync_mapping",
+    responses((status = 200, body = Vec<SyncMapping>))
+)]
+pub async fn list_mappings(
+    State(pool): State<MySqlPool>,
+) -> Result<Json<Vec<SyncMapping>>, AppError> {
+    Ok(Json(SyncMappingRepository::new(&pool).find_all().await?))
+}
+
+/// 获取同步映射
+#[utoipa::path(
+    get,
+    path = "/api/sync_mappings/{id}",
+    tag = "sync_mapping",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = SyncMapping))
+)]
+pub async fn get_mapping(
+    State(pool): State<MySqlPool>,
+    Path(id): Path<i64>,
+) -> Result<Json<SyncMapping>, AppError> {
+    Ok(Json(
+        SyncMappingRepository::new(&pool).find_by_id(id).await?,
+    ))
+}
+
+/// 创建同步映射，X-User 请求头记录为创建人
+#[utoipa::path(
+    post,
+    path = "/api/sync_mappings",
+    tag = "sync_mapping",
+    request_body = SyncMappingRequest,
+    responses((status = 200, body = SyncMapping))
+)]
+pub async fn create_mapping(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    headers: HeaderMap,
+    Json(request): Json<SyncMappingRequest>,
+) -> Result<Json<SyncMapping>, AppError> {
+    SyncMappingService::validate(&request)?;
+    let repo = SyncMappingRepository::new(&pool);
+    let id = repo
+        .create(&request, request_user(&headers).as_deref())
+        .await?;
+    Ok(Json(repo.find_by_id(id).await?))
+}
+
+/// 更新同步映射
+/// 通过 If-Match 头或请求体中的 version 做乐观锁检查，冲突时返回 409 和服务端当前的映射
+#[utoipa::path(
+    put,
+    path = "/api/sync_mappings/{id}",
+    tag = "sync_mapping",
+    params(("id" = i64, Path)),
+    request_body = SyncMappingRequest,
+    responses(
+        (status = 200, body = SyncMapping),
+        (status = 409, description = "If-Match 与当前版本不一致，返回 `error` 和 `current`", body = serde_json::Value)
+    )
+)]
+pub async fn update_mapping(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+    Json(mut request): Json<SyncMappingRequest>,
+) -> Result<Response, AppError> {
+    if let Some(version) = parse_if_match(&headers)? {
+        request.version = Some(version);
+    }
+    SyncMappingService::validate(&request)?;
+    let repo = SyncMappingRepository::new(&pool);
+    match repo.update(id, &request).await {
+        Ok(version) => Ok((
+            [(header::ETAG, format!("\"{}\"", version))],
+            Json(repo.find_by_id(id).await?),
+        )
+            .into_response()),
+        Err(crate::utils::error::AppError::Conflict(message)) => {
+            // 名称重复也返回 Conflict，只有版本不一致时才附带当前的映射
+            let current = repo.find_by_id(id).await?;
+            if request.version.is_none_or(|v| v == current.version) {
+                return Err(crate::utils::error::AppError::Conflict(message).into());
+            }
+            Ok((
+                StatusCode::CONFLICT,
+                Json(json!({ "error": message, "current": current })),
+            )
+                .into_response())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 删除同步映射，已提交的任务不受影响
+#[utoipa::path(
+    delete,
+    path = "/api/sync_mappings/{id}",
+    tag = "sync_mapping",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn delete_mapping(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    SyncMappingRepository::new(&pool).delete(id).await?;
+    Ok(Json(json!({ "success": true })))
+}
+
+/// 按映射的定义提交同步，返回值与 /api/sync/multiple 相同
+#[utoipa::path(
+    post,
+    path = "/api/sync_mappings/{id}/run",
+    tag = "sync_mapping",
+    params(("id" = i64, Path), SyncQuery),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn run_mapping(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+    Query(params): Query<SyncQuery>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let repo = SyncMappingRepository::new(&pool);
+    let mapping = repo.find_by_id(id).await?;
+    if mapping.paused {
+        return Err(crate::utils::error::AppError::Conflict(format!(
+            "Sync mapping {} is paused, resume it before running",
+            mapping.name
+        ))
+        .into());
+    }
+    let requests = SyncMappingService::requests(&mapping.definition())?;
+
+    let mut result = super::sync::submit(pool.clone(), &headers, requests, params.dedupe).await?;
+    repo.record_run(id, result["task_id"].as_i64()).await?;
+    result["mapping_id"] = json!(id);
+    Ok(Json(result))
+}
+
+/// 暂停管道（id 为同步映射）：sink 停止写入、相关定时同步停用、映射标记为暂停
+#[utoipa::path(
+    post,
+    path = "/api/pipelines/{id}/pause",
+    tag = "sync_mapping",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = PipelinePauseResult))
+)]
+pub async fn pause_pipeline(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<Json<PipelinePauseResult>, AppError> {
+    Ok(Json(
+        SyncMappingService::pause(&pool, id, request_user(&headers).as_deref()).await?,
+    ))
+}
+
+/// 恢复暂停的管道
+#[utoipa::path(
+    post,
+    path = "/api/pipelines/{id}/resume",
+    tag = "sync_mapping",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = PipelinePauseResult))
+)]
+pub async fn resume_pipeline(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<Json<PipelinePauseResult>, AppError> {
+    Ok(Json(
+        SyncMappingService::resume(&pool, id, request_user(&headers).as_deref()).await?,
+    ))
+}
@@ -0,0 +1,43 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use sqlx::MySqlPool;
+
+use super::connection::AppError;
+use crate::models::{SyncMetric, SyncMetricQuery};
+use crate::services::SyncMetricsService;
+
+/// 已完成任务中各表当前的 MySQL / RisingWave / StarRocks 行数
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/row_counts",
+    tag = "sync_metrics",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = Vec<SyncMetric>))
+)]
+pub async fn get_task_row_counts(
+    State(pool): State<MySqlPool>,
+    Path(task_id): Path<i64>,
+) -> Result<Json<Vec<SyncMetric>>, AppError> {
+    let counts = SyncMetricsService::count_task(&pool, task_id).await?;
+    Ok(Json(counts))
+}
+
+/// 行数采样趋势
+#[utoipa::path(
+    get,
+    path = "/api/sync_metrics",
+    tag = "sync_metrics",
+    params(SyncMetricQuery),
+    responses((status = 200, body = Vec<SyncMetric>))
+)]
+pub async fn get_sync_metrics(
+    State(pool): State<MySqlPool>,
+    Query(params): Query<SyncMetricQuery>,
+) -> Result<Json<Vec<SyncMetric>>, AppError> {
+    let metrics =
+        SyncMetricsService::history(&pool, params.days.unwrap_or(7), params.target.as_deref())
+            .await?;
+    Ok(Json(metrics))
+}
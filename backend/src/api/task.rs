@@ -1,39 +1,228 @@
 use axum::{
     Json,
+    body::Body,
     extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
 };
 use serde_json::json;
 use sqlx::MySqlPool;
+use tokio_stream::wrappers::ReceiverStream;
 
+use super::auth::Operator;
 use super::connection::AppError;
 use crate::db::TaskRepository;
-use crate::models::{SyncTask, TaskLog, TaskStatus, HistoryQuery, PaginatedTasksResponse};
+use crate::models::{
+    CountMode, DEFAULT_TASK_LOG_LIMIT, ErrorStats, ErrorStatsQuery, HistoryQuery,
+    MAX_TASK_LOG_LIMIT, PaginatedTasksResponse, SyncTask, TaskCursor, TaskDailyStat,
+    TaskLogCursor, TaskLogPage, TaskLogQuery, TaskStatsQuery, TaskStatus,
+};
+use crate::services::ErrorStatsService;
+
+/// CSV 导出每次从数据库读取的任务数
+const EXPORT_PAGE_SIZE: i64 = 500;
+
+/// CSV 导出的列
+const EXPORT_CSV_HEADER: &str = "id,task_name,status,mysql_database,mysql_table,target_database,target_table,started_at,completed_at,duration_seconds,error_message\r\n";
 
 
 /// 获取任务历史
+/// 传入 cursor 时使用游标分页（大数据量下比 offset 快），count=approximate 时返回估算总数
+#[utoipa::path(
+    get,
+    path = "/api/tasks/history",
+    tag = "task",
+    params(HistoryQuery),
+    responses((status = 200, body = PaginatedTasksResponse))
+)]
 pub async fn get_history(
     State(pool): State<MySqlPool>,
     Query(params): Query<HistoryQuery>,
 ) -> Result<Json<PaginatedTasksResponse>, AppError> {
     let repo = TaskRepository::new(&pool);
 
-    // Map optional status string into an optional TaskStatus, discarding infallible errors
-    let status = params.status.and_then(|s| TaskStatus::try_from(s).ok());
+    // 无法识别的 status 会被忽略
+    let filter = params.filter();
     let limit = params.limit.unwrap_or(20);
-    let offset = params.offset.unwrap_or(0);
 
-    let tasks = repo.find_history(status.clone(), limit, offset).await?;
-    let total = repo.count_tasks(status).await?;
+    let (tasks, offset) = match params.cursor.as_deref().filter(|c| !c.is_empty()) {
+        Some(cursor) => {
+            let cursor = TaskCursor::decode(cursor).ok_or_else(|| {
+                AppError(crate::utils::error::AppError::InvalidInput(format!(
+                    "Invalid cursor: {}",
+                    cursor
+                )))
+            })?;
+            (repo.find_history_by_cursor(&filter, Some(&cursor), limit).await?, 0)
+        }
+        None => {
+            let offset = params.offset.unwrap_or(0);
+            (repo.find_history(&filter, limit, offset).await?, offset)
+        }
+    };
+
+    let (total, total_estimated) = match params.count {
+        CountMode::Exact => (repo.count_tasks(&filter).await?, false),
+        CountMode::Approximate => repo.estimate_tasks(&filter).await?,
+    };
+
+    let next_cursor = if tasks.len() as i64 == limit {
+        tasks.last().map(|t| TaskCursor::from_task(t).encode())
+    } else {
+        None
+    };
 
     Ok(Json(PaginatedTasksResponse {
         tasks,
         total,
+        total_estimated,
         limit,
         offset,
+        next_cursor,
     }))
 }
 
+/// 按历史查询的过滤条件导出任务（CSV）
+/// 按游标分批读取并流式写出，limit / offset / cursor 参数会被忽略
+#[utoipa::path(
+    get,
+    path = "/api/tasks/export",
+    tag = "task",
+    params(HistoryQuery),
+    responses((status = 200, description = "CSV 文件", content_type = "text/csv", body = String))
+)]
+pub async fn export_history(
+    State(pool): State<MySqlPool>,
+    Query(params): Query<HistoryQuery>,
+) -> Result<Response, AppError> {
+    let filter = params.filter();
+    let (tx, rx) = tokio::sync::mpsc::channel::<std::result::Result<String, std::io::Error>>(4);
+
+    tokio::spawn(async move {
+        let repo = TaskRepository::new(&pool);
+        if tx.send(Ok(EXPORT_CSV_HEADER.to_string())).await.is_err() {
+            return;
+        }
+
+        let mut cursor = None;
+        loop {
+            let tasks = match repo
+                .find_history_by_cursor(&filter, cursor.as_ref(), EXPORT_PAGE_SIZE)
+                .await
+            {
+                Ok(tasks) => tasks,
+                Err(e) => {
+                    tracing::error!("Failed to export task history: {}", e);
+                    let _ = tx.send(Err(std::io::Error::other(e.to_string()))).await;
+                    return;
+                }
+            };
+
+            let chunk: String = tasks.iter().map(csv_row).collect();
+            if tx.send(Ok(chunk)).await.is_err() {
+                return;
+            }
+            if (tasks.len() as i64) < EXPORT_PAGE_SIZE {
+                return;
+            }
+            cursor = tasks.last().map(TaskCursor::from_task);
+        }
+    });
+
+    let filename = format!(
+        "sync_tasks_{}.csv",
+        chrono::Utc::now().format("%Y%m%d%H%M%S")
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        Body::from_stream(ReceiverStream::new(rx)),
+    )
+        .into_response())
+}
+
+/// 任务的一行 CSV，时间为 RFC 3339，未结束的任务耗时为空
+fn csv_row(task: &SyncTask) -> String {
+    let duration = task
+        .completed_at
+        .map(|end| format!("{:.3}", (end - task.started_at).num_milliseconds() as f64 / 1000.0))
+        .unwrap_or_default();
+    let fields = [
+        task.id.to_string(),
+        csv_field(&task.task_name),
+        task.status.as_str().to_string(),
+        csv_field(&task.mysql_database),
+        csv_field(&task.mysql_table),
+        csv_field(&task.target_database),
+        csv_field(&task.target_table),
+        task.started_at.to_rfc3339(),
+        task.completed_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        duration,
+        csv_field(task.error_message.as_deref().unwrap_or_default()),
+    ];
+    format!("{}\r\n", fields.join(","))
+}
+
+/// 按 RFC 4180 转义字段；以 = + - @ 开头的值加前缀 '，避免在表格软件中被当作公式执行
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// 获取最近 N 天的任务每日统计
+#[utoipa::path(
+    get,
+    path = "/api/tasks/stats/daily",
+    tag = "task",
+    params(TaskStatsQuery),
+    responses((status = 200, body = Vec<TaskDailyStat>))
+)]
+pub async fn get_daily_stats(
+    State(pool): State<MySqlPool>,
+    Query(params): Query<TaskStatsQuery>,
+) -> Result<Json<Vec<TaskDailyStat>>, AppError> {
+    let days = params.days.unwrap_or(30).clamp(1, 366);
+    let since = (chrono::Utc::now() - chrono::Duration::days(days - 1)).date_naive();
+    let stats = TaskRepository::new(&pool).daily_stats_since(since).await?;
+    Ok(Json(stats))
+}
+
+/// 按错误分类统计最近的失败任务，包含每个分类的趋势
+#[utoipa::path(
+    get,
+    path = "/api/stats/errors",
+    tag = "task",
+    params(ErrorStatsQuery),
+    responses((status = 200, body = ErrorStats))
+)]
+pub async fn get_error_stats(
+    State(pool): State<MySqlPool>,
+    Query(params): Query<ErrorStatsQuery>,
+) -> Result<Json<ErrorStats>, AppError> {
+    Ok(Json(ErrorStatsService::summarize(&pool, &params).await?))
+}
+
 /// 获取任务详情
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}",
+    tag = "task",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = SyncTask))
+)]
 pub async fn get_detail(
     State(pool): State<MySqlPool>,
     Path(id): Path<i64>,
@@ -43,19 +232,86 @@ pub async fn get_detail(
     Ok(Json(task))
 }
 
-/// 获取任务日志
+/// 获取任务日志（分页），支持 limit / offset、cursor、tail=N 和最低级别过滤
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/logs",
+    tag = "task",
+    params(("id" = i64, Path), TaskLogQuery),
+    responses((status = 200, body = TaskLogPage))
+)]
 pub async fn get_logs(
     State(pool): State<MySqlPool>,
     Path(id): Path<i64>,
-) -> Result<Json<Vec<TaskLog>>, AppError> {
+    Query(params): Query<TaskLogQuery>,
+) -> Result<Json<TaskLogPage>, AppError> {
     let repo = TaskRepository::new(&pool);
-    let logs = repo.get_logs(id).await?;
-    Ok(Json(logs))
+    let levels = params.levels().ok_or_else(|| {
+        AppError(crate::utils::error::AppError::InvalidInput(format!(
+            "Invalid log level '{}' (use debug, info, warn or error)",
+            params.level.as_deref().unwrap_or_default()
+        )))
+    })?;
+    let total = repo.count_logs(id, &levels).await?;
+
+    if let Some(tail) = params.tail {
+        let limit = tail.clamp(1, MAX_TASK_LOG_LIMIT);
+        let logs = repo.tail_logs(id, &levels, limit).await?;
+        let next_cursor = logs.last().map(|log| TaskLogCursor::from_log(log).encode());
+        return Ok(Json(TaskLogPage {
+            offset: (total - logs.len() as i64).max(0),
+            logs,
+            total,
+            limit,
+            next_cursor,
+        }));
+    }
+
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_TASK_LOG_LIMIT)
+        .clamp(1, MAX_TASK_LOG_LIMIT);
+    let (logs, offset) = match params.cursor.as_deref().filter(|c| !c.is_empty()) {
+        Some(cursor) => {
+            let cursor = TaskLogCursor::decode(cursor).ok_or_else(|| {
+                AppError(crate::utils::error::AppError::InvalidInput(format!(
+                    "Invalid cursor: {}",
+                    cursor
+                )))
+            })?;
+            (repo.get_logs(id, &levels, Some(&cursor), limit, 0).await?, 0)
+        }
+        None => {
+            let offset = params.offset.unwrap_or(0).max(0);
+            (repo.get_logs(id, &levels, None, limit, offset).await?, offset)
+        }
+    };
+    let next_cursor = if logs.len() as i64 == limit {
+        logs.last().map(|log| TaskLogCursor::from_log(log).encode())
+    } else {
+        None
+    };
+
+    Ok(Json(TaskLogPage {
+        logs,
+        total,
+        limit,
+        offset,
+        next_cursor,
+    }))
 }
 
 /// 取消任务
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/cancel",
+    tag = "task",
+    params(("id" = i64, Path)),
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn cancel_task(
     State(pool): State<MySqlPool>,
+    _: Operator,
     Path(id): Path<i64>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // TODO: 实现任务取消逻辑
@@ -70,3 +326,40 @@ pub async fn cancel_task(
 
     Ok(Json(json!({ "success": true })))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_row() {
+        let started_at = chrono::DateTime::parse_from_rfc3339("2024-05-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let task = SyncTask {
+            id: 7,
+            task_name: "orders, daily".to_string(),
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: "orders".to_string(),
+            target_database: "dw".to_string(),
+            target_table: "orders".to_string(),
+            status: TaskStatus::Failed,
+            started_at,
+            completed_at: Some(started_at + chrono::Duration::milliseconds(1500)),
+            error_message: Some("Table \"orders\" not found".to_string()),
+            options: "{}".to_string(),
+            preflight_result: None,
+            requests: None,
+            sink_requests: None,
+        };
+
+        assert_eq!(
+            csv_row(&task),
+            "7,\"orders, daily\",failed,shop,orders,dw,orders,2024-05-01T10:00:00+00:00,2024-05-01T10:00:01.500+00:00,1.500,\"Table \"\"orders\"\" not found\"\r\n"
+        );
+        assert_eq!(csv_field("=1+1"), "'=1+1");
+    }
+}
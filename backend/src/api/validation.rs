@@ -0,0 +1,47 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use sqlx::MySqlPool;
+
+use super::auth::Operator;
+use super::connection::AppError;
+use crate::db::ValidationRepository;
+use crate::models::{ValidateQuery, ValidationResult};
+use crate::services::ValidationService;
+
+/// 对任务执行数据一致性校验
+#[utoipa::path(
+    post,
+    path = "/api/validate/{task_id}",
+    tag = "validation",
+    params(("task_id" = i64, Path), ValidateQuery),
+    responses((status = 200, body = Vec<ValidationResult>))
+)]
+pub async fn validate_task(
+    State(pool): State<MySqlPool>,
+    _: Operator,
+    Path(task_id): Path<i64>,
+    Query(params): Query<ValidateQuery>,
+) -> Result<Json<Vec<ValidationResult>>, AppError> {
+    let results =
+        ValidationService::validate_task(&pool, task_id, params.checksum.unwrap_or(false)).await?;
+    Ok(Json(results))
+}
+
+/// 获取任务的历史校验结果
+#[utoipa::path(
+    get,
+    path = "/api/validate/{task_id}",
+    tag = "validation",
+    params(("task_id" = i64, Path)),
+    responses((status = 200, body = Vec<ValidationResult>))
+)]
+pub async fn get_validation_results(
+    State(pool): State<MySqlPool>,
+    Path(task_id): Path<i64>,
+) -> Result<Json<Vec<ValidationResult>>, AppError> {
+    let repo = ValidationRepository::new(&pool);
+    let results = repo.find_by_task(task_id).await?;
+    Ok(Json(results))
+}
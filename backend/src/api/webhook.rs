@@ -1,12 +1,18 @@
+use crate::db::{AlertRepository, NotificationChannelRepository};
+use crate::models::{AlertStatus, NewAlert, Notification, NotificationEvent};
+use crate::services::{
+    AlertRouter, AlertRouting, IncidentService, NotificationChannel, NotificationService,
+};
 use axum::{extract::State, http::StatusCode, Json};
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use serde_json::json;
+use sha2::{Digest, Sha256};
 use sqlx::MySqlPool;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Alertmanager Webhook 请求体
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AlertmanagerWebhook {
     pub version: Option<String>,
@@ -21,7 +27,7 @@ pub struct AlertmanagerWebhook {
     pub alerts: Vec<Alert>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Alert {
     pub status: String,
@@ -33,20 +39,8 @@ pub struct Alert {
     pub fingerprint: Option<String>,
 }
 
-/// 飞书消息格式
-#[derive(Debug, Serialize)]
-pub struct LarkTextMessage {
-    pub msg_type: String,
-    pub content: LarkTextContent,
-}
-
-#[derive(Debug, Serialize)]
-pub struct LarkTextContent {
-    pub text: String,
-}
-
-/// 格式化告警消息为飞书文本格式
-fn format_alert_for_lark(alert: &Alert, status: &str) -> LarkTextMessage {
+/// 将告警转换为通知，标题包含状态和摘要，正文包含描述和详细信息
+fn alert_notification(alert: &Alert, status: &str) -> Notification {
     let labels = &alert.labels;
     let annotations = &alert.annotations;
 
@@ -106,11 +100,9 @@ fn format_alert_for_lark(alert: &Alert, status: &str) -> LarkTextMessage {
         _ => format!("• Job: {}\n• Instance: {}", job, instance),
     };
 
-    // 构建完整消息
-    let message = format!(
-        r#"{} {}
-
-{}
+    // 构建正文
+    let text = format!(
+        r#"{}
 
 详细信息:
 {}
@@ -118,8 +110,6 @@ fn format_alert_for_lark(alert: &Alert, status: &str) -> LarkTextMessage {
 • Component: {}
 • Time: {}
 • Alert: {}"#,
-        prefix,
-        summary,
         description,
         extra_info,
         severity.to_uppercase(),
@@ -128,36 +118,61 @@ fn format_alert_for_lark(alert: &Alert, status: &str) -> LarkTextMessage {
         alert_name
     );
 
-    LarkTextMessage {
-        msg_type: "text".to_string(),
-        content: LarkTextContent { text: message },
+    Notification {
+        event: NotificationEvent::Alert,
+        title: format!("{} {}", prefix, summary),
+        text,
+        fields: alert.labels.clone().into_iter().collect::<BTreeMap<_, _>>(),
     }
 }
 
-/// 发送消息到飞书
-async fn send_to_lark(
-    webhook_url: &str,
-    message: &LarkTextMessage,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let client = reqwest::Client::new();
-    let response = client
-        .post(webhook_url)
-        .json(message)
-        .timeout(std::time::Duration::from_secs(10))
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        let error_text = response.text().await?;
-        return Err(format!("Failed to send to Lark: {}", error_text).into());
-    }
+/// 解析 Alertmanager 的时间，未设置的时间（0001-01-01）返回 None
+fn parse_alert_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+        .filter(|dt| dt.timestamp() > 0)
+}
+
+/// 转换为告警历史记录；没有 fingerprint 时按标签计算
+fn new_alert(alert: &Alert, silenced: bool) -> NewAlert {
+    let labels: BTreeMap<String, String> = alert.labels.clone().into_iter().collect();
+    let fingerprint = alert
+        .fingerprint
+        .clone()
+        .filter(|f| !f.is_empty())
+        .unwrap_or_else(|| {
+            let digest = Sha256::digest(serde_json::to_string(&labels).unwrap_or_default());
+            digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
+        });
 
-    Ok(())
+    NewAlert {
+        fingerprint,
+        alertname: labels
+            .get("alertname")
+            .cloned()
+            .unwrap_or_else(|| "Unknown".to_string()),
+        status: AlertStatus::parse(&alert.status).unwrap_or(AlertStatus::Firing),
+        severity: labels.get("severity").cloned(),
+        annotations: alert.annotations.clone().into_iter().collect(),
+        starts_at: parse_alert_time(&alert.starts_at).unwrap_or_else(Utc::now),
+        ends_at: alert.ends_at.as_deref().and_then(parse_alert_time),
+        labels,
+        silenced,
+    }
 }
 
-/// Webhook 端点 - 接收 Alertmanager 告警
+/// Webhook 端点 - 接收 Alertmanager 告警，按告警路由规则发送到通知渠道
+/// 被静默的告警不发送；没有匹配规则的告警发送到所有订阅了告警的渠道
+#[utoipa::path(
+    post,
+    path = "/api/webhook/alertmanager",
+    tag = "webhook",
+    request_body = AlertmanagerWebhook,
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn receive_alertmanager_webhook(
-    State(_pool): State<MySqlPool>,
+    State(pool): State<MySqlPool>,
     Json(payload): Json<AlertmanagerWebhook>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     tracing::info!(
@@ -166,62 +181,158 @@ pub async fn receive_alertmanager_webhook(
         payload.status
     );
 
-    // 从环境变量获取飞书 webhook URL
-    let lark_webhook_url = std::env::var("LARK_WEBHOOK_URL").unwrap_or_else(|_| {
-        tracing::warn!("LARK_WEBHOOK_URL not set in environment");
-        String::new()
-    });
-
-    if lark_webhook_url.is_empty() {
-        tracing::warn!("Lark webhook URL not configured, skipping notification");
-        return Ok(Json(json!({
-            "status": "received",
-            "count": payload.alerts.len(),
-            "notification_sent": false,
-            "reason": "webhook_url_not_configured"
-        })));
-    }
+    let router = AlertRouter::load(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let now = Utc::now();
 
     let mut success_count = 0;
     let mut error_count = 0;
-
-    // 处理每个告警
+    let mut silenced_count = 0;
+    let mut channel_count = 0;
+    let alert_repo = AlertRepository::new(&pool);
+    let mut recorded = Vec::new();
     for alert in &payload.alerts {
-        // 转换为飞书格式
-        let lark_message = format_alert_for_lark(alert, &payload.status);
+        let routing = router.route(&alert.labels, now);
+        // 告警历史写入失败不影响通知发送
+        let record = new_alert(alert, matches!(routing, AlertRouting::Silenced(_)));
+        match alert_repo.record(&record).await {
+            Ok(id) => recorded.push((id, record.labels)),
+            Err(e) => tracing::warn!("Failed to record alert {}: {}", record.fingerprint, e),
+        }
 
-        // 发送到飞书
-        match send_to_lark(&lark_webhook_url, &lark_message).await {
-            Ok(_) => {
+        let notification = alert_notification(alert, &payload.status);
+        let results = match routing {
+            AlertRouting::Silenced(silence_id) => {
                 tracing::info!(
-                    "Successfully sent alert {} to Lark",
-                    alert.labels.get("alertname").unwrap_or(&"Unknown".to_string())
+                    "Alert {} silenced by silence {}",
+                    alert.labels.get("alertname").map(String::as_str).unwrap_or("Unknown"),
+                    silence_id
                 );
-                success_count += 1;
+                silenced_count += 1;
+                continue;
             }
-            Err(e) => {
-                tracing::error!("Failed to send alert to Lark: {}", e);
-                error_count += 1;
+            AlertRouting::Channels(channel_ids) => {
+                NotificationService::dispatch_to(&pool, &notification, &channel_ids).await
+            }
+            AlertRouting::Default => NotificationService::dispatch(&pool, &notification).await,
+        }
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        channel_count = channel_count.max(results.len());
+        for (channel, result) in results {
+            match result {
+                Ok(_) => {
+                    tracing::info!(
+                        "Successfully sent alert {} to {}",
+                        alert.labels.get("alertname").map(String::as_str).unwrap_or("Unknown"),
+                        channel
+                    );
+                    success_count += 1;
+                }
+                Err(_) => error_count += 1,
             }
         }
     }
 
+    // 关联到管道和任务，失败不影响通知发送
+    if let Err(e) = IncidentService::link_alerts(&pool, &recorded).await {
+        tracing::warn!("Failed to link alerts to pipelines: {}", e);
+    }
+
+    if silenced_count == payload.alerts.len() && !payload.alerts.is_empty() {
+        return Ok(Json(json!({
+            "status": "received",
+            "count": payload.alerts.len(),
+            "notification_sent": false,
+            "silenced_count": silenced_count,
+            "reason": "silenced"
+        })));
+    }
+
+    if channel_count == 0 && !payload.alerts.is_empty() {
+        tracing::warn!("No notification channel configured, skipping notification");
+        return Ok(Json(json!({
+            "status": "received",
+            "count": payload.alerts.len(),
+            "notification_sent": false,
+            "silenced_count": silenced_count,
+            "reason": "no_channel_configured"
+        })));
+    }
+
     Ok(Json(json!({
         "status": "received",
         "count": payload.alerts.len(),
         "notification_sent": true,
         "success_count": success_count,
-        "error_count": error_count
+        "error_count": error_count,
+        "silenced_count": silenced_count
     })))
 }
 
 /// 健康检查端点
-pub async fn webhook_health() -> Json<serde_json::Value> {
-    let lark_configured = std::env::var("LARK_WEBHOOK_URL").is_ok();
+#[utoipa::path(
+    get,
+    path = "/api/webhook/health",
+    tag = "webhook",
+    security(()),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn webhook_health(State(pool): State<MySqlPool>) -> Json<serde_json::Value> {
+    let lark_configured = NotificationChannel::lark_from_env().is_some();
+    let channels = NotificationChannelRepository::new(&pool)
+        .find_all()
+        .await
+        .map(|channels| channels.iter().filter(|c| c.subscribes(NotificationEvent::Alert)).count())
+        .unwrap_or(0);
 
     Json(json!({
         "status": "healthy",
         "service": "alertmanager-webhook",
-        "lark_configured": lark_configured
+        "lark_configured": lark_configured,
+        "alert_channels": channels
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_alert() {
+        let alert: Alert = serde_json::from_str(
+            r#"{
+                "status": "resolved",
+                "labels": {"alertname": "SinkLag", "severity": "warning"},
+                "annotations": {"summary": "lag"},
+                "startsAt": "2026-01-02T03:04:05Z",
+                "endsAt": "2026-01-02T04:00:00Z",
+                "fingerprint": "abc123"
+            }"#,
+        )
+        .unwrap();
+        let record = new_alert(&alert, false);
+        assert_eq!(record.fingerprint, "abc123");
+        assert_eq!(record.alertname, "SinkLag");
+        assert_eq!(record.status, AlertStatus::Resolved);
+        assert_eq!(record.severity.as_deref(), Some("warning"));
+        assert_eq!(record.starts_at.to_rfc3339(), "2026-01-02T03:04:05+00:00");
+        assert!(record.ends_at.is_some());
+
+        let alert: Alert = serde_json::from_str(
+            r#"{
+                "status": "firing",
+                "labels": {"alertname": "SinkLag"},
+                "annotations": {},
+                "startsAt": "2026-01-02T03:04:05Z",
+                "endsAt": "0001-01-01T00:00:00Z"
+            }"#,
+        )
+        .unwrap();
+        let record = new_alert(&alert, true);
+        assert_eq!(record.fingerprint.len(), 16);
+        assert_eq!(record.fingerprint, new_alert(&alert, true).fingerprint);
+        assert_eq!(record.ends_at, None);
+        assert!(record.silenced);
+    }
+}
@@ -0,0 +1,352 @@
+//! 命令行模式：不启动 HTTP 服务，直接调用 services 和 generators，用于 CI/CD 和 cron
+
+use crate::db::{ConfigRepository, TaskRepository};
+use crate::models::{
+    DatabaseConfig, DbType, SyncMappingRequest, TaskStatus, TestConnectionRequest,
+};
+use crate::services::{
+    ApprovalPolicy, ConnectionService, KeyRotationService, MetadataService, SettingsService,
+    SyncEngine, SyncMappingService,
+};
+use crate::utils::error::{AppError, Result};
+use crate::utils::service::{self, ServiceOptions};
+use clap::{Args, Parser, Subcommand};
+use sqlx::MySqlPool;
+use std::path::PathBuf;
+
+/// 命令行提交的任务记录的提交人
+const CLI_USER: &str = "cli";
+/// 等待同步任务结束时查询状态的间隔（秒）
+const POLL_INTERVAL_SECS: u64 = 2;
+
+/// 命令行参数，不带子命令时启动 Web 服务
+#[derive(Debug, Clone, PartialEq, Eq, Parser)]
+#[command(
+    name = "rw_cdc_sr",
+    version,
+    about = "同步 MySQL 表到 StarRocks / Doris（经由 RisingWave CDC），不带子命令时启动 Web 服务"
+)]
+pub struct Cli {
+    /// 启动前切换到的工作目录；install-service 时为服务的工作目录
+    #[arg(long, global = true, value_name = "DIR")]
+    pub working_dir: Option<PathBuf>,
+    /// 启动前加载的环境文件（已设置的环境变量优先）；install-service 时为服务加载的环境文件
+    #[arg(long, global = true, value_name = "FILE")]
+    pub env_file: Option<PathBuf>,
+    /// 演示模式：向空的元数据库写入示例数据，禁用破坏性接口
+    #[arg(long)]
+    pub demo: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// 命令行子命令
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// 按映射文件（YAML 或 JSON，格式与 /api/sync_mappings 的请求体相同）提交同步，等待任务结束
+    Sync {
+        /// 映射文件
+        #[arg(long, value_name = "FILE")]
+        config: PathBuf,
+        /// 只输出将执行的 DDL，不提交
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 测试已保存的连接
+    TestConnection {
+        /// 连接 ID 或名称
+        #[arg(long)]
+        connection: String,
+    },
+    /// 列出 MySQL 连接中的表，未指定数据库时列出数据库
+    ListTables {
+        /// 连接 ID 或名称
+        #[arg(long)]
+        connection: String,
+        #[arg(long)]
+        database: Option<String>,
+    },
+    /// 用当前密钥重新加密存储的密文
+    RotateKey,
+    /// 注册为系统服务（systemd / launchd / Windows 计划任务）
+    InstallService(ServiceArgs),
+    /// 移除系统服务
+    UninstallService(ServiceArgs),
+}
+
+/// install-service / uninstall-service 的参数，工作目录和环境文件使用全局的 --working-dir / --env-file
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub struct ServiceArgs {
+    /// 服务名（默认 rw-cdc-sr）
+    #[arg(long)]
+    pub name: Option<String>,
+    /// systemd 中运行服务的用户（默认 root）
+    #[arg(long)]
+    pub user: Option<String>,
+}
+
+impl Cli {
+    /// install-service / uninstall-service：注册或移除系统服务，返回输出信息；其他命令返回 None
+    /// 在加载环境文件和启动运行时之前执行
+    pub fn service_command(&self) -> Option<Result<String>> {
+        let (install, args) = match &self.command {
+            Some(Command::InstallService(args)) => (true, args),
+            Some(Command::UninstallService(args)) => (false, args),
+            _ => return None,
+        };
+        let options = match ServiceOptions::new(
+            args.name.clone(),
+            self.working_dir.clone(),
+            self.env_file.clone(),
+            args.user.clone(),
+        ) {
+            Ok(options) => options,
+            Err(e) => return Some(Err(e)),
+        };
+        Some(if install {
+            service::install(&options).map(|target| {
+                format!(
+                    "Installed service {} ({}), env file: {}",
+                    options.name,
+                    target,
+                    options.env_file.display()
+                )
+            })
+        } else {
+            service::uninstall(&options)
+                .map(|target| format!("Removed service {} ({})", options.name, target))
+        })
+    }
+}
+
+impl Command {
+    /// 执行子命令，返回进程退出码
+    pub async fn run(self) -> Result<i32> {
+        crate::utils::crypto::init()?;
+        let db = crate::db::init_database().await?;
+        SettingsService::load(&db).await?;
+
+        match self {
+            Command::Sync { config, dry_run } => sync(&db, &config, dry_run).await,
+            Command::TestConnection { connection } => test_connection(&db, &connection).await,
+            Command::ListTables {
+                connection,
+                database,
+            } => list_tables(&db, &connection, database.as_deref()).await,
+            Command::RotateKey => rotate_key(&db).await,
+            Command::InstallService(_) | Command::UninstallService(_) => {
+                unreachable!("handled by Cli::service_command before startup")
+            }
+        }
+    }
+}
+
+/// 按 ID 或名称查找连接
+async fn find_connection(db: &MySqlPool, connection: &str) -> Result<DatabaseConfig> {
+    let repo = ConfigRepository::new(db);
+    if let Ok(id) = connection.parse::<i64>() {
+        return repo.find_by_id(id).await;
+    }
+    let id = repo
+        .find_all()
+        .await?
+        .into_iter()
+        .find(|config| config.name == connection)
+        .map(|config| config.id)
+        .ok_or_else(|| AppError::NotFound(format!("Connection {} not found", connection)))?;
+    repo.find_by_id(id).await
+}
+
+async fn sync(db: &MySqlPool, path: &PathBuf, dry_run: bool) -> Result<i32> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::Config(format!("Cannot read {}: {}", path.display(), e)))?;
+    let mapping: SyncMappingRequest = serde_yaml::from_str(&content)
+        .map_err(|e| AppError::Config(format!("Invalid mapping file {}: {}", path.display(), e)))?;
+    let requests = SyncMappingService::requests(&mapping)?;
+    if requests.is_empty() {
+        return Err(AppError::Validation("No tables to sync".to_string()));
+    }
+    let engine = SyncEngine::new(db.clone());
+
+    if dry_run {
+        for preview in engine.preview_ddl(&requests, None).await? {
+            println!(
+                "-- {}.{} -> {}.{}",
+                preview.mysql_database,
+                preview.mysql_table,
+                preview.target_database,
+                preview.target_table
+            );
+            println!("-- RisingWave");
+            preview
+                .risingwave
+                .iter()
+                .for_each(|sql| println!("{}", sql));
+            println!("-- StarRocks");
+            preview.starrocks.iter().for_each(|sql| println!("{}", sql));
+            println!();
+        }
+        return Ok(0);
+    }
+
+    // 命令行不创建审批单，需要审批的同步通过接口提交
+    if let Some(reason) = ApprovalPolicy::from_env().reason(&requests) {
+        return Err(AppError::Validation(format!(
+            "Sync requires approval ({}), submit it through the API",
+            reason
+        )));
+    }
+    if let Some(task_id) = engine.find_duplicate(&requests).await? {
+        return Err(AppError::Conflict(format!(
+            "Task {} with the same tables is still pending or running",
+            task_id
+        )));
+    }
+
+    let task_id = engine.sync_multiple_tables(requests).await?;
+    let repo = TaskRepository::new(db);
+    repo.set_created_by(task_id, CLI_USER).await?;
+    println!("Submitted task {}", task_id);
+
+    // 任务在本进程中执行，等待结束后退出
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(POLL_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        let task = repo.find_by_id(task_id).await?;
+        match task.status {
+            TaskStatus::Pending | TaskStatus::Running => continue,
+            TaskStatus::Completed => {
+                println!("Task {} completed", task_id);
+                return Ok(0);
+            }
+            status => {
+                println!(
+                    "Task {} {}: {}",
+                    task_id,
+                    status.as_str(),
+                    task.error_message.unwrap_or_default()
+                );
+                return Ok(1);
+            }
+        }
+    }
+}
+
+async fn rotate_key(db: &MySqlPool) -> Result<i32> {
+    let result = KeyRotationService::reencrypt_all(db).await?;
+    for column in &result.columns {
+        println!("{}.{}: {} rows", column.table, column.column, column.rows);
+    }
+    println!("All stored values are encrypted with key {}", result.key_id);
+    Ok(0)
+}
+
+async fn test_connection(db: &MySqlPool, connection: &str) -> Result<i32> {
+    let config = find_connection(db, connection).await?;
+    let request = TestConnectionRequest {
+        db_type: config.db_type.clone(),
+        host: config.host.clone(),
+        port: config.port,
+        username: config.username.clone(),
+        password: config.password.clone(),
+        no_password: config.no_password,
+        database_name: config.database_name.clone(),
+        proxy: config.proxy.clone(),
+        tls: config.tls.clone(),
+        config_id: None,
+    };
+    let result = match config.db_type {
+        DbType::MySQL => ConnectionService::test_mysql(&request).await?,
+        DbType::RisingWave => ConnectionService::test_risingwave(&request).await?,
+        DbType::StarRocks | DbType::Doris => ConnectionService::test_starrocks(&request).await?,
+    };
+
+    if result.success {
+        println!("{} ({}): {}", config.name, config.id, result.message);
+        Ok(0)
+    } else {
+        println!(
+            "{} ({}): {}",
+            config.name,
+            config.id,
+            result.error.unwrap_or(result.message)
+        );
+        Ok(1)
+    }
+}
+
+async fn list_tables(db: &MySqlPool, connection: &str, database: Option<&str>) -> Result<i32> {
+    let config = find_connection(db, connection).await?;
+    if config.db_type != DbType::MySQL {
+        return Err(AppError::Validation(format!(
+            "Connection {} is not a MySQL connection",
+            config.name
+        )));
+    }
+    let config = ConnectionService::resolve_config(config).await?;
+    let names = match database {
+        Some(database) => MetadataService::list_mysql_tables(&config, database).await?,
+        None => MetadataService::list_mysql_databases(&config).await?,
+    };
+    names.iter().for_each(|name| println!("{}", name));
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> std::result::Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("rw_cdc_sr").chain(s.split_whitespace()))
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let cli = parse("--env-file prod.env").unwrap();
+        assert_eq!(cli.env_file, Some(PathBuf::from("prod.env")));
+        assert_eq!(cli.command, None);
+        assert!(parse("--demo").unwrap().demo);
+        assert_eq!(
+            parse("sync --config nightly.yaml --dry-run --env-file prod.env").unwrap(),
+            Cli {
+                working_dir: None,
+                env_file: Some(PathBuf::from("prod.env")),
+                demo: false,
+                command: Some(Command::Sync {
+                    config: PathBuf::from("nightly.yaml"),
+                    dry_run: true
+                }),
+            }
+        );
+        assert_eq!(
+            parse("list-tables --connection prod-mysql --database shop")
+                .unwrap()
+                .command,
+            Some(Command::ListTables {
+                connection: "prod-mysql".to_string(),
+                database: Some("shop".to_string())
+            })
+        );
+        assert_eq!(
+            parse("install-service --name sync --working-dir /srv/sync").unwrap(),
+            Cli {
+                working_dir: Some(PathBuf::from("/srv/sync")),
+                env_file: None,
+                demo: false,
+                command: Some(Command::InstallService(ServiceArgs {
+                    name: Some("sync".to_string()),
+                    user: None
+                })),
+            }
+        );
+        assert_eq!(
+            parse("rotate-key").unwrap().command,
+            Some(Command::RotateKey)
+        );
+
+        assert!(parse("sync").is_err());
+        assert!(parse("test-connection --connection").is_err());
+        assert!(parse("test-connection --database shop").is_err());
+        assert!(parse("bogus").is_err());
+    }
+}
@@ -8,7 +8,11 @@ CREATE TABLE IF NOT EXISTS database_configs (
     port INT NOT NULL,
     username VARCHAR(255) NOT NULL,
     password TEXT NOT NULL,
+    no_password BOOLEAN NOT NULL DEFAULT FALSE,
     database_name VARCHAR(255),
+    proxy_url TEXT NULL,
+    tls_config TEXT NULL,
+    version INT NOT NULL DEFAULT 1,
     created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
     updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -31,10 +35,13 @@ CREATE TABLE IF NOT EXISTS sync_tasks (
     completed_at TIMESTAMP NULL,
     error_message TEXT,
     options TEXT NOT NULL DEFAULT ('{}'),
+    preflight_result TEXT NULL,
+    requests MEDIUMTEXT NULL,
+    sink_requests MEDIUMTEXT NULL,
     FOREIGN KEY (mysql_config_id) REFERENCES database_configs(id) ON DELETE CASCADE,
     FOREIGN KEY (rw_config_id) REFERENCES database_configs(id) ON DELETE CASCADE,
     FOREIGN KEY (sr_config_id) REFERENCES database_configs(id) ON DELETE CASCADE,
-    INDEX idx_status (status),
+    INDEX idx_status_started_at (status, started_at),
     INDEX idx_started_at (started_at)
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
 "#;
@@ -47,8 +54,402 @@ CREATE TABLE IF NOT EXISTS task_logs (
     log_level VARCHAR(50) NOT NULL,
     message TEXT NOT NULL,
     created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    payload TEXT NULL,
     FOREIGN KEY (task_id) REFERENCES sync_tasks(id) ON DELETE CASCADE,
     INDEX idx_task_id (task_id),
     INDEX idx_created_at (created_at)
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
 "#;
+
+/// 任务步骤表的 CREATE TABLE 语句 (MySQL 8)
+/// 任务创建时写入全部计划步骤，执行时更新状态和耗时，用于计算同步进度
+pub const CREATE_TASK_STEPS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS task_steps (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    task_id INT NOT NULL,
+    step_index INT NOT NULL,
+    step VARCHAR(50) NOT NULL,
+    table_name VARCHAR(512) NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    started_at TIMESTAMP NULL,
+    completed_at TIMESTAMP NULL,
+    duration_ms BIGINT NULL,
+    error_message TEXT NULL,
+    FOREIGN KEY (task_id) REFERENCES sync_tasks(id) ON DELETE CASCADE,
+    UNIQUE KEY uk_task_step (task_id, step_index)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 数据一致性校验结果表的 CREATE TABLE 语句 (MySQL 8)
+pub const CREATE_VALIDATION_RESULTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS validation_results (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    task_id INT NOT NULL,
+    mysql_database VARCHAR(255) NOT NULL,
+    mysql_table VARCHAR(255) NOT NULL,
+    target_database VARCHAR(255) NOT NULL,
+    target_table VARCHAR(255) NOT NULL,
+    source_count BIGINT NULL,
+    target_count BIGINT NULL,
+    source_checksum TEXT NULL,
+    target_checksum TEXT NULL,
+    matched BOOLEAN NOT NULL DEFAULT FALSE,
+    error_message TEXT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (task_id) REFERENCES sync_tasks(id) ON DELETE CASCADE,
+    INDEX idx_task_id (task_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 管道健康采样表（用于 SLA / 可用率统计）
+pub const CREATE_PIPELINE_HEALTH_SAMPLES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS pipeline_health_samples (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    source VARCHAR(512) NOT NULL,
+    target VARCHAR(512) NOT NULL,
+    health VARCHAR(16) NOT NULL,
+    last_sync_at TIMESTAMP NULL,
+    sampled_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_sampled_at (sampled_at),
+    INDEX idx_pipeline_sampled_at (target(191), sampled_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 行数对比采样表（MySQL / RisingWave / StarRocks 三端行数的定期快照）
+pub const CREATE_SYNC_METRICS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS sync_metrics (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    task_id INT NOT NULL,
+    mysql_database VARCHAR(255) NOT NULL,
+    mysql_table VARCHAR(255) NOT NULL,
+    target_database VARCHAR(255) NOT NULL,
+    target_table VARCHAR(255) NOT NULL,
+    source_count BIGINT NULL,
+    rw_count BIGINT NULL,
+    target_count BIGINT NULL,
+    error_message TEXT NULL,
+    sampled_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (task_id) REFERENCES sync_tasks(id) ON DELETE CASCADE,
+    INDEX idx_sampled_at (sampled_at),
+    INDEX idx_target_sampled_at (target_database(100), target_table(100), sampled_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 任务每日统计表，任务结束时增量更新，仪表盘统计不需要扫描任务历史
+pub const CREATE_TASK_DAILY_STATS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS task_daily_stats (
+    stat_date DATE NOT NULL,
+    status VARCHAR(50) NOT NULL,
+    mysql_config_id INT NOT NULL,
+    rw_config_id INT NOT NULL,
+    sr_config_id INT NOT NULL,
+    task_count INT NOT NULL DEFAULT 0,
+    total_duration_ms BIGINT NOT NULL DEFAULT 0,
+    max_duration_ms BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (stat_date, status, mysql_config_id, rw_config_id, sr_config_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// task_daily_stats 为空时从已结束的任务回填
+pub const BACKFILL_TASK_DAILY_STATS: &str = r#"
+INSERT INTO task_daily_stats (
+    stat_date, status, mysql_config_id, rw_config_id, sr_config_id,
+    task_count, total_duration_ms, max_duration_ms
+)
+SELECT
+    DATE(started_at), status, mysql_config_id, rw_config_id, sr_config_id,
+    COUNT(*),
+    SUM(TIMESTAMPDIFF(MICROSECOND, started_at, completed_at) DIV 1000),
+    MAX(TIMESTAMPDIFF(MICROSECOND, started_at, completed_at) DIV 1000)
+FROM sync_tasks
+WHERE completed_at IS NOT NULL
+GROUP BY DATE(started_at), status, mysql_config_id, rw_config_id, sr_config_id
+"#;
+
+/// CDC Source server.id 分配表，保证每个 Source 的 server.id 固定且唯一
+pub const CREATE_SERVER_ID_ALLOCATIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS server_id_allocations (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    mysql_host VARCHAR(255) NOT NULL,
+    mysql_port INT NOT NULL,
+    mysql_database VARCHAR(64) NOT NULL,
+    source_name VARCHAR(255) NOT NULL,
+    server_id INT UNSIGNED NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE KEY uk_server_id (server_id),
+    UNIQUE KEY uk_source (mysql_host, mysql_port, mysql_database, source_name)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 共享 RisingWave 对象表，记录跨目标 schema 复用的 Source 和 Secret
+pub const CREATE_MANAGED_OBJECTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS managed_objects (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    rw_config_id INT NOT NULL,
+    object_type VARCHAR(50) NOT NULL,
+    object_name VARCHAR(512) NOT NULL,
+    share_key VARCHAR(512) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (rw_config_id) REFERENCES database_configs(id) ON DELETE CASCADE,
+    UNIQUE KEY uk_managed_object (rw_config_id, object_type, object_name),
+    INDEX idx_share_key (rw_config_id, object_type, share_key)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 共享对象的引用表，每个引用对象的目标表一行
+pub const CREATE_MANAGED_OBJECT_REFS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS managed_object_refs (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    object_id INT NOT NULL,
+    target_database VARCHAR(255) NOT NULL,
+    target_table VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (object_id) REFERENCES managed_objects(id) ON DELETE CASCADE,
+    UNIQUE KEY uk_object_ref (object_id, target_database, target_table)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 功能开关表，按工作区逐步开启有风险的新功能
+pub const CREATE_FEATURE_FLAGS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS feature_flags (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    workspace VARCHAR(64) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    UNIQUE KEY uk_workspace_name (workspace, name)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 运行时设置表的 CREATE TABLE 语句 (MySQL 8)
+pub const CREATE_SETTINGS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS settings (
+    name VARCHAR(100) PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 同步审批单表的 CREATE TABLE 语句 (MySQL 8)
+pub const CREATE_APPROVAL_REQUESTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS approval_requests (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    reason VARCHAR(500) NOT NULL,
+    requests MEDIUMTEXT NOT NULL,
+    requested_by VARCHAR(100) NOT NULL,
+    requested_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    decided_by VARCHAR(100) NULL,
+    decided_at TIMESTAMP NULL,
+    comment TEXT NULL,
+    task_id INT NULL,
+    INDEX idx_status_requested_at (status, requested_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 任务 / 管道评论表的 CREATE TABLE 语句 (MySQL 8)
+pub const CREATE_COMMENTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS comments (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    subject_type VARCHAR(20) NOT NULL,
+    subject_id VARCHAR(512) NOT NULL,
+    author VARCHAR(100) NOT NULL,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_subject (subject_type, subject_id(191), created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 通知渠道表的 CREATE TABLE 语句 (MySQL 8)
+/// settings 为加密后的渠道配置 JSON（包含 Webhook 地址、签名密钥和 SMTP 密码）
+pub const CREATE_NOTIFICATION_CHANNELS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS notification_channels (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    name VARCHAR(100) NOT NULL UNIQUE,
+    kind VARCHAR(20) NOT NULL,
+    settings TEXT NOT NULL,
+    events VARCHAR(255) NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+pub const CREATE_ALERT_ROUTES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS alert_routes (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    matchers TEXT NOT NULL,
+    channel_ids TEXT NOT NULL,
+    priority INT NOT NULL DEFAULT 0,
+    continue_matching BOOLEAN NOT NULL DEFAULT FALSE,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+pub const CREATE_ALERT_SILENCES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS alert_silences (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    matchers TEXT NOT NULL,
+    starts_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ends_at TIMESTAMP NOT NULL,
+    comment TEXT NULL,
+    created_by VARCHAR(100) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_ends_at (ends_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+pub const CREATE_ALERTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS alerts (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    fingerprint VARCHAR(64) NOT NULL,
+    alertname VARCHAR(255) NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    severity VARCHAR(50) NULL,
+    labels TEXT NOT NULL,
+    annotations TEXT NOT NULL,
+    starts_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ends_at TIMESTAMP NULL,
+    receive_count INT NOT NULL DEFAULT 1,
+    last_received_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    silenced BOOLEAN NOT NULL DEFAULT FALSE,
+    resolved_at TIMESTAMP NULL,
+    resolved_by VARCHAR(100) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE KEY uk_fingerprint_starts_at (fingerprint, starts_at),
+    INDEX idx_status_last_received (status, last_received_at),
+    INDEX idx_last_received (last_received_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 告警与任务 / 管道的关联表，告警标签中的 sink / source 名称匹配到管道时写入
+pub const CREATE_ALERT_LINKS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS alert_links (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    alert_id BIGINT NOT NULL,
+    subject_type VARCHAR(20) NOT NULL,
+    subject_id VARCHAR(512) NOT NULL,
+    object_name VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE KEY uk_alert_subject (alert_id, subject_type, subject_id(191)),
+    INDEX idx_subject (subject_type, subject_id(191))
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+pub const CREATE_AUDIT_LOGS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS audit_logs (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    actor VARCHAR(100) NULL,
+    action VARCHAR(50) NOT NULL,
+    target VARCHAR(512) NOT NULL,
+    engine VARCHAR(20) NULL,
+    sql_text MEDIUMTEXT NULL,
+    task_id INT NULL,
+    success BOOLEAN NOT NULL DEFAULT TRUE,
+    error_message TEXT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_created_at (created_at),
+    INDEX idx_action_created_at (action, created_at),
+    INDEX idx_task_id (task_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+pub const CREATE_USERS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS users (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    username VARCHAR(100) NOT NULL UNIQUE,
+    password_hash VARCHAR(255) NOT NULL,
+    role VARCHAR(20) NOT NULL DEFAULT 'viewer',
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    last_login_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+pub const CREATE_API_TOKENS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS api_tokens (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    user_id INT NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    token_prefix VARCHAR(20) NOT NULL,
+    expires_at TIMESTAMP NULL,
+    last_used_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_user_id (user_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+pub const CREATE_SYNC_SCHEDULES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS sync_schedules (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    cron VARCHAR(255) NOT NULL,
+    timezone VARCHAR(64) NOT NULL,
+    requests MEDIUMTEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    next_run_at TIMESTAMP NULL,
+    last_run_at TIMESTAMP NULL,
+    last_task_id INT NULL,
+    last_error TEXT NULL,
+    created_by VARCHAR(100) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    INDEX idx_enabled_next_run (enabled, next_run_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+pub const CREATE_SYNC_MAPPINGS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS sync_mappings (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    description TEXT NULL,
+    mysql_config_id INT NOT NULL,
+    rw_config_id INT NOT NULL,
+    sr_config_id INT NOT NULL,
+    target_database VARCHAR(255) NULL,
+    tables MEDIUMTEXT NOT NULL,
+    options TEXT NOT NULL,
+    target_table_template VARCHAR(255) NULL,
+    last_task_id INT NULL,
+    last_run_at TIMESTAMP NULL,
+    paused BOOLEAN NOT NULL DEFAULT FALSE,
+    paused_at TIMESTAMP NULL,
+    paused_schedule_ids VARCHAR(1024) NOT NULL DEFAULT '[]',
+    version INT NOT NULL DEFAULT 1,
+    created_by VARCHAR(100) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    UNIQUE KEY uk_name (name)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+"#;
+
+/// 已有表的增量列迁移：(表名, 列名, 列定义)
+/// CREATE TABLE IF NOT EXISTS 不会给旧表补列，升级时按此列表逐一检查并 ALTER
+pub const COLUMN_MIGRATIONS: &[(&str, &str, &str)] = &[
+    ("task_logs", "payload", "TEXT NULL"),
+    ("sync_tasks", "preflight_result", "TEXT NULL"),
+    ("sync_tasks", "requests", "MEDIUMTEXT NULL"),
+    ("sync_tasks", "sink_requests", "MEDIUMTEXT NULL"),
+    ("database_configs", "version", "INT NOT NULL DEFAULT 1"),
+    ("database_configs", "proxy_url", "TEXT NULL"),
+    ("database_configs", "no_password", "BOOLEAN NOT NULL DEFAULT FALSE"),
+    ("database_configs", "tls_config", "TEXT NULL"),
+    ("sync_tasks", "created_by", "VARCHAR(100) NULL"),
+    ("sync_tasks", "table_outcomes", "TEXT NULL"),
+    ("sync_mappings", "paused", "BOOLEAN NOT NULL DEFAULT FALSE"),
+    ("sync_mappings", "paused_at", "TIMESTAMP NULL"),
+    ("sync_mappings", "paused_schedule_ids", "VARCHAR(1024) NOT NULL DEFAULT '[]'"),
+    ("sync_mappings", "version", "INT NOT NULL DEFAULT 1"),
+    // 引入角色之前的用户都拥有全部权限
+    ("users", "role", "VARCHAR(20) NOT NULL DEFAULT 'admin'"),
+];
+
+/// 已有表的增量索引迁移：(表名, 索引名, 列)
+pub const INDEX_MIGRATIONS: &[(&str, &str, &str)] = &[
+    ("sync_tasks", "idx_status_started_at", "status, started_at"),
+];
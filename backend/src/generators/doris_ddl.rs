@@ -0,0 +1,223 @@
+use crate::models::{Column, StarRocksPartition, StarRocksTableOptions, TableSchema};
+use crate::utils::error::{AppError, Result};
+use crate::generators::StarRocksDDLGenerator;
+use crate::utils::type_mapper::TypeMapper;
+
+/// Doris DDL 生成器
+/// Doris 与 StarRocks 同样使用 MySQL 协议，删除、清空表等语句可复用 StarRocksDDLGenerator
+pub struct DorisDDLGenerator;
+
+impl DorisDDLGenerator {
+    /// 生成 Doris 表创建语句（Unique Key 模型，开启 merge-on-write 以支持 upsert）
+    pub fn generate_table_ddl(
+        schema: &TableSchema,
+        target_database: &str,
+        target_table: &str,
+        options: &StarRocksTableOptions,
+        partition: Option<&StarRocksPartition>,
+    ) -> Result<String> {
+        if partition.is_some() {
+            return Err(AppError::Validation(
+                "Partitioning is not supported for Doris targets yet".to_string(),
+            ));
+        }
+
+        // Doris Unique Key 表需要 key 列，没有主键时使用第一列
+        let key_columns = if !schema.primary_keys.is_empty() {
+            schema.primary_keys.clone()
+        } else if let Some(first) = schema.columns.first() {
+            vec![first.name.clone()]
+        } else {
+            return Err(AppError::SqlGeneration("Table has no columns".to_string()));
+        };
+
+        // key 列必须放在最前面
+        let mut column_defs = Vec::new();
+        for key in &key_columns {
+            if let Some(col) = schema.columns.iter().find(|c| &c.name == key) {
+                column_defs.push(format!("  {}", Self::column_definition(col)?));
+            }
+        }
+        for col in &schema.columns {
+            if !key_columns.contains(&col.name) {
+                column_defs.push(format!("  {}", Self::column_definition(col)?));
+            }
+        }
+
+        let buckets = match options.buckets {
+            _ if options.auto_bucket => "AUTO".to_string(),
+            Some(0) => {
+                return Err(AppError::Validation(
+                    "Doris buckets must be greater than 0".to_string(),
+                ));
+            }
+            Some(buckets) => buckets.to_string(),
+            None => "AUTO".to_string(),
+        };
+        let properties = Self::generate_properties(options)?;
+
+        Ok(format!(
+            r#"CREATE TABLE IF NOT EXISTS {}.{} (
+               {}
+               ) ENGINE=OLAP
+               UNIQUE KEY({}){}
+               DISTRIBUTED BY HASH({}) BUCKETS {}
+               PROPERTIES (
+                   {}
+               );"#,
+            StarRocksDDLGenerator::quote_identifier(target_database),
+            StarRocksDDLGenerator::quote_identifier(target_table),
+            column_defs.join(",\n"),
+            StarRocksDDLGenerator::quote_identifiers(&key_columns),
+            StarRocksDDLGenerator::table_comment_clause(schema),
+            StarRocksDDLGenerator::quote_identifier(&key_columns[0]),
+            buckets,
+            properties.join(",\n                   ")
+        ))
+    }
+
+    /// 生成单列定义：`col` TYPE [NOT] NULL [COMMENT '...']
+    fn column_definition(col: &Column) -> Result<String> {
+        let doris_type = TypeMapper::mysql_to_doris(&col.data_type)?;
+        let nullable = if col.is_nullable {
+            " NULL"
+        } else {
+            " NOT NULL"
+        };
+        let comment = match &col.comment {
+            Some(comment) => format!(" COMMENT {}", StarRocksDDLGenerator::quote_string(comment)),
+            None => String::new(),
+        };
+
+        Ok(format!(
+            "{} {}{}{}",
+            StarRocksDDLGenerator::quote_identifier(&col.name),
+            doris_type,
+            nullable,
+            comment
+        ))
+    }
+
+    /// 生成 PROPERTIES 中的键值对
+    fn generate_properties(options: &StarRocksTableOptions) -> Result<Vec<String>> {
+        if options.replication_num == 0 {
+            return Err(AppError::Validation(
+                "Doris replication_num must be greater than 0".to_string(),
+            ));
+        }
+        if options.colocate_with.is_some() {
+            return Err(AppError::Validation(
+                "Colocation groups are not supported for Doris targets yet".to_string(),
+            ));
+        }
+        if !options.sort_keys.is_empty()
+            || !options.bitmap_indexes.is_empty()
+            || !options.bloom_filter_columns.is_empty()
+        {
+            return Err(AppError::Validation(
+                "Sort keys and indexes are not supported for Doris targets yet".to_string(),
+            ));
+        }
+        if !options.dynamic_partition.is_empty() {
+            return Err(AppError::Validation(
+                "Dynamic partition is not supported for Doris targets yet".to_string(),
+            ));
+        }
+
+        let mut properties = vec![
+            (
+                "replication_num".to_string(),
+                options.replication_num.to_string(),
+            ),
+            (
+                "enable_unique_key_merge_on_write".to_string(),
+                "true".to_string(),
+            ),
+        ];
+
+        if let Some(medium) = &options.storage_medium {
+            let medium = medium.to_uppercase();
+            if medium != "HDD" && medium != "SSD" {
+                return Err(AppError::Validation(format!(
+                    "Invalid Doris storage_medium: {}",
+                    medium
+                )));
+            }
+            properties.push(("storage_medium".to_string(), medium));
+        }
+
+        Ok(properties
+            .into_iter()
+            .map(|(k, v)| format!("\"{}\" = \"{}\"", k, v))
+            .collect())
+    }
+
+    /// 生成创建数据库的语句
+    pub fn generate_create_database_ddl(database: &str) -> String {
+        format!(
+            "CREATE DATABASE IF NOT EXISTS {};",
+            StarRocksDDLGenerator::quote_identifier(database)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ColumnTypeSource;
+
+    fn column(name: &str, data_type: &str, is_nullable: bool) -> Column {
+        Column {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_nullable,
+            default_value: None,
+            comment: None,
+            character_maximum_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+        }
+    }
+
+    #[test]
+    fn test_generate_table_ddl() {
+        let schema = TableSchema {
+            database: "test_db".to_string(),
+            table_name: "events".to_string(),
+            columns: vec![
+                column("payload", "BLOB", true),
+                column("id", "BIGINT", false),
+                column("at", "TIME", true),
+            ],
+            primary_keys: vec!["id".to_string()],
+            indexes: vec![],
+            type_source: ColumnTypeSource::MySQL,
+            comment: None,
+        };
+
+        let ddl = DorisDDLGenerator::generate_table_ddl(
+            &schema,
+            "dw",
+            "events",
+            &StarRocksTableOptions::default(),
+            None,
+        )
+        .unwrap();
+
+        assert!(ddl.contains("CREATE TABLE IF NOT EXISTS `dw`.`events`"));
+        assert!(ddl.find("`id` BIGINT NOT NULL").unwrap() < ddl.find("`payload`").unwrap());
+        assert!(ddl.contains("`payload` STRING NULL"));
+        assert!(ddl.contains("`at` STRING NULL"));
+        assert!(ddl.contains("UNIQUE KEY(`id`)"));
+        assert!(ddl.contains("DISTRIBUTED BY HASH(`id`) BUCKETS AUTO"));
+        assert!(ddl.contains("\"enable_unique_key_merge_on_write\" = \"true\""));
+
+        let options = StarRocksTableOptions {
+            buckets: Some(0),
+            ..Default::default()
+        };
+        assert!(
+            DorisDDLGenerator::generate_table_ddl(&schema, "dw", "events", &options, None).is_err()
+        );
+    }
+}
@@ -1,5 +1,10 @@
+pub mod doris_ddl;
 pub mod risingwave_ddl;
 pub mod starrocks_ddl;
 
+pub use doris_ddl::*;
 pub use risingwave_ddl::*;
 pub use starrocks_ddl::*;
+
+#[cfg(test)]
+mod proptests;
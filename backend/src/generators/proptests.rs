@@ -0,0 +1,378 @@
+//! DDL 生成器的属性测试：随机表结构（特殊标识符、超大精度、unicode 注释）以及随机的
+//! 目标库名、表名生成的 StarRocks / Doris / RisingWave 语句能被 sqlparser 解析，且标识符原样往返
+
+use crate::generators::{DorisDDLGenerator, RisingWaveDDLGenerator, StarRocksDDLGenerator};
+use crate::models::{
+    Column, ColumnTypeSource, DatabaseConfig, DbType, StarRocksTableOptions, SyncRequest, TableSchema,
+};
+use proptest::prelude::*;
+use sqlparser::ast::{
+    ColumnOption, SchemaName, SelectItem, SetExpr, Statement, TableConstraint, TableFactor,
+};
+use sqlparser::dialect::{MySqlDialect, PostgreSqlDialect};
+use sqlparser::parser::Parser;
+use std::collections::{BTreeMap, HashSet};
+
+/// 建表语句中列定义结束的位置，之后是 StarRocks / Doris 特有的子句
+const ENGINE_CLAUSE: &str = "\n               ) ENGINE=OLAP\n               ";
+
+/// 标识符：普通小写名、大小写混合、保留字、引号、空格和 unicode（不含控制字符）
+fn identifier() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-z_][a-z0-9_]{0,15}",
+        "[A-Za-z][A-Za-z0-9 _$-]{0,15}",
+        prop::sample::select(vec![
+            "select", "from", "order", "table", "user", "KEY", "Index"
+        ])
+        .prop_map(str::to_string),
+        "[a-z]{1,5}[`\"'\\\\][a-z]{0,5}",
+        "\\PC{1,10}",
+    ]
+}
+
+/// MySQL 列类型（COLUMN_TYPE 格式），包括超出 StarRocks 上限的精度和 unsigned 修饰
+fn mysql_type() -> impl Strategy<Value = String> {
+    prop_oneof![
+        prop::sample::select(vec![
+            "bigint",
+            "int(11) unsigned",
+            "bigint unsigned zerofill",
+            "tinyint(1)",
+            "double",
+            "datetime",
+            "timestamp",
+            "date",
+            "time",
+            "json",
+            "text",
+            "blob",
+            "enum('a','b')",
+        ])
+        .prop_map(str::to_string),
+        (1u32..=65, 0u32..=30).prop_map(|(p, s)| format!("decimal({},{}) unsigned", p, s.min(p))),
+        (1u32..=65535).prop_map(|n| format!("varchar({})", n)),
+        (1u32..=255).prop_map(|n| format!("char({})", n)),
+    ]
+}
+
+fn column() -> impl Strategy<Value = Column> {
+    (
+        identifier(),
+        mysql_type(),
+        any::<bool>(),
+        prop::option::of("\\PC{0,20}"),
+    )
+        .prop_map(|(name, data_type, is_nullable, comment)| Column {
+            name,
+            data_type,
+            is_nullable,
+            default_value: None,
+            comment,
+            character_maximum_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+        })
+}
+
+/// 随机表结构，列名按 MySQL 规则大小写不敏感去重，主键取前若干列
+fn table_schema() -> impl Strategy<Value = TableSchema> {
+    (prop::collection::vec(column(), 1..8), 0usize..3).prop_map(|(columns, pk_len)| {
+        let mut seen = HashSet::new();
+        let columns: Vec<Column> = columns
+            .into_iter()
+            .filter(|c| seen.insert(c.name.to_lowercase()))
+            .collect();
+        let primary_keys = columns
+            .iter()
+            .take(pk_len)
+            .map(|c| c.name.clone())
+            .collect();
+        TableSchema {
+            database: "src".to_string(),
+            table_name: "t".to_string(),
+            columns,
+            primary_keys,
+            indexes: vec![],
+            type_source: ColumnTypeSource::MySQL,
+            comment: None,
+        }
+    })
+}
+
+/// 按 PostgreSQL 语法解析 RisingWave 对象名，返回去掉引号后的各部分
+fn parse_rw_object_name(name: &str) -> Vec<String> {
+    let sql = format!("SELECT 1 FROM {}", name);
+    let statements = Parser::parse_sql(&PostgreSqlDialect {}, &sql)
+        .unwrap_or_else(|e| panic!("failed to parse {}: {}", sql, e));
+    let Statement::Query(query) = &statements[0] else {
+        panic!("unexpected statement: {}", sql);
+    };
+    let SetExpr::Select(select) = query.body.as_ref() else {
+        panic!("unexpected query: {}", sql);
+    };
+    match &select.from[0].relation {
+        TableFactor::Table { name, .. } => name.0.iter().map(|i| i.value.clone()).collect(),
+        other => panic!("unexpected relation: {}", other),
+    }
+}
+
+/// 截取建表语句的列定义部分，加上主键约束后按 MySQL 语法解析
+fn parse_create_table(ddl: &str, key_clause: &str) -> sqlparser::ast::CreateTable {
+    let (head, tail) = ddl
+        .split_once(ENGINE_CLAUSE)
+        .expect("missing ENGINE clause");
+    let key_columns = tail
+        .strip_prefix(key_clause)
+        .and_then(|rest| rest.split_once(")\n"))
+        .map(|(columns, _)| columns)
+        .expect("missing key clause");
+    let sql = format!("{},\n  PRIMARY KEY({}))", head, key_columns);
+    let mut statements = Parser::parse_sql(&MySqlDialect {}, &sql)
+        .unwrap_or_else(|e| panic!("failed to parse {}: {}", sql, e));
+    match statements.pop() {
+        Some(Statement::CreateTable(create)) if statements.is_empty() => create,
+        other => panic!("unexpected statement: {:?}", other),
+    }
+}
+
+/// 检查列名、注释、主键和表名原样往返
+fn assert_round_trip(
+    create: &sqlparser::ast::CreateTable,
+    schema: &TableSchema,
+    database: &str,
+    table: &str,
+) {
+    let names: Vec<&str> = create.name.0.iter().map(|i| i.value.as_str()).collect();
+    assert_eq!(names, vec![database, table]);
+
+    let mut expected: Vec<&Column> = schema.columns.iter().collect();
+    let mut parsed: Vec<(String, Option<String>)> = create
+        .columns
+        .iter()
+        .map(|c| {
+            let comment = c.options.iter().find_map(|o| match &o.option {
+                ColumnOption::Comment(comment) => Some(comment.clone()),
+                _ => None,
+            });
+            (c.name.value.clone(), comment)
+        })
+        .collect();
+    expected.sort_by(|a, b| a.name.cmp(&b.name));
+    parsed.sort();
+    let expected: Vec<(String, Option<String>)> = expected
+        .into_iter()
+        .map(|c| (c.name.clone(), c.comment.clone()))
+        .collect();
+    assert_eq!(parsed, expected);
+
+    let key: Vec<String> = create
+        .constraints
+        .iter()
+        .find_map(|c| match c {
+            TableConstraint::PrimaryKey { columns, .. } => {
+                Some(columns.iter().map(|i| i.value.clone()).collect())
+            }
+            _ => None,
+        })
+        .expect("missing primary key");
+    let expected_key = if schema.primary_keys.is_empty() {
+        vec![schema.columns[0].name.clone()]
+    } else {
+        schema.primary_keys.clone()
+    };
+    assert_eq!(key, expected_key);
+}
+
+fn sr_config() -> DatabaseConfig {
+    DatabaseConfig {
+        id: 2,
+        name: "sr".to_string(),
+        db_type: DbType::StarRocks,
+        host: "sr-fe".to_string(),
+        port: 9030,
+        username: "root".to_string(),
+        password: "password".to_string(),
+        no_password: false,
+        database_name: None,
+        proxy: None,
+        tls: None,
+        created_at: chrono::Utc::now(),
+        version: 1,
+        updated_at: chrono::Utc::now(),
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn starrocks_table_ddl_round_trips(
+        schema in table_schema(),
+        database in identifier(),
+        table in identifier(),
+    ) {
+        let ddl = StarRocksDDLGenerator::generate_table_ddl(
+            &schema,
+            &database,
+            &table,
+            &StarRocksTableOptions::default(),
+            None,
+        )
+        .unwrap();
+        let create = parse_create_table(&ddl, "PRIMARY KEY(");
+        assert_round_trip(&create, &schema, &database, &table);
+
+        let drop = StarRocksDDLGenerator::generate_drop_table_ddl(&database, &table);
+        let statements = Parser::parse_sql(&MySqlDialect {}, &drop).unwrap();
+        let Statement::Drop { names, .. } = &statements[0] else {
+            panic!("unexpected statement: {}", drop);
+        };
+        let parts: Vec<&str> = names[0].0.iter().map(|i| i.value.as_str()).collect();
+        prop_assert_eq!(parts, vec![database.as_str(), table.as_str()]);
+    }
+
+    #[test]
+    fn doris_table_ddl_round_trips(
+        schema in table_schema(),
+        database in identifier(),
+        table in identifier(),
+    ) {
+        let ddl = DorisDDLGenerator::generate_table_ddl(
+            &schema,
+            &database,
+            &table,
+            &StarRocksTableOptions::default(),
+            None,
+        )
+        .unwrap();
+        let create = parse_create_table(&ddl, "UNIQUE KEY(");
+        assert_round_trip(&create, &schema, &database, &table);
+    }
+
+    #[test]
+    fn risingwave_object_names_round_trip(
+        database in identifier(),
+        table in identifier(),
+    ) {
+        let sink = format!("{}_to_sr_sink", table);
+        prop_assert_eq!(
+            parse_rw_object_name(&RisingWaveDDLGenerator::get_rw_table_name(&database, &table)),
+            vec![database.clone(), table.clone()]
+        );
+        prop_assert_eq!(
+            parse_rw_object_name(&RisingWaveDDLGenerator::get_sink_name(&database, &table)),
+            vec![database.clone(), sink]
+        );
+        prop_assert_eq!(
+            parse_rw_object_name(&RisingWaveDDLGenerator::get_source_name("src", &database)),
+            vec![database.clone(), "src_source".to_string()]
+        );
+        prop_assert_eq!(
+            RisingWaveDDLGenerator::get_source_secret_name(
+                &RisingWaveDDLGenerator::get_source_name(&table, &database)
+            ),
+            RisingWaveDDLGenerator::get_secret_name(&database)
+        );
+
+        let drop = RisingWaveDDLGenerator::generate_drop_table_ddl(&database, &table);
+        let statements = Parser::parse_sql(&PostgreSqlDialect {}, &drop).unwrap();
+        let Statement::Drop { names, .. } = &statements[0] else {
+            panic!("unexpected statement: {}", drop);
+        };
+        let parts: Vec<&str> = names[0].0.iter().map(|i| i.value.as_str()).collect();
+        prop_assert_eq!(parts, vec![database.as_str(), table.as_str()]);
+
+        let schema_ddl = RisingWaveDDLGenerator::generate_create_schema_ddl(&database);
+        let statements = Parser::parse_sql(&PostgreSqlDialect {}, &schema_ddl).unwrap();
+        let Statement::CreateSchema { schema_name: SchemaName::Simple(name), .. } = &statements[0]
+        else {
+            panic!("unexpected statement: {}", schema_ddl);
+        };
+        let parts: Vec<&str> = name.0.iter().map(|i| i.value.as_str()).collect();
+        prop_assert_eq!(parts, vec![database.as_str()]);
+    }
+
+    #[test]
+    fn risingwave_sink_select_round_trips(
+        schema in table_schema(),
+        database in identifier(),
+        table in identifier(),
+        renames in prop::collection::vec(
+            prop::option::of(prop_oneof![
+                "[A-Za-z_][A-Za-z0-9_]{0,10}",
+                prop::sample::select(vec!["select", "order", "user", "Id"]).prop_map(str::to_string),
+            ]),
+            8,
+        ),
+    ) {
+        let mut schema = schema;
+        if schema.primary_keys.is_empty() {
+            schema.primary_keys = vec![schema.columns[0].name.clone()];
+        }
+        let column_mapping: BTreeMap<String, String> = schema
+            .columns
+            .iter()
+            .zip(renames)
+            .filter_map(|(column, rename)| Some((column.name.clone(), rename?)))
+            .collect();
+        let request = SyncRequest {
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "src".to_string(),
+            mysql_table: "t".to_string(),
+            target_database: database.clone(),
+            target_table: table.clone(),
+            options: Default::default(),
+            row_filter: Some("1 = 1".to_string()),
+            partition: None,
+            column_mapping,
+            nested_columns: BTreeMap::new(),
+            spatial_columns: BTreeMap::new(),
+        };
+        // 重命名后目标列名重复时生成器会拒绝
+        let ddl = RisingWaveDDLGenerator::generate_sink_ddl(&sr_config(), &request, &schema);
+        prop_assume!(ddl.is_ok());
+        let ddl = ddl.unwrap();
+
+        let (head, query) = ddl.split_once(" AS\n").expect("missing SELECT");
+        let sink_name = head
+            .strip_prefix("CREATE SINK IF NOT EXISTS ")
+            .expect("missing sink name");
+        prop_assert_eq!(
+            parse_rw_object_name(sink_name),
+            vec![database.clone(), format!("{}_to_sr_sink", table)]
+        );
+        let (query, _) = query.rsplit_once("\n                   WITH (").expect("missing WITH");
+        let statements = Parser::parse_sql(&PostgreSqlDialect {}, query)
+            .unwrap_or_else(|e| panic!("failed to parse {}: {}", query, e));
+        let Statement::Query(query) = &statements[0] else {
+            panic!("unexpected statement: {}", query);
+        };
+        let SetExpr::Select(select) = query.body.as_ref() else {
+            panic!("unexpected query: {}", query);
+        };
+        let names: Vec<String> = select
+            .projection
+            .iter()
+            .map(|item| match item {
+                SelectItem::ExprWithAlias { alias, .. } => alias.value.clone(),
+                SelectItem::UnnamedExpr(sqlparser::ast::Expr::Identifier(ident)) => {
+                    ident.value.clone()
+                }
+                other => panic!("unexpected select item: {}", other),
+            })
+            .collect();
+        let expected: Vec<String> = schema
+            .columns
+            .iter()
+            .map(|c| request.target_column_name(&c.name).to_string())
+            .collect();
+        prop_assert_eq!(names, expected);
+        let TableFactor::Table { name, .. } = &select.from[0].relation else {
+            panic!("unexpected relation: {}", query);
+        };
+        let from: Vec<&str> = name.0.iter().map(|i| i.value.as_str()).collect();
+        prop_assert_eq!(from, vec![database.as_str(), table.as_str()]);
+    }
+}
@@ -1,15 +1,86 @@
-use crate::models::{DatabaseConfig, SyncRequest, TableSchema};
+use crate::models::{
+    Column, CreateKafkaSourceRequest, DatabaseConfig, DbType, IcebergSinkOptions, KafkaFormat,
+    NestedColumnMode, SpatialColumnMode, SyncRequest, TableSchema,
+};
+use crate::utils::endpoint;
+use crate::utils::sql_validator::{self, SqlDialect};
 use crate::utils::error::{AppError, Result};
-use rand::Rng;
+
+/// 需要加引号才能作为列名使用的 PostgreSQL 保留字
+const RESERVED_KEYWORDS: &[&str] = &[
+    "all", "and", "any", "array", "as", "asc", "both", "case", "cast", "check", "collate",
+    "column", "constraint", "create", "current_date", "current_time", "current_timestamp",
+    "current_user", "default", "desc", "distinct", "do", "else", "end", "except", "false",
+    "fetch", "for", "foreign", "from", "grant", "group", "having", "in", "into", "is", "join",
+    "leading", "limit", "not", "null", "offset", "on", "only", "or", "order", "primary",
+    "references", "returning", "select", "some", "table", "then", "to", "trailing", "true",
+    "union", "unique", "user", "using", "when", "where", "window", "with",
+];
 
 /// RisingWave DDL 生成器
 pub struct RisingWaveDDLGenerator;
 
 impl RisingWaveDDLGenerator {
+    /// 引用列名：小写的普通标识符保持原样，其余（大小写、保留字、特殊字符）加双引号，双引号写两次
+    pub fn quote_identifier(name: &str) -> String {
+        let plain = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            && !RESERVED_KEYWORDS.contains(&name);
+        if plain {
+            name.to_string()
+        } else {
+            Self::force_quote_identifier(name)
+        }
+    }
+
+    /// 始终加双引号，双引号写两次
+    pub fn force_quote_identifier(name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    /// 带 schema 的对象名称：schema 始终加双引号（与已创建对象的名称一致），名称按 quote_identifier 引用
+    pub fn qualified_name(schema: &str, name: &str) -> String {
+        format!(
+            "{}.{}",
+            Self::force_quote_identifier(schema),
+            Self::quote_identifier(name)
+        )
+    }
+
+    /// 升级前创建的对象名称：名称不加引号，RisingWave 把大写字母折叠为小写
+    /// 与当前名称相同时返回 None
+    pub fn legacy_name(name: &str) -> Option<String> {
+        let legacy = name.to_lowercase();
+        (legacy != name).then_some(legacy)
+    }
+
+    /// 升级前记录在元数据库中的完整名称（server.id 分配、共享对象登记）：`"schema".Name`
+    pub fn legacy_qualified_name(schema: &str, name: &str) -> String {
+        format!("{}.{}", Self::force_quote_identifier(schema), name)
+    }
+
+    /// 生成重命名对象的语句，kind 为 TABLE / SINK / SOURCE
+    pub fn generate_rename_ddl(kind: &str, schema: &str, from: &str, to: &str) -> String {
+        format!(
+            "ALTER {} {} RENAME TO {};",
+            kind,
+            Self::qualified_name(schema, from),
+            Self::quote_identifier(to)
+        )
+    }
+
     /// 生成创建 schema 的语句
     /// 使用 target_database 作为 schema 名称
     pub fn generate_create_schema_ddl(target_database: &str) -> String {
-        format!("CREATE SCHEMA IF NOT EXISTS \"{}\";", target_database)
+        format!(
+            "CREATE SCHEMA IF NOT EXISTS {};",
+            Self::force_quote_identifier(target_database)
+        )
     }
 
     /// 生成创建 SECRET 的语句（用于存储 MySQL 密码）
@@ -31,7 +102,7 @@ impl RisingWaveDDLGenerator {
 
     /// 获取 secret 名称: {target_database}.mysql_pwd
     pub fn get_secret_name(target_database: &str) -> String {
-        format!("\"{}\".mysql_pwd", target_database)
+        Self::qualified_name(target_database, "mysql_pwd")
     }
 
     /// 生成创建 StarRocks SECRET 的语句（用于存储 StarRocks 密码）
@@ -53,20 +124,19 @@ impl RisingWaveDDLGenerator {
 
     /// 获取 StarRocks secret 名称: {target_database}.starrocks_pwd
     pub fn get_starrocks_secret_name(target_database: &str) -> String {
-        format!("\"{}\".starrocks_pwd", target_database)
+        Self::qualified_name(target_database, "starrocks_pwd")
     }
 
     /// 生成数据库级别的 CDC Source 创建语句
     /// 一个 Source 对应整个 MySQL 数据库，而不是单个表
     /// Source 命名: {target_database}.{mysql_database}_source
+    /// server_id 由 ServerIdRepository 分配，重建时保持不变
     pub fn generate_source_ddl(
         mysql_config: &DatabaseConfig,
         mysql_database: &str,
         target_database: &str,
+        server_id: u32,
     ) -> Result<String> {
-        // 生成随机 server.id (避免冲突)
-        let server_id: u32 = rand::thread_rng().gen_range(5000..9999);
-
         // Source 命名: {target_database}.{mysql_database}_source
         let source_name = Self::get_source_name(mysql_database, target_database);
         let secret_name = Self::get_secret_name(target_database);
@@ -83,11 +153,11 @@ impl RisingWaveDDLGenerator {
               auto.schema.change = 'true'
             );"#,
             source_name,
-            mysql_config.host,
+            endpoint::driver_host(&mysql_config.host).replace('\'', "''"),
             mysql_config.port,
-            mysql_config.username,
+            mysql_config.username.replace('\'', "''"),
             secret_name,
-            mysql_database,
+            mysql_database.replace('\'', "''"),
             server_id
         );
 
@@ -95,7 +165,34 @@ impl RisingWaveDDLGenerator {
     }
 
     pub fn get_source_name(mysql_database: &str, target_database: &str) -> String {
-        format!("\"{}\".{}_source", target_database, mysql_database)
+        Self::qualified_name(target_database, &format!("{}_source", mysql_database))
+    }
+
+    /// Source 使用的 MySQL secret，与 Source 位于同一个 schema
+    /// schema 总是带引号，按引号边界截取，Source 名中的 `.` 不影响结果
+    pub fn get_source_secret_name(source_name: &str) -> String {
+        let Some(rest) = source_name.strip_prefix('"') else {
+            return match source_name.split_once('.') {
+                Some((schema, _)) => format!("{}.mysql_pwd", schema),
+                None => "mysql_pwd".to_string(),
+            };
+        };
+        let mut end = None;
+        let mut chars = rest.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if c == '"' {
+                if chars.peek().map(|(_, c)| *c) == Some('"') {
+                    chars.next();
+                } else {
+                    end = Some(i);
+                    break;
+                }
+            }
+        }
+        match end {
+            Some(i) => format!("{}.mysql_pwd", &source_name[..i + 2]),
+            None => "mysql_pwd".to_string(),
+        }
     }
 
     /// 生成 Table 创建语句（从 CDC Source，使用简化语法）
@@ -106,21 +203,45 @@ impl RisingWaveDDLGenerator {
         mysql_table: &str,
         target_database: &str,
         target_table: &str,
+    ) -> Result<String> {
+        Self::generate_table_ddl_from_source(
+            &Self::get_source_name(mysql_database, target_database),
+            mysql_database,
+            mysql_table,
+            target_database,
+            target_table,
+        )
+    }
+
+    /// 从指定的 CDC Source 创建 Table，用于共享其他 schema 中的 Source
+    pub fn generate_table_ddl_from_source(
+        source_name: &str,
+        mysql_database: &str,
+        mysql_table: &str,
+        target_database: &str,
+        target_table: &str,
     ) -> Result<String> {
         let table_name = Self::get_rw_table_name(target_database, target_table);
-        let source_name = Self::get_source_name(mysql_database, target_database);
 
         // 使用 (*) 语法自动推断所有列
         let ddl = format!(
             r#"CREATE TABLE IF NOT EXISTS {} (*) FROM {} TABLE '{}.{}';"#,
-            table_name, source_name, mysql_database, mysql_table
+            table_name,
+            source_name,
+            mysql_database.replace('\'', "''"),
+            mysql_table.replace('\'', "''")
         );
 
         Ok(ddl)
     }
 
     pub fn get_rw_table_name(target_database: &str, target_table: &str) -> String {
-        format!("\"{}\".{}", target_database, target_table)
+        Self::qualified_name(target_database, target_table)
+    }
+
+    /// Sink 名称: {target_database}.{target_table}_to_sr_sink
+    pub fn get_sink_name(target_database: &str, target_table: &str) -> String {
+        Self::qualified_name(target_database, &format!("{}_to_sr_sink", target_table))
     }
 
     /// 生成 Sink 到 StarRocks 的语句
@@ -135,10 +256,7 @@ impl RisingWaveDDLGenerator {
             Self::get_rw_table_name(&request.target_database, &request.target_table);
 
         // Sink 命名: {target_database}.{target_table}_to_sr_sink
-        let sink_name = format!(
-            "\"{}\".{}_to_sr_sink",
-            &request.target_database, &request.target_table
-        );
+        let sink_name = Self::get_sink_name(&request.target_database, &request.target_table);
 
         // 获取 StarRocks secret 名称
         let sr_secret_name = Self::get_starrocks_secret_name(&request.target_database);
@@ -151,112 +269,612 @@ impl RisingWaveDDLGenerator {
             )));
         }
 
-        // 检查是否有需要类型转换的列
+        Self::validate_column_mapping(request, schema)?;
+
+        // 检查是否有需要类型转换或重命名的列
         let mut needs_type_conversion = false;
         let mut select_columns = Vec::new();
 
         for col in &schema.columns {
             let col_type_upper = col.data_type.to_uppercase();
             let base_type = col_type_upper.split('(').next().unwrap_or(&col_type_upper);
+            let source_name = Self::quote_identifier(&col.name);
+            let target_name = request.target_column_name(&col.name);
+            let renamed = target_name != col.name;
+            let target_name = Self::quote_identifier(target_name);
+
+            // 嵌套类型列：STRUCT 字段逐个取出，或序列化为 JSON 字符串
+            if let Some(fields) = request.flattened_fields(col) {
+                needs_type_conversion = true;
+                for (path, field) in fields {
+                    let expr = path.iter().fold(source_name.clone(), |expr, name| {
+                        format!("({}).{}", expr, Self::quote_identifier(name))
+                    });
+                    select_columns.push(format!("{} as {}", expr, Self::quote_identifier(&field.name)));
+                }
+                continue;
+            }
+            if request.nested_columns.get(&col.name) == Some(&NestedColumnMode::Json) {
+                needs_type_conversion = true;
+                select_columns.push(format!("to_jsonb({})::VARCHAR as {}", source_name, target_name));
+                continue;
+            }
+
+            // 空间类型列：去掉 MySQL 内部格式前 4 字节的 SRID，得到标准 WKB
+            if let Some(mode) = request.spatial_mode(col) {
+                needs_type_conversion = true;
+                let wkb = format!("substr({}, 5)", source_name);
+                select_columns.push(match mode {
+                    SpatialColumnMode::Hex => format!("encode({}, 'hex') as {}", wkb, target_name),
+                    SpatialColumnMode::Binary => format!("{} as {}", wkb, target_name),
+                });
+                continue;
+            }
 
             // MySQL TIMESTAMP/DATETIME -> RisingWave TIMESTAMPTZ -> StarRocks DATETIME
             // 需要转换为 TIMESTAMP（不带时区）
-            let _ = match base_type {
+            match base_type {
                 "TIMESTAMP" | "DATETIME" => {
                     needs_type_conversion = true;
-                    select_columns.push(format!("{}::TIMESTAMP as {}", col.name, col.name));
+                    select_columns.push(format!("{}::TIMESTAMP as {}", source_name, target_name));
                 }
                 "TINYINT" => {
                     needs_type_conversion = true;
                     select_columns.push(format!(
                         "case {} when 1 then 1 when 0 then 0 else {} end as {}",
-                        col.name, col.name, col.name
+                        source_name, source_name, target_name
                     ));
                 }
+                _ if renamed => {
+                    needs_type_conversion = true;
+                    select_columns.push(format!("{} as {}", source_name, target_name));
+                }
                 _ => {
-                    select_columns.push(col.name.clone());
+                    select_columns.push(source_name);
                 }
-            };
+            }
         }
 
-        let ddl = if needs_type_conversion {
-            // 使用 SELECT 语句进行类型转换
+        // 派生分区列：按粒度截断来源列，TIMESTAMPTZ 先转为 TIMESTAMP
+        let derivation = request.partition.as_ref().and_then(|p| p.derivation());
+        if let Some((derived, column, granularity)) = derivation {
+            let source = Self::derivation_source(request, schema, column)?;
+            let cast = if granularity.derived_type() == "DATE" { "::DATE" } else { "" };
+            needs_type_conversion = true;
+            select_columns.push(format!(
+                "date_trunc('{}', {}::TIMESTAMP){} as {}",
+                granularity.as_str(),
+                Self::quote_identifier(&source.name),
+                cast,
+                Self::quote_identifier(derived)
+            ));
+        }
+
+        // StarRocks 端的主键使用目标列名，派生分区列也属于主键
+        let primary_key = schema
+            .primary_keys
+            .iter()
+            .map(|pk| request.target_column_name(pk))
+            .chain(derivation.map(|(derived, _, _)| derived))
+            .collect::<Vec<_>>()
+            .join(",")
+            .replace('\'', "''");
+
+        // 行过滤条件
+        let where_clause = match request.row_filter.as_deref().map(str::trim) {
+            Some(filter) if !filter.is_empty() => {
+                Self::validate_row_filter(filter)?;
+                format!("\n                   WHERE {}", filter)
+            }
+            _ => String::new(),
+        };
+
+        let connector = Self::sink_connector_properties(
+            sr_config,
+            &request.target_database,
+            &request.target_table,
+            &sr_secret_name,
+        );
+
+        let ddl = if needs_type_conversion || !where_clause.is_empty() {
+            // 使用 SELECT 语句进行类型转换和行过滤
             format!(
                 r#"CREATE SINK IF NOT EXISTS {} AS
                    SELECT
                    {}
-                   FROM {}
+                   FROM {}{}
                    WITH (
-                   connector = 'starrocks',
-                   starrocks.host = '{}',
-                   starrocks.mysqlport = '{}',
-                   starrocks.httpport = '8030',
-                   starrocks.user = '{}',
-                   starrocks.password = secret {},
-                   starrocks.database = '{}',
-                   starrocks.table = '{}',
+                   {},
                    type = 'upsert',
                    primary_key = '{}'
                    );"#,
                 sink_name,
                 select_columns.join(",\n  "),
                 rw_table_name,
-                sr_config.host,
-                sr_config.port,
-                sr_config.username,
-                sr_secret_name,
-                &request.target_database,
-                &request.target_table,
-                schema.primary_keys.join(",")
+                where_clause,
+                connector,
+                primary_key
             )
         } else {
             // 不需要类型转换，直接从表创建 sink
             format!(
                 r#"CREATE SINK IF NOT EXISTS {} FROM {}
                    WITH (
-                   connector = 'starrocks',
-                   starrocks.host = '{}',
-                   starrocks.mysqlport = '{}',
-                   starrocks.httpport = '8030',
-                   starrocks.user = '{}',
-                   starrocks.password = secret {},
-                   starrocks.database = '{}',
-                   starrocks.table = '{}',
+                   {},
                    type = 'upsert',
                    primary_key = '{}'
                    );"#,
                 sink_name,
                 rw_table_name,
-                sr_config.host,
-                sr_config.port,
-                sr_config.username,
-                sr_secret_name,
-                &request.target_database,
-                &request.target_table,
-                schema.primary_keys.join(",")
+                connector,
+                primary_key
             )
         };
 
         Ok(ddl)
     }
 
+    /// 生成 sink 的 connector 属性，按目标库类型选择 starrocks 或 doris connector
+    /// 两者都通过 FE 的 HTTP 端口（8030）Stream Load 写入
+    fn sink_connector_properties(
+        target_config: &DatabaseConfig,
+        target_database: &str,
+        target_table: &str,
+        secret_name: &str,
+    ) -> String {
+        let literal = |value: &str| format!("'{}'", value.replace('\'', "''"));
+        let properties = match target_config.db_type {
+            DbType::Doris => vec![
+                ("connector", "'doris'".to_string()),
+                (
+                    "doris.url",
+                    literal(&format!("http://{}:8030", endpoint::url_host(&target_config.host))),
+                ),
+                ("doris.user", literal(&target_config.username)),
+                ("doris.password", format!("secret {}", secret_name)),
+                ("doris.database", literal(target_database)),
+                ("doris.table", literal(target_table)),
+            ],
+            _ => vec![
+                ("connector", "'starrocks'".to_string()),
+                (
+                    "starrocks.host",
+                    literal(&endpoint::url_host(&target_config.host)),
+                ),
+                ("starrocks.mysqlport", format!("'{}'", target_config.port)),
+                ("starrocks.httpport", "'8030'".to_string()),
+                ("starrocks.user", literal(&target_config.username)),
+                ("starrocks.password", format!("secret {}", secret_name)),
+                ("starrocks.database", literal(target_database)),
+                ("starrocks.table", literal(target_table)),
+            ],
+        };
+
+        properties
+            .into_iter()
+            .map(|(key, value)| format!("{} = {}", key, value))
+            .collect::<Vec<_>>()
+            .join(",\n                   ")
+    }
+
+    /// 校验列名映射：源列必须存在，目标列名合法且不重复
+    pub fn validate_column_mapping(request: &SyncRequest, schema: &TableSchema) -> Result<()> {
+        for (source, target) in &request.column_mapping {
+            if !schema.columns.iter().any(|c| &c.name == source) {
+                return Err(AppError::Validation(format!(
+                    "Mapped column not found in {}.{}: {}",
+                    request.mysql_database, request.mysql_table, source
+                )));
+            }
+            if target.is_empty()
+                || !target.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                return Err(AppError::Validation(format!(
+                    "Invalid target column name: {}",
+                    target
+                )));
+            }
+        }
+
+        for (source, mode) in &request.nested_columns {
+            let Some(column) = schema.columns.iter().find(|c| &c.name == source) else {
+                return Err(AppError::Validation(format!(
+                    "Nested column not found in {}.{}: {}",
+                    request.mysql_database, request.mysql_table, source
+                )));
+            };
+            if schema.primary_keys.contains(source) {
+                return Err(AppError::Validation(format!(
+                    "Primary key column {} cannot be flattened or serialized",
+                    source
+                )));
+            }
+            let supported = match mode {
+                NestedColumnMode::Flatten => column.struct_fields().is_some(),
+                NestedColumnMode::Json => column.is_nested(),
+            };
+            if !supported {
+                return Err(AppError::Validation(format!(
+                    "Column {} ({}) cannot be written as {:?}: flatten requires a STRUCT column, json requires a STRUCT, array, MAP or JSON column",
+                    source, column.data_type, mode
+                )));
+            }
+        }
+
+        for source in request.spatial_columns.keys() {
+            let Some(column) = schema.columns.iter().find(|c| &c.name == source) else {
+                return Err(AppError::Validation(format!(
+                    "Spatial column not found in {}.{}: {}",
+                    request.mysql_database, request.mysql_table, source
+                )));
+            };
+            if !column.is_spatial() {
+                return Err(AppError::Validation(format!(
+                    "Column {} ({}) is not a spatial column",
+                    source, column.data_type
+                )));
+            }
+        }
+
+        let mut target_names = std::collections::HashSet::new();
+        for col in &schema.columns {
+            for target in request.target_columns(col, schema.type_source) {
+                if !target_names.insert(target.name.to_lowercase()) {
+                    return Err(AppError::Validation(format!(
+                        "Duplicate target column name: {}",
+                        target.name
+                    )));
+                }
+            }
+        }
+
+        if let Some((derived, column, _)) = request.partition.as_ref().and_then(|p| p.derivation()) {
+            if derived.is_empty()
+                || !derived.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                return Err(AppError::Validation(format!(
+                    "Invalid derived partition column name: {}",
+                    derived
+                )));
+            }
+            if target_names.contains(&derived.to_lowercase()) {
+                return Err(AppError::Validation(format!(
+                    "Derived partition column {} conflicts with an existing column",
+                    derived
+                )));
+            }
+            Self::derivation_source(request, schema, column)?;
+        }
+
+        Ok(())
+    }
+
+    /// 派生分区列的来源列（按目标列名查找），必须是 NOT NULL 的日期时间列
+    fn derivation_source<'a>(
+        request: &SyncRequest,
+        schema: &'a TableSchema,
+        column: &str,
+    ) -> Result<&'a Column> {
+        let source = schema
+            .columns
+            .iter()
+            .find(|c| request.target_column_name(&c.name) == column)
+            .ok_or_else(|| {
+                AppError::Validation(format!("Partition column not found: {}", column))
+            })?;
+
+        let data_type = source.data_type.to_uppercase();
+        let base_type = data_type.split('(').next().unwrap_or(&data_type).trim();
+        if !matches!(base_type, "DATE" | "DATETIME" | "TIMESTAMP") {
+            return Err(AppError::Validation(format!(
+                "Partition column {} must be DATE, DATETIME or TIMESTAMP to derive a partition key, got {}",
+                column, source.data_type
+            )));
+        }
+        if source.is_nullable {
+            return Err(AppError::Validation(format!(
+                "Partition column {} must be NOT NULL to derive a partition key",
+                column
+            )));
+        }
+        Ok(source)
+    }
+
+    /// 校验行过滤条件：必须是单个完整的表达式且不带注释，避免拼接进 DDL 后改变语义
+    fn validate_row_filter(filter: &str) -> Result<()> {
+        sql_validator::validate_expression(filter, SqlDialect::RisingWave)
+    }
+
+    /// Iceberg S3 secret 名称: {schema}.{sink}_s3_key
+    pub fn get_iceberg_secret_name(schema: &str, sink_name: &str) -> String {
+        Self::qualified_name(schema, &format!("{}_s3_key", sink_name))
+    }
+
+    /// 生成存储 Iceberg S3 secret key 的 SECRET，没有配置时返回 None
+    pub fn generate_iceberg_secret_ddl(
+        options: &IcebergSinkOptions,
+        schema: &str,
+        sink_name: &str,
+    ) -> Option<String> {
+        options.s3_secret_key.as_ref().map(|key| {
+            format!(
+                r#"CREATE SECRET IF NOT EXISTS {} WITH ( backend = 'meta' ) AS '{}';"#,
+                Self::get_iceberg_secret_name(schema, sink_name),
+                key.replace('\'', "''")
+            )
+        })
+    }
+
+    /// 生成 Sink 到 Iceberg 的语句
+    /// Sink 命名: {schema}.{target_table}_to_iceberg_sink
+    pub fn generate_iceberg_sink_ddl(
+        options: &IcebergSinkOptions,
+        schema: &TableSchema,
+        target_database: &str,
+        target_table: &str,
+    ) -> Result<String> {
+        const CATALOG_TYPES: [&str; 5] = ["storage", "rest", "glue", "hive", "jdbc"];
+        if !CATALOG_TYPES.contains(&options.catalog_type.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Invalid Iceberg catalog_type: {}",
+                options.catalog_type
+            )));
+        }
+        if options.catalog_type != "storage"
+            && options.catalog_type != "glue"
+            && options.catalog_uri.is_none()
+        {
+            return Err(AppError::Validation(format!(
+                "catalog_uri is required for {} catalog",
+                options.catalog_type
+            )));
+        }
+        if options.warehouse_path.trim().is_empty() {
+            return Err(AppError::Validation("warehouse_path is required".to_string()));
+        }
+        if !options.append_only && schema.primary_keys.is_empty() {
+            return Err(AppError::SqlGeneration(format!(
+                "{} has no primary key, use append_only for Iceberg sink",
+                schema.table_name
+            )));
+        }
+
+        let sink_name = format!("{}_to_iceberg_sink", target_table);
+        let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+
+        let mut properties = vec![("connector", quote("iceberg"))];
+        if options.append_only {
+            properties.push(("type", quote("append-only")));
+            properties.push(("force_append_only", quote("true")));
+        } else {
+            properties.push(("type", quote("upsert")));
+            properties.push(("primary_key", quote(&schema.primary_keys.join(","))));
+        }
+        properties.push(("catalog.type", quote(&options.catalog_type)));
+        if let Some(uri) = &options.catalog_uri {
+            properties.push(("catalog.uri", quote(uri)));
+        }
+        if let Some(name) = &options.catalog_name {
+            properties.push(("catalog.name", quote(name)));
+        }
+        properties.push(("warehouse.path", quote(options.warehouse_path.trim())));
+        if let Some(endpoint) = &options.s3_endpoint {
+            properties.push(("s3.endpoint", quote(endpoint)));
+        }
+        if let Some(region) = &options.s3_region {
+            properties.push(("s3.region", quote(region)));
+        }
+        if let Some(access_key) = &options.s3_access_key {
+            properties.push(("s3.access.key", quote(access_key)));
+        }
+        if options.s3_secret_key.is_some() {
+            properties.push((
+                "s3.secret.key",
+                format!(
+                    "secret {}",
+                    Self::get_iceberg_secret_name(&schema.database, &sink_name)
+                ),
+            ));
+        }
+        properties.push(("database.name", quote(target_database)));
+        properties.push(("table.name", quote(target_table)));
+        if options.create_table_if_not_exists {
+            properties.push(("create_table_if_not_exists", quote("true")));
+        }
+
+        Ok(format!(
+            "CREATE SINK IF NOT EXISTS {} FROM {}\nWITH (\n  {}\n);",
+            Self::qualified_name(&schema.database, &sink_name),
+            Self::get_rw_table_name(&schema.database, &schema.table_name),
+            properties
+                .into_iter()
+                .map(|(key, value)| format!("{} = {}", key, value))
+                .collect::<Vec<_>>()
+                .join(",\n  ")
+        ))
+    }
+
+    /// 生成从 Kafka topic 读取的 Table 创建语句
+    /// 使用带 connector 的 TABLE 而不是 SOURCE，这样可以直接用于创建 upsert sink
+    pub fn generate_kafka_table_ddl(request: &CreateKafkaSourceRequest) -> Result<String> {
+        let is_identifier = |s: &str| {
+            !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+        let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+
+        if !is_identifier(&request.schema) || !is_identifier(&request.name) {
+            return Err(AppError::Validation(format!(
+                "Invalid table name: {}.{}",
+                request.schema, request.name
+            )));
+        }
+        if request.bootstrap_servers.trim().is_empty() || request.topic.trim().is_empty() {
+            return Err(AppError::Validation(
+                "bootstrap_servers and topic are required".to_string(),
+            ));
+        }
+
+        let mut definitions = Vec::new();
+        for column in &request.columns {
+            let valid_type = !column.data_type.is_empty()
+                && column
+                    .data_type
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || " _(),[]".contains(c));
+            if !is_identifier(&column.name) || !valid_type {
+                return Err(AppError::Validation(format!(
+                    "Invalid column definition: {} {}",
+                    column.name, column.data_type
+                )));
+            }
+            definitions.push(format!("{} {}", column.name, column.data_type));
+        }
+
+        for pk in &request.primary_key {
+            let declared = request.columns.iter().any(|c| &c.name == pk);
+            // Avro 可以省略列定义，此时只校验名称
+            if !is_identifier(pk) || (!declared && request.format == KafkaFormat::Json) {
+                return Err(AppError::Validation(format!(
+                    "Primary key column not found: {}",
+                    pk
+                )));
+            }
+        }
+        if !request.primary_key.is_empty() {
+            definitions.push(format!("PRIMARY KEY ({})", request.primary_key.join(", ")));
+        }
+
+        let encode = match request.format {
+            KafkaFormat::Json => {
+                if request.columns.is_empty() {
+                    return Err(AppError::Validation(
+                        "Columns are required for JSON format".to_string(),
+                    ));
+                }
+                "JSON".to_string()
+            }
+            KafkaFormat::Avro => {
+                let registry = request
+                    .schema_registry
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|r| !r.is_empty())
+                    .ok_or_else(|| {
+                        AppError::Validation(
+                            "schema_registry is required for Avro format".to_string(),
+                        )
+                    })?;
+                format!("AVRO (schema.registry = {})", quote(registry))
+            }
+        };
+
+        let startup_mode = request.scan_startup_mode.as_deref().unwrap_or("earliest");
+        if startup_mode != "earliest" && startup_mode != "latest" {
+            return Err(AppError::Validation(format!(
+                "Invalid scan_startup_mode: {}",
+                startup_mode
+            )));
+        }
+
+        let mut properties = vec![
+            "connector = 'kafka'".to_string(),
+            format!("topic = {}", quote(request.topic.trim())),
+            format!(
+                "properties.bootstrap.server = {}",
+                quote(request.bootstrap_servers.trim())
+            ),
+            format!("scan.startup.mode = '{}'", startup_mode),
+        ];
+        for (key, value) in &request.properties {
+            if key.is_empty()
+                || !key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+            {
+                return Err(AppError::Validation(format!(
+                    "Invalid connector property: {}",
+                    key
+                )));
+            }
+            properties.push(format!("{} = {}", key, quote(value)));
+        }
+
+        let columns = if definitions.is_empty() {
+            String::new()
+        } else {
+            format!(" (\n  {}\n)", definitions.join(",\n  "))
+        };
+
+        Ok(format!(
+            "CREATE TABLE IF NOT EXISTS {}{} WITH (\n  {}\n) FORMAT PLAIN ENCODE {};",
+            Self::get_rw_table_name(&request.schema, &request.name),
+            columns,
+            properties.join(",\n  "),
+            encode
+        ))
+    }
+
     /// 生成删除 Table 的语句
     pub fn generate_drop_table_ddl(target_database: &str, target_table: &str) -> String {
-        let table_name = format!("\"{}\".{}", target_database, target_table);
-        format!("DROP TABLE IF EXISTS {} CASCADE;", table_name)
+        format!(
+            "DROP TABLE IF EXISTS {} CASCADE;",
+            Self::get_rw_table_name(target_database, target_table)
+        )
+    }
+
+    /// 生成删除表的语句，不带 CASCADE，表仍被其他对象依赖时删除失败
+    pub fn generate_drop_table_restrict_ddl(target_database: &str, target_table: &str) -> String {
+        format!(
+            "DROP TABLE IF EXISTS {};",
+            Self::get_rw_table_name(target_database, target_table)
+        )
+    }
+
+    /// 生成删除共享 Source 的语句，name 为完整名称
+    pub fn generate_drop_source_ddl(source_name: &str) -> String {
+        format!("DROP SOURCE IF EXISTS {};", source_name)
+    }
+
+    /// 生成修改 SECRET 内容的语句（密码轮换），name 为完整名称
+    pub fn generate_alter_secret_ddl(secret_name: &str, value: &str) -> String {
+        format!(
+            r#"ALTER SECRET {} WITH ( backend = 'meta' ) AS '{}';"#,
+            secret_name,
+            value.replace('\'', "''")
+        )
+    }
+
+    /// 生成删除 SECRET 的语句，name 为完整名称
+    pub fn generate_drop_secret_ddl(secret_name: &str) -> String {
+        format!("DROP SECRET IF EXISTS {};", secret_name)
     }
 
     /// 生成删除 Sink 的语句
     pub fn generate_drop_sink_ddl(target_database: &str, target_table: &str) -> String {
-        let sink_name = format!("\"{}\".{}_to_sr_sink", target_database, target_table);
-        format!("DROP SINK IF EXISTS {};", sink_name)
+        format!(
+            "DROP SINK IF EXISTS {};",
+            Self::get_sink_name(target_database, target_table)
+        )
+    }
+
+    /// 生成暂停或恢复 Sink 写入的语句：暂停时限速为 0，恢复时取消限速
+    pub fn generate_sink_rate_limit_ddl(
+        target_database: &str,
+        target_table: &str,
+        paused: bool,
+    ) -> String {
+        format!(
+            "ALTER SINK {} SET SINK_RATE_LIMIT TO {};",
+            Self::get_sink_name(target_database, target_table),
+            if paused { "0" } else { "DEFAULT" }
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::DbType;
+    use crate::models::ColumnTypeSource;
 
     #[test]
     fn test_generate_schema_ddl() {
@@ -274,8 +892,12 @@ mod tests {
             port: 3306,
             username: "root".to_string(),
             password: "my'password".to_string(),
+            no_password: false,
             database_name: Some("test_db".to_string()),
+            proxy: None,
+            tls: None,
             created_at: chrono::Utc::now(),
+            version: 1,
             updated_at: chrono::Utc::now(),
         };
 
@@ -294,15 +916,20 @@ mod tests {
             port: 3306,
             username: "root".to_string(),
             password: "password".to_string(),
+            no_password: false,
             database_name: Some("test_db".to_string()),
+            proxy: None,
+            tls: None,
             created_at: chrono::Utc::now(),
+            version: 1,
             updated_at: chrono::Utc::now(),
         };
 
-        let ddl = RisingWaveDDLGenerator::generate_source_ddl(&config, "apnv3", "ods_apn").unwrap();
+        let ddl = RisingWaveDDLGenerator::generate_source_ddl(&config, "apnv3", "ods_apn", 5001).unwrap();
         assert!(ddl.contains("CREATE SOURCE IF NOT EXISTS \"ods_apn\".apnv3_source"));
         assert!(ddl.contains("connector = 'mysql-cdc'"));
         assert!(ddl.contains("auto.schema.change = 'true'"));
+        assert!(ddl.contains("server.id = '5001'"));
         assert!(ddl.contains("password = secret \"ods_apn\".mysql_pwd"));
     }
 
@@ -318,4 +945,470 @@ mod tests {
         assert!(ddl.contains("CREATE TABLE IF NOT EXISTS \"ods_apn\".invoice_activity (*)"));
         assert!(ddl.contains("FROM \"ods_apn\".apnv3_source TABLE 'apnv3.invoice_activity'"));
     }
+
+    #[test]
+    fn test_generate_table_ddl_from_shared_source() {
+        let source_name = RisingWaveDDLGenerator::get_source_name("apnv3", "ods_apn");
+        let ddl = RisingWaveDDLGenerator::generate_table_ddl_from_source(
+            &source_name,
+            "apnv3",
+            "invoice_activity",
+            "dw_apn",
+            "invoice_activity",
+        )
+        .unwrap();
+        assert!(ddl.contains("CREATE TABLE IF NOT EXISTS \"dw_apn\".invoice_activity (*)"));
+        assert!(ddl.contains("FROM \"ods_apn\".apnv3_source TABLE 'apnv3.invoice_activity'"));
+        assert_eq!(
+            RisingWaveDDLGenerator::get_source_secret_name(&source_name),
+            RisingWaveDDLGenerator::get_secret_name("ods_apn")
+        );
+        assert_eq!(
+            crate::models::ManagedObject::source_share_key(1, "apnv3"),
+            "mysql:1/apnv3"
+        );
+    }
+
+    fn create_sr_config() -> DatabaseConfig {
+        DatabaseConfig {
+            id: 2,
+            name: "sr".to_string(),
+            db_type: DbType::StarRocks,
+            host: "sr-fe".to_string(),
+            port: 9030,
+            username: "root".to_string(),
+            password: "password".to_string(),
+            no_password: false,
+            database_name: None,
+            proxy: None,
+            tls: None,
+            created_at: chrono::Utc::now(),
+            version: 1,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn create_sink_request(row_filter: Option<&str>) -> SyncRequest {
+        SyncRequest {
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "apnv3".to_string(),
+            mysql_table: "orders".to_string(),
+            target_database: "ods_apn".to_string(),
+            target_table: "orders".to_string(),
+            options: Default::default(),
+            row_filter: row_filter.map(str::to_string),
+            partition: None,
+            column_mapping: Default::default(),
+            nested_columns: Default::default(),
+            spatial_columns: Default::default(),
+        }
+    }
+
+    fn create_sink_schema() -> TableSchema {
+        let column = |name: &str, data_type: &str| crate::models::Column {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_nullable: false,
+            default_value: None,
+            comment: None,
+            character_maximum_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+        };
+
+        TableSchema {
+            database: "apnv3".to_string(),
+            table_name: "orders".to_string(),
+            columns: vec![column("id", "bigint"), column("amount", "decimal(10,2)")],
+            primary_keys: vec!["id".to_string()],
+            indexes: vec![],
+            type_source: ColumnTypeSource::MySQL,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn test_generate_sink_ddl_without_filter() {
+        let ddl = RisingWaveDDLGenerator::generate_sink_ddl(
+            &create_sr_config(),
+            &create_sink_request(None),
+            &create_sink_schema(),
+        )
+        .unwrap();
+        assert!(ddl.contains("CREATE SINK IF NOT EXISTS \"ods_apn\".orders_to_sr_sink FROM \"ods_apn\".orders"));
+        assert!(!ddl.contains("WHERE"));
+        assert!(ddl.contains("connector = 'starrocks',\n                   starrocks.host = 'sr-fe'"));
+    }
+
+    fn create_kafka_request() -> CreateKafkaSourceRequest {
+        CreateKafkaSourceRequest {
+            rw_config_id: 1,
+            schema: "ods_events".to_string(),
+            name: "clicks".to_string(),
+            bootstrap_servers: "kafka:9092".to_string(),
+            topic: "clicks".to_string(),
+            format: KafkaFormat::Json,
+            schema_registry: None,
+            columns: vec![
+                crate::models::KafkaColumn {
+                    name: "id".to_string(),
+                    data_type: "BIGINT".to_string(),
+                },
+                crate::models::KafkaColumn {
+                    name: "url".to_string(),
+                    data_type: "VARCHAR".to_string(),
+                },
+            ],
+            primary_key: vec!["id".to_string()],
+            scan_startup_mode: None,
+            properties: [("properties.security.protocol".to_string(), "SASL_SSL".to_string())]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_generate_kafka_table_ddl() {
+        let ddl = RisingWaveDDLGenerator::generate_kafka_table_ddl(&create_kafka_request()).unwrap();
+        assert!(ddl.starts_with("CREATE TABLE IF NOT EXISTS \"ods_events\".clicks (\n  id BIGINT,\n  url VARCHAR,\n  PRIMARY KEY (id)\n)"));
+        assert!(ddl.contains("connector = 'kafka'"));
+        assert!(ddl.contains("properties.bootstrap.server = 'kafka:9092'"));
+        assert!(ddl.contains("scan.startup.mode = 'earliest'"));
+        assert!(ddl.contains("properties.security.protocol = 'SASL_SSL'"));
+        assert!(ddl.ends_with("FORMAT PLAIN ENCODE JSON;"));
+
+        let avro = CreateKafkaSourceRequest {
+            format: KafkaFormat::Avro,
+            schema_registry: Some("http://registry:8081".to_string()),
+            columns: vec![],
+            ..create_kafka_request()
+        };
+        let ddl = RisingWaveDDLGenerator::generate_kafka_table_ddl(&avro).unwrap();
+        assert!(ddl.contains("clicks (\n  PRIMARY KEY (id)\n)"));
+        assert!(ddl.ends_with("ENCODE AVRO (schema.registry = 'http://registry:8081');"));
+    }
+
+    #[test]
+    fn test_generate_kafka_table_ddl_rejects_invalid_input() {
+        let no_registry = CreateKafkaSourceRequest {
+            format: KafkaFormat::Avro,
+            ..create_kafka_request()
+        };
+        assert!(RisingWaveDDLGenerator::generate_kafka_table_ddl(&no_registry).is_err());
+
+        let bad_pk = CreateKafkaSourceRequest {
+            primary_key: vec!["missing".to_string()],
+            ..create_kafka_request()
+        };
+        assert!(RisingWaveDDLGenerator::generate_kafka_table_ddl(&bad_pk).is_err());
+
+        let mut bad_property = create_kafka_request();
+        bad_property
+            .properties
+            .insert("topic = 'x', connector".to_string(), "y".to_string());
+        assert!(RisingWaveDDLGenerator::generate_kafka_table_ddl(&bad_property).is_err());
+    }
+
+    #[test]
+    fn test_generate_iceberg_sink_ddl() {
+        let options = IcebergSinkOptions {
+            catalog_type: "rest".to_string(),
+            catalog_uri: Some("http://iceberg-rest:8181".to_string()),
+            catalog_name: None,
+            warehouse_path: "s3://lake/warehouse".to_string(),
+            s3_endpoint: None,
+            s3_region: Some("us-east-1".to_string()),
+            s3_access_key: Some("AKIA".to_string()),
+            s3_secret_key: Some("secret".to_string()),
+            append_only: false,
+            create_table_if_not_exists: true,
+        };
+        let mut schema = create_sink_schema();
+        schema.database = "ods_apn".to_string();
+
+        let ddl =
+            RisingWaveDDLGenerator::generate_iceberg_sink_ddl(&options, &schema, "lake", "orders")
+                .unwrap();
+        assert!(ddl.starts_with(
+            "CREATE SINK IF NOT EXISTS \"ods_apn\".orders_to_iceberg_sink FROM \"ods_apn\".orders"
+        ));
+        assert!(ddl.contains("connector = 'iceberg'"));
+        assert!(ddl.contains("primary_key = 'id'"));
+        assert!(ddl.contains("catalog.uri = 'http://iceberg-rest:8181'"));
+        assert!(ddl.contains("s3.secret.key = secret \"ods_apn\".orders_to_iceberg_sink_s3_key"));
+        assert!(ddl.contains("database.name = 'lake'"));
+        assert!(!ddl.contains("'secret'"));
+
+        let missing_uri = IcebergSinkOptions {
+            catalog_uri: None,
+            ..options.clone()
+        };
+        assert!(
+            RisingWaveDDLGenerator::generate_iceberg_sink_ddl(&missing_uri, &schema, "lake", "orders")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_generate_doris_sink_ddl() {
+        let doris_config = DatabaseConfig {
+            db_type: DbType::Doris,
+            host: "doris-fe".to_string(),
+            ..create_sr_config()
+        };
+        let ddl = RisingWaveDDLGenerator::generate_sink_ddl(
+            &doris_config,
+            &create_sink_request(None),
+            &create_sink_schema(),
+        )
+        .unwrap();
+        assert!(ddl.contains("connector = 'doris'"));
+        assert!(ddl.contains("doris.url = 'http://doris-fe:8030'"));
+        assert!(ddl.contains("doris.password = secret \"ods_apn\".starrocks_pwd"));
+        assert!(ddl.contains("doris.table = 'orders'"));
+        assert!(!ddl.contains("starrocks.host"));
+    }
+
+    #[test]
+    fn test_generate_sink_ddl_with_row_filter() {
+        let ddl = RisingWaveDDLGenerator::generate_sink_ddl(
+            &create_sr_config(),
+            &create_sink_request(Some(" deleted_at IS NULL ")),
+            &create_sink_schema(),
+        )
+        .unwrap();
+        assert!(ddl.contains("CREATE SINK IF NOT EXISTS \"ods_apn\".orders_to_sr_sink AS"));
+        assert!(ddl.contains("FROM \"ods_apn\".orders\n                   WHERE deleted_at IS NULL"));
+        assert!(ddl.contains("primary_key = 'id'"));
+    }
+
+    #[test]
+    fn test_generate_sink_ddl_rejects_multi_statement_filter() {
+        let result = RisingWaveDDLGenerator::generate_sink_ddl(
+            &create_sr_config(),
+            &create_sink_request(Some("1 = 1; DROP TABLE x")),
+            &create_sink_schema(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_generate_sink_ddl_with_column_mapping() {
+        let mut request = create_sink_request(None);
+        request
+            .column_mapping
+            .insert("id".to_string(), "order_id".to_string());
+        request
+            .column_mapping
+            .insert("amount".to_string(), "order_amount".to_string());
+
+        let ddl = RisingWaveDDLGenerator::generate_sink_ddl(
+            &create_sr_config(),
+            &request,
+            &create_sink_schema(),
+        )
+        .unwrap();
+        assert!(ddl.contains("id as order_id"));
+        assert!(ddl.contains("amount as order_amount"));
+        assert!(ddl.contains("primary_key = 'order_id'"));
+    }
+
+    #[test]
+    fn test_generate_sink_ddl_rejects_invalid_column_mapping() {
+        let mut request = create_sink_request(None);
+        request
+            .column_mapping
+            .insert("missing".to_string(), "x".to_string());
+        assert!(
+            RisingWaveDDLGenerator::generate_sink_ddl(&create_sr_config(), &request, &create_sink_schema())
+                .is_err()
+        );
+
+        let mut request = create_sink_request(None);
+        request
+            .column_mapping
+            .insert("amount".to_string(), "id".to_string());
+        assert!(
+            RisingWaveDDLGenerator::generate_sink_ddl(&create_sr_config(), &request, &create_sink_schema())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_generate_sink_ddl_with_derived_partition() {
+        let mut request = create_sink_request(None);
+        request.partition = Some(crate::models::StarRocksPartition::Expression {
+            column: "created_at".to_string(),
+            granularity: crate::models::PartitionGranularity::Day,
+            derived_column: Some("created_day".to_string()),
+        });
+        let mut schema = create_sink_schema();
+        schema.columns.push(crate::models::Column {
+            name: "created_at".to_string(),
+            data_type: "timestamp".to_string(),
+            is_nullable: false,
+            default_value: None,
+            comment: None,
+            character_maximum_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+        });
+
+        let ddl =
+            RisingWaveDDLGenerator::generate_sink_ddl(&create_sr_config(), &request, &schema).unwrap();
+        assert!(ddl.contains("date_trunc('day', created_at::TIMESTAMP)::DATE as created_day"));
+        assert!(ddl.contains("primary_key = 'id,created_day'"));
+
+        let target = request.target_schema(&schema);
+        assert_eq!(target.primary_keys, vec!["id", "created_day"]);
+        assert_eq!(target.columns.last().unwrap().data_type, "DATE");
+
+        // 可为空的来源列不能派生主键中的分区列
+        schema.columns[2].is_nullable = true;
+        assert!(RisingWaveDDLGenerator::validate_column_mapping(&request, &schema).is_err());
+    }
+
+    #[test]
+    fn test_generate_sink_ddl_with_nested_columns() {
+        let mut schema = create_sink_schema();
+        schema.type_source = ColumnTypeSource::RisingWave;
+        for (name, data_type) in [
+            ("addr", "struct<city character varying, geo struct<lat double precision, lng double precision>>"),
+            ("tags", "character varying[]"),
+        ] {
+            schema.columns.push(crate::models::Column {
+                name: name.to_string(),
+                data_type: data_type.to_string(),
+                is_nullable: true,
+                default_value: None,
+                comment: None,
+                character_maximum_length: None,
+                numeric_precision: None,
+                numeric_scale: None,
+            });
+        }
+        let mut request = create_sink_request(None);
+        request
+            .nested_columns
+            .insert("addr".to_string(), NestedColumnMode::Flatten);
+        request
+            .nested_columns
+            .insert("tags".to_string(), NestedColumnMode::Json);
+
+        let ddl =
+            RisingWaveDDLGenerator::generate_sink_ddl(&create_sr_config(), &request, &schema).unwrap();
+        assert!(ddl.contains("(addr).city as addr_city"));
+        assert!(ddl.contains("((addr).geo).lat as addr_geo_lat"));
+        assert!(ddl.contains("to_jsonb(tags)::VARCHAR as tags"));
+
+        let target = request.target_schema(&schema);
+        let columns: Vec<(&str, &str)> = target
+            .columns
+            .iter()
+            .map(|c| (c.name.as_str(), c.data_type.as_str()))
+            .skip(2)
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                ("addr_city", "character varying"),
+                ("addr_geo_lat", "double precision"),
+                ("addr_geo_lng", "double precision"),
+                ("tags", "character varying"),
+            ]
+        );
+
+        // 只有 STRUCT 列可以展开，展开后的列名不能与已有列重复
+        let mut request = create_sink_request(None);
+        request
+            .nested_columns
+            .insert("tags".to_string(), NestedColumnMode::Flatten);
+        assert!(RisingWaveDDLGenerator::validate_column_mapping(&request, &schema).is_err());
+
+        let mut request = create_sink_request(None);
+        request
+            .nested_columns
+            .insert("addr".to_string(), NestedColumnMode::Flatten);
+        request
+            .column_mapping
+            .insert("amount".to_string(), "addr_city".to_string());
+        assert!(RisingWaveDDLGenerator::validate_column_mapping(&request, &schema).is_err());
+    }
+
+    #[test]
+    fn test_generate_sink_ddl_with_spatial_columns() {
+        let mut schema = create_sink_schema();
+        for (name, data_type) in [("location", "point"), ("area", "polygon")] {
+            schema.columns.push(crate::models::Column {
+                name: name.to_string(),
+                data_type: data_type.to_string(),
+                is_nullable: true,
+                default_value: None,
+                comment: None,
+                character_maximum_length: None,
+                numeric_precision: None,
+                numeric_scale: None,
+            });
+        }
+        let mut request = create_sink_request(None);
+        request
+            .spatial_columns
+            .insert("area".to_string(), SpatialColumnMode::Binary);
+
+        // 未配置的空间类型列默认写入十六进制 WKB
+        let ddl =
+            RisingWaveDDLGenerator::generate_sink_ddl(&create_sr_config(), &request, &schema).unwrap();
+        assert!(ddl.contains("encode(substr(location, 5), 'hex') as location"));
+        assert!(ddl.contains("substr(area, 5) as area"));
+
+        let target = request.target_schema(&schema);
+        let columns: Vec<(&str, &str)> = target
+            .columns
+            .iter()
+            .map(|c| (c.name.as_str(), c.data_type.as_str()))
+            .skip(2)
+            .collect();
+        assert_eq!(columns, vec![("location", "longtext"), ("area", "longblob")]);
+
+        // 只能为空间类型列指定写入方式
+        let mut request = create_sink_request(None);
+        request
+            .spatial_columns
+            .insert("amount".to_string(), SpatialColumnMode::Hex);
+        assert!(RisingWaveDDLGenerator::validate_column_mapping(&request, &schema).is_err());
+    }
+
+    #[test]
+    fn test_spatial_columns_default_to_hex_wkb() {
+        let mut schema = create_sink_schema();
+        schema.columns.push(crate::models::Column {
+            name: "geom".to_string(),
+            data_type: "geometry".to_string(),
+            is_nullable: true,
+            default_value: None,
+            comment: None,
+            character_maximum_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+        });
+        // 没有 spatial_columns 配置时写入十六进制 WKB（不是 WKT），例如 POINT(1 2) 写入
+        // 0101000000000000000000f03f0000000000000040
+        let request = create_sink_request(None);
+        assert!(request.spatial_columns.is_empty());
+        let ddl =
+            RisingWaveDDLGenerator::generate_sink_ddl(&create_sr_config(), &request, &schema).unwrap();
+        assert!(ddl.contains("encode(substr(geom, 5), 'hex') as geom"));
+
+        let table_ddl = crate::generators::StarRocksDDLGenerator::generate_table_ddl(
+            &request.target_schema(&schema),
+            "ods",
+            "orders",
+            &Default::default(),
+            None,
+        )
+        .unwrap();
+        assert!(table_ddl.contains("`geom` STRING"), "{}", table_ddl);
+    }
 }
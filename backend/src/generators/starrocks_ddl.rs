@@ -1,6 +1,31 @@
-use crate::models::TableSchema;
-use crate::utils::error::Result;
-use crate::utils::type_mapper::TypeMapper;
+use crate::models::{Column, ColumnTypeSource, StarRocksPartition, StarRocksTableOptions, TableSchema};
+use crate::utils::error::{AppError, Result};
+use crate::utils::type_mapper::{ColumnDefault, TypeMapper};
+
+/// Colocation Group 成员的分布属性，同组成员必须一致
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColocationMember {
+    pub group: String,
+    /// 成员表名，已存在的组为 SHOW PROC 返回的组名
+    pub table: String,
+    pub buckets: u32,
+    pub replication_num: u32,
+    /// 分桶列类型，例如 BIGINT
+    pub distribution_type: String,
+}
+
+impl ColocationMember {
+    /// 分桶列类型的比较键：忽略大小写和整数显示宽度，例如 int(11) 与 INT 相同
+    fn type_key(&self) -> String {
+        let lower = self.distribution_type.trim().to_lowercase();
+        match lower.split_once('(') {
+            Some((base, _)) if !base.contains("char") && !base.contains("decimal") => {
+                base.trim().to_string()
+            }
+            _ => lower.replace(' ', ""),
+        }
+    }
+}
 
 /// StarRocks DDL 生成器
 pub struct StarRocksDDLGenerator;
@@ -11,6 +36,8 @@ impl StarRocksDDLGenerator {
         schema: &TableSchema,
         target_database: &str,
         target_table: &str,
+        options: &StarRocksTableOptions,
+        partition: Option<&StarRocksPartition>,
     ) -> Result<String> {
         // 确定主键列
         let pk_columns = if !schema.primary_keys.is_empty() {
@@ -32,98 +59,520 @@ impl StarRocksDDLGenerator {
         // 先处理主键列，放到最前面
         for pk_col_name in &pk_columns {
             if let Some(col) = schema.columns.iter().find(|c| &c.name == pk_col_name) {
-                let sr_type = TypeMapper::mysql_to_starrocks(&col.data_type)?;
-                let nullable = if col.is_nullable {
-                    " NULL"
-                } else {
-                    " NOT NULL"
-                };
-
-                let comment = if let Some(ref comment) = col.comment {
-                    format!(" COMMENT '{}'", comment.replace('\'', "''"))
-                } else {
-                    String::new()
-                };
-
-                column_defs.push(format!(
-                    "  `{}` {}{}{}",
-                    col.name, sr_type, nullable, comment
-                ));
+                column_defs.push(format!("  {}", Self::column_definition(col, schema.type_source, col.is_nullable)?));
             }
         }
 
         // 再处理非主键列
         for col in &schema.columns {
             if !pk_columns.contains(&col.name) {
-                let sr_type = TypeMapper::mysql_to_starrocks(&col.data_type)?;
-                let nullable = if col.is_nullable {
-                    " NULL"
-                } else {
-                    " NOT NULL"
-                };
-
-                let comment = if let Some(ref comment) = col.comment {
-                    format!(" COMMENT '{}'", comment.replace('\'', "''"))
-                } else {
-                    String::new()
-                };
-
-                non_pk_columns.push(format!(
-                    "  `{}` {}{}{}",
-                    col.name, sr_type, nullable, comment
-                ));
+                non_pk_columns.push(format!("  {}", Self::column_definition(col, schema.type_source, col.is_nullable)?));
             }
         }
 
         // 合并主键列和非主键列
         column_defs.extend(non_pk_columns);
+        column_defs.extend(Self::generate_bitmap_indexes(schema, options)?);
 
         // 构建主键
-        let primary_key = format!("PRIMARY KEY({})", pk_columns.join(", "));
+        let primary_key = format!("PRIMARY KEY({})", Self::quote_identifiers(&pk_columns));
 
         // 确定 DISTRIBUTED BY HASH 的列
-        let hash_column = pk_columns[0].clone();
+        let hash_column = Self::quote_identifier(&pk_columns[0]);
+
+        let comment_clause = Self::table_comment_clause(schema);
+        let partition_clause = match partition {
+            Some(partition) => format!(
+                "\n               {}",
+                Self::generate_partition_clause(schema, &pk_columns, partition)?
+            ),
+            None => String::new(),
+        };
+        let buckets = Self::generate_buckets_clause(options)?;
+        let order_by = Self::generate_order_by_clause(schema, options)?;
+        let properties = Self::generate_properties(schema, options)?;
 
         let ddl = format!(
-            r#"CREATE TABLE IF NOT EXISTS `{}`.`{}` (
+            r#"CREATE TABLE IF NOT EXISTS {}.{} (
                {}
                ) ENGINE=OLAP
-               {}
-               DISTRIBUTED BY HASH({})
+               {}{}{}
+               DISTRIBUTED BY HASH({}){}{}
                PROPERTIES (
-                   "replication_num" = "1",
-                   "storage_format" = "DEFAULT"
+                   {}
                );"#,
-            target_database,
-            target_table,
+            Self::quote_identifier(target_database),
+            Self::quote_identifier(target_table),
             column_defs.join(",\n"),
             primary_key,
-            hash_column
+            comment_clause,
+            partition_clause,
+            hash_column,
+            buckets,
+            order_by,
+            properties.join(",\n                   ")
         );
 
         Ok(ddl)
     }
 
+    /// 表的 Colocation Group 成员信息，未设置 colocate_with 时返回 None
+    /// 分桶列与建表语句一致（第一个主键列，没有主键时为第一列）
+    pub fn colocation_member(
+        schema: &TableSchema,
+        target_table: &str,
+        options: &StarRocksTableOptions,
+    ) -> Result<Option<ColocationMember>> {
+        let Some(group) = &options.colocate_with else {
+            return Ok(None);
+        };
+        Self::validate_colocation_group_name(group)?;
+
+        let buckets = match options.buckets {
+            Some(buckets) if !options.auto_bucket && buckets > 0 => buckets,
+            _ => {
+                return Err(AppError::Validation(format!(
+                    "Colocation group {} requires an explicit bucket count for {}",
+                    group, target_table
+                )));
+            }
+        };
+
+        let hash_column = schema
+            .primary_keys
+            .first()
+            .or_else(|| schema.columns.first().map(|c| &c.name))
+            .and_then(|name| schema.columns.iter().find(|c| &c.name == name))
+            .ok_or_else(|| AppError::SqlGeneration("Table has no columns".to_string()))?;
+
+        Ok(Some(ColocationMember {
+            group: group.clone(),
+            table: target_table.to_string(),
+            buckets,
+            replication_num: options.replication_num,
+            distribution_type: TypeMapper::to_starrocks(&hash_column.data_type, schema.type_source)?,
+        }))
+    }
+
+    /// 校验同一 Colocation Group 的成员分桶数、副本数和分桶列类型一致
+    pub fn validate_colocation(members: &[ColocationMember]) -> Result<()> {
+        for (index, member) in members.iter().enumerate() {
+            let Some(first) = members[..index].iter().find(|m| m.group == member.group) else {
+                continue;
+            };
+            let mismatch = if first.buckets != member.buckets {
+                Some(format!("buckets {} vs {}", first.buckets, member.buckets))
+            } else if first.replication_num != member.replication_num {
+                Some(format!(
+                    "replication_num {} vs {}",
+                    first.replication_num, member.replication_num
+                ))
+            } else if first.type_key() != member.type_key() {
+                Some(format!(
+                    "distribution column type {} vs {}",
+                    first.distribution_type, member.distribution_type
+                ))
+            } else {
+                None
+            };
+
+            if let Some(mismatch) = mismatch {
+                return Err(AppError::Validation(format!(
+                    "Colocation group {} mismatch between {} and {}: {}",
+                    member.group, first.table, member.table, mismatch
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_colocation_group_name(group: &str) -> Result<()> {
+        if group.is_empty()
+            || group.len() > 64
+            || !group.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(AppError::Validation(format!(
+                "Invalid colocation group name: {}",
+                group
+            )));
+        }
+        Ok(())
+    }
+
+    /// 用反引号引用标识符，标识符中的反引号写两次
+    pub fn quote_identifier(name: &str) -> String {
+        format!("`{}`", name.replace('`', "``"))
+    }
+
+    /// 引用并用逗号连接多个列名，用于 PRIMARY KEY / UNIQUE KEY 等列表
+    pub fn quote_identifiers(names: &[String]) -> String {
+        names
+            .iter()
+            .map(|name| Self::quote_identifier(name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// 单引号字符串字面量，转义反斜杠和单引号
+    pub fn quote_string(value: &str) -> String {
+        format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+    }
+
+    /// information_schema.tables / columns 中按库名和表名过滤的条件
+    pub fn table_filter(database: &str, table: &str) -> String {
+        format!(
+            "table_schema = {} AND table_name = {}",
+            Self::quote_string(database),
+            Self::quote_string(table)
+        )
+    }
+
+    /// 表注释子句，位于 key 定义之后、分区之前，没有注释时为空
+    pub(crate) fn table_comment_clause(schema: &TableSchema) -> String {
+        match &schema.comment {
+            Some(comment) => format!("\n               COMMENT {}", Self::quote_string(comment)),
+            None => String::new(),
+        }
+    }
+
+    /// 生成单列定义：`col` TYPE [NOT] NULL [DEFAULT ...] [COMMENT '...']
+    fn column_definition(col: &Column, source: ColumnTypeSource, nullable: bool) -> Result<String> {
+        let sr_type = TypeMapper::to_starrocks(&col.data_type, source)?;
+        let nullable = if nullable { " NULL" } else { " NOT NULL" };
+        let default = Self::default_clause(col, source, &sr_type);
+
+        let comment = if let Some(ref comment) = col.comment {
+            format!(" COMMENT {}", Self::quote_string(comment))
+        } else {
+            String::new()
+        };
+
+        Ok(format!(
+            "{} {}{}{}{}",
+            Self::quote_identifier(&col.name),
+            sr_type,
+            nullable,
+            default,
+            comment
+        ))
+    }
+
+    /// DEFAULT 子句，只映射 MySQL 列的默认值，StarRocks 不支持的默认值不生成
+    fn default_clause(col: &Column, source: ColumnTypeSource, sr_type: &str) -> String {
+        let default = match (source, &col.default_value) {
+            (ColumnTypeSource::MySQL, Some(default)) => {
+                TypeMapper::mysql_default_to_starrocks(default, sr_type)
+            }
+            _ => None,
+        };
+        match default {
+            Some(ColumnDefault::Literal(value)) => format!(" DEFAULT {}", Self::quote_string(&value)),
+            Some(ColumnDefault::CurrentTimestamp) => " DEFAULT CURRENT_TIMESTAMP".to_string(),
+            Some(ColumnDefault::Uuid) => " DEFAULT (uuid())".to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// 为 StarRocks 表中缺少的列生成 ADD COLUMN 语句，没有新列时返回 None
+    /// 新列一律允许 NULL（已有数据没有该列的值），多个列合并为一个 schema change 任务
+    pub fn generate_add_columns_ddl(
+        schema: &TableSchema,
+        existing_columns: &[String],
+        target_database: &str,
+        target_table: &str,
+    ) -> Result<Option<String>> {
+        let new_columns = schema
+            .columns
+            .iter()
+            .filter(|col| {
+                !existing_columns
+                    .iter()
+                    .any(|existing| existing.eq_ignore_ascii_case(&col.name))
+            })
+            .map(|col| Self::column_definition(col, schema.type_source, true))
+            .collect::<Result<Vec<_>>>()?;
+
+        if new_columns.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(format!(
+            "ALTER TABLE {}.{} ADD COLUMN ({});",
+            Self::quote_identifier(target_database),
+            Self::quote_identifier(target_table),
+            new_columns.join(", ")
+        )))
+    }
+
+    /// 生成 PARTITION BY 子句
+    fn generate_partition_clause(
+        schema: &TableSchema,
+        pk_columns: &[String],
+        partition: &StarRocksPartition,
+    ) -> Result<String> {
+        let column_name = partition.column();
+        let column = schema
+            .columns
+            .iter()
+            .find(|c| c.name == column_name)
+            .ok_or_else(|| {
+                AppError::Validation(format!("Partition column not found: {}", column_name))
+            })?;
+
+        let sr_type = TypeMapper::to_starrocks(&column.data_type, schema.type_source)?;
+        if sr_type != "DATE" && sr_type != "DATETIME" {
+            return Err(AppError::Validation(format!(
+                "Partition column {} must be DATE or DATETIME, got {}",
+                column_name, column.data_type
+            )));
+        }
+
+        // 主键表的分区列必须包含在主键中
+        if !pk_columns.iter().any(|c| c == column_name) {
+            return Err(AppError::Validation(format!(
+                "Partition column {} must be part of the primary key ({})",
+                column_name,
+                pk_columns.join(", ")
+            )));
+        }
+
+        match partition {
+            StarRocksPartition::Expression { granularity, .. } => Ok(format!(
+                "PARTITION BY date_trunc('{}', {})",
+                granularity.as_str(),
+                Self::quote_identifier(column_name)
+            )),
+            StarRocksPartition::Range { ranges, .. } => {
+                let mut partitions = Vec::new();
+                for range in ranges {
+                    if range.name.is_empty()
+                        || !range
+                            .name
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    {
+                        return Err(AppError::Validation(format!(
+                            "Invalid partition name: {}",
+                            range.name
+                        )));
+                    }
+                    if [&range.lower, &range.upper]
+                        .iter()
+                        .any(|v| v.contains('"') || v.contains('\\'))
+                    {
+                        return Err(AppError::Validation(format!(
+                            "Invalid bound in partition {}",
+                            range.name
+                        )));
+                    }
+                    partitions.push(format!(
+                        "                   PARTITION {} VALUES [(\"{}\"), (\"{}\"))",
+                        range.name, range.lower, range.upper
+                    ));
+                }
+
+                if partitions.is_empty() {
+                    Ok(format!(
+                        "PARTITION BY RANGE({}) ()",
+                        Self::quote_identifier(column_name)
+                    ))
+                } else {
+                    Ok(format!(
+                        "PARTITION BY RANGE({}) (\n{}\n               )",
+                        Self::quote_identifier(column_name),
+                        partitions.join(",\n")
+                    ))
+                }
+            }
+        }
+    }
+
+    /// 生成 BUCKETS 子句，自动分桶或未指定时为空
+    fn generate_buckets_clause(options: &StarRocksTableOptions) -> Result<String> {
+        match options.buckets {
+            _ if options.auto_bucket => Ok(String::new()),
+            Some(0) => Err(AppError::Validation(
+                "StarRocks buckets must be greater than 0".to_string(),
+            )),
+            Some(buckets) => Ok(format!(" BUCKETS {}", buckets)),
+            None => Ok(String::new()),
+        }
+    }
+
+    /// 校验索引/排序键引用的列存在，返回列定义
+    fn find_option_column<'a>(schema: &'a TableSchema, name: &str, usage: &str) -> Result<&'a Column> {
+        schema
+            .columns
+            .iter()
+            .find(|c| c.name == name)
+            .ok_or_else(|| AppError::Validation(format!("{} column not found: {}", usage, name)))
+    }
+
+    /// 生成 ORDER BY 子句，未指定排序键时为空
+    fn generate_order_by_clause(
+        schema: &TableSchema,
+        options: &StarRocksTableOptions,
+    ) -> Result<String> {
+        if options.sort_keys.is_empty() {
+            return Ok(String::new());
+        }
+
+        let mut columns = Vec::new();
+        for name in &options.sort_keys {
+            Self::find_option_column(schema, name, "Sort key")?;
+            let column = Self::quote_identifier(name);
+            if columns.contains(&column) {
+                return Err(AppError::Validation(format!("Duplicate sort key column: {}", name)));
+            }
+            columns.push(column);
+        }
+
+        Ok(format!("\n               ORDER BY({})", columns.join(", ")))
+    }
+
+    /// 生成 Bitmap 索引定义：INDEX idx_<col>_bitmap (`col`) USING BITMAP
+    fn generate_bitmap_indexes(
+        schema: &TableSchema,
+        options: &StarRocksTableOptions,
+    ) -> Result<Vec<String>> {
+        let mut indexes = Vec::new();
+        for name in &options.bitmap_indexes {
+            Self::find_option_column(schema, name, "Bitmap index")?;
+            let index = format!(
+                "  INDEX {} ({}) USING BITMAP",
+                Self::quote_identifier(&format!("idx_{}_bitmap", name)),
+                Self::quote_identifier(name)
+            );
+            if !indexes.contains(&index) {
+                indexes.push(index);
+            }
+        }
+        Ok(indexes)
+    }
+
+    /// 校验 Bloom Filter 索引列，返回 bloom_filter_columns 属性值
+    /// StarRocks 不支持在 TINYINT、FLOAT、DOUBLE、DECIMAL 和 BOOLEAN 列上创建 Bloom Filter 索引
+    fn bloom_filter_property(
+        schema: &TableSchema,
+        options: &StarRocksTableOptions,
+    ) -> Result<Option<String>> {
+        if options.bloom_filter_columns.is_empty() {
+            return Ok(None);
+        }
+
+        let mut columns: Vec<&str> = Vec::new();
+        for name in &options.bloom_filter_columns {
+            let column = Self::find_option_column(schema, name, "Bloom filter")?;
+            let sr_type = TypeMapper::to_starrocks(&column.data_type, schema.type_source)?;
+            let base = sr_type.split('(').next().unwrap_or_default();
+            if matches!(base, "TINYINT" | "FLOAT" | "DOUBLE" | "DECIMAL" | "BOOLEAN") {
+                return Err(AppError::Validation(format!(
+                    "Bloom filter index is not supported on {} column {}",
+                    sr_type, name
+                )));
+            }
+            if !columns.contains(&name.as_str()) {
+                columns.push(name);
+            }
+        }
+        Ok(Some(columns.join(",")))
+    }
+
+    /// 生成 PROPERTIES 中的键值对
+    fn generate_properties(
+        schema: &TableSchema,
+        options: &StarRocksTableOptions,
+    ) -> Result<Vec<String>> {
+        if options.replication_num == 0 {
+            return Err(AppError::Validation(
+                "StarRocks replication_num must be greater than 0".to_string(),
+            ));
+        }
+
+        let mut properties = vec![
+            ("replication_num".to_string(), options.replication_num.to_string()),
+            ("storage_format".to_string(), "DEFAULT".to_string()),
+        ];
+
+        if let Some(medium) = &options.storage_medium {
+            let medium = medium.to_uppercase();
+            if medium != "HDD" && medium != "SSD" {
+                return Err(AppError::Validation(format!(
+                    "Invalid StarRocks storage_medium: {}",
+                    medium
+                )));
+            }
+            properties.push(("storage_medium".to_string(), medium));
+        }
+
+        if let Some(group) = &options.colocate_with {
+            Self::validate_colocation_group_name(group)?;
+            if options.auto_bucket || options.buckets.is_none() {
+                return Err(AppError::Validation(format!(
+                    "Colocation group {} requires an explicit bucket count",
+                    group
+                )));
+            }
+            properties.push(("colocate_with".to_string(), group.clone()));
+        }
+
+        if let Some(columns) = Self::bloom_filter_property(schema, options)? {
+            properties.push(("bloom_filter_columns".to_string(), columns));
+        }
+
+        for (key, value) in &options.dynamic_partition {
+            let key = if key.starts_with("dynamic_partition.") {
+                key.clone()
+            } else {
+                format!("dynamic_partition.{}", key)
+            };
+            properties.push((key, value.clone()));
+        }
+
+        Ok(properties
+            .into_iter()
+            .map(|(k, v)| format!("\"{}\" = \"{}\"", k, v.replace('"', "\\\"")))
+            .collect())
+    }
+
     /// 生成删除表的语句
     pub fn generate_drop_table_ddl(database: &str, table: &str) -> String {
-        format!("DROP TABLE IF EXISTS `{}`.`{}`;", database, table)
+        format!(
+            "DROP TABLE IF EXISTS {}.{};",
+            Self::quote_identifier(database),
+            Self::quote_identifier(table)
+        )
+    }
+
+    /// 生成重命名表的语句
+    pub fn generate_rename_table_ddl(database: &str, table: &str, new_name: &str) -> String {
+        format!(
+            "ALTER TABLE {}.{} RENAME {};",
+            Self::quote_identifier(database),
+            Self::quote_identifier(table),
+            Self::quote_identifier(new_name)
+        )
     }
 
     /// 生成清空表数据的语句
     pub fn generate_truncate_table_ddl(database: &str, table: &str) -> String {
-        format!("TRUNCATE TABLE `{}`.`{}`;", database, table)
+        format!(
+            "TRUNCATE TABLE {}.{};",
+            Self::quote_identifier(database),
+            Self::quote_identifier(table)
+        )
     }
 
     /// 生成创建数据库的语句
     pub fn generate_create_database_ddl(database: &str) -> String {
-        format!("CREATE DATABASE IF NOT EXISTS `{}`;", database)
+        format!("CREATE DATABASE IF NOT EXISTS {};", Self::quote_identifier(database))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Column;
+    use crate::models::{ColumnTypeSource, PartitionGranularity, RangePartition};
 
     fn create_test_schema() -> TableSchema {
         TableSchema {
@@ -163,21 +612,265 @@ mod tests {
             ],
             primary_keys: vec!["id".to_string()],
             indexes: vec![],
+            type_source: ColumnTypeSource::MySQL,
+            comment: None,
         }
     }
 
+    #[test]
+    fn test_table_filter() {
+        assert_eq!(
+            StarRocksDDLGenerator::table_filter("ods", "orders"),
+            "table_schema = 'ods' AND table_name = 'orders'"
+        );
+        // 反斜杠不能转义结束引号
+        assert_eq!(
+            StarRocksDDLGenerator::table_filter("ods", "t\\' OR 1=1 -- "),
+            "table_schema = 'ods' AND table_name = 't\\\\'' OR 1=1 -- '"
+        );
+    }
+
     #[test]
     fn test_generate_table_ddl() {
-        let schema = create_test_schema();
+        let mut schema = create_test_schema();
+        schema.comment = Some("User's accounts".to_string());
+        schema.columns[1].default_value = Some("'guest'".to_string());
+        schema.columns[2].default_value = Some("CURRENT_TIMESTAMP".to_string());
         let ddl =
-            StarRocksDDLGenerator::generate_table_ddl(&schema, "target_db", "users_sr").unwrap();
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "target_db", "users_sr", &StarRocksTableOptions::default(), None).unwrap();
 
         assert!(ddl.contains("CREATE TABLE IF NOT EXISTS `target_db`.`users_sr`"));
         assert!(ddl.contains("`id` INT NOT NULL COMMENT 'User ID'"));
-        assert!(ddl.contains("`name` VARCHAR(255) NULL COMMENT 'User name'"));
-        assert!(ddl.contains("`created_at` DATETIME NOT NULL"));
-        assert!(ddl.contains("PRIMARY KEY(id)"));
-        assert!(ddl.contains("DISTRIBUTED BY HASH(id)"));
+        assert!(ddl.contains("`name` VARCHAR(255) NULL DEFAULT 'guest' COMMENT 'User name'"));
+        assert!(ddl.contains("`created_at` DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP"));
+        assert!(ddl.contains("PRIMARY KEY(`id`)\n               COMMENT 'User''s accounts'\n"));
+        assert!(ddl.contains("DISTRIBUTED BY HASH(`id`)\n"));
+        assert!(ddl.contains("\"replication_num\" = \"1\""));
+    }
+
+    #[test]
+    fn test_generate_table_ddl_with_table_options() {
+        let schema = create_test_schema();
+        let mut options = StarRocksTableOptions {
+            replication_num: 3,
+            buckets: Some(16),
+            storage_medium: Some("ssd".to_string()),
+            ..Default::default()
+        };
+        options
+            .dynamic_partition
+            .insert("enable".to_string(), "true".to_string());
+        options
+            .dynamic_partition
+            .insert("dynamic_partition.time_unit".to_string(), "DAY".to_string());
+
+        let ddl =
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "target_db", "users_sr", &options, None)
+                .unwrap();
+        assert!(ddl.contains("DISTRIBUTED BY HASH(`id`) BUCKETS 16"));
+        assert!(ddl.contains("\"replication_num\" = \"3\""));
+        assert!(ddl.contains("\"storage_medium\" = \"SSD\""));
+        assert!(ddl.contains("\"dynamic_partition.enable\" = \"true\""));
+        assert!(ddl.contains("\"dynamic_partition.time_unit\" = \"DAY\""));
+
+        options.auto_bucket = true;
+        let ddl =
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "target_db", "users_sr", &options, None)
+                .unwrap();
+        assert!(!ddl.contains("BUCKETS"));
+    }
+
+    #[test]
+    fn test_generate_table_ddl_rejects_invalid_options() {
+        let schema = create_test_schema();
+        let options = StarRocksTableOptions {
+            storage_medium: Some("tape".to_string()),
+            ..Default::default()
+        };
+        assert!(
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "db", "t", &options, None).is_err()
+        );
+
+        let options = StarRocksTableOptions {
+            buckets: Some(0),
+            ..Default::default()
+        };
+        assert!(
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "db", "t", &options, None).is_err()
+        );
+    }
+
+    #[test]
+    fn test_generate_table_ddl_with_sort_keys_and_indexes() {
+        let schema = create_test_schema();
+        let options = StarRocksTableOptions {
+            sort_keys: vec!["created_at".to_string(), "id".to_string()],
+            bitmap_indexes: vec!["name".to_string()],
+            bloom_filter_columns: vec!["name".to_string(), "created_at".to_string()],
+            ..Default::default()
+        };
+
+        let ddl =
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "db", "t", &options, None).unwrap();
+        assert!(ddl.contains("  INDEX `idx_name_bitmap` (`name`) USING BITMAP"));
+        assert!(ddl.contains("DISTRIBUTED BY HASH(`id`)\n               ORDER BY(`created_at`, `id`)\n"));
+        assert!(ddl.contains("\"bloom_filter_columns\" = \"name,created_at\""));
+
+        let options = StarRocksTableOptions {
+            sort_keys: vec!["missing".to_string()],
+            ..Default::default()
+        };
+        assert!(
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "db", "t", &options, None).is_err()
+        );
+
+        let mut schema = create_test_schema();
+        schema.columns[0].data_type = "DOUBLE".to_string();
+        let options = StarRocksTableOptions {
+            bloom_filter_columns: vec!["id".to_string()],
+            ..Default::default()
+        };
+        assert!(
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "db", "t", &options, None).is_err()
+        );
+    }
+
+    #[test]
+    fn test_colocation() {
+        let schema = create_test_schema();
+        let options = StarRocksTableOptions {
+            buckets: Some(8),
+            colocate_with: Some("orders_group".to_string()),
+            ..Default::default()
+        };
+        let ddl =
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "db", "users", &options, None)
+                .unwrap();
+        assert!(ddl.contains("\"colocate_with\" = \"orders_group\""));
+
+        let member = StarRocksDDLGenerator::colocation_member(&schema, "users", &options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(member.distribution_type, "INT");
+
+        let existing = ColocationMember {
+            table: "10005_orders_group".to_string(),
+            distribution_type: "int(11)".to_string(),
+            ..member.clone()
+        };
+        assert!(StarRocksDDLGenerator::validate_colocation(&[existing, member.clone()]).is_ok());
+
+        let other = ColocationMember {
+            table: "orders".to_string(),
+            buckets: 16,
+            ..member.clone()
+        };
+        assert!(StarRocksDDLGenerator::validate_colocation(&[member.clone(), other]).is_err());
+        let other = ColocationMember {
+            table: "orders".to_string(),
+            distribution_type: "BIGINT".to_string(),
+            ..member.clone()
+        };
+        assert!(StarRocksDDLGenerator::validate_colocation(&[member, other]).is_err());
+
+        let options = StarRocksTableOptions {
+            auto_bucket: true,
+            colocate_with: Some("orders_group".to_string()),
+            ..Default::default()
+        };
+        assert!(
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "db", "users", &options, None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_generate_table_ddl_with_partition() {
+        let mut schema = create_test_schema();
+        schema.primary_keys = vec!["id".to_string(), "created_at".to_string()];
+        let options = StarRocksTableOptions::default();
+
+        let partition = StarRocksPartition::Expression {
+            column: "created_at".to_string(),
+            granularity: PartitionGranularity::Day,
+            derived_column: None,
+        };
+        let ddl = StarRocksDDLGenerator::generate_table_ddl(
+            &schema,
+            "target_db",
+            "users_sr",
+            &options,
+            Some(&partition),
+        )
+        .unwrap();
+        assert!(ddl.contains(
+            "PRIMARY KEY(`id`, `created_at`)\n               PARTITION BY date_trunc('day', `created_at`)\n               DISTRIBUTED BY HASH(`id`)"
+        ));
+
+        let partition = StarRocksPartition::Range {
+            column: "created_at".to_string(),
+            ranges: vec![RangePartition {
+                name: "p202401".to_string(),
+                lower: "2024-01-01".to_string(),
+                upper: "2024-02-01".to_string(),
+            }],
+        };
+        let ddl = StarRocksDDLGenerator::generate_table_ddl(
+            &schema,
+            "target_db",
+            "users_sr",
+            &options,
+            Some(&partition),
+        )
+        .unwrap();
+        assert!(ddl.contains("PARTITION BY RANGE(`created_at`) ("));
+        assert!(ddl.contains("PARTITION p202401 VALUES [(\"2024-01-01\"), (\"2024-02-01\"))"));
+    }
+
+    #[test]
+    fn test_generate_table_ddl_rejects_invalid_partition() {
+        let schema = create_test_schema();
+        let options = StarRocksTableOptions::default();
+
+        // 分区列不在主键中
+        let partition = StarRocksPartition::Expression {
+            column: "created_at".to_string(),
+            granularity: PartitionGranularity::Month,
+            derived_column: None,
+        };
+        assert!(
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "db", "t", &options, Some(&partition))
+                .is_err()
+        );
+
+        // 分区列不是日期类型
+        let partition = StarRocksPartition::Range {
+            column: "id".to_string(),
+            ranges: vec![],
+        };
+        assert!(
+            StarRocksDDLGenerator::generate_table_ddl(&schema, "db", "t", &options, Some(&partition))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_generate_add_columns_ddl() {
+        let schema = create_test_schema();
+        let existing: Vec<String> = schema.columns.iter().map(|c| c.name.to_uppercase()).collect();
+        assert_eq!(
+            StarRocksDDLGenerator::generate_add_columns_ddl(&schema, &existing, "db", "t").unwrap(),
+            None
+        );
+
+        let existing: Vec<String> = schema.columns[..1].iter().map(|c| c.name.clone()).collect();
+        let ddl = StarRocksDDLGenerator::generate_add_columns_ddl(&schema, &existing, "db", "t")
+            .unwrap()
+            .unwrap();
+        assert!(ddl.starts_with("ALTER TABLE `db`.`t` ADD COLUMN ("));
+        assert!(!ddl.contains(&format!("`{}`", schema.columns[0].name)));
+        assert!(ddl.contains(&format!("`{}`", schema.columns[1].name)));
+        assert!(!ddl.contains("NOT NULL"));
     }
 
     #[test]
@@ -236,9 +929,11 @@ mod tests {
             ],
             primary_keys: vec!["order_id".to_string(), "user_id".to_string()],
             indexes: vec![],
+            type_source: ColumnTypeSource::MySQL,
+            comment: None,
         };
 
-        let ddl = StarRocksDDLGenerator::generate_table_ddl(&schema, "target_db", "orders").unwrap();
+        let ddl = StarRocksDDLGenerator::generate_table_ddl(&schema, "target_db", "orders", &StarRocksTableOptions::default(), None).unwrap();
 
         // 验证主键字段在最前面
         let lines: Vec<&str> = ddl.lines().collect();
@@ -262,7 +957,7 @@ mod tests {
 
     #[test]
     fn test_mysql_tinyint_to_starrocks_tinyint() {
-        // 测试 MySQL TINYINT 映射到 StarRocks SMALLINT（为了与 RisingWave 兼容）
+        // 测试 MySQL TINYINT 映射到 StarRocks TINYINT
         let schema = TableSchema {
             database: "test_db".to_string(),
             table_name: "users".to_string(),
@@ -290,11 +985,13 @@ mod tests {
             ],
             primary_keys: vec!["id".to_string()],
             indexes: vec![],
+            type_source: ColumnTypeSource::MySQL,
+            comment: None,
         };
 
-        let ddl = StarRocksDDLGenerator::generate_table_ddl(&schema, "target_db", "users").unwrap();
+        let ddl = StarRocksDDLGenerator::generate_table_ddl(&schema, "target_db", "users", &StarRocksTableOptions::default(), None).unwrap();
 
-        // 验证 TINYINT 类型被映射为 SMALLINT（为了与 RisingWave 兼容）
-        assert!(ddl.contains("`active` SMALLINT NOT NULL"));
+        // 验证 TINYINT 类型保持为 TINYINT（与 TypeMapper::mysql_to_starrocks 一致）
+        assert!(ddl.contains("`active` TINYINT NOT NULL"));
     }
 }
@@ -1,4 +1,5 @@
 pub mod api;
+pub mod cli;
 pub mod db;
 pub mod generators;
 pub mod models;
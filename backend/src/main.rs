@@ -1,20 +1,15 @@
 //! RisingWave CDC to StarRocks Sync Tool
 //! Web API Server
 
-mod api;
-mod db;
-mod generators;
-mod models;
-mod services;
-mod utils;
-
 use axum::{
     body::Body,
     http::{header, StatusCode, Uri},
     response::{IntoResponse, Response},
     Router,
 };
+use clap::Parser;
 use rust_embed::RustEmbed;
+use rw_cdc_sr::{api, cli, db, services, utils};
 use std::net::SocketAddr;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -79,8 +74,61 @@ fn not_found() -> Response {
 
 use axum::http::HeaderValue;
 
-#[tokio::main]
-async fn main() {
+fn main() {
+    let cli = cli::Cli::parse();
+
+    // install-service / uninstall-service：注册或移除系统服务后退出
+    if let Some(result) = cli.service_command() {
+        match result {
+            Ok(message) => println!("{}", message),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // 服务方式启动时传入的 --working-dir / --env-file，需在启动运行时的线程之前处理
+    let env = utils::service::startup_env(cli.working_dir.as_deref(), cli.env_file.as_deref())
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        });
+    for (key, value) in env {
+        // SAFETY: 此时还没有其他线程读取环境变量
+        unsafe { std::env::set_var(key, value) };
+    }
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build Tokio runtime");
+
+    // sync / test-connection / list-tables / rotate-key：不启动 HTTP 服务，执行后以命令的结果退出
+    let Some(command) = cli.command else {
+        runtime.block_on(serve(cli.demo));
+        return;
+    };
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "rw_cdc_sr=warn".into()),
+        )
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .init();
+    let code = match runtime.block_on(command.run()) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    };
+    std::process::exit(code);
+}
+
+/// demo 为 true 时以演示模式启动
+async fn serve(demo: bool) {
     // 初始化日志
     tracing_subscriber::registry()
         .with(
@@ -92,6 +140,9 @@ async fn main() {
 
     tracing::info!("Starting RW CDC SR Web Server...");
 
+    // 校验存储加密密钥
+    utils::crypto::init().expect("Invalid encryption key configuration");
+
     // 初始化数据库
     let db = db::init_database()
         .await
@@ -99,9 +150,55 @@ async fn main() {
 
     tracing::info!("Database initialized successfully");
 
+    // 开发 / 集成测试用的故障注入
+    utils::fault::init_from_env().expect("Invalid FAULT_INJECTION");
+
+    // 加载运行时设置
+    services::SettingsService::load(&db)
+        .await
+        .expect("Failed to load settings");
+
+    // 登录认证：没有用户时创建初始管理员
+    if services::AuthService::is_enabled() {
+        services::AuthService::bootstrap_admin(&db)
+            .await
+            .expect("Failed to create initial admin user");
+    } else {
+        tracing::warn!("AUTH_ENABLED=false, API endpoints are not protected");
+    }
+
+    // 演示模式：写入示例数据，禁用破坏性接口
+    if demo {
+        tracing::info!("Demo mode enabled, destructive endpoints are disabled");
+        services::DemoService::seed(&db)
+            .await
+            .expect("Failed to seed demo data");
+    } else if let Err(e) = services::TaskRecoveryService::recover(&db).await {
+        // 上次运行遗留的执行中任务标记为中断，演示数据中的执行中任务保持原样
+        tracing::error!("Failed to recover interrupted tasks: {}", e);
+    }
+
+    // 启动管道健康采样
+    services::SlaService::spawn_sampler(db.clone());
+    // 定期记录三端行数
+    services::SyncMetricsService::spawn_snapshotter(db.clone());
+    // 按保留天数清理任务日志
+    services::SettingsService::spawn_log_retention(db.clone());
+    // 定时同步（演示模式不提交任务）
+    if !demo {
+        services::SchedulerService::spawn(db.clone());
+    }
+    // 关闭空闲的缓存连接池
+    services::ConnectionPoolManager::spawn_eviction();
+
     // 创建 API 路由
+    let mut api_router = api::create_router(db);
+    if demo {
+        api_router = api_router.layer(axum::middleware::from_fn(api::demo::reject_destructive));
+    }
+
     let app = Router::new()
-        .merge(api::create_router(db))
+        .merge(api_router)
         // 静态文件服务（嵌入的前端）
         .fallback(static_handler)
         // 请求追踪
@@ -123,7 +220,11 @@ async fn main() {
         .await
         .expect("Failed to bind address");
 
+    // 收到 Ctrl+C / SIGTERM 后等待处理中的请求和 DDL 结束
     axum::serve(listener, app)
+        .with_graceful_shutdown(services::Shutdown::signal())
         .await
         .expect("Server error");
+    services::Shutdown::drain().await;
+    tracing::info!("Server stopped");
 }
@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 告警状态，与 Alertmanager 一致
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+// 实现 String 到 AlertStatus 的转换（用于 SQLx）
+impl TryFrom<String> for AlertStatus {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        AlertStatus::parse(&s).ok_or_else(|| format!("Unknown alert status: {}", s))
+    }
+}
+
+impl AlertStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertStatus::Firing => "firing",
+            AlertStatus::Resolved => "resolved",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "firing" => Some(AlertStatus::Firing),
+            "resolved" => Some(AlertStatus::Resolved),
+            _ => None,
+        }
+    }
+}
+
+/// 收到的告警，同一告警（fingerprint + starts_at）重复发送时更新同一条记录
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct AlertRecord {
+    pub id: i64,
+    pub fingerprint: String,
+    pub alertname: String,
+    #[sqlx(try_from = "String")]
+    pub status: AlertStatus,
+    pub severity: Option<String>,
+    #[sqlx(json)]
+    pub labels: BTreeMap<String, String>,
+    #[sqlx(json)]
+    pub annotations: BTreeMap<String, String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Alertmanager 重复发送的次数（包括第一次）
+    pub receive_count: i64,
+    pub last_received_at: DateTime<Utc>,
+    /// 最后一次收到时是否被静默
+    pub silenced: bool,
+    /// 收到 resolved 或手动解决的时间
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// 手动解决的用户，Alertmanager 发送 resolved 时为空
+    pub resolved_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 告警关联的对象类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSubject {
+    /// 同步任务，subject_id 为任务 ID
+    Task,
+    /// 管道，subject_id 为目标表 `database.table`（与状态页一致）
+    Pipeline,
+}
+
+impl AlertSubject {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertSubject::Task => "task",
+            AlertSubject::Pipeline => "pipeline",
+        }
+    }
+}
+
+/// 告警标签匹配到的管道：告警同时关联到该管道和最近一次涉及它的任务
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlertPipelineMatch {
+    /// 目标表 `database.table`
+    pub target: String,
+    pub task_id: i64,
+    /// 匹配的 RisingWave 对象，例如 `"ods".orders_to_sr_sink`
+    pub object_name: String,
+}
+
+/// Webhook 收到的一条告警
+#[derive(Debug, Clone)]
+pub struct NewAlert {
+    pub fingerprint: String,
+    pub alertname: String,
+    pub status: AlertStatus,
+    pub severity: Option<String>,
+    pub labels: BTreeMap<String, String>,
+    pub annotations: BTreeMap<String, String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub silenced: bool,
+}
+
+/// 告警历史的查询参数
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AlertQuery {
+    pub status: Option<AlertStatus>,
+    pub severity: Option<String>,
+    pub fingerprint: Option<String>,
+}
+
+impl AlertQuery {
+    /// 分页的默认条数
+    pub const DEFAULT_LIMIT: i64 = 50;
+    /// 分页的最大条数
+    pub const MAX_LIMIT: i64 = 200;
+}
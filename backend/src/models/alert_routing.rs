@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 标签匹配方式，与 Alertmanager 的匹配器一致
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub enum MatchOp {
+    #[default]
+    #[serde(rename = "=")]
+    Equal,
+    #[serde(rename = "!=")]
+    NotEqual,
+    /// 正则匹配整个标签值
+    #[serde(rename = "=~")]
+    Regex,
+    #[serde(rename = "!~")]
+    NotRegex,
+}
+
+impl MatchOp {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchOp::Equal => "=",
+            MatchOp::NotEqual => "!=",
+            MatchOp::Regex => "=~",
+            MatchOp::NotRegex => "!~",
+        }
+    }
+}
+
+/// 标签匹配器，告警没有该标签时按空字符串匹配
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct LabelMatcher {
+    pub label: String,
+    #[serde(default)]
+    pub op: MatchOp,
+    pub value: String,
+}
+
+impl LabelMatcher {
+    /// 正则匹配器编译后的表达式（锚定整个值），其他匹配方式返回 None
+    pub fn regex(&self) -> Result<Option<Regex>, regex::Error> {
+        match self.op {
+            MatchOp::Regex | MatchOp::NotRegex => {
+                Regex::new(&format!("^(?:{})$", self.value)).map(Some)
+            }
+            MatchOp::Equal | MatchOp::NotEqual => Ok(None),
+        }
+    }
+
+    /// 表达式无效的正则匹配器不匹配任何告警
+    pub fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        let value = labels.get(&self.label).map(String::as_str).unwrap_or("");
+        match self.op {
+            MatchOp::Equal => value == self.value,
+            MatchOp::NotEqual => value != self.value,
+            MatchOp::Regex | MatchOp::NotRegex => match self.regex() {
+                Ok(Some(re)) => re.is_match(value) == (self.op == MatchOp::Regex),
+                _ => false,
+            },
+        }
+    }
+
+    /// 校验匹配器：标签名不能为空，正则必须能编译
+    pub fn validate_all(matchers: &[LabelMatcher]) -> Result<(), String> {
+        for matcher in matchers {
+            if matcher.label.trim().is_empty() {
+                return Err("Matcher label is required".to_string());
+            }
+            if let Err(e) = matcher.regex() {
+                return Err(format!("Invalid regex for label {}: {}", matcher.label, e));
+            }
+        }
+        Ok(())
+    }
+
+    /// 所有匹配器都匹配（没有匹配器时匹配所有告警）
+    pub fn match_all(matchers: &[LabelMatcher], labels: &HashMap<String, String>) -> bool {
+        matchers.iter().all(|m| m.matches(labels))
+    }
+}
+
+/// 告警路由规则：按 priority 从小到大匹配，匹配的告警只发送到 channel_ids 中的渠道
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct AlertRoute {
+    pub id: i64,
+    pub name: String,
+    #[sqlx(json)]
+    pub matchers: Vec<LabelMatcher>,
+    #[sqlx(json)]
+    pub channel_ids: Vec<i64>,
+    pub priority: i32,
+    /// 匹配后继续匹配后面的规则，发送到所有匹配规则的渠道
+    pub continue_matching: bool,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 创建或更新告警路由规则的请求
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct AlertRouteRequest {
+    pub name: String,
+    #[serde(default)]
+    pub matchers: Vec<LabelMatcher>,
+    pub channel_ids: Vec<i64>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub continue_matching: bool,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl AlertRouteRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Route name is required".to_string());
+        }
+        if self.channel_ids.is_empty() {
+            return Err("Select at least one channel".to_string());
+        }
+        LabelMatcher::validate_all(&self.matchers)
+    }
+}
+
+/// 静默窗口：时间范围内匹配的告警不发送
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct AlertSilence {
+    pub id: i64,
+    #[sqlx(json)]
+    pub matchers: Vec<LabelMatcher>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub comment: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AlertSilence {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+}
+
+/// 创建静默窗口的请求，starts_at 为空时立即生效
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct AlertSilenceRequest {
+    pub matchers: Vec<LabelMatcher>,
+    #[serde(default)]
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: DateTime<Utc>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl AlertSilenceRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        // 没有匹配器的静默会屏蔽所有告警
+        if self.matchers.is_empty() {
+            return Err("A silence needs at least one matcher".to_string());
+        }
+        if self.ends_at <= self.starts_at.unwrap_or_else(Utc::now) {
+            return Err("ends_at must be after starts_at".to_string());
+        }
+        LabelMatcher::validate_all(&self.matchers)
+    }
+}
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::SyncRequest;
+
+/// 审批状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    /// 等待审批
+    Pending,
+    /// 已批准并提交同步任务
+    Approved,
+    /// 已拒绝
+    Rejected,
+}
+
+// 实现 String 到 ApprovalStatus 的转换（用于 SQLx）
+impl TryFrom<String> for ApprovalStatus {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.as_str() {
+            "pending" => Ok(ApprovalStatus::Pending),
+            "approved" => Ok(ApprovalStatus::Approved),
+            "rejected" => Ok(ApprovalStatus::Rejected),
+            _ => Err(format!("Unknown approval status: {}", s)),
+        }
+    }
+}
+
+impl ApprovalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApprovalStatus::Pending => "pending",
+            ApprovalStatus::Approved => "approved",
+            ApprovalStatus::Rejected => "rejected",
+        }
+    }
+}
+
+/// 同步审批单，记录申请人、审批人和待执行的同步请求
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ApprovalRequest {
+    pub id: i64,
+    #[sqlx(try_from = "String")]
+    pub status: ApprovalStatus,
+    /// 需要审批的原因，例如重建目标表、生产工作区
+    pub reason: String,
+    pub requests: String, // JSON serialized Vec<SyncRequest>
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+    pub decided_by: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub comment: Option<String>,
+    /// 批准后提交的同步任务
+    pub task_id: Option<i64>,
+}
+
+impl ApprovalRequest {
+    /// 还原审批单中的同步请求
+    pub fn sync_requests(&self) -> Result<Vec<SyncRequest>, serde_json::Error> {
+        serde_json::from_str(&self.requests)
+    }
+}
+
+/// 查询审批单
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ApprovalQuery {
+    pub status: Option<ApprovalStatus>,
+}
+
+/// 批准或拒绝审批单
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema)]
+pub struct ApprovalDecision {
+    pub comment: Option<String>,
+}
@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 审计日志：破坏性 DDL（DROP / TRUNCATE / CREATE SINK 等）和连接配置变更
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct AuditLog {
+    pub id: i64,
+    /// 操作人；任务中执行的语句取任务的提交人，系统自动执行时为空
+    pub actor: Option<String>,
+    pub action: String,
+    /// 目标对象，例如 `"ods".orders_to_sr_sink` 或 `connection mysql-prod (#3)`
+    pub target: String,
+    /// 执行语句的系统：RisingWave、StarRocks，连接配置变更为空
+    pub engine: Option<String>,
+    /// 执行的语句（已隐藏密码）
+    pub sql_text: Option<String>,
+    pub task_id: Option<i64>,
+    pub success: bool,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 待写入的审计记录
+#[derive(Debug, Clone, Default)]
+pub struct NewAuditLog {
+    pub actor: Option<String>,
+    pub action: String,
+    pub target: String,
+    pub engine: Option<String>,
+    pub sql_text: Option<String>,
+    pub task_id: Option<i64>,
+    pub error_message: Option<String>,
+}
+
+/// 审计日志的查询参数
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditQuery {
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    /// 目标对象包含的文本
+    pub target: Option<String>,
+    pub task_id: Option<i64>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl AuditQuery {
+    /// 分页的默认条数
+    pub const DEFAULT_LIMIT: i64 = 50;
+    /// 分页的最大条数
+    pub const MAX_LIMIT: i64 = 500;
+}
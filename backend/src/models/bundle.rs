@@ -0,0 +1,200 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{
+    DbType, LabelMatcher, NestedColumnMode, NotificationEvent, SpatialColumnMode,
+    StarRocksPartition, SyncOptions, SyncRequest,
+};
+
+/// 导出文件格式版本
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// 导出时密码的处理方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordMode {
+    /// 不导出密码，导入后需要重新填写
+    #[default]
+    Exclude,
+    /// 使用导出口令重新加密，导入时需要同一口令
+    Encrypt,
+}
+
+/// 导出请求
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema)]
+#[serde(default)]
+pub struct ExportRequest {
+    pub passwords: PasswordMode,
+    pub passphrase: Option<String>,
+}
+
+/// 导出文件中的连接配置
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BundleConnection {
+    pub name: String,
+    pub db_type: DbType,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    /// 使用导出口令加密的密码
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// 不使用密码认证，导入时不需要重新填写密码
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_password: bool,
+    pub database_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+}
+
+/// 导出文件中的同步定义，连接按名称引用
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BundleSyncDefinition {
+    pub mysql_config: String,
+    pub rw_config: String,
+    pub sr_config: String,
+    pub mysql_database: String,
+    pub mysql_table: String,
+    pub target_database: String,
+    pub target_table: String,
+    #[serde(default)]
+    pub options: SyncOptions,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_filter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partition: Option<StarRocksPartition>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_mapping: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub nested_columns: BTreeMap<String, NestedColumnMode>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub spatial_columns: BTreeMap<String, SpatialColumnMode>,
+}
+
+impl BundleSyncDefinition {
+    /// 使用导入后的连接 ID 生成同步请求
+    pub fn into_request(
+        self,
+        mysql_config_id: i64,
+        rw_config_id: i64,
+        sr_config_id: i64,
+    ) -> SyncRequest {
+        SyncRequest {
+            mysql_config_id,
+            rw_config_id,
+            sr_config_id,
+            mysql_database: self.mysql_database,
+            mysql_table: self.mysql_table,
+            target_database: self.target_database,
+            target_table: self.target_table,
+            options: self.options,
+            row_filter: self.row_filter,
+            partition: self.partition,
+            column_mapping: self.column_mapping,
+            nested_columns: self.nested_columns,
+            spatial_columns: self.spatial_columns,
+        }
+    }
+}
+
+/// 连接配置和同步定义的导出文件（YAML）
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConfigBundle {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    #[serde(default)]
+    pub passwords: PasswordMode,
+    #[serde(default)]
+    pub connections: Vec<BundleConnection>,
+    #[serde(default)]
+    pub sync_definitions: Vec<BundleSyncDefinition>,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ImportResult {
+    /// 新建的连接名称
+    pub created: Vec<String>,
+    /// 已存在同名连接而跳过的名称
+    pub skipped: Vec<String>,
+    /// 导入时没有密码、需要重新填写的连接名称
+    pub missing_passwords: Vec<String>,
+    /// 按本地连接 ID 重写后的同步定义，可直接提交到 /api/sync/multiple
+    pub sync_requests: Vec<SyncRequest>,
+}
+
+/// 导出文件中的通知渠道
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct BundleChannel {
+    pub name: String,
+    /// 渠道类型，仅供阅读，导入时以 settings 为准
+    pub kind: String,
+    /// 使用导出口令加密的渠道配置 JSON（包含 Webhook 地址和密钥）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<String>,
+    pub events: Vec<NotificationEvent>,
+    pub enabled: bool,
+}
+
+/// 导出文件中的告警路由规则，渠道按名称引用
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct BundleAlertRoute {
+    pub name: String,
+    #[serde(default)]
+    pub matchers: Vec<LabelMatcher>,
+    pub channels: Vec<String>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub continue_matching: bool,
+    pub enabled: bool,
+}
+
+/// 导出文件中的静默窗口
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct BundleSilence {
+    pub matchers: Vec<LabelMatcher>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+}
+
+/// 通知渠道、告警路由规则和静默窗口的导出文件（YAML）
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MonitoringBundle {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    /// 渠道配置的处理方式，与连接密码相同
+    #[serde(default)]
+    pub passwords: PasswordMode,
+    #[serde(default)]
+    pub channels: Vec<BundleChannel>,
+    #[serde(default)]
+    pub alert_routes: Vec<BundleAlertRoute>,
+    /// 导出时尚未结束的静默窗口
+    #[serde(default)]
+    pub silences: Vec<BundleSilence>,
+}
+
+/// 一类对象的导入结果，渠道和规则按名称标识
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ImportChanges {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    /// 与本地一致而未改动
+    pub unchanged: Vec<String>,
+    /// 无法导入而跳过：没有渠道配置且本地不存在的渠道、已结束的静默窗口
+    pub skipped: Vec<String>,
+}
+
+/// 监控配置的导入结果，重复导入同一文件时只有 unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MonitoringImportResult {
+    pub channels: ImportChanges,
+    pub alert_routes: ImportChanges,
+    pub silences: ImportChanges,
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 评论对象类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentSubject {
+    /// 同步任务，subject_id 为任务 ID
+    Task,
+    /// 管道，subject_id 为目标表 `database.table`（与状态页一致）
+    Pipeline,
+}
+
+// 实现 String 到 CommentSubject 的转换（用于 SQLx）
+impl TryFrom<String> for CommentSubject {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.as_str() {
+            "task" => Ok(CommentSubject::Task),
+            "pipeline" => Ok(CommentSubject::Pipeline),
+            _ => Err(format!("Unknown comment subject: {}", s)),
+        }
+    }
+}
+
+impl CommentSubject {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentSubject::Task => "task",
+            CommentSubject::Pipeline => "pipeline",
+        }
+    }
+}
+
+/// 任务或管道上的评论
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct Comment {
+    pub id: i64,
+    #[sqlx(try_from = "String")]
+    pub subject_type: CommentSubject,
+    pub subject_id: String,
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 添加评论的请求，作者取自 X-User 请求头
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct CreateCommentRequest {
+    pub body: String,
+}
@@ -0,0 +1,127 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 失败原因分类，按错误信息中的关键字识别
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// MySQL 账号缺少 REPLICATION SLAVE / REPLICATION CLIENT 权限
+    BinlogPermission,
+    /// binlog 未开启或格式不是 ROW
+    BinlogConfig,
+    /// 缺少其他权限
+    PermissionDenied,
+    Authentication,
+    Timeout,
+    Connection,
+    /// 磁盘、内存或配额不足
+    Capacity,
+    ObjectNotFound,
+    ObjectExists,
+    PrimaryKey,
+    UnsupportedType,
+    SqlSyntax,
+    Unknown,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::BinlogPermission => "binlog_permission",
+            ErrorCode::BinlogConfig => "binlog_config",
+            ErrorCode::PermissionDenied => "permission_denied",
+            ErrorCode::Authentication => "authentication",
+            ErrorCode::Timeout => "timeout",
+            ErrorCode::Connection => "connection",
+            ErrorCode::Capacity => "capacity",
+            ErrorCode::ObjectNotFound => "object_not_found",
+            ErrorCode::ObjectExists => "object_exists",
+            ErrorCode::PrimaryKey => "primary_key",
+            ErrorCode::UnsupportedType => "unsupported_type",
+            ErrorCode::SqlSyntax => "sql_syntax",
+            ErrorCode::Unknown => "unknown",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ErrorCode::BinlogPermission => "MySQL user lacks binlog replication privileges",
+            ErrorCode::BinlogConfig => "MySQL binlog is disabled or not in ROW format",
+            ErrorCode::PermissionDenied => "Missing privileges on the source or target",
+            ErrorCode::Authentication => "Wrong username or password",
+            ErrorCode::Timeout => "Connection or statement timed out",
+            ErrorCode::Connection => "Cannot reach the database",
+            ErrorCode::Capacity => "Out of disk, memory or quota",
+            ErrorCode::ObjectNotFound => "Database, table or object does not exist",
+            ErrorCode::ObjectExists => "Object already exists",
+            ErrorCode::PrimaryKey => "Primary key missing or invalid",
+            ErrorCode::UnsupportedType => "Unsupported column type",
+            ErrorCode::SqlSyntax => "Generated SQL was rejected",
+            ErrorCode::Unknown => "Unclassified error",
+        }
+    }
+}
+
+/// 趋势的统计粒度，按周统计时以周一为起点
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TrendBucket {
+    #[default]
+    Day,
+    Week,
+}
+
+/// 错误分类统计查询参数
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ErrorStatsQuery {
+    /// 最近多少天（默认 30，最多 366）
+    pub days: Option<i64>,
+    #[serde(default)]
+    pub bucket: TrendBucket,
+}
+
+/// 一次失败的任务
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TaskFailure {
+    pub id: i64,
+    pub failed_at: DateTime<Utc>,
+    pub error_message: Option<String>,
+}
+
+/// 单个错误分类的次数和趋势
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ErrorCodeStat {
+    pub code: ErrorCode,
+    pub description: String,
+    pub count: i64,
+    /// 每个统计区间的次数，与 ErrorStats.buckets 一一对应
+    pub trend: Vec<i64>,
+    pub last_seen: DateTime<Utc>,
+    pub last_task_id: i64,
+    /// 最近一次的错误信息（截断）
+    pub sample: Option<String>,
+}
+
+/// 按错误分类汇总的失败任务，codes 按次数倒序
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ErrorStats {
+    pub since: NaiveDate,
+    pub bucket: TrendBucket,
+    /// 每个统计区间的起始日期（UTC）
+    pub buckets: Vec<NaiveDate>,
+    pub total: i64,
+    pub codes: Vec<ErrorCodeStat>,
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 没有指定工作区时使用的默认工作区
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+/// 受开关控制的功能
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    /// 控制器模式（持续对齐管道期望状态）
+    ControllerMode,
+    /// 自动把 MySQL 新增列同步到目标表（SyncOptions.reconcile_sr_schema）
+    AutoAlterPropagation,
+    /// 失败任务自动恢复
+    AutoRecovery,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 3] = [
+        Feature::ControllerMode,
+        Feature::AutoAlterPropagation,
+        Feature::AutoRecovery,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Feature::ControllerMode => "controller_mode",
+            Feature::AutoAlterPropagation => "auto_alter_propagation",
+            Feature::AutoRecovery => "auto_recovery",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.as_str() == s)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Feature::ControllerMode => "Continuously reconcile pipelines to their desired state",
+            Feature::AutoAlterPropagation => "Add new MySQL columns to existing target tables",
+            Feature::AutoRecovery => "Automatically retry failed pipelines",
+        }
+    }
+}
+
+/// 工作区内的功能开关，数据库中没有记录的功能视为关闭
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct FeatureFlag {
+    pub workspace: String,
+    pub name: String,
+    pub enabled: bool,
+    pub description: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// 查询功能开关
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FeatureFlagQuery {
+    pub workspace: Option<String>,
+}
+
+/// 更新功能开关的请求
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct UpdateFeatureFlagRequest {
+    pub workspace: Option<String>,
+    pub enabled: bool,
+}
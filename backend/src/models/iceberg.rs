@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Iceberg 目标的 sink 配置（RisingWave connector = 'iceberg'）
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct IcebergSinkOptions {
+    /// catalog 类型：storage、rest、glue、hive、jdbc（默认 storage）
+    #[serde(default = "default_catalog_type")]
+    pub catalog_type: String,
+    /// rest / hive / jdbc catalog 的地址
+    #[serde(default)]
+    pub catalog_uri: Option<String>,
+    #[serde(default)]
+    pub catalog_name: Option<String>,
+    /// 仓库路径，例如 s3://bucket/warehouse
+    pub warehouse_path: String,
+    #[serde(default)]
+    pub s3_endpoint: Option<String>,
+    #[serde(default)]
+    pub s3_region: Option<String>,
+    #[serde(default)]
+    pub s3_access_key: Option<String>,
+    /// 存储为 RisingWave SECRET，不直接写入 sink 定义
+    #[serde(default)]
+    pub s3_secret_key: Option<String>,
+    /// 只追加写入，不需要主键
+    #[serde(default)]
+    pub append_only: bool,
+    /// Iceberg 表不存在时自动创建
+    #[serde(default = "default_true")]
+    pub create_table_if_not_exists: bool,
+}
+
+fn default_catalog_type() -> String {
+    "storage".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
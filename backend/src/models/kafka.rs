@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Kafka 消息格式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum KafkaFormat {
+    #[default]
+    Json,
+    /// 需要 schema registry，列可以省略由 RisingWave 推断
+    Avro,
+}
+
+/// Kafka 表的列定义（RisingWave 类型）
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct KafkaColumn {
+    pub name: String,
+    pub data_type: String,
+}
+
+/// 将 Kafka topic 注册为 RisingWave 表的请求
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreateKafkaSourceRequest {
+    pub rw_config_id: i64,
+    pub schema: String,
+    pub name: String,
+    pub bootstrap_servers: String,
+    pub topic: String,
+    #[serde(default)]
+    pub format: KafkaFormat,
+    /// Avro 格式的 schema registry 地址
+    #[serde(default)]
+    pub schema_registry: Option<String>,
+    #[serde(default)]
+    pub columns: Vec<KafkaColumn>,
+    /// 主键列，创建 upsert sink 时必须指定
+    #[serde(default)]
+    pub primary_key: Vec<String>,
+    /// 消费起始位置：earliest 或 latest（默认 earliest）
+    #[serde(default)]
+    pub scan_startup_mode: Option<String>,
+    /// 其他 connector 属性，例如 properties.security.protocol
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+}
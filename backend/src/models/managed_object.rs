@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 由本服务创建并在多个同步映射之间共享的 RisingWave 对象类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ManagedObjectType {
+    /// 保存 MySQL 密码的 SECRET
+    Secret,
+    /// MySQL CDC Source
+    Source,
+}
+
+// 实现 String 到 ManagedObjectType 的转换（用于 SQLx）
+impl TryFrom<String> for ManagedObjectType {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.as_str() {
+            "secret" => Ok(ManagedObjectType::Secret),
+            "source" => Ok(ManagedObjectType::Source),
+            _ => Err(format!("Unknown managed object type: {}", s)),
+        }
+    }
+}
+
+impl ManagedObjectType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ManagedObjectType::Secret => "secret",
+            ManagedObjectType::Source => "source",
+        }
+    }
+}
+
+/// 共享的 RisingWave 对象及引用它的同步映射数
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ManagedObject {
+    pub id: i64,
+    pub rw_config_id: i64,
+    #[sqlx(try_from = "String")]
+    pub object_type: ManagedObjectType,
+    /// RisingWave 中的完整名称，例如 "ods".shop_source
+    pub object_name: String,
+    /// 共享键，相同共享键的映射复用同一个对象
+    pub share_key: Option<String>,
+    /// 引用该对象的目标表数量
+    pub ref_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ManagedObject {
+    /// RisingWave 对象的完整名称，与 DDL 生成器的命名一致
+    pub fn qualified_name(schema: &str, name: &str) -> String {
+        crate::generators::RisingWaveDDLGenerator::qualified_name(schema, name)
+    }
+
+    /// CDC Source 的共享键：同一 MySQL 连接上的同一个数据库共用一个 Source
+    pub fn source_share_key(mysql_config_id: i64, mysql_database: &str) -> String {
+        format!("mysql:{}/{}", mysql_config_id, mysql_database)
+    }
+}
+
+/// 查询共享对象
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ManagedObjectQuery {
+    pub rw_config_id: Option<i64>,
+}
@@ -1,15 +1,67 @@
+pub mod alert;
+pub mod alert_routing;
+pub mod approval;
+pub mod audit;
+pub mod auth;
+pub mod bundle;
+pub mod comment;
 pub mod config;
+pub mod error_stats;
+pub mod feature_flag;
+pub mod iceberg;
+pub mod kafka;
+pub mod managed_object;
+pub mod notification;
+pub mod orphan_cleanup;
+pub mod pipeline_cleanup;
+pub mod rw_secret;
+pub mod schedule;
+pub mod search;
+pub mod setting;
+pub mod sink_maintenance;
+pub mod sla;
+pub mod sql_console;
+pub mod status;
+pub mod sync_mapping;
+pub mod sync_metric;
 pub mod table;
 pub mod task;
+pub mod validation;
 
+pub use alert::*;
+pub use alert_routing::*;
+pub use approval::*;
+pub use audit::*;
+pub use auth::*;
+pub use bundle::*;
+pub use comment::*;
 pub use config::*;
+pub use error_stats::*;
+pub use feature_flag::*;
+pub use iceberg::*;
+pub use kafka::*;
+pub use managed_object::*;
+pub use notification::*;
+pub use orphan_cleanup::*;
+pub use pipeline_cleanup::*;
+pub use rw_secret::*;
+pub use schedule::*;
+pub use search::*;
+pub use setting::*;
+pub use sink_maintenance::*;
+pub use sla::*;
+pub use sql_console::*;
+pub use status::*;
+pub use sync_mapping::*;
+pub use sync_metric::*;
 pub use table::*;
 pub use task::*;
+pub use validation::*;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// 泛型分页响应结构，用于所有需要分页的API端点
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub struct PaginatedResponse<T> {
     pub data: Vec<T>,
     pub total: i64,
@@ -27,3 +79,66 @@ impl<T> PaginatedResponse<T> {
         }
     }
 }
+
+/// 分页查询参数，与其他查询参数一起从 query string 中读取
+#[derive(Debug, Clone, Copy, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl PageQuery {
+    /// 未指定 limit 时的默认值
+    pub const DEFAULT_LIMIT: i64 = 20;
+    /// limit 的上限
+    pub const MAX_LIMIT: i64 = 100;
+
+    /// 获取有效的 limit 值（默认20，范围1-100）
+    pub fn get_limit(&self) -> i64 {
+        self.limit_within(Self::DEFAULT_LIMIT, Self::MAX_LIMIT)
+    }
+
+    /// 按接口的默认值和上限获取有效的 limit 值（最小1）
+    pub fn limit_within(&self, default: i64, max: i64) -> i64 {
+        self.limit.unwrap_or(default).clamp(1, max)
+    }
+
+    /// 获取有效的 offset 值（默认0，最小0）
+    pub fn get_offset(&self) -> i64 {
+        self.offset.unwrap_or(0).max(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(limit: Option<i64>, offset: Option<i64>) -> PageQuery {
+        PageQuery { limit, offset }
+    }
+
+    #[test]
+    fn test_page_query_defaults() {
+        let query = PageQuery::default();
+        assert_eq!(query.get_limit(), 20);
+        assert_eq!(query.get_offset(), 0);
+        assert_eq!(query.limit_within(50, 500), 50);
+    }
+
+    #[test]
+    fn test_page_query_validation() {
+        assert_eq!(page(Some(0), None).get_limit(), 1);
+        assert_eq!(page(Some(200), None).get_limit(), 100);
+        assert_eq!(page(Some(-10), None).get_limit(), 1);
+        assert_eq!(page(Some(200), None).limit_within(50, 500), 200);
+        assert_eq!(page(Some(900), None).limit_within(50, 500), 500);
+        assert_eq!(page(None, Some(-10)).get_offset(), 0);
+        assert_eq!(page(None, Some(100)).get_offset(), 100);
+
+        // 与其他查询参数共用同一个 query string
+        let uri = "/api/risingwave/tables?config_id=1&limit=5&offset=10".parse().unwrap();
+        let axum::extract::Query(query) = axum::extract::Query::<PageQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!((query.get_limit(), query.get_offset()), (5, 10));
+    }
+}
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 通知事件类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// Alertmanager 推送的告警
+    Alert,
+    /// 同步任务失败
+    TaskFailed,
+    /// 同步任务完成
+    TaskCompleted,
+}
+
+impl NotificationEvent {
+    pub const ALL: [NotificationEvent; 3] = [
+        NotificationEvent::Alert,
+        NotificationEvent::TaskFailed,
+        NotificationEvent::TaskCompleted,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationEvent::Alert => "alert",
+            NotificationEvent::TaskFailed => "task_failed",
+            NotificationEvent::TaskCompleted => "task_completed",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.as_str() == s)
+    }
+}
+
+/// SMTP 连接加密方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    /// 明文连接（通常为 25 端口）
+    None,
+    /// 明文连接后升级为 TLS（通常为 587 端口）
+    #[default]
+    StartTls,
+    /// 直接建立 TLS 连接（通常为 465 端口）
+    Tls,
+}
+
+/// 通知渠道的类型和配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ChannelSettings {
+    /// 飞书自定义机器人
+    Lark { webhook_url: String },
+    /// 钉钉自定义机器人，设置 secret 时按加签方式发送
+    DingTalk {
+        webhook_url: String,
+        #[serde(default)]
+        secret: Option<String>,
+    },
+    /// Slack Incoming Webhook
+    Slack { webhook_url: String },
+    /// 任意 HTTP 端点，POST 通知的 JSON
+    Webhook {
+        url: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+    /// SMTP 邮件
+    Email {
+        smtp_host: String,
+        smtp_port: u16,
+        #[serde(default)]
+        tls: SmtpTls,
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+        from: String,
+        to: Vec<String>,
+    },
+}
+
+impl ChannelSettings {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ChannelSettings::Lark { .. } => "lark",
+            ChannelSettings::DingTalk { .. } => "dingtalk",
+            ChannelSettings::Slack { .. } => "slack",
+            ChannelSettings::Webhook { .. } => "webhook",
+            ChannelSettings::Email { .. } => "email",
+        }
+    }
+}
+
+/// 通知渠道
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct NotificationChannelConfig {
+    pub id: i64,
+    pub name: String,
+    pub settings: ChannelSettings,
+    /// 订阅的事件
+    pub events: Vec<NotificationEvent>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl NotificationChannelConfig {
+    pub fn subscribes(&self, event: NotificationEvent) -> bool {
+        self.enabled && self.events.contains(&event)
+    }
+}
+
+fn default_events() -> Vec<NotificationEvent> {
+    NotificationEvent::ALL.to_vec()
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 创建或更新通知渠道的请求
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct NotificationChannelRequest {
+    pub name: String,
+    pub settings: ChannelSettings,
+    /// 未指定时订阅全部事件
+    #[serde(default = "default_events")]
+    pub events: Vec<NotificationEvent>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl NotificationChannelRequest {
+    /// 校验渠道配置，返回错误信息
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Channel name is required".to_string());
+        }
+        if self.events.is_empty() {
+            return Err("Subscribe to at least one event".to_string());
+        }
+        match &self.settings {
+            ChannelSettings::Lark { webhook_url }
+            | ChannelSettings::DingTalk { webhook_url, .. }
+            | ChannelSettings::Slack { webhook_url }
+            | ChannelSettings::Webhook {
+                url: webhook_url, ..
+            } => {
+                if !webhook_url.starts_with("http://") && !webhook_url.starts_with("https://") {
+                    return Err("Webhook URL must start with http:// or https://".to_string());
+                }
+            }
+            ChannelSettings::Email {
+                smtp_host,
+                from,
+                to,
+                ..
+            } => {
+                if smtp_host.trim().is_empty() || from.trim().is_empty() || to.is_empty() {
+                    return Err(
+                        "smtp_host, from and to are required for email channels".to_string()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 发送到各渠道的一条通知，通用 Webhook 渠道直接收到此 JSON
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub title: String,
+    pub text: String,
+    /// 附加的结构化信息，例如任务 ID、告警标签
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
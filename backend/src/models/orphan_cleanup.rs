@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+/// 按本工具命名规则识别的 RisingWave 对象类型
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum OrphanObjectKind {
+    Sink,
+    Table,
+    Source,
+    Secret,
+}
+
+impl OrphanObjectKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrphanObjectKind::Sink => "sink",
+            OrphanObjectKind::Table => "table",
+            OrphanObjectKind::Source => "source",
+            OrphanObjectKind::Secret => "secret",
+        }
+    }
+}
+
+/// 判定为遗留对象的原因
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanReason {
+    /// 没有同步任务创建过该目标表
+    NoSyncTask,
+    /// 管道最近一次任务是清理任务
+    PipelineCleanedUp,
+    /// StarRocks 目标表已被手动删除
+    TargetTableMissing,
+    /// Source / Secret 只被遗留对象引用或没有被引用
+    Unreferenced,
+}
+
+/// 没有对应同步任务的 RisingWave 对象
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct OrphanObject {
+    pub kind: OrphanObjectKind,
+    pub schema_name: String,
+    pub name: String,
+    pub reason: OrphanReason,
+}
+
+impl OrphanObject {
+    pub fn key(&self) -> OrphanObjectRef {
+        OrphanObjectRef {
+            kind: self.kind,
+            schema_name: self.schema_name.clone(),
+            name: self.name.clone(),
+        }
+    }
+}
+
+/// 指定要删除的遗留对象
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, utoipa::ToSchema)]
+pub struct OrphanObjectRef {
+    pub kind: OrphanObjectKind,
+    pub schema_name: String,
+    pub name: String,
+}
+
+/// 查询遗留对象
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OrphanQuery {
+    pub config_id: i64,
+}
+
+/// 批量删除遗留对象
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct OrphanCleanupRequest {
+    pub config_id: i64,
+    /// 要删除的对象，为空时列出扫描到的全部遗留对象；实际删除（dry_run 为 false）时必填
+    #[serde(default)]
+    pub objects: Option<Vec<OrphanObjectRef>>,
+    /// 只返回将执行的语句，不执行，默认为 true
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+fn default_dry_run() -> bool {
+    true
+}
+
+/// 删除遗留对象的结果，名称为 `kind schema.name`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct OrphanCleanupResult {
+    pub statements: Vec<String>,
+    pub dropped: Vec<String>,
+    pub failed: Vec<String>,
+    /// 请求中重新扫描时已不再是遗留对象（或已不存在）的对象
+    pub skipped: Vec<String>,
+}
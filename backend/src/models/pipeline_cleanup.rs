@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+/// 清理任务的 mysql_table 标记，清理任务的 requests 为空列表，不计入管道状态
+pub const CLEANUP_TASK_MARKER: &str = "[Cleanup]";
+
+/// 清理 StarRocks 目标表的方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CleanupMode {
+    /// 直接删除目标表
+    Drop,
+    /// 重命名为 `{table}__trash_{时间戳}`，需要时可以手动恢复
+    #[default]
+    Trash,
+}
+
+/// 清理已下线管道的目标对象：RisingWave sink 和表，以及 StarRocks 目标表
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct PipelineCleanupRequest {
+    pub rw_config_id: i64,
+    pub target_database: String,
+    pub target_table: String,
+    #[serde(default)]
+    pub mode: CleanupMode,
+    /// 确认文本，必须为 `target_database.target_table`，dry_run 时可以为空
+    #[serde(default)]
+    pub confirm: Option<String>,
+    /// 只返回将要执行的语句，不执行
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl PipelineCleanupRequest {
+    /// 需要输入的确认文本
+    pub fn expected_confirm(&self) -> String {
+        format!("{}.{}", self.target_database, self.target_table)
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.confirm
+            .as_deref()
+            .is_some_and(|c| c.trim() == self.expected_confirm())
+    }
+}
+
+/// 清理计划中的单条语句，system 为 RisingWave 或 StarRocks
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PipelineCleanupStatement {
+    pub system: String,
+    pub sql: String,
+}
+
+/// 清理计划，不是 dry_run 时返回清理任务的 task_id
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PipelineCleanupResult {
+    pub statements: Vec<PipelineCleanupStatement>,
+    /// Trash 模式下目标表的新名称
+    pub trash_table: Option<String>,
+    pub task_id: Option<i64>,
+}
+
+fn default_teardown_mode() -> CleanupMode {
+    CleanupMode::Drop
+}
+
+/// 拆除同步任务创建的整条管道：RisingWave sink、表，可选的 CDC Source，以及 StarRocks 目标表
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct TaskTeardownRequest {
+    /// StarRocks 目标表的处理方式，默认直接删除
+    #[serde(default = "default_teardown_mode")]
+    pub mode: CleanupMode,
+    /// 同时删除不再被其他表使用的 CDC Source（共享 Source 由引用计数管理）
+    #[serde(default)]
+    pub drop_source: bool,
+    /// 确认文本，必须为任务 ID，dry_run 时可以为空
+    #[serde(default)]
+    pub confirm: Option<String>,
+    /// 只返回将要执行的语句，不执行
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl TaskTeardownRequest {
+    pub fn is_confirmed(&self, task_id: i64) -> bool {
+        self.confirm
+            .as_deref()
+            .is_some_and(|c| c.trim() == task_id.to_string())
+    }
+}
+
+/// 拆除计划，不是 dry_run 时返回拆除任务的 task_id
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TaskTeardownResult {
+    pub statements: Vec<PipelineCleanupStatement>,
+    /// Trash 模式下目标表的新名称，`database.table`
+    pub trash_tables: Vec<String>,
+    pub task_id: Option<i64>,
+}
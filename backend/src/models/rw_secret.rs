@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// 引用 SECRET 的 RisingWave Source 或 Sink
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct RwSecretReference {
+    /// source 或 sink
+    pub kind: String,
+    pub schema_name: String,
+    pub name: String,
+}
+
+/// 同步目标 schema 中的 SECRET 及引用它的对象
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RwSecretUsage {
+    pub id: i32,
+    pub name: String,
+    pub schema_name: String,
+    /// 是否登记为共享对象
+    pub managed: bool,
+    pub references: Vec<RwSecretReference>,
+}
+
+/// 查询 SECRET 的使用情况
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RwSecretQuery {
+    pub config_id: i64,
+}
+
+/// 删除没有被 Source / Sink 引用的 SECRET
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct DeleteUnusedSecretsRequest {
+    pub config_id: i64,
+    /// 只返回将被删除的 SECRET，不执行
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 删除未使用 SECRET 的结果，名称为完整名称
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DeleteUnusedSecretsResult {
+    pub secrets: Vec<String>,
+    pub failed: Vec<String>,
+}
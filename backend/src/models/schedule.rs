@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::SyncRequest;
+
+/// 定时同步：按 cron 表达式在指定时区的本地时间提交同步
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct SyncSchedule {
+    pub id: i64,
+    pub name: String,
+    /// 5 段（分 时 日 月 周）或带秒的 6 段 cron 表达式
+    pub cron: String,
+    /// IANA 时区名，例如 Asia/Shanghai
+    pub timezone: String,
+    #[sqlx(json)]
+    pub requests: Vec<SyncRequest>,
+    pub enabled: bool,
+    /// 下一次运行时间，停用时为空
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// 上一次运行提交的任务，需要审批或与执行中任务重复时为空
+    pub last_task_id: Option<i64>,
+    pub last_error: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 创建或更新定时同步的请求
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct ScheduleRequest {
+    pub name: String,
+    pub cron: String,
+    pub timezone: String,
+    pub requests: Vec<SyncRequest>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// 一次计划运行时间，分别以定时同步的时区和 UTC 表示
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct ScheduledRun {
+    /// 带偏移量的本地时间，例如 2026-03-08T03:00:00-04:00
+    pub local: String,
+    pub utc: DateTime<Utc>,
+}
+
+/// 定时同步及接下来的运行时间
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ScheduleDetail {
+    #[serde(flatten)]
+    pub schedule: SyncSchedule,
+    pub next_runs: Vec<ScheduledRun>,
+}
+
+/// 预览 cron 表达式和时区的请求
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct SchedulePreviewRequest {
+    pub cron: String,
+    pub timezone: String,
+}
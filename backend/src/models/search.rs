@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// 全局搜索的结果类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchResultKind {
+    Connection,
+    /// 同步映射：源表到目标表
+    Mapping,
+    Task,
+    /// RisingWave 中的表、Source、Sink、物化视图等
+    RwObject,
+    SrTable,
+}
+
+/// 单条搜索结果
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SearchResult {
+    pub kind: SearchResultKind,
+    pub name: String,
+    /// 补充说明，例如连接类型、任务状态、对象类型
+    pub detail: Option<String>,
+    /// 连接或任务的 ID
+    pub id: Option<i64>,
+    /// 对象所在的连接
+    pub config_id: Option<i64>,
+    /// 对象所在的 schema / 数据库
+    pub schema: Option<String>,
+}
+
+/// 全局搜索参数，limit 为每种类型最多返回的条数
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    pub q: String,
+    pub limit: Option<i64>,
+}
+
+impl SearchQuery {
+    /// 获取有效的 limit 值（默认20，范围1-100）
+    pub fn get_limit(&self) -> i64 {
+        self.limit.unwrap_or(20).clamp(1, 100)
+    }
+}
+
+/// 全局搜索结果，无法访问的集群记录在 errors 中，不影响其他结果
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SearchResponse {
+    pub query: String,
+    pub results: Vec<SearchResult>,
+    pub errors: Vec<String>,
+}
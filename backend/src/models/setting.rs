@@ -0,0 +1,270 @@
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// RisingWave 的内置默认数据库
+pub const DEFAULT_RW_DATABASE: &str = "dev";
+
+/// 运行时设置的覆盖值，由 SettingsService 从 settings 表加载
+static OVERRIDES: Lazy<RwLock<HashMap<Setting, String>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 可在运行时修改的全局默认值
+/// 取值顺序：settings 表 > 环境变量 > 内置默认值
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, utoipa::ToSchema)]
+pub enum Setting {
+    /// StarRocks 建表默认分桶数，auto 表示自动分桶
+    #[serde(rename = "sr.default_buckets")]
+    SrDefaultBuckets,
+    /// StarRocks 建表默认副本数
+    #[serde(rename = "sr.default_replication_num")]
+    SrDefaultReplicationNum,
+    /// StarRocks 建表默认存储介质
+    #[serde(rename = "sr.default_storage_medium")]
+    SrDefaultStorageMedium,
+    /// 浏览 RisingWave 对象时的默认 schema
+    #[serde(rename = "rw.default_schema")]
+    RwDefaultSchema,
+    /// RisingWave 连接未指定数据库时使用的数据库
+    #[serde(rename = "rw.default_database")]
+    RwDefaultDatabase,
+    /// DDL 最大尝试次数
+    #[serde(rename = "ddl.retry_max_attempts")]
+    DdlRetryMaxAttempts,
+    /// DDL 第一次重试前的等待时间（毫秒）
+    #[serde(rename = "ddl.retry_backoff_ms")]
+    DdlRetryBackoffMs,
+    /// 每个任务的日志行数上限，0 表示不限制
+    #[serde(rename = "task_logs.max_rows")]
+    TaskLogMaxRows,
+    /// 任务日志保留天数，0 表示永久保留
+    #[serde(rename = "task_logs.retention_days")]
+    TaskLogRetentionDays,
+    /// 缓存的连接池空闲多久后关闭（秒）
+    #[serde(rename = "pool.idle_ttl_secs")]
+    PoolIdleTtlSecs,
+    /// SQL 控制台单次查询返回的最大行数
+    #[serde(rename = "sql_console.max_rows")]
+    SqlConsoleMaxRows,
+    /// SQL 控制台查询超时（秒）
+    #[serde(rename = "sql_console.timeout_secs")]
+    SqlConsoleTimeoutSecs,
+    /// 预览 DDL 时先在其上试运行的预发 RisingWave 连接
+    #[serde(rename = "rw.staging_config_id")]
+    RwStagingConfigId,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 13] = [
+        Setting::SrDefaultBuckets,
+        Setting::SrDefaultReplicationNum,
+        Setting::SrDefaultStorageMedium,
+        Setting::RwDefaultSchema,
+        Setting::RwDefaultDatabase,
+        Setting::DdlRetryMaxAttempts,
+        Setting::DdlRetryBackoffMs,
+        Setting::TaskLogMaxRows,
+        Setting::TaskLogRetentionDays,
+        Setting::PoolIdleTtlSecs,
+        Setting::SqlConsoleMaxRows,
+        Setting::SqlConsoleTimeoutSecs,
+        Setting::RwStagingConfigId,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Setting::SrDefaultBuckets => "sr.default_buckets",
+            Setting::SrDefaultReplicationNum => "sr.default_replication_num",
+            Setting::SrDefaultStorageMedium => "sr.default_storage_medium",
+            Setting::RwDefaultSchema => "rw.default_schema",
+            Setting::RwDefaultDatabase => "rw.default_database",
+            Setting::DdlRetryMaxAttempts => "ddl.retry_max_attempts",
+            Setting::DdlRetryBackoffMs => "ddl.retry_backoff_ms",
+            Setting::TaskLogMaxRows => "task_logs.max_rows",
+            Setting::TaskLogRetentionDays => "task_logs.retention_days",
+            Setting::PoolIdleTtlSecs => "pool.idle_ttl_secs",
+            Setting::SqlConsoleMaxRows => "sql_console.max_rows",
+            Setting::SqlConsoleTimeoutSecs => "sql_console.timeout_secs",
+            Setting::RwStagingConfigId => "rw.staging_config_id",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|setting| setting.as_str() == s)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Setting::SrDefaultBuckets => "Default bucket count for new StarRocks tables (number or auto)",
+            Setting::SrDefaultReplicationNum => "Default replication_num for new StarRocks tables",
+            Setting::SrDefaultStorageMedium => "Default storage medium for new StarRocks tables (HDD or SSD)",
+            Setting::RwDefaultSchema => "Default RisingWave schema when browsing objects",
+            Setting::RwDefaultDatabase => "RisingWave database used when a connection does not specify one",
+            Setting::DdlRetryMaxAttempts => "Maximum attempts for a DDL statement, including the first one",
+            Setting::DdlRetryBackoffMs => "Wait before the first DDL retry in milliseconds, doubled on each retry",
+            Setting::TaskLogMaxRows => "Maximum log rows written per task, 0 for unlimited",
+            Setting::TaskLogRetentionDays => "Days to keep task logs, 0 to keep forever",
+            Setting::PoolIdleTtlSecs => "Seconds a cached connection pool may stay idle before it is closed",
+            Setting::SqlConsoleMaxRows => "Maximum rows returned by a SQL console query",
+            Setting::SqlConsoleTimeoutSecs => "Timeout of a SQL console query in seconds",
+            Setting::RwStagingConfigId => "RisingWave connection used to dry-run generated DDL before it reaches production",
+        }
+    }
+
+    /// 兼容的环境变量
+    pub fn env_var(&self) -> &'static str {
+        match self {
+            Setting::SrDefaultBuckets => "SR_DEFAULT_BUCKETS",
+            Setting::SrDefaultReplicationNum => "SR_DEFAULT_REPLICATION_NUM",
+            Setting::SrDefaultStorageMedium => "SR_DEFAULT_STORAGE_MEDIUM",
+            Setting::RwDefaultSchema => "RW_DEFAULT_SCHEMA",
+            Setting::RwDefaultDatabase => "RW_DEFAULT_DATABASE",
+            Setting::DdlRetryMaxAttempts => "DDL_RETRY_MAX_ATTEMPTS",
+            Setting::DdlRetryBackoffMs => "DDL_RETRY_BACKOFF_MS",
+            Setting::TaskLogMaxRows => "TASK_LOG_MAX_ROWS",
+            Setting::TaskLogRetentionDays => "TASK_LOG_RETENTION_DAYS",
+            Setting::PoolIdleTtlSecs => "POOL_IDLE_TTL_SECS",
+            Setting::SqlConsoleMaxRows => "SQL_CONSOLE_MAX_ROWS",
+            Setting::SqlConsoleTimeoutSecs => "SQL_CONSOLE_TIMEOUT_SECS",
+            Setting::RwStagingConfigId => "RW_STAGING_CONFIG_ID",
+        }
+    }
+
+    /// 内置默认值，为空表示不指定
+    pub fn default_value(&self) -> Option<&'static str> {
+        match self {
+            Setting::SrDefaultBuckets
+            | Setting::SrDefaultStorageMedium
+            | Setting::RwStagingConfigId => None,
+            Setting::SrDefaultReplicationNum => Some("1"),
+            Setting::RwDefaultSchema => Some("public"),
+            Setting::RwDefaultDatabase => Some(DEFAULT_RW_DATABASE),
+            Setting::DdlRetryMaxAttempts => Some("3"),
+            Setting::DdlRetryBackoffMs => Some("1000"),
+            Setting::TaskLogMaxRows => Some("5000"),
+            Setting::TaskLogRetentionDays => Some("0"),
+            Setting::PoolIdleTtlSecs => Some("300"),
+            Setting::SqlConsoleMaxRows => Some("1000"),
+            Setting::SqlConsoleTimeoutSecs => Some("30"),
+        }
+    }
+
+    /// 校验并规范化设置值
+    pub fn normalize(&self, value: &str) -> Result<String, String> {
+        let value = value.trim();
+        let invalid = || format!("Invalid value for {}: {}", self.as_str(), value);
+        match self {
+            Setting::SrDefaultBuckets if value.eq_ignore_ascii_case("auto") => {
+                Ok("auto".to_string())
+            }
+            Setting::SrDefaultBuckets
+            | Setting::SrDefaultReplicationNum
+            | Setting::DdlRetryMaxAttempts
+            | Setting::PoolIdleTtlSecs
+            | Setting::SqlConsoleMaxRows
+            | Setting::SqlConsoleTimeoutSecs => match value.parse::<u32>() {
+                Ok(n) if n > 0 => Ok(n.to_string()),
+                _ => Err(invalid()),
+            },
+            Setting::RwStagingConfigId => match value.parse::<i64>() {
+                Ok(id) if id > 0 => Ok(id.to_string()),
+                _ => Err(invalid()),
+            },
+            Setting::DdlRetryBackoffMs
+            | Setting::TaskLogMaxRows
+            | Setting::TaskLogRetentionDays => value
+                .parse::<u64>()
+                .map(|n| n.to_string())
+                .map_err(|_| invalid()),
+            Setting::SrDefaultStorageMedium => match value.to_uppercase().as_str() {
+                medium @ ("HDD" | "SSD") => Ok(medium.to_string()),
+                _ => Err(invalid()),
+            },
+            Setting::RwDefaultSchema | Setting::RwDefaultDatabase => {
+                if !value.is_empty()
+                    && value.len() <= 64
+                    && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    Ok(value.to_string())
+                } else {
+                    Err(invalid())
+                }
+            }
+        }
+    }
+
+    /// 当前生效的值及其来源
+    pub fn resolve(&self) -> (Option<String>, SettingSource) {
+        if let Some(value) = OVERRIDES.read().unwrap().get(self) {
+            return (Some(value.clone()), SettingSource::Database);
+        }
+        if let Some(value) = std::env::var(self.env_var())
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+        {
+            return (Some(value), SettingSource::Env);
+        }
+        (
+            self.default_value().map(str::to_string),
+            SettingSource::Default,
+        )
+    }
+
+    /// 当前生效的值
+    pub fn current(&self) -> Option<String> {
+        self.resolve().0
+    }
+
+    /// 当前生效的值按类型解析，解析失败时使用内置默认值
+    pub fn parsed<T: std::str::FromStr>(&self) -> Option<T> {
+        self.current()
+            .and_then(|v| v.trim().parse().ok())
+            .or_else(|| self.default_value().and_then(|v| v.parse().ok()))
+    }
+
+    /// 替换全部覆盖值
+    pub fn load_overrides(values: HashMap<Setting, String>) {
+        *OVERRIDES.write().unwrap() = values;
+    }
+
+    /// 设置或清除单个覆盖值
+    pub fn set_override(&self, value: Option<String>) {
+        let mut overrides = OVERRIDES.write().unwrap();
+        match value {
+            Some(value) => overrides.insert(*self, value),
+            None => overrides.remove(self),
+        };
+    }
+}
+
+/// 设置值的来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingSource {
+    /// settings 表
+    Database,
+    /// 环境变量
+    Env,
+    /// 内置默认值
+    Default,
+}
+
+/// 设置项及当前生效的值
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SettingValue {
+    pub name: String,
+    pub value: Option<String>,
+    pub source: SettingSource,
+    pub description: String,
+    pub env_var: String,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// 更新设置的请求
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct UpdateSettingRequest {
+    pub value: String,
+}
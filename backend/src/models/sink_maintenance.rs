@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// 检查或重建某个 RisingWave 上由同步任务创建的 sink
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct RecreateSinksRequest {
+    pub rw_config_id: i64,
+    /// 只返回差异，不重建
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// sink 的单个属性差异，current 为空表示 RisingWave 上没有该属性
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+pub struct SinkPropertyChange {
+    pub key: String,
+    pub current: Option<String>,
+    pub expected: String,
+}
+
+/// 属性与当前生成器输出不一致的 sink
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SinkDrift {
+    pub target_database: String,
+    pub target_table: String,
+    pub sink_name: String,
+    pub changes: Vec<SinkPropertyChange>,
+}
+
+/// 无法检查的 sink 及原因
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SkippedSink {
+    pub sink_name: String,
+    pub reason: String,
+}
+
+/// sink 检查结果，有差异且不是 dry_run 时返回重建任务的 task_id
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RecreateSinksResult {
+    pub checked: usize,
+    pub drifts: Vec<SinkDrift>,
+    pub skipped: Vec<SkippedSink>,
+    pub task_id: Option<i64>,
+}
@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::PipelineHealth;
+
+/// 管道健康采样
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct HealthSample {
+    pub id: i64,
+    pub source: String,
+    pub target: String,
+    #[sqlx(try_from = "String")]
+    pub health: PipelineHealth,
+    pub last_sync_at: Option<DateTime<Utc>>,
+    pub sampled_at: DateTime<Utc>,
+}
+
+/// 单个管道在统计窗口内的可用率
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct SlaWindow {
+    pub source: String,
+    pub target: String,
+    pub samples: i64,
+    /// 非 red 的采样数
+    pub up_samples: i64,
+    /// green 的采样数
+    pub green_samples: i64,
+}
+
+impl SlaWindow {
+    /// 可用率（%）：非 red 采样占比
+    pub fn uptime_percent(&self) -> f64 {
+        Self::percent(self.up_samples, self.samples)
+    }
+
+    /// SLA 达成率（%）：green 采样占比
+    pub fn sla_percent(&self) -> f64 {
+        Self::percent(self.green_samples, self.samples)
+    }
+
+    fn percent(part: i64, total: i64) -> f64 {
+        if total == 0 {
+            return 100.0;
+        }
+        (part as f64 * 10000.0 / total as f64).round() / 100.0
+    }
+}
+
+/// 管道 SLA 报告（7 天 / 30 天）
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PipelineSla {
+    pub source: String,
+    pub target: String,
+    pub uptime_7d: f64,
+    pub sla_7d: f64,
+    pub samples_7d: i64,
+    pub uptime_30d: f64,
+    pub sla_30d: f64,
+    pub samples_30d: i64,
+}
+
+/// SLA 违约区间（连续的非 green 采样）
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SlaBreach {
+    pub source: String,
+    pub target: String,
+    /// 区间内最差的健康状态
+    pub worst_health: PipelineHealth,
+    pub started_at: DateTime<Utc>,
+    /// 为空表示仍未恢复
+    pub ended_at: Option<DateTime<Utc>>,
+    pub samples: i64,
+}
+
+/// SLA 违约查询参数
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SlaBreachQuery {
+    pub days: Option<i64>,
+    pub target: Option<String>,
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// 在 RisingWave 上执行的只读查询
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct SqlQueryRequest {
+    pub config_id: i64,
+    pub sql: String,
+    /// 返回的最大行数，不能超过 sql_console.max_rows
+    #[serde(default)]
+    pub max_rows: Option<usize>,
+}
+
+/// 查询结果，所有值按 RisingWave 的文本格式返回，NULL 为 null
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SqlQueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Option<String>>>,
+    /// 结果超过行数上限被截断
+    pub truncated: bool,
+    pub elapsed_ms: u64,
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::AlertRecord;
+
+/// 管道健康状态
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, utoipa::ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum PipelineHealth {
+    /// 最近一次同步成功
+    Green,
+    /// 同步进行中，或最近一次失败但之前成功过
+    Yellow,
+    /// 最近一次同步失败且从未成功
+    Red,
+}
+
+impl TryFrom<String> for PipelineHealth {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.as_str() {
+            "green" => Ok(PipelineHealth::Green),
+            "yellow" => Ok(PipelineHealth::Yellow),
+            "red" => Ok(PipelineHealth::Red),
+            _ => Err(format!("Unknown pipeline health: {}", s)),
+        }
+    }
+}
+
+impl PipelineHealth {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PipelineHealth::Green => "green",
+            PipelineHealth::Yellow => "yellow",
+            PipelineHealth::Red => "red",
+        }
+    }
+}
+
+/// 单个管道（源表 -> 目标表）的公开状态，不包含任何连接信息
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PipelineStatus {
+    pub source: String,
+    pub target: String,
+    pub health: PipelineHealth,
+    pub last_status: String,
+    pub last_task_at: DateTime<Utc>,
+    pub last_sync_at: Option<DateTime<Utc>>,
+}
+
+/// 状态页汇总
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct StatusSummary {
+    pub overall: PipelineHealth,
+    pub generated_at: DateTime<Utc>,
+    pub pipelines: Vec<PipelineStatus>,
+}
+
+/// 管道详情
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PipelineDetail {
+    /// 目标表 `database.table`
+    pub target: String,
+    /// 最近任务中该管道的状态，最近的任务都不涉及该管道时为空
+    pub status: Option<PipelineStatus>,
+    /// 最近一次涉及该管道的任务
+    pub last_task_id: Option<i64>,
+    /// 关联到该管道、仍在触发的告警
+    pub active_incidents: Vec<AlertRecord>,
+}
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{NestedColumnMode, SpatialColumnMode, StarRocksPartition, SyncOptions};
+
+/// 保存的映射中的一张表
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MappingTable {
+    pub mysql_database: String,
+    pub mysql_table: String,
+    /// 为空时使用映射的 target_database，仍为空时与 MySQL 库名相同
+    #[serde(default)]
+    pub target_database: Option<String>,
+    /// 为空时按映射的 target_table_template 生成，没有模板时与 MySQL 表名相同
+    #[serde(default)]
+    pub target_table: Option<String>,
+    #[serde(default)]
+    pub row_filter: Option<String>,
+    #[serde(default)]
+    pub partition: Option<StarRocksPartition>,
+    #[serde(default)]
+    pub column_mapping: BTreeMap<String, String>,
+    #[serde(default)]
+    pub nested_columns: BTreeMap<String, NestedColumnMode>,
+    #[serde(default)]
+    pub spatial_columns: BTreeMap<String, SpatialColumnMode>,
+}
+
+/// 同步映射：保存连接、表清单、同步选项和目标表名模板，一次调用即可按定义重新提交同步
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct SyncMapping {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub mysql_config_id: i64,
+    pub rw_config_id: i64,
+    pub sr_config_id: i64,
+    pub target_database: Option<String>,
+    #[sqlx(json)]
+    pub tables: Vec<MappingTable>,
+    #[sqlx(json)]
+    pub options: SyncOptions,
+    pub target_table_template: Option<String>,
+    /// 上一次运行提交的任务，需要审批时为空
+    pub last_task_id: Option<i64>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// 暂停后 sink 停止写入，相关定时同步停用，映射不能运行
+    pub paused: bool,
+    pub paused_at: Option<DateTime<Utc>>,
+    /// 暂停时停用的定时同步，恢复时重新启用
+    #[sqlx(json)]
+    pub paused_schedule_ids: Vec<i64>,
+    /// 乐观锁版本号，每次更新加 1
+    pub version: i64,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SyncMapping {
+    /// 映射的定义部分
+    pub fn definition(&self) -> SyncMappingRequest {
+        SyncMappingRequest {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            mysql_config_id: self.mysql_config_id,
+            rw_config_id: self.rw_config_id,
+            sr_config_id: self.sr_config_id,
+            target_database: self.target_database.clone(),
+            tables: self.tables.clone(),
+            options: self.options.clone(),
+            target_table_template: self.target_table_template.clone(),
+            version: None,
+        }
+    }
+}
+
+/// 创建或更新同步映射的请求
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SyncMappingRequest {
+    /// 命令行的映射文件可以省略
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub mysql_config_id: i64,
+    pub rw_config_id: i64,
+    pub sr_config_id: i64,
+    #[serde(default)]
+    pub target_database: Option<String>,
+    pub tables: Vec<MappingTable>,
+    #[serde(default)]
+    pub options: SyncOptions,
+    /// 目标表名模板，例如 `ods_{table}`，规则与 /api/sync/multiple 的 target_table_template 相同
+    #[serde(default)]
+    pub target_table_template: Option<String>,
+    /// 更新时期望的版本号（也可以通过 If-Match 头传入），为空时不检查
+    #[serde(default)]
+    pub version: Option<i64>,
+}
+
+/// 暂停或恢复管道的结果，sink 名称为 `schema.name`
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PipelinePauseResult {
+    pub mapping_id: i64,
+    pub paused: bool,
+    pub sinks: Vec<String>,
+    /// 执行失败的 sink 及错误
+    pub failed: Vec<String>,
+    /// 停用或重新启用的定时同步
+    pub schedule_ids: Vec<i64>,
+}
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 单表在 MySQL、RisingWave 和 StarRocks 三端的行数
+/// lag 为 MySQL 与 StarRocks 的行数差，任一端查询失败时为空
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct SyncMetric {
+    pub id: i64,
+    pub task_id: i64,
+    pub mysql_database: String,
+    pub mysql_table: String,
+    pub target_database: String,
+    pub target_table: String,
+    pub source_count: Option<i64>,
+    pub rw_count: Option<i64>,
+    pub target_count: Option<i64>,
+    pub lag: Option<i64>,
+    pub error_message: Option<String>,
+    pub sampled_at: DateTime<Utc>,
+}
+
+impl SyncMetric {
+    pub fn lag_of(source_count: Option<i64>, target_count: Option<i64>) -> Option<i64> {
+        Some(source_count? - target_count?)
+    }
+}
+
+/// 行数趋势查询参数
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SyncMetricQuery {
+    /// 目标表 `database.table`，为空时返回所有表
+    pub target: Option<String>,
+    /// 最近天数（默认 7，最多 90）
+    pub days: Option<i64>,
+}
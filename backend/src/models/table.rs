@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 /// 数据库表的列信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct Column {
     pub name: String,
     pub data_type: String,
     pub is_nullable: bool,
+    /// 默认值，MySQL 列为 SHOW CREATE TABLE 中的写法（例如 `'abc'`、`CURRENT_TIMESTAMP`）
     pub default_value: Option<String>,
     pub comment: Option<String>,
     pub character_maximum_length: Option<i64>,
@@ -13,8 +14,93 @@ pub struct Column {
     pub numeric_scale: Option<i64>,
 }
 
+impl Column {
+    /// STRUCT 列的字段（RisingWave 目录类型，例如 `struct<a integer, b character varying>`），
+    /// 不是 STRUCT 类型时返回 None
+    pub fn struct_fields(&self) -> Option<Vec<Column>> {
+        let trimmed = self.data_type.trim();
+        let inner = trimmed
+            .get(..7)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("struct<"))
+            .and_then(|_| trimmed[7..].strip_suffix('>'))?;
+
+        // 按顶层逗号拆分字段，忽略嵌套类型和引号中的逗号
+        let mut parts = Vec::new();
+        let mut depth = 0i32;
+        let mut quoted = false;
+        let mut start = 0;
+        for (i, c) in inner.char_indices() {
+            match c {
+                '"' => quoted = !quoted,
+                '<' | '(' | '[' if !quoted => depth += 1,
+                '>' | ')' | ']' if !quoted => depth -= 1,
+                ',' if !quoted && depth == 0 => {
+                    parts.push(&inner[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(&inner[start..]);
+
+        parts
+            .into_iter()
+            .map(|part| {
+                let part = part.trim();
+                let (name, data_type) = match part.strip_prefix('"') {
+                    Some(rest) => {
+                        let (name, data_type) = rest.split_once('"')?;
+                        (name.to_string(), data_type)
+                    }
+                    None => {
+                        let (name, data_type) = part.split_once(char::is_whitespace)?;
+                        (name.to_string(), data_type)
+                    }
+                };
+                let data_type = data_type.trim();
+                (!name.is_empty() && !data_type.is_empty()).then(|| Column {
+                    name,
+                    data_type: data_type.to_string(),
+                    is_nullable: true,
+                    default_value: None,
+                    comment: None,
+                    character_maximum_length: None,
+                    numeric_precision: None,
+                    numeric_scale: None,
+                })
+            })
+            .collect()
+    }
+
+    /// 嵌套类型（STRUCT、数组、MAP）或 JSON 列
+    pub fn is_nested(&self) -> bool {
+        let lower = self.data_type.trim().to_lowercase();
+        lower.ends_with("[]")
+            || lower.starts_with("struct<")
+            || lower.starts_with("map(")
+            || matches!(lower.as_str(), "json" | "jsonb")
+    }
+
+    /// MySQL 空间类型列（GEOMETRY、POINT、POLYGON 等），忽略 SRID 等修饰
+    pub fn is_spatial(&self) -> bool {
+        let lower = self.data_type.trim().to_lowercase();
+        matches!(
+            lower.split_whitespace().next().unwrap_or(""),
+            "geometry"
+                | "point"
+                | "linestring"
+                | "polygon"
+                | "multipoint"
+                | "multilinestring"
+                | "multipolygon"
+                | "geometrycollection"
+                | "geomcollection"
+        )
+    }
+}
+
 /// 表索引信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct Index {
     pub index_name: String,
     pub column_name: String,
@@ -22,12 +108,47 @@ pub struct Index {
     pub seq_in_index: i32,
 }
 
+/// 列类型的来源，决定映射到 StarRocks 类型时使用的规则
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum ColumnTypeSource {
+    /// MySQL 的 COLUMN_TYPE，例如 `bigint unsigned`
+    #[default]
+    #[serde(rename = "mysql")]
+    MySQL,
+    /// RisingWave 目录中的类型名，例如 `character varying`、`integer[]`
+    #[serde(rename = "risingwave")]
+    RisingWave,
+}
+
 /// 表结构信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct TableSchema {
     pub database: String,
     pub table_name: String,
     pub columns: Vec<Column>,
     pub primary_keys: Vec<String>,
     pub indexes: Vec<Index>,
+    #[serde(default)]
+    pub type_source: ColumnTypeSource,
+    /// 表注释（MySQL 的 TABLE_COMMENT），没有注释时为 None
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// 源表的容量统计（来自 INFORMATION_SCHEMA.TABLES，为估算值）
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TableStats {
+    pub database: String,
+    pub table_name: String,
+    pub estimated_rows: i64,
+    pub data_bytes: i64,
+}
+
+/// 表数据预览，值为文本格式，不是有效 UTF-8 的二进制值显示为十六进制（`0x...`）
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TablePreview {
+    pub database: String,
+    pub table_name: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Option<String>>>,
 }
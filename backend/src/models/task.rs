@@ -1,8 +1,11 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{Column, ColumnTypeSource, Setting, TableSchema};
 
 /// 任务状态
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
 pub enum TaskStatus {
     #[serde(rename = "pending")]
     Pending,
@@ -14,6 +17,9 @@ pub enum TaskStatus {
     Failed,
     #[serde(rename = "cancelled")]
     Cancelled,
+    /// 服务重启或关闭时仍在执行的任务
+    #[serde(rename = "interrupted")]
+    Interrupted,
 }
 
 // 实现 String 到 TaskStatus 的转换（用于 SQLx）
@@ -27,6 +33,7 @@ impl TryFrom<String> for TaskStatus {
             "completed" => Ok(TaskStatus::Completed),
             "failed" => Ok(TaskStatus::Failed),
             "cancelled" => Ok(TaskStatus::Cancelled),
+            "interrupted" => Ok(TaskStatus::Interrupted),
             _ => Err(format!("Unknown task status: {}", s)),
         }
     }
@@ -40,12 +47,242 @@ impl TaskStatus {
             TaskStatus::Completed => "completed",
             TaskStatus::Failed => "failed",
             TaskStatus::Cancelled => "cancelled",
+            TaskStatus::Interrupted => "interrupted",
+        }
+    }
+}
+
+/// StarRocks 容量预检模式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CapacityCheckMode {
+    /// 不做检查
+    Disabled,
+    /// 空间不足时只记录警告
+    #[default]
+    Warn,
+    /// 空间不足时阻止任务执行
+    Block,
+}
+
+/// StarRocks 建表属性
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(default)]
+pub struct StarRocksTableOptions {
+    /// 副本数
+    pub replication_num: u32,
+    /// 分桶数，为空时不指定 BUCKETS，由 StarRocks 决定
+    pub buckets: Option<u32>,
+    /// 启用自动分桶（忽略 buckets，StarRocks 2.5.7+）
+    pub auto_bucket: bool,
+    /// 存储介质：HDD 或 SSD
+    pub storage_medium: Option<String>,
+    /// 动态分区属性，键可以省略 "dynamic_partition." 前缀，例如 {"enable": "true", "time_unit": "DAY"}
+    pub dynamic_partition: BTreeMap<String, String>,
+    /// Colocation Group 名称，同组的表需要相同的分桶数、副本数和分桶列类型
+    pub colocate_with: Option<String>,
+    /// 排序键列（ORDER BY），为空时按主键排序
+    pub sort_keys: Vec<String>,
+    /// 创建 Bitmap 索引的列，适合低基数列的等值过滤
+    pub bitmap_indexes: Vec<String>,
+    /// 创建 Bloom Filter 索引的列，适合高基数列的点查
+    pub bloom_filter_columns: Vec<String>,
+}
+
+impl Default for StarRocksTableOptions {
+    fn default() -> Self {
+        Self {
+            replication_num: 1,
+            buckets: None,
+            auto_bucket: false,
+            storage_medium: None,
+            dynamic_partition: BTreeMap::new(),
+            colocate_with: None,
+            sort_keys: Vec::new(),
+            bitmap_indexes: Vec::new(),
+            bloom_filter_columns: Vec::new(),
+        }
+    }
+}
+
+impl StarRocksTableOptions {
+    /// 全局默认建表属性，取自运行时设置（兼容环境变量
+    /// SR_DEFAULT_REPLICATION_NUM、SR_DEFAULT_BUCKETS、SR_DEFAULT_STORAGE_MEDIUM）
+    pub fn global_defaults() -> Self {
+        let mut options = Self::default();
+        if let Some(replication_num) = Setting::SrDefaultReplicationNum.parsed() {
+            options.replication_num = replication_num;
+        }
+        if let Some(buckets) = Setting::SrDefaultBuckets.current() {
+            if buckets.trim().eq_ignore_ascii_case("auto") {
+                options.auto_bucket = true;
+            } else {
+                options.buckets = buckets.trim().parse().ok();
+            }
+        }
+        options.storage_medium = Setting::SrDefaultStorageMedium.current();
+        options
+    }
+}
+
+/// DDL 执行的重试策略（指数退避）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// 最大尝试次数（包含第一次），1 表示不重试
+    pub max_attempts: u32,
+    /// 第一次重试前的等待时间（毫秒），之后每次翻倍
+    pub initial_backoff_ms: u64,
+    /// 单次等待上限（毫秒）
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 30_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 全局默认重试策略，取自运行时设置（兼容环境变量 DDL_RETRY_MAX_ATTEMPTS、DDL_RETRY_BACKOFF_MS）
+    pub fn global_defaults() -> Self {
+        let mut policy = Self::default();
+        if let Some(max_attempts) = Setting::DdlRetryMaxAttempts.parsed() {
+            policy.max_attempts = max_attempts;
+        }
+        if let Some(backoff) = Setting::DdlRetryBackoffMs.parsed() {
+            policy.initial_backoff_ms = backoff;
+        }
+        policy
+    }
+
+    /// 第 attempt 次失败后的等待时间
+    pub fn backoff(&self, attempt: u32) -> std::time::Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(20);
+        let millis = self
+            .initial_backoff_ms
+            .saturating_mul(factor)
+            .min(self.max_backoff_ms);
+        std::time::Duration::from_millis(millis)
+    }
+}
+
+/// 任务日志详细程度
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskLogLevel {
+    /// 只记录步骤边界（开始、每张表的开始/完成、结束）和警告/错误
+    Minimal,
+    /// 记录每个操作步骤
+    #[default]
+    Normal,
+    /// 额外记录生成的 DDL 和执行耗时
+    Debug,
+}
+
+/// StarRocks 分区时间粒度
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PartitionGranularity {
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+impl PartitionGranularity {
+    /// 派生分区列的类型：按小时截断保留时间，其余截断为日期
+    pub fn derived_type(&self) -> &'static str {
+        match self {
+            PartitionGranularity::Hour => "DATETIME",
+            _ => "DATE",
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            PartitionGranularity::Hour => "hour",
+            PartitionGranularity::Day => "day",
+            PartitionGranularity::Month => "month",
+            PartitionGranularity::Year => "year",
+        }
+    }
+}
+
+/// RANGE 分区定义，区间为 [lower, upper)
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RangePartition {
+    pub name: String,
+    pub lower: String,
+    pub upper: String,
+}
+
+/// StarRocks 分区配置，分区列必须是 DATE/DATETIME 类型且属于主键
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StarRocksPartition {
+    /// 表达式分区：PARTITION BY date_trunc('<granularity>', column)（StarRocks 3.1+）
+    Expression {
+        column: String,
+        granularity: PartitionGranularity,
+        /// 派生分区列名：设置后目标表新增该列（由 column 按粒度截断得到）并加入主键，
+        /// column 本身不必属于主键，但写入后不能再修改（例如 created_at）
+        #[serde(default)]
+        derived_column: Option<String>,
+    },
+    /// RANGE 分区：PARTITION BY RANGE(column)，ranges 为空时需配合动态分区使用
+    Range {
+        column: String,
+        #[serde(default)]
+        ranges: Vec<RangePartition>,
+    },
+}
+
+impl StarRocksPartition {
+    /// 目标表中的分区列，设置了派生列时为派生列
+    pub fn column(&self) -> &str {
+        match self {
+            StarRocksPartition::Expression {
+                derived_column: Some(derived),
+                ..
+            } => derived,
+            StarRocksPartition::Expression { column, .. } => column,
+            StarRocksPartition::Range { column, .. } => column,
+        }
+    }
+
+    /// 派生分区列：(派生列名, 来源列名, 粒度)
+    pub fn derivation(&self) -> Option<(&str, &str, PartitionGranularity)> {
+        match self {
+            StarRocksPartition::Expression {
+                column,
+                granularity,
+                derived_column: Some(derived),
+            } => Some((derived, column, *granularity)),
+            _ => None,
         }
     }
 }
 
 /// 同步选项
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(default)]
 pub struct SyncOptions {
     /// 是否重建 RisingWave Source
     pub recreate_rw_source: bool,
@@ -53,20 +290,44 @@ pub struct SyncOptions {
     pub recreate_sr_table: bool,
     /// 是否清空 StarRocks 表数据
     pub truncate_sr_table: bool,
+    /// 表已存在时为 MySQL 新增的列执行 ALTER TABLE ADD COLUMN
+    pub reconcile_sr_schema: bool,
+    /// 建表前的 StarRocks 容量预检模式
+    pub capacity_check: CapacityCheckMode,
+    /// 回填后 StarRocks 需保留的最小空闲比例（0~1），为空时使用全局默认值
+    pub min_free_space_ratio: Option<f64>,
+    /// StarRocks 建表属性，为空时使用全局默认值
+    pub starrocks_table: Option<StarRocksTableOptions>,
+    /// 任务日志详细程度
+    pub log_level: TaskLogLevel,
+    /// DDL 执行的重试策略，为空时使用全局默认值
+    pub retry: Option<RetryPolicy>,
+    /// 固定 CDC Source 的 server.id，为空时自动分配
+    pub server_id: Option<u32>,
+    /// 与同一 MySQL 数据库的其他映射共享 CDC Source，不再按目标 schema 各建一个
+    pub share_source: bool,
+    /// 所属工作区，用于功能开关，为空时使用默认工作区
+    pub workspace: Option<String>,
+    /// 任务完成或失败时不发送通知
+    pub disable_notifications: bool,
 }
 
-impl Default for SyncOptions {
-    fn default() -> Self {
-        Self {
-            recreate_rw_source: false,
-            recreate_sr_table: false,
-            truncate_sr_table: false,
-        }
+impl SyncOptions {
+    /// 本次同步实际使用的重试策略
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry.clone().unwrap_or_else(RetryPolicy::global_defaults)
+    }
+
+    /// 本次同步实际使用的 StarRocks 建表属性
+    pub fn starrocks_table_options(&self) -> StarRocksTableOptions {
+        self.starrocks_table
+            .clone()
+            .unwrap_or_else(StarRocksTableOptions::global_defaults)
     }
 }
 
 /// 同步请求
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct SyncRequest {
     pub mysql_config_id: i64,
     pub rw_config_id: i64,
@@ -76,11 +337,231 @@ pub struct SyncRequest {
     pub target_database: String,
     pub target_table: String,
     pub options: SyncOptions,
+    /// 行过滤条件（SQL 谓词），例如 `deleted_at IS NULL`，只同步满足条件的行
+    #[serde(default)]
+    pub row_filter: Option<String>,
+    /// StarRocks 目标表分区配置（使用目标列名），为空时不分区
+    #[serde(default)]
+    pub partition: Option<StarRocksPartition>,
+    /// 列名映射：源列名 -> 目标列名，未列出的列保持原名
+    #[serde(default)]
+    pub column_mapping: BTreeMap<String, String>,
+    /// 嵌套类型列的写入方式：源列名 -> flatten / json，未列出的列原样写入
+    #[serde(default)]
+    pub nested_columns: BTreeMap<String, NestedColumnMode>,
+    /// 空间类型列的写入方式：源列名 -> hex / binary，未列出的空间类型列按 hex 写入
+    #[serde(default)]
+    pub spatial_columns: BTreeMap<String, SpatialColumnMode>,
+}
+
+/// 嵌套类型列（STRUCT、数组、MAP、JSON）写入 StarRocks 的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NestedColumnMode {
+    /// STRUCT 字段展开为独立的列，`addr.city` 写入 `addr_city`，嵌套的 STRUCT 逐层展开
+    Flatten,
+    /// 序列化为 JSON 字符串，写入 STRING 列
+    Json,
+}
+
+/// 空间类型列（GEOMETRY、POINT、POLYGON 等）写入 StarRocks 的方式
+/// RisingWave 没有空间函数，无法转换为 WKT，CDC 表中的值为 MySQL 内部格式（4 字节 SRID + WKB），
+/// Sink 去掉 SRID 后以标准 WKB 写入
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SpatialColumnMode {
+    /// 十六进制编码的 WKB（不是 WKT），写入 STRING 列
+    #[default]
+    Hex,
+    /// WKB 二进制，写入 VARBINARY 列（Doris 没有 VARBINARY，仍写入 STRING 列）
+    Binary,
 }
 
+impl SyncRequest {
+    /// 源列在目标表中的列名
+    pub fn target_column_name<'a>(&'a self, source_column: &'a str) -> &'a str {
+        self.column_mapping
+            .get(source_column)
+            .map(String::as_str)
+            .unwrap_or(source_column)
+    }
+
+    /// 展开 STRUCT 列的叶子字段：(字段路径, 目标列)，列未配置为 flatten 或不是 STRUCT 时返回 None
+    pub fn flattened_fields(&self, column: &Column) -> Option<Vec<(Vec<String>, Column)>> {
+        if self.nested_columns.get(&column.name) != Some(&NestedColumnMode::Flatten) {
+            return None;
+        }
+        fn leaves(
+            prefix: &[String],
+            target: &str,
+            column: &Column,
+            out: &mut Vec<(Vec<String>, Column)>,
+        ) -> bool {
+            let Some(fields) = column.struct_fields() else {
+                return false;
+            };
+            for field in fields {
+                let mut path = prefix.to_vec();
+                path.push(field.name.clone());
+                let name = format!("{}_{}", target, field.name);
+                if !leaves(&path, &name, &field, out) {
+                    out.push((path, Column { name, ..field }));
+                }
+            }
+            true
+        }
+        let mut out = Vec::new();
+        leaves(&[], self.target_column_name(&column.name), column, &mut out).then_some(out)
+    }
+
+    /// 空间类型列的写入方式，不是空间类型的列返回 None
+    pub fn spatial_mode(&self, column: &Column) -> Option<SpatialColumnMode> {
+        column.is_spatial().then(|| {
+            self.spatial_columns
+                .get(&column.name)
+                .copied()
+                .unwrap_or_default()
+        })
+    }
+
+    /// 源列写入的目标列（按列名映射、嵌套列和空间类型列配置）
+    pub fn target_columns(&self, column: &Column, source: ColumnTypeSource) -> Vec<Column> {
+        if let Some(fields) = self.flattened_fields(column) {
+            return fields.into_iter().map(|(_, field)| field).collect();
+        }
+        let mut target = column.clone();
+        target.name = self.target_column_name(&column.name).to_string();
+        if self.nested_columns.get(&column.name) == Some(&NestedColumnMode::Json) {
+            target.data_type = match source {
+                ColumnTypeSource::MySQL => "longtext",
+                ColumnTypeSource::RisingWave => "character varying",
+            }
+            .to_string();
+        }
+        if let Some(mode) = self.spatial_mode(column) {
+            target.data_type = match mode {
+                SpatialColumnMode::Hex => "longtext",
+                SpatialColumnMode::Binary => "longblob",
+            }
+            .to_string();
+        }
+        vec![target]
+    }
+
+    /// 按列名映射生成目标表结构
+    pub fn target_schema(&self, schema: &TableSchema) -> TableSchema {
+        let mut target = schema.clone();
+        target.columns = schema
+            .columns
+            .iter()
+            .flat_map(|column| self.target_columns(column, schema.type_source))
+            .collect();
+        for pk in &mut target.primary_keys {
+            *pk = self.target_column_name(pk).to_string();
+        }
+        for index in &mut target.indexes {
+            index.column_name = self.target_column_name(&index.column_name).to_string();
+        }
+        // 派生分区列加入目标表和主键，值由 Sink 计算
+        if let Some((derived, column, granularity)) =
+            self.partition.as_ref().and_then(StarRocksPartition::derivation)
+        {
+            target.columns.push(Column {
+                name: derived.to_string(),
+                data_type: granularity.derived_type().to_string(),
+                is_nullable: false,
+                default_value: None,
+                comment: Some(format!("date_trunc('{}', {})", granularity.as_str(), column)),
+                character_maximum_length: None,
+                numeric_precision: None,
+                numeric_scale: None,
+            });
+            target.primary_keys.push(derived.to_string());
+        }
+        target
+    }
+
+    /// 按克隆参数替换连接配置和目标库，表名保持不变
+    pub fn retarget(&self, clone: &ClonePipelineRequest) -> SyncRequest {
+        let mut request = self.clone();
+        if let Some(id) = clone.mysql_config_id {
+            request.mysql_config_id = id;
+        }
+        if let Some(id) = clone.rw_config_id {
+            request.rw_config_id = id;
+        }
+        if let Some(id) = clone.sr_config_id {
+            request.sr_config_id = id;
+        }
+        if let Some(database) = &clone.target_database {
+            request.target_database = database.clone();
+        }
+        request
+    }
+}
+
+/// 克隆管道请求：把已有任务的同步定义复制到其他目标库或集群
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ClonePipelineRequest {
+    pub mysql_config_id: Option<i64>,
+    pub rw_config_id: Option<i64>,
+    pub sr_config_id: Option<i64>,
+    pub target_database: Option<String>,
+    /// 为 true 时立即创建同步任务，否则只返回生成的 DDL 预览
+    #[serde(default)]
+    pub execute: bool,
+    /// 预览时先在该 RisingWave 连接上试运行，默认使用设置 rw.staging_config_id
+    pub staging_rw_config_id: Option<i64>,
+}
+
+/// DDL 预览的查询参数
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DdlPreviewQuery {
+    /// 先在该 RisingWave 连接上试运行，默认使用设置 rw.staging_config_id
+    pub staging_rw_config_id: Option<i64>,
+}
+
+/// 单表同步的 DDL 预览（不包含 SECRET 语句，避免泄露密码）
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TableDdlPreview {
+    pub mysql_database: String,
+    pub mysql_table: String,
+    pub target_database: String,
+    pub target_table: String,
+    /// 按执行顺序在 RisingWave 上执行的语句
+    pub risingwave: Vec<String>,
+    /// 按执行顺序在 StarRocks 上执行的语句
+    pub starrocks: Vec<String>,
+    /// 按源表大小建议的分桶数，已指定分桶数或使用自动分桶时为空
+    pub suggested_buckets: Option<u32>,
+    /// RisingWave 语句在预发连接上的试运行结果，与 risingwave 一一对应，未试运行时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub staging_validation: Option<Vec<StagingStatementResult>>,
+}
+
+/// 语句在预发 RisingWave 上的试运行状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StagingStatus {
+    Passed,
+    /// 语法或兼容性错误，在生产环境执行也会失败
+    Failed,
+    /// 通过了解析和绑定，连接 MySQL / StarRocks 时失败（预发使用假密码，属于预期）
+    ConnectorError,
+    /// 依赖的对象没有创建，未执行
+    Skipped,
+}
+
+/// 单条语句在预发 RisingWave 上的试运行结果
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct StagingStatementResult {
+    pub status: StagingStatus,
+    pub error: Option<String>,
+}
 
 /// 同步任务
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct SyncTask {
     pub id: i64,
     pub task_name: String,
@@ -97,31 +578,622 @@ pub struct SyncTask {
     pub completed_at: Option<DateTime<Utc>>,
     pub error_message: Option<String>,
     pub options: String, // JSON serialized SyncOptions
+    pub preflight_result: Option<String>, // JSON serialized CapacityCheckResult
+    pub requests: Option<String>, // JSON serialized Vec<SyncRequest>
+    /// 创建 sink 任务的请求（加密的 JSON，可能包含 Iceberg 密钥，不返回给前端）
+    #[serde(default, skip_serializing)]
+    pub sink_requests: Option<String>,
+}
+
+impl SyncTask {
+    /// 还原任务对应的同步请求
+    /// 旧任务没有记录 requests，只能按单表任务还原，旧的批量任务返回空列表
+    pub fn sync_requests(&self) -> Result<Vec<SyncRequest>, serde_json::Error> {
+        if self.is_sink_task() {
+            return Ok(vec![]);
+        }
+        if let Some(requests) = &self.requests {
+            return serde_json::from_str(requests);
+        }
+
+        if self.mysql_table.starts_with("[Batch") {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![SyncRequest {
+            mysql_config_id: self.mysql_config_id,
+            rw_config_id: self.rw_config_id,
+            sr_config_id: self.sr_config_id,
+            mysql_database: self.mysql_database.clone(),
+            mysql_table: self.mysql_table.clone(),
+            target_database: self.target_database.clone(),
+            target_table: self.target_table.clone(),
+            options: serde_json::from_str(&self.options).unwrap_or_default(),
+            row_filter: None,
+            partition: None,
+            column_mapping: BTreeMap::new(),
+            nested_columns: BTreeMap::new(),
+            spatial_columns: BTreeMap::new(),
+        }])
+    }
+
+    /// 从 RisingWave 页面创建 sink 的任务，没有对应的同步请求
+    pub fn is_sink_task(&self) -> bool {
+        self.sink_requests.is_some()
+    }
+
+    /// 是否为清理已下线管道目标对象的任务
+    pub fn is_cleanup_task(&self) -> bool {
+        self.mysql_table == super::CLEANUP_TASK_MARKER
+    }
+
+    /// 重试时判断是否为同一任务：任务名、集群和目标表相同
+    pub fn retry_key(&self) -> (String, i64, i64, String, String) {
+        (
+            self.task_name.clone(),
+            self.rw_config_id,
+            self.sr_config_id,
+            self.target_database.clone(),
+            self.target_table.clone(),
+        )
+    }
+}
+
+/// 批量同步的查询参数
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BatchSyncQuery {
+    /// 目标表名模板，例如 `ods_{table}`、`{db}_{table}`，覆盖请求中的 target_table
+    pub target_table_template: Option<String>,
+    /// 相同的请求已在等待或执行时返回已有任务，不创建新任务
+    #[serde(default)]
+    pub dedupe: bool,
+}
+
+/// 单表同步的查询参数
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SyncQuery {
+    /// 相同的请求已在等待或执行时返回已有任务，不创建新任务
+    #[serde(default)]
+    pub dedupe: bool,
+}
+
+/// 批量重试最多处理的任务数
+pub const MAX_RETRY_BATCH: usize = 100;
+
+/// 批量重试失败任务：task_ids 和 failed_within_hours 至少提供一个
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema)]
+pub struct RetryBatchRequest {
+    #[serde(default)]
+    pub task_ids: Vec<i64>,
+    /// 重试最近 N 小时内失败的任务，同一任务多次失败时只重试最近一次
+    pub failed_within_hours: Option<i64>,
+}
+
+/// 单个任务的重试结果，需要审批时只返回 approval_id
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct RetriedTask {
+    pub task_id: i64,
+    pub new_task_id: Option<i64>,
+    pub approval_id: Option<i64>,
+    pub error: Option<String>,
+}
+
+/// 批量重试结果
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct RetryBatchResult {
+    /// 原任务 id → 新任务 id
+    pub mapping: BTreeMap<i64, i64>,
+    pub results: Vec<RetriedTask>,
+}
+
+/// StarRocks 容量预检结果
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CapacityCheckResult {
+    pub mode: CapacityCheckMode,
+    /// 预估回填所需空间（字节）
+    pub required_bytes: i64,
+    /// StarRocks 当前可用空间（字节）
+    pub available_bytes: i64,
+    /// StarRocks 总容量（字节）
+    pub total_bytes: i64,
+    pub min_free_ratio: f64,
+    /// 回填后预计剩余的空闲比例
+    pub projected_free_ratio: f64,
+    pub passed: bool,
+    pub message: String,
+    pub checked_at: DateTime<Utc>,
 }
 
 /// 任务日志
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct TaskLog {
     pub id: i64,
     pub task_id: i64,
     pub log_level: String,
     pub message: String,
     pub created_at: DateTime<Utc>,
+    #[sqlx(json(nullable))]
+    pub payload: Option<TaskLogPayload>,
+}
+
+/// 任务日志默认每页条数
+pub const DEFAULT_TASK_LOG_LIMIT: i64 = 1000;
+/// 任务日志每页最多条数（含 tail）
+pub const MAX_TASK_LOG_LIMIT: i64 = 10000;
+/// 日志级别，由低到高
+pub const TASK_LOG_LEVELS: [&str; 4] = ["debug", "info", "warn", "error"];
+
+/// 任务日志查询
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskLogQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// 上一页返回的 next_cursor，传入时返回该条之后的日志并忽略 offset
+    pub cursor: Option<String>,
+    /// 只返回最后 N 条（按时间正序），传入时忽略 limit / offset / cursor
+    pub tail: Option<i64>,
+    /// 最低日志级别：debug / info / warn / error
+    pub level: Option<String>,
+}
+
+impl TaskLogQuery {
+    /// 不低于 level 的日志级别，未指定时为空（不过滤），无法识别时返回 None
+    pub fn levels(&self) -> Option<Vec<&'static str>> {
+        match self.level.as_deref().map(str::trim).filter(|l| !l.is_empty()) {
+            None => Some(Vec::new()),
+            Some(level) => TASK_LOG_LEVELS
+                .iter()
+                .position(|l| l.eq_ignore_ascii_case(level))
+                .map(|i| TASK_LOG_LEVELS[i..].to_vec()),
+        }
+    }
+}
+
+/// 任务日志游标（按 created_at、id 正序），格式为 `<created_at 微秒>_<id>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskLogCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: i64,
+}
+
+impl TaskLogCursor {
+    pub fn from_log(log: &TaskLog) -> Self {
+        Self {
+            created_at: log.created_at,
+            id: log.id,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.created_at.timestamp_micros(), self.id)
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let (micros, id) = cursor.split_once('_')?;
+        Some(Self {
+            created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+/// 分页的任务日志
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct TaskLogPage {
+    pub logs: Vec<TaskLog>,
+    /// 符合级别条件的日志总数
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    /// 获取之后日志的游标：分页时没有更多数据则为空，tail 模式下为最后一条，用于继续追踪新日志
+    pub next_cursor: Option<String>,
+}
+
+/// 任务日志的结构化内容，用于按步骤渲染时间线
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TaskLogPayload {
+    /// 步骤名称，例如 "create table"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<String>,
+    /// 执行的 DDL（只在 debug 级别记录）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ddl: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// 涉及的对象，例如 `shop.orders`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<String>,
+}
+
+impl TaskLogPayload {
+    pub fn step(step: &str) -> Self {
+        Self {
+            step: Some(step.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+/// 同步任务的执行步骤
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStep {
+    /// 连接 RisingWave / StarRocks 并做容量预检（每个任务一次）
+    Connect,
+    /// 获取 MySQL 表结构
+    FetchSchema,
+    /// 创建 RisingWave schema、secret 和 CDC Source（同批次共享，已创建时跳过）
+    SetupSource,
+    /// 创建 RisingWave 表
+    CreateRwTable,
+    /// 创建目标数据库，按选项删除或清空目标表
+    PrepareTarget,
+    /// 创建目标表并同步新增列
+    CreateTargetTable,
+    /// 创建 RisingWave Sink
+    CreateSink,
+}
+
+impl TryFrom<String> for SyncStep {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.as_str() {
+            "connect" => Ok(SyncStep::Connect),
+            "fetch_schema" => Ok(SyncStep::FetchSchema),
+            "setup_source" => Ok(SyncStep::SetupSource),
+            "create_rw_table" => Ok(SyncStep::CreateRwTable),
+            "prepare_target" => Ok(SyncStep::PrepareTarget),
+            "create_target_table" => Ok(SyncStep::CreateTargetTable),
+            "create_sink" => Ok(SyncStep::CreateSink),
+            _ => Err(format!("Unknown sync step: {}", s)),
+        }
+    }
+}
+
+impl SyncStep {
+    /// 每张表依次执行的步骤
+    pub const TABLE_STEPS: [SyncStep; 6] = [
+        SyncStep::FetchSchema,
+        SyncStep::SetupSource,
+        SyncStep::CreateRwTable,
+        SyncStep::PrepareTarget,
+        SyncStep::CreateTargetTable,
+        SyncStep::CreateSink,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncStep::Connect => "connect",
+            SyncStep::FetchSchema => "fetch_schema",
+            SyncStep::SetupSource => "setup_source",
+            SyncStep::CreateRwTable => "create_rw_table",
+            SyncStep::PrepareTarget => "prepare_target",
+            SyncStep::CreateTargetTable => "create_target_table",
+            SyncStep::CreateSink => "create_sink",
+        }
+    }
+}
+
+/// 步骤状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Pending,
+    Running,
+    Completed,
+    /// 无需执行，例如共享的 Source 已由同批次前面的表创建
+    Skipped,
+    Failed,
+}
+
+impl TryFrom<String> for StepStatus {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.as_str() {
+            "pending" => Ok(StepStatus::Pending),
+            "running" => Ok(StepStatus::Running),
+            "completed" => Ok(StepStatus::Completed),
+            "skipped" => Ok(StepStatus::Skipped),
+            "failed" => Ok(StepStatus::Failed),
+            _ => Err(format!("Unknown step status: {}", s)),
+        }
+    }
+}
+
+impl StepStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StepStatus::Pending => "pending",
+            StepStatus::Running => "running",
+            StepStatus::Completed => "completed",
+            StepStatus::Skipped => "skipped",
+            StepStatus::Failed => "failed",
+        }
+    }
+
+    /// 已结束（不再计入剩余进度）
+    pub fn is_done(&self) -> bool {
+        matches!(self, StepStatus::Completed | StepStatus::Skipped)
+    }
+}
+
+/// 任务步骤记录
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct TaskStep {
+    pub id: i64,
+    pub task_id: i64,
+    pub step_index: i32,
+    #[sqlx(try_from = "String")]
+    pub step: SyncStep,
+    /// 步骤所属的表（源表 db.table），任务级步骤为空
+    pub table_name: Option<String>,
+    #[sqlx(try_from = "String")]
+    pub status: StepStatus,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub duration_ms: Option<i64>,
+    pub error_message: Option<String>,
+}
+
+/// 待创建的任务步骤
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewTaskStep {
+    pub step_index: i32,
+    pub step: SyncStep,
+    pub table_name: Option<String>,
+}
+
+impl NewTaskStep {
+    /// 批量同步的步骤计划：先连接一次，再为每张表执行 TABLE_STEPS
+    pub fn plan(requests: &[SyncRequest]) -> Vec<NewTaskStep> {
+        let mut steps = vec![NewTaskStep {
+            step_index: 0,
+            step: SyncStep::Connect,
+            table_name: None,
+        }];
+        for request in requests {
+            let table = format!("{}.{}", request.mysql_database, request.mysql_table);
+            for step in SyncStep::TABLE_STEPS {
+                steps.push(NewTaskStep {
+                    step_index: steps.len() as i32,
+                    step,
+                    table_name: Some(table.clone()),
+                });
+            }
+        }
+        steps
+    }
+
+    /// 第 table_index 张表的 step 在计划中的序号
+    pub fn index_of(table_index: usize, step: SyncStep) -> i32 {
+        match SyncStep::TABLE_STEPS.iter().position(|s| *s == step) {
+            Some(position) => (1 + table_index * SyncStep::TABLE_STEPS.len() + position) as i32,
+            None => 0,
+        }
+    }
+}
+
+/// 同步进度，由任务步骤计算
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct SyncProgress {
+    pub task_id: i64,
+    pub status: TaskStatus,
+    /// 当前（或失败的）步骤描述，没有步骤记录时为空
+    pub current_step: Option<String>,
+    /// 已完成（含跳过）的步骤数
+    pub current_step_index: usize,
+    pub total_steps: usize,
+    /// 完成百分比（0~100）
+    pub percent: u32,
+    pub steps: Vec<TaskStep>,
+    /// 已处理完的表的结果（创建/跳过/更新）
+    pub tables: Vec<TableSyncOutcome>,
+}
+
+impl SyncProgress {
+    pub fn new(task: &SyncTask, steps: Vec<TaskStep>) -> Self {
+        let total_steps = steps.len();
+        let done = steps.iter().filter(|s| s.status.is_done()).count();
+        let percent = if task.status == TaskStatus::Completed {
+            100
+        } else {
+            (done * 100).checked_div(total_steps).unwrap_or(0) as u32
+        };
+        let current_step = steps
+            .iter()
+            .find(|s| !s.status.is_done())
+            .map(|s| match &s.table_name {
+                Some(table) => format!("{} ({})", s.step.as_str(), table),
+                None => s.step.as_str().to_string(),
+            });
+
+        Self {
+            task_id: task.id,
+            status: task.status.clone(),
+            current_step,
+            current_step_index: done,
+            total_steps,
+            percent,
+            steps,
+            tables: Vec::new(),
+        }
+    }
+}
+
+/// 单张表的同步结果
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TableSyncAction {
+    /// 对象均已存在且定义一致，未执行 DDL
+    Skipped,
+    /// 创建了缺失的对象
+    Created,
+    /// 重建或修改了已存在的对象
+    Updated,
+}
+
+impl TableSyncAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TableSyncAction::Skipped => "skipped",
+            TableSyncAction::Created => "created",
+            TableSyncAction::Updated => "updated",
+        }
+    }
+}
+
+/// 任务中每张表的处理结果
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TableSyncOutcome {
+    /// 源表 db.table
+    pub source_table: String,
+    /// 目标表 db.table
+    pub target_table: String,
+    pub action: TableSyncAction,
+}
+
+/// 任务每日统计（按日期、状态和连接聚合）
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct TaskDailyStat {
+    pub stat_date: NaiveDate,
+    #[sqlx(try_from = "String")]
+    pub status: TaskStatus,
+    pub mysql_config_id: i64,
+    pub rw_config_id: i64,
+    pub sr_config_id: i64,
+    pub task_count: i64,
+    pub total_duration_ms: i64,
+    pub max_duration_ms: i64,
+}
+
+/// 每日统计查询参数
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskStatsQuery {
+    /// 最近多少天（默认 30）
+    pub days: Option<i64>,
+}
+
+/// 待批量写入的任务日志
+#[derive(Debug, Clone)]
+pub struct NewTaskLog {
+    pub log_level: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+    pub payload: Option<TaskLogPayload>,
 }
 
 
 
-#[derive(Deserialize)]
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct HistoryQuery {
     pub status: Option<String>,
+    pub mysql_database: Option<String>,
+    pub mysql_table: Option<String>,
+    pub target_table: Option<String>,
+    /// started_at 下限（包含），RFC 3339 格式
+    pub started_after: Option<DateTime<Utc>>,
+    /// started_at 上限（不包含），RFC 3339 格式
+    pub started_before: Option<DateTime<Utc>>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    /// 上一页返回的 next_cursor，传入时使用游标分页并忽略 offset
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub count: CountMode,
+}
+
+/// 任务总数的统计方式
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CountMode {
+    #[default]
+    Exact,
+    /// 无过滤条件时使用表统计信息，有过滤条件时最多数到上限
+    Approximate,
+}
+
+/// 任务历史游标（按 started_at、id 倒序），格式为 `<started_at 微秒>_<id>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskCursor {
+    pub started_at: DateTime<Utc>,
+    pub id: i64,
+}
+
+impl TaskCursor {
+    pub fn from_task(task: &SyncTask) -> Self {
+        Self {
+            started_at: task.started_at,
+            id: task.id,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.started_at.timestamp_micros(), self.id)
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let (micros, id) = cursor.split_once('_')?;
+        Some(Self {
+            started_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+impl HistoryQuery {
+    pub fn filter(&self) -> TaskFilter {
+        TaskFilter {
+            status: self
+                .status
+                .clone()
+                .and_then(|s| TaskStatus::try_from(s).ok()),
+            mysql_database: self.mysql_database.clone().filter(|s| !s.is_empty()),
+            mysql_table: self.mysql_table.clone().filter(|s| !s.is_empty()),
+            target_table: self.target_table.clone().filter(|s| !s.is_empty()),
+            started_after: self.started_after,
+            started_before: self.started_before,
+        }
+    }
+}
+
+/// 任务历史过滤条件
+/// 表名条件同时匹配批量任务 requests 中的表
+#[derive(Debug, Clone, Default)]
+pub struct TaskFilter {
+    pub status: Option<TaskStatus>,
+    pub mysql_database: Option<String>,
+    pub mysql_table: Option<String>,
+    pub target_table: Option<String>,
+    pub started_after: Option<DateTime<Utc>>,
+    pub started_before: Option<DateTime<Utc>>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 pub struct PaginatedTasksResponse {
     pub tasks: Vec<SyncTask>,
     pub total: i64,
+    /// total 是否为估算值
+    pub total_estimated: bool,
     pub limit: i64,
     pub offset: i64,
+    /// 下一页游标，没有更多数据时为空
+    pub next_cursor: Option<String>,
 }
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 单表数据一致性校验结果
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ValidationResult {
+    pub id: i64,
+    pub task_id: i64,
+    pub mysql_database: String,
+    pub mysql_table: String,
+    pub target_database: String,
+    pub target_table: String,
+    pub source_count: Option<i64>,
+    pub target_count: Option<i64>,
+    pub source_checksum: Option<String>,
+    pub target_checksum: Option<String>,
+    pub matched: bool,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 校验请求参数
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ValidateQuery {
+    /// 是否额外比较数值列的求和校验值
+    pub checksum: Option<bool>,
+}
@@ -0,0 +1,204 @@
+use crate::db::AlertRoutingRepository;
+use crate::models::{AlertRoute, AlertSilence, LabelMatcher};
+use crate::utils::error::Result;
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+use std::collections::HashMap;
+
+/// 一条告警的路由结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlertRouting {
+    /// 被静默窗口抑制，值为静默窗口 ID
+    Silenced(i64),
+    /// 发送到匹配规则指定的渠道
+    Channels(Vec<i64>),
+    /// 没有匹配的规则，发送到所有订阅告警的渠道
+    Default,
+}
+
+/// Alertmanager 告警的路由和静默
+pub struct AlertRouter {
+    routes: Vec<AlertRoute>,
+    silences: Vec<AlertSilence>,
+}
+
+impl AlertRouter {
+    /// 只保留启用的规则，按 priority、id 排序
+    pub fn new(mut routes: Vec<AlertRoute>, silences: Vec<AlertSilence>) -> Self {
+        routes.retain(|r| r.enabled);
+        routes.sort_by_key(|r| (r.priority, r.id));
+        Self { routes, silences }
+    }
+
+    /// 加载所有规则和未过期的静默窗口
+    pub async fn load(app_db: &MySqlPool) -> Result<Self> {
+        let repo = AlertRoutingRepository::new(app_db);
+        Ok(Self::new(
+            repo.find_routes().await?,
+            repo.find_unexpired_silences().await?,
+        ))
+    }
+
+    pub fn route(&self, labels: &HashMap<String, String>, now: DateTime<Utc>) -> AlertRouting {
+        if let Some(silence) = self
+            .silences
+            .iter()
+            .find(|s| s.is_active(now) && LabelMatcher::match_all(&s.matchers, labels))
+        {
+            return AlertRouting::Silenced(silence.id);
+        }
+
+        let mut channel_ids: Vec<i64> = Vec::new();
+        let mut matched = false;
+        for route in &self.routes {
+            if !LabelMatcher::match_all(&route.matchers, labels) {
+                continue;
+            }
+            matched = true;
+            for id in &route.channel_ids {
+                if !channel_ids.contains(id) {
+                    channel_ids.push(*id);
+                }
+            }
+            if !route.continue_matching {
+                break;
+            }
+        }
+
+        if matched {
+            AlertRouting::Channels(channel_ids)
+        } else {
+            AlertRouting::Default
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MatchOp;
+
+    fn matcher(label: &str, op: MatchOp, value: &str) -> LabelMatcher {
+        LabelMatcher {
+            label: label.to_string(),
+            op,
+            value: value.to_string(),
+        }
+    }
+
+    fn route(
+        id: i64,
+        priority: i32,
+        matchers: Vec<LabelMatcher>,
+        channel_ids: Vec<i64>,
+    ) -> AlertRoute {
+        AlertRoute {
+            id,
+            name: format!("route-{}", id),
+            matchers,
+            channel_ids,
+            priority,
+            continue_matching: false,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn labels(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_route_alerts() {
+        let now = Utc::now();
+        let mut compute = route(
+            2,
+            20,
+            vec![matcher("component", MatchOp::Equal, "compute")],
+            vec![20],
+        );
+        compute.continue_matching = true;
+        let routes = vec![
+            route(
+                1,
+                10,
+                vec![matcher("component", MatchOp::Equal, "sink")],
+                vec![10],
+            ),
+            compute,
+            route(
+                3,
+                30,
+                vec![matcher("severity", MatchOp::Regex, "critical|page")],
+                vec![30, 20],
+            ),
+            AlertRoute {
+                enabled: false,
+                ..route(4, 0, vec![], vec![40])
+            },
+        ];
+        let silences = vec![
+            AlertSilence {
+                id: 7,
+                matchers: vec![matcher("alertname", MatchOp::Equal, "SinkLag")],
+                starts_at: now - chrono::Duration::hours(1),
+                ends_at: now + chrono::Duration::hours(1),
+                comment: None,
+                created_by: None,
+                created_at: now,
+            },
+            AlertSilence {
+                id: 8,
+                matchers: vec![matcher("alertname", MatchOp::NotEqual, "")],
+                starts_at: now + chrono::Duration::hours(1),
+                ends_at: now + chrono::Duration::hours(2),
+                comment: None,
+                created_by: None,
+                created_at: now,
+            },
+        ];
+        let router = AlertRouter::new(routes, silences);
+
+        assert_eq!(
+            router.route(
+                &labels(&[("alertname", "SinkLag"), ("component", "sink")]),
+                now
+            ),
+            AlertRouting::Silenced(7)
+        );
+        assert_eq!(
+            router.route(
+                &labels(&[
+                    ("alertname", "SinkError"),
+                    ("component", "sink"),
+                    ("severity", "critical")
+                ]),
+                now
+            ),
+            AlertRouting::Channels(vec![10])
+        );
+        // continue_matching 的规则继续匹配后面的规则，渠道去重
+        assert_eq!(
+            router.route(
+                &labels(&[("component", "compute"), ("severity", "critical")]),
+                now
+            ),
+            AlertRouting::Channels(vec![20, 30])
+        );
+        assert_eq!(
+            router.route(
+                &labels(&[("component", "compute"), ("severity", "critical-ish")]),
+                now
+            ),
+            AlertRouting::Channels(vec![20])
+        );
+        assert_eq!(
+            router.route(&labels(&[("component", "meta")]), now),
+            AlertRouting::Default
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use crate::db::{ApprovalRepository, TaskRepository};
+use crate::models::{ApprovalRequest, ApprovalStatus, DEFAULT_WORKSPACE, SyncRequest};
+use crate::services::SyncEngine;
+use crate::utils::error::{AppError, Result};
+use sqlx::MySqlPool;
+
+/// 同步审批策略
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalPolicy {
+    /// 重建 Source / 重建或清空目标表的同步需要审批
+    pub destructive: bool,
+    /// 这些工作区内的所有同步都需要审批（例如 prod）
+    pub workspaces: Vec<String>,
+}
+
+impl ApprovalPolicy {
+    /// 从环境变量 APPROVAL_REQUIRED_FOR_DESTRUCTIVE 和 APPROVAL_REQUIRED_WORKSPACES 读取
+    pub fn from_env() -> Self {
+        let destructive = std::env::var("APPROVAL_REQUIRED_FOR_DESTRUCTIVE")
+            .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+        let workspaces = std::env::var("APPROVAL_REQUIRED_WORKSPACES")
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|w| !w.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            destructive,
+            workspaces,
+        }
+    }
+
+    /// 同步请求需要审批的原因，不需要审批时返回 None
+    pub fn reason(&self, requests: &[SyncRequest]) -> Option<String> {
+        let mut reasons = Vec::new();
+
+        if self.destructive {
+            for request in requests {
+                let options = &request.options;
+                let mut actions = Vec::new();
+                if options.recreate_rw_source {
+                    actions.push("recreate RisingWave source");
+                }
+                if options.recreate_sr_table {
+                    actions.push("recreate target table");
+                }
+                if options.truncate_sr_table {
+                    actions.push("truncate target table");
+                }
+                if !actions.is_empty() {
+                    reasons.push(format!(
+                        "{} for {}.{}",
+                        actions.join(", "),
+                        request.target_database,
+                        request.target_table
+                    ));
+                }
+            }
+        }
+
+        for request in requests {
+            let workspace = request
+                .options
+                .workspace
+                .as_deref()
+                .unwrap_or(DEFAULT_WORKSPACE);
+            if self.workspaces.iter().any(|w| w == workspace) {
+                let reason = format!("workspace {} requires approval", workspace);
+                if !reasons.contains(&reason) {
+                    reasons.push(reason);
+                }
+            }
+        }
+
+        if reasons.is_empty() {
+            None
+        } else {
+            Some(reasons.join("; "))
+        }
+    }
+}
+
+/// 同步审批服务
+/// 需要审批的同步先生成审批单，由申请人以外的用户批准后才提交给同步引擎
+pub struct ApprovalService;
+
+impl ApprovalService {
+    /// 为同步请求创建审批单
+    pub async fn request(
+        pool: &MySqlPool,
+        requests: &[SyncRequest],
+        reason: &str,
+        requested_by: &str,
+    ) -> Result<ApprovalRequest> {
+        let repo = ApprovalRepository::new(pool);
+        let id = repo
+            .create(reason, &serde_json::to_string(requests)?, requested_by)
+            .await?;
+        tracing::info!(
+            "Approval request {} created by {}: {}",
+            id,
+            requested_by,
+            reason
+        );
+        repo.find_by_id(id).await
+    }
+
+    pub async fn list(
+        pool: &MySqlPool,
+        status: Option<ApprovalStatus>,
+    ) -> Result<Vec<ApprovalRequest>> {
+        ApprovalRepository::new(pool).list(status).await
+    }
+
+    /// 批准审批单并提交同步任务
+    pub async fn approve(
+        pool: &MySqlPool,
+        id: i64,
+        approved_by: &str,
+        comment: Option<&str>,
+    ) -> Result<ApprovalRequest> {
+        let repo = ApprovalRepository::new(pool);
+        let approval = repo.find_by_id(id).await?;
+        if approval.requested_by == approved_by {
+            return Err(AppError::Validation(
+                "An approval request must be approved by a different user".to_string(),
+            ));
+        }
+        let requests = approval.sync_requests()?;
+
+        if !repo
+            .decide(id, ApprovalStatus::Approved, approved_by, comment)
+            .await?
+        {
+            return Err(Self::already_decided(id));
+        }
+
+        let engine = SyncEngine::new(pool.clone());
+        match engine.sync_multiple_tables(requests).await {
+            Ok(task_id) => {
+                repo.set_task_id(id, task_id).await?;
+                TaskRepository::new(pool)
+                    .set_created_by(task_id, &approval.requested_by)
+                    .await?;
+                tracing::info!(
+                    "Approval request {} approved by {}, submitted task {}",
+                    id,
+                    approved_by,
+                    task_id
+                );
+            }
+            Err(e) => {
+                repo.reopen(id).await?;
+                return Err(e);
+            }
+        }
+
+        repo.find_by_id(id).await
+    }
+
+    /// 拒绝审批单
+    pub async fn reject(
+        pool: &MySqlPool,
+        id: i64,
+        rejected_by: &str,
+        comment: Option<&str>,
+    ) -> Result<ApprovalRequest> {
+        let repo = ApprovalRepository::new(pool);
+        if !repo
+            .decide(id, ApprovalStatus::Rejected, rejected_by, comment)
+            .await?
+        {
+            repo.find_by_id(id).await?;
+            return Err(Self::already_decided(id));
+        }
+        tracing::info!("Approval request {} rejected by {}", id, rejected_by);
+
+        repo.find_by_id(id).await
+    }
+
+    fn already_decided(id: i64) -> AppError {
+        AppError::Conflict(format!("Approval request {} has already been decided", id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SyncOptions;
+
+    fn request(options: SyncOptions) -> SyncRequest {
+        SyncRequest {
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: "orders".to_string(),
+            target_database: "dw".to_string(),
+            target_table: "orders".to_string(),
+            options,
+            row_filter: None,
+            partition: None,
+            column_mapping: Default::default(),
+            nested_columns: Default::default(),
+            spatial_columns: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_approval_reason() {
+        let policy = ApprovalPolicy {
+            destructive: true,
+            workspaces: vec!["prod".to_string()],
+        };
+
+        assert_eq!(policy.reason(&[request(SyncOptions::default())]), None);
+
+        let destructive = request(SyncOptions {
+            recreate_sr_table: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            policy.reason(std::slice::from_ref(&destructive)).as_deref(),
+            Some("recreate target table for dw.orders")
+        );
+        assert_eq!(ApprovalPolicy::default().reason(&[destructive]), None);
+
+        let prod = request(SyncOptions {
+            workspace: Some("prod".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            policy.reason(&[prod.clone(), prod]).as_deref(),
+            Some("workspace prod requires approval")
+        );
+    }
+}
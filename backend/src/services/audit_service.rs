@@ -0,0 +1,206 @@
+use crate::db::AuditRepository;
+use crate::models::NewAuditLog;
+use crate::services::TaskLogger;
+use crate::utils::redact;
+use sqlx::MySqlPool;
+
+/// DROP 语句中可以出现的对象类型，多词类型在前
+const DROP_OBJECT_KINDS: &[&str] = &[
+    "MATERIALIZED VIEW",
+    "TABLE",
+    "SINK",
+    "SOURCE",
+    "VIEW",
+    "INDEX",
+    "SECRET",
+    "SCHEMA",
+    "DATABASE",
+];
+
+/// 审计日志：记录破坏性 DDL、SECRET 更新和连接配置变更
+/// 写入失败只记录警告，不影响操作本身
+pub struct AuditService;
+
+impl AuditService {
+    /// 需要审计的语句返回 (action, 目标对象)：DROP、TRUNCATE、CREATE SINK、ALTER SINK、ALTER SECRET 和 ALTER TABLE ... RENAME
+    pub fn classify_ddl(sql: &str) -> Option<(String, String)> {
+        let tokens: Vec<&str> = sql.split_whitespace().collect();
+        let upper: Vec<String> = tokens.iter().map(|t| t.to_uppercase()).collect();
+        let keyword = |i: usize| upper.get(i).map(String::as_str).unwrap_or_default();
+        let name_at = |i: usize| {
+            tokens
+                .get(i)
+                .map(|t| t.trim_end_matches([';', ',']).to_string())
+                .filter(|t| !t.is_empty())
+        };
+
+        match keyword(0) {
+            "DROP" => {
+                let kind = DROP_OBJECT_KINDS.iter().find(|kind| {
+                    kind.split(' ')
+                        .enumerate()
+                        .all(|(i, word)| keyword(1 + i) == word)
+                })?;
+                let mut i = 1 + kind.split(' ').count();
+                if keyword(i) == "IF" && keyword(i + 1) == "EXISTS" {
+                    i += 2;
+                }
+                let action = format!("drop_{}", kind.to_lowercase().replace(' ', "_"));
+                Some((action, name_at(i)?))
+            }
+            "TRUNCATE" => {
+                let i = if keyword(1) == "TABLE" { 2 } else { 1 };
+                Some(("truncate_table".to_string(), name_at(i)?))
+            }
+            "CREATE" if keyword(1) == "SINK" => {
+                let i = if keyword(2) == "IF" && keyword(3) == "NOT" && keyword(4) == "EXISTS" {
+                    5
+                } else {
+                    2
+                };
+                Some(("create_sink".to_string(), name_at(i)?))
+            }
+            "ALTER" if keyword(1) == "SINK" => Some(("alter_sink".to_string(), name_at(2)?)),
+            "ALTER" if keyword(1) == "SECRET" => Some(("alter_secret".to_string(), name_at(2)?)),
+            "ALTER" if keyword(1) == "TABLE" && keyword(3) == "RENAME" => {
+                Some(("rename_table".to_string(), name_at(2)?))
+            }
+            _ => None,
+        }
+    }
+
+    /// 记录执行的语句，不需要审计的语句直接忽略
+    pub async fn record_ddl(
+        app_db: &MySqlPool,
+        actor: Option<&str>,
+        task_id: Option<i64>,
+        engine: &str,
+        sql: &str,
+        error: Option<&str>,
+    ) {
+        let Some((action, target)) = Self::classify_ddl(sql) else {
+            return;
+        };
+        Self::insert(
+            app_db,
+            NewAuditLog {
+                actor: actor.map(str::to_string),
+                action,
+                target,
+                engine: Some(engine.to_string()),
+                sql_text: Some(redact::scrub_sql(sql).into_owned()),
+                task_id,
+                error_message: error.map(str::to_string),
+            },
+        )
+        .await;
+    }
+
+    /// 记录任务中执行的语句，查询时操作人取任务的提交人
+    pub async fn record_task_ddl(
+        logger: &TaskLogger<'_>,
+        engine: &str,
+        sql: &str,
+        error: Option<&str>,
+    ) {
+        Self::record_ddl(
+            logger.app_db(),
+            None,
+            Some(logger.task_id()),
+            engine,
+            sql,
+            error,
+        )
+        .await;
+    }
+
+    /// 记录连接配置变更，action 例如 `connection_update`
+    pub async fn record_connection(
+        app_db: &MySqlPool,
+        actor: Option<&str>,
+        action: &str,
+        config_id: i64,
+        name: &str,
+    ) {
+        Self::insert(
+            app_db,
+            NewAuditLog {
+                actor: actor.map(str::to_string),
+                action: action.to_string(),
+                target: format!("connection {} (#{})", name, config_id),
+                ..Default::default()
+            },
+        )
+        .await;
+    }
+
+    async fn insert(app_db: &MySqlPool, log: NewAuditLog) {
+        if let Err(e) = AuditRepository::new(app_db).insert(&log).await {
+            tracing::warn!(
+                "Failed to write audit log {} {}: {}",
+                log.action,
+                log.target,
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(sql: &str) -> Option<(String, String)> {
+        AuditService::classify_ddl(sql)
+    }
+
+    #[test]
+    fn test_classify_ddl() {
+        assert_eq!(
+            classify("DROP SINK IF EXISTS \"ods\".orders_to_sr_sink;"),
+            Some((
+                "drop_sink".to_string(),
+                "\"ods\".orders_to_sr_sink".to_string()
+            ))
+        );
+        assert_eq!(
+            classify("drop materialized view \"public\".mv_daily cascade"),
+            Some((
+                "drop_materialized_view".to_string(),
+                "\"public\".mv_daily".to_string()
+            ))
+        );
+        assert_eq!(
+            classify("TRUNCATE TABLE `ods`.`orders`;"),
+            Some(("truncate_table".to_string(), "`ods`.`orders`".to_string()))
+        );
+        assert_eq!(
+            classify("CREATE SINK IF NOT EXISTS \"ods\".t_to_sr_sink\nFROM \"ods\".t\nWITH (...)"),
+            Some((
+                "create_sink".to_string(),
+                "\"ods\".t_to_sr_sink".to_string()
+            ))
+        );
+        assert_eq!(
+            classify("ALTER TABLE `ods`.`orders` RENAME `orders__trash_20240101000000`;"),
+            Some(("rename_table".to_string(), "`ods`.`orders`".to_string()))
+        );
+
+        assert_eq!(
+            classify("ALTER SECRET \"ods\".mysql_pwd WITH ( backend = 'meta' ) AS 'pw';"),
+            Some(("alter_secret".to_string(), "\"ods\".mysql_pwd".to_string()))
+        );
+        assert_eq!(
+            classify("ALTER SINK \"ods\".orders_to_sr_sink SET SINK_RATE_LIMIT TO 0;"),
+            Some((
+                "alter_sink".to_string(),
+                "\"ods\".orders_to_sr_sink".to_string()
+            ))
+        );
+
+        assert_eq!(classify("CREATE TABLE IF NOT EXISTS t (id INT)"), None);
+        assert_eq!(classify("ALTER TABLE t ADD COLUMN c INT"), None);
+        assert_eq!(classify("SELECT 1"), None);
+        assert_eq!(classify("DROP"), None);
+    }
+}
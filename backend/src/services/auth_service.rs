@@ -0,0 +1,335 @@
+use crate::db::{ApiTokenRepository, UserRepository};
+use crate::models::{AuthUser, Claims, CreatedApiToken, LoginResponse, Role};
+use crate::utils::error::{AppError, Result};
+use argon2::Argon2;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use once_cell::sync::Lazy;
+use rand::distributions::{Alphanumeric, DistString};
+use sha2::{Digest, Sha256};
+use sqlx::MySqlPool;
+
+/// API Token 的固定前缀，用来和 JWT 区分
+pub const API_TOKEN_PREFIX: &str = "rwcs_";
+
+/// 密码最小长度
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+/// 密码至少包含的字符类别数（小写字母、大写字母、数字、其他字符）
+pub const MIN_PASSWORD_CLASSES: usize = 3;
+
+/// 满足长度和字符类别要求但仍然常见的密码，不区分大小写
+const COMMON_PASSWORDS: &[&str] = &[
+    "password1", "password1!", "password123", "passw0rd", "p@ssw0rd", "p@ssword1", "admin123",
+    "admin@123", "admin1234", "administrator1", "welcome1", "welcome123", "welcome@123",
+    "changeme1", "changeme123", "qwerty123", "qwerty@123", "qwer1234", "abc12345", "abcd1234",
+    "letmein1", "iloveyou1", "root1234", "test1234", "zaq12wsx", "1qaz2wsx", "1q2w3e4r",
+];
+
+/// JWT 签名密钥，未配置 JWT_SECRET 时每次启动随机生成（重启后需要重新登录）
+static JWT_SECRET: Lazy<Vec<u8>> = Lazy::new(|| match std::env::var("JWT_SECRET") {
+    Ok(secret) if !secret.trim().is_empty() => secret.into_bytes(),
+    _ => {
+        tracing::warn!("JWT_SECRET is not set, using a random key; sessions end on restart");
+        Alphanumeric
+            .sample_string(&mut rand::thread_rng(), 64)
+            .into_bytes()
+    }
+});
+
+/// 认证服务：用户密码、登录会话（JWT）和 API Token
+pub struct AuthService;
+
+impl AuthService {
+    /// 是否启用认证，AUTH_ENABLED=false 时关闭（仅用于本地开发）
+    pub fn is_enabled() -> bool {
+        std::env::var("AUTH_ENABLED")
+            .map(|v| !matches!(v.trim().to_lowercase().as_str(), "0" | "false" | "no"))
+            .unwrap_or(true)
+    }
+
+    /// 会话有效期（秒），通过 JWT_TTL_SECS 配置，默认 12 小时
+    fn session_ttl() -> Duration {
+        let secs = std::env::var("JWT_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse::<i64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(12 * 3600);
+        Duration::seconds(secs)
+    }
+
+    pub fn hash_password(password: &str) -> Result<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| AppError::Encryption(format!("Failed to hash password: {}", e)))
+    }
+
+    pub fn verify_password(password: &str, hash: &str) -> bool {
+        PasswordHash::new(hash)
+            .map(|parsed| {
+                Argon2::default()
+                    .verify_password(password.as_bytes(), &parsed)
+                    .is_ok()
+            })
+            .unwrap_or(false)
+    }
+
+    /// 密码至少 8 位，包含小写字母、大写字母、数字、其他字符中的至少 3 类，且不是常见密码
+    pub fn validate_password(password: &str) -> Result<()> {
+        if password.chars().count() < MIN_PASSWORD_LEN {
+            return Err(AppError::Validation(format!(
+                "Password must be at least {} characters",
+                MIN_PASSWORD_LEN
+            )));
+        }
+        let classes = [
+            password.chars().any(|c| c.is_lowercase()),
+            password.chars().any(|c| c.is_uppercase()),
+            password.chars().any(|c| c.is_ascii_digit()),
+            password.chars().any(|c| !c.is_alphanumeric()),
+        ];
+        if classes.iter().filter(|&&present| present).count() < MIN_PASSWORD_CLASSES {
+            return Err(AppError::Validation(format!(
+                "Password must contain at least {} of: lowercase letters, uppercase letters, digits, other characters",
+                MIN_PASSWORD_CLASSES
+            )));
+        }
+        if COMMON_PASSWORDS.contains(&password.to_lowercase().as_str()) {
+            return Err(AppError::Validation(
+                "Password is too common, choose another one".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 签发会话 JWT，返回 token 和过期时间
+    pub fn issue_jwt(user_id: i64, username: &str) -> Result<(String, chrono::DateTime<Utc>)> {
+        let now = Utc::now();
+        let expires_at = now + Self::session_ttl();
+        let claims = Claims {
+            sub: user_id.to_string(),
+            name: username.to_string(),
+            iat: now.timestamp(),
+            exp: expires_at.timestamp(),
+        };
+        let token = jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(&JWT_SECRET),
+        )
+        .map_err(|e| AppError::Encryption(format!("Failed to sign token: {}", e)))?;
+
+        Ok((token, expires_at))
+    }
+
+    pub fn decode_jwt(token: &str) -> Result<Claims> {
+        jsonwebtoken::decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(&JWT_SECRET),
+            &Validation::new(Algorithm::HS256),
+        )
+        .map(|data| data.claims)
+        .map_err(|e| AppError::Unauthorized(format!("Invalid token: {}", e)))
+    }
+
+    /// API Token 只保存 SHA-256 哈希
+    pub fn hash_api_token(token: &str) -> String {
+        Sha256::digest(token.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// 用户名密码登录
+    pub async fn login(pool: &MySqlPool, username: &str, password: &str) -> Result<LoginResponse> {
+        let repo = UserRepository::new(pool);
+        let user = repo
+            .find_by_username(username.trim())
+            .await?
+            .filter(|u| u.enabled && Self::verify_password(password, &u.password_hash))
+            .ok_or_else(|| AppError::Unauthorized("Invalid username or password".to_string()))?;
+
+        repo.touch_login(user.id).await?;
+        let (token, expires_at) = Self::issue_jwt(user.id, &user.username)?;
+
+        Ok(LoginResponse {
+            token,
+            expires_at,
+            user,
+        })
+    }
+
+    /// 校验 Bearer token（会话 JWT 或 API Token），返回对应的用户
+    pub async fn authenticate(pool: &MySqlPool, token: &str) -> Result<AuthUser> {
+        let users = UserRepository::new(pool);
+        let (user_id, token_id) = if token.starts_with(API_TOKEN_PREFIX) {
+            let tokens = ApiTokenRepository::new(pool);
+            let api_token = tokens
+                .find_by_hash(&Self::hash_api_token(token))
+                .await?
+                .filter(|t| t.expires_at.is_none_or(|at| at > Utc::now()))
+                .ok_or_else(|| {
+                    AppError::Unauthorized("Invalid or expired API token".to_string())
+                })?;
+            tokens.touch(api_token.id).await?;
+            (api_token.user_id, Some(api_token.id))
+        } else {
+            let claims = Self::decode_jwt(token)?;
+            let user_id = claims
+                .sub
+                .parse::<i64>()
+                .map_err(|_| AppError::Unauthorized("Invalid token subject".to_string()))?;
+            (user_id, None)
+        };
+
+        // 用户被删除或禁用后，已签发的 token 立即失效
+        let user = match users.find_by_id(user_id).await {
+            Ok(user) if user.enabled => user,
+            Ok(_) | Err(AppError::NotFound(_)) => {
+                return Err(AppError::Unauthorized("User is disabled".to_string()));
+            }
+            Err(e) => return Err(e),
+        };
+
+        Ok(AuthUser {
+            id: user.id,
+            username: user.username,
+            role: user.role,
+            token_id,
+        })
+    }
+
+    /// 为用户创建 API Token，明文只返回这一次
+    pub async fn create_api_token(
+        pool: &MySqlPool,
+        user_id: i64,
+        name: &str,
+        expires_in_days: Option<i64>,
+    ) -> Result<CreatedApiToken> {
+        let token = format!(
+            "{}{}",
+            API_TOKEN_PREFIX,
+            Alphanumeric.sample_string(&mut rand::thread_rng(), 40)
+        );
+        let expires_at = expires_in_days.map(|days| Utc::now() + Duration::days(days));
+        let repo = ApiTokenRepository::new(pool);
+        let id = repo
+            .create(
+                user_id,
+                name,
+                &Self::hash_api_token(&token),
+                &token[..API_TOKEN_PREFIX.len() + 6],
+                expires_at,
+            )
+            .await?;
+
+        Ok(CreatedApiToken {
+            token,
+            info: repo.find_by_id(id).await?,
+        })
+    }
+
+    /// 用户表为空时创建初始管理员
+    /// 用户名取 ADMIN_USERNAME（默认 admin），密码取 ADMIN_PASSWORD，未配置时随机生成并打印到日志
+    pub async fn bootstrap_admin(pool: &MySqlPool) -> Result<()> {
+        let repo = UserRepository::new(pool);
+        if repo.count().await? > 0 {
+            return Ok(());
+        }
+
+        let username = std::env::var("ADMIN_USERNAME")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| "admin".to_string());
+        let password = match std::env::var("ADMIN_PASSWORD") {
+            Ok(password) if !password.is_empty() => {
+                Self::validate_password(&password)?;
+                password
+            }
+            _ => {
+                let password = Alphanumeric.sample_string(&mut rand::thread_rng(), 16);
+                tracing::warn!(
+                    "Created initial admin user '{}' with generated password: {} (change it after login)",
+                    username,
+                    password
+                );
+                password
+            }
+        };
+
+        repo.create(&username, &Self::hash_password(&password)?, Role::Admin)
+            .await?;
+        tracing::info!("Initial admin user '{}' created", username);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_and_jwt() {
+        let hash = AuthService::hash_password("correct horse").unwrap();
+        assert!(AuthService::verify_password("correct horse", &hash));
+        assert!(!AuthService::verify_password("wrong horse", &hash));
+        assert!(!AuthService::verify_password("correct horse", "not-a-hash"));
+        assert!(AuthService::validate_password("short").is_err());
+
+        let (token, expires_at) = AuthService::issue_jwt(7, "alice").unwrap();
+        let claims = AuthService::decode_jwt(&token).unwrap();
+        assert_eq!((claims.sub.as_str(), claims.name.as_str()), ("7", "alice"));
+        assert_eq!(claims.exp, expires_at.timestamp());
+
+        // 篡改签名后校验失败
+        let tampered = format!("{}x", token);
+        assert!(matches!(
+            AuthService::decode_jwt(&tampered),
+            Err(AppError::Unauthorized(_))
+        ));
+
+        let hashed = AuthService::hash_api_token("rwcs_abc");
+        assert_eq!(hashed.len(), 64);
+        assert_ne!(hashed, AuthService::hash_api_token("rwcs_abd"));
+    }
+
+    #[test]
+    fn test_validate_password() {
+        for rejected in [
+            "Ab1!xyz",        // 太短
+            "alllowercase",   // 只有一类字符
+            "lowercase123",   // 两类字符
+            "ALLUPPER!!",     // 两类字符
+            "12345678",       // 只有数字
+            "Password1",      // 常见密码
+            "P@ssw0rd",       // 常见密码
+            "QWERTY123",      // 常见密码（不区分大小写）
+        ] {
+            assert!(
+                matches!(
+                    AuthService::validate_password(rejected),
+                    Err(AppError::Validation(_))
+                ),
+                "{} should be rejected",
+                rejected
+            );
+        }
+
+        for accepted in [
+            "Sync2024orders",
+            "correct-horse-7",
+            "Bärenstark9",
+            "ZhBq7T3mWk5nR2pX",
+        ] {
+            assert!(
+                AuthService::validate_password(accepted).is_ok(),
+                "{} should be accepted",
+                accepted
+            );
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use crate::services::TaskLogger;
+use crate::utils::error::{AppError, Result};
+use crate::utils::proxy;
+use mysql_async::prelude::*;
+use std::time::{Duration, Instant};
+
+/// 默认的 RisingWave 负载查询：compute / meta 进程中最高的 CPU 使用率（单核百分比）
+const DEFAULT_RW_LOAD_PROMQL: &str =
+    r#"max(rate(process_cpu_seconds_total{job=~".*(compute|meta).*"}[1m])) * 100"#;
+
+/// 负载过高时最长等待时间的默认值
+const DEFAULT_MAX_WAIT: Duration = Duration::from_secs(600);
+
+/// 负载检查间隔的默认值
+const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Prometheus 查询超时
+const PROMETHEUS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 一次负载采样，无法获取的指标为空
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClusterLoad {
+    /// StarRocks 存活 BE 中最高的 CPU 使用率（%）
+    pub sr_cpu_pct: Option<f64>,
+    /// RW_LOAD_PROMQL 的查询结果
+    pub rw_load: Option<f64>,
+}
+
+/// 回填前的负载阈值，未配置的阈值不检查
+#[derive(Debug, Clone, Default)]
+pub struct LoadThresholds {
+    pub sr_cpu_pct: Option<f64>,
+    pub rw_load: Option<f64>,
+}
+
+impl LoadThresholds {
+    /// BACKFILL_MAX_SR_CPU_PCT 和 BACKFILL_MAX_RW_LOAD（需同时配置 RW_PROMETHEUS_URL）
+    pub fn from_env() -> Self {
+        let threshold = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| *v > 0.0)
+        };
+        Self {
+            sr_cpu_pct: threshold("BACKFILL_MAX_SR_CPU_PCT"),
+            rw_load: threshold("BACKFILL_MAX_RW_LOAD").filter(|_| prometheus_url().is_some()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.sr_cpu_pct.is_some() || self.rw_load.is_some()
+    }
+
+    /// 超过阈值的指标说明，都未超过时为空
+    pub fn exceeded(&self, load: &ClusterLoad) -> Vec<String> {
+        let mut exceeded = Vec::new();
+        if let (Some(limit), Some(value)) = (self.sr_cpu_pct, load.sr_cpu_pct)
+            && value > limit
+        {
+            exceeded.push(format!("StarRocks BE CPU {:.1}% > {}%", value, limit));
+        }
+        if let (Some(limit), Some(value)) = (self.rw_load, load.rw_load)
+            && value > limit
+        {
+            exceeded.push(format!("RisingWave load {:.1} > {}", value, limit));
+        }
+        exceeded
+    }
+}
+
+/// 回填前检查集群负载，负载高于阈值时推迟执行，决定写入任务日志
+pub struct BackfillGate;
+
+impl BackfillGate {
+    /// 负载高于阈值时按间隔等待，超过最长等待时间后仍继续执行；指标获取失败时不阻塞
+    pub async fn wait(logger: &TaskLogger<'_>, sr_conn: &mut mysql_async::Conn) -> Result<()> {
+        let thresholds = LoadThresholds::from_env();
+        if !thresholds.is_enabled() {
+            return Ok(());
+        }
+        let max_wait = duration_env("BACKFILL_LOAD_MAX_WAIT_SECS", DEFAULT_MAX_WAIT);
+        let interval = duration_env("BACKFILL_LOAD_CHECK_INTERVAL_SECS", DEFAULT_CHECK_INTERVAL);
+        let started = Instant::now();
+
+        loop {
+            let load = Self::measure(logger, sr_conn, &thresholds).await?;
+            let exceeded = thresholds.exceeded(&load);
+            if exceeded.is_empty() {
+                let waited = started.elapsed().as_secs();
+                let message = if waited > 0 {
+                    format!("Cluster load is below thresholds after waiting {}s", waited)
+                } else {
+                    "Cluster load is below thresholds".to_string()
+                };
+                return logger.step(&message).await;
+            }
+            if started.elapsed() >= max_wait {
+                return logger
+                    .warn(&format!(
+                        "Cluster load still above thresholds after {}s ({}), starting backfill anyway",
+                        started.elapsed().as_secs(),
+                        exceeded.join(", ")
+                    ))
+                    .await;
+            }
+
+            logger
+                .warn(&format!(
+                    "Delaying backfill for {}s: {}",
+                    interval.as_secs(),
+                    exceeded.join(", ")
+                ))
+                .await?;
+            // 等待期间日志即时可见
+            logger.flush().await?;
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn measure(
+        logger: &TaskLogger<'_>,
+        sr_conn: &mut mysql_async::Conn,
+        thresholds: &LoadThresholds,
+    ) -> Result<ClusterLoad> {
+        let mut load = ClusterLoad::default();
+        if thresholds.sr_cpu_pct.is_some() {
+            match Self::starrocks_cpu(sr_conn).await {
+                Ok(cpu) => load.sr_cpu_pct = cpu,
+                Err(e) => {
+                    logger
+                        .warn(&format!("Failed to read StarRocks BE load: {}", e))
+                        .await?
+                }
+            }
+        }
+        if thresholds.rw_load.is_some() {
+            match Self::risingwave_load().await {
+                Ok(value) => load.rw_load = value,
+                Err(e) => {
+                    logger
+                        .warn(&format!("Failed to read RisingWave load: {}", e))
+                        .await?
+                }
+            }
+        }
+        Ok(load)
+    }
+
+    /// SHOW BACKENDS 中存活 BE 的最高 CpuUsedPct，旧版本没有该列时为空
+    async fn starrocks_cpu(conn: &mut mysql_async::Conn) -> Result<Option<f64>> {
+        let rows: Vec<mysql_async::Row> = conn
+            .query("SHOW BACKENDS")
+            .await
+            .map_err(|e| AppError::Connection(format!("SHOW BACKENDS failed: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .filter(|row| {
+                row.get::<Option<String>, _>("Alive").flatten().as_deref() == Some("true")
+            })
+            .filter_map(|row| row.get::<Option<String>, _>("CpuUsedPct").flatten())
+            .filter_map(|value| Self::parse_percent(&value))
+            .reduce(f64::max))
+    }
+
+    /// 解析 StarRocks 返回的百分比，例如 "12.34 %"
+    fn parse_percent(value: &str) -> Option<f64> {
+        value.trim().trim_end_matches('%').trim().parse().ok()
+    }
+
+    async fn risingwave_load() -> Result<Option<f64>> {
+        let Some(url) = prometheus_url() else {
+            return Ok(None);
+        };
+        let query = std::env::var("RW_LOAD_PROMQL")
+            .ok()
+            .filter(|q| !q.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_RW_LOAD_PROMQL.to_string());
+        let client = proxy::http_client_builder()
+            .timeout(PROMETHEUS_TIMEOUT)
+            .build()
+            .map_err(|e| AppError::Connection(e.to_string()))?;
+        let response: serde_json::Value = client
+            .get(format!("{}/api/v1/query", url.trim_end_matches('/')))
+            .query(&[("query", query.as_str())])
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| AppError::Connection(format!("Prometheus query failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::Connection(format!("Invalid Prometheus response: {}", e)))?;
+        Ok(Self::prometheus_value(&response))
+    }
+
+    /// 即时查询结果中最大的样本值，没有样本时为空
+    fn prometheus_value(response: &serde_json::Value) -> Option<f64> {
+        response["data"]["result"]
+            .as_array()?
+            .iter()
+            .filter_map(|sample| sample["value"][1].as_str()?.parse::<f64>().ok())
+            .filter(|v| v.is_finite())
+            .reduce(f64::max)
+    }
+}
+
+fn prometheus_url() -> Option<String> {
+    std::env::var("RW_PROMETHEUS_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+}
+
+fn duration_env(name: &str, default: Duration) -> Duration {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_thresholds() {
+        assert_eq!(BackfillGate::parse_percent("12.34 %"), Some(12.34));
+        assert_eq!(BackfillGate::parse_percent("N/A"), None);
+        let response = serde_json::json!({
+            "status": "success",
+            "data": {"resultType": "vector", "result": [
+                {"metric": {"job": "compute"}, "value": [1700000000.0, "85.5"]},
+                {"metric": {"job": "meta"}, "value": [1700000000.0, "NaN"]}
+            ]}
+        });
+        assert_eq!(BackfillGate::prometheus_value(&response), Some(85.5));
+
+        let thresholds = LoadThresholds {
+            sr_cpu_pct: Some(80.0),
+            rw_load: Some(90.0),
+        };
+        let load = ClusterLoad {
+            sr_cpu_pct: Some(92.0),
+            rw_load: Some(85.5),
+        };
+        assert_eq!(
+            thresholds.exceeded(&load),
+            vec!["StarRocks BE CPU 92.0% > 80%".to_string()]
+        );
+        // 无法获取的指标不阻塞
+        assert!(thresholds.exceeded(&ClusterLoad::default()).is_empty());
+    }
+}
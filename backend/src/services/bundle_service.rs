@@ -0,0 +1,646 @@
+use crate::db::{
+    AlertRoutingRepository, ConfigRepository, NotificationChannelRepository, TaskRepository,
+};
+use crate::models::{
+    AlertRouteRequest, AlertSilence, AlertSilenceRequest, BUNDLE_FORMAT_VERSION, BundleAlertRoute,
+    BundleChannel, BundleConnection, BundleSilence, BundleSyncDefinition, ConfigBundle,
+    CreateConnectionRequest, DatabaseConfig, ExportRequest, ImportResult, LabelMatcher,
+    MonitoringBundle, MonitoringImportResult, NotificationChannelRequest, PasswordMode, SyncTask,
+    TaskFilter,
+};
+use crate::utils::crypto;
+use crate::utils::error::{AppError, Result};
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+use std::collections::{HashMap, HashSet};
+
+/// 导出同步定义时最多扫描的任务数
+const EXPORT_TASK_SCAN_LIMIT: i64 = 1000;
+
+/// 连接配置与同步定义的导出 / 导入
+pub struct BundleService;
+
+impl BundleService {
+    /// 导出所有连接配置，以及每个目标表最近一次使用的同步定义
+    pub async fn export(pool: &MySqlPool, request: &ExportRequest) -> Result<ConfigBundle> {
+        let key = match request.passwords {
+            PasswordMode::Exclude => None,
+            PasswordMode::Encrypt => Some(crypto::derive_key(Self::passphrase(
+                request.passphrase.as_deref(),
+            )?)),
+        };
+
+        let configs = ConfigRepository::new(pool).find_all().await?;
+        let tasks = TaskRepository::new(pool)
+            .find_history(&TaskFilter::default(), EXPORT_TASK_SCAN_LIMIT, 0)
+            .await?;
+
+        let names: HashMap<i64, String> = configs.iter().map(|c| (c.id, c.name.clone())).collect();
+        let connections = configs
+            .into_iter()
+            .map(|config| Self::to_bundle_connection(config, key.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ConfigBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            exported_at: chrono::Utc::now(),
+            passwords: request.passwords,
+            connections,
+            sync_definitions: Self::latest_definitions(&tasks, &names),
+        })
+    }
+
+    /// 导入连接配置，同名连接保留本地版本，同步定义按本地连接 ID 重写后返回
+    pub async fn import(
+        pool: &MySqlPool,
+        bundle: ConfigBundle,
+        passphrase: Option<&str>,
+    ) -> Result<ImportResult> {
+        if bundle.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(AppError::Validation(format!(
+                "Unsupported bundle format version: {}",
+                bundle.format_version
+            )));
+        }
+        let key = match bundle.passwords {
+            PasswordMode::Exclude => None,
+            PasswordMode::Encrypt => Some(crypto::derive_key(Self::passphrase(passphrase)?)),
+        };
+
+        let repo = ConfigRepository::new(pool);
+        let mut ids: HashMap<String, i64> = repo
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|c| (c.name, c.id))
+            .collect();
+
+        let mut result = ImportResult::default();
+        for connection in bundle.connections {
+            if ids.contains_key(&connection.name) {
+                result.skipped.push(connection.name);
+                continue;
+            }
+
+            let password = match (&connection.password, &key) {
+                _ if connection.no_password => String::new(),
+                (Some(encrypted), Some(key)) => {
+                    crypto::decrypt_with_key(encrypted, key).map_err(|_| {
+                        AppError::Validation(format!(
+                            "Failed to decrypt password for '{}': wrong passphrase?",
+                            connection.name
+                        ))
+                    })?
+                }
+                _ => {
+                    result.missing_passwords.push(connection.name.clone());
+                    String::new()
+                }
+            };
+
+            let id = repo
+                .save(CreateConnectionRequest {
+                    name: connection.name.clone(),
+                    db_type: connection.db_type,
+                    host: connection.host,
+                    port: connection.port,
+                    username: connection.username,
+                    password,
+                    no_password: connection.no_password,
+                    database_name: connection.database_name,
+                    proxy: connection.proxy,
+                    // TLS 证书和私钥不导出，导入后需要重新配置
+                    tls: None,
+                    version: None,
+                })
+                .await?;
+            ids.insert(connection.name.clone(), id);
+            result.created.push(connection.name);
+        }
+
+        for definition in bundle.sync_definitions {
+            let resolve = |name: &str| {
+                ids.get(name).copied().ok_or_else(|| {
+                    AppError::Validation(format!(
+                        "Sync definition {}.{} references unknown connection '{}'",
+                        definition.target_database, definition.target_table, name
+                    ))
+                })
+            };
+            let mysql_id = resolve(&definition.mysql_config)?;
+            let rw_id = resolve(&definition.rw_config)?;
+            let sr_id = resolve(&definition.sr_config)?;
+            result
+                .sync_requests
+                .push(definition.into_request(mysql_id, rw_id, sr_id));
+        }
+
+        Ok(result)
+    }
+
+    /// 导出通知渠道、告警路由规则和尚未结束的静默窗口
+    /// 渠道配置包含 Webhook 地址和密钥，与连接密码一样只在使用导出口令时导出
+    pub async fn export_monitoring(
+        pool: &MySqlPool,
+        request: &ExportRequest,
+    ) -> Result<MonitoringBundle> {
+        let key = match request.passwords {
+            PasswordMode::Exclude => None,
+            PasswordMode::Encrypt => Some(crypto::derive_key(Self::passphrase(
+                request.passphrase.as_deref(),
+            )?)),
+        };
+
+        let channels = NotificationChannelRepository::new(pool).find_all().await?;
+        let routing = AlertRoutingRepository::new(pool);
+        let routes = routing.find_routes().await?;
+        let silences = routing.find_unexpired_silences().await?;
+
+        let names: HashMap<i64, String> =
+            channels.iter().map(|c| (c.id, c.name.clone())).collect();
+        let channels = channels
+            .into_iter()
+            .map(|channel| {
+                let settings = key
+                    .as_ref()
+                    .map(|key| {
+                        crypto::encrypt_with_key(&serde_json::to_string(&channel.settings)?, key)
+                    })
+                    .transpose()?;
+                Ok(BundleChannel {
+                    kind: channel.settings.kind().to_string(),
+                    name: channel.name,
+                    settings,
+                    events: channel.events,
+                    enabled: channel.enabled,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let alert_routes = routes
+            .into_iter()
+            .map(|route| BundleAlertRoute {
+                name: route.name,
+                matchers: route.matchers,
+                // 已删除的渠道不导出
+                channels: route
+                    .channel_ids
+                    .iter()
+                    .filter_map(|id| names.get(id).cloned())
+                    .collect(),
+                priority: route.priority,
+                continue_matching: route.continue_matching,
+                enabled: route.enabled,
+            })
+            .collect();
+        let silences = silences
+            .into_iter()
+            .map(|silence| BundleSilence {
+                matchers: silence.matchers,
+                starts_at: silence.starts_at,
+                ends_at: silence.ends_at,
+                comment: silence.comment,
+                created_by: silence.created_by,
+            })
+            .collect();
+
+        Ok(MonitoringBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            exported_at: Utc::now(),
+            passwords: request.passwords,
+            channels,
+            alert_routes,
+            silences,
+        })
+    }
+
+    /// 导入监控配置：渠道按名称、规则按名称、静默窗口按匹配器和时间范围对应本地记录，
+    /// 已存在的渠道和规则用导出文件中的内容更新，重复导入同一文件不会产生重复记录
+    /// 所有内容先校验再写入，任何一项无效时不做修改
+    pub async fn import_monitoring(
+        pool: &MySqlPool,
+        bundle: MonitoringBundle,
+        passphrase: Option<&str>,
+    ) -> Result<MonitoringImportResult> {
+        if bundle.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(AppError::Validation(format!(
+                "Unsupported bundle format version: {}",
+                bundle.format_version
+            )));
+        }
+        let key = match bundle.passwords {
+            PasswordMode::Exclude => None,
+            PasswordMode::Encrypt => Some(crypto::derive_key(Self::passphrase(passphrase)?)),
+        };
+
+        let channel_repo = NotificationChannelRepository::new(pool);
+        let routing = AlertRoutingRepository::new(pool);
+        let local_channels = channel_repo.find_all().await?;
+        let local_routes = routing.find_routes().await?;
+        let local_silences = routing.find_unexpired_silences().await?;
+        let mut result = MonitoringImportResult::default();
+
+        // 渠道：没有导出配置时沿用本地同名渠道的配置
+        let mut channels = Vec::new();
+        for channel in bundle.channels {
+            let local = local_channels.iter().find(|c| c.name == channel.name);
+            let settings = match (&channel.settings, &key) {
+                (Some(encrypted), Some(key)) => {
+                    let json = crypto::decrypt_with_key(encrypted, key).map_err(|_| {
+                        AppError::Validation(format!(
+                            "Failed to decrypt settings for channel '{}': wrong passphrase?",
+                            channel.name
+                        ))
+                    })?;
+                    serde_json::from_str(&json)?
+                }
+                _ => match local {
+                    Some(local) => local.settings.clone(),
+                    None => {
+                        result.channels.skipped.push(channel.name);
+                        continue;
+                    }
+                },
+            };
+            let request = NotificationChannelRequest {
+                name: channel.name,
+                settings,
+                events: channel.events,
+                enabled: channel.enabled,
+            };
+            request.validate().map_err(|message| {
+                AppError::Validation(format!("Channel '{}': {}", request.name, message))
+            })?;
+            channels.push((local.map(|c| c.id), request));
+        }
+
+        // 规则引用的渠道必须在本地存在或随本次导入创建，新建的渠道 ID 先记为 0
+        let mut channel_ids: HashMap<String, i64> = local_channels
+            .iter()
+            .map(|c| (c.name.clone(), c.id))
+            .collect();
+        for (id, request) in &channels {
+            channel_ids.insert(request.name.clone(), id.unwrap_or(0));
+        }
+        for route in &bundle.alert_routes {
+            Self::route_request(route, &channel_ids)?;
+        }
+
+        let mut silences = Vec::new();
+        let now = Utc::now();
+        for silence in bundle.silences {
+            let label = Self::silence_label(&silence.matchers, silence.ends_at);
+            if silence.ends_at <= now {
+                result.silences.skipped.push(label);
+                continue;
+            }
+            let request = AlertSilenceRequest {
+                matchers: silence.matchers,
+                starts_at: Some(silence.starts_at),
+                ends_at: silence.ends_at,
+                comment: silence.comment,
+            };
+            request
+                .validate()
+                .map_err(|message| AppError::Validation(format!("Silence {}: {}", label, message)))?;
+            silences.push((label, request, silence.created_by));
+        }
+
+        for (id, request) in channels {
+            match id.and_then(|id| local_channels.iter().find(|c| c.id == id)) {
+                Some(local)
+                    if local.settings == request.settings
+                        && local.events == request.events
+                        && local.enabled == request.enabled =>
+                {
+                    result.channels.unchanged.push(request.name);
+                }
+                Some(local) => {
+                    channel_repo.update(local.id, &request).await?;
+                    result.channels.updated.push(request.name);
+                }
+                None => {
+                    let id = channel_repo.create(&request).await?;
+                    channel_ids.insert(request.name.clone(), id);
+                    result.channels.created.push(request.name);
+                }
+            }
+        }
+
+        for route in bundle.alert_routes {
+            let request = Self::route_request(&route, &channel_ids)?;
+            match local_routes.iter().find(|r| r.name == request.name) {
+                Some(local)
+                    if local.matchers == request.matchers
+                        && local.channel_ids == request.channel_ids
+                        && local.priority == request.priority
+                        && local.continue_matching == request.continue_matching
+                        && local.enabled == request.enabled =>
+                {
+                    result.alert_routes.unchanged.push(request.name);
+                }
+                Some(local) => {
+                    routing.update_route(local.id, &request).await?;
+                    result.alert_routes.updated.push(request.name);
+                }
+                None => {
+                    routing.create_route(&request).await?;
+                    result.alert_routes.created.push(request.name);
+                }
+            }
+        }
+
+        for (label, request, created_by) in silences {
+            if local_silences
+                .iter()
+                .any(|local| Self::same_silence(local, &request))
+            {
+                result.silences.unchanged.push(label);
+            } else {
+                routing.create_silence(&request, created_by.as_deref()).await?;
+                result.silences.created.push(label);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// 按渠道名称解析规则的渠道 ID 并校验规则
+    fn route_request(
+        route: &BundleAlertRoute,
+        channel_ids: &HashMap<String, i64>,
+    ) -> Result<AlertRouteRequest> {
+        let channel_ids = route
+            .channels
+            .iter()
+            .map(|name| {
+                channel_ids.get(name).copied().ok_or_else(|| {
+                    AppError::Validation(format!(
+                        "Alert route '{}' references unknown channel '{}'",
+                        route.name, name
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let request = AlertRouteRequest {
+            name: route.name.clone(),
+            matchers: route.matchers.clone(),
+            channel_ids,
+            priority: route.priority,
+            continue_matching: route.continue_matching,
+            enabled: route.enabled,
+        };
+        request.validate().map_err(|message| {
+            AppError::Validation(format!("Alert route '{}': {}", route.name, message))
+        })?;
+        Ok(request)
+    }
+
+    /// 静默窗口的匹配器和时间范围相同时视为同一个
+    fn same_silence(local: &AlertSilence, request: &AlertSilenceRequest) -> bool {
+        local.matchers == request.matchers
+            && Some(local.starts_at) == request.starts_at
+            && local.ends_at == request.ends_at
+    }
+
+    /// 导入结果中静默窗口的显示名称，例如 `alertname=SinkLag until 2024-01-01T00:00:00Z`
+    fn silence_label(matchers: &[LabelMatcher], ends_at: DateTime<Utc>) -> String {
+        let matchers = matchers
+            .iter()
+            .map(|m| format!("{}{}{}", m.label, m.op.as_str(), m.value))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{} until {}", matchers, ends_at.to_rfc3339())
+    }
+
+    pub fn to_yaml<T: serde::Serialize>(bundle: &T) -> Result<String> {
+        serde_yaml::to_string(bundle)
+            .map_err(|e| AppError::Unknown(format!("Failed to serialize bundle: {}", e)))
+    }
+
+    pub fn from_yaml<T: serde::de::DeserializeOwned>(yaml: &str) -> Result<T> {
+        serde_yaml::from_str(yaml)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid bundle YAML: {}", e)))
+    }
+
+    fn passphrase(passphrase: Option<&str>) -> Result<&str> {
+        passphrase.filter(|p| !p.is_empty()).ok_or_else(|| {
+            AppError::Validation("A passphrase is required for encrypted passwords".to_string())
+        })
+    }
+
+    fn to_bundle_connection(
+        config: DatabaseConfig,
+        key: Option<&[u8; 32]>,
+    ) -> Result<BundleConnection> {
+        let password = key
+            .filter(|_| !config.no_password)
+            .map(|key| crypto::encrypt_with_key(&config.password, key))
+            .transpose()?;
+
+        Ok(BundleConnection {
+            name: config.name,
+            db_type: config.db_type,
+            host: config.host,
+            port: config.port,
+            username: config.username,
+            password,
+            no_password: config.no_password,
+            database_name: config.database_name,
+            proxy: config.proxy,
+        })
+    }
+
+    /// 按目标表去重，保留最近一次任务的同步定义（任务按开始时间倒序）
+    pub fn latest_definitions(
+        tasks: &[SyncTask],
+        names: &HashMap<i64, String>,
+    ) -> Vec<BundleSyncDefinition> {
+        let mut seen = HashSet::new();
+        let mut definitions = Vec::new();
+
+        for task in tasks {
+            let Ok(requests) = task.sync_requests() else {
+                continue;
+            };
+            for request in requests {
+                let key = (
+                    request.target_database.clone(),
+                    request.target_table.clone(),
+                );
+                if seen.contains(&key) {
+                    continue;
+                }
+                let (Some(mysql), Some(rw), Some(sr)) = (
+                    names.get(&request.mysql_config_id),
+                    names.get(&request.rw_config_id),
+                    names.get(&request.sr_config_id),
+                ) else {
+                    continue;
+                };
+                seen.insert(key);
+                definitions.push(BundleSyncDefinition {
+                    mysql_config: mysql.clone(),
+                    rw_config: rw.clone(),
+                    sr_config: sr.clone(),
+                    mysql_database: request.mysql_database,
+                    mysql_table: request.mysql_table,
+                    target_database: request.target_database,
+                    target_table: request.target_table,
+                    options: request.options,
+                    row_filter: request.row_filter,
+                    partition: request.partition,
+                    column_mapping: request.column_mapping,
+                    nested_columns: request.nested_columns,
+                    spatial_columns: request.spatial_columns,
+                });
+            }
+        }
+
+        definitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{MatchOp, SyncRequest, TaskStatus};
+
+    fn task(target_table: &str, mysql_table: &str, sr_config_id: i64) -> SyncTask {
+        let request = SyncRequest {
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id,
+            mysql_database: "shop".to_string(),
+            mysql_table: mysql_table.to_string(),
+            target_database: "ods".to_string(),
+            target_table: target_table.to_string(),
+            options: Default::default(),
+            row_filter: None,
+            partition: None,
+            column_mapping: Default::default(),
+            nested_columns: Default::default(),
+            spatial_columns: Default::default(),
+        };
+        SyncTask {
+            id: 1,
+            task_name: "t".to_string(),
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id,
+            mysql_database: "shop".to_string(),
+            mysql_table: mysql_table.to_string(),
+            target_database: "ods".to_string(),
+            target_table: target_table.to_string(),
+            status: TaskStatus::Completed,
+            started_at: chrono::Utc::now(),
+            completed_at: None,
+            error_message: None,
+            options: "{}".to_string(),
+            preflight_result: None,
+            requests: Some(serde_json::to_string(&vec![request]).unwrap()),
+            sink_requests: None,
+        }
+    }
+
+    #[test]
+    fn test_latest_definitions_dedup_and_names() {
+        let names: HashMap<i64, String> = [(1, "mysql"), (2, "rw"), (3, "sr")]
+            .into_iter()
+            .map(|(id, name)| (id, name.to_string()))
+            .collect();
+        let tasks = vec![
+            task("orders", "orders_v2", 3),
+            task("orders", "orders", 3),
+            task("users", "users", 3),
+            // 引用已删除的连接
+            task("items", "items", 99),
+        ];
+
+        let definitions = BundleService::latest_definitions(&tasks, &names);
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(definitions[0].target_table, "orders");
+        assert_eq!(definitions[0].mysql_table, "orders_v2");
+        assert_eq!(definitions[0].sr_config, "sr");
+        assert_eq!(definitions[1].target_table, "users");
+    }
+
+    #[test]
+    fn test_bundle_yaml_round_trip() {
+        let bundle = ConfigBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            exported_at: chrono::Utc::now(),
+            passwords: PasswordMode::Exclude,
+            connections: vec![BundleConnection {
+                name: "prod-mysql".to_string(),
+                db_type: crate::models::DbType::MySQL,
+                host: "mysql.prod".to_string(),
+                port: 3306,
+                username: "root".to_string(),
+                password: None,
+                no_password: false,
+                database_name: None,
+                proxy: None,
+            }],
+            sync_definitions: vec![],
+        };
+
+        let yaml = BundleService::to_yaml(&bundle).unwrap();
+        assert!(yaml.contains("passwords: exclude"));
+        assert!(!yaml.contains("password:"));
+
+        let parsed: ConfigBundle = BundleService::from_yaml(&yaml).unwrap();
+        assert_eq!(parsed.connections[0].name, "prod-mysql");
+        assert!(BundleService::from_yaml::<ConfigBundle>("connections: [").is_err());
+    }
+
+    #[test]
+    fn test_monitoring_bundle_yaml() {
+        let matchers = vec![
+            LabelMatcher {
+                label: "alertname".to_string(),
+                op: MatchOp::Equal,
+                value: "SinkLag".to_string(),
+            },
+            LabelMatcher {
+                label: "sink".to_string(),
+                op: MatchOp::Regex,
+                value: "ods_.*".to_string(),
+            },
+        ];
+        let ends_at = "2030-01-01T00:00:00Z".parse().unwrap();
+        assert_eq!(
+            BundleService::silence_label(&matchers, ends_at),
+            "alertname=SinkLag,sink=~ods_.* until 2030-01-01T00:00:00+00:00"
+        );
+
+        let yaml = r#"
+format_version: 1
+exported_at: 2030-01-01T00:00:00Z
+channels:
+  - name: ops
+    kind: lark
+    events: [alert]
+    enabled: true
+alert_routes:
+  - name: sinks
+    matchers:
+      - label: component
+        value: sink
+    channels: [ops]
+    enabled: true
+"#;
+        let bundle: MonitoringBundle = BundleService::from_yaml(yaml).unwrap();
+        assert_eq!(bundle.passwords, PasswordMode::Exclude);
+        assert_eq!(bundle.channels[0].settings, None);
+        assert!(bundle.silences.is_empty());
+
+        let ids: HashMap<String, i64> = [("ops".to_string(), 7)].into_iter().collect();
+        let request = BundleService::route_request(&bundle.alert_routes[0], &ids).unwrap();
+        assert_eq!(request.channel_ids, vec![7]);
+        assert!(BundleService::route_request(&bundle.alert_routes[0], &HashMap::new()).is_err());
+
+        let round_trip: MonitoringBundle =
+            BundleService::from_yaml(&BundleService::to_yaml(&bundle).unwrap()).unwrap();
+        assert_eq!(round_trip.alert_routes, bundle.alert_routes);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::models::{CapacityCheckMode, CapacityCheckResult, TableStats};
+use crate::utils::error::{AppError, Result};
+use mysql_async::prelude::*;
+
+/// 回填后 StarRocks 需保留的默认最小空闲比例
+pub const DEFAULT_MIN_FREE_SPACE_RATIO: f64 = 0.2;
+
+/// 建议分桶时每个桶的目标数据量（源表数据量，1 GiB）
+const BUCKET_TARGET_BYTES: i64 = 1024 * 1024 * 1024;
+
+/// 建议分桶时每个桶的目标行数
+const BUCKET_TARGET_ROWS: i64 = 10_000_000;
+
+/// 建议分桶数上限
+const MAX_SUGGESTED_BUCKETS: u32 = 256;
+
+/// 未指定分桶数时的处理方式，通过环境变量 SR_BUCKET_SUGGESTION 设置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BucketStrategy {
+    /// 按源表数据量和行数估算分桶数（默认）
+    Size,
+    /// 使用自动分桶（StarRocks 3.x 推荐）
+    Auto,
+    /// 不指定 BUCKETS，由 StarRocks 决定
+    Off,
+}
+
+/// StarRocks 集群磁盘容量汇总（所有存活的 BE）
+#[derive(Debug, Clone, Default)]
+pub struct StarRocksCapacity {
+    pub total_bytes: i64,
+    pub available_bytes: i64,
+    pub alive_backends: usize,
+}
+
+/// 容量预检服务
+pub struct CapacityService;
+
+impl CapacityService {
+    /// 全局默认的最小空闲比例，可通过环境变量 SR_MIN_FREE_SPACE_RATIO 覆盖
+    pub fn default_min_free_ratio() -> f64 {
+        std::env::var("SR_MIN_FREE_SPACE_RATIO")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|v| (0.0..1.0).contains(v))
+            .unwrap_or(DEFAULT_MIN_FREE_SPACE_RATIO)
+    }
+
+    /// 未指定分桶数时的处理方式：size（默认）、auto、off
+    pub fn bucket_strategy() -> BucketStrategy {
+        match std::env::var("SR_BUCKET_SUGGESTION")
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+            .as_str()
+        {
+            "auto" => BucketStrategy::Auto,
+            "off" => BucketStrategy::Off,
+            _ => BucketStrategy::Size,
+        }
+    }
+
+    /// 按源表数据量（每桶约 1 GiB）和行数（每桶约 1000 万行）估算分桶数
+    pub fn suggest_buckets(stats: &TableStats) -> u32 {
+        let by_bytes = (stats.data_bytes.max(0) as u64).div_ceil(BUCKET_TARGET_BYTES as u64);
+        let by_rows = (stats.estimated_rows.max(0) as u64).div_ceil(BUCKET_TARGET_ROWS as u64);
+        by_bytes
+            .max(by_rows)
+            .clamp(1, MAX_SUGGESTED_BUCKETS as u64) as u32
+    }
+
+    /// 通过 SHOW BACKENDS 查询 StarRocks BE 的磁盘使用情况
+    pub async fn fetch_starrocks_capacity(
+        conn: &mut mysql_async::Conn,
+    ) -> Result<StarRocksCapacity> {
+        let rows: Vec<mysql_async::Row> = conn.query("SHOW BACKENDS").await.map_err(|e| {
+            tracing::error!("Failed to query StarRocks backends: {}", e);
+            AppError::Connection(format!("Failed to query StarRocks backends: {}", e))
+        })?;
+
+        let mut capacity = StarRocksCapacity::default();
+        for row in rows {
+            let alive: Option<String> = row.get("Alive");
+            if alive.as_deref() != Some("true") {
+                continue;
+            }
+
+            let total: Option<String> = row.get("TotalCapacity");
+            let available: Option<String> = row.get("AvailCapacity");
+            capacity.total_bytes += total.as_deref().and_then(Self::parse_capacity).unwrap_or(0);
+            capacity.available_bytes += available
+                .as_deref()
+                .and_then(Self::parse_capacity)
+                .unwrap_or(0);
+            capacity.alive_backends += 1;
+        }
+
+        if capacity.alive_backends == 0 {
+            return Err(AppError::Connection(
+                "No alive StarRocks backend found".to_string(),
+            ));
+        }
+
+        Ok(capacity)
+    }
+
+    /// 解析 StarRocks 返回的容量字符串，例如 "97.560 GB"、"1.000 TB"
+    pub fn parse_capacity(value: &str) -> Option<i64> {
+        let mut parts = value.split_whitespace();
+        let number: f64 = parts.next()?.parse().ok()?;
+        let multiplier: f64 = match parts.next().unwrap_or("B").to_uppercase().as_str() {
+            "B" => 1.0,
+            "KB" => 1024.0,
+            "MB" => 1024.0 * 1024.0,
+            "GB" => 1024.0 * 1024.0 * 1024.0,
+            "TB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+            "PB" => 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0,
+            _ => return None,
+        };
+
+        Some((number * multiplier) as i64)
+    }
+
+    /// 根据源表数据量估算 StarRocks 所需空间
+    pub fn estimate_required_bytes(stats: &[TableStats], replication_num: i64) -> i64 {
+        let source_bytes: i64 = stats.iter().map(|s| s.data_bytes).sum();
+        source_bytes.saturating_mul(replication_num.max(1))
+    }
+
+    /// 评估回填后剩余空间是否满足阈值
+    pub fn evaluate(
+        mode: CapacityCheckMode,
+        required_bytes: i64,
+        capacity: &StarRocksCapacity,
+        min_free_ratio: f64,
+    ) -> CapacityCheckResult {
+        let projected_free_ratio = if capacity.total_bytes > 0 {
+            (capacity.available_bytes - required_bytes) as f64 / capacity.total_bytes as f64
+        } else {
+            0.0
+        };
+        let passed = projected_free_ratio >= min_free_ratio;
+
+        let message = if passed {
+            format!(
+                "StarRocks capacity check passed: need ~{} bytes, {} of {} bytes available ({:.1}% free after backfill)",
+                required_bytes,
+                capacity.available_bytes,
+                capacity.total_bytes,
+                projected_free_ratio * 100.0
+            )
+        } else {
+            format!(
+                "StarRocks free space below threshold: need ~{} bytes, {} of {} bytes available ({:.1}% free after backfill, minimum {:.1}%)",
+                required_bytes,
+                capacity.available_bytes,
+                capacity.total_bytes,
+                projected_free_ratio * 100.0,
+                min_free_ratio * 100.0
+            )
+        };
+
+        CapacityCheckResult {
+            mode,
+            required_bytes,
+            available_bytes: capacity.available_bytes,
+            total_bytes: capacity.total_bytes,
+            min_free_ratio,
+            projected_free_ratio,
+            passed,
+            message,
+            checked_at: chrono::Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_capacity() {
+        assert_eq!(CapacityService::parse_capacity("1.000 KB"), Some(1024));
+        assert_eq!(
+            CapacityService::parse_capacity("2.500 GB"),
+            Some(2_684_354_560)
+        );
+        assert_eq!(CapacityService::parse_capacity("0.000 "), Some(0));
+        assert_eq!(CapacityService::parse_capacity("N/A"), None);
+        assert_eq!(CapacityService::parse_capacity("1.0 XB"), None);
+    }
+
+    #[test]
+    fn test_suggest_buckets() {
+        let stats = |estimated_rows, data_bytes| TableStats {
+            database: "shop".to_string(),
+            table_name: "orders".to_string(),
+            estimated_rows,
+            data_bytes,
+        };
+
+        assert_eq!(CapacityService::suggest_buckets(&stats(0, 0)), 1);
+        assert_eq!(CapacityService::suggest_buckets(&stats(1_000, 16 * 1024)), 1);
+        assert_eq!(
+            CapacityService::suggest_buckets(&stats(1_000_000, 5 * BUCKET_TARGET_BYTES + 1)),
+            6
+        );
+        assert_eq!(CapacityService::suggest_buckets(&stats(95_000_000, 0)), 10);
+        assert_eq!(
+            CapacityService::suggest_buckets(&stats(0, 10_000 * BUCKET_TARGET_BYTES)),
+            MAX_SUGGESTED_BUCKETS
+        );
+    }
+
+    #[test]
+    fn test_evaluate_threshold() {
+        let capacity = StarRocksCapacity {
+            total_bytes: 1000,
+            available_bytes: 500,
+            alive_backends: 3,
+        };
+
+        let ok = CapacityService::evaluate(CapacityCheckMode::Warn, 200, &capacity, 0.2);
+        assert!(ok.passed);
+        assert!((ok.projected_free_ratio - 0.3).abs() < f64::EPSILON);
+
+        let blocked = CapacityService::evaluate(CapacityCheckMode::Block, 400, &capacity, 0.2);
+        assert!(!blocked.passed);
+        assert!(blocked.message.contains("below threshold"));
+    }
+}
@@ -0,0 +1,106 @@
+use crate::db::{CommentRepository, TaskRepository};
+use crate::models::{Comment, CommentSubject};
+use crate::utils::error::{AppError, Result};
+use sqlx::MySqlPool;
+
+/// 单条评论的最大长度（字符）
+const MAX_COMMENT_CHARS: usize = 4000;
+
+/// 任务 / 管道评论服务
+pub struct CommentService;
+
+impl CommentService {
+    pub async fn list(
+        pool: &MySqlPool,
+        subject_type: CommentSubject,
+        subject_id: &str,
+    ) -> Result<Vec<Comment>> {
+        CommentRepository::new(pool)
+            .find_by_subject(subject_type, subject_id)
+            .await
+    }
+
+    /// 添加评论，任务评论要求任务存在
+    pub async fn add(
+        pool: &MySqlPool,
+        subject_type: CommentSubject,
+        subject_id: &str,
+        author: &str,
+        body: &str,
+    ) -> Result<Comment> {
+        let body = Self::body(body)?;
+        match subject_type {
+            CommentSubject::Task => {
+                let task_id = subject_id.parse::<i64>().map_err(|_| {
+                    AppError::Validation(format!("Invalid task id: {}", subject_id))
+                })?;
+                TaskRepository::new(pool).find_by_id(task_id).await?;
+            }
+            CommentSubject::Pipeline => Self::pipeline_target(subject_id)?,
+        }
+
+        let repo = CommentRepository::new(pool);
+        let id = repo.create(subject_type, subject_id, author, body).await?;
+        repo.find_by_id(id).await
+    }
+
+    /// 删除评论，只有作者本人可以删除
+    pub async fn delete(pool: &MySqlPool, id: i64, user: &str) -> Result<()> {
+        let repo = CommentRepository::new(pool);
+        let comment = repo.find_by_id(id).await?;
+        if comment.author != user {
+            return Err(AppError::Validation(
+                "Only the author can delete a comment".to_string(),
+            ));
+        }
+        repo.delete(id).await
+    }
+
+    fn body(body: &str) -> Result<&str> {
+        let body = body.trim();
+        if body.is_empty() {
+            return Err(AppError::Validation(
+                "Comment must not be empty".to_string(),
+            ));
+        }
+        if body.chars().count() > MAX_COMMENT_CHARS {
+            return Err(AppError::Validation(format!(
+                "Comment must be at most {} characters",
+                MAX_COMMENT_CHARS
+            )));
+        }
+        Ok(body)
+    }
+
+    /// 管道以目标表 `database.table` 标识
+    fn pipeline_target(target: &str) -> Result<()> {
+        match target.split_once('.') {
+            Some((db, table)) if !db.is_empty() && !table.is_empty() && target.len() <= 512 => {
+                Ok(())
+            }
+            _ => Err(AppError::Validation(format!(
+                "Invalid pipeline target, expected database.table: {}",
+                target
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(
+            CommentService::body("  re-ran at 14:00 \n").unwrap(),
+            "re-ran at 14:00"
+        );
+        assert!(CommentService::body("   ").is_err());
+        assert!(CommentService::body(&"x".repeat(MAX_COMMENT_CHARS + 1)).is_err());
+
+        assert!(CommentService::pipeline_target("dw.orders").is_ok());
+        assert!(CommentService::pipeline_target("orders").is_err());
+        assert!(CommentService::pipeline_target(".orders").is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use crate::models::{DatabaseConfig, Setting};
+use crate::services::ConnectionService;
+use crate::utils::error::{AppError, Result};
+use once_cell::sync::Lazy;
+use sqlx::pool::PoolOptions;
+use sqlx::{Database, MySql, MySqlPool, PgPool, Pool, Postgres};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 空闲连接池的检查间隔（秒）
+const POOL_EVICTION_INTERVAL_SECS: u64 = 60;
+
+/// 每个连接池的最大连接数
+const POOL_MAX_CONNECTIONS: u32 = 5;
+
+static MYSQL_POOLS: Lazy<Mutex<PoolCache<MySqlPool>>> =
+    Lazy::new(|| Mutex::new(PoolCache::default()));
+static POSTGRES_POOLS: Lazy<Mutex<PoolCache<PgPool>>> =
+    Lazy::new(|| Mutex::new(PoolCache::default()));
+
+/// 按连接配置缓存的 MySQL / RisingWave 连接池，在请求和同步任务之间复用
+/// 配置的 version 变化后重建连接池，空闲超过 pool.idle_ttl_secs 后关闭
+pub struct ConnectionPoolManager;
+
+impl ConnectionPoolManager {
+    /// MySQL 连接池
+    pub async fn mysql(config: &DatabaseConfig) -> Result<MySqlPool> {
+        if let Some(pool) = MYSQL_POOLS.lock().unwrap().get(config) {
+            return Ok(pool);
+        }
+        let endpoint = Self::endpoint(config).await?;
+        let opts = ConnectionService::build_mysql_options_from_config(&endpoint);
+        let pool = Self::connect::<MySql>(config, opts).await?;
+        Ok(Self::store(&MYSQL_POOLS, config, pool).await)
+    }
+
+    /// RisingWave 连接池
+    pub async fn postgres(config: &DatabaseConfig) -> Result<PgPool> {
+        if let Some(pool) = POSTGRES_POOLS.lock().unwrap().get(config) {
+            return Ok(pool);
+        }
+        let endpoint = Self::endpoint(config).await?;
+        let opts = ConnectionService::build_postgres_options_from_config(&endpoint);
+        let pool = Self::connect::<Postgres>(config, opts).await?;
+        Ok(Self::store(&POSTGRES_POOLS, config, pool).await)
+    }
+
+    /// 连接配置修改或删除后关闭它的连接池
+    pub async fn invalidate(config_id: i64) {
+        let mysql = MYSQL_POOLS.lock().unwrap().remove(config_id);
+        let postgres = POSTGRES_POOLS.lock().unwrap().remove(config_id);
+        if let Some(pool) = mysql {
+            pool.close().await;
+        }
+        if let Some(pool) = postgres {
+            pool.close().await;
+        }
+    }
+
+    /// 关闭空闲超过 TTL 的连接池，返回关闭的数量
+    pub async fn evict_idle() -> usize {
+        let ttl = Self::idle_ttl();
+        let mysql = MYSQL_POOLS.lock().unwrap().take_idle(ttl);
+        let postgres = POSTGRES_POOLS.lock().unwrap().take_idle(ttl);
+        let evicted = mysql.len() + postgres.len();
+        for pool in mysql {
+            pool.close().await;
+        }
+        for pool in postgres {
+            pool.close().await;
+        }
+        if evicted > 0 {
+            tracing::info!("Closed {} idle connection pools", evicted);
+        }
+        evicted
+    }
+
+    /// 启动空闲连接池的定期清理
+    pub fn spawn_eviction() {
+        tokio::spawn(async {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(POOL_EVICTION_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                Self::evict_idle().await;
+            }
+        });
+    }
+
+    fn idle_ttl() -> Duration {
+        Duration::from_secs(Setting::PoolIdleTtlSecs.parsed().unwrap_or(300))
+    }
+
+    /// 解析 SRV 名称并应用代理后的连接地址
+    async fn endpoint(config: &DatabaseConfig) -> Result<DatabaseConfig> {
+        let config = ConnectionService::resolve_config(config.clone()).await?;
+        ConnectionService::via_proxy(&config).await
+    }
+
+    async fn connect<DB: Database>(
+        config: &DatabaseConfig,
+        opts: <DB::Connection as sqlx::Connection>::Options,
+    ) -> Result<Pool<DB>> {
+        PoolOptions::<DB>::new()
+            .max_connections(POOL_MAX_CONNECTIONS)
+            .idle_timeout(Self::idle_ttl())
+            .connect_with(opts)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to connect to {}: {}", config.name, e);
+                AppError::Connection(format!("Failed to connect to {}: {}", config.name, e))
+            })
+    }
+
+    /// 保存新建的连接池，返回缓存中的连接池并关闭被替换或多余的连接池
+    async fn store<DB: Database>(
+        cache: &Mutex<PoolCache<Pool<DB>>>,
+        config: &DatabaseConfig,
+        pool: Pool<DB>,
+    ) -> Pool<DB> {
+        let (pool, stale) = cache.lock().unwrap().insert(config, pool);
+        if let Some(stale) = stale {
+            stale.close().await;
+        }
+        pool
+    }
+}
+
+struct CachedPool<P> {
+    pool: P,
+    version: i64,
+    last_used: Instant,
+}
+
+/// 按配置 ID 缓存的连接池，未保存的临时配置（id 为 0）不缓存
+struct PoolCache<P> {
+    pools: HashMap<i64, CachedPool<P>>,
+}
+
+impl<P> Default for PoolCache<P> {
+    fn default() -> Self {
+        Self {
+            pools: HashMap::new(),
+        }
+    }
+}
+
+impl<P: Clone> PoolCache<P> {
+    /// 与配置版本一致的连接池，同时刷新最近使用时间
+    fn get(&mut self, config: &DatabaseConfig) -> Option<P> {
+        let cached = self.pools.get_mut(&config.id)?;
+        if cached.version != config.version {
+            return None;
+        }
+        cached.last_used = Instant::now();
+        Some(cached.pool.clone())
+    }
+
+    /// 保存连接池，返回应使用的连接池和需要关闭的连接池
+    /// 并发创建时保留先保存的同版本连接池
+    fn insert(&mut self, config: &DatabaseConfig, pool: P) -> (P, Option<P>) {
+        if config.id == 0 {
+            return (pool, None);
+        }
+        if let Some(existing) = self.get(config) {
+            return (existing, Some(pool));
+        }
+        let stale = self.pools.insert(
+            config.id,
+            CachedPool {
+                pool: pool.clone(),
+                version: config.version,
+                last_used: Instant::now(),
+            },
+        );
+        (pool, stale.map(|cached| cached.pool))
+    }
+
+    fn remove(&mut self, config_id: i64) -> Option<P> {
+        self.pools.remove(&config_id).map(|cached| cached.pool)
+    }
+
+    /// 移除空闲超过 ttl 的连接池
+    fn take_idle(&mut self, ttl: Duration) -> Vec<P> {
+        let idle: Vec<i64> = self
+            .pools
+            .iter()
+            .filter(|(_, cached)| cached.last_used.elapsed() >= ttl)
+            .map(|(id, _)| *id)
+            .collect();
+        idle.into_iter().filter_map(|id| self.remove(id)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DbType;
+
+    fn config(id: i64, version: i64) -> DatabaseConfig {
+        DatabaseConfig {
+            id,
+            name: "rw".to_string(),
+            db_type: DbType::RisingWave,
+            host: "localhost".to_string(),
+            port: 4566,
+            username: "root".to_string(),
+            password: String::new(),
+            no_password: true,
+            database_name: Some("dev".to_string()),
+            proxy: None,
+            tls: None,
+            version,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_pool_cache_versions_and_eviction() {
+        let mut cache = PoolCache::default();
+        assert_eq!(cache.insert(&config(1, 1), "a"), ("a", None));
+        assert_eq!(cache.get(&config(1, 1)), Some("a"));
+        // 并发创建的同版本连接池被丢弃
+        assert_eq!(cache.insert(&config(1, 1), "b"), ("a", Some("b")));
+
+        // 配置更新后重建
+        assert_eq!(cache.get(&config(1, 2)), None);
+        assert_eq!(cache.insert(&config(1, 2), "c"), ("c", Some("a")));
+
+        // 临时配置不缓存
+        assert_eq!(cache.insert(&config(0, 0), "d"), ("d", None));
+        assert_eq!(cache.get(&config(0, 0)), None);
+
+        cache.insert(&config(2, 1), "e");
+        assert!(cache.take_idle(Duration::from_secs(60)).is_empty());
+        let mut idle = cache.take_idle(Duration::ZERO);
+        idle.sort();
+        assert_eq!(idle, vec!["c", "e"]);
+        assert_eq!(cache.remove(1), None);
+    }
+}
@@ -1,11 +1,17 @@
-use crate::models::{ConnectionTestResult, DatabaseConfig, DbType, TestConnectionRequest};
+use crate::models::{
+    ConnectionTestResult, CreateConnectionRequest, DatabaseConfig, DbType, TestConnectionRequest,
+};
+use crate::utils::endpoint;
+use crate::utils::proxy::{self, ProxyConfig};
 use crate::utils::error::{AppError, Result};
+use crate::utils::tls;
 use sqlx::{
     mysql::{MySqlConnectOptions, MySqlSslMode},
     postgres::{PgConnectOptions, PgPoolOptions, PgSslMode},
     Connection, MySqlConnection,
 };
 use mysql_async::prelude::*;
+use std::time::Duration;
 
 /// 连接服务
 pub struct ConnectionService;
@@ -20,7 +26,11 @@ impl ConnectionService {
         }
 
         tracing::info!("Testing MySQL connection to {}:{}", req.host, req.port);
-        let opts = Self::build_mysql_options(req);
+        let req = &match Self::prepare_request(req).await {
+            Ok(req) => req,
+            Err(e) => return Ok(ConnectionTestResult::failure(e.to_string())),
+        };
+        let opts = Self::build_mysql_options_from_config(&Self::request_config(req));
 
         match MySqlConnection::connect_with(&opts).await {
             Ok(mut conn) => {
@@ -55,7 +65,11 @@ impl ConnectionService {
         }
 
         tracing::info!("Testing RisingWave connection to {}:{}", req.host, req.port);
-        let opts = Self::build_postgres_options(req);
+        let req = &match Self::prepare_request(req).await {
+            Ok(req) => req,
+            Err(e) => return Ok(ConnectionTestResult::failure(e.to_string())),
+        };
+        let opts = Self::build_postgres_options_from_config(&Self::request_config(req));
 
         match PgPoolOptions::new()
             .max_connections(1)
@@ -85,28 +99,23 @@ impl ConnectionService {
         }
     }
 
-    /// 测试 StarRocks 连接 (使用 MySQL 协议)
+    /// 测试 StarRocks / Doris 连接 (使用 MySQL 协议)
     pub async fn test_starrocks(req: &TestConnectionRequest) -> Result<ConnectionTestResult> {
-        if req.db_type != DbType::StarRocks {
+        if !req.db_type.is_sink_target() {
             return Err(AppError::InvalidInput(
-                "Expected StarRocks connection type".to_string(),
+                "Expected StarRocks or Doris connection type".to_string(),
             ));
         }
 
         tracing::info!("Testing StarRocks connection to {}:{}", req.host, req.port);
-        // 使用 OptsBuilder 并禁用 socket 连接（StarRocks 不支持 @@socket 变量）
-        let mut opts_builder = mysql_async::OptsBuilder::default()
-            .ip_or_hostname(&req.host)
-            .tcp_port(req.port)
-            .user(Some(&req.username))
-            .pass(Some(&req.password))
-            .prefer_socket(false);  // 关键：禁用 socket，只使用 TCP
-
-        if let Some(db) = &req.database_name {
-            opts_builder = opts_builder.db_name(Some(db));
-        }
-
-        let opts = mysql_async::Opts::from(opts_builder);
+        let req = &match Self::prepare_request(req).await {
+            Ok(req) => req,
+            Err(e) => return Ok(ConnectionTestResult::failure(e.to_string())),
+        };
+        let opts = match Self::build_starrocks_opts_from_config(&Self::request_config(req)) {
+            Ok(opts) => opts,
+            Err(e) => return Ok(ConnectionTestResult::failure(e.to_string())),
+        };
 
         match mysql_async::Conn::new(opts).await {
             Ok(mut conn) => {
@@ -138,51 +147,158 @@ impl ConnectionService {
         }
     }
 
-    /// 构建 MySQL 连接选项（避免密码特殊字符问题）
-    fn build_mysql_options(req: &TestConnectionRequest) -> MySqlConnectOptions {
-        let mut opts = MySqlConnectOptions::new()
-            .host(&req.host)
-            .port(req.port)
-            .username(&req.username)
-            .password(&req.password);
-
-        // StarRocks 使用 Disabled SSL，普通 MySQL 使用 Preferred
-        if req.db_type == DbType::StarRocks {
-            opts = opts.ssl_mode(MySqlSslMode::Disabled);
-        } else {
-            opts = opts.ssl_mode(MySqlSslMode::Preferred);
+    /// 解析配置中的主机（SRV 名称解析为实际主机和端口），连接和生成 DDL 前调用
+    pub async fn resolve_config(config: DatabaseConfig) -> Result<DatabaseConfig> {
+        let (host, port) = endpoint::resolve(&config.host, config.port).await?;
+        Ok(DatabaseConfig {
+            host,
+            port,
+            ..config
+        })
+    }
+
+    /// 配置了代理时，返回经本地隧道连接的配置（主机和端口替换为隧道地址）
+    /// 只用于本服务直接建立的连接，生成给 RisingWave 的 DDL 仍使用原始地址
+    pub async fn via_proxy(config: &DatabaseConfig) -> Result<DatabaseConfig> {
+        let (host, port) =
+            Self::proxied_endpoint(&config.host, config.port, config.proxy.as_deref()).await?;
+        Ok(DatabaseConfig {
+            host,
+            port,
+            ..config.clone()
+        })
+    }
+
+    /// 连接 StarRocks / Doris，配置了代理时经本地隧道连接
+    pub async fn connect_starrocks(config: &DatabaseConfig) -> Result<mysql_async::Conn> {
+        let opts = Self::build_starrocks_opts_from_config(&Self::via_proxy(config).await?)?;
+        mysql_async::Conn::new(opts)
+            .await
+            .map_err(|e| AppError::Connection(format!("StarRocks connection failed: {}", e)))
+    }
+
+    /// 保存 RisingWave 连接前检查数据库（未指定时为 rw.default_database）是否存在
+    /// 只在确定数据库不存在时返回错误，无法连接等其他错误只记录警告，不阻止保存
+    pub async fn ensure_rw_database(req: &CreateConnectionRequest) -> Result<()> {
+        if req.db_type != DbType::RisingWave {
+            return Ok(());
         }
 
-        if let Some(db) = &req.database_name {
-            opts = opts.database(db);
+        let req = Self::prepare_request(&TestConnectionRequest {
+            db_type: req.db_type.clone(),
+            host: req.host.clone(),
+            port: req.port,
+            username: req.username.clone(),
+            password: req.password.clone(),
+            no_password: req.no_password,
+            database_name: req.database_name.clone(),
+            proxy: req.proxy.clone(),
+            tls: req.tls.clone(),
+            config_id: None,
+        })
+        .await?;
+        let config = Self::request_config(&req);
+        let database = config.rw_database();
+
+        match PgPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_secs(10))
+            .connect_with(Self::build_postgres_options_from_config(&config))
+            .await
+        {
+            Ok(pool) => {
+                pool.close().await;
+                Ok(())
+            }
+            Err(sqlx::Error::Database(e))
+                if e.code().as_deref() == Some("3D000") || Self::is_missing_database(e.message()) =>
+            {
+                Err(AppError::Validation(format!(
+                    "RisingWave database {} does not exist",
+                    database
+                )))
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Cannot verify RisingWave database {} on {}:{}: {}",
+                    database,
+                    req.host,
+                    req.port,
+                    e
+                );
+                Ok(())
+            }
         }
+    }
 
-        opts
+    /// PostgreSQL 返回 `database "x" does not exist`，RisingWave 返回 `database not found: x`
+    fn is_missing_database(message: &str) -> bool {
+        let message = message.to_lowercase();
+        message.contains("database")
+            && (message.contains("does not exist") || message.contains("not found"))
+    }
+
+    /// 解析 SRV 名称、校验 TLS 配置并应用代理，测试连接前调用
+    async fn prepare_request(req: &TestConnectionRequest) -> Result<TestConnectionRequest> {
+        let tls = req.tls.clone().map(tls::normalize).transpose()?;
+        let (host, port) = endpoint::resolve(&req.host, req.port).await?;
+        let (host, port) = Self::proxied_endpoint(&host, port, req.proxy.as_deref()).await?;
+        Ok(TestConnectionRequest {
+            host,
+            port,
+            tls,
+            ..req.clone()
+        })
     }
 
-    /// 构建 PostgreSQL 连接选项（避免密码特殊字符问题）
-    fn build_postgres_options(req: &TestConnectionRequest) -> PgConnectOptions {
-        let database = req.database_name.as_deref().unwrap_or("dev");
-
-        PgConnectOptions::new()
-            .host(&req.host)
-            .port(req.port)
-            .username(&req.username)
-            .password(&req.password)
-            .database(database)
-            .ssl_mode(PgSslMode::Prefer)
+    /// 把测试请求转换为临时配置，与已保存的配置使用相同的连接选项
+    fn request_config(req: &TestConnectionRequest) -> DatabaseConfig {
+        let now = chrono::Utc::now();
+        DatabaseConfig {
+            id: 0,
+            name: String::new(),
+            db_type: req.db_type.clone(),
+            host: req.host.clone(),
+            port: req.port,
+            username: req.username.clone(),
+            password: req.password.clone(),
+            no_password: req.no_password,
+            database_name: req.database_name.clone(),
+            proxy: req.proxy.clone(),
+            tls: req.tls.clone(),
+            version: 0,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    async fn proxied_endpoint(
+        host: &str,
+        port: u16,
+        proxy: Option<&str>,
+    ) -> Result<(String, u16)> {
+        match ProxyConfig::effective(proxy)? {
+            Some(proxy) => {
+                let addr =
+                    proxy::local_tunnel(&proxy, &endpoint::driver_host(host), port).await?;
+                Ok((addr.ip().to_string(), addr.port()))
+            }
+            None => Ok((host.to_string(), port)),
+        }
     }
 
-    /// 从 DatabaseConfig 构建 MySQL 连接选项
+    /// 从 DatabaseConfig 构建 MySQL 连接选项（避免密码特殊字符问题）
     pub fn build_mysql_options_from_config(config: &DatabaseConfig) -> MySqlConnectOptions {
         let mut opts = MySqlConnectOptions::new()
-            .host(&config.host)
+            .host(&endpoint::driver_host(&config.host))
             .port(config.port)
-            .username(&config.username)
-            .password(&config.password);
+            .username(&config.username);
+        if !config.no_password {
+            opts = opts.password(&config.password);
+        }
 
         // 根据数据库类型设置 SSL 模式和字符集
-        if config.db_type == DbType::StarRocks {
+        if config.db_type.is_sink_target() {
             opts = opts
                 .ssl_mode(MySqlSslMode::Disabled)
                 .charset("utf8mb4")
@@ -191,6 +307,23 @@ impl ConnectionService {
             opts = opts.ssl_mode(MySqlSslMode::Preferred);
         }
 
+        // 配置了 TLS 时强制加密，提供 CA 时校验服务端证书
+        if let Some(tls) = &config.tls {
+            opts = opts.ssl_mode(match (&tls.ca_cert, tls.verify_hostname) {
+                (Some(_), true) => MySqlSslMode::VerifyIdentity,
+                (Some(_), false) => MySqlSslMode::VerifyCa,
+                (None, _) => MySqlSslMode::Required,
+            });
+            if let Some(ca) = &tls.ca_cert {
+                opts = opts.ssl_ca_from_pem(ca.clone().into_bytes());
+            }
+            if let (Some(cert), Some(key)) = (&tls.client_cert, &tls.client_key) {
+                opts = opts
+                    .ssl_client_cert_from_pem(cert.as_bytes())
+                    .ssl_client_key_from_pem(key.as_bytes());
+            }
+        }
+
         if let Some(db) = &config.database_name {
             opts = opts.database(db);
         }
@@ -198,16 +331,87 @@ impl ConnectionService {
         opts
     }
 
-    /// 从 DatabaseConfig 构建 PostgreSQL 连接选项
+    /// 从 DatabaseConfig 构建 StarRocks 连接选项（mysql_async，禁用 socket）
+    pub fn build_starrocks_opts_from_config(config: &DatabaseConfig) -> Result<mysql_async::Opts> {
+        // StarRocks 不支持 @@socket 变量，只使用 TCP
+        let mut opts_builder = mysql_async::OptsBuilder::default()
+            .ip_or_hostname(endpoint::driver_host(&config.host))
+            .tcp_port(config.port)
+            .user(Some(&config.username))
+            .pass((!config.no_password).then_some(&config.password))
+            .prefer_socket(false);
+
+        if let Some(db) = &config.database_name {
+            opts_builder = opts_builder.db_name(Some(db));
+        }
+
+        if let Some(tls) = &config.tls {
+            let mut ssl_opts = mysql_async::SslOpts::default()
+                .with_danger_accept_invalid_certs(tls.ca_cert.is_none())
+                .with_danger_skip_domain_validation(!tls.verify_hostname);
+            if let Some(ca) = &tls.ca_cert {
+                ssl_opts = ssl_opts.with_root_certs(vec![ca.clone().into_bytes().into()]);
+            }
+            if let (Some(cert), Some(key)) = (&tls.client_cert, &tls.client_key) {
+                let identity = tls::client_identity(cert, key)?;
+                ssl_opts = ssl_opts.with_client_identity(Some(
+                    mysql_async::ClientIdentity::new(identity.into()).with_password(""),
+                ));
+            }
+            opts_builder = opts_builder.ssl_opts(Some(ssl_opts));
+        }
+
+        Ok(mysql_async::Opts::from(opts_builder))
+    }
+
+    /// 从 DatabaseConfig 构建 PostgreSQL 连接选项（避免密码特殊字符问题）
     pub fn build_postgres_options_from_config(config: &DatabaseConfig) -> PgConnectOptions {
-        let database = config.database_name.as_deref().unwrap_or("dev");
+        let database = config.rw_database();
 
-        PgConnectOptions::new()
-            .host(&config.host)
+        let mut opts = PgConnectOptions::new()
+            .host(&endpoint::driver_host(&config.host))
             .port(config.port)
             .username(&config.username)
-            .password(&config.password)
-            .database(database)
-            .ssl_mode(PgSslMode::Prefer)
+            .database(&database)
+            .ssl_mode(PgSslMode::Prefer);
+        if !config.no_password {
+            opts = opts.password(&config.password);
+        }
+
+        if let Some(tls) = &config.tls {
+            opts = opts.ssl_mode(match (&tls.ca_cert, tls.verify_hostname) {
+                (Some(_), true) => PgSslMode::VerifyFull,
+                (Some(_), false) => PgSslMode::VerifyCa,
+                (None, _) => PgSslMode::Require,
+            });
+            if let Some(ca) = &tls.ca_cert {
+                opts = opts.ssl_root_cert_from_pem(ca.clone().into_bytes());
+            }
+            if let (Some(cert), Some(key)) = (&tls.client_cert, &tls.client_key) {
+                opts = opts
+                    .ssl_client_cert_from_pem(cert.as_bytes())
+                    .ssl_client_key_from_pem(key.as_bytes());
+            }
+        }
+
+        opts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_missing_database() {
+        assert!(ConnectionService::is_missing_database(
+            "database \"analytics\" does not exist"
+        ));
+        assert!(ConnectionService::is_missing_database(
+            "Catalog error: database not found: analytics"
+        ));
+        assert!(!ConnectionService::is_missing_database(
+            "password authentication failed for user \"root\""
+        ));
     }
 }
@@ -0,0 +1,346 @@
+use crate::models::{DatabaseConfig, RetryPolicy};
+use crate::services::{
+    AuditService, ConnectionPoolManager, ConnectionService, Shutdown, TaskLogger,
+};
+use crate::utils::error::{AppError, Result};
+use crate::utils::fault::{self, DdlTarget};
+use mysql_async::prelude::*;
+use sqlx::PgPool;
+use std::time::Instant;
+
+/// 同步任务的 DDL 执行器
+/// 对 RisingWave / StarRocks 执行语句，遇到瞬时错误按重试策略退避重试，StarRocks 连接断开时自动重连
+pub struct DdlExecutor<'a> {
+    logger: &'a TaskLogger<'a>,
+    policy: RetryPolicy,
+    rw_pool: PgPool,
+    /// 只连接 RisingWave 时为空
+    sr_opts: Option<mysql_async::Opts>,
+    sr_conn: Option<mysql_async::Conn>,
+}
+
+impl<'a> DdlExecutor<'a> {
+    /// 连接 RisingWave 和 StarRocks
+    pub async fn connect(
+        logger: &'a TaskLogger<'a>,
+        policy: RetryPolicy,
+        rw_config: &DatabaseConfig,
+        sr_config: &DatabaseConfig,
+    ) -> Result<Self> {
+        let mut executor = Self::connect_rw(logger, policy, rw_config).await?;
+        let sr_config = ConnectionService::via_proxy(sr_config).await?;
+        executor.sr_opts = Some(ConnectionService::build_starrocks_opts_from_config(&sr_config)?);
+
+        logger.info("Connecting to StarRocks...").await?;
+        executor
+            .sr("connect to StarRocks", "SELECT 1", false)
+            .await?;
+
+        Ok(executor)
+    }
+
+    /// 只连接 RisingWave，用于不写入 StarRocks 的任务
+    pub async fn connect_rw(
+        logger: &'a TaskLogger<'a>,
+        policy: RetryPolicy,
+        rw_config: &DatabaseConfig,
+    ) -> Result<Self> {
+        logger.info("Connecting to RisingWave...").await?;
+        let executor = Self {
+            logger,
+            policy,
+            rw_pool: ConnectionPoolManager::postgres(rw_config).await?,
+            sr_opts: None,
+            sr_conn: None,
+        };
+        executor
+            .rw("connect to RisingWave", "SELECT 1", false)
+            .await?;
+        Ok(executor)
+    }
+
+    pub fn rw_pool(&self) -> &PgPool {
+        &self.rw_pool
+    }
+
+    /// 当前 StarRocks 连接，断开后重新连接
+    pub async fn sr_conn(&mut self) -> Result<&mut mysql_async::Conn> {
+        if self.sr_conn.is_none() {
+            let opts = self.sr_opts.clone().ok_or_else(|| {
+                AppError::Config("This task is not connected to StarRocks".to_string())
+            })?;
+            let conn = mysql_async::Conn::new(opts)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to connect to StarRocks: {}", e);
+                    AppError::Connection(format!("StarRocks connection failed: {}", e))
+                })?;
+            self.sr_conn = Some(conn);
+        }
+        Ok(self.sr_conn.as_mut().expect("StarRocks connection"))
+    }
+
+    /// 在 RisingWave 上执行语句，log_ddl 为 true 时在 debug 日志中记录语句
+    pub async fn rw(&self, action: &str, sql: &str, log_ddl: bool) -> Result<()> {
+        let mut attempt = 1;
+        loop {
+            let guard = Shutdown::ddl_guard()?;
+            let started = Instant::now();
+            let result = match fault::before_ddl(DdlTarget::RisingWave).await {
+                Some(fault) => Err(sqlx::Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::ConnectionReset,
+                    fault,
+                ))),
+                None => sqlx::query(sql).execute(&self.rw_pool).await,
+            };
+            drop(guard);
+            let error = match result {
+                Ok(_) => {
+                    if log_ddl {
+                        self.logger.ddl("RisingWave", action, sql, started).await?;
+                    }
+                    AuditService::record_task_ddl(self.logger, "RisingWave", sql, None).await;
+                    return Ok(());
+                }
+                Err(e) => e,
+            };
+
+            tracing::error!("Failed to {}: {}", action, error);
+            if !Self::is_transient_sqlx(&error)
+                || !self.wait_retry(action, attempt, &error.to_string()).await?
+            {
+                AuditService::record_task_ddl(
+                    self.logger,
+                    "RisingWave",
+                    sql,
+                    Some(&error.to_string()),
+                )
+                .await;
+                return Err(error.into());
+            }
+            attempt += 1;
+        }
+    }
+
+    /// 在 StarRocks 上执行语句
+    pub async fn sr(&mut self, action: &str, sql: &str, log_ddl: bool) -> Result<()> {
+        let mut attempt = 1;
+        loop {
+            let guard = Shutdown::ddl_guard()?;
+            let started = Instant::now();
+            let result = match fault::before_ddl(DdlTarget::StarRocks).await {
+                Some(fault) => Err((
+                    AppError::Unknown(format!("Failed to {}: {}", action, fault)),
+                    true,
+                )),
+                None => match self.sr_conn().await {
+                    Ok(conn) => conn.query_drop(sql).await.map_err(|e| {
+                        let transient = Self::is_transient_mysql(&e);
+                        (
+                            AppError::Unknown(format!("Failed to {}: {}", action, e)),
+                            transient,
+                        )
+                    }),
+                    Err(e) => Err((e, true)),
+                },
+            };
+            drop(guard);
+
+            let (error, transient) = match result {
+                Ok(()) => {
+                    if log_ddl {
+                        self.logger.ddl("StarRocks", action, sql, started).await?;
+                    }
+                    AuditService::record_task_ddl(self.logger, "StarRocks", sql, None).await;
+                    return Ok(());
+                }
+                Err(e) => e,
+            };
+
+            tracing::error!("{}", error);
+            if transient {
+                // 连接可能已经失效，下次重试时重新连接
+                self.sr_conn = None;
+            }
+            if !transient || !self.wait_retry(action, attempt, &error.to_string()).await? {
+                AuditService::record_task_ddl(
+                    self.logger,
+                    "StarRocks",
+                    sql,
+                    Some(&error.to_string()),
+                )
+                .await;
+                return Err(error);
+            }
+            attempt += 1;
+        }
+    }
+
+    /// 在 StarRocks 上查询第一行
+    pub async fn sr_query_first<T: FromRow + Send + 'static>(
+        &mut self,
+        action: &str,
+        sql: &str,
+    ) -> Result<Option<T>> {
+        let mut attempt = 1;
+        loop {
+            let result = match self.sr_conn().await {
+                Ok(conn) => conn.query_first(sql).await.map_err(|e| {
+                    let transient = Self::is_transient_mysql(&e);
+                    (
+                        AppError::Unknown(format!("Failed to {}: {}", action, e)),
+                        transient,
+                    )
+                }),
+                Err(e) => Err((e, true)),
+            };
+
+            let (error, transient) = match result {
+                Ok(row) => return Ok(row),
+                Err(e) => e,
+            };
+
+            tracing::error!("{}", error);
+            if !transient {
+                return Err(error);
+            }
+            self.sr_conn = None;
+            if !self.wait_retry(action, attempt, &error.to_string()).await? {
+                return Err(error);
+            }
+            attempt += 1;
+        }
+    }
+
+    /// 在 StarRocks 上查询所有行
+    pub async fn sr_query<T: FromRow + Send + 'static>(
+        &mut self,
+        action: &str,
+        sql: &str,
+    ) -> Result<Vec<T>> {
+        let mut attempt = 1;
+        loop {
+            let result = match self.sr_conn().await {
+                Ok(conn) => conn.query(sql).await.map_err(|e| {
+                    let transient = Self::is_transient_mysql(&e);
+                    (
+                        AppError::Unknown(format!("Failed to {}: {}", action, e)),
+                        transient,
+                    )
+                }),
+                Err(e) => Err((e, true)),
+            };
+
+            let (error, transient) = match result {
+                Ok(rows) => return Ok(rows),
+                Err(e) => e,
+            };
+
+            tracing::error!("{}", error);
+            if !transient {
+                return Err(error);
+            }
+            self.sr_conn = None;
+            if !self.wait_retry(action, attempt, &error.to_string()).await? {
+                return Err(error);
+            }
+            attempt += 1;
+        }
+    }
+
+    /// 关闭 StarRocks 连接，RisingWave 连接池由 ConnectionPoolManager 管理
+    pub async fn close(self) {
+        if let Some(conn) = self.sr_conn {
+            let _ = conn.disconnect().await;
+        }
+    }
+
+    /// 还有重试机会时记录日志并等待，返回 false 表示不再重试
+    async fn wait_retry(&self, action: &str, attempt: u32, error: &str) -> Result<bool> {
+        if attempt >= self.policy.max_attempts {
+            return Ok(false);
+        }
+
+        let delay = self.policy.backoff(attempt);
+        self.logger
+            .warn(&format!(
+                "Failed to {} (attempt {}/{}): {}. Retrying in {} ms...",
+                action,
+                attempt,
+                self.policy.max_attempts,
+                error,
+                delay.as_millis()
+            ))
+            .await?;
+        tokio::time::sleep(delay).await;
+
+        Ok(true)
+    }
+
+    fn is_transient_sqlx(error: &sqlx::Error) -> bool {
+        match error {
+            sqlx::Error::Io(_)
+            | sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::WorkerCrashed
+            | sqlx::Error::Tls(_) => true,
+            other => Self::is_transient_message(&other.to_string()),
+        }
+    }
+
+    fn is_transient_mysql(error: &mysql_async::Error) -> bool {
+        match error {
+            mysql_async::Error::Io(_) => true,
+            other => Self::is_transient_message(&other.to_string()),
+        }
+    }
+
+    /// 根据错误信息判断是否为瞬时错误（网络抖动、超时、服务暂不可用）
+    fn is_transient_message(message: &str) -> bool {
+        const PATTERNS: [&str; 10] = [
+            "timeout",
+            "timed out",
+            "connection reset",
+            "connection refused",
+            "connection closed",
+            "broken pipe",
+            "unavailable",
+            "too many connections",
+            "try again",
+            "lost connection",
+        ];
+        let message = message.to_lowercase();
+        PATTERNS.iter().any(|p| message.contains(p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_transient_message() {
+        assert!(DdlExecutor::is_transient_message(
+            "error communicating with database: Connection reset by peer"
+        ));
+        assert!(DdlExecutor::is_transient_message(
+            "Service Unavailable: meta node is not ready"
+        ));
+        assert!(!DdlExecutor::is_transient_message(
+            "table \"orders\" already exists"
+        ));
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 3000,
+        };
+        assert_eq!(policy.backoff(1).as_millis(), 500);
+        assert_eq!(policy.backoff(2).as_millis(), 1000);
+        assert_eq!(policy.backoff(3).as_millis(), 2000);
+        assert_eq!(policy.backoff(4).as_millis(), 3000);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::db::{ConfigRepository, TaskRepository};
+use crate::models::{
+    CreateConnectionRequest, DbType, SyncOptions, SyncTask, TaskFilter, TaskStatus,
+};
+use crate::utils::error::{AppError, Result};
+use sqlx::MySqlPool;
+
+/// 演示模式的示例连接名称前缀，已存在时不重复写入
+const DEMO_PREFIX: &str = "demo-";
+
+/// 演示模式服务：写入示例连接和任务
+pub struct DemoService;
+
+impl DemoService {
+    /// 写入示例数据（只在第一次启动时写入）
+    /// 示例数据只能写入空的元数据库，避免把示例连接和任务混入正在使用的库
+    pub async fn seed(pool: &MySqlPool) -> Result<()> {
+        let config_repo = ConfigRepository::new(pool);
+        let configs = config_repo.find_all().await?;
+        if configs.iter().any(|c| c.name.starts_with(DEMO_PREFIX)) {
+            tracing::info!("Demo data already present, skipping seed");
+            return Ok(());
+        }
+        let tasks = TaskRepository::new(pool)
+            .count_tasks(&TaskFilter::default())
+            .await?;
+        if !configs.is_empty() || tasks > 0 {
+            return Err(AppError::Config(format!(
+                "--demo requires an empty metadata database, DATABASE_URL already has {} connections and {} tasks",
+                configs.len(),
+                tasks
+            )));
+        }
+
+        let connection =
+            |name: &str, db_type: DbType, host: &str, port: u16| CreateConnectionRequest {
+                name: format!("{}{}", DEMO_PREFIX, name),
+                db_type,
+                host: host.to_string(),
+                port,
+                username: "demo".to_string(),
+                password: "demo".to_string(),
+                no_password: false,
+                database_name: None,
+                proxy: None,
+                tls: None,
+                version: None,
+            };
+        let mysql_id = config_repo
+            .save(connection("mysql", DbType::MySQL, "mysql.demo.local", 3306))
+            .await?;
+        let rw_id = config_repo
+            .save(connection(
+                "risingwave",
+                DbType::RisingWave,
+                "risingwave.demo.local",
+                4566,
+            ))
+            .await?;
+        let sr_id = config_repo
+            .save(connection(
+                "starrocks",
+                DbType::StarRocks,
+                "starrocks.demo.local",
+                9030,
+            ))
+            .await?;
+
+        let task_repo = TaskRepository::new(pool);
+        let samples = [
+            ("shop", "orders", TaskStatus::Completed, None),
+            ("shop", "customers", TaskStatus::Completed, None),
+            (
+                "shop",
+                "payments",
+                TaskStatus::Failed,
+                Some("StarRocks connection failed: Connection refused (os error 111)"),
+            ),
+        ];
+        for (database, table, status, error) in samples {
+            let task = SyncTask {
+                id: 0,
+                task_name: format!("Sync {}.{}", database, table),
+                mysql_config_id: mysql_id,
+                rw_config_id: rw_id,
+                sr_config_id: sr_id,
+                mysql_database: database.to_string(),
+                mysql_table: table.to_string(),
+                target_database: format!("ods_{}", database),
+                target_table: table.to_string(),
+                status: TaskStatus::Running,
+                started_at: chrono::Utc::now(),
+                completed_at: None,
+                error_message: None,
+                options: serde_json::to_string(&SyncOptions::default())?,
+                preflight_result: None,
+                requests: None,
+                sink_requests: None,
+            };
+            let task_id = task_repo
+                .create_with_log(&task, "Starting batch sync for 1 tables")
+                .await?;
+
+            let (level, message) = match error {
+                Some(error) => ("error", format!("Batch sync failed: {}", error)),
+                None => (
+                    "info",
+                    format!(
+                        "Successfully synced {}.{} to ods_{}.{} (1/1)",
+                        database, table, database, table
+                    ),
+                ),
+            };
+            task_repo
+                .finish_with_log(task_id, status, error.map(str::to_string), level, &message)
+                .await?;
+        }
+
+        tracing::info!("Seeded demo connections and tasks");
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use crate::db::TaskRepository;
+use crate::models::{
+    ErrorCode, ErrorCodeStat, ErrorStats, ErrorStatsQuery, TaskFailure, TrendBucket,
+};
+use crate::utils::error::Result;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use sqlx::MySqlPool;
+use std::collections::HashMap;
+
+/// 错误信息样例的最大字符数
+const SAMPLE_MAX_CHARS: usize = 300;
+
+/// 按顺序匹配的关键字（小写），先匹配到的分类优先
+const ERROR_PATTERNS: &[(ErrorCode, &[&str])] = &[
+    (
+        ErrorCode::BinlogPermission,
+        &["replication slave", "replication client"],
+    ),
+    (
+        ErrorCode::BinlogConfig,
+        &[
+            "binlog_format",
+            "binlog_row_image",
+            "log_bin",
+            "binlog is not enabled",
+            "binary log",
+        ],
+    ),
+    (
+        ErrorCode::PermissionDenied,
+        &["privilege", "command denied", "permission denied"],
+    ),
+    (
+        ErrorCode::Authentication,
+        &[
+            "access denied",
+            "authentication failed",
+            "password authentication",
+            "invalid password",
+        ],
+    ),
+    (ErrorCode::Timeout, &["timed out", "timeout"]),
+    (
+        ErrorCode::Connection,
+        &[
+            "connection refused",
+            "connection reset",
+            "could not connect",
+            "failed to connect",
+            "no route to host",
+            "name or service not known",
+            "broken pipe",
+            "connection error",
+        ],
+    ),
+    (
+        ErrorCode::Capacity,
+        &[
+            "no space left",
+            "disk full",
+            "quota",
+            "out of memory",
+            "capacity",
+        ],
+    ),
+    (
+        ErrorCode::ObjectNotFound,
+        &[
+            "doesn't exist",
+            "does not exist",
+            "unknown table",
+            "unknown database",
+            "not found",
+        ],
+    ),
+    (ErrorCode::ObjectExists, &["already exists"]),
+    (ErrorCode::PrimaryKey, &["primary key"]),
+    (
+        ErrorCode::UnsupportedType,
+        &["unsupported", "type mapping error"],
+    ),
+    (
+        ErrorCode::SqlSyntax,
+        &["syntax", "parser error", "sql generation error"],
+    ),
+];
+
+/// 按错误分类汇总历史失败任务，用于发现某类错误的集中出现
+pub struct ErrorStatsService;
+
+impl ErrorStatsService {
+    /// 识别错误信息的分类，没有匹配的关键字时为 Unknown
+    pub fn classify(error_message: Option<&str>) -> ErrorCode {
+        let Some(message) = error_message.map(str::to_lowercase) else {
+            return ErrorCode::Unknown;
+        };
+        ERROR_PATTERNS
+            .iter()
+            .find(|(_, keywords)| keywords.iter().any(|k| message.contains(k)))
+            .map(|(code, _)| *code)
+            .unwrap_or(ErrorCode::Unknown)
+    }
+
+    /// 最近 days 天（默认 30，最多 366）的失败任务按分类统计
+    pub async fn summarize(app_db: &MySqlPool, query: &ErrorStatsQuery) -> Result<ErrorStats> {
+        let days = query.days.unwrap_or(30).clamp(1, 366);
+        let today = Utc::now().date_naive();
+        let since = today - Duration::days(days - 1);
+        let failures = TaskRepository::new(app_db)
+            .find_failures_since(since.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+            .await?;
+        Ok(Self::aggregate(&failures, since, today, query.bucket))
+    }
+
+    /// failures 按失败时间排序
+    fn aggregate(
+        failures: &[TaskFailure],
+        since: NaiveDate,
+        until: NaiveDate,
+        bucket: TrendBucket,
+    ) -> ErrorStats {
+        let step = match bucket {
+            TrendBucket::Day => 1,
+            TrendBucket::Week => 7,
+        };
+        let first = Self::bucket_start(since, bucket);
+        let buckets: Vec<NaiveDate> = (0..)
+            .map(|i| first + Duration::days(i * step))
+            .take_while(|date| *date <= until)
+            .collect();
+
+        let mut stats: HashMap<ErrorCode, ErrorCodeStat> = HashMap::new();
+        for failure in failures {
+            let code = Self::classify(failure.error_message.as_deref());
+            let stat = stats.entry(code).or_insert_with(|| ErrorCodeStat {
+                code,
+                description: code.description().to_string(),
+                count: 0,
+                trend: vec![0; buckets.len()],
+                last_seen: failure.failed_at,
+                last_task_id: failure.id,
+                sample: None,
+            });
+            stat.count += 1;
+            let index = (Self::bucket_start(failure.failed_at.date_naive(), bucket) - first)
+                .num_days()
+                / step;
+            if let Some(slot) = usize::try_from(index)
+                .ok()
+                .and_then(|i| stat.trend.get_mut(i))
+            {
+                *slot += 1;
+            }
+            if failure.failed_at >= stat.last_seen {
+                stat.last_seen = failure.failed_at;
+                stat.last_task_id = failure.id;
+                stat.sample = failure
+                    .error_message
+                    .as_ref()
+                    .map(|m| m.chars().take(SAMPLE_MAX_CHARS).collect());
+            }
+        }
+
+        let mut codes: Vec<ErrorCodeStat> = stats.into_values().collect();
+        codes.sort_by(|a, b| b.count.cmp(&a.count).then(a.code.cmp(&b.code)));
+        ErrorStats {
+            since,
+            bucket,
+            buckets,
+            total: failures.len() as i64,
+            codes,
+        }
+    }
+
+    fn bucket_start(date: NaiveDate, bucket: TrendBucket) -> NaiveDate {
+        match bucket {
+            TrendBucket::Day => date,
+            TrendBucket::Week => {
+                date - Duration::days(date.weekday().num_days_from_monday() as i64)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn failure(id: i64, day: u32, message: &str) -> TaskFailure {
+        TaskFailure {
+            id,
+            failed_at: Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+            error_message: Some(message.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_classify_and_aggregate() {
+        assert_eq!(
+            ErrorStatsService::classify(Some(
+                "Access denied; you need (at least one of) the REPLICATION SLAVE privilege(s)"
+            )),
+            ErrorCode::BinlogPermission
+        );
+        assert_eq!(
+            ErrorStatsService::classify(Some(
+                "Connection error: Access denied for user 'cdc'@'10.0.0.1' (using password: YES)"
+            )),
+            ErrorCode::Authentication
+        );
+        assert_eq!(
+            ErrorStatsService::classify(Some("Connection error: connection timed out")),
+            ErrorCode::Timeout
+        );
+        assert_eq!(ErrorStatsService::classify(None), ErrorCode::Unknown);
+
+        // 2026-03-02 是周一
+        let failures = vec![
+            failure(1, 2, "Unknown table 'shop.orders'"),
+            failure(2, 9, "REPLICATION CLIENT privilege(s) required"),
+            failure(3, 10, "REPLICATION SLAVE privilege(s) required"),
+            failure(4, 11, "something else"),
+        ];
+        let since = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let until = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap();
+
+        let weekly = ErrorStatsService::aggregate(&failures, since, until, TrendBucket::Week);
+        assert_eq!(weekly.buckets.len(), 2);
+        assert_eq!(weekly.total, 4);
+        let binlog = &weekly.codes[0];
+        assert_eq!(binlog.code, ErrorCode::BinlogPermission);
+        assert_eq!(binlog.trend, vec![0, 2]);
+        assert_eq!(binlog.last_task_id, 3);
+        assert_eq!(weekly.codes.len(), 3);
+
+        let daily = ErrorStatsService::aggregate(&failures, since, until, TrendBucket::Day);
+        assert_eq!(daily.buckets.len(), 8);
+        assert_eq!(daily.codes[0].trend[5..], [1, 1, 0]);
+    }
+}
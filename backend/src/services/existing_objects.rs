@@ -0,0 +1,233 @@
+use crate::generators::StarRocksDDLGenerator;
+use crate::models::{SyncRequest, TableSchema};
+use crate::services::{DdlExecutor, SinkMaintenanceService};
+use crate::utils::error::Result;
+use sqlx::{PgPool, Row};
+use std::collections::BTreeSet;
+
+/// 重跑任务前已存在对象的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectState {
+    Missing,
+    /// 已存在且与本次生成的定义一致
+    Matching,
+    /// 已存在但定义不同
+    Changed,
+}
+
+/// 检查 RisingWave Table、Sink 和目标表是否已按相同定义存在，用于跳过重复创建
+pub struct ExistingObjectService;
+
+impl ExistingObjectService {
+    /// RisingWave Table 是否从同一个 Source 的同一张上游表创建
+    pub async fn rw_table(
+        rw_pool: &PgPool,
+        request: &SyncRequest,
+        source_name: &str,
+    ) -> Result<ObjectState> {
+        let definition = Self::rw_definition(
+            rw_pool,
+            "rw_catalog.rw_tables",
+            &request.target_database,
+            &request.target_table,
+        )
+        .await?;
+        Ok(match definition {
+            None => ObjectState::Missing,
+            Some(definition) => Self::table_state(&definition, source_name, request),
+        })
+    }
+
+    /// Sink 是否读取同一张 RisingWave Table 且 WITH 属性与新生成的语句一致
+    pub async fn sink(
+        rw_pool: &PgPool,
+        request: &SyncRequest,
+        sink_ddl: &str,
+    ) -> Result<ObjectState> {
+        let definition = Self::rw_definition(
+            rw_pool,
+            "rw_catalog.rw_sinks",
+            &request.target_database,
+            &format!("{}_to_sr_sink", request.target_table),
+        )
+        .await?;
+        Ok(match definition {
+            None => ObjectState::Missing,
+            Some(definition) => Self::sink_state(&definition, sink_ddl, request),
+        })
+    }
+
+    /// 目标表的列是否与映射后的源表列一致（不区分顺序和大小写）
+    pub async fn target_table(
+        executor: &mut DdlExecutor<'_>,
+        request: &SyncRequest,
+        schema: &TableSchema,
+    ) -> Result<ObjectState> {
+        let sql = format!(
+            "SELECT COLUMN_NAME FROM information_schema.columns WHERE {}",
+            StarRocksDDLGenerator::table_filter(&request.target_database, &request.target_table)
+        );
+        let existing: Vec<String> = executor.sr_query("list target columns", &sql).await?;
+        Ok(Self::columns_state(&existing, &request.target_schema(schema)))
+    }
+
+    async fn rw_definition(
+        rw_pool: &PgPool,
+        catalog: &str,
+        schema: &str,
+        name: &str,
+    ) -> Result<Option<String>> {
+        let sql = format!(
+            "SELECT r.definition FROM {} r
+             JOIN rw_catalog.rw_schemas sch ON r.schema_id = sch.id
+             WHERE sch.name = $1 AND r.name = $2",
+            catalog
+        );
+        let row = sqlx::query(&sql)
+            .bind(schema)
+            .bind(name)
+            .fetch_optional(rw_pool)
+            .await?;
+        Ok(row.map(|row| row.get("definition")))
+    }
+
+    fn table_state(definition: &str, source_name: &str, request: &SyncRequest) -> ObjectState {
+        let definition = Self::normalize(definition);
+        let upstream = format!(
+            "table '{}.{}'",
+            request.mysql_database.to_lowercase(),
+            request.mysql_table.to_lowercase()
+        );
+        // RisingWave 保存的定义里 Source 可能带 schema 也可能不带
+        let source = Self::normalize(source_name);
+        let bare = source.rsplit('.').next().unwrap_or(&source).to_string();
+        let matches = [source, bare]
+            .iter()
+            .any(|name| definition.contains(&format!("from {} {}", name, upstream)));
+        if matches {
+            ObjectState::Matching
+        } else {
+            ObjectState::Changed
+        }
+    }
+
+    fn sink_state(definition: &str, sink_ddl: &str, request: &SyncRequest) -> ObjectState {
+        let rw_table = Self::normalize(&format!(
+            "{}.{}",
+            request.target_database, request.target_table
+        ));
+        let same_table = Self::normalize(definition).contains(&format!("from {}", rw_table));
+        if same_table && SinkMaintenanceService::diff_properties(definition, sink_ddl).is_empty() {
+            ObjectState::Matching
+        } else {
+            ObjectState::Changed
+        }
+    }
+
+    fn columns_state(existing: &[String], target: &TableSchema) -> ObjectState {
+        if existing.is_empty() {
+            return ObjectState::Missing;
+        }
+        let existing: BTreeSet<String> = existing.iter().map(|c| c.to_lowercase()).collect();
+        let expected: BTreeSet<String> =
+            target.columns.iter().map(|c| c.name.to_lowercase()).collect();
+        if existing == expected {
+            ObjectState::Matching
+        } else {
+            ObjectState::Changed
+        }
+    }
+
+    /// 转小写、去掉标识符引号并合并空白，便于比较定义
+    fn normalize(sql: &str) -> String {
+        sql.replace(['"', '`'], "")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, ColumnTypeSource, SyncOptions};
+
+    fn column(name: &str) -> Column {
+        Column {
+            name: name.to_string(),
+            data_type: "INT".to_string(),
+            is_nullable: false,
+            default_value: None,
+            comment: None,
+            character_maximum_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+        }
+    }
+
+    #[test]
+    fn test_existing_object_states() {
+        let request = SyncRequest {
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: "orders".to_string(),
+            target_database: "ods".to_string(),
+            target_table: "orders".to_string(),
+            options: SyncOptions::default(),
+            row_filter: None,
+            partition: None,
+            column_mapping: Default::default(),
+            nested_columns: Default::default(),
+            spatial_columns: Default::default(),
+        };
+
+        let definition = "CREATE TABLE orders (id INT, PRIMARY KEY (id)) FROM shop_source TABLE 'shop.orders'";
+        assert_eq!(
+            ExistingObjectService::table_state(definition, "\"ods\".shop_source", &request),
+            ObjectState::Matching
+        );
+        let other = "CREATE TABLE orders (id INT, PRIMARY KEY (id)) FROM shop_source TABLE 'shop.orders_v2'";
+        assert_eq!(
+            ExistingObjectService::table_state(other, "\"ods\".shop_source", &request),
+            ObjectState::Changed
+        );
+
+        let sink_ddl = r#"CREATE SINK IF NOT EXISTS "ods".orders_to_sr_sink FROM "ods".orders WITH (connector = 'starrocks', starrocks.table = 'orders');"#;
+        let sink = "CREATE SINK orders_to_sr_sink FROM ods.orders WITH (connector = 'starrocks', starrocks.table = 'orders')";
+        assert_eq!(
+            ExistingObjectService::sink_state(sink, sink_ddl, &request),
+            ObjectState::Matching
+        );
+        let drifted = "CREATE SINK orders_to_sr_sink FROM ods.orders WITH (connector = 'starrocks', starrocks.table = 'orders_old')";
+        assert_eq!(
+            ExistingObjectService::sink_state(drifted, sink_ddl, &request),
+            ObjectState::Changed
+        );
+
+        let schema = TableSchema {
+            database: "shop".to_string(),
+            table_name: "orders".to_string(),
+            columns: vec![column("id"), column("amount")],
+            primary_keys: vec!["id".to_string()],
+            indexes: vec![],
+            type_source: ColumnTypeSource::MySQL,
+            comment: None,
+        };
+        let existing = vec!["AMOUNT".to_string(), "id".to_string()];
+        assert_eq!(
+            ExistingObjectService::columns_state(&existing, &schema),
+            ObjectState::Matching
+        );
+        assert_eq!(
+            ExistingObjectService::columns_state(&existing[..1], &schema),
+            ObjectState::Changed
+        );
+        assert_eq!(
+            ExistingObjectService::columns_state(&[], &schema),
+            ObjectState::Missing
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::db::FeatureFlagRepository;
+use crate::models::{DEFAULT_WORKSPACE, Feature, FeatureFlag};
+use crate::utils::error::{AppError, Result};
+use sqlx::MySqlPool;
+
+/// 功能开关服务
+pub struct FeatureFlagService;
+
+impl FeatureFlagService {
+    /// 功能是否在工作区内开启，没有记录时视为关闭
+    pub async fn is_enabled(pool: &MySqlPool, workspace: &str, feature: Feature) -> Result<bool> {
+        let repo = FeatureFlagRepository::new(pool);
+        Ok(repo
+            .find(Self::workspace(Some(workspace))?, feature.as_str())
+            .await?
+            .unwrap_or(false))
+    }
+
+    /// 列出工作区内所有已知功能的开关状态
+    pub async fn list(pool: &MySqlPool, workspace: Option<&str>) -> Result<Vec<FeatureFlag>> {
+        let workspace = Self::workspace(workspace)?;
+        let stored = FeatureFlagRepository::new(pool)
+            .find_by_workspace(workspace)
+            .await?;
+
+        Ok(Feature::ALL
+            .into_iter()
+            .map(|feature| {
+                let row = stored.iter().find(|(name, _, _)| name == feature.as_str());
+                FeatureFlag {
+                    workspace: workspace.to_string(),
+                    name: feature.as_str().to_string(),
+                    enabled: row.is_some_and(|(_, enabled, _)| *enabled),
+                    description: Some(feature.description().to_string()),
+                    updated_at: row.map(|(_, _, updated_at)| *updated_at),
+                }
+            })
+            .collect())
+    }
+
+    /// 设置开关
+    pub async fn set(
+        pool: &MySqlPool,
+        workspace: Option<&str>,
+        name: &str,
+        enabled: bool,
+    ) -> Result<()> {
+        let feature = Feature::from_str(name)
+            .ok_or_else(|| AppError::NotFound(format!("Unknown feature flag: {}", name)))?;
+        let workspace = Self::workspace(workspace)?;
+
+        FeatureFlagRepository::new(pool)
+            .set(workspace, feature.as_str(), enabled)
+            .await?;
+        tracing::info!(
+            "Feature flag {} {} for workspace {}",
+            feature.as_str(),
+            if enabled { "enabled" } else { "disabled" },
+            workspace
+        );
+        Ok(())
+    }
+
+    /// 校验工作区名称，为空时使用默认工作区
+    fn workspace(workspace: Option<&str>) -> Result<&str> {
+        match workspace.map(str::trim).filter(|w| !w.is_empty()) {
+            None => Ok(DEFAULT_WORKSPACE),
+            Some(w)
+                if w.len() <= 64
+                    && w.chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') =>
+            {
+                Ok(w)
+            }
+            Some(w) => Err(AppError::Validation(format!("Invalid workspace: {}", w))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace() {
+        assert_eq!(FeatureFlagService::workspace(None).unwrap(), "default");
+        assert_eq!(FeatureFlagService::workspace(Some(" ")).unwrap(), "default");
+        assert_eq!(
+            FeatureFlagService::workspace(Some("team-a")).unwrap(),
+            "team-a"
+        );
+        assert!(FeatureFlagService::workspace(Some("a b")).is_err());
+    }
+
+    #[test]
+    fn test_feature_names() {
+        for feature in Feature::ALL {
+            assert_eq!(Feature::from_str(feature.as_str()), Some(feature));
+        }
+        assert_eq!(Feature::from_str("unknown"), None);
+    }
+}
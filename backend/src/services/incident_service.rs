@@ -0,0 +1,261 @@
+use crate::db::{AlertRepository, TaskRepository};
+use crate::generators::RisingWaveDDLGenerator;
+use crate::models::{
+    AlertPipelineMatch, AlertRecord, AlertSubject, PipelineDetail, SyncTask, TaskFilter,
+};
+use crate::services::StatusService;
+use crate::utils::error::Result;
+use sqlx::MySqlPool;
+use std::collections::{BTreeMap, HashSet};
+
+/// 匹配告警时扫描的最近任务数，与状态页一致
+const INCIDENT_TASK_WINDOW: i64 = 500;
+
+/// 管道详情和任务告警列表返回的最大告警数
+const INCIDENT_LIST_LIMIT: i64 = 100;
+
+/// 告警标签中的 RisingWave 对象名称，与 RisingWave 指标的标签一致
+const OBJECT_LABELS: [&str; 3] = ["sink_name", "source_name", "table_name"];
+
+/// 把 Alertmanager 告警关联到本服务管理的管道和任务
+pub struct IncidentService;
+
+impl IncidentService {
+    /// 按告警标签中的 sink / source / 表名匹配管道并写入关联，没有对象标签的告警直接跳过
+    pub async fn link_alerts(
+        pool: &MySqlPool,
+        alerts: &[(i64, BTreeMap<String, String>)],
+    ) -> Result<()> {
+        let alerts: Vec<_> = alerts
+            .iter()
+            .filter(|(_, labels)| OBJECT_LABELS.iter().any(|l| labels.contains_key(*l)))
+            .collect();
+        if alerts.is_empty() {
+            return Ok(());
+        }
+
+        let tasks = TaskRepository::new(pool)
+            .find_history(&TaskFilter::default(), INCIDENT_TASK_WINDOW, 0)
+            .await?;
+        let repo = AlertRepository::new(pool);
+        for (alert_id, labels) in alerts {
+            for matched in Self::match_pipelines(labels, &tasks) {
+                tracing::info!(
+                    "Linking alert {} to pipeline {} (task {}) via {}",
+                    alert_id,
+                    matched.target,
+                    matched.task_id,
+                    matched.object_name
+                );
+                repo.link(*alert_id, &matched).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 告警标签匹配到的管道，每个管道只关联最近一次涉及它的任务（tasks 按 started_at 倒序）
+    /// sink 命名为 `{target_table}_to_sr_sink`，source 为 `{mysql_database}_source`，
+    /// RisingWave 表与目标表同名，均位于目标库同名的 schema 中
+    pub fn match_pipelines(
+        labels: &BTreeMap<String, String>,
+        tasks: &[SyncTask],
+    ) -> Vec<AlertPipelineMatch> {
+        let objects: Vec<(&str, Option<&str>, &str)> = OBJECT_LABELS
+            .iter()
+            .filter_map(|label| {
+                let (schema, name) = Self::split_object_name(labels.get(*label)?);
+                Some((*label, schema, name))
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        let mut matches = Vec::new();
+        for task in tasks {
+            let Ok(requests) = task.sync_requests() else {
+                continue;
+            };
+            for request in requests {
+                let target = format!("{}.{}", request.target_database, request.target_table);
+                if seen.contains(&target) {
+                    continue;
+                }
+                let matched = objects.iter().find_map(|(label, schema, name)| {
+                    if schema.is_some_and(|s| s != request.target_database) {
+                        return None;
+                    }
+                    let expected = match *label {
+                        "sink_name" => format!("{}_to_sr_sink", request.target_table),
+                        "source_name" => format!("{}_source", request.mysql_database),
+                        _ => request.target_table.clone(),
+                    };
+                    (*name == expected).then(|| {
+                        RisingWaveDDLGenerator::qualified_name(&request.target_database, &expected)
+                    })
+                });
+                if let Some(object_name) = matched {
+                    seen.insert(target.clone());
+                    matches.push(AlertPipelineMatch {
+                        target,
+                        task_id: task.id,
+                        object_name,
+                    });
+                }
+            }
+        }
+
+        matches
+    }
+
+    /// 拆分对象名称中的 schema，例如 `"ods".orders_to_sr_sink` -> (Some("ods"), "orders_to_sr_sink")
+    fn split_object_name(value: &str) -> (Option<&str>, &str) {
+        fn unquote(s: &str) -> &str {
+            s.trim().trim_matches('"')
+        }
+        match value.rsplit_once('.') {
+            Some((schema, name)) => (Some(unquote(schema)), unquote(name)),
+            None => (None, unquote(value)),
+        }
+    }
+
+    /// 管道详情：状态页中的状态、最近一次任务和仍在触发的告警
+    pub async fn pipeline_detail(pool: &MySqlPool, target: &str) -> Result<PipelineDetail> {
+        let tasks = TaskRepository::new(pool)
+            .find_history(&TaskFilter::default(), INCIDENT_TASK_WINDOW, 0)
+            .await?;
+        let status = StatusService::summarize_tasks(&tasks)
+            .pipelines
+            .into_iter()
+            .find(|p| p.target == target);
+        let last_task_id = tasks
+            .iter()
+            .find(|task| {
+                task.sync_requests().is_ok_and(|requests| {
+                    requests
+                        .iter()
+                        .any(|r| format!("{}.{}", r.target_database, r.target_table) == target)
+                })
+            })
+            .map(|task| task.id);
+        let active_incidents = AlertRepository::new(pool)
+            .find_linked(AlertSubject::Pipeline, target, true, INCIDENT_LIST_LIMIT)
+            .await?;
+
+        Ok(PipelineDetail {
+            target: target.to_string(),
+            status,
+            last_task_id,
+            active_incidents,
+        })
+    }
+
+    /// 关联到任务的告警（包括已解决的）
+    pub async fn task_alerts(pool: &MySqlPool, task_id: i64) -> Result<Vec<AlertRecord>> {
+        AlertRepository::new(pool)
+            .find_linked(
+                AlertSubject::Task,
+                &task_id.to_string(),
+                false,
+                INCIDENT_LIST_LIMIT,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{SyncRequest, TaskStatus};
+
+    fn task(id: i64, mysql_table: &str, target_table: &str) -> SyncTask {
+        let request = SyncRequest {
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: mysql_table.to_string(),
+            target_database: "ods".to_string(),
+            target_table: target_table.to_string(),
+            options: Default::default(),
+            row_filter: None,
+            partition: None,
+            column_mapping: Default::default(),
+            nested_columns: Default::default(),
+            spatial_columns: Default::default(),
+        };
+        SyncTask {
+            id,
+            task_name: "t".to_string(),
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: mysql_table.to_string(),
+            target_database: "ods".to_string(),
+            target_table: target_table.to_string(),
+            status: TaskStatus::Completed,
+            started_at: chrono::Utc::now(),
+            completed_at: None,
+            error_message: None,
+            options: "{}".to_string(),
+            preflight_result: None,
+            requests: Some(serde_json::to_string(&vec![request]).unwrap()),
+            sink_requests: None,
+        }
+    }
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_match_pipelines() {
+        let tasks = vec![
+            task(3, "orders", "orders"),
+            task(2, "orders", "orders"),
+            task(1, "users", "users"),
+        ];
+
+        // sink 只匹配对应的管道，并关联最近一次任务
+        let matches = IncidentService::match_pipelines(
+            &labels(&[
+                ("alertname", "SinkErrors"),
+                ("sink_name", "orders_to_sr_sink"),
+            ]),
+            &tasks,
+        );
+        assert_eq!(
+            matches,
+            vec![AlertPipelineMatch {
+                target: "ods.orders".to_string(),
+                task_id: 3,
+                object_name: "\"ods\".orders_to_sr_sink".to_string(),
+            }]
+        );
+
+        // 共享的 source 匹配该库的所有管道
+        let matches =
+            IncidentService::match_pipelines(&labels(&[("source_name", "shop_source")]), &tasks);
+        let targets: Vec<_> = matches.iter().map(|m| m.target.as_str()).collect();
+        assert_eq!(targets, vec!["ods.orders", "ods.users"]);
+
+        // 带 schema 的名称必须与目标库一致
+        assert!(
+            IncidentService::match_pipelines(
+                &labels(&[("sink_name", "\"dw\".orders_to_sr_sink")]),
+                &tasks
+            )
+            .is_empty()
+        );
+        assert_eq!(
+            IncidentService::match_pipelines(&labels(&[("table_name", "ods.users")]), &tasks).len(),
+            1
+        );
+        assert!(
+            IncidentService::match_pipelines(&labels(&[("job", "compute")]), &tasks).is_empty()
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use crate::db::{ENCRYPTED_COLUMNS, EncryptedValueRepository};
+use crate::models::{KeyRotationResult, ReencryptedColumn};
+use crate::utils::crypto;
+use crate::utils::error::{AppError, Result};
+use sqlx::MySqlPool;
+
+/// 存储加密密钥轮换：用当前密钥重新加密所有存储的密文
+/// 旧密钥需通过 ENCRYPTION_KEY_PREVIOUS 提供
+pub struct KeyRotationService;
+
+impl KeyRotationService {
+    /// 先解密全部值，任何一个无法解密时不做修改；写回在一个事务中完成
+    pub async fn reencrypt_all(app_db: &MySqlPool) -> Result<KeyRotationResult> {
+        let repo = EncryptedValueRepository::new(app_db);
+        let mut values = Vec::new();
+        let mut columns = Vec::new();
+        for &(table, column) in ENCRYPTED_COLUMNS {
+            let mut rows = 0;
+            for (id, encrypted) in repo.list(table, column).await? {
+                let reencrypted = crypto::reencrypt(&encrypted).map_err(|e| {
+                    AppError::Encryption(format!("{}.{} id {}: {}", table, column, id, e))
+                })?;
+                if let Some(value) = reencrypted {
+                    values.push((table, column, id, value));
+                    rows += 1;
+                }
+            }
+            columns.push(ReencryptedColumn {
+                table: table.to_string(),
+                column: column.to_string(),
+                rows,
+            });
+        }
+
+        repo.update_all(&values).await?;
+        tracing::info!(
+            "Re-encrypted {} stored values with key {}",
+            values.len(),
+            crypto::current_key_id()
+        );
+        Ok(KeyRotationResult {
+            key_id: crypto::current_key_id().to_string(),
+            columns,
+        })
+    }
+}
@@ -0,0 +1,282 @@
+use crate::db::{ManagedObjectRepository, ServerIdRepository};
+use crate::generators::RisingWaveDDLGenerator;
+use crate::models::{ManagedObjectType, SyncRequest};
+use crate::services::{DdlExecutor, TaskLogger};
+use crate::utils::error::Result;
+use sqlx::{PgPool, Row};
+use std::collections::HashSet;
+
+/// 按旧名称查找的 RisingWave 对象类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LegacyObjectKind {
+    Table,
+    Sink,
+    Source,
+}
+
+impl LegacyObjectKind {
+    fn keyword(self) -> &'static str {
+        match self {
+            LegacyObjectKind::Table => "TABLE",
+            LegacyObjectKind::Sink => "SINK",
+            LegacyObjectKind::Source => "SOURCE",
+        }
+    }
+}
+
+/// (类型, schema, 名称)
+type ObjectKey = (LegacyObjectKind, String, String);
+
+/// 需要从旧名称改为当前名称的对象
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyRename {
+    pub kind: LegacyObjectKind,
+    pub schema: String,
+    pub legacy: String,
+    pub name: String,
+}
+
+impl LegacyRename {
+    pub fn ddl(&self) -> String {
+        RisingWaveDDLGenerator::generate_rename_ddl(
+            self.kind.keyword(),
+            &self.schema,
+            &self.legacy,
+            &self.name,
+        )
+    }
+}
+
+/// 沿用升级前创建的对象
+/// 旧版本创建 Table、Sink、Source 时名称不加引号，大小写混合的名称被折叠为小写；
+/// 现在名称原样加引号。重跑同步、清理和拆除前把旧名称的对象重命名为当前名称，
+/// 避免重复创建对象或找不到要删除的对象
+pub struct LegacyNameService;
+
+impl LegacyNameService {
+    /// 同步请求对应的对象；共享 Source 按登记的名称使用，不重命名
+    pub fn objects(request: &SyncRequest) -> Vec<ObjectKey> {
+        let schema = &request.target_database;
+        let mut objects = vec![
+            (
+                LegacyObjectKind::Table,
+                schema.clone(),
+                request.target_table.clone(),
+            ),
+            (
+                LegacyObjectKind::Sink,
+                schema.clone(),
+                format!("{}_to_sr_sink", request.target_table),
+            ),
+        ];
+        if !request.options.share_source {
+            objects.push((
+                LegacyObjectKind::Source,
+                schema.clone(),
+                format!("{}_source", request.mysql_database),
+            ));
+        }
+        objects
+    }
+
+    /// 旧名称的对象存在且当前名称的对象不存在时重命名
+    pub fn plan(objects: &[ObjectKey], existing: &HashSet<ObjectKey>) -> Vec<LegacyRename> {
+        let mut renames: Vec<LegacyRename> = Vec::new();
+        for (kind, schema, name) in objects {
+            let Some(legacy) = RisingWaveDDLGenerator::legacy_name(name) else {
+                continue;
+            };
+            let found = |n: &str| existing.contains(&(*kind, schema.clone(), n.to_string()));
+            let rename = LegacyRename {
+                kind: *kind,
+                schema: schema.clone(),
+                legacy,
+                name: name.clone(),
+            };
+            if found(&rename.legacy) && !found(name) && !renames.contains(&rename) {
+                renames.push(rename);
+            }
+        }
+        renames
+    }
+
+    /// 重命名请求涉及的旧名称对象，Source 的 server.id 分配随之改为新名称
+    pub async fn adopt(
+        executor: &DdlExecutor<'_>,
+        logger: &TaskLogger<'_>,
+        requests: &[SyncRequest],
+    ) -> Result<()> {
+        let objects: Vec<ObjectKey> = requests.iter().flat_map(Self::objects).collect();
+        let schemas: Vec<String> = objects
+            .iter()
+            .map(|(_, schema, _)| schema.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let existing = Self::existing(executor.rw_pool(), &schemas).await?;
+
+        for rename in Self::plan(&objects, &existing) {
+            let legacy_source =
+                RisingWaveDDLGenerator::legacy_qualified_name(&rename.schema, &rename.name);
+            // 仍登记为共享对象的 Source 由登记的名称引用，保持不变
+            if rename.kind == LegacyObjectKind::Source
+                && Self::is_shared(logger, requests, &legacy_source).await?
+            {
+                continue;
+            }
+
+            logger
+                .info(&format!(
+                    "Renaming RisingWave {} created before the upgrade: {} -> {}",
+                    rename.kind.keyword().to_lowercase(),
+                    RisingWaveDDLGenerator::qualified_name(&rename.schema, &rename.legacy),
+                    RisingWaveDDLGenerator::qualified_name(&rename.schema, &rename.name)
+                ))
+                .await?;
+            executor
+                .rw("rename RisingWave object", &rename.ddl(), true)
+                .await?;
+            if rename.kind == LegacyObjectKind::Source {
+                ServerIdRepository::new(logger.app_db())
+                    .rename_source(
+                        &legacy_source,
+                        &RisingWaveDDLGenerator::qualified_name(&rename.schema, &rename.name),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn is_shared(
+        logger: &TaskLogger<'_>,
+        requests: &[SyncRequest],
+        source_name: &str,
+    ) -> Result<bool> {
+        let repo = ManagedObjectRepository::new(logger.app_db());
+        let rw_config_ids: HashSet<i64> = requests.iter().map(|r| r.rw_config_id).collect();
+        for rw_config_id in rw_config_ids {
+            if repo
+                .find_by_name(rw_config_id, ManagedObjectType::Source, source_name)
+                .await?
+                .is_some()
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn existing(rw_pool: &PgPool, schemas: &[String]) -> Result<HashSet<ObjectKey>> {
+        let rows = sqlx::query(
+            "SELECT 'table' AS kind, sch.name AS schema_name, o.name
+             FROM rw_catalog.rw_tables o
+             JOIN rw_catalog.rw_schemas sch ON o.schema_id = sch.id
+             WHERE sch.name = ANY($1)
+             UNION ALL
+             SELECT 'sink' AS kind, sch.name AS schema_name, o.name
+             FROM rw_catalog.rw_sinks o
+             JOIN rw_catalog.rw_schemas sch ON o.schema_id = sch.id
+             WHERE sch.name = ANY($1)
+             UNION ALL
+             SELECT 'source' AS kind, sch.name AS schema_name, o.name
+             FROM rw_catalog.rw_sources o
+             JOIN rw_catalog.rw_schemas sch ON o.schema_id = sch.id
+             WHERE sch.name = ANY($1)",
+        )
+        .bind(schemas)
+        .fetch_all(rw_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let kind = match row.get::<String, _>("kind").as_str() {
+                    "table" => LegacyObjectKind::Table,
+                    "sink" => LegacyObjectKind::Sink,
+                    _ => LegacyObjectKind::Source,
+                };
+                (kind, row.get("schema_name"), row.get("name"))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SyncOptions;
+
+    fn request(mysql_database: &str, table: &str) -> SyncRequest {
+        SyncRequest {
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: mysql_database.to_string(),
+            mysql_table: table.to_string(),
+            target_database: "ods".to_string(),
+            target_table: table.to_string(),
+            options: SyncOptions::default(),
+            row_filter: None,
+            partition: None,
+            column_mapping: Default::default(),
+            nested_columns: Default::default(),
+            spatial_columns: Default::default(),
+        }
+    }
+
+    fn catalog(objects: &[(LegacyObjectKind, &str)]) -> HashSet<ObjectKey> {
+        objects
+            .iter()
+            .map(|(kind, name)| (*kind, "ods".to_string(), name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_resync_existing_mixed_case_table() {
+        use LegacyObjectKind::*;
+        let requests = [request("Shop", "Orders"), request("Shop", "OrderItems")];
+        let objects: Vec<ObjectKey> = requests.iter().flat_map(LegacyNameService::objects).collect();
+
+        // 升级前同步的表：名称都被折叠为小写
+        let legacy = catalog(&[
+            (Table, "orders"),
+            (Sink, "orders_to_sr_sink"),
+            (Source, "shop_source"),
+        ]);
+        let ddl: Vec<String> = LegacyNameService::plan(&objects, &legacy)
+            .iter()
+            .map(LegacyRename::ddl)
+            .collect();
+        assert_eq!(
+            ddl,
+            vec![
+                "ALTER TABLE \"ods\".orders RENAME TO \"Orders\";",
+                "ALTER SINK \"ods\".orders_to_sr_sink RENAME TO \"Orders_to_sr_sink\";",
+                "ALTER SOURCE \"ods\".shop_source RENAME TO \"Shop_source\";",
+            ]
+        );
+
+        // 重命名之后再次同步不再重命名
+        let renamed = catalog(&[
+            (Table, "Orders"),
+            (Sink, "Orders_to_sr_sink"),
+            (Source, "Shop_source"),
+        ]);
+        assert!(LegacyNameService::plan(&objects, &renamed).is_empty());
+
+        // 当前名称已存在时不动旧对象；小写名称没有旧名称
+        let both = legacy.union(&renamed).cloned().collect();
+        assert!(LegacyNameService::plan(&objects, &both).is_empty());
+        let lower = request("shop", "orders");
+        assert!(LegacyNameService::plan(&LegacyNameService::objects(&lower), &legacy).is_empty());
+
+        // 共享 Source 按登记的名称使用
+        let mut shared = request("Shop", "Orders");
+        shared.options.share_source = true;
+        assert_eq!(
+            LegacyNameService::plan(&LegacyNameService::objects(&shared), &legacy).len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::db::ManagedObjectRepository;
+use crate::generators::RisingWaveDDLGenerator;
+use crate::models::{ManagedObject, ManagedObjectType};
+use crate::services::AuditService;
+use crate::utils::error::{AppError, Result};
+use sqlx::{MySqlPool, PgPool};
+
+/// 共享 RisingWave 对象的引用计数与清理
+/// 共享的 Source / Secret 只在没有目标表引用时删除，手动删除时可以用 force 忽略引用
+pub struct ManagedObjectService;
+
+impl ManagedObjectService {
+    /// 删除目标表后释放它对共享对象的引用，并清理不再被引用的对象，返回被删除的对象
+    pub async fn release_table(
+        app_db: &MySqlPool,
+        rw_pool: &PgPool,
+        rw_config_id: i64,
+        target_database: &str,
+        target_table: &str,
+    ) -> Result<Vec<ManagedObject>> {
+        let released = ManagedObjectRepository::new(app_db)
+            .remove_refs(rw_config_id, target_database, target_table)
+            .await?;
+        if released == 0 {
+            return Ok(Vec::new());
+        }
+        tracing::info!(
+            "Released {} shared object references of {}.{}",
+            released,
+            target_database,
+            target_table
+        );
+
+        Self::collect_garbage(app_db, rw_pool, rw_config_id).await
+    }
+
+    /// 删除该 RisingWave 上所有不再被引用的共享对象，返回被删除的对象
+    /// 删除失败的对象（例如 Secret 仍被未共享的 Source 使用）保留登记，下次再试
+    pub async fn collect_garbage(
+        app_db: &MySqlPool,
+        rw_pool: &PgPool,
+        rw_config_id: i64,
+    ) -> Result<Vec<ManagedObject>> {
+        let repo = ManagedObjectRepository::new(app_db);
+        let mut dropped = Vec::new();
+        for object in Self::unreferenced(repo.list(Some(rw_config_id)).await?) {
+            let ddl = match object.object_type {
+                ManagedObjectType::Source => {
+                    RisingWaveDDLGenerator::generate_drop_source_ddl(&object.object_name)
+                }
+                ManagedObjectType::Secret => {
+                    RisingWaveDDLGenerator::generate_drop_secret_ddl(&object.object_name)
+                }
+            };
+            let result = sqlx::query(&ddl).execute(rw_pool).await;
+            let error = result.as_ref().err().map(|e| e.to_string());
+            AuditService::record_ddl(app_db, None, None, "RisingWave", &ddl, error.as_deref())
+                .await;
+            if let Err(e) = result {
+                tracing::warn!(
+                    "Failed to drop unreferenced {} {}: {}",
+                    object.object_type.as_str(),
+                    object.object_name,
+                    e
+                );
+                continue;
+            }
+            repo.delete(object.id).await?;
+            tracing::info!(
+                "Dropped unreferenced {} {}",
+                object.object_type.as_str(),
+                object.object_name
+            );
+            dropped.push(object);
+        }
+        Ok(dropped)
+    }
+
+    /// 手动删除对象前检查引用，返回该对象的登记；仍被引用且没有 force 时返回 Conflict
+    pub async fn check_drop(
+        app_db: &MySqlPool,
+        rw_config_id: i64,
+        object_type: ManagedObjectType,
+        object_name: &str,
+        force: bool,
+    ) -> Result<Option<ManagedObject>> {
+        let Some(object) = ManagedObjectRepository::new(app_db)
+            .find_by_name(rw_config_id, object_type, object_name)
+            .await?
+        else {
+            return Ok(None);
+        };
+        if object.ref_count > 0 && !force {
+            return Err(AppError::Conflict(format!(
+                "Shared {} {} is still used by {} tables, set force to drop it anyway",
+                object_type.as_str(),
+                object_name,
+                object.ref_count
+            )));
+        }
+        Ok(Some(object))
+    }
+
+    /// 对象已在 RisingWave 中删除后移除登记
+    pub async fn forget(app_db: &MySqlPool, object: &ManagedObject) -> Result<()> {
+        ManagedObjectRepository::new(app_db).delete(object.id).await
+    }
+
+    /// 没有引用的对象，Source 排在 Secret 之前（Secret 被 Source 使用）
+    fn unreferenced(objects: Vec<ManagedObject>) -> Vec<ManagedObject> {
+        let mut unused: Vec<_> = objects.into_iter().filter(|o| o.ref_count == 0).collect();
+        unused.sort_by_key(|o| o.object_type == ManagedObjectType::Secret);
+        unused
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(id: i64, object_type: ManagedObjectType, ref_count: i64) -> ManagedObject {
+        ManagedObject {
+            id,
+            rw_config_id: 1,
+            object_type,
+            object_name: format!("\"ods\".object_{}", id),
+            share_key: Some("mysql:1/shop".to_string()),
+            ref_count,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_unreferenced_drop_order() {
+        let objects = vec![
+            object(1, ManagedObjectType::Secret, 0),
+            object(2, ManagedObjectType::Source, 0),
+            object(3, ManagedObjectType::Source, 2),
+            object(4, ManagedObjectType::Secret, 0),
+        ];
+        let ids: Vec<i64> = ManagedObjectService::unreferenced(objects)
+            .iter()
+            .map(|o| o.id)
+            .collect();
+        assert_eq!(ids, vec![2, 1, 4]);
+    }
+}
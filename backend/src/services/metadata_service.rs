@@ -1,7 +1,10 @@
-use crate::models::{Column, DatabaseConfig, TableSchema};
-use crate::services::ConnectionService;
-use crate::utils::error::Result;
-use sqlx::{MySqlPool, Row};
+use crate::generators::StarRocksDDLGenerator;
+use crate::models::{Column, ColumnTypeSource, DatabaseConfig, TablePreview, TableSchema, TableStats};
+use crate::services::{ConnectionPoolManager, ConnectionService};
+use crate::utils::error::{AppError, Result};
+use mysql_async::prelude::*;
+use sqlx::{Column as _, MySqlPool, Row};
+use tokio_stream::StreamExt;
 
 /// 元数据服务
 pub struct MetadataService;
@@ -10,11 +13,7 @@ impl MetadataService {
     /// 获取 MySQL 数据库列表
     pub async fn list_mysql_databases(config: &DatabaseConfig) -> Result<Vec<String>> {
         tracing::info!("Listing MySQL databases for config: {}", config.name);
-        let opts = ConnectionService::build_mysql_options_from_config(config);
-        let pool = MySqlPool::connect_with(opts).await.map_err(|e| {
-            tracing::error!("Failed to connect to MySQL: {}", e);
-            e
-        })?;
+        let pool = ConnectionPoolManager::mysql(config).await?;
 
         let databases: Vec<String> = sqlx::query_scalar(
             "SELECT SCHEMA_NAME FROM INFORMATION_SCHEMA.SCHEMATA
@@ -29,7 +28,6 @@ impl MetadataService {
         })?;
 
         tracing::info!("Found {} databases", databases.len());
-        pool.close().await;
 
         Ok(databases)
     }
@@ -37,11 +35,7 @@ impl MetadataService {
     /// 获取 MySQL 数据库的表列表
     pub async fn list_mysql_tables(config: &DatabaseConfig, database: &str) -> Result<Vec<String>> {
         tracing::info!("Listing tables in database: {}", database);
-        let opts = ConnectionService::build_mysql_options_from_config(config);
-        let pool = MySqlPool::connect_with(opts).await.map_err(|e| {
-            tracing::error!("Failed to connect to MySQL: {}", e);
-            e
-        })?;
+        let pool = ConnectionPoolManager::mysql(config).await?;
 
         let tables: Vec<String> = sqlx::query_scalar(
             "SELECT TABLE_NAME FROM INFORMATION_SCHEMA.TABLES
@@ -61,7 +55,6 @@ impl MetadataService {
         })?;
 
         tracing::info!("Found {} tables in database {}", tables.len(), database);
-        pool.close().await;
 
         Ok(tables)
     }
@@ -73,11 +66,7 @@ impl MetadataService {
         table: &str,
     ) -> Result<TableSchema> {
         tracing::info!("Fetching schema for table: {}.{}", database, table);
-        let opts = ConnectionService::build_mysql_options_from_config(config);
-        let pool = MySqlPool::connect_with(opts).await.map_err(|e| {
-            tracing::error!("Failed to connect to MySQL: {}", e);
-            e
-        })?;
+        let pool = ConnectionPoolManager::mysql(config).await?;
 
         // 获取列信息
         let columns = Self::get_columns(&pool, database, table)
@@ -109,6 +98,14 @@ impl MetadataService {
         //     })?;
         let indexes = vec![];
 
+        // 获取表注释
+        let comment = Self::get_table_comment(&pool, database, table)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get table comment for {}.{}: {}", database, table, e);
+                e
+            })?;
+
         tracing::info!(
             "Successfully fetched schema for {}.{}: {} columns, {} primary keys, {} indexes",
             database,
@@ -118,19 +115,184 @@ impl MetadataService {
             indexes.len()
         );
 
-        pool.close().await;
-
         Ok(TableSchema {
             database: database.to_string(),
             table_name: table.to_string(),
             columns,
             primary_keys,
             indexes,
+            type_source: ColumnTypeSource::MySQL,
+            comment,
         })
     }
 
+    /// 获取多个 MySQL 表的行数与数据量估算（复用同一个连接）
+    pub async fn get_mysql_table_stats(
+        config: &DatabaseConfig,
+        tables: &[(String, String)],
+    ) -> Result<Vec<TableStats>> {
+        tracing::info!("Fetching table stats for {} tables", tables.len());
+        let pool = ConnectionPoolManager::mysql(config).await?;
+
+        let mut stats = Vec::with_capacity(tables.len());
+        for (database, table) in tables {
+            let row: Option<(i64, i64)> = sqlx::query_as(
+                r#"
+                SELECT
+                    CAST(COALESCE(TABLE_ROWS, 0) AS SIGNED),
+                    CAST(COALESCE(DATA_LENGTH, 0) AS SIGNED)
+                FROM INFORMATION_SCHEMA.TABLES
+                WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?
+                "#,
+            )
+            .bind(database)
+            .bind(table)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to query table stats for {}.{}: {}", database, table, e);
+                e
+            })?;
+
+            let (estimated_rows, data_bytes) = row.unwrap_or((0, 0));
+            stats.push(TableStats {
+                database: database.clone(),
+                table_name: table.clone(),
+                estimated_rows,
+                data_bytes,
+            });
+        }
+
+        Ok(stats)
+    }
+
+    /// 预览 MySQL 表的前 limit 行，有主键时按主键排序，返回排序使用的主键
+    pub async fn preview_mysql_table(
+        config: &DatabaseConfig,
+        database: &str,
+        table: &str,
+        limit: usize,
+    ) -> Result<(TablePreview, Vec<String>)> {
+        tracing::info!("Previewing {} rows of {}.{}", limit, database, table);
+        let pool = ConnectionPoolManager::mysql(config).await?;
+        let primary_keys = Self::get_primary_keys(&pool, database, table).await?;
+        let sql = Self::preview_sql(database, table, &primary_keys, limit);
+
+        // 使用文本协议，所有类型的值都以 MySQL 的文本格式返回
+        let mut stream = sqlx::raw_sql(&sql).fetch(&pool);
+        let mut columns = Vec::new();
+        let mut rows = Vec::new();
+        while let Some(row) = stream.next().await {
+            let row = row?;
+            if columns.is_empty() {
+                columns = row.columns().iter().map(|c| c.name().to_string()).collect();
+            }
+            rows.push(
+                (0..row.len())
+                    .map(|i| {
+                        row.try_get_unchecked::<Option<Vec<u8>>, _>(i)
+                            .ok()
+                            .flatten()
+                            .map(|bytes| Self::bytes_text(&bytes))
+                    })
+                    .collect(),
+            );
+        }
+
+        let preview = TablePreview {
+            database: database.to_string(),
+            table_name: table.to_string(),
+            columns,
+            rows,
+        };
+        Ok((preview, primary_keys))
+    }
+
+    /// 预览 StarRocks / Doris 表的前 limit 行，按 order_by 排序以便与源表逐行对照
+    pub async fn preview_starrocks_table(
+        config: &DatabaseConfig,
+        database: &str,
+        table: &str,
+        order_by: &[String],
+        limit: usize,
+    ) -> Result<TablePreview> {
+        tracing::info!("Previewing {} rows of StarRocks table {}.{}", limit, database, table);
+        let mut conn = ConnectionService::connect_starrocks(config).await?;
+        let sql = Self::preview_sql(database, table, order_by, limit);
+        let result = conn.query::<mysql_async::Row, _>(sql).await;
+        let _ = conn.disconnect().await;
+        let result = result
+            .map_err(|e| AppError::Unknown(format!("StarRocks query failed: {}", e)))?;
+
+        let columns = result
+            .first()
+            .map(|row| {
+                row.columns_ref()
+                    .iter()
+                    .map(|c| c.name_str().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let rows = result
+            .iter()
+            .map(|row| {
+                (0..row.len())
+                    .map(|i| row.as_ref(i).and_then(Self::sr_value_text))
+                    .collect()
+            })
+            .collect();
+
+        Ok(TablePreview {
+            database: database.to_string(),
+            table_name: table.to_string(),
+            columns,
+            rows,
+        })
+    }
+
+    /// 预览查询，MySQL 与 StarRocks 使用相同的反引号标识符
+    fn preview_sql(database: &str, table: &str, order_by: &[String], limit: usize) -> String {
+        let mut sql = format!(
+            "SELECT * FROM {}.{}",
+            StarRocksDDLGenerator::quote_identifier(database),
+            StarRocksDDLGenerator::quote_identifier(table)
+        );
+        if !order_by.is_empty() {
+            sql.push_str(&format!(
+                " ORDER BY {}",
+                StarRocksDDLGenerator::quote_identifiers(order_by)
+            ));
+        }
+        sql.push_str(&format!(" LIMIT {}", limit));
+        sql
+    }
+
+    /// mysql_async 返回值的文本格式，NULL 返回 None
+    pub(crate) fn sr_value_text(value: &mysql_async::Value) -> Option<String> {
+        match value {
+            mysql_async::Value::NULL => None,
+            mysql_async::Value::Bytes(bytes) => Some(Self::bytes_text(bytes)),
+            mysql_async::Value::Int(v) => Some(v.to_string()),
+            mysql_async::Value::UInt(v) => Some(v.to_string()),
+            mysql_async::Value::Float(v) => Some(v.to_string()),
+            mysql_async::Value::Double(v) => Some(v.to_string()),
+            value => Some(value.as_sql(true)),
+        }
+    }
+
+    /// 有效的 UTF-8 原样返回，否则显示为十六进制
+    fn bytes_text(bytes: &[u8]) -> String {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => text.to_string(),
+            Err(_) => {
+                let hex: String = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+                format!("0x{}", hex)
+            }
+        }
+    }
+
     /// 获取列信息
-    async fn get_columns(pool: &MySqlPool, database: &str, table: &str) -> Result<Vec<Column>> {
+    pub(crate) async fn get_columns(pool: &MySqlPool, database: &str, table: &str) -> Result<Vec<Column>> {
         tracing::debug!("Fetching columns for {}.{}", database, table);
         let rows = sqlx::query(
             r#"
@@ -141,7 +303,10 @@ impl MetadataService {
                 CHARACTER_MAXIMUM_LENGTH,
                 NUMERIC_PRECISION,
                 NUMERIC_SCALE,
-                CAST(COLUMN_TYPE AS CHAR) AS COLUMN_TYPE
+                CAST(COLUMN_TYPE AS CHAR) AS COLUMN_TYPE,
+                CAST(COLUMN_COMMENT AS CHAR) AS COLUMN_COMMENT,
+                CAST(COLUMN_DEFAULT AS CHAR) AS COLUMN_DEFAULT,
+                EXTRA
             FROM INFORMATION_SCHEMA.COLUMNS
             WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?
             ORDER BY ORDINAL_POSITION
@@ -170,8 +335,11 @@ impl MetadataService {
                 name: column_name,
                 data_type: column_type,
                 is_nullable: row.try_get::<String, _>("IS_NULLABLE")? == "YES",
-                default_value: Some(String::from("")),
-                comment: Some(String::from("")), // INFORMATION_SCHEMA.COLUMNS 不包含注释字段
+                default_value: Self::default_expression(
+                    row.try_get("COLUMN_DEFAULT").ok().flatten(),
+                    &row.try_get::<String, _>("EXTRA").unwrap_or_default(),
+                ),
+                comment: Self::non_empty(row.try_get("COLUMN_COMMENT").ok()),
                 character_maximum_length: row.try_get("CHARACTER_MAXIMUM_LENGTH").ok(),
                 numeric_precision: row.try_get("NUMERIC_PRECISION").ok(),
                 numeric_scale: row.try_get("NUMERIC_SCALE").ok(),
@@ -187,6 +355,49 @@ impl MetadataService {
         Ok(columns)
     }
 
+    /// 获取表注释，没有注释时返回 None
+    async fn get_table_comment(
+        pool: &MySqlPool,
+        database: &str,
+        table: &str,
+    ) -> Result<Option<String>> {
+        let comment: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT CAST(TABLE_COMMENT AS CHAR)
+            FROM INFORMATION_SCHEMA.TABLES
+            WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?
+            "#,
+        )
+        .bind(database)
+        .bind(table)
+        .fetch_optional(pool)
+        .await?
+        .flatten();
+
+        Ok(Self::non_empty(comment))
+    }
+
+    /// COLUMN_DEFAULT 转为 SHOW CREATE TABLE 中的写法：字面量加单引号，
+    /// 表达式默认值（EXTRA 含 DEFAULT_GENERATED）、CURRENT_TIMESTAMP 和 BIT 字面量原样保留
+    /// 没有默认值或默认值为 NULL 时返回 None
+    pub(crate) fn default_expression(column_default: Option<String>, extra: &str) -> Option<String> {
+        let value = column_default?;
+        let upper = value.trim().to_uppercase();
+        let is_expression = extra.to_uppercase().contains("DEFAULT_GENERATED")
+            || upper.starts_with("CURRENT_TIMESTAMP")
+            || (upper.starts_with("B'") && upper.ends_with('\''));
+        if is_expression {
+            Some(value)
+        } else {
+            Some(StarRocksDDLGenerator::quote_string(&value))
+        }
+    }
+
+    /// INFORMATION_SCHEMA 中没有注释时为空字符串，统一为 None
+    fn non_empty(comment: Option<String>) -> Option<String> {
+        comment.filter(|c| !c.is_empty())
+    }
+
     /// 获取主键信息
     async fn get_primary_keys(
         pool: &MySqlPool,
@@ -209,3 +420,59 @@ impl MetadataService {
         Ok(primary_keys)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_sql_and_text() {
+        assert_eq!(
+            MetadataService::preview_sql("shop", "order`s", &[], 10),
+            "SELECT * FROM `shop`.`order``s` LIMIT 10"
+        );
+        assert_eq!(
+            MetadataService::preview_sql("shop", "orders", &["id".to_string(), "ts".to_string()], 5),
+            "SELECT * FROM `shop`.`orders` ORDER BY `id`, `ts` LIMIT 5"
+        );
+
+        assert_eq!(MetadataService::bytes_text("中文 ok".as_bytes()), "中文 ok");
+        assert_eq!(MetadataService::bytes_text(&[0xff, 0x00, 0x1a]), "0xFF001A");
+        assert_eq!(MetadataService::sr_value_text(&mysql_async::Value::NULL), None);
+        assert_eq!(
+            MetadataService::sr_value_text(&mysql_async::Value::Int(-3)),
+            Some("-3".to_string())
+        );
+
+        assert_eq!(MetadataService::default_expression(None, ""), None);
+        assert_eq!(
+            MetadataService::default_expression(Some("it's".to_string()), ""),
+            Some("'it''s'".to_string())
+        );
+        assert_eq!(
+            MetadataService::default_expression(Some("0".to_string()), ""),
+            Some("'0'".to_string())
+        );
+        assert_eq!(
+            MetadataService::default_expression(
+                Some("CURRENT_TIMESTAMP".to_string()),
+                "DEFAULT_GENERATED on update CURRENT_TIMESTAMP"
+            ),
+            Some("CURRENT_TIMESTAMP".to_string())
+        );
+        assert_eq!(
+            MetadataService::default_expression(Some("uuid()".to_string()), "DEFAULT_GENERATED"),
+            Some("uuid()".to_string())
+        );
+        assert_eq!(
+            MetadataService::default_expression(Some("b'1'".to_string()), ""),
+            Some("b'1'".to_string())
+        );
+
+        assert_eq!(MetadataService::non_empty(Some(String::new())), None);
+        assert_eq!(
+            MetadataService::non_empty(Some("订单表".to_string())),
+            Some("订单表".to_string())
+        );
+    }
+}
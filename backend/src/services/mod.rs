@@ -1,7 +1,83 @@
+pub mod alert_router;
+pub mod approval_service;
+pub mod audit_service;
+pub mod auth_service;
+pub mod backfill_gate;
+pub mod bundle_service;
+pub mod capacity_service;
+pub mod comment_service;
+pub mod connection_pool;
 pub mod connection_service;
+pub mod ddl_executor;
+pub mod demo_service;
+pub mod error_stats;
+pub mod existing_objects;
+pub mod feature_flag_service;
+pub mod incident_service;
+pub mod key_rotation;
+pub mod legacy_names;
+pub mod managed_object_service;
 pub mod metadata_service;
+pub mod notification_service;
+pub mod orphan_cleanup;
+pub mod pipeline_cleanup;
+pub mod rw_secret_service;
+pub mod scheduler;
+pub mod search_service;
+pub mod secret_rotation;
+pub mod settings_service;
+pub mod shutdown;
+pub mod sink_maintenance;
+pub mod sla_service;
+pub mod sql_console;
+pub mod staging_validation;
+pub mod status_service;
+pub mod step_tracker;
 pub mod sync_engine;
+pub mod sync_mapping;
+pub mod sync_metrics;
+pub mod task_logger;
+pub mod task_recovery;
+pub mod validation;
 
+pub use alert_router::*;
+pub use approval_service::*;
+pub use audit_service::*;
+pub use auth_service::*;
+pub use backfill_gate::*;
+pub use bundle_service::*;
+pub use capacity_service::*;
+pub use comment_service::*;
+pub use connection_pool::*;
 pub use connection_service::*;
+pub use ddl_executor::*;
+pub use demo_service::*;
+pub use error_stats::*;
+pub use existing_objects::*;
+pub use feature_flag_service::*;
+pub use incident_service::*;
+pub use key_rotation::*;
+pub use legacy_names::*;
+pub use managed_object_service::*;
 pub use metadata_service::*;
+pub use notification_service::*;
+pub use orphan_cleanup::*;
+pub use pipeline_cleanup::*;
+pub use rw_secret_service::*;
+pub use scheduler::*;
+pub use search_service::*;
+pub use secret_rotation::*;
+pub use settings_service::*;
+pub use shutdown::*;
+pub use sink_maintenance::*;
+pub use sla_service::*;
+pub use sql_console::*;
+pub use staging_validation::*;
+pub use status_service::*;
+pub use step_tracker::*;
 pub use sync_engine::*;
+pub use sync_mapping::*;
+pub use sync_metrics::*;
+pub use task_logger::*;
+pub use task_recovery::*;
+pub use validation::*;
@@ -0,0 +1,515 @@
+use crate::db::{NotificationChannelRepository, TaskRepository};
+use crate::models::{
+    ChannelSettings, Notification, NotificationChannelConfig, NotificationEvent, SyncOptions,
+    SyncTask, TaskStatus,
+};
+use crate::utils::error::{AppError, Result};
+use crate::utils::{proxy, smtp};
+use base64::{Engine as _, engine::general_purpose};
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::json;
+use sha2::Sha256;
+use sqlx::MySqlPool;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// 默认的单次发送超时
+pub const DEFAULT_SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 默认的最大并发发送数
+pub const DEFAULT_MAX_CONCURRENT_SENDS: usize = 4;
+
+/// 共享的 HTTP 客户端，复用到各渠道的连接
+static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    proxy::http_client_builder()
+        .pool_max_idle_per_host(DEFAULT_MAX_CONCURRENT_SENDS)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .connect_timeout(Duration::from_secs(5))
+        .build()
+        .unwrap_or_default()
+});
+
+/// 全局发送并发限制，告警突发时避免同时打开过多连接
+static SEND_PERMITS: Lazy<Semaphore> = Lazy::new(|| {
+    let permits = std::env::var("NOTIFY_MAX_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_SENDS);
+    Semaphore::new(permits)
+});
+
+/// 通知渠道（HTTP 发送目标）
+#[derive(Debug, Clone)]
+pub struct NotificationChannel {
+    pub name: String,
+    pub webhook_url: String,
+    /// 单次发送超时
+    pub timeout: Duration,
+    /// 附加的请求头
+    pub headers: BTreeMap<String, String>,
+}
+
+impl NotificationChannel {
+    fn http(name: &str, url: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            webhook_url: url.to_string(),
+            timeout: DEFAULT_SEND_TIMEOUT,
+            headers: BTreeMap::new(),
+        }
+    }
+
+    /// 飞书渠道，通过环境变量 LARK_WEBHOOK_URL、LARK_WEBHOOK_TIMEOUT_SECS 配置
+    pub fn lark_from_env() -> Option<Self> {
+        let webhook_url = std::env::var("LARK_WEBHOOK_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())?;
+        let timeout = Self::parse_timeout(std::env::var("LARK_WEBHOOK_TIMEOUT_SECS").ok());
+
+        Some(Self {
+            name: "lark".to_string(),
+            webhook_url,
+            timeout,
+            headers: BTreeMap::new(),
+        })
+    }
+
+    fn parse_timeout(secs: Option<String>) -> Duration {
+        secs.and_then(|v| v.parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_SEND_TIMEOUT)
+    }
+}
+
+/// 飞书文本消息
+#[derive(Debug, Serialize)]
+pub struct LarkTextMessage {
+    pub msg_type: String,
+    pub content: LarkTextContent,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LarkTextContent {
+    pub text: String,
+}
+
+impl LarkTextMessage {
+    pub fn new(text: String) -> Self {
+        Self {
+            msg_type: "text".to_string(),
+            content: LarkTextContent { text },
+        }
+    }
+}
+
+/// 通知发送服务
+pub struct NotificationService;
+
+impl NotificationService {
+    /// 发送一条消息（JSON），受全局并发限制
+    pub async fn send<T: Serialize>(channel: &NotificationChannel, payload: &T) -> Result<()> {
+        let _permit = SEND_PERMITS
+            .acquire()
+            .await
+            .map_err(|e| AppError::Unknown(e.to_string()))?;
+
+        let mut request = HTTP_CLIENT.post(&channel.webhook_url).json(payload);
+        for (name, value) in &channel.headers {
+            request = request.header(name, value);
+        }
+        let response = request.timeout(channel.timeout).send().await.map_err(|e| {
+            AppError::Connection(format!("Failed to send to {}: {}", channel.name, e))
+        })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::Connection(format!(
+                "Failed to send to {}: HTTP {} {}",
+                channel.name, status, error_text
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// 并发发送多条消息，返回结果与输入顺序一致
+    pub async fn send_all<T>(channel: &NotificationChannel, payloads: Vec<T>) -> Vec<Result<()>>
+    where
+        T: Serialize + Send + Sync + 'static,
+    {
+        let channel = Arc::new(channel.clone());
+        let handles: Vec<_> = payloads
+            .into_iter()
+            .map(|payload| {
+                let channel = Arc::clone(&channel);
+                tokio::spawn(async move { Self::send(&channel, &payload).await })
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(handles.len());
+        for handle in handles {
+            results.push(
+                handle
+                    .await
+                    .unwrap_or_else(|e| Err(AppError::Unknown(e.to_string()))),
+            );
+        }
+        results
+    }
+
+    /// 发送到订阅了该事件的所有启用渠道，以及 LARK_WEBHOOK_URL 配置的飞书渠道，
+    /// 返回每个渠道的名称和发送结果
+    pub async fn dispatch(
+        app_db: &MySqlPool,
+        notification: &Notification,
+    ) -> Result<Vec<(String, Result<()>)>> {
+        let channels: Vec<NotificationChannelConfig> = NotificationChannelRepository::new(app_db)
+            .find_all()
+            .await?
+            .into_iter()
+            .filter(|c| c.subscribes(notification.event))
+            .collect();
+
+        // 环境变量配置的飞书渠道只接收告警和失败通知
+        let lark = NotificationChannel::lark_from_env()
+            .filter(|_| notification.event != NotificationEvent::TaskCompleted);
+        Ok(Self::deliver_all(lark, channels, notification).await)
+    }
+
+    /// 只发送到指定的启用渠道（告警路由规则匹配时使用），不检查渠道订阅的事件
+    pub async fn dispatch_to(
+        app_db: &MySqlPool,
+        notification: &Notification,
+        channel_ids: &[i64],
+    ) -> Result<Vec<(String, Result<()>)>> {
+        let channels: Vec<NotificationChannelConfig> = NotificationChannelRepository::new(app_db)
+            .find_all()
+            .await?
+            .into_iter()
+            .filter(|c| c.enabled && channel_ids.contains(&c.id))
+            .collect();
+        Ok(Self::deliver_all(None, channels, notification).await)
+    }
+
+    /// 并发发送到各渠道，返回每个渠道的名称和发送结果
+    async fn deliver_all(
+        lark: Option<NotificationChannel>,
+        channels: Vec<NotificationChannelConfig>,
+        notification: &Notification,
+    ) -> Vec<(String, Result<()>)> {
+        let mut handles = Vec::new();
+        if let Some(lark) = lark {
+            let message = LarkTextMessage::new(Self::plain_text(notification));
+            handles.push((
+                lark.name.clone(),
+                tokio::spawn(async move { Self::send(&lark, &message).await }),
+            ));
+        }
+        for channel in channels {
+            let notification = notification.clone();
+            handles.push((
+                channel.name.clone(),
+                tokio::spawn(async move { Self::deliver(&channel, &notification).await }),
+            ));
+        }
+
+        let mut results = Vec::with_capacity(handles.len());
+        for (name, handle) in handles {
+            let result = handle
+                .await
+                .unwrap_or_else(|e| Err(AppError::Unknown(e.to_string())));
+            if let Err(e) = &result {
+                tracing::warn!("Failed to send notification to {}: {}", name, e);
+            }
+            results.push((name, result));
+        }
+        results
+    }
+
+    /// 按渠道类型格式化并发送一条通知
+    pub async fn deliver(
+        channel: &NotificationChannelConfig,
+        notification: &Notification,
+    ) -> Result<()> {
+        let name = channel.name.as_str();
+        match &channel.settings {
+            ChannelSettings::Lark { webhook_url } => {
+                let message = LarkTextMessage::new(Self::plain_text(notification));
+                Self::send(&NotificationChannel::http(name, webhook_url), &message).await
+            }
+            ChannelSettings::DingTalk {
+                webhook_url,
+                secret,
+            } => {
+                let url = match secret.as_deref().filter(|s| !s.is_empty()) {
+                    Some(secret) => Self::signed_dingtalk_url(
+                        webhook_url,
+                        secret,
+                        chrono::Utc::now().timestamp_millis(),
+                    )?,
+                    None => webhook_url.clone(),
+                };
+                let message = json!({
+                    "msgtype": "text",
+                    "text": { "content": Self::plain_text(notification) },
+                });
+                Self::send(&NotificationChannel::http(name, &url), &message).await
+            }
+            ChannelSettings::Slack { webhook_url } => {
+                let message = json!({
+                    "text": format!("*{}*\n{}", notification.title, notification.text),
+                });
+                Self::send(&NotificationChannel::http(name, webhook_url), &message).await
+            }
+            ChannelSettings::Webhook { url, headers } => {
+                let mut target = NotificationChannel::http(name, url);
+                target.headers = headers.clone();
+                Self::send(&target, notification).await
+            }
+            ChannelSettings::Email {
+                smtp_host,
+                smtp_port,
+                tls,
+                username,
+                password,
+                from,
+                to,
+            } => {
+                let _permit = SEND_PERMITS
+                    .acquire()
+                    .await
+                    .map_err(|e| AppError::Unknown(e.to_string()))?;
+                let (host, port, tls) = (smtp_host.clone(), *smtp_port, *tls);
+                let credentials = username.clone().zip(password.clone());
+                let (from, to) = (from.clone(), to.clone());
+                let (subject, body) = (notification.title.clone(), Self::plain_text(notification));
+                tokio::task::spawn_blocking(move || {
+                    smtp::send(
+                        &smtp::SmtpServer {
+                            host: &host,
+                            port,
+                            tls,
+                            credentials: credentials
+                                .as_ref()
+                                .map(|(u, p)| (u.as_str(), p.as_str())),
+                            timeout: DEFAULT_SEND_TIMEOUT,
+                        },
+                        &smtp::Mail {
+                            from: &from,
+                            to: &to,
+                            subject: &subject,
+                            body: &body,
+                        },
+                    )
+                })
+                .await
+                .map_err(|e| AppError::Unknown(e.to_string()))?
+                .map_err(|e| AppError::Connection(format!("Failed to send to {}: {}", name, e)))
+            }
+        }
+    }
+
+    /// 任务结束（完成或失败）后在后台发送通知，任务选项 disable_notifications 为 true 时不发送
+    /// 需在任务状态写入后调用，通知内容从任务记录读取
+    pub fn notify_task_finished(app_db: &MySqlPool, task_id: i64) {
+        let app_db = app_db.clone();
+        tokio::spawn(async move {
+            let task = match TaskRepository::new(&app_db).find_by_id(task_id).await {
+                Ok(task) => task,
+                Err(e) => {
+                    tracing::warn!("Failed to load task {} for notification: {}", task_id, e);
+                    return;
+                }
+            };
+            let options: SyncOptions = serde_json::from_str(&task.options).unwrap_or_default();
+            if options.disable_notifications {
+                return;
+            }
+            let Some(notification) = Self::task_notification(&task) else {
+                return;
+            };
+            if let Err(e) = Self::dispatch(&app_db, &notification).await {
+                tracing::warn!("Failed to dispatch task {} notification: {}", task_id, e);
+            }
+        });
+    }
+
+    /// 已完成或失败任务的通知，其他状态返回 None
+    fn task_notification(task: &SyncTask) -> Option<Notification> {
+        let (event, title) = match task.status {
+            TaskStatus::Completed => (
+                NotificationEvent::TaskCompleted,
+                format!("✅ 同步任务完成: {}", task.task_name),
+            ),
+            TaskStatus::Failed => (
+                NotificationEvent::TaskFailed,
+                format!("❌ 同步任务失败: {}", task.task_name),
+            ),
+            _ => return None,
+        };
+
+        let tables: Vec<String> = match task.sync_requests() {
+            Ok(requests) if !requests.is_empty() => requests
+                .iter()
+                .map(|r| {
+                    format!(
+                        "{}.{} -> {}.{}",
+                        r.mysql_database, r.mysql_table, r.target_database, r.target_table
+                    )
+                })
+                .collect(),
+            _ => vec![format!(
+                "{}.{} -> {}.{}",
+                task.mysql_database, task.mysql_table, task.target_database, task.target_table
+            )],
+        };
+        let duration = task
+            .completed_at
+            .map(|completed_at| Self::format_duration(completed_at - task.started_at));
+
+        let mut lines = vec![format!("• 任务: #{}", task.id)];
+        lines.push(format!("• 表: {}", tables.join(", ")));
+        if let Some(duration) = &duration {
+            lines.push(format!("• 耗时: {}", duration));
+        }
+        if let Some(error) = &task.error_message {
+            lines.push(format!("• 错误: {}", error));
+        }
+
+        let mut fields = BTreeMap::from([
+            ("task_id".to_string(), task.id.to_string()),
+            ("task_name".to_string(), task.task_name.clone()),
+            ("status".to_string(), task.status.as_str().to_string()),
+            ("tables".to_string(), tables.join(", ")),
+        ]);
+        if let Some(duration) = duration {
+            fields.insert("duration".to_string(), duration);
+        }
+        if let Some(error) = &task.error_message {
+            fields.insert("error".to_string(), error.clone());
+        }
+
+        Some(Notification {
+            event,
+            title,
+            text: lines.join("\n"),
+            fields,
+        })
+    }
+
+    /// 任务耗时，例如 `45s`、`3m 20s`、`1h 5m`
+    fn format_duration(duration: chrono::Duration) -> String {
+        let secs = duration.num_seconds().max(0);
+        match secs {
+            0..60 => format!("{}s", secs),
+            60..3600 => format!("{}m {}s", secs / 60, secs % 60),
+            _ => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+        }
+    }
+
+    /// 标题和正文组成的纯文本消息
+    fn plain_text(notification: &Notification) -> String {
+        format!("{}\n\n{}", notification.title, notification.text)
+    }
+
+    /// 钉钉加签：timestamp + "\n" + secret 以 secret 为密钥做 HmacSHA256，Base64 后 URL 编码
+    fn signed_dingtalk_url(webhook_url: &str, secret: &str, timestamp: i64) -> Result<String> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .map_err(|e| AppError::Unknown(e.to_string()))?;
+        mac.update(format!("{}\n{}", timestamp, secret).as_bytes());
+        let sign = general_purpose::STANDARD.encode(mac.finalize().into_bytes());
+        let separator = if webhook_url.contains('?') { '&' } else { '?' };
+        Ok(format!(
+            "{}{}timestamp={}&sign={}",
+            webhook_url,
+            separator,
+            timestamp,
+            urlencoding::encode(&sign)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(
+            NotificationChannel::parse_timeout(Some("3".to_string())),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            NotificationChannel::parse_timeout(Some("0".to_string())),
+            DEFAULT_SEND_TIMEOUT
+        );
+        assert_eq!(
+            NotificationChannel::parse_timeout(Some("abc".to_string())),
+            DEFAULT_SEND_TIMEOUT
+        );
+        assert_eq!(
+            NotificationChannel::parse_timeout(None),
+            DEFAULT_SEND_TIMEOUT
+        );
+    }
+
+    #[test]
+    fn test_signed_dingtalk_url() {
+        assert_eq!(
+            NotificationService::signed_dingtalk_url(
+                "https://oapi.dingtalk.com/robot/send?access_token=abc",
+                "SECabc",
+                1700000000000
+            )
+            .unwrap(),
+            "https://oapi.dingtalk.com/robot/send?access_token=abc&timestamp=1700000000000&sign=jcUpW0QmtKduN03n4JqQ0PBosVjqnM8gU7fIIvsDmCM%3D"
+        );
+    }
+
+    #[test]
+    fn test_task_notification() {
+        let started_at = chrono::Utc::now();
+        let mut task = SyncTask {
+            id: 42,
+            task_name: "Sync shop.orders".to_string(),
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: "orders".to_string(),
+            target_database: "ods".to_string(),
+            target_table: "orders".to_string(),
+            status: TaskStatus::Running,
+            started_at,
+            completed_at: None,
+            error_message: None,
+            options: "{}".to_string(),
+            preflight_result: None,
+            requests: None,
+            sink_requests: None,
+        };
+        assert!(NotificationService::task_notification(&task).is_none());
+
+        task.status = TaskStatus::Failed;
+        task.completed_at = Some(started_at + chrono::Duration::seconds(200));
+        task.error_message = Some("Failed to create sink".to_string());
+        let notification = NotificationService::task_notification(&task).unwrap();
+        assert_eq!(notification.event, NotificationEvent::TaskFailed);
+        assert_eq!(notification.fields["tables"], "shop.orders -> ods.orders");
+        assert_eq!(notification.fields["duration"], "3m 20s");
+        assert!(notification.text.contains("• 错误: Failed to create sink"));
+
+        task.status = TaskStatus::Completed;
+        task.error_message = None;
+        let notification = NotificationService::task_notification(&task).unwrap();
+        assert_eq!(notification.event, NotificationEvent::TaskCompleted);
+        assert!(!notification.fields.contains_key("error"));
+    }
+}
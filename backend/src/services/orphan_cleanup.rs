@@ -0,0 +1,731 @@
+use crate::db::{ConfigRepository, ManagedObjectRepository, TaskRepository};
+use crate::generators::{RisingWaveDDLGenerator, StarRocksDDLGenerator};
+use crate::models::{
+    ManagedObject, OrphanCleanupRequest, OrphanCleanupResult, OrphanObject, OrphanObjectKind,
+    OrphanObjectRef, OrphanReason, RwSecretReference, SyncTask, TaskStatus,
+};
+use crate::services::{AuditService, ConnectionPoolManager, ConnectionService, RwSecretService};
+use crate::utils::crypto;
+use crate::utils::error::{AppError, Result};
+use mysql_async::prelude::*;
+use sqlx::{MySqlPool, PgPool, Row};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// 本工具创建的 Sink 名称后缀：{target_table}_to_sr_sink
+const SINK_SUFFIX: &str = "_to_sr_sink";
+/// 本工具创建的 CDC Source 名称后缀：{mysql_database}_source
+const SOURCE_SUFFIX: &str = "_source";
+/// 本工具在目标 schema 中创建的 SECRET
+const TOOL_SECRETS: [&str; 2] = ["mysql_pwd", "starrocks_pwd"];
+
+/// (目标 schema, 目标表)
+type PipelineKey = (String, String);
+
+/// 清理遗留的 RisingWave 对象：手动删除目标表或下线管道后，本工具创建的 Sink、Table、
+/// Source 和 SECRET 没有对应的同步任务时仍会留在 RisingWave 中
+pub struct OrphanCleanupService;
+
+/// 任务记录中的管道，按最新的任务判断状态
+#[derive(Debug, Clone, Default)]
+struct Pipeline {
+    sr_config_id: i64,
+    mysql_database: Option<String>,
+    /// 由同步任务创建（RisingWave 表属于本工具）
+    sync: bool,
+    /// 最近一次任务是清理任务
+    cleaned: bool,
+    /// 有等待中或执行中的任务，对象可能正在创建
+    active: bool,
+}
+
+#[derive(Debug, Clone)]
+struct CatalogTable {
+    schema: String,
+    name: String,
+    /// 定义中 `FROM xxx_source TABLE '...'` 引用的 Source（不带 schema）
+    source: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct CatalogSecret {
+    schema: String,
+    name: String,
+    references: Vec<RwSecretReference>,
+}
+
+/// 同步目标 schema 中符合本工具命名规则的对象
+#[derive(Debug, Clone, Default)]
+struct RwCatalog {
+    sinks: Vec<(String, String)>,
+    tables: Vec<CatalogTable>,
+    sources: Vec<(String, String)>,
+    secrets: Vec<CatalogSecret>,
+}
+
+impl OrphanCleanupService {
+    /// 扫描该 RisingWave 同步目标 schema 中的遗留对象
+    pub async fn scan(app_db: &MySqlPool, rw_config_id: i64) -> Result<Vec<OrphanObject>> {
+        let task_repo = TaskRepository::new(app_db);
+        let (pipelines, uncertain) =
+            Self::pipelines(&task_repo.find_by_rw_config(rw_config_id).await?);
+        // 有无法还原目标表的任务时，该 schema 中的对象可能仍在使用，整个 schema 不扫描
+        for schema in &uncertain {
+            tracing::warn!(
+                "Skipping orphan scan of schema {}: it has tasks whose target tables cannot be reconstructed",
+                schema
+            );
+        }
+        let schemas: Vec<String> = task_repo
+            .find_target_databases(rw_config_id)
+            .await?
+            .into_iter()
+            .filter(|schema| !uncertain.contains(schema))
+            .collect();
+        if schemas.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let rw_config = ConfigRepository::new(app_db)
+            .find_by_id(rw_config_id)
+            .await?;
+        let rw_pool = ConnectionPoolManager::postgres(&rw_config).await?;
+        let managed: HashSet<String> = ManagedObjectRepository::new(app_db)
+            .list(Some(rw_config_id))
+            .await?
+            .into_iter()
+            .map(|o| o.object_name)
+            .collect();
+        let catalog = Self::catalog(&rw_pool, &schemas).await?;
+        let missing = Self::missing_targets(app_db, &pipelines).await;
+
+        Ok(Self::classify(&catalog, &pipelines, &missing, &managed))
+    }
+
+    /// 按 Sink、Table、Source、SECRET 的顺序删除遗留对象
+    /// 删除前重新扫描，请求中已不再是遗留对象的跳过；实际删除时必须列出要删除的对象
+    pub async fn cleanup(
+        app_db: &MySqlPool,
+        request: &OrphanCleanupRequest,
+        actor: Option<&str>,
+    ) -> Result<OrphanCleanupResult> {
+        if !request.dry_run && request.objects.is_none() {
+            return Err(AppError::Validation(
+                "objects is required when dry_run is false, review the scan result and list the objects to drop"
+                    .to_string(),
+            ));
+        }
+        let orphans = Self::scan(app_db, request.config_id).await?;
+        let current: HashSet<OrphanObjectRef> = orphans.iter().map(OrphanObject::key).collect();
+
+        let mut result = OrphanCleanupResult::default();
+        let mut targets: Vec<OrphanObjectRef> = match &request.objects {
+            Some(objects) => {
+                let mut targets = Vec::new();
+                for object in objects {
+                    if current.contains(object) {
+                        targets.push(object.clone());
+                    } else {
+                        result.skipped.push(Self::display(object));
+                    }
+                }
+                targets
+            }
+            None => orphans.iter().map(OrphanObject::key).collect(),
+        };
+        targets.sort_by_key(|o| o.kind);
+        targets.dedup();
+
+        let plan: Vec<(OrphanObjectRef, String)> = targets
+            .into_iter()
+            .map(|o| {
+                let ddl = Self::drop_ddl(&o);
+                (o, ddl)
+            })
+            .collect();
+        result.statements = plan.iter().map(|(_, ddl)| ddl.clone()).collect();
+        if request.dry_run || plan.is_empty() {
+            return Ok(result);
+        }
+
+        let rw_config = ConfigRepository::new(app_db)
+            .find_by_id(request.config_id)
+            .await?;
+        let rw_pool = ConnectionPoolManager::postgres(&rw_config).await?;
+        for (object, ddl) in plan {
+            let outcome = sqlx::query(&ddl).execute(&rw_pool).await;
+            let error = outcome.as_ref().err().map(|e| e.to_string());
+            AuditService::record_ddl(app_db, actor, None, "RisingWave", &ddl, error.as_deref())
+                .await;
+            match error {
+                None => {
+                    tracing::info!("Dropped orphaned {}", Self::display(&object));
+                    result.dropped.push(Self::display(&object));
+                }
+                Some(e) => {
+                    tracing::warn!("Failed to drop orphaned {}: {}", Self::display(&object), e);
+                    result.failed.push(Self::display(&object));
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    fn display(object: &OrphanObjectRef) -> String {
+        format!(
+            "{} {}",
+            object.kind.as_str(),
+            ManagedObject::qualified_name(&object.schema_name, &object.name)
+        )
+    }
+
+    /// 删除语句；表不使用 CASCADE，仍被用户创建的物化视图等依赖时删除失败
+    fn drop_ddl(object: &OrphanObjectRef) -> String {
+        let qualified = ManagedObject::qualified_name(&object.schema_name, &object.name);
+        match object.kind {
+            OrphanObjectKind::Sink => format!("DROP SINK IF EXISTS {};", qualified),
+            OrphanObjectKind::Table => RisingWaveDDLGenerator::generate_drop_table_restrict_ddl(
+                &object.schema_name,
+                &object.name,
+            ),
+            OrphanObjectKind::Source => {
+                RisingWaveDDLGenerator::generate_drop_source_ddl(&qualified)
+            }
+            OrphanObjectKind::Secret => {
+                RisingWaveDDLGenerator::generate_drop_secret_ddl(&qualified)
+            }
+        }
+    }
+
+    /// 任务记录中的管道，tasks 最新的在前；同时返回有无法还原目标表的任务的 schema
+    /// （没有记录 requests 的旧批量任务、无法解密的 sink 任务），按任务的 target_database 记录
+    /// 写入 Iceberg 的 sink 命名规则不同，不计入
+    fn pipelines(tasks: &[SyncTask]) -> (HashMap<PipelineKey, Pipeline>, HashSet<String>) {
+        let mut pipelines: HashMap<PipelineKey, Pipeline> = HashMap::new();
+        let mut uncertain = HashSet::new();
+        for task in tasks {
+            let active = matches!(task.status, TaskStatus::Pending | TaskStatus::Running);
+            if task.is_cleanup_task() {
+                let pipeline = pipelines
+                    .entry((task.target_database.clone(), task.target_table.clone()))
+                    .or_insert_with(|| Pipeline {
+                        cleaned: true,
+                        ..Default::default()
+                    });
+                pipeline.active |= active;
+                continue;
+            }
+
+            let Some(targets) = Self::task_targets(task) else {
+                uncertain.insert(task.target_database.clone());
+                continue;
+            };
+            for (key, sr_config_id, mysql_database) in targets {
+                let sync = mysql_database.is_some();
+                let pipeline = pipelines.entry(key).or_insert_with(|| Pipeline {
+                    sr_config_id,
+                    ..Default::default()
+                });
+                pipeline.active |= active;
+                pipeline.sync |= sync;
+                if pipeline.mysql_database.is_none() {
+                    pipeline.mysql_database = mysql_database;
+                }
+            }
+        }
+        // 升级前创建的对象名称被折叠为小写，按小写名称同样能找到管道
+        let aliases: Vec<(PipelineKey, Pipeline)> = pipelines
+            .iter()
+            .filter_map(|((schema, table), pipeline)| {
+                let legacy = RisingWaveDDLGenerator::legacy_name(table)?;
+                Some(((schema.clone(), legacy), pipeline.clone()))
+            })
+            .collect();
+        for (key, pipeline) in aliases {
+            pipelines.entry(key).or_insert(pipeline);
+        }
+        (pipelines, uncertain)
+    }
+
+    /// 任务写入的目标：(目标 schema, 目标表)、StarRocks 连接、MySQL 数据库（创建 sink 的任务为空）
+    /// 无法还原时返回 None
+    fn task_targets(task: &SyncTask) -> Option<Vec<(PipelineKey, i64, Option<String>)>> {
+        if let Some(encrypted) = &task.sink_requests {
+            let requests: Vec<serde_json::Value> = crypto::decrypt(encrypted)
+                .ok()
+                .and_then(|json| serde_json::from_str(&json).ok())?;
+            return Some(
+                requests
+                    .iter()
+                    .filter(|r| r.get("iceberg").is_none_or(|v| v.is_null()))
+                    .filter_map(|r| {
+                        Some((
+                            (
+                                r.get("target_database")?.as_str()?.to_string(),
+                                r.get("target_table")?.as_str()?.to_string(),
+                            ),
+                            r.get("sr_config_id")?.as_i64()?,
+                            None,
+                        ))
+                    })
+                    .collect(),
+            );
+        }
+
+        let requests = task.sync_requests().ok().filter(|r| !r.is_empty())?;
+        Some(
+            requests
+                .into_iter()
+                .map(|r| {
+                    (
+                        (r.target_database, r.target_table),
+                        r.sr_config_id,
+                        Some(r.mysql_database),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    async fn catalog(rw_pool: &PgPool, schemas: &[String]) -> Result<RwCatalog> {
+        let rows = sqlx::query(
+            "SELECT 'sink' AS kind, sch.name AS schema_name, o.name, o.definition
+             FROM rw_catalog.rw_sinks o
+             JOIN rw_catalog.rw_schemas sch ON o.schema_id = sch.id
+             WHERE sch.name = ANY($1)
+             UNION ALL
+             SELECT 'table' AS kind, sch.name AS schema_name, o.name, o.definition
+             FROM rw_catalog.rw_tables o
+             JOIN rw_catalog.rw_schemas sch ON o.schema_id = sch.id
+             WHERE sch.name = ANY($1)
+             UNION ALL
+             SELECT 'source' AS kind, sch.name AS schema_name, o.name, o.definition
+             FROM rw_catalog.rw_sources o
+             JOIN rw_catalog.rw_schemas sch ON o.schema_id = sch.id
+             WHERE sch.name = ANY($1)
+             UNION ALL
+             SELECT 'secret' AS kind, sch.name AS schema_name, o.name, '' AS definition
+             FROM rw_catalog.rw_secrets o
+             JOIN rw_catalog.rw_schemas sch ON o.schema_id = sch.id
+             WHERE sch.name = ANY($1)",
+        )
+        .bind(schemas)
+        .fetch_all(rw_pool)
+        .await?;
+
+        let mut catalog = RwCatalog::default();
+        for row in &rows {
+            let kind: String = row.get("kind");
+            let schema: String = row.get("schema_name");
+            let name: String = row.get("name");
+            match kind.as_str() {
+                "sink" if name.ends_with(SINK_SUFFIX) => catalog.sinks.push((schema, name)),
+                "table" => {
+                    let definition: String = row.get("definition");
+                    catalog.tables.push(CatalogTable {
+                        source: Self::table_source(&definition),
+                        schema,
+                        name,
+                    });
+                }
+                "source" if name.ends_with(SOURCE_SUFFIX) => catalog.sources.push((schema, name)),
+                "secret" if TOOL_SECRETS.contains(&name.as_str()) => {
+                    let references =
+                        RwSecretService::find_references(rw_pool, &schema, &name).await?;
+                    catalog.secrets.push(CatalogSecret {
+                        schema,
+                        name,
+                        references,
+                    });
+                }
+                _ => {}
+            }
+        }
+        Ok(catalog)
+    }
+
+    /// 表定义中 `FROM xxx_source TABLE 'db.table'` 引用的 Source
+    fn table_source(definition: &str) -> Option<String> {
+        let normalized = definition.replace('"', "").to_lowercase();
+        let tokens: Vec<&str> = normalized.split_whitespace().collect();
+        tokens.windows(3).find_map(|t| {
+            let source = t[1].rsplit('.').next().unwrap_or(t[1]);
+            (t[0] == "from" && source.ends_with(SOURCE_SUFFIX) && t[2] == "table")
+                .then(|| source.to_string())
+        })
+    }
+
+    /// 目标表在 StarRocks 中已不存在的管道；无法连接 StarRocks 时不做判断
+    async fn missing_targets(
+        app_db: &MySqlPool,
+        pipelines: &HashMap<PipelineKey, Pipeline>,
+    ) -> HashSet<PipelineKey> {
+        let mut by_config: BTreeMap<i64, Vec<&PipelineKey>> = BTreeMap::new();
+        for (key, pipeline) in pipelines {
+            if !pipeline.cleaned && !pipeline.active {
+                by_config
+                    .entry(pipeline.sr_config_id)
+                    .or_default()
+                    .push(key);
+            }
+        }
+
+        let mut missing = HashSet::new();
+        for (sr_config_id, keys) in by_config {
+            match Self::existing_targets(app_db, sr_config_id, &keys).await {
+                Ok(existing) => missing.extend(
+                    keys.into_iter()
+                        .filter(|(db, table)| {
+                            !existing.contains(&(db.to_lowercase(), table.to_lowercase()))
+                        })
+                        .cloned(),
+                ),
+                Err(e) => tracing::warn!(
+                    "Skipping target table check for StarRocks connection {}: {}",
+                    sr_config_id,
+                    e
+                ),
+            }
+        }
+        missing
+    }
+
+    async fn existing_targets(
+        app_db: &MySqlPool,
+        sr_config_id: i64,
+        keys: &[&PipelineKey],
+    ) -> Result<HashSet<PipelineKey>> {
+        let config = ConfigRepository::new(app_db)
+            .find_by_id(sr_config_id)
+            .await?;
+        let resolved = ConnectionService::resolve_config(config).await?;
+        let mut conn = ConnectionService::connect_starrocks(&resolved).await?;
+        let databases: HashSet<&str> = keys.iter().map(|(db, _)| db.as_str()).collect();
+        let sql = format!(
+            "SELECT TABLE_SCHEMA, TABLE_NAME FROM information_schema.tables WHERE TABLE_SCHEMA IN ({})",
+            databases
+                .iter()
+                .map(|db| StarRocksDDLGenerator::quote_string(db))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let rows: std::result::Result<Vec<(String, String)>, _> = conn.query(sql).await;
+        let _ = conn.disconnect().await;
+        Ok(rows
+            .map_err(|e| AppError::Unknown(format!("StarRocks query failed: {}", e)))?
+            .into_iter()
+            .map(|(db, table)| (db.to_lowercase(), table.to_lowercase()))
+            .collect())
+    }
+
+    /// 按任务记录判断对象是否遗留
+    /// Source 只被遗留的表引用、SECRET 只被遗留的 Source / Sink 引用且 schema 中没有
+    /// 正常的管道时同样视为遗留；共享登记的 Source / SECRET 由引用计数管理，不在此处理
+    fn classify(
+        catalog: &RwCatalog,
+        pipelines: &HashMap<PipelineKey, Pipeline>,
+        missing: &HashSet<PipelineKey>,
+        managed: &HashSet<String>,
+    ) -> Vec<OrphanObject> {
+        let reason = |schema: &str, table: &str, require_sync: bool| {
+            let key = (schema.to_string(), table.to_string());
+            match pipelines.get(&key) {
+                None => Some(OrphanReason::NoSyncTask),
+                Some(p) if p.active => None,
+                Some(p) if p.cleaned => Some(OrphanReason::PipelineCleanedUp),
+                Some(p) if require_sync && !p.sync => Some(OrphanReason::NoSyncTask),
+                Some(_) if missing.contains(&key) => Some(OrphanReason::TargetTableMissing),
+                Some(_) => None,
+            }
+        };
+        let mut orphans = Vec::new();
+        let mut push = |kind, schema: &str, name: &str, reason| {
+            orphans.push(OrphanObject {
+                kind,
+                schema_name: schema.to_string(),
+                name: name.to_string(),
+                reason,
+            })
+        };
+
+        let mut orphan_sinks = HashSet::new();
+        for (schema, name) in &catalog.sinks {
+            let table = name.strip_suffix(SINK_SUFFIX).unwrap_or(name);
+            if let Some(r) = reason(schema, table, false) {
+                push(OrphanObjectKind::Sink, schema, name, r);
+                orphan_sinks.insert((schema.clone(), name.clone()));
+            }
+        }
+
+        let mut orphan_tables = HashSet::new();
+        let mut live_tables = Vec::new();
+        for table in &catalog.tables {
+            let key = (table.schema.clone(), table.name.clone());
+            let synced = pipelines.get(&key).is_some_and(|p| p.sync || p.cleaned);
+            if !synced && table.source.is_none() {
+                continue;
+            }
+            match reason(&table.schema, &table.name, true) {
+                Some(r) => {
+                    push(OrphanObjectKind::Table, &table.schema, &table.name, r);
+                    orphan_tables.insert(key);
+                }
+                None => live_tables.push(table),
+            }
+        }
+
+        let mut orphan_sources = HashSet::new();
+        for (schema, name) in &catalog.sources {
+            if managed.contains(&ManagedObject::qualified_name(schema, name)) {
+                continue;
+            }
+            let used_by_table = live_tables.iter().any(|t| {
+                t.schema == *schema && t.source.as_deref() == Some(name.to_lowercase().as_str())
+            });
+            let used_by_pipeline = pipelines.iter().any(|((s, t), p)| {
+                s == schema
+                    && p.mysql_database.as_deref().is_some_and(|db| {
+                        format!("{}{}", db, SOURCE_SUFFIX).eq_ignore_ascii_case(name)
+                    })
+                    && reason(s, t, true).is_none()
+            });
+            if !used_by_table && !used_by_pipeline {
+                push(
+                    OrphanObjectKind::Source,
+                    schema,
+                    name,
+                    OrphanReason::Unreferenced,
+                );
+                orphan_sources.insert((schema.clone(), name.clone()));
+            }
+        }
+
+        for secret in &catalog.secrets {
+            if managed.contains(&ManagedObject::qualified_name(&secret.schema, &secret.name)) {
+                continue;
+            }
+            let referenced = secret.references.iter().any(|r| {
+                let key = (r.schema_name.clone(), r.name.clone());
+                match r.kind.as_str() {
+                    "sink" => !orphan_sinks.contains(&key),
+                    "source" => !orphan_sources.contains(&key),
+                    _ => true,
+                }
+            });
+            let live_pipeline = pipelines
+                .keys()
+                .any(|(s, t)| *s == secret.schema && reason(s, t, false).is_none());
+            if !referenced && !live_pipeline {
+                push(
+                    OrphanObjectKind::Secret,
+                    &secret.schema,
+                    &secret.name,
+                    OrphanReason::Unreferenced,
+                );
+            }
+        }
+
+        orphans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(schema: &str, table: &str) -> PipelineKey {
+        (schema.to_string(), table.to_string())
+    }
+
+    fn table(schema: &str, name: &str, source: Option<&str>) -> CatalogTable {
+        CatalogTable {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            source: source.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_classify_orphans() {
+        assert_eq!(
+            OrphanCleanupService::table_source(
+                "CREATE TABLE \"ods\".orders (id INT PRIMARY KEY) FROM \"ods\".shop_source TABLE 'shop.orders'"
+            ),
+            Some("shop_source".to_string())
+        );
+
+        let synced = Pipeline {
+            sr_config_id: 3,
+            mysql_database: Some("shop".to_string()),
+            sync: true,
+            ..Default::default()
+        };
+        let mut pipelines = HashMap::new();
+        pipelines.insert(key("ods", "orders"), synced.clone());
+        pipelines.insert(key("ods", "items"), synced.clone());
+        pipelines.insert(
+            key("ods", "users"),
+            Pipeline {
+                cleaned: true,
+                ..Default::default()
+            },
+        );
+        pipelines.insert(
+            key("dw", "stock"),
+            Pipeline {
+                mysql_database: Some("wms".to_string()),
+                ..synced
+            },
+        );
+        let missing: HashSet<PipelineKey> = [key("ods", "items"), key("dw", "stock")].into();
+
+        let sinks = |schema: &str, names: &[&str]| {
+            names
+                .iter()
+                .map(|n| (schema.to_string(), format!("{}_to_sr_sink", n)))
+                .collect::<Vec<_>>()
+        };
+        let secret = |schema: &str, name: &str, refs: &[(&str, &str)]| CatalogSecret {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            references: refs
+                .iter()
+                .map(|(kind, n)| RwSecretReference {
+                    kind: kind.to_string(),
+                    schema_name: schema.to_string(),
+                    name: n.to_string(),
+                })
+                .collect(),
+        };
+        let catalog = RwCatalog {
+            sinks: [
+                sinks("ods", &["orders", "items", "users", "ghost"]),
+                sinks("dw", &["stock"]),
+            ]
+            .concat(),
+            tables: vec![
+                table("ods", "orders", Some("shop_source")),
+                table("ods", "items", Some("shop_source")),
+                table("ods", "users", Some("shop_source")),
+                table("ods", "manual", None),
+                table("dw", "stock", Some("wms_source")),
+            ],
+            sources: vec![key("ods", "shop_source"), key("dw", "wms_source")],
+            secrets: vec![
+                secret("ods", "mysql_pwd", &[("source", "shop_source")]),
+                secret("dw", "mysql_pwd", &[("source", "wms_source")]),
+                secret("dw", "starrocks_pwd", &[("sink", "stock_to_sr_sink")]),
+            ],
+        };
+
+        let orphans = OrphanCleanupService::classify(
+            &catalog,
+            &pipelines,
+            &missing,
+            &[ManagedObject::qualified_name("dw", "mysql_pwd")].into(),
+        );
+        let found: Vec<(OrphanObjectKind, &str, &str, OrphanReason)> = orphans
+            .iter()
+            .map(|o| (o.kind, o.schema_name.as_str(), o.name.as_str(), o.reason))
+            .collect();
+        use OrphanObjectKind::*;
+        use OrphanReason::*;
+        assert_eq!(
+            found,
+            vec![
+                (Sink, "ods", "items_to_sr_sink", TargetTableMissing),
+                (Sink, "ods", "users_to_sr_sink", PipelineCleanedUp),
+                (Sink, "ods", "ghost_to_sr_sink", NoSyncTask),
+                (Sink, "dw", "stock_to_sr_sink", TargetTableMissing),
+                (Table, "ods", "items", TargetTableMissing),
+                (Table, "ods", "users", PipelineCleanedUp),
+                (Table, "dw", "stock", TargetTableMissing),
+                (Source, "dw", "wms_source", Unreferenced),
+                (Secret, "dw", "starrocks_pwd", Unreferenced),
+            ]
+        );
+    }
+
+    fn task(target_database: &str, mysql_table: &str, requests: Option<&str>) -> SyncTask {
+        SyncTask {
+            id: 1,
+            task_name: "t".to_string(),
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: mysql_table.to_string(),
+            target_database: target_database.to_string(),
+            target_table: "orders".to_string(),
+            status: TaskStatus::Completed,
+            started_at: chrono::Utc::now(),
+            completed_at: None,
+            error_message: None,
+            options: "{}".to_string(),
+            preflight_result: None,
+            requests: requests.map(str::to_string),
+            sink_requests: None,
+        }
+    }
+
+    #[test]
+    fn test_pipelines_skip_unreconstructable_tasks() {
+        let (pipelines, uncertain) = OrphanCleanupService::pipelines(&[
+            task("ods", "orders", None),
+            // 没有记录 requests 的旧批量任务，写入的表无法还原
+            task("dw", "[Batch: shop.a, shop.b]", None),
+            task("ads", "orders", Some("not json")),
+        ]);
+        assert!(pipelines.contains_key(&key("ods", "orders")));
+        assert_eq!(pipelines.len(), 1);
+        assert_eq!(uncertain, ["dw".to_string(), "ads".to_string()].into());
+
+        // 升级前同步的大小写混合表，对象名称是小写的，不算遗留
+        let requests = serde_json::to_string(&[crate::models::SyncRequest {
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "Shop".to_string(),
+            mysql_table: "Orders".to_string(),
+            target_database: "ods".to_string(),
+            target_table: "Orders".to_string(),
+            options: Default::default(),
+            row_filter: None,
+            partition: None,
+            column_mapping: Default::default(),
+            nested_columns: Default::default(),
+            spatial_columns: Default::default(),
+        }])
+        .unwrap();
+        let (pipelines, _) =
+            OrphanCleanupService::pipelines(&[task("ods", "Orders", Some(&requests))]);
+        let catalog = RwCatalog {
+            sinks: vec![key("ods", "orders_to_sr_sink")],
+            tables: vec![table("ods", "orders", Some("shop_source"))],
+            sources: vec![key("ods", "shop_source")],
+            secrets: vec![],
+        };
+        assert!(
+            OrphanCleanupService::classify(&catalog, &pipelines, &HashSet::new(), &HashSet::new())
+                .is_empty()
+        );
+
+        let request: OrphanCleanupRequest = serde_json::from_str(r#"{"config_id": 1}"#).unwrap();
+        assert!(request.dry_run);
+        assert!(request.objects.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_requires_objects() {
+        // 校验在连接数据库之前完成
+        let pool = MySqlPool::connect_lazy("mysql://localhost/unused").unwrap();
+        let request = OrphanCleanupRequest {
+            config_id: 1,
+            objects: None,
+            dry_run: false,
+        };
+        assert!(matches!(
+            OrphanCleanupService::cleanup(&pool, &request, None).await,
+            Err(AppError::Validation(_))
+        ));
+    }
+}
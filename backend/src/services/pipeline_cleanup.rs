@@ -0,0 +1,727 @@
+use crate::db::{ConfigRepository, TaskRepository};
+use crate::generators::{RisingWaveDDLGenerator, StarRocksDDLGenerator};
+use crate::models::{
+    CLEANUP_TASK_MARKER, CleanupMode, PipelineCleanupRequest, PipelineCleanupResult,
+    PipelineCleanupStatement, RetryPolicy, SyncOptions, SyncRequest, SyncTask, TaskFilter,
+    TaskLogLevel, TaskStatus, TaskTeardownRequest, TaskTeardownResult,
+};
+use crate::services::{
+    ConnectionService, DdlExecutor, LegacyNameService, ManagedObjectService, NotificationService,
+    TaskLogger,
+};
+use crate::utils::error::{AppError, Result};
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+
+/// 查找管道的同步任务时最多扫描的任务数
+const PIPELINE_TASK_SCAN_LIMIT: i64 = 1000;
+
+/// 已下线管道的目标对象清理（需要显式确认）
+/// 按顺序删除 RisingWave sink、RisingWave 表，再删除 StarRocks 目标表或重命名到回收名称
+pub struct PipelineCleanupService;
+
+impl PipelineCleanupService {
+    /// dry_run 时只返回将执行的语句，否则创建清理任务在后台执行
+    pub async fn cleanup(
+        app_db: &MySqlPool,
+        request: &PipelineCleanupRequest,
+    ) -> Result<PipelineCleanupResult> {
+        if !request.dry_run && !request.is_confirmed() {
+            return Err(AppError::Validation(format!(
+                "Type '{}' to confirm the cleanup",
+                request.expected_confirm()
+            )));
+        }
+
+        let tasks = TaskRepository::new(app_db)
+            .find_history(
+                &TaskFilter {
+                    target_table: Some(request.target_table.clone()),
+                    ..Default::default()
+                },
+                PIPELINE_TASK_SCAN_LIMIT,
+                0,
+            )
+            .await?;
+        let pipeline = Self::latest_request(&tasks, request).ok_or_else(|| {
+            AppError::NotFound(format!(
+                "No sync task found for {}",
+                request.expected_confirm()
+            ))
+        })?;
+        if let Some(active) = tasks.iter().find(|task| {
+            matches!(task.status, TaskStatus::Pending | TaskStatus::Running)
+                && task.rw_config_id == request.rw_config_id
+                && task.target_database == request.target_database
+        }) {
+            return Err(AppError::Conflict(format!(
+                "Task {} of this pipeline is still {}",
+                active.id,
+                active.status.as_str()
+            )));
+        }
+
+        let trash_table = (request.mode == CleanupMode::Trash)
+            .then(|| Self::trash_table_name(&request.target_table, Utc::now()));
+        let result = PipelineCleanupResult {
+            statements: Self::plan(request, trash_table.as_deref()),
+            trash_table,
+            task_id: None,
+        };
+        if request.dry_run {
+            return Ok(result);
+        }
+
+        let task_id = Self::spawn_cleanup(app_db, pipeline, result.clone()).await?;
+        Ok(PipelineCleanupResult {
+            task_id: Some(task_id),
+            ..result
+        })
+    }
+
+    /// 该目标表最近一次同步的请求（任务按开始时间倒序）
+    fn latest_request(tasks: &[SyncTask], request: &PipelineCleanupRequest) -> Option<SyncRequest> {
+        tasks
+            .iter()
+            .filter_map(|task| task.sync_requests().ok())
+            .flatten()
+            .find(|r| {
+                r.rw_config_id == request.rw_config_id
+                    && r.target_database == request.target_database
+                    && r.target_table == request.target_table
+            })
+    }
+
+    /// 回收表名：`{table}__trash_{YYYYmmddHHMMSS}`
+    pub fn trash_table_name(table: &str, now: DateTime<Utc>) -> String {
+        format!("{}__trash_{}", table, now.format("%Y%m%d%H%M%S"))
+    }
+
+    /// 按执行顺序生成清理语句
+    /// RisingWave 表不带 CASCADE 删除，仍被用户创建的物化视图等依赖时任务失败，不会误删下游对象
+    pub fn plan(
+        request: &PipelineCleanupRequest,
+        trash_table: Option<&str>,
+    ) -> Vec<PipelineCleanupStatement> {
+        let db = &request.target_database;
+        let table = &request.target_table;
+        let sr_sql = match trash_table {
+            Some(trash) => StarRocksDDLGenerator::generate_rename_table_ddl(db, table, trash),
+            None => StarRocksDDLGenerator::generate_drop_table_ddl(db, table),
+        };
+        vec![
+            PipelineCleanupStatement {
+                system: "RisingWave".to_string(),
+                sql: RisingWaveDDLGenerator::generate_drop_sink_ddl(db, table),
+            },
+            PipelineCleanupStatement {
+                system: "RisingWave".to_string(),
+                sql: RisingWaveDDLGenerator::generate_drop_table_restrict_ddl(db, table),
+            },
+            PipelineCleanupStatement {
+                system: "StarRocks".to_string(),
+                sql: sr_sql,
+            },
+        ]
+    }
+
+    /// 创建清理任务并在后台执行，返回 task_id
+    async fn spawn_cleanup(
+        app_db: &MySqlPool,
+        pipeline: SyncRequest,
+        plan: PipelineCleanupResult,
+    ) -> Result<i64> {
+        let task = SyncTask {
+            id: 0,
+            task_name: format!(
+                "Cleanup {}.{}",
+                pipeline.target_database, pipeline.target_table
+            ),
+            mysql_config_id: pipeline.mysql_config_id,
+            rw_config_id: pipeline.rw_config_id,
+            sr_config_id: pipeline.sr_config_id,
+            mysql_database: pipeline.mysql_database.clone(),
+            mysql_table: CLEANUP_TASK_MARKER.to_string(),
+            target_database: pipeline.target_database.clone(),
+            target_table: pipeline.target_table.clone(),
+            status: TaskStatus::Running,
+            started_at: Utc::now(),
+            completed_at: None,
+            error_message: None,
+            options: serde_json::to_string(&SyncOptions::default())?,
+            preflight_result: None,
+            requests: Some("[]".to_string()),
+            sink_requests: None,
+        };
+        let task_id = TaskRepository::new(app_db)
+            .create_with_log(
+                &task,
+                &format!(
+                    "Task created for cleaning up {}.{}",
+                    pipeline.target_database, pipeline.target_table
+                ),
+            )
+            .await?;
+
+        let app_db = app_db.clone();
+        tokio::spawn(async move {
+            let logger = TaskLogger::new(&app_db, task_id, TaskLogLevel::default());
+            let result = Self::run_cleanup(&app_db, &logger, &pipeline, &plan).await;
+            if let Err(e) = logger.finish().await {
+                tracing::warn!("Failed to flush logs for task {}: {}", task_id, e);
+            }
+
+            let message = match &plan.trash_table {
+                Some(trash) => format!("Cleaned up pipeline, StarRocks table renamed to {}", trash),
+                None => "Cleaned up pipeline, StarRocks table dropped".to_string(),
+            };
+            Self::finish_task(&app_db, task_id, result, &message).await;
+        });
+
+        Ok(task_id)
+    }
+
+    async fn run_cleanup(
+        app_db: &MySqlPool,
+        logger: &TaskLogger<'_>,
+        pipeline: &SyncRequest,
+        plan: &PipelineCleanupResult,
+    ) -> Result<()> {
+        let config_repo = ConfigRepository::new(app_db);
+        let rw_config =
+            ConnectionService::resolve_config(config_repo.find_by_id(pipeline.rw_config_id).await?)
+                .await?;
+        let sr_config =
+            ConnectionService::resolve_config(config_repo.find_by_id(pipeline.sr_config_id).await?)
+                .await?;
+        let mut executor = DdlExecutor::connect(
+            logger,
+            RetryPolicy::global_defaults(),
+            &rw_config,
+            &sr_config,
+        )
+        .await?;
+        // 升级前以小写名称创建的对象先改为当前名称，再按当前名称删除
+        LegacyNameService::adopt(&executor, logger, std::slice::from_ref(pipeline)).await?;
+
+        let db = &pipeline.target_database;
+        let table = &pipeline.target_table;
+        for statement in &plan.statements {
+            if statement.system == "RisingWave" {
+                logger.step(&format!("Executing {}", statement.sql)).await?;
+                executor
+                    .rw("drop RisingWave object", &statement.sql, true)
+                    .await?;
+                continue;
+            }
+
+            // RisingWave 表已删除，释放共享 Source 的引用
+            Self::release_shared(app_db, &executor, logger, pipeline.rw_config_id, db, table)
+                .await?;
+
+            Self::clean_starrocks_table(
+                &mut executor,
+                logger,
+                db,
+                table,
+                &statement.sql,
+                plan.trash_table.is_some(),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// 释放目标表对共享 Source / Secret 的引用，删除不再被引用的共享对象
+    async fn release_shared(
+        app_db: &MySqlPool,
+        executor: &DdlExecutor<'_>,
+        logger: &TaskLogger<'_>,
+        rw_config_id: i64,
+        db: &str,
+        table: &str,
+    ) -> Result<()> {
+        let released =
+            ManagedObjectService::release_table(app_db, executor.rw_pool(), rw_config_id, db, table)
+                .await?;
+        for object in released {
+            logger
+                .info(&format!(
+                    "Dropped unused shared object {}",
+                    object.object_name
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// 删除或重命名 StarRocks 目标表，重命名时表不存在只记录警告
+    async fn clean_starrocks_table(
+        executor: &mut DdlExecutor<'_>,
+        logger: &TaskLogger<'_>,
+        db: &str,
+        table: &str,
+        sql: &str,
+        rename: bool,
+    ) -> Result<()> {
+        if rename {
+            let exists: Option<i32> = executor
+                .sr_query_first(
+                    "check table existence",
+                    &format!(
+                        "SELECT 1 FROM information_schema.tables WHERE table_schema = {} AND table_name = {} LIMIT 1",
+                        StarRocksDDLGenerator::quote_string(db),
+                        StarRocksDDLGenerator::quote_string(table)
+                    ),
+                )
+                .await?;
+            if exists.is_none() {
+                logger
+                    .warn(&format!(
+                        "StarRocks table {}.{} does not exist, skip renaming",
+                        db, table
+                    ))
+                    .await?;
+                return Ok(());
+            }
+        }
+        logger.step(&format!("Executing {}", sql)).await?;
+        executor.sr("clean up StarRocks table", sql, true).await
+    }
+
+    /// 结束清理 / 拆除任务并发送通知
+    async fn finish_task(app_db: &MySqlPool, task_id: i64, result: Result<()>, message: &str) {
+        let task_repo = TaskRepository::new(app_db);
+        let finished = match result {
+            Ok(()) => {
+                task_repo
+                    .finish_with_log(task_id, TaskStatus::Completed, None, "info", message)
+                    .await
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                tracing::error!("Cleanup task {} failed: {}", task_id, error_msg);
+                task_repo
+                    .finish_with_log(
+                        task_id,
+                        TaskStatus::Failed,
+                        Some(error_msg.clone()),
+                        "error",
+                        &format!("Cleanup failed: {}", error_msg),
+                    )
+                    .await
+            }
+        };
+        match finished {
+            Ok(()) => NotificationService::notify_task_finished(app_db, task_id),
+            Err(e) => tracing::warn!("Failed to finish task {}: {}", task_id, e),
+        }
+    }
+
+    /// 拆除同步任务创建的管道：dry_run 时只返回将执行的语句，否则创建拆除任务在后台执行
+    pub async fn teardown(
+        app_db: &MySqlPool,
+        task_id: i64,
+        request: &TaskTeardownRequest,
+    ) -> Result<TaskTeardownResult> {
+        if !request.dry_run && !request.is_confirmed(task_id) {
+            return Err(AppError::Validation(format!(
+                "Type '{}' to confirm the teardown",
+                task_id
+            )));
+        }
+
+        let repo = TaskRepository::new(app_db);
+        let task = repo.find_by_id(task_id).await?;
+        if task.is_sink_task() || task.is_cleanup_task() {
+            return Err(AppError::Validation(
+                "Only sync tasks can be torn down".to_string(),
+            ));
+        }
+        let requests = task.sync_requests()?;
+        if requests.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Task {} did not record its tables and cannot be torn down",
+                task_id
+            )));
+        }
+        let active = repo
+            .find_active_syncs(task.mysql_config_id, task.rw_config_id, task.sr_config_id)
+            .await?;
+        if let Some(active) = active.iter().find(|t| {
+            t.id == task_id
+                || t.sync_requests().unwrap_or_default().iter().any(|r| {
+                    requests.iter().any(|q| {
+                        q.target_database == r.target_database && q.target_table == r.target_table
+                    })
+                })
+        }) {
+            return Err(AppError::Conflict(format!(
+                "Task {} of this pipeline is still {}",
+                active.id,
+                active.status.as_str()
+            )));
+        }
+
+        let steps = Self::teardown_steps(&requests, request, Utc::now());
+        let result = TaskTeardownResult {
+            statements: steps.iter().map(TeardownStep::statement).collect(),
+            trash_tables: steps
+                .iter()
+                .filter_map(|step| match step {
+                    TeardownStep::StarRocks {
+                        db,
+                        trash: Some(trash),
+                        ..
+                    } => Some(format!("{}.{}", db, trash)),
+                    _ => None,
+                })
+                .collect(),
+            task_id: None,
+        };
+        if request.dry_run {
+            return Ok(result);
+        }
+
+        let teardown_task_id = Self::spawn_teardown(app_db, &task, requests, steps).await?;
+        Ok(TaskTeardownResult {
+            task_id: Some(teardown_task_id),
+            ..result
+        })
+    }
+
+    /// 拆除步骤：先删除所有 sink 和 RisingWave 表，再删除 CDC Source，最后处理 StarRocks 目标表
+    /// 共享 Source 由引用计数管理，不生成删除步骤
+    fn teardown_steps(
+        requests: &[SyncRequest],
+        request: &TaskTeardownRequest,
+        now: DateTime<Utc>,
+    ) -> Vec<TeardownStep> {
+        let mut steps: Vec<TeardownStep> = requests
+            .iter()
+            .map(|r| TeardownStep::Sink {
+                db: r.target_database.clone(),
+                table: r.target_table.clone(),
+            })
+            .collect();
+        steps.extend(requests.iter().map(|r| TeardownStep::Table {
+            db: r.target_database.clone(),
+            table: r.target_table.clone(),
+        }));
+        if request.drop_source {
+            let mut sources: Vec<(String, String)> = requests
+                .iter()
+                .filter(|r| !r.options.share_source)
+                .map(|r| {
+                    (
+                        r.target_database.clone(),
+                        format!("{}_source", r.mysql_database),
+                    )
+                })
+                .collect();
+            sources.sort();
+            sources.dedup();
+            steps.extend(
+                sources
+                    .into_iter()
+                    .map(|(db, name)| TeardownStep::Source { db, name }),
+            );
+        }
+        steps.extend(requests.iter().map(|r| TeardownStep::StarRocks {
+            db: r.target_database.clone(),
+            table: r.target_table.clone(),
+            trash: (request.mode == CleanupMode::Trash)
+                .then(|| Self::trash_table_name(&r.target_table, now)),
+        }));
+        steps
+    }
+
+    /// 创建拆除任务并在后台执行，返回 task_id
+    async fn spawn_teardown(
+        app_db: &MySqlPool,
+        source_task: &SyncTask,
+        requests: Vec<SyncRequest>,
+        steps: Vec<TeardownStep>,
+    ) -> Result<i64> {
+        let first = &requests[0];
+        let (target_database, target_table) = if requests.len() == 1 {
+            (first.target_database.clone(), first.target_table.clone())
+        } else {
+            (
+                first.target_database.clone(),
+                format!("[Batch: {} tables]", requests.len()),
+            )
+        };
+        let task = SyncTask {
+            id: 0,
+            task_name: format!("Teardown task {}", source_task.id),
+            mysql_config_id: source_task.mysql_config_id,
+            rw_config_id: source_task.rw_config_id,
+            sr_config_id: source_task.sr_config_id,
+            mysql_database: source_task.mysql_database.clone(),
+            mysql_table: CLEANUP_TASK_MARKER.to_string(),
+            target_database,
+            target_table,
+            status: TaskStatus::Running,
+            started_at: Utc::now(),
+            completed_at: None,
+            error_message: None,
+            options: serde_json::to_string(&SyncOptions::default())?,
+            preflight_result: None,
+            requests: Some("[]".to_string()),
+            sink_requests: None,
+        };
+        let task_id = TaskRepository::new(app_db)
+            .create_with_log(
+                &task,
+                &format!(
+                    "Task created for tearing down {} tables of task {}",
+                    requests.len(),
+                    source_task.id
+                ),
+            )
+            .await?;
+
+        let app_db = app_db.clone();
+        let rw_config_id = source_task.rw_config_id;
+        let sr_config_id = source_task.sr_config_id;
+        tokio::spawn(async move {
+            let logger = TaskLogger::new(&app_db, task_id, TaskLogLevel::default());
+            let result = Self::run_teardown(
+                &app_db,
+                &logger,
+                rw_config_id,
+                sr_config_id,
+                &requests,
+                &steps,
+            )
+            .await;
+            if let Err(e) = logger.finish().await {
+                tracing::warn!("Failed to flush logs for task {}: {}", task_id, e);
+            }
+            let message = format!("Tore down {} tables", requests.len());
+            Self::finish_task(&app_db, task_id, result, &message).await;
+        });
+
+        Ok(task_id)
+    }
+
+    async fn run_teardown(
+        app_db: &MySqlPool,
+        logger: &TaskLogger<'_>,
+        rw_config_id: i64,
+        sr_config_id: i64,
+        requests: &[SyncRequest],
+        steps: &[TeardownStep],
+    ) -> Result<()> {
+        let config_repo = ConfigRepository::new(app_db);
+        let rw_config =
+            ConnectionService::resolve_config(config_repo.find_by_id(rw_config_id).await?).await?;
+        let sr_config =
+            ConnectionService::resolve_config(config_repo.find_by_id(sr_config_id).await?).await?;
+        let mut executor = DdlExecutor::connect(
+            logger,
+            RetryPolicy::global_defaults(),
+            &rw_config,
+            &sr_config,
+        )
+        .await?;
+        LegacyNameService::adopt(&executor, logger, requests).await?;
+
+        for step in steps {
+            let sql = step.statement().sql;
+            match step {
+                TeardownStep::Sink { .. } => {
+                    logger.step(&format!("Executing {}", sql)).await?;
+                    executor.rw("drop RisingWave sink", &sql, true).await?;
+                }
+                TeardownStep::Table { db, table } => {
+                    logger.step(&format!("Executing {}", sql)).await?;
+                    executor.rw("drop RisingWave table", &sql, true).await?;
+                    Self::release_shared(app_db, &executor, logger, rw_config_id, db, table)
+                        .await?;
+                }
+                TeardownStep::Source { db, name } => {
+                    let dependents: i64 = sqlx::query_scalar(
+                        "SELECT COUNT(*) FROM rw_catalog.rw_depend d
+                         JOIN rw_catalog.rw_sources s ON d.refobjid = s.id
+                         JOIN rw_catalog.rw_schemas sch ON s.schema_id = sch.id
+                         WHERE sch.name = $1 AND s.name = $2",
+                    )
+                    .bind(db)
+                    .bind(name)
+                    .fetch_one(executor.rw_pool())
+                    .await?;
+                    if dependents > 0 {
+                        logger
+                            .warn(&format!(
+                                "Source {} is still used by {} tables, skip dropping",
+                                RisingWaveDDLGenerator::qualified_name(db, name),
+                                dependents
+                            ))
+                            .await?;
+                        continue;
+                    }
+                    logger.step(&format!("Executing {}", sql)).await?;
+                    executor.rw("drop CDC source", &sql, true).await?;
+                }
+                TeardownStep::StarRocks { db, table, trash } => {
+                    Self::clean_starrocks_table(
+                        &mut executor,
+                        logger,
+                        db,
+                        table,
+                        &sql,
+                        trash.is_some(),
+                    )
+                    .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 拆除步骤，按执行顺序排列
+#[derive(Debug, Clone)]
+enum TeardownStep {
+    Sink { db: String, table: String },
+    Table { db: String, table: String },
+    /// 执行前检查是否仍被其他表使用，仍在使用时跳过
+    Source { db: String, name: String },
+    StarRocks {
+        db: String,
+        table: String,
+        trash: Option<String>,
+    },
+}
+
+impl TeardownStep {
+    fn statement(&self) -> PipelineCleanupStatement {
+        let (system, sql) = match self {
+            TeardownStep::Sink { db, table } => (
+                "RisingWave",
+                RisingWaveDDLGenerator::generate_drop_sink_ddl(db, table),
+            ),
+            TeardownStep::Table { db, table } => (
+                "RisingWave",
+                RisingWaveDDLGenerator::generate_drop_table_restrict_ddl(db, table),
+            ),
+            TeardownStep::Source { db, name } => (
+                "RisingWave",
+                RisingWaveDDLGenerator::generate_drop_source_ddl(
+                    &RisingWaveDDLGenerator::qualified_name(db, name),
+                ),
+            ),
+            TeardownStep::StarRocks { db, table, trash } => (
+                "StarRocks",
+                match trash {
+                    Some(trash) => StarRocksDDLGenerator::generate_rename_table_ddl(db, table, trash),
+                    None => StarRocksDDLGenerator::generate_drop_table_ddl(db, table),
+                },
+            ),
+        };
+        PipelineCleanupStatement {
+            system: system.to_string(),
+            sql,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn request(mode: CleanupMode, confirm: Option<&str>) -> PipelineCleanupRequest {
+        PipelineCleanupRequest {
+            rw_config_id: 1,
+            target_database: "ods".to_string(),
+            target_table: "orders".to_string(),
+            mode,
+            confirm: confirm.map(str::to_string),
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn test_cleanup_plan() {
+        assert!(request(CleanupMode::Drop, Some(" ods.orders ")).is_confirmed());
+        assert!(!request(CleanupMode::Drop, Some("orders")).is_confirmed());
+        assert!(!request(CleanupMode::Drop, None).is_confirmed());
+
+        let now = Utc.with_ymd_and_hms(2024, 3, 5, 8, 9, 10).unwrap();
+        let trash = PipelineCleanupService::trash_table_name("orders", now);
+        assert_eq!(trash, "orders__trash_20240305080910");
+
+        let plan = PipelineCleanupService::plan(&request(CleanupMode::Trash, None), Some(&trash));
+        let sqls: Vec<&str> = plan.iter().map(|s| s.sql.as_str()).collect();
+        assert_eq!(
+            sqls,
+            vec![
+                "DROP SINK IF EXISTS \"ods\".orders_to_sr_sink;",
+                "DROP TABLE IF EXISTS \"ods\".orders;",
+                "ALTER TABLE `ods`.`orders` RENAME `orders__trash_20240305080910`;",
+            ]
+        );
+
+        let plan = PipelineCleanupService::plan(&request(CleanupMode::Drop, None), None);
+        assert_eq!(plan[2].system, "StarRocks");
+        assert_eq!(plan[2].sql, "DROP TABLE IF EXISTS `ods`.`orders`;");
+    }
+
+    #[test]
+    fn test_teardown_steps() {
+        let table = |db: &str, mysql_table: &str, share_source: bool| SyncRequest {
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "Shop".to_string(),
+            mysql_table: mysql_table.to_string(),
+            target_database: db.to_string(),
+            target_table: mysql_table.to_string(),
+            options: SyncOptions {
+                share_source,
+                ..Default::default()
+            },
+            row_filter: None,
+            partition: None,
+            column_mapping: Default::default(),
+            nested_columns: Default::default(),
+            spatial_columns: Default::default(),
+        };
+        let requests = vec![
+            table("ods", "orders", false),
+            table("ods", "items", false),
+            table("dw", "users", true),
+        ];
+        let request = TaskTeardownRequest {
+            mode: CleanupMode::Drop,
+            drop_source: true,
+            confirm: None,
+            dry_run: true,
+        };
+        let now = Utc.with_ymd_and_hms(2024, 3, 5, 8, 9, 10).unwrap();
+        let sqls: Vec<String> = PipelineCleanupService::teardown_steps(&requests, &request, now)
+            .iter()
+            .map(|step| step.statement().sql)
+            .collect();
+        assert_eq!(
+            sqls,
+            vec![
+                "DROP SINK IF EXISTS \"ods\".orders_to_sr_sink;",
+                "DROP SINK IF EXISTS \"ods\".items_to_sr_sink;",
+                "DROP SINK IF EXISTS \"dw\".users_to_sr_sink;",
+                "DROP TABLE IF EXISTS \"ods\".orders;",
+                "DROP TABLE IF EXISTS \"ods\".items;",
+                "DROP TABLE IF EXISTS \"dw\".users;",
+                "DROP SOURCE IF EXISTS \"ods\".\"Shop_source\";",
+                "DROP TABLE IF EXISTS `ods`.`orders`;",
+                "DROP TABLE IF EXISTS `ods`.`items`;",
+                "DROP TABLE IF EXISTS `dw`.`users`;",
+            ]
+        );
+
+        assert!(!request.is_confirmed(42));
+    }
+}
@@ -0,0 +1,304 @@
+use crate::db::{ConfigRepository, ManagedObjectRepository, TaskRepository};
+use crate::generators::RisingWaveDDLGenerator;
+use crate::models::{
+    DeleteUnusedSecretsResult, ManagedObject, ManagedObjectType, RwSecretReference, RwSecretUsage,
+};
+use crate::services::{AuditService, ConnectionPoolManager, ManagedObjectService};
+use crate::utils::error::{AppError, Result};
+use sqlx::{MySqlPool, PgPool, Row};
+
+/// 同步目标 schema 中的 RisingWave SECRET：查看引用关系，删除不再使用的 SECRET
+pub struct RwSecretService;
+
+/// Source / Sink 的定义语句，用于查找引用的 SECRET
+struct ObjectDefinition {
+    reference: RwSecretReference,
+    definition: String,
+}
+
+impl RwSecretService {
+    /// 列出同步目标 schema 中的 SECRET 及引用它们的 Source / Sink
+    pub async fn list(app_db: &MySqlPool, rw_config_id: i64) -> Result<Vec<RwSecretUsage>> {
+        let rw_pool = Self::rw_pool(app_db, rw_config_id).await?;
+        let schemas = TaskRepository::new(app_db)
+            .find_target_databases(rw_config_id)
+            .await?;
+        if schemas.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let managed: Vec<String> = ManagedObjectRepository::new(app_db)
+            .list(Some(rw_config_id))
+            .await?
+            .into_iter()
+            .filter(|o| o.object_type == ManagedObjectType::Secret)
+            .map(|o| o.object_name)
+            .collect();
+        let definitions = Self::object_definitions(&rw_pool).await?;
+
+        let rows = sqlx::query(
+            "SELECT sec.id, sec.name, sch.name AS schema_name
+             FROM rw_catalog.rw_secrets sec
+             JOIN rw_catalog.rw_schemas sch ON sec.schema_id = sch.id
+             WHERE sch.name = ANY($1)
+             ORDER BY sch.name, sec.name",
+        )
+        .bind(&schemas)
+        .fetch_all(&rw_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let name: String = row.get("name");
+                let schema_name: String = row.get("schema_name");
+                RwSecretUsage {
+                    id: row.get("id"),
+                    managed: managed.contains(&ManagedObject::qualified_name(&schema_name, &name)),
+                    references: Self::references(&definitions, &schema_name, &name),
+                    name,
+                    schema_name,
+                }
+            })
+            .collect())
+    }
+
+    /// 定义中引用该 SECRET 的 Source / Sink
+    pub async fn find_references(
+        rw_pool: &PgPool,
+        schema: &str,
+        name: &str,
+    ) -> Result<Vec<RwSecretReference>> {
+        Ok(Self::references(
+            &Self::object_definitions(rw_pool).await?,
+            schema,
+            name,
+        ))
+    }
+
+    /// 删除 SECRET，仍被 Source / Sink 引用时返回 Conflict
+    /// force 只跳过共享对象登记的引用计数检查
+    pub async fn delete(
+        app_db: &MySqlPool,
+        rw_config_id: i64,
+        schema: &str,
+        name: &str,
+        force: bool,
+        actor: Option<&str>,
+    ) -> Result<()> {
+        let qualified = ManagedObject::qualified_name(schema, name);
+        let managed = ManagedObjectService::check_drop(
+            app_db,
+            rw_config_id,
+            ManagedObjectType::Secret,
+            &qualified,
+            force,
+        )
+        .await?;
+
+        let rw_pool = Self::rw_pool(app_db, rw_config_id).await?;
+        let references = Self::find_references(&rw_pool, schema, name).await?;
+        if !references.is_empty() {
+            return Err(AppError::Conflict(format!(
+                "Secret {} is still used by {}",
+                qualified,
+                references
+                    .iter()
+                    .map(|r| format!("{} \"{}\".{}", r.kind, r.schema_name, r.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        Self::drop_secret(app_db, &rw_pool, &qualified, actor).await?;
+        if let Some(object) = managed {
+            ManagedObjectService::forget(app_db, &object).await?;
+        }
+        Ok(())
+    }
+
+    /// 删除同步目标 schema 中所有没有被引用的 SECRET，dry_run 时只返回名称
+    pub async fn delete_unused(
+        app_db: &MySqlPool,
+        rw_config_id: i64,
+        dry_run: bool,
+        actor: Option<&str>,
+    ) -> Result<DeleteUnusedSecretsResult> {
+        let unused: Vec<RwSecretUsage> = Self::list(app_db, rw_config_id)
+            .await?
+            .into_iter()
+            .filter(|s| s.references.is_empty())
+            .collect();
+        let mut result = DeleteUnusedSecretsResult {
+            secrets: unused
+                .iter()
+                .map(|s| ManagedObject::qualified_name(&s.schema_name, &s.name))
+                .collect(),
+            failed: vec![],
+        };
+        if dry_run || unused.is_empty() {
+            return Ok(result);
+        }
+
+        let rw_pool = Self::rw_pool(app_db, rw_config_id).await?;
+        let repo = ManagedObjectRepository::new(app_db);
+        let mut dropped = Vec::new();
+        for qualified in result.secrets {
+            if let Err(e) = Self::drop_secret(app_db, &rw_pool, &qualified, actor).await {
+                tracing::warn!("Failed to drop unused secret {}: {}", qualified, e);
+                result.failed.push(qualified);
+                continue;
+            }
+            // 没有对象引用时登记的引用计数已无意义，一并移除
+            if let Some(object) = repo
+                .find_by_name(rw_config_id, ManagedObjectType::Secret, &qualified)
+                .await?
+            {
+                ManagedObjectService::forget(app_db, &object).await?;
+            }
+            dropped.push(qualified);
+        }
+        result.secrets = dropped;
+        Ok(result)
+    }
+
+    async fn drop_secret(
+        app_db: &MySqlPool,
+        rw_pool: &PgPool,
+        qualified: &str,
+        actor: Option<&str>,
+    ) -> Result<()> {
+        let ddl = RisingWaveDDLGenerator::generate_drop_secret_ddl(qualified);
+        let result = sqlx::query(&ddl).execute(rw_pool).await;
+        let error = result.as_ref().err().map(|e| e.to_string());
+        AuditService::record_ddl(app_db, actor, None, "RisingWave", &ddl, error.as_deref()).await;
+        result?;
+        tracing::info!("Dropped secret {}", qualified);
+        Ok(())
+    }
+
+    async fn rw_pool(app_db: &MySqlPool, rw_config_id: i64) -> Result<PgPool> {
+        let rw_config = ConfigRepository::new(app_db)
+            .find_by_id(rw_config_id)
+            .await?;
+        ConnectionPoolManager::postgres(&rw_config).await
+    }
+
+    /// 所有 Source 和 Sink 的定义语句（SECRET 可能被其他 schema 的对象引用）
+    async fn object_definitions(rw_pool: &PgPool) -> Result<Vec<ObjectDefinition>> {
+        let rows = sqlx::query(
+            "SELECT 'source' AS kind, sch.name AS schema_name, o.name, o.definition
+             FROM rw_catalog.rw_sources o
+             JOIN rw_catalog.rw_schemas sch ON o.schema_id = sch.id
+             UNION ALL
+             SELECT 'sink' AS kind, sch.name AS schema_name, o.name, o.definition
+             FROM rw_catalog.rw_sinks o
+             JOIN rw_catalog.rw_schemas sch ON o.schema_id = sch.id",
+        )
+        .fetch_all(rw_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ObjectDefinition {
+                reference: RwSecretReference {
+                    kind: row.get("kind"),
+                    schema_name: row.get("schema_name"),
+                    name: row.get("name"),
+                },
+                definition: row.get("definition"),
+            })
+            .collect())
+    }
+
+    fn references(
+        definitions: &[ObjectDefinition],
+        schema: &str,
+        name: &str,
+    ) -> Vec<RwSecretReference> {
+        definitions
+            .iter()
+            .filter(|d| {
+                Self::references_secret(&d.definition, &d.reference.schema_name, schema, name)
+            })
+            .map(|d| d.reference.clone())
+            .collect()
+    }
+
+    /// 定义语句中是否有 `secret schema.name`；未带 schema 的引用按对象所在的 schema 解析
+    fn references_secret(
+        definition: &str,
+        object_schema: &str,
+        secret_schema: &str,
+        secret_name: &str,
+    ) -> bool {
+        let normalized = definition.replace('"', "").to_lowercase();
+        let tokens: Vec<&str> = normalized
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+            .filter(|t| !t.is_empty())
+            .collect();
+        let qualified = format!("{}.{}", secret_schema, secret_name).to_lowercase();
+        let unqualified = secret_name.to_lowercase();
+        tokens.windows(2).any(|pair| {
+            pair[0] == "secret"
+                && (pair[1] == qualified
+                    || (pair[1] == unqualified
+                        && object_schema.eq_ignore_ascii_case(secret_schema)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_references_secret() {
+        let source = "CREATE SOURCE \"ods\".shop_source WITH (connector = 'mysql-cdc', \
+                      password = secret \"ods\".mysql_pwd, database.name = 'shop')";
+        assert!(RwSecretService::references_secret(
+            source,
+            "ods",
+            "ods",
+            "mysql_pwd"
+        ));
+        assert!(RwSecretService::references_secret(
+            source,
+            "dw",
+            "ODS",
+            "MYSQL_PWD"
+        ));
+        assert!(!RwSecretService::references_secret(
+            source,
+            "ods",
+            "ods",
+            "starrocks_pwd"
+        ));
+        assert!(!RwSecretService::references_secret(
+            source,
+            "ods",
+            "dw",
+            "mysql_pwd"
+        ));
+
+        let sink = "CREATE SINK orders_to_sr_sink FROM orders WITH (starrocks.password = SECRET starrocks_pwd)";
+        assert!(RwSecretService::references_secret(
+            sink,
+            "ods",
+            "ods",
+            "starrocks_pwd"
+        ));
+        assert!(!RwSecretService::references_secret(
+            sink,
+            "dw",
+            "ods",
+            "starrocks_pwd"
+        ));
+        assert!(!RwSecretService::references_secret(
+            "CREATE SINK s FROM t WITH (comment = 'mysql_pwd')",
+            "ods",
+            "ods",
+            "mysql_pwd"
+        ));
+    }
+}
@@ -0,0 +1,376 @@
+use crate::db::{ScheduleRepository, TaskRepository};
+use crate::models::{ScheduleDetail, ScheduleRequest, ScheduledRun, SyncSchedule};
+use crate::services::{ApprovalPolicy, ApprovalService, Shutdown, SyncEngine};
+use crate::utils::error::{AppError, Result};
+use chrono::{
+    DateTime, Duration, LocalResult, NaiveDateTime, Offset, SecondsFormat, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use cron::Schedule;
+use sqlx::MySqlPool;
+use std::str::FromStr;
+
+/// 检查到期定时同步的间隔（秒）
+const SCHEDULER_TICK_SECS: u64 = 30;
+/// 接口返回的接下来的运行次数
+pub const NEXT_RUNS_PREVIEW: usize = 5;
+/// 定时同步没有记录创建人时，任务和审批单使用的提交人
+const SCHEDULER_USER: &str = "scheduler";
+
+/// 内置调度器：按 cron 表达式在指定 IANA 时区的本地时间提交同步任务
+pub struct SchedulerService;
+
+impl SchedulerService {
+    /// 解析 cron 表达式和时区，5 段表达式补充秒字段
+    /// 5 段表达式按标准 cron 解释星期（0 和 7 为星期日），6 段表达式沿用 cron 库的 1 为星期日
+    pub fn parse(cron: &str, timezone: &str) -> Result<(Schedule, Tz)> {
+        let tz = Tz::from_str(timezone.trim()).map_err(|_| {
+            AppError::Validation(format!(
+                "Unknown timezone '{}' (use an IANA name like Asia/Shanghai)",
+                timezone
+            ))
+        })?;
+        let cron = cron.trim();
+        let fields: Vec<&str> = cron.split_whitespace().collect();
+        let expression = if fields.len() == 5 {
+            let weekdays = Self::standard_weekdays(fields[4]).map_err(|e| {
+                AppError::Validation(format!("Invalid cron expression '{}': {}", cron, e))
+            })?;
+            format!("0 {} {}", fields[..4].join(" "), weekdays)
+        } else {
+            cron.to_string()
+        };
+        let schedule = Schedule::from_str(&expression).map_err(|e| {
+            AppError::Validation(format!("Invalid cron expression '{}': {}", cron, e))
+        })?;
+        Ok((schedule, tz))
+    }
+
+    /// 把标准 cron 的数字星期（0-7，0 和 7 为星期日）换成 cron 库的 1-7（1 为星期日）
+    /// 数字的值、范围和步长展开为逗号列表，MON-FRI 等缩写保持不变
+    fn standard_weekdays(field: &str) -> std::result::Result<String, String> {
+        let mut items = Vec::new();
+        for item in field.split(',') {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => (range, Some(step)),
+                None => (item, None),
+            };
+            let bounds = if range == "*" && step.is_some() {
+                Some((0, 6))
+            } else if let Some((start, end)) = range.split_once('-') {
+                start.parse::<u32>().ok().zip(end.parse::<u32>().ok())
+            } else {
+                range.parse::<u32>().ok().map(|day| (day, day))
+            };
+            let Some((start, end)) = bounds else {
+                items.push(item.to_string());
+                continue;
+            };
+            if start > end || end > 7 {
+                return Err(format!("day of week '{}' must be within 0-7", item));
+            }
+            let step = match step {
+                Some(step) => step
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("invalid step in day of week '{}'", item))?,
+                None => 1,
+            };
+            items.extend((start..=end).step_by(step).map(|day| (day % 7 + 1).to_string()));
+        }
+        items.sort();
+        items.dedup();
+        Ok(items.join(","))
+    }
+
+    /// after 之后的运行时间
+    /// 本地时间按墙上时钟匹配：夏令时结束时重复的时间只运行一次，夏令时开始时跳过的时间顺延到跳变之后
+    fn upcoming(
+        schedule: &Schedule,
+        tz: Tz,
+        after: DateTime<Utc>,
+    ) -> impl Iterator<Item = DateTime<Tz>> + '_ {
+        // cron 在 UTC 中按墙上时间迭代，再换算为时区中的时刻
+        let wall_after = Utc.from_utc_datetime(&after.with_timezone(&tz).naive_local());
+        let mut last = after;
+        schedule.after(&wall_after).filter_map(move |wall| {
+            let run = Self::resolve_local(tz, wall.naive_utc());
+            let utc = run.with_timezone(&Utc);
+            if utc <= last {
+                return None;
+            }
+            last = utc;
+            Some(run)
+        })
+    }
+
+    /// 本地时间对应的时刻，重复的时间取第一次，不存在的时间按跳变前的偏移换算
+    fn resolve_local(tz: Tz, local: NaiveDateTime) -> DateTime<Tz> {
+        match tz.from_local_datetime(&local) {
+            LocalResult::Single(run) => run,
+            LocalResult::Ambiguous(earliest, _) => earliest,
+            LocalResult::None => {
+                let offset = tz
+                    .offset_from_utc_datetime(&(local - Duration::days(1)))
+                    .fix();
+                let utc = local - Duration::seconds(offset.local_minus_utc() as i64);
+                tz.from_utc_datetime(&utc)
+            }
+        }
+    }
+
+    /// 接下来的 count 次运行时间，分别以时区本地时间和 UTC 表示
+    pub fn next_runs(
+        cron: &str,
+        timezone: &str,
+        after: DateTime<Utc>,
+        count: usize,
+    ) -> Result<Vec<ScheduledRun>> {
+        let (schedule, tz) = Self::parse(cron, timezone)?;
+        Ok(Self::upcoming(&schedule, tz, after)
+            .take(count)
+            .map(|run| ScheduledRun {
+                local: run.to_rfc3339_opts(SecondsFormat::Secs, true),
+                utc: run.with_timezone(&Utc),
+            })
+            .collect())
+    }
+
+    /// 下一次运行时间，停用或表达式不再匹配任何时间时为空
+    pub fn next_run_at(
+        cron: &str,
+        timezone: &str,
+        enabled: bool,
+        after: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>> {
+        if !enabled {
+            return Ok(None);
+        }
+        Ok(Self::next_runs(cron, timezone, after, 1)?
+            .into_iter()
+            .next()
+            .map(|run| run.utc))
+    }
+
+    /// 校验创建或更新定时同步的请求
+    pub fn validate(request: &ScheduleRequest) -> Result<()> {
+        if request.name.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Schedule name is required".to_string(),
+            ));
+        }
+        if request.requests.is_empty() {
+            return Err(AppError::Validation("No tables to sync".to_string()));
+        }
+        SyncEngine::ensure_same_configs(&request.requests)?;
+        Self::parse(&request.cron, &request.timezone)?;
+        Ok(())
+    }
+
+    /// 定时同步及从现在开始的运行时间，停用时为空
+    pub fn detail(schedule: SyncSchedule) -> ScheduleDetail {
+        let next_runs = if schedule.enabled {
+            Self::next_runs(
+                &schedule.cron,
+                &schedule.timezone,
+                Utc::now(),
+                NEXT_RUNS_PREVIEW,
+            )
+            .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        ScheduleDetail {
+            schedule,
+            next_runs,
+        }
+    }
+
+    /// 启动后台调度
+    pub fn spawn(app_db: MySqlPool) {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(SCHEDULER_TICK_SECS));
+            loop {
+                interval.tick().await;
+                if Shutdown::is_draining() {
+                    break;
+                }
+                if let Err(e) = Self::run_due(&app_db).await {
+                    tracing::warn!("Failed to run due schedules: {}", e);
+                }
+            }
+        });
+    }
+
+    /// 提交所有到期的定时同步，返回处理的数量
+    /// 停机期间错过的多次运行只补一次
+    pub async fn run_due(app_db: &MySqlPool) -> Result<usize> {
+        let now = Utc::now();
+        let repo = ScheduleRepository::new(app_db);
+        let due = repo.find_due(now).await?;
+        for schedule in &due {
+            let (task_id, error) = match Self::submit(app_db, schedule).await {
+                Ok(task_id) => (task_id, None),
+                Err(e) => {
+                    tracing::warn!(
+                        "Schedule {} ({}) did not run: {}",
+                        schedule.id,
+                        schedule.name,
+                        e
+                    );
+                    (None, Some(e.to_string()))
+                }
+            };
+            let next_run_at = Self::next_run_at(&schedule.cron, &schedule.timezone, true, now)?;
+            repo.record_run(schedule.id, now, task_id, error.as_deref(), next_run_at)
+                .await?;
+        }
+        Ok(due.len())
+    }
+
+    /// 提交一次运行，需要审批时创建审批单并返回 None
+    /// 上一次运行提交的同步仍在等待或执行时不重复提交
+    async fn submit(app_db: &MySqlPool, schedule: &SyncSchedule) -> Result<Option<i64>> {
+        let engine = SyncEngine::new(app_db.clone());
+        if let Some(task_id) = engine.find_duplicate(&schedule.requests).await? {
+            return Err(AppError::Conflict(format!(
+                "Task {} with the same tables is still pending or running",
+                task_id
+            )));
+        }
+
+        let user = schedule.created_by.as_deref().unwrap_or(SCHEDULER_USER);
+        if let Some(reason) = ApprovalPolicy::from_env().reason(&schedule.requests) {
+            let approval =
+                ApprovalService::request(app_db, &schedule.requests, &reason, user).await?;
+            tracing::info!(
+                "Schedule {} ({}) requires approval, created approval request {}",
+                schedule.id,
+                schedule.name,
+                approval.id
+            );
+            return Ok(None);
+        }
+
+        let task_id = engine
+            .sync_multiple_tables(schedule.requests.clone())
+            .await?;
+        TaskRepository::new(app_db)
+            .set_created_by(task_id, user)
+            .await?;
+        tracing::info!(
+            "Schedule {} ({}) submitted task {}",
+            schedule.id,
+            schedule.name,
+            task_id
+        );
+        Ok(Some(task_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn locals(cron: &str, timezone: &str, after: &str, count: usize) -> Vec<String> {
+        SchedulerService::next_runs(cron, timezone, utc(after), count)
+            .unwrap()
+            .into_iter()
+            .map(|run| run.local)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        assert!(SchedulerService::parse("0 2 * * *", "Asia/Shanghai").is_ok());
+        assert!(SchedulerService::parse("0 0 2 * * MON-FRI", "UTC").is_ok());
+        assert!(SchedulerService::parse("0 2 * * *", "Mars/Olympus").is_err());
+        assert!(SchedulerService::parse("every day", "UTC").is_err());
+        assert!(SchedulerService::parse("0 2 * * 0", "UTC").is_ok());
+        assert!(SchedulerService::parse("0 2 * * 8", "UTC").is_err());
+    }
+
+    #[test]
+    fn test_five_field_weekdays_follow_standard_cron() {
+        // 2026-01-05 是星期一
+        let weekdays = |cron: &str| -> Vec<String> {
+            SchedulerService::next_runs(cron, "UTC", utc("2026-01-04T12:00:00Z"), 7)
+                .unwrap()
+                .into_iter()
+                .map(|run| run.utc.format("%a").to_string())
+                .collect()
+        };
+        assert_eq!(
+            weekdays("0 2 * * 1-5"),
+            vec!["Mon", "Tue", "Wed", "Thu", "Fri", "Mon", "Tue"]
+        );
+        assert_eq!(weekdays("0 2 * * 0")[0], "Sun");
+        assert_eq!(weekdays("0 2 * * 7")[0], "Sun");
+        assert_eq!(
+            weekdays("0 2 * * 5-7")[..3],
+            ["Fri", "Sat", "Sun"]
+        );
+        assert_eq!(
+            weekdays("0 2 * * MON-FRI"),
+            weekdays("0 2 * * 1-5")
+        );
+        assert_eq!(weekdays("0 2 * * */2")[..4], ["Tue", "Thu", "Sat", "Sun"]);
+    }
+
+    #[test]
+    fn test_next_runs_in_timezone() {
+        let runs = SchedulerService::next_runs(
+            "0 2 * * *",
+            "Asia/Shanghai",
+            utc("2026-01-01T00:00:00Z"),
+            2,
+        )
+        .unwrap();
+        assert_eq!(runs[0].local, "2026-01-02T02:00:00+08:00");
+        assert_eq!(runs[0].utc, utc("2026-01-01T18:00:00Z"));
+        assert_eq!(runs[1].local, "2026-01-03T02:00:00+08:00");
+    }
+
+    #[test]
+    fn test_next_runs_across_dst() {
+        // 2026-03-08 纽约 02:00 跳到 03:00，02:30 不存在，顺延到 03:30
+        assert_eq!(
+            locals("30 2 * * *", "America/New_York", "2026-03-07T12:00:00Z", 3),
+            vec![
+                "2026-03-08T03:30:00-04:00",
+                "2026-03-09T02:30:00-04:00",
+                "2026-03-10T02:30:00-04:00",
+            ]
+        );
+        // 2026-11-01 纽约 02:00 回到 01:00，01:30 出现两次，只运行一次
+        assert_eq!(
+            locals("30 1 * * *", "America/New_York", "2026-10-31T12:00:00Z", 2),
+            vec!["2026-11-01T01:30:00-04:00", "2026-11-02T01:30:00-05:00"]
+        );
+        // 第一次 01:30 之后计算时不会再匹配重复的 01:30
+        assert_eq!(
+            locals("30 1 * * *", "America/New_York", "2026-11-01T05:30:00Z", 1),
+            vec!["2026-11-02T01:30:00-05:00"]
+        );
+        // 每 30 分钟运行时跳过的 02:00 / 02:30 与 03:00 之后的时间不重复
+        assert_eq!(
+            locals(
+                "*/30 * * * *",
+                "America/New_York",
+                "2026-03-08T06:45:00Z",
+                3
+            ),
+            vec![
+                "2026-03-08T03:00:00-04:00",
+                "2026-03-08T03:30:00-04:00",
+                "2026-03-08T04:00:00-04:00",
+            ]
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use crate::db::{ConfigRepository, TaskRepository, contains_pattern};
+use crate::generators::StarRocksDDLGenerator;
+use crate::models::{
+    DatabaseConfig, DbType, SearchQuery, SearchResponse, SearchResult, SearchResultKind,
+};
+use crate::services::{ConnectionPoolManager, ConnectionService};
+use crate::utils::error::{AppError, Result};
+use mysql_async::prelude::*;
+use sqlx::{MySqlPool, Row};
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+/// 单个集群的搜索超时，超时的集群记录在 errors 中
+const CLUSTER_SEARCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 全局搜索：连接、同步映射、任务、RisingWave 对象和 StarRocks 表
+pub struct SearchService;
+
+impl SearchService {
+    pub async fn search(app_db: &MySqlPool, query: &SearchQuery) -> Result<SearchResponse> {
+        let q = query.q.trim();
+        if q.is_empty() {
+            return Err(AppError::Validation("Search text is empty".to_string()));
+        }
+        let limit = query.get_limit();
+
+        let configs = ConfigRepository::new(app_db).find_all().await?;
+        let mut results = Self::search_connections(&configs, q, limit);
+
+        let task_repo = TaskRepository::new(app_db);
+        let mut mappings: Vec<SearchResult> = task_repo
+            .search_mappings(q, limit)
+            .await?
+            .into_iter()
+            .map(
+                |(task_id, rw_config_id, mysql_db, mysql_table, target_db, target_table)| {
+                    SearchResult {
+                        kind: SearchResultKind::Mapping,
+                        name: format!(
+                            "{}.{} → {}.{}",
+                            mysql_db, mysql_table, target_db, target_table
+                        ),
+                        detail: Some(format!("latest task #{}", task_id)),
+                        id: Some(task_id),
+                        config_id: Some(rw_config_id),
+                        schema: Some(target_db),
+                    }
+                },
+            )
+            .collect();
+        Self::sort_by_rank(&mut mappings, q);
+        results.extend(mappings);
+
+        results.extend(
+            task_repo
+                .search_task_names(q, limit)
+                .await?
+                .into_iter()
+                .map(|(id, task_name, status)| SearchResult {
+                    kind: SearchResultKind::Task,
+                    name: task_name,
+                    detail: Some(status),
+                    id: Some(id),
+                    config_id: None,
+                    schema: None,
+                }),
+        );
+
+        // 各集群并发查询
+        let mut clusters = JoinSet::new();
+        for config in configs {
+            if config.db_type != DbType::RisingWave && !config.db_type.is_sink_target() {
+                continue;
+            }
+            let q = q.to_string();
+            clusters.spawn(async move {
+                let search = async {
+                    if config.db_type == DbType::RisingWave {
+                        Self::search_rw_objects(&config, &q, limit).await
+                    } else {
+                        Self::search_sr_tables(&config, &q, limit).await
+                    }
+                };
+                let result = match tokio::time::timeout(CLUSTER_SEARCH_TIMEOUT, search).await {
+                    Ok(result) => result,
+                    Err(_) => Err(AppError::Connection("search timed out".to_string())),
+                };
+                (config.name, result)
+            });
+        }
+
+        let mut cluster_results = Vec::new();
+        let mut errors = Vec::new();
+        while let Some(joined) = clusters.join_next().await {
+            match joined {
+                Ok((_, Ok(found))) => cluster_results.extend(found),
+                Ok((name, Err(e))) => errors.push(format!("{}: {}", name, e)),
+                Err(e) => errors.push(e.to_string()),
+            }
+        }
+        Self::sort_by_rank(&mut cluster_results, q);
+        errors.sort();
+        results.extend(cluster_results);
+
+        Ok(SearchResponse {
+            query: q.to_string(),
+            results,
+            errors,
+        })
+    }
+
+    fn search_connections(configs: &[DatabaseConfig], q: &str, limit: i64) -> Vec<SearchResult> {
+        let needle = q.to_lowercase();
+        let mut found: Vec<SearchResult> = configs
+            .iter()
+            .filter(|c| c.name.to_lowercase().contains(&needle))
+            .map(|c| SearchResult {
+                kind: SearchResultKind::Connection,
+                name: c.name.clone(),
+                detail: Some(c.db_type.as_str().to_string()),
+                id: Some(c.id),
+                config_id: Some(c.id),
+                schema: None,
+            })
+            .collect();
+        Self::sort_by_rank(&mut found, q);
+        found.truncate(limit as usize);
+        found
+    }
+
+    async fn search_rw_objects(
+        config: &DatabaseConfig,
+        q: &str,
+        limit: i64,
+    ) -> Result<Vec<SearchResult>> {
+        let rw_pool = ConnectionPoolManager::postgres(config).await?;
+        let rows = sqlx::query(
+            "SELECT r.name, s.name AS schema_name, r.relation_type
+             FROM rw_catalog.rw_relations r
+             JOIN rw_catalog.rw_schemas s ON r.schema_id = s.id
+             WHERE r.name ILIKE $1
+               AND s.name NOT IN ('rw_catalog', 'information_schema', 'pg_catalog')
+             ORDER BY r.name
+             LIMIT $2",
+        )
+        .bind(contains_pattern(q))
+        .bind(limit)
+        .fetch_all(&rw_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| SearchResult {
+                kind: SearchResultKind::RwObject,
+                name: row.get("name"),
+                detail: Some(row.get("relation_type")),
+                id: None,
+                config_id: Some(config.id),
+                schema: Some(row.get("schema_name")),
+            })
+            .collect())
+    }
+
+    async fn search_sr_tables(
+        config: &DatabaseConfig,
+        q: &str,
+        limit: i64,
+    ) -> Result<Vec<SearchResult>> {
+        let resolved = ConnectionService::resolve_config(config.clone()).await?;
+        let mut conn = ConnectionService::connect_starrocks(&resolved).await?;
+        // StarRocks 旧版本不支持服务端预处理语句，搜索词转义为字面量
+        let sql = format!(
+            "SELECT TABLE_SCHEMA, TABLE_NAME, TABLE_TYPE FROM information_schema.tables
+             WHERE TABLE_NAME LIKE {}
+               AND TABLE_SCHEMA NOT IN ('information_schema', '_statistics_', 'sys')
+             ORDER BY TABLE_NAME
+             LIMIT {}",
+            StarRocksDDLGenerator::quote_string(&contains_pattern(q)),
+            limit
+        );
+        let rows: std::result::Result<Vec<(String, String, Option<String>)>, _> =
+            conn.query(sql).await;
+        let _ = conn.disconnect().await;
+        let rows = rows.map_err(|e| AppError::Unknown(format!("StarRocks query failed: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(schema, name, table_type)| SearchResult {
+                kind: SearchResultKind::SrTable,
+                name,
+                detail: table_type,
+                id: None,
+                config_id: Some(config.id),
+                schema: Some(schema),
+            })
+            .collect())
+    }
+
+    /// StarRocks 表排在其他结果之后；同类结果中完全匹配在前，其次是前缀匹配，其余保持原顺序
+    fn sort_by_rank(results: &mut [SearchResult], q: &str) {
+        results.sort_by_key(|r| {
+            (
+                r.kind == SearchResultKind::SrTable,
+                Self::match_rank(&r.name, q),
+            )
+        });
+    }
+
+    fn match_rank(name: &str, q: &str) -> u8 {
+        let name = name.to_lowercase();
+        let q = q.to_lowercase();
+        if name == q {
+            0
+        } else if name.starts_with(&q) {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(kind: SearchResultKind, name: &str) -> SearchResult {
+        SearchResult {
+            kind,
+            name: name.to_string(),
+            detail: None,
+            id: None,
+            config_id: None,
+            schema: None,
+        }
+    }
+
+    #[test]
+    fn test_sort_by_rank() {
+        let mut results = vec![
+            result(SearchResultKind::SrTable, "orders"),
+            result(SearchResultKind::RwObject, "orders_to_sr_sink"),
+            result(SearchResultKind::RwObject, "daily_orders"),
+            result(SearchResultKind::RwObject, "Orders"),
+        ];
+        SearchService::sort_by_rank(&mut results, "orders");
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Orders", "orders_to_sr_sink", "daily_orders", "orders"]
+        );
+
+        assert_eq!(
+            SearchQuery {
+                q: "orders".to_string(),
+                limit: Some(1000)
+            }
+            .get_limit(),
+            100
+        );
+    }
+}
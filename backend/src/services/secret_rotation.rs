@@ -0,0 +1,389 @@
+use crate::db::{ConfigRepository, TaskRepository};
+use crate::generators::RisingWaveDDLGenerator;
+use crate::models::{
+    CreateConnectionRequest, DatabaseConfig, DbType, RotatePasswordRequest, RotatePasswordResult,
+    RotatedSecret, RotationCheck, RwSecretReference, TestConnectionRequest,
+};
+use crate::services::{AuditService, ConnectionPoolManager, ConnectionService, RwSecretService};
+use crate::utils::error::{AppError, Result};
+use chrono::{DateTime, Utc};
+use sqlx::{MySqlPool, PgPool};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// 连接密码轮换：校验新密码、更新保存的配置、更新引用该密码的 RisingWave SECRET 并检查 Source / Sink
+pub struct SecretRotationService;
+
+impl SecretRotationService {
+    /// actor 记录到审计日志
+    pub async fn rotate(
+        app_db: &MySqlPool,
+        config_id: i64,
+        request: &RotatePasswordRequest,
+        actor: Option<&str>,
+    ) -> Result<RotatePasswordResult> {
+        if request.password.is_empty() {
+            return Err(AppError::Validation("New password is empty".to_string()));
+        }
+        let repo = ConfigRepository::new(app_db);
+        let config = repo.find_by_id(config_id).await?;
+        if config.no_password {
+            return Err(AppError::Validation(format!(
+                "Connection {} does not use password authentication",
+                config.name
+            )));
+        }
+
+        // 新密码无法登录时不做任何修改
+        Self::test_password(&config, &request.password).await?;
+
+        let started_at = Utc::now();
+        let version = repo
+            .update(
+                config_id,
+                CreateConnectionRequest {
+                    name: config.name.clone(),
+                    db_type: config.db_type.clone(),
+                    host: config.host.clone(),
+                    port: config.port,
+                    username: config.username.clone(),
+                    password: request.password.clone(),
+                    no_password: false,
+                    database_name: config.database_name.clone(),
+                    proxy: config.proxy.clone(),
+                    tls: config.tls.clone(),
+                    version: Some(config.version),
+                },
+            )
+            .await?;
+        ConnectionPoolManager::invalidate(config_id).await;
+        tracing::info!(
+            "Rotated password of connection {} ({})",
+            config.name,
+            config_id
+        );
+
+        // 按 RisingWave 连接分组目标 schema
+        let mut targets: BTreeMap<i64, Vec<String>> = BTreeMap::new();
+        if config.db_type != DbType::RisingWave {
+            for (rw_config_id, target_database) in TaskRepository::new(app_db)
+                .find_secret_targets(config_id, &config.db_type)
+                .await?
+            {
+                targets
+                    .entry(rw_config_id)
+                    .or_default()
+                    .push(target_database);
+            }
+        }
+
+        let mut secrets = Vec::new();
+        let mut rotated: Vec<(i64, PgPool, Vec<String>)> = Vec::new();
+        for (rw_config_id, schemas) in targets {
+            let rw_pool = match Self::rw_pool(app_db, rw_config_id).await {
+                Ok(pool) => pool,
+                Err(e) => {
+                    secrets.extend(schemas.iter().filter_map(|schema| {
+                        Some(RotatedSecret {
+                            rw_config_id,
+                            secret_name: Self::secret_name(&config.db_type, schema)?,
+                            updated: false,
+                            error: Some(e.to_string()),
+                            touched: vec![],
+                        })
+                    }));
+                    continue;
+                }
+            };
+
+            let mut updated_schemas = Vec::new();
+            for schema in schemas {
+                let Some(secret_name) = Self::secret_name(&config.db_type, &schema) else {
+                    continue;
+                };
+                // 复用共享 Source 的 schema 中没有 SECRET
+                match Self::secret_exists(&rw_pool, &schema, &config.db_type).await {
+                    Ok(false) => continue,
+                    Ok(true) => {}
+                    Err(e) => {
+                        secrets.push(RotatedSecret {
+                            rw_config_id,
+                            secret_name,
+                            updated: false,
+                            error: Some(e.to_string()),
+                            touched: vec![],
+                        });
+                        continue;
+                    }
+                }
+
+                let ddl = RisingWaveDDLGenerator::generate_alter_secret_ddl(
+                    &secret_name,
+                    &request.password,
+                );
+                // SECRET 语句包含密码，不记录到日志，审计日志中的密码已隐藏
+                let error = sqlx::query(&ddl)
+                    .execute(&rw_pool)
+                    .await
+                    .err()
+                    .map(|e| e.to_string());
+                AuditService::record_ddl(app_db, actor, None, "RisingWave", &ddl, error.as_deref())
+                    .await;
+                let touched =
+                    Self::touched_objects(&rw_pool, &schema, &config.db_type, &secret_name).await;
+                if let Some(error) = &error {
+                    tracing::error!("Failed to update secret {}: {}", secret_name, error);
+                } else {
+                    updated_schemas.push(schema);
+                }
+                secrets.push(RotatedSecret {
+                    rw_config_id,
+                    updated: error.is_none(),
+                    secret_name,
+                    error,
+                    touched,
+                });
+            }
+            if !updated_schemas.is_empty() {
+                rotated.push((rw_config_id, rw_pool, updated_schemas));
+            }
+        }
+
+        // 等待 Source / Sink 使用新密码重连后检查是否有新的错误事件
+        let mut checks = Vec::new();
+        if !rotated.is_empty() {
+            tokio::time::sleep(Duration::from_secs(request.get_verify_delay_secs())).await;
+        }
+        for (rw_config_id, rw_pool, schemas) in rotated {
+            for schema in schemas {
+                // 密码已经更新，检查失败只记录在结果中
+                match Self::check_objects(
+                    &rw_pool,
+                    rw_config_id,
+                    &schema,
+                    &config.db_type,
+                    started_at,
+                )
+                .await
+                {
+                    Ok(result) => checks.extend(result),
+                    Err(e) => checks.push(RotationCheck {
+                        rw_config_id,
+                        kind: "schema".to_string(),
+                        name: format!("\"{}\"", schema),
+                        healthy: false,
+                        error: Some(e.to_string()),
+                    }),
+                }
+            }
+        }
+
+        let success = secrets.iter().all(|s| s.updated) && checks.iter().all(|c| c.healthy);
+        Ok(RotatePasswordResult {
+            config_id,
+            version,
+            secrets,
+            checks,
+            success,
+        })
+    }
+
+    /// 用新密码测试连接
+    async fn test_password(config: &DatabaseConfig, password: &str) -> Result<()> {
+        let request = TestConnectionRequest {
+            db_type: config.db_type.clone(),
+            host: config.host.clone(),
+            port: config.port,
+            username: config.username.clone(),
+            password: password.to_string(),
+            no_password: false,
+            database_name: config.database_name.clone(),
+            proxy: config.proxy.clone(),
+            tls: config.tls.clone(),
+            config_id: None,
+        };
+        let result = match config.db_type {
+            DbType::MySQL => ConnectionService::test_mysql(&request).await?,
+            DbType::RisingWave => ConnectionService::test_risingwave(&request).await?,
+            DbType::StarRocks | DbType::Doris => {
+                ConnectionService::test_starrocks(&request).await?
+            }
+        };
+        if !result.success {
+            return Err(AppError::Validation(format!(
+                "Cannot connect with the new password: {}",
+                result.error.unwrap_or(result.message)
+            )));
+        }
+        Ok(())
+    }
+
+    async fn rw_pool(app_db: &MySqlPool, rw_config_id: i64) -> Result<PgPool> {
+        let rw_config = ConfigRepository::new(app_db)
+            .find_by_id(rw_config_id)
+            .await?;
+        ConnectionPoolManager::postgres(&rw_config).await
+    }
+
+    /// 引用该类型连接密码的 SECRET 名称，与同步时创建的名称一致
+    fn secret_name(db_type: &DbType, schema: &str) -> Option<String> {
+        match db_type {
+            DbType::MySQL => Some(RisingWaveDDLGenerator::get_secret_name(schema)),
+            DbType::StarRocks | DbType::Doris => {
+                Some(RisingWaveDDLGenerator::get_starrocks_secret_name(schema))
+            }
+            DbType::RisingWave => None,
+        }
+    }
+
+    /// SECRET 在 schema 中的名称
+    fn secret_object_name(db_type: &DbType) -> &'static str {
+        if *db_type == DbType::MySQL {
+            "mysql_pwd"
+        } else {
+            "starrocks_pwd"
+        }
+    }
+
+    /// 定义中引用该 SECRET 的 Source / Sink，查询失败时只记录警告
+    async fn touched_objects(
+        rw_pool: &PgPool,
+        schema: &str,
+        db_type: &DbType,
+        secret_name: &str,
+    ) -> Vec<RwSecretReference> {
+        match RwSecretService::find_references(rw_pool, schema, Self::secret_object_name(db_type))
+            .await
+        {
+            Ok(touched) => {
+                for object in &touched {
+                    tracing::info!(
+                        "Secret {} is used by {} \"{}\".{}",
+                        secret_name,
+                        object.kind,
+                        object.schema_name,
+                        object.name
+                    );
+                }
+                touched
+            }
+            Err(e) => {
+                tracing::warn!("Failed to find objects using secret {}: {}", secret_name, e);
+                vec![]
+            }
+        }
+    }
+
+    async fn secret_exists(rw_pool: &PgPool, schema: &str, db_type: &DbType) -> Result<bool> {
+        let name = Self::secret_object_name(db_type);
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM rw_catalog.rw_secrets sec
+             JOIN rw_catalog.rw_schemas sch ON sec.schema_id = sch.id
+             WHERE sch.name = $1 AND sec.name = $2",
+        )
+        .bind(schema)
+        .bind(name)
+        .fetch_one(rw_pool)
+        .await?;
+        Ok(count > 0)
+    }
+
+    /// 使用该密码的对象：MySQL 为 mysql-cdc Source，StarRocks / Doris 为对应 connector 的 Sink
+    fn dependent_objects(db_type: &DbType) -> Option<(&'static str, &'static str, &'static str)> {
+        match db_type {
+            DbType::MySQL => Some(("source", "rw_catalog.rw_sources", "mysql-cdc")),
+            DbType::StarRocks => Some(("sink", "rw_catalog.rw_sinks", "starrocks")),
+            DbType::Doris => Some(("sink", "rw_catalog.rw_sinks", "doris")),
+            DbType::RisingWave => None,
+        }
+    }
+
+    /// 检查 schema 中引用该密码的对象在轮换后是否出现错误事件
+    async fn check_objects(
+        rw_pool: &PgPool,
+        rw_config_id: i64,
+        schema: &str,
+        db_type: &DbType,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<RotationCheck>> {
+        let Some((kind, catalog, connector)) = Self::dependent_objects(db_type) else {
+            return Ok(vec![]);
+        };
+        let names: Vec<String> = sqlx::query_scalar(&format!(
+            "SELECT o.name FROM {} o
+             JOIN rw_catalog.rw_schemas sch ON o.schema_id = sch.id
+             WHERE sch.name = $1 AND lower(o.connector) = $2
+             ORDER BY o.name",
+            catalog
+        ))
+        .bind(schema)
+        .bind(connector)
+        .fetch_all(rw_pool)
+        .await?;
+
+        let mut checks = Vec::new();
+        for name in names {
+            // rw_event_logs 在旧版本 RisingWave 中可能不存在，查询失败时视为没有错误
+            let error: Option<String> = sqlx::query_scalar(
+                "SELECT info::varchar FROM rw_catalog.rw_event_logs
+                 WHERE timestamp >= $1::timestamptz AND info::varchar LIKE '%' || $2 || '%'
+                 ORDER BY timestamp DESC
+                 LIMIT 1",
+            )
+            .bind(since.to_rfc3339())
+            .bind(&name)
+            .fetch_optional(rw_pool)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::debug!("Failed to query rw_event_logs: {}", e);
+                None
+            });
+
+            checks.push(RotationCheck {
+                rw_config_id,
+                kind: kind.to_string(),
+                name: RisingWaveDDLGenerator::qualified_name(schema, &name),
+                healthy: error.is_none(),
+                error,
+            });
+        }
+        Ok(checks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_targets() {
+        assert_eq!(
+            SecretRotationService::secret_name(&DbType::MySQL, "ods").as_deref(),
+            Some("\"ods\".mysql_pwd")
+        );
+        assert_eq!(
+            SecretRotationService::secret_name(&DbType::Doris, "ods").as_deref(),
+            Some("\"ods\".starrocks_pwd")
+        );
+        assert_eq!(
+            SecretRotationService::secret_name(&DbType::RisingWave, "ods"),
+            None
+        );
+
+        assert_eq!(
+            SecretRotationService::dependent_objects(&DbType::MySQL).map(|(kind, _, c)| (kind, c)),
+            Some(("source", "mysql-cdc"))
+        );
+        assert_eq!(
+            SecretRotationService::dependent_objects(&DbType::StarRocks)
+                .map(|(kind, _, c)| (kind, c)),
+            Some(("sink", "starrocks"))
+        );
+
+        let ddl = RisingWaveDDLGenerator::generate_alter_secret_ddl("\"ods\".mysql_pwd", "new'pw");
+        assert_eq!(
+            ddl,
+            r#"ALTER SECRET "ods".mysql_pwd WITH ( backend = 'meta' ) AS 'new''pw';"#
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use crate::db::{SettingRepository, TaskRepository};
+use crate::models::{Setting, SettingValue};
+use crate::utils::error::{AppError, Result};
+use chrono::{Duration, Utc};
+use sqlx::MySqlPool;
+use std::collections::HashMap;
+
+/// 日志保留清理的执行间隔（秒）
+const LOG_RETENTION_INTERVAL_SECS: u64 = 3600;
+
+/// 运行时设置服务
+/// 设置保存在 settings 表并缓存在内存中，修改后立即对新任务生效
+pub struct SettingsService;
+
+impl SettingsService {
+    /// 从 settings 表加载覆盖值，忽略未知或无效的设置
+    pub async fn load(pool: &MySqlPool) -> Result<()> {
+        let rows = SettingRepository::new(pool).find_all().await?;
+        let mut overrides = HashMap::new();
+        for (name, value, _) in rows {
+            let Some(setting) = Setting::from_str(&name) else {
+                tracing::warn!("Ignoring unknown setting {}", name);
+                continue;
+            };
+            match setting.normalize(&value) {
+                Ok(value) => {
+                    overrides.insert(setting, value);
+                }
+                Err(e) => tracing::warn!("Ignoring stored setting: {}", e),
+            }
+        }
+        tracing::info!("Loaded {} settings", overrides.len());
+        Setting::load_overrides(overrides);
+        Ok(())
+    }
+
+    /// 列出所有设置及当前生效的值
+    pub async fn list(pool: &MySqlPool) -> Result<Vec<SettingValue>> {
+        let stored = SettingRepository::new(pool).find_all().await?;
+        Ok(Setting::ALL
+            .into_iter()
+            .map(|setting| {
+                let (value, source) = setting.resolve();
+                SettingValue {
+                    name: setting.as_str().to_string(),
+                    value,
+                    source,
+                    description: setting.description().to_string(),
+                    env_var: setting.env_var().to_string(),
+                    updated_at: stored
+                        .iter()
+                        .find(|(name, _, _)| name == setting.as_str())
+                        .map(|(_, _, updated_at)| *updated_at),
+                }
+            })
+            .collect())
+    }
+
+    /// 保存设置
+    pub async fn set(pool: &MySqlPool, name: &str, value: &str) -> Result<()> {
+        let setting = Self::setting(name)?;
+        let value = setting.normalize(value).map_err(AppError::Validation)?;
+
+        SettingRepository::new(pool)
+            .set(setting.as_str(), &value)
+            .await?;
+        tracing::info!("Setting {} changed to {}", setting.as_str(), value);
+        setting.set_override(Some(value));
+        Ok(())
+    }
+
+    /// 删除设置，恢复为环境变量或内置默认值
+    pub async fn reset(pool: &MySqlPool, name: &str) -> Result<()> {
+        let setting = Self::setting(name)?;
+        SettingRepository::new(pool).delete(setting.as_str()).await?;
+        tracing::info!("Setting {} reset", setting.as_str());
+        setting.set_override(None);
+        Ok(())
+    }
+
+    fn setting(name: &str) -> Result<Setting> {
+        Setting::from_str(name)
+            .ok_or_else(|| AppError::NotFound(format!("Unknown setting: {}", name)))
+    }
+
+    /// 启动任务日志的定期清理，保留天数为 0 时跳过
+    pub fn spawn_log_retention(app_db: MySqlPool) {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(LOG_RETENTION_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::purge_expired_logs(&app_db).await {
+                    tracing::warn!("Failed to purge expired task logs: {}", e);
+                }
+            }
+        });
+    }
+
+    /// 删除超过保留天数的任务日志
+    pub async fn purge_expired_logs(app_db: &MySqlPool) -> Result<u64> {
+        let days: i64 = Setting::TaskLogRetentionDays.parsed().unwrap_or(0);
+        if days <= 0 {
+            return Ok(0);
+        }
+
+        let deleted = TaskRepository::new(app_db)
+            .delete_logs_before(Utc::now() - Duration::days(days))
+            .await?;
+        if deleted > 0 {
+            tracing::info!("Deleted {} task logs older than {} days", deleted, days);
+        }
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SettingSource;
+
+    #[test]
+    fn test_setting_normalize_and_override() {
+        assert_eq!(
+            Setting::SrDefaultBuckets.normalize(" AUTO ").as_deref(),
+            Ok("auto")
+        );
+        assert!(Setting::SrDefaultBuckets.normalize("0").is_err());
+        assert_eq!(
+            Setting::SrDefaultStorageMedium.normalize("ssd").as_deref(),
+            Ok("SSD")
+        );
+        assert!(Setting::RwDefaultSchema.normalize("bad schema").is_err());
+        assert!(Setting::TaskLogRetentionDays.normalize("-1").is_err());
+
+        let setting = Setting::RwDefaultSchema;
+        setting.set_override(Some("ods".to_string()));
+        assert_eq!(
+            setting.resolve(),
+            (Some("ods".to_string()), SettingSource::Database)
+        );
+        setting.set_override(None);
+        assert_ne!(setting.resolve().1, SettingSource::Database);
+    }
+}
@@ -0,0 +1,99 @@
+use crate::utils::error::{AppError, Result};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// 关闭时等待执行中 DDL 的默认最长时间
+const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(60);
+
+static DRAINING: AtomicBool = AtomicBool::new(false);
+static IN_FLIGHT_DDL: AtomicUsize = AtomicUsize::new(0);
+static DDL_FINISHED: Notify = Notify::const_new();
+
+/// 优雅关闭：收到信号后不再开始新的 DDL，等待执行中的 DDL 结束
+pub struct Shutdown;
+
+/// 执行中的 DDL，drop 时计数减一
+pub struct DdlGuard;
+
+impl Drop for DdlGuard {
+    fn drop(&mut self) {
+        if IN_FLIGHT_DDL.fetch_sub(1, Ordering::SeqCst) == 1 {
+            DDL_FINISHED.notify_waiters();
+        }
+    }
+}
+
+impl Shutdown {
+    /// 开始执行一条 DDL，关闭过程中返回错误，任务按中断结束
+    pub fn ddl_guard() -> Result<DdlGuard> {
+        IN_FLIGHT_DDL.fetch_add(1, Ordering::SeqCst);
+        let guard = DdlGuard;
+        if Self::is_draining() {
+            return Err(AppError::Unknown("Server is shutting down".to_string()));
+        }
+        Ok(guard)
+    }
+
+    /// 是否已收到关闭信号
+    pub fn is_draining() -> bool {
+        DRAINING.load(Ordering::SeqCst)
+    }
+
+    /// 等待 Ctrl+C 或 SIGTERM，之后不再开始新的 DDL
+    pub async fn signal() {
+        let ctrl_c = async {
+            if let Err(e) = tokio::signal::ctrl_c().await {
+                tracing::error!("Failed to listen for Ctrl+C: {}", e);
+                std::future::pending::<()>().await;
+            }
+        };
+        #[cfg(unix)]
+        let terminate = async {
+            match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+                Ok(mut signal) => {
+                    signal.recv().await;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to listen for SIGTERM: {}", e);
+                    std::future::pending::<()>().await;
+                }
+            }
+        };
+        #[cfg(not(unix))]
+        let terminate = std::future::pending::<()>();
+
+        tokio::select! {
+            _ = ctrl_c => {},
+            _ = terminate => {},
+        }
+        DRAINING.store(true, Ordering::SeqCst);
+        tracing::info!("Shutdown signal received, waiting for in-flight requests and DDL");
+    }
+
+    /// 等待执行中的 DDL 结束，最长 SHUTDOWN_TIMEOUT_SECS 秒（默认 60）
+    pub async fn drain() {
+        DRAINING.store(true, Ordering::SeqCst);
+        let timeout = std::env::var("SHUTDOWN_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_DRAIN_TIMEOUT);
+        let wait = async {
+            loop {
+                let finished = DDL_FINISHED.notified();
+                if IN_FLIGHT_DDL.load(Ordering::SeqCst) == 0 {
+                    return;
+                }
+                finished.await;
+            }
+        };
+        if tokio::time::timeout(timeout, wait).await.is_err() {
+            tracing::warn!(
+                "{} DDL statements still running after {}s, exiting anyway",
+                IN_FLIGHT_DDL.load(Ordering::SeqCst),
+                timeout.as_secs()
+            );
+        }
+    }
+}
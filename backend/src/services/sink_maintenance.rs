@@ -0,0 +1,486 @@
+use crate::db::{ConfigRepository, TaskRepository};
+use crate::generators::RisingWaveDDLGenerator;
+use crate::models::{
+    DatabaseConfig, RecreateSinksRequest, RecreateSinksResult, SinkDrift, SinkPropertyChange,
+    SkippedSink, SyncOptions, SyncRequest, SyncTask, TaskFilter, TaskLogLevel, TaskStatus,
+};
+use crate::services::{
+    AuditService, ConnectionPoolManager, ConnectionService, MetadataService, NotificationService,
+    TaskLogger,
+};
+use crate::utils::error::{AppError, Result};
+use sqlx::{MySqlPool, PgPool, Row};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Instant;
+
+/// 查找同步任务创建的 sink 时最多扫描的任务数
+const SINK_TASK_SCAN_LIMIT: i64 = 1000;
+
+/// RisingWave 升级后的 sink 维护
+/// 对比 RisingWave 上 sink 的 WITH 属性与当前生成器的输出，重建属性不一致的 sink
+pub struct SinkMaintenanceService;
+
+/// 需要重建的 sink 及新的建 sink 语句
+struct PendingSink {
+    request: SyncRequest,
+    sink_ddl: String,
+}
+
+impl SinkMaintenanceService {
+    /// 检查该 RisingWave 上所有同步任务创建的 sink
+    /// dry_run 时只返回差异，否则创建批量任务，按 DROP SINK + CREATE SINK 重建有差异的 sink
+    pub async fn recreate(
+        app_db: &MySqlPool,
+        request: &RecreateSinksRequest,
+    ) -> Result<RecreateSinksResult> {
+        let targets = Self::managed_sinks(app_db, request.rw_config_id).await?;
+        if targets.is_empty() {
+            return Err(AppError::Validation(format!(
+                "No sinks created by sync tasks on RisingWave connection {}",
+                request.rw_config_id
+            )));
+        }
+
+        let rw_config = ConfigRepository::new(app_db)
+            .find_by_id(request.rw_config_id)
+            .await?;
+        let rw_pool = ConnectionPoolManager::postgres(&rw_config).await?;
+
+        let mut configs: HashMap<i64, DatabaseConfig> = HashMap::new();
+        let mut result = RecreateSinksResult {
+            checked: 0,
+            drifts: Vec::new(),
+            skipped: Vec::new(),
+            task_id: None,
+        };
+        let mut pending = Vec::new();
+
+        for target in targets {
+            let sink_name = Self::sink_name(&target);
+            match Self::check_sink(app_db, &rw_pool, &mut configs, &target).await {
+                Ok(Some((changes, sink_ddl))) => {
+                    result.checked += 1;
+                    if changes.is_empty() {
+                        continue;
+                    }
+                    result.drifts.push(SinkDrift {
+                        target_database: target.target_database.clone(),
+                        target_table: target.target_table.clone(),
+                        sink_name,
+                        changes,
+                    });
+                    pending.push(PendingSink {
+                        request: target,
+                        sink_ddl,
+                    });
+                }
+                Ok(None) => result.skipped.push(SkippedSink {
+                    sink_name,
+                    reason: "Sink does not exist in RisingWave".to_string(),
+                }),
+                Err(e) => {
+                    tracing::warn!("Failed to check sink {}: {}", sink_name, e);
+                    result.skipped.push(SkippedSink {
+                        sink_name,
+                        reason: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        if !request.dry_run && !pending.is_empty() {
+            result.task_id = Some(Self::spawn_recreate(app_db, rw_pool, pending).await?);
+        }
+        Ok(result)
+    }
+
+    /// 每个目标表最近一次成功同步的请求（任务按开始时间倒序）
+    async fn managed_sinks(app_db: &MySqlPool, rw_config_id: i64) -> Result<Vec<SyncRequest>> {
+        let filter = TaskFilter {
+            status: Some(TaskStatus::Completed),
+            ..Default::default()
+        };
+        let tasks = TaskRepository::new(app_db)
+            .find_history(&filter, SINK_TASK_SCAN_LIMIT, 0)
+            .await?;
+        Ok(Self::latest_requests(&tasks, rw_config_id))
+    }
+
+    fn latest_requests(tasks: &[SyncTask], rw_config_id: i64) -> Vec<SyncRequest> {
+        let mut seen = HashSet::new();
+        tasks
+            .iter()
+            .filter_map(|task| task.sync_requests().ok())
+            .flatten()
+            .filter(|request| request.rw_config_id == rw_config_id)
+            .filter(|request| {
+                seen.insert((
+                    request.target_database.clone(),
+                    request.target_table.clone(),
+                ))
+            })
+            .collect()
+    }
+
+    /// 返回属性差异和新的建 sink 语句，sink 不存在时返回 None
+    async fn check_sink(
+        app_db: &MySqlPool,
+        rw_pool: &PgPool,
+        configs: &mut HashMap<i64, DatabaseConfig>,
+        request: &SyncRequest,
+    ) -> Result<Option<(Vec<SinkPropertyChange>, String)>> {
+        let Some(definition) = Self::sink_definition(rw_pool, request).await? else {
+            return Ok(None);
+        };
+
+        let mysql_config = Self::config(app_db, configs, request.mysql_config_id).await?;
+        let sr_config = Self::config(app_db, configs, request.sr_config_id).await?;
+        let schema = MetadataService::get_mysql_table_schema(
+            &mysql_config,
+            &request.mysql_database,
+            &request.mysql_table,
+        )
+        .await?;
+        let sink_ddl = RisingWaveDDLGenerator::generate_sink_ddl(&sr_config, request, &schema)?;
+
+        Ok(Some((
+            Self::diff_properties(&definition, &sink_ddl),
+            sink_ddl,
+        )))
+    }
+
+    /// 已解析 SRV 的连接配置，按 ID 缓存
+    async fn config(
+        app_db: &MySqlPool,
+        configs: &mut HashMap<i64, DatabaseConfig>,
+        id: i64,
+    ) -> Result<DatabaseConfig> {
+        if let Some(config) = configs.get(&id) {
+            return Ok(config.clone());
+        }
+        let config = ConfigRepository::new(app_db).find_by_id(id).await?;
+        let config = ConnectionService::resolve_config(config).await?;
+        configs.insert(id, config.clone());
+        Ok(config)
+    }
+
+    async fn sink_definition(rw_pool: &PgPool, request: &SyncRequest) -> Result<Option<String>> {
+        let row = sqlx::query(
+            "SELECT s.definition FROM rw_catalog.rw_sinks s
+             JOIN rw_catalog.rw_schemas sch ON s.schema_id = sch.id
+             WHERE sch.name = $1 AND s.name = $2",
+        )
+        .bind(&request.target_database)
+        .bind(format!("{}_to_sr_sink", request.target_table))
+        .fetch_optional(rw_pool)
+        .await?;
+        Ok(row.map(|row| row.get("definition")))
+    }
+
+    fn sink_name(request: &SyncRequest) -> String {
+        format!(
+            "{}.{}_to_sr_sink",
+            request.target_database, request.target_table
+        )
+    }
+
+    /// 创建重建任务并在后台执行，返回 task_id
+    async fn spawn_recreate(
+        app_db: &MySqlPool,
+        rw_pool: PgPool,
+        pending: Vec<PendingSink>,
+    ) -> Result<i64> {
+        let first = &pending[0].request;
+        let task = SyncTask {
+            id: 0,
+            task_name: format!("Recreate {} sinks", pending.len()),
+            mysql_config_id: first.mysql_config_id,
+            rw_config_id: first.rw_config_id,
+            sr_config_id: first.sr_config_id,
+            mysql_database: first.mysql_database.clone(),
+            mysql_table: format!("[Batch: recreate {} sinks]", pending.len()),
+            target_database: first.target_database.clone(),
+            target_table: format!("[Batch: {} sinks]", pending.len()),
+            status: TaskStatus::Running,
+            started_at: chrono::Utc::now(),
+            completed_at: None,
+            error_message: None,
+            options: serde_json::to_string(&SyncOptions::default())?,
+            preflight_result: None,
+            requests: None,
+            sink_requests: None,
+        };
+        let task_id = TaskRepository::new(app_db)
+            .create_with_log(
+                &task,
+                &format!("Task created for recreating {} sinks", pending.len()),
+            )
+            .await?;
+
+        let app_db = app_db.clone();
+        tokio::spawn(async move {
+            let logger = TaskLogger::new(&app_db, task_id, TaskLogLevel::default());
+            let failed = Self::run_recreate(&logger, &rw_pool, &pending).await;
+            if let Err(e) = logger.finish().await {
+                tracing::warn!("Failed to flush logs for task {}: {}", task_id, e);
+            }
+
+            let task_repo = TaskRepository::new(&app_db);
+            let finished = match failed {
+                Ok(0) => {
+                    task_repo
+                        .finish_with_log(
+                            task_id,
+                            TaskStatus::Completed,
+                            None,
+                            "info",
+                            &format!("Recreated {} sinks", pending.len()),
+                        )
+                        .await
+                }
+                Ok(failed) => {
+                    let error_msg =
+                        format!("Failed to recreate {} of {} sinks", failed, pending.len());
+                    task_repo
+                        .finish_with_log(
+                            task_id,
+                            TaskStatus::Failed,
+                            Some(error_msg.clone()),
+                            "error",
+                            &error_msg,
+                        )
+                        .await
+                }
+                Err(e) => {
+                    let error_msg = e.to_string();
+                    tracing::error!("Sink recreation task {} failed: {}", task_id, error_msg);
+                    task_repo
+                        .finish_with_log(
+                            task_id,
+                            TaskStatus::Failed,
+                            Some(error_msg.clone()),
+                            "error",
+                            &format!("Sink recreation failed: {}", error_msg),
+                        )
+                        .await
+                }
+            };
+            match finished {
+                Ok(()) => NotificationService::notify_task_finished(&app_db, task_id),
+                Err(e) => tracing::warn!("Failed to finish task {}: {}", task_id, e),
+            }
+        });
+
+        Ok(task_id)
+    }
+
+    /// 逐个重建 sink，单个失败时记录后继续，返回失败的数量
+    async fn run_recreate(
+        logger: &TaskLogger<'_>,
+        rw_pool: &PgPool,
+        pending: &[PendingSink],
+    ) -> Result<usize> {
+        let mut failed = 0;
+        for sink in pending {
+            let sink_name = Self::sink_name(&sink.request);
+            logger
+                .step(&format!("Recreating sink {}", sink_name))
+                .await?;
+            if let Err(e) = Self::recreate_one(logger, rw_pool, sink).await {
+                tracing::error!("Failed to recreate sink {}: {}", sink_name, e);
+                logger
+                    .warn(&format!("Failed to recreate sink {}: {}", sink_name, e))
+                    .await?;
+                failed += 1;
+            }
+        }
+        Ok(failed)
+    }
+
+    async fn recreate_one(
+        logger: &TaskLogger<'_>,
+        rw_pool: &PgPool,
+        sink: &PendingSink,
+    ) -> Result<()> {
+        let drop_ddl = RisingWaveDDLGenerator::generate_drop_sink_ddl(
+            &sink.request.target_database,
+            &sink.request.target_table,
+        );
+        for (action, sql) in [
+            ("drop RisingWave sink", drop_ddl.as_str()),
+            ("create RisingWave sink", sink.sink_ddl.as_str()),
+        ] {
+            let started = Instant::now();
+            if let Err(e) = sqlx::query(sql).execute(rw_pool).await {
+                AuditService::record_task_ddl(logger, "RisingWave", sql, Some(&e.to_string()))
+                    .await;
+                return Err(e.into());
+            }
+            logger.ddl("RisingWave", action, sql, started).await?;
+            AuditService::record_task_ddl(logger, "RisingWave", sql, None).await;
+        }
+        Ok(())
+    }
+
+    /// 对比两条建 sink 语句的 WITH 属性，只检查生成器输出中出现的属性
+    pub fn diff_properties(current_ddl: &str, expected_ddl: &str) -> Vec<SinkPropertyChange> {
+        let current = Self::with_properties(current_ddl);
+        Self::with_properties(expected_ddl)
+            .into_iter()
+            .filter_map(|(key, expected)| {
+                let current = current.get(&key).cloned();
+                (current.as_deref() != Some(expected.as_str())).then_some(SinkPropertyChange {
+                    key,
+                    current,
+                    expected,
+                })
+            })
+            .collect()
+    }
+
+    /// 解析语句中最后一个 WITH ( ... ) 的属性，键转为小写，值去掉引号
+    fn with_properties(ddl: &str) -> BTreeMap<String, String> {
+        let mut properties = BTreeMap::new();
+        let Some(body) = Self::with_clause(ddl) else {
+            return properties;
+        };
+
+        for item in Self::split_top_level(body) {
+            let Some((key, value)) = item.split_once('=') else {
+                continue;
+            };
+            properties.insert(
+                key.trim().to_lowercase(),
+                Self::normalize_value(value.trim()),
+            );
+        }
+        properties
+    }
+
+    /// 引号之外最后一个 WITH 后括号内的内容
+    fn with_clause(ddl: &str) -> Option<&str> {
+        let bytes = ddl.as_bytes();
+        let mut quote = None;
+        let mut start = None;
+        let mut i = 0;
+        while i < bytes.len() {
+            let c = bytes[i];
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == b'\'' || c == b'"' => quote = Some(c),
+                None if bytes[i..].len() >= 4
+                    && bytes[i..i + 4].eq_ignore_ascii_case(b"with")
+                    && (i == 0 || !Self::is_word(bytes[i - 1])) =>
+                {
+                    let rest = ddl[i + 4..].trim_start();
+                    if rest.starts_with('(') {
+                        start = Some(ddl.len() - rest.len() + 1);
+                    }
+                }
+                None => {}
+            }
+            i += 1;
+        }
+
+        let start = start?;
+        let mut depth = 1;
+        let mut quote = None;
+        for (offset, c) in ddl[start..].char_indices() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '\'' || c == '"' => quote = Some(c),
+                None if c == '(' => depth += 1,
+                None if c == ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(&ddl[start..start + offset]);
+                    }
+                }
+                None => {}
+            }
+        }
+        None
+    }
+
+    fn is_word(c: u8) -> bool {
+        c.is_ascii_alphanumeric() || c == b'_'
+    }
+
+    /// 按引号和括号之外的逗号拆分
+    fn split_top_level(body: &str) -> Vec<&str> {
+        let mut items = Vec::new();
+        let mut depth = 0;
+        let mut quote = None;
+        let mut last = 0;
+        for (i, c) in body.char_indices() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '\'' || c == '"' => quote = Some(c),
+                None if c == '(' => depth += 1,
+                None if c == ')' => depth -= 1,
+                None if c == ',' && depth == 0 => {
+                    items.push(&body[last..i]);
+                    last = i + 1;
+                }
+                None => {}
+            }
+        }
+        items.push(&body[last..]);
+        items
+    }
+
+    /// 单引号字符串取原值，其他值（如 secret 引用）去掉双引号并合并空白
+    fn normalize_value(value: &str) -> String {
+        if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+            return inner.replace("''", "'");
+        }
+        value
+            .replace('"', "")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_sink_properties() {
+        let current = "CREATE SINK \"ods\".orders_to_sr_sink AS SELECT id, \
+            created_at::TIMESTAMP AS created_at FROM \"ods\".orders WHERE note <> 'with (x)' \
+            WITH (connector = 'starrocks', starrocks.host = 'sr-old', \
+            starrocks.password = secret ods.ods_sr_secret, type = 'upsert', primary_key = 'id')";
+        let expected = r#"CREATE SINK IF NOT EXISTS "ods".orders_to_sr_sink FROM "ods".orders
+                   WITH (
+                   connector = 'starrocks',
+                   starrocks.host = 'sr-new',
+                   starrocks.httpport = '8030',
+                   starrocks.password = secret "ods".ods_sr_secret,
+                   type = 'upsert',
+                   primary_key = 'id'
+                   );"#;
+
+        let changes = SinkMaintenanceService::diff_properties(current, expected);
+        assert_eq!(
+            changes,
+            vec![
+                SinkPropertyChange {
+                    key: "starrocks.host".to_string(),
+                    current: Some("sr-old".to_string()),
+                    expected: "sr-new".to_string(),
+                },
+                SinkPropertyChange {
+                    key: "starrocks.httpport".to_string(),
+                    current: None,
+                    expected: "8030".to_string(),
+                },
+            ]
+        );
+        assert!(SinkMaintenanceService::diff_properties(expected, expected).is_empty());
+    }
+}
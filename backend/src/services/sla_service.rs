@@ -0,0 +1,211 @@
+use crate::db::HealthSampleRepository;
+use crate::models::{HealthSample, PipelineHealth, PipelineSla, SlaBreach, SlaWindow};
+use crate::services::StatusService;
+use crate::utils::error::Result;
+use chrono::{Duration, Utc};
+use sqlx::MySqlPool;
+use std::collections::BTreeMap;
+
+/// 默认采样间隔（秒）
+const DEFAULT_SAMPLE_INTERVAL_SECS: u64 = 300;
+/// 采样保留天数（覆盖 30 天统计窗口）
+const SAMPLE_RETENTION_DAYS: i64 = 35;
+
+/// SLA / 可用率统计服务
+pub struct SlaService;
+
+impl SlaService {
+    /// 采样间隔，可通过环境变量 HEALTH_SAMPLE_INTERVAL_SECS 覆盖，0 表示关闭采样
+    pub fn sample_interval_secs() -> u64 {
+        std::env::var("HEALTH_SAMPLE_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SAMPLE_INTERVAL_SECS)
+    }
+
+    /// 启动后台采样任务
+    pub fn spawn_sampler(app_db: MySqlPool) {
+        let interval_secs = Self::sample_interval_secs();
+        if interval_secs == 0 {
+            tracing::info!("Pipeline health sampling disabled");
+            return;
+        }
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::record_samples(&app_db).await {
+                    tracing::warn!("Failed to record pipeline health samples: {}", e);
+                }
+            }
+        });
+    }
+
+    /// 记录一次所有管道的健康状态，并清理过期采样
+    pub async fn record_samples(app_db: &MySqlPool) -> Result<()> {
+        let summary = StatusService::summarize(app_db).await?;
+        let repo = HealthSampleRepository::new(app_db);
+        repo.save_all(&summary.pipelines).await?;
+
+        let deleted = repo
+            .delete_before(Utc::now() - Duration::days(SAMPLE_RETENTION_DAYS))
+            .await?;
+        if deleted > 0 {
+            tracing::debug!("Deleted {} expired health samples", deleted);
+        }
+
+        Ok(())
+    }
+
+    /// 每个管道 7 天 / 30 天的可用率和 SLA 达成率
+    pub async fn report(app_db: &MySqlPool) -> Result<Vec<PipelineSla>> {
+        let repo = HealthSampleRepository::new(app_db);
+        let now = Utc::now();
+        let week = repo.aggregate_since(now - Duration::days(7)).await?;
+        let month = repo.aggregate_since(now - Duration::days(30)).await?;
+
+        Ok(Self::merge_windows(&week, &month))
+    }
+
+    /// 最近 days 天内的 SLA 违约区间
+    pub async fn breaches(
+        app_db: &MySqlPool,
+        days: i64,
+        target: Option<&str>,
+    ) -> Result<Vec<SlaBreach>> {
+        let repo = HealthSampleRepository::new(app_db);
+        let samples = repo
+            .find_since(Utc::now() - Duration::days(days.clamp(1, 30)), target)
+            .await?;
+
+        Ok(Self::compute_breaches(&samples))
+    }
+
+    /// 合并 7 天和 30 天窗口（30 天窗口包含所有出现过的管道）
+    fn merge_windows(week: &[SlaWindow], month: &[SlaWindow]) -> Vec<PipelineSla> {
+        let week: BTreeMap<(&str, &str), &SlaWindow> = week
+            .iter()
+            .map(|w| ((w.source.as_str(), w.target.as_str()), w))
+            .collect();
+
+        month
+            .iter()
+            .map(|m| {
+                let w = week.get(&(m.source.as_str(), m.target.as_str()));
+                PipelineSla {
+                    source: m.source.clone(),
+                    target: m.target.clone(),
+                    uptime_7d: w.map(|w| w.uptime_percent()).unwrap_or(100.0),
+                    sla_7d: w.map(|w| w.sla_percent()).unwrap_or(100.0),
+                    samples_7d: w.map(|w| w.samples).unwrap_or(0),
+                    uptime_30d: m.uptime_percent(),
+                    sla_30d: m.sla_percent(),
+                    samples_30d: m.samples,
+                }
+            })
+            .collect()
+    }
+
+    /// 把连续的非 green 采样合并为违约区间（samples 按管道、时间升序）
+    fn compute_breaches(samples: &[HealthSample]) -> Vec<SlaBreach> {
+        let mut breaches = Vec::new();
+        let mut current: Option<SlaBreach> = None;
+
+        for sample in samples {
+            // 换了管道，上一段仍未恢复
+            if let Some(breach) = &current
+                && (breach.source != sample.source || breach.target != sample.target)
+            {
+                breaches.extend(current.take());
+            }
+
+            if sample.health == PipelineHealth::Green {
+                if let Some(mut breach) = current.take() {
+                    breach.ended_at = Some(sample.sampled_at);
+                    breaches.push(breach);
+                }
+                continue;
+            }
+
+            match &mut current {
+                Some(breach) => {
+                    breach.samples += 1;
+                    breach.worst_health = breach.worst_health.max(sample.health);
+                }
+                None => {
+                    current = Some(SlaBreach {
+                        source: sample.source.clone(),
+                        target: sample.target.clone(),
+                        worst_health: sample.health,
+                        started_at: sample.sampled_at,
+                        ended_at: None,
+                        samples: 1,
+                    });
+                }
+            }
+        }
+        breaches.extend(current);
+
+        // 最新的在前
+        breaches.sort_by_key(|b| std::cmp::Reverse(b.started_at));
+        breaches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(target: &str, health: PipelineHealth, minutes: i64) -> HealthSample {
+        HealthSample {
+            id: minutes,
+            source: format!("shop.{}", target),
+            target: format!("ods.{}", target),
+            health,
+            last_sync_at: None,
+            sampled_at: Utc::now() - Duration::hours(1) + Duration::minutes(minutes),
+        }
+    }
+
+    #[test]
+    fn test_compute_breaches() {
+        let samples = vec![
+            sample("orders", PipelineHealth::Green, 0),
+            sample("orders", PipelineHealth::Yellow, 5),
+            sample("orders", PipelineHealth::Red, 10),
+            sample("orders", PipelineHealth::Green, 15),
+            sample("users", PipelineHealth::Red, 20),
+        ];
+
+        let breaches = SlaService::compute_breaches(&samples);
+        assert_eq!(breaches.len(), 2);
+
+        // 最新的在前：users 仍未恢复
+        assert_eq!(breaches[0].target, "ods.users");
+        assert!(breaches[0].ended_at.is_none());
+
+        assert_eq!(breaches[1].target, "ods.orders");
+        assert_eq!(breaches[1].samples, 2);
+        assert_eq!(breaches[1].worst_health, PipelineHealth::Red);
+        assert_eq!(breaches[1].ended_at, Some(samples[3].sampled_at));
+    }
+
+    #[test]
+    fn test_window_percentages() {
+        let window = SlaWindow {
+            source: "shop.orders".to_string(),
+            target: "ods.orders".to_string(),
+            samples: 3,
+            up_samples: 2,
+            green_samples: 1,
+        };
+        assert_eq!(window.uptime_percent(), 66.67);
+        assert_eq!(window.sla_percent(), 33.33);
+
+        let report = SlaService::merge_windows(&[], std::slice::from_ref(&window));
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].samples_7d, 0);
+        assert_eq!(report[0].uptime_30d, 66.67);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::db::ConfigRepository;
+use crate::models::{Setting, SqlQueryRequest, SqlQueryResult};
+use crate::services::ConnectionPoolManager;
+use crate::utils::error::{AppError, Result};
+use crate::utils::sql_validator::{self, SqlDialect};
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::tokenizer::{Token, Tokenizer};
+use sqlx::{Column, Row};
+use std::time::{Duration, Instant};
+use tokio_stream::StreamExt;
+
+/// 允许作为语句开头的关键字
+const ALLOWED_STATEMENTS: &[&str] = &["SELECT", "WITH", "SHOW", "DESCRIBE", "EXPLAIN", "VALUES"];
+
+/// 语句中任何位置都不允许出现的关键字（不含字符串、引号标识符和注释中的内容）
+const FORBIDDEN_KEYWORDS: &[&str] = &[
+    "INSERT", "UPDATE", "DELETE", "CREATE", "DROP", "ALTER", "TRUNCATE", "GRANT", "REVOKE", "SET",
+    "COPY", "INTO", "FLUSH", "RECOVER", "CANCEL", "KILL", "COMMIT", "BEGIN", "START", "ANALYZE",
+];
+
+/// RisingWave SQL 控制台：执行单条只读语句，限制返回行数和执行时间
+pub struct SqlConsoleService;
+
+impl SqlConsoleService {
+    pub async fn query(app_db: &sqlx::MySqlPool, req: &SqlQueryRequest) -> Result<SqlQueryResult> {
+        let sql = Self::check_read_only(&req.sql)?;
+        let max_rows = Setting::SqlConsoleMaxRows.parsed().unwrap_or(1000);
+        let max_rows = req.max_rows.unwrap_or(max_rows).clamp(1, max_rows);
+        let timeout = Setting::SqlConsoleTimeoutSecs.parsed().unwrap_or(30);
+
+        let config = ConfigRepository::new(app_db)
+            .find_by_id(req.config_id)
+            .await?;
+        let pool = ConnectionPoolManager::postgres(&config).await?;
+        tracing::info!("SQL console query on {}: {}", config.name, sql);
+
+        let started = Instant::now();
+        // 在只读事务中执行，结束后回滚；使用简单查询协议，所有类型的值都以文本格式返回
+        let fetch = async {
+            let mut tx = pool.begin_with("START TRANSACTION READ ONLY").await?;
+            let mut stream = sqlx::raw_sql(&sql).fetch(&mut *tx);
+            let mut columns = Vec::new();
+            let mut rows = Vec::new();
+            let mut truncated = false;
+            while let Some(row) = stream.next().await {
+                let row = row?;
+                if rows.len() == max_rows {
+                    truncated = true;
+                    break;
+                }
+                if columns.is_empty() {
+                    columns = row.columns().iter().map(|c| c.name().to_string()).collect();
+                }
+                rows.push(
+                    (0..row.len())
+                        .map(|i| row.try_get_unchecked::<Option<String>, _>(i).ok().flatten())
+                        .collect(),
+                );
+            }
+            drop(stream);
+            tx.rollback().await?;
+            Ok::<_, AppError>((columns, rows, truncated))
+        };
+        let (columns, rows, truncated) = tokio::time::timeout(Duration::from_secs(timeout), fetch)
+            .await
+            .map_err(|_| AppError::Unknown(format!("Query timed out after {}s", timeout)))??;
+
+        Ok(SqlQueryResult {
+            columns,
+            rows,
+            truncated,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// 校验语句为单条只读语句，返回去掉末尾分号的语句
+    pub fn check_read_only(sql: &str) -> Result<String> {
+        let words = Self::top_level_words(sql)?;
+        let Some(first) = words.first() else {
+            return Err(AppError::Validation("Empty SQL statement".to_string()));
+        };
+        if !ALLOWED_STATEMENTS.contains(&first.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Only read-only statements are allowed ({}), got {}",
+                ALLOWED_STATEMENTS.join(", "),
+                first
+            )));
+        }
+        if let Some(word) = words
+            .iter()
+            .find(|w| FORBIDDEN_KEYWORDS.contains(&w.as_str()))
+        {
+            return Err(AppError::Validation(format!(
+                "Keyword {} is not allowed in the SQL console",
+                word
+            )));
+        }
+        // 查询语句再按完整语法校验一次，SHOW / DESCRIBE / EXPLAIN 是 RisingWave 特有的语法，只做关键字检查
+        if matches!(first.as_str(), "SELECT" | "WITH" | "VALUES") {
+            sql_validator::validate_query(sql, SqlDialect::RisingWave)?;
+        }
+        Ok(sql
+            .trim()
+            .trim_end_matches([';', ' ', '\n', '\r', '\t'])
+            .to_string())
+    }
+
+    /// 语句中不在字符串、引号标识符和注释内的单词（大写），出现多条语句时返回错误
+    /// 使用 sqlparser 的 PostgreSQL 分词器，E'' 转义字符串和 $$ 字符串按 RisingWave 的规则处理
+    fn top_level_words(sql: &str) -> Result<Vec<String>> {
+        let tokens = Tokenizer::new(&PostgreSqlDialect {}, sql)
+            .tokenize()
+            .map_err(|e| AppError::Validation(format!("Invalid SQL: {}", e)))?;
+        let mut words = Vec::new();
+        let mut ended = false;
+        for token in tokens {
+            match token {
+                Token::Whitespace(_) | Token::EOF => {}
+                Token::SemiColon => ended = true,
+                _ if ended => {
+                    return Err(AppError::Validation(
+                        "Only a single SQL statement is allowed".to_string(),
+                    ));
+                }
+                Token::Word(word) if word.quote_style.is_none() => {
+                    words.push(word.value.to_uppercase())
+                }
+                _ => {}
+            }
+        }
+        Ok(words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_read_only() {
+        assert_eq!(
+            SqlConsoleService::check_read_only("  select * from t;  \n").unwrap(),
+            "select * from t"
+        );
+        assert!(SqlConsoleService::check_read_only("SHOW TABLES").is_ok());
+        assert!(SqlConsoleService::check_read_only("explain select 1").is_ok());
+        // 字符串、引号标识符和注释中的关键字不受限制
+        assert!(
+            SqlConsoleService::check_read_only(
+                "select 'drop table t; --', \"update\" from t -- delete\n/* ; insert */"
+            )
+            .is_ok()
+        );
+
+        assert!(SqlConsoleService::check_read_only("").is_err());
+        assert!(SqlConsoleService::check_read_only("-- only a comment").is_err());
+        assert!(SqlConsoleService::check_read_only("DROP TABLE t").is_err());
+        assert!(SqlConsoleService::check_read_only("select 1; drop table t").is_err());
+        assert!(SqlConsoleService::check_read_only("select 1; 'x'").is_err());
+        assert!(SqlConsoleService::check_read_only("with x as (delete from t) select 1").is_err());
+        assert!(SqlConsoleService::check_read_only("select * into t2 from t").is_err());
+        assert!(SqlConsoleService::check_read_only("explain analyze select 1").is_err());
+        // E'' 转义字符串中的 \' 不结束字符串，后面的 drop 是独立的语句
+        assert!(
+            SqlConsoleService::check_read_only("select E'\\''; drop table t; select '1'").is_err()
+        );
+        assert!(
+            SqlConsoleService::check_read_only("select $$'$$; drop table t; select '1'").is_err()
+        );
+        assert!(SqlConsoleService::check_read_only("select E'\\'; drop'").is_ok());
+    }
+}
@@ -0,0 +1,390 @@
+use crate::db::{ConfigRepository, ServerIdRepository};
+use crate::generators::RisingWaveDDLGenerator;
+use crate::models::{
+    DatabaseConfig, DbType, Setting, StagingStatementResult, StagingStatus, TableDdlPreview,
+    TableSchema,
+};
+use crate::services::ConnectionPoolManager;
+use crate::utils::error::{AppError, Result};
+use crate::utils::type_mapper::TypeMapper;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use sqlx::{MySqlPool, PgPool};
+use std::collections::HashSet;
+
+/// 预发环境中 SECRET 的内容，连接器无法用它登录 MySQL / StarRocks
+const DUMMY_SECRET: &str = "rw_cdc_sr_staging";
+/// 试运行使用的临时 schema 前缀
+const STAGING_SCHEMA_PREFIX: &str = "rw_cdc_sr_staging_";
+/// 分配试运行 Source 的 server.id 时使用的 schema，每个 MySQL 数据库固定占用一个 server.id
+const STAGING_SOURCE_SCHEMA: &str = "rw_cdc_sr_staging";
+/// 连接器校验阶段的错误，说明语句已通过解析和绑定
+const CONNECTOR_ERROR_HINTS: [&str; 8] = [
+    "connector",
+    "connection",
+    "connect to",
+    "access denied",
+    "authentication",
+    "timed out",
+    "sink error",
+    "validate",
+];
+
+/// 对象名、FROM 和 SECRET 引用中带引号的 schema
+static SCHEMA_REFERENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)\b(EXISTS|FROM|secret)\s+"(?:[^"]|"")*"\."#).unwrap());
+/// CREATE 语句创建的对象
+static CREATED_OBJECT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)^\s*CREATE\s+(SCHEMA|SOURCE|TABLE|SINK)\s+IF\s+NOT\s+EXISTS\s+([^\s(;]+)"#)
+        .unwrap()
+});
+/// CDC Source 的 server.id 属性
+static SERVER_ID: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)\bserver\.id\s*=\s*'\d+'"#).unwrap());
+/// 从 CDC Source 创建 Table 时引用的 Source
+static TABLE_SOURCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)\bFROM\s+(\S+)\s+TABLE\s+'"#).unwrap());
+
+/// 在预发 RisingWave 上试运行生成的 DDL，发现语法和版本兼容问题
+/// 所有对象创建在临时 schema 中，SECRET 使用假密码，试运行后全部删除
+pub struct StagingValidator;
+
+impl StagingValidator {
+    /// 请求中指定的预发连接，未指定时使用设置 rw.staging_config_id
+    pub fn staging_config_id(requested: Option<i64>) -> Option<i64> {
+        requested.or_else(|| Setting::RwStagingConfigId.parsed())
+    }
+
+    /// 试运行每个表的 RisingWave 语句，结果写入 staging_validation
+    /// schemas 为各表的 MySQL 表结构，Source 无法创建时用于建立替身表来校验 Sink
+    /// 试运行的 Source 连接同一个 MySQL，使用单独分配的 server.id，不与生产 Source 冲突
+    pub async fn validate(
+        app_db: &MySqlPool,
+        staging_config_id: i64,
+        production_config_id: i64,
+        mysql_config: &DatabaseConfig,
+        previews: &mut [TableDdlPreview],
+        schemas: &[TableSchema],
+    ) -> Result<()> {
+        if staging_config_id == production_config_id {
+            return Err(AppError::Validation(
+                "Staging RisingWave connection must differ from the production one".to_string(),
+            ));
+        }
+        let config = ConfigRepository::new(app_db)
+            .find_by_id(staging_config_id)
+            .await?;
+        if config.db_type != DbType::RisingWave {
+            return Err(AppError::Validation(format!(
+                "Connection {} is not a RisingWave connection",
+                config.name
+            )));
+        }
+
+        let pool = ConnectionPoolManager::postgres(&config).await?;
+        let schema = format!("{}{}", STAGING_SCHEMA_PREFIX, Utc::now().timestamp_millis());
+        let mut created = Vec::new();
+        let result = Self::run(
+            app_db,
+            &pool,
+            &schema,
+            mysql_config,
+            previews,
+            schemas,
+            &mut created,
+        )
+        .await;
+        Self::cleanup(&pool, &schema, created).await;
+        result
+    }
+
+    async fn run(
+        app_db: &MySqlPool,
+        pool: &PgPool,
+        schema: &str,
+        mysql_config: &DatabaseConfig,
+        previews: &mut [TableDdlPreview],
+        schemas: &[TableSchema],
+        created: &mut Vec<String>,
+    ) -> Result<()> {
+        sqlx::query(&RisingWaveDDLGenerator::generate_create_schema_ddl(schema))
+            .execute(pool)
+            .await
+            .map_err(|e| {
+                AppError::Validation(format!(
+                    "Cannot create schema {} on the staging RisingWave: {}",
+                    schema, e
+                ))
+            })?;
+        for secret in [
+            RisingWaveDDLGenerator::get_secret_name(schema),
+            RisingWaveDDLGenerator::get_starrocks_secret_name(schema),
+        ] {
+            sqlx::query(&format!(
+                "CREATE SECRET IF NOT EXISTS {} WITH ( backend = 'meta' ) AS '{}';",
+                secret, DUMMY_SECRET
+            ))
+            .execute(pool)
+            .await?;
+            created.push(RisingWaveDDLGenerator::generate_drop_secret_ddl(&secret));
+        }
+
+        let mut sources = HashSet::new();
+        for (preview, table_schema) in previews.iter_mut().zip(schemas) {
+            let mut results = Vec::with_capacity(preview.risingwave.len());
+            let mut table_ready = false;
+            for statement in &preview.risingwave {
+                let staged = Self::rewrite(statement, schema);
+                let Some((kind, name)) = Self::created_object(&staged) else {
+                    results.push(Self::execute(pool, &staged).await);
+                    continue;
+                };
+                let result = match kind.as_str() {
+                    // 使用临时 schema 代替目标 schema
+                    "SCHEMA" => StagingStatementResult {
+                        status: StagingStatus::Passed,
+                        error: None,
+                    },
+                    "SOURCE" => {
+                        let server_id =
+                            Self::staging_server_id(app_db, mysql_config, &preview.mysql_database)
+                                .await?;
+                        let staged = Self::with_server_id(&staged, server_id);
+                        let result = Self::execute(pool, &staged).await;
+                        if result.status == StagingStatus::Passed {
+                            sources.insert(name.clone());
+                            created.push(format!("DROP SOURCE IF EXISTS {};", name));
+                        }
+                        result
+                    }
+                    "TABLE" => {
+                        let source = TABLE_SOURCE.captures(&staged).map(|c| c[1].to_string());
+                        let result = if source.is_some_and(|s| sources.contains(&s)) {
+                            Self::execute(pool, &staged).await
+                        } else {
+                            StagingStatementResult {
+                                status: StagingStatus::Skipped,
+                                error: Some("CDC source was not created on staging".to_string()),
+                            }
+                        };
+                        // Source 不可用时按 MySQL 表结构建立替身表，继续校验 Sink
+                        table_ready = result.status == StagingStatus::Passed
+                            || match Self::stand_in_table_ddl(&name, table_schema) {
+                                Ok(ddl) => {
+                                    Self::execute(pool, &ddl).await.status == StagingStatus::Passed
+                                }
+                                Err(_) => false,
+                            };
+                        if table_ready {
+                            created.push(format!("DROP TABLE IF EXISTS {};", name));
+                        }
+                        result
+                    }
+                    "SINK" if !table_ready => StagingStatementResult {
+                        status: StagingStatus::Skipped,
+                        error: Some("Upstream table was not created on staging".to_string()),
+                    },
+                    _ => {
+                        let result = Self::execute(pool, &staged).await;
+                        if result.status == StagingStatus::Passed {
+                            created.push(format!("DROP {} IF EXISTS {};", kind, name));
+                        }
+                        result
+                    }
+                };
+                results.push(result);
+            }
+            preview.staging_validation = Some(results);
+        }
+        Ok(())
+    }
+
+    async fn execute(pool: &PgPool, sql: &str) -> StagingStatementResult {
+        match sqlx::query(sql).execute(pool).await {
+            Ok(_) => StagingStatementResult {
+                status: StagingStatus::Passed,
+                error: None,
+            },
+            Err(e) => {
+                let error = e.to_string();
+                StagingStatementResult {
+                    status: Self::classify(&error),
+                    error: Some(error),
+                }
+            }
+        }
+    }
+
+    /// 按创建的相反顺序删除试运行的对象和临时 schema，失败时只记录警告
+    async fn cleanup(pool: &PgPool, schema: &str, created: Vec<String>) {
+        for sql in created.iter().rev().map(String::as_str).chain([format!(
+            "DROP SCHEMA IF EXISTS \"{}\";",
+            schema
+        )
+        .as_str()])
+        {
+            if let Err(e) = sqlx::query(sql).execute(pool).await {
+                tracing::warn!("Failed to clean up staging validation ({}): {}", sql, e);
+            }
+        }
+    }
+
+    /// 把语句中的目标 schema 替换为临时 schema
+    fn rewrite(statement: &str, schema: &str) -> String {
+        SCHEMA_REFERENCE
+            .replace_all(statement, format!("${{1}} \"{}\".", schema))
+            .into_owned()
+    }
+
+    /// 试运行 Source 使用的 server.id，同一个 MySQL 数据库的试运行始终相同
+    async fn staging_server_id(
+        app_db: &MySqlPool,
+        mysql_config: &DatabaseConfig,
+        mysql_database: &str,
+    ) -> Result<u32> {
+        ServerIdRepository::new(app_db)
+            .allocate(
+                &mysql_config.host,
+                mysql_config.port,
+                mysql_database,
+                &RisingWaveDDLGenerator::get_source_name(mysql_database, STAGING_SOURCE_SCHEMA),
+                None,
+            )
+            .await
+    }
+
+    /// 替换 Source 语句中的 server.id
+    fn with_server_id(statement: &str, server_id: u32) -> String {
+        SERVER_ID
+            .replace_all(statement, format!("server.id = '{}'", server_id))
+            .into_owned()
+    }
+
+    /// CREATE 语句创建的对象类型（大写）和名称
+    fn created_object(statement: &str) -> Option<(String, String)> {
+        CREATED_OBJECT
+            .captures(statement)
+            .map(|c| (c[1].to_uppercase(), c[2].to_string()))
+    }
+
+    /// 与 CDC 表结构相同的普通表
+    fn stand_in_table_ddl(table_name: &str, schema: &TableSchema) -> Result<String> {
+        let mut columns = Vec::with_capacity(schema.columns.len() + 1);
+        for column in &schema.columns {
+            let data_type = TypeMapper::mysql_to_risingwave(&column.data_type)?;
+            // RisingWave 的字符串类型不带长度
+            let data_type = if data_type.starts_with("CHAR") || data_type.starts_with("VARCHAR") {
+                "VARCHAR".to_string()
+            } else {
+                data_type
+            };
+            columns.push(format!(
+                "{} {}",
+                RisingWaveDDLGenerator::quote_identifier(&column.name),
+                data_type
+            ));
+        }
+        columns.push(format!(
+            "PRIMARY KEY ({})",
+            schema
+                .primary_keys
+                .iter()
+                .map(|k| RisingWaveDDLGenerator::quote_identifier(k))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        Ok(format!(
+            "CREATE TABLE IF NOT EXISTS {} ({});",
+            table_name,
+            columns.join(", ")
+        ))
+    }
+
+    /// 连接器校验阶段的错误说明语句本身可以执行，其余错误视为语法或兼容性问题
+    fn classify(error: &str) -> StagingStatus {
+        let error = error.to_lowercase();
+        if CONNECTOR_ERROR_HINTS
+            .iter()
+            .any(|hint| error.contains(hint))
+        {
+            StagingStatus::ConnectorError
+        } else {
+            StagingStatus::Failed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_to_staging_schema() {
+        let staged = StagingValidator::rewrite(
+            r#"CREATE SINK IF NOT EXISTS "ods".orders_to_sr_sink FROM "ods".orders WITH (starrocks.password = secret "ods".starrocks_pwd, starrocks.database = 'ods');"#,
+            "staging",
+        );
+        assert_eq!(
+            staged,
+            r#"CREATE SINK IF NOT EXISTS "staging".orders_to_sr_sink FROM "staging".orders WITH (starrocks.password = secret "staging".starrocks_pwd, starrocks.database = 'ods');"#
+        );
+        assert_eq!(
+            StagingValidator::created_object(&staged),
+            Some((
+                "SINK".to_string(),
+                r#""staging".orders_to_sr_sink"#.to_string()
+            ))
+        );
+
+        let table = StagingValidator::rewrite(
+            r#"CREATE TABLE IF NOT EXISTS "ods".orders (*) FROM "shared".shop_source TABLE 'shop.orders';"#,
+            "staging",
+        );
+        assert_eq!(
+            TABLE_SOURCE.captures(&table).map(|c| c[1].to_string()),
+            Some(r#""staging".shop_source"#.to_string())
+        );
+
+        let source = StagingValidator::with_server_id(
+            &RisingWaveDDLGenerator::generate_source_ddl(
+                &DatabaseConfig {
+                    id: 1,
+                    name: "mysql".to_string(),
+                    db_type: DbType::MySQL,
+                    host: "mysql".to_string(),
+                    port: 3306,
+                    username: "root".to_string(),
+                    password: String::new(),
+                    no_password: true,
+                    database_name: None,
+                    proxy: None,
+                    tls: None,
+                    created_at: Utc::now(),
+                    version: 1,
+                    updated_at: Utc::now(),
+                },
+                "shop",
+                "ods",
+                5001,
+            )
+            .unwrap(),
+            6001,
+        );
+        assert!(source.contains("server.id = '6001'"));
+        assert!(!source.contains("5001"));
+
+        assert_eq!(
+            StagingValidator::classify(
+                "error returned from database: Connector error: Access denied for user 'root'"
+            ),
+            StagingStatus::ConnectorError
+        );
+        assert_eq!(
+            StagingValidator::classify(
+                "error returned from database: sql parser error: Expected ), found: WITH"
+            ),
+            StagingStatus::Failed
+        );
+    }
+}
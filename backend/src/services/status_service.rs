@@ -0,0 +1,247 @@
+use crate::db::TaskRepository;
+use crate::models::{
+    PipelineHealth, PipelineStatus, StatusSummary, SyncTask, TaskFilter, TaskStatus,
+};
+use crate::utils::error::Result;
+use sqlx::MySqlPool;
+use std::collections::HashMap;
+
+/// 状态页统计时扫描的最近任务数
+const STATUS_TASK_WINDOW: i64 = 500;
+
+/// 管道状态服务（用于公开状态页）
+pub struct StatusService;
+
+impl StatusService {
+    /// 状态页是否开启：状态页不需要登录，默认关闭，设置 STATUS_PAGE_ENABLED=true 开启
+    pub fn is_enabled() -> bool {
+        Self::parse_enabled(std::env::var("STATUS_PAGE_ENABLED").ok().as_deref())
+    }
+
+    fn parse_enabled(value: Option<&str>) -> bool {
+        value.is_some_and(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+    }
+
+    /// 汇总最近任务中每个管道的健康状态
+    pub async fn summarize(app_db: &MySqlPool) -> Result<StatusSummary> {
+        let task_repo = TaskRepository::new(app_db);
+        let tasks = task_repo
+            .find_history(&TaskFilter::default(), STATUS_TASK_WINDOW, 0)
+            .await?;
+        Ok(Self::summarize_tasks(&tasks))
+    }
+
+    /// 按 "源表 -> 目标表" 聚合任务（tasks 按 started_at 倒序）
+    pub fn summarize_tasks(tasks: &[SyncTask]) -> StatusSummary {
+        let mut pipelines: Vec<PipelineStatus> = Vec::new();
+        let mut index: HashMap<(String, String), usize> = HashMap::new();
+
+        for task in tasks {
+            let Ok(requests) = task.sync_requests() else {
+                continue;
+            };
+
+            for request in requests {
+                let source = format!("{}.{}", request.mysql_database, request.mysql_table);
+                let target = format!("{}.{}", request.target_database, request.target_table);
+                let key = (source.clone(), target.clone());
+
+                match index.get(&key) {
+                    // 已经记录过更新的任务，只补充最近一次成功时间
+                    Some(&i) => {
+                        let pipeline = &mut pipelines[i];
+                        if pipeline.last_sync_at.is_none() && task.status == TaskStatus::Completed {
+                            pipeline.last_sync_at = task.completed_at;
+                            if pipeline.health == PipelineHealth::Red {
+                                pipeline.health = PipelineHealth::Yellow;
+                            }
+                        }
+                    }
+                    None => {
+                        let health = match task.status {
+                            TaskStatus::Completed => PipelineHealth::Green,
+                            TaskStatus::Pending | TaskStatus::Running => PipelineHealth::Yellow,
+                            TaskStatus::Failed
+                            | TaskStatus::Cancelled
+                            | TaskStatus::Interrupted => PipelineHealth::Red,
+                        };
+                        let last_sync_at = if task.status == TaskStatus::Completed {
+                            task.completed_at
+                        } else {
+                            None
+                        };
+
+                        index.insert(key, pipelines.len());
+                        pipelines.push(PipelineStatus {
+                            source,
+                            target,
+                            health,
+                            last_status: task.status.as_str().to_string(),
+                            last_task_at: task.started_at,
+                            last_sync_at,
+                        });
+                    }
+                }
+            }
+        }
+
+        let overall = pipelines
+            .iter()
+            .map(|p| p.health)
+            .max()
+            .unwrap_or(PipelineHealth::Green);
+
+        StatusSummary {
+            overall,
+            generated_at: chrono::Utc::now(),
+            pipelines,
+        }
+    }
+
+    /// 渲染为简单的 HTML 页面
+    pub fn render_html(summary: &StatusSummary) -> String {
+        let rows = summary
+            .pipelines
+            .iter()
+            .map(|p| {
+                format!(
+                    "<tr><td><span class=\"dot {}\"></span>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    p.health.as_str(),
+                    html_escape(&p.target),
+                    html_escape(&p.source),
+                    html_escape(&p.last_status),
+                    p.last_sync_at
+                        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    p.last_task_at.format("%Y-%m-%d %H:%M:%S UTC")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>RW CDC SR Status</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ text-align: left; padding: 6px 10px; border-bottom: 1px solid #eee; }}
+.dot {{ display: inline-block; width: 10px; height: 10px; border-radius: 50%; margin-right: 8px; }}
+.green {{ background: #52c41a; }}
+.yellow {{ background: #faad14; }}
+.red {{ background: #f5222d; }}
+</style>
+</head>
+<body>
+<h1><span class="dot {}"></span>Pipeline Status</h1>
+<p>Generated at {}</p>
+<table>
+<tr><th>Target</th><th>Source</th><th>Last Status</th><th>Last Successful Sync</th><th>Last Run</th></tr>
+{}
+</table>
+</body>
+</html>"#,
+            summary.overall.as_str(),
+            summary.generated_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            rows
+        )
+    }
+}
+
+/// 转义 HTML 特殊字符
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn task(id: i64, table: &str, status: TaskStatus, hours_ago: i64) -> SyncTask {
+        let started_at = Utc::now() - Duration::hours(hours_ago);
+        SyncTask {
+            id,
+            task_name: format!("Sync shop.{}", table),
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: table.to_string(),
+            target_database: "ods".to_string(),
+            target_table: table.to_string(),
+            completed_at: (status == TaskStatus::Completed).then_some(started_at),
+            status,
+            started_at,
+            error_message: None,
+            options: "{}".to_string(),
+            preflight_result: None,
+            requests: None,
+            sink_requests: None,
+        }
+    }
+
+    #[test]
+    fn test_status_page_is_opt_in() {
+        assert!(!StatusService::parse_enabled(None));
+        assert!(!StatusService::parse_enabled(Some("")));
+        assert!(!StatusService::parse_enabled(Some("false")));
+        assert!(!StatusService::parse_enabled(Some("off")));
+        assert!(StatusService::parse_enabled(Some("true")));
+        assert!(StatusService::parse_enabled(Some(" TRUE ")));
+        assert!(StatusService::parse_enabled(Some("1")));
+    }
+
+    #[test]
+    fn test_summarize_tasks_uses_latest_task_per_pipeline() {
+        // 按 started_at 倒序
+        let tasks = vec![
+            task(4, "orders", TaskStatus::Failed, 1),
+            task(3, "users", TaskStatus::Completed, 2),
+            task(2, "orders", TaskStatus::Completed, 3),
+            task(1, "items", TaskStatus::Failed, 4),
+        ];
+
+        let summary = StatusService::summarize_tasks(&tasks);
+        assert_eq!(summary.pipelines.len(), 3);
+        assert_eq!(summary.overall, PipelineHealth::Red);
+
+        let orders = &summary.pipelines[0];
+        assert_eq!(orders.target, "ods.orders");
+        assert_eq!(orders.last_status, "failed");
+        assert_eq!(orders.health, PipelineHealth::Yellow);
+        assert!(orders.last_sync_at.is_some());
+
+        assert_eq!(summary.pipelines[1].health, PipelineHealth::Green);
+        assert_eq!(summary.pipelines[2].health, PipelineHealth::Red);
+    }
+
+    #[test]
+    fn test_summarize_tasks_skips_sink_tasks() {
+        let mut sink_task = task(2, "orders_mv", TaskStatus::Failed, 1);
+        sink_task.sink_requests = Some("encrypted".to_string());
+        let tasks = vec![sink_task, task(1, "orders", TaskStatus::Completed, 2)];
+
+        let summary = StatusService::summarize_tasks(&tasks);
+        assert_eq!(summary.pipelines.len(), 1);
+        assert_eq!(summary.pipelines[0].target, "ods.orders");
+        assert_eq!(summary.overall, PipelineHealth::Green);
+    }
+
+    #[test]
+    fn test_render_html_escapes_names() {
+        let mut summary =
+            StatusService::summarize_tasks(&[task(1, "a<b>", TaskStatus::Completed, 1)]);
+        summary.generated_at = Utc::now();
+        let html = StatusService::render_html(&summary);
+        assert!(html.contains("ods.a&lt;b&gt;"));
+        assert!(!html.contains("<b>"));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::db::TaskRepository;
+use crate::models::StepStatus;
+use crate::utils::error::Result;
+use sqlx::MySqlPool;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// 同步任务步骤记录器
+/// 记录当前步骤的开始时间，结束时写入状态和耗时，任务出错时把当前步骤标记为失败
+pub struct StepTracker<'a> {
+    app_db: &'a MySqlPool,
+    task_id: i64,
+    current: Mutex<Option<(i32, Instant)>>,
+}
+
+impl<'a> StepTracker<'a> {
+    pub fn new(app_db: &'a MySqlPool, task_id: i64) -> Self {
+        Self {
+            app_db,
+            task_id,
+            current: Mutex::new(None),
+        }
+    }
+
+    /// 开始执行计划中的第 step_index 个步骤
+    pub async fn begin(&self, step_index: i32) -> Result<()> {
+        *self.current.lock().unwrap() = Some((step_index, Instant::now()));
+        TaskRepository::new(self.app_db)
+            .start_step(self.task_id, step_index)
+            .await
+    }
+
+    /// 结束当前步骤
+    pub async fn finish(&self, status: StepStatus) -> Result<()> {
+        self.finish_current(status, None).await
+    }
+
+    /// 把当前步骤标记为失败，没有进行中的步骤时什么都不做
+    pub async fn fail(&self, error: &str) -> Result<()> {
+        self.finish_current(StepStatus::Failed, Some(error)).await
+    }
+
+    async fn finish_current(&self, status: StepStatus, error: Option<&str>) -> Result<()> {
+        let Some((step_index, started)) = self.current.lock().unwrap().take() else {
+            return Ok(());
+        };
+        TaskRepository::new(self.app_db)
+            .finish_step(
+                self.task_id,
+                step_index,
+                status,
+                Some(started.elapsed().as_millis() as i64),
+                error,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        NewTaskStep, StepStatus, SyncOptions, SyncProgress, SyncRequest, SyncStep, SyncTask,
+        TaskStatus, TaskStep,
+    };
+
+    fn request(table: &str) -> SyncRequest {
+        SyncRequest {
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: table.to_string(),
+            target_database: "dw".to_string(),
+            target_table: table.to_string(),
+            options: SyncOptions::default(),
+            row_filter: None,
+            partition: None,
+            column_mapping: Default::default(),
+            nested_columns: Default::default(),
+            spatial_columns: Default::default(),
+        }
+    }
+
+    fn task(status: TaskStatus) -> SyncTask {
+        SyncTask {
+            id: 7,
+            task_name: "Batch Sync 2 tables".to_string(),
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            mysql_database: "shop".to_string(),
+            mysql_table: "[Batch]".to_string(),
+            target_database: "dw".to_string(),
+            target_table: "[Batch]".to_string(),
+            status,
+            started_at: chrono::Utc::now(),
+            completed_at: None,
+            error_message: None,
+            options: "{}".to_string(),
+            preflight_result: None,
+            requests: None,
+            sink_requests: None,
+        }
+    }
+
+    #[test]
+    fn test_step_plan_and_progress() {
+        let plan = NewTaskStep::plan(&[request("orders"), request("users")]);
+        assert_eq!(plan.len(), 1 + 2 * SyncStep::TABLE_STEPS.len());
+        assert_eq!(plan[0].step, SyncStep::Connect);
+        let index = NewTaskStep::index_of(1, SyncStep::CreateSink);
+        assert_eq!(plan[index as usize].step, SyncStep::CreateSink);
+        assert_eq!(plan[index as usize].table_name.as_deref(), Some("shop.users"));
+        assert_eq!(index as usize, plan.len() - 1);
+
+        let steps: Vec<TaskStep> = plan
+            .iter()
+            .map(|step| TaskStep {
+                id: step.step_index as i64 + 1,
+                task_id: 7,
+                step_index: step.step_index,
+                step: step.step,
+                table_name: step.table_name.clone(),
+                status: match step.step_index {
+                    0..=2 => StepStatus::Completed,
+                    3 => StepStatus::Skipped,
+                    4 => StepStatus::Running,
+                    _ => StepStatus::Pending,
+                },
+                started_at: None,
+                completed_at: None,
+                duration_ms: None,
+                error_message: None,
+            })
+            .collect();
+
+        let progress = SyncProgress::new(&task(TaskStatus::Running), steps.clone());
+        assert_eq!(progress.total_steps, 13);
+        assert_eq!(progress.current_step_index, 4);
+        assert_eq!(progress.percent, 30);
+        assert_eq!(
+            progress.current_step.as_deref(),
+            Some("prepare_target (shop.orders)")
+        );
+
+        assert_eq!(SyncProgress::new(&task(TaskStatus::Completed), steps).percent, 100);
+        assert_eq!(SyncProgress::new(&task(TaskStatus::Running), vec![]).percent, 0);
+    }
+}
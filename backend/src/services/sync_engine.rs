@@ -1,10 +1,40 @@
-use crate::db::{ConfigRepository, TaskRepository};
-use crate::generators::{RisingWaveDDLGenerator, StarRocksDDLGenerator};
-use crate::models::{DatabaseConfig, SyncRequest, SyncTask, TaskStatus};
-use crate::services::{ConnectionService, MetadataService};
+use crate::db::{ConfigRepository, ManagedObjectRepository, ServerIdRepository, TaskRepository};
+use crate::generators::{
+    ColocationMember, DorisDDLGenerator, RisingWaveDDLGenerator, StarRocksDDLGenerator,
+};
+use crate::models::{
+    CapacityCheckMode, DatabaseConfig, DbType, Feature, ManagedObject, ManagedObjectType,
+    NewTaskStep, StarRocksTableOptions,
+    StepStatus, SyncRequest, SyncStep, SyncTask, TableDdlPreview, TableSchema, TableSyncAction,
+    TableSyncOutcome, TaskLogPayload, TaskStatus, DEFAULT_WORKSPACE,
+};
+use crate::services::{
+    AuditService, BackfillGate, BucketStrategy, CapacityService, ConnectionService, DdlExecutor,
+    ExistingObjectService, FeatureFlagService, LegacyNameService, MetadataService,
+    NotificationService, ObjectState, Shutdown, StagingValidator, StepTracker, TaskLogger,
+};
 use crate::utils::error::Result;
-use mysql_async::prelude::*;
+use crate::utils::fault;
+use once_cell::sync::Lazy;
 use sqlx::{MySqlPool, PgPool};
+use std::collections::HashSet;
+use std::future::Future;
+use std::time::Instant;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// 默认的最大并发同步任务数
+pub const DEFAULT_MAX_CONCURRENT_SYNCS: usize = 4;
+
+/// 全局同步并发限制，批量重试等一次提交大量任务时超出的任务排队，
+/// 避免同时对 MySQL、RisingWave 和 StarRocks 打开过多连接
+static SYNC_PERMITS: Lazy<Semaphore> = Lazy::new(|| {
+    let permits = std::env::var("SYNC_MAX_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_SYNCS);
+    Semaphore::new(permits)
+});
 
 /// 同步引擎
 pub struct SyncEngine {
@@ -41,23 +71,19 @@ impl SyncEngine {
         tracing::info!("Starting batch sync for {} tables", requests.len());
 
         // 验证所有请求使用相同的配置
+        Self::ensure_same_configs(&requests)?;
         let first_request = &requests[0];
-        for req in &requests {
-            if req.mysql_config_id != first_request.mysql_config_id
-                || req.rw_config_id != first_request.rw_config_id
-                || req.sr_config_id != first_request.sr_config_id
-            {
-                return Err(crate::utils::error::AppError::Validation(
-                    "All tables must use the same database configurations".to_string(),
-                ));
-            }
-        }
 
         // 获取数据库配置
         let config_repo = ConfigRepository::new(&self.app_db);
         let mysql_config = config_repo.find_by_id(first_request.mysql_config_id).await?;
         let rw_config = config_repo.find_by_id(first_request.rw_config_id).await?;
         let sr_config = config_repo.find_by_id(first_request.sr_config_id).await?;
+        Self::ensure_sink_target(&sr_config)?;
+        // SRV 名称解析为实际主机和端口
+        let mysql_config = ConnectionService::resolve_config(mysql_config).await?;
+        let rw_config = ConnectionService::resolve_config(rw_config).await?;
+        let sr_config = ConnectionService::resolve_config(sr_config).await?;
 
         // 创建批量任务记录
         let task_repo = TaskRepository::new(&self.app_db);
@@ -98,13 +124,34 @@ impl SyncEngine {
             completed_at: None,
             error_message: None,
             options: serde_json::to_string(&first_request.options)?,
+            preflight_result: None,
+            requests: Some(serde_json::to_string(&requests)?),
+            sink_requests: None,
         };
 
-        let task_id = task_repo.create(&task).await?;
+        let task_id = task_repo
+            .create_with_steps(
+                &task,
+                &format!("Task created for {} tables", requests.len()),
+                &NewTaskStep::plan(&requests),
+            )
+            .await?;
 
         // 异步执行批量同步任务
         let app_db_clone = self.app_db.clone();
         tokio::spawn(async move {
+            let _permit = Self::acquire_slot(&SYNC_PERMITS, || async {
+                tracing::info!("Batch sync task {} is waiting for a free sync slot", task_id);
+                let _ = TaskRepository::new(&app_db_clone)
+                    .add_log(
+                        task_id,
+                        "info",
+                        "Waiting for other sync tasks to finish (SYNC_MAX_CONCURRENCY reached)",
+                        None,
+                    )
+                    .await;
+            })
+            .await;
             tracing::info!("Executing batch sync task ID: {}", task_id);
             let result = Self::execute_batch_sync(
                 app_db_clone.clone(),
@@ -120,38 +167,509 @@ impl SyncEngine {
             match result {
                 Ok(_) => {
                     let _ = task_repo
-                        .update_status(task_id, TaskStatus::Completed, None)
+                        .finish_with_log(
+                            task_id,
+                            TaskStatus::Completed,
+                            None,
+                            "info",
+                            "Batch sync completed successfully",
+                        )
                         .await;
+                }
+                // 关闭过程中不再开始新的 DDL，任务按中断结束
+                Err(e) if Shutdown::is_draining() => {
+                    let error_msg = format!("Interrupted by server shutdown: {}", e);
+                    tracing::warn!("Batch sync task {}: {}", task_id, error_msg);
                     let _ = task_repo
-                        .add_log(task_id, "info", "Batch sync completed successfully")
+                        .finish_with_log(
+                            task_id,
+                            TaskStatus::Interrupted,
+                            Some(error_msg.clone()),
+                            "warn",
+                            &error_msg,
+                        )
                         .await;
                 }
                 Err(e) => {
                     let error_msg = e.to_string();
                     tracing::error!("Batch sync task {} failed: {}", task_id, error_msg);
                     let _ = task_repo
-                        .update_status(task_id, TaskStatus::Failed, Some(error_msg.clone()))
-                        .await;
-                    let _ = task_repo
-                        .add_log(task_id, "error", &format!("Batch sync failed: {}", error_msg))
+                        .finish_with_log(
+                            task_id,
+                            TaskStatus::Failed,
+                            Some(error_msg.clone()),
+                            "error",
+                            &format!("Batch sync failed: {}", error_msg),
+                        )
                         .await;
                 }
             }
+            NotificationService::notify_task_finished(&app_db_clone, task_id);
         });
 
         Ok(task_id)
     }
 
+    /// 取得同步并发许可；许可用完时先执行 on_wait，再排队等待
+    async fn acquire_slot<'a, W: Future<Output = ()>>(
+        permits: &'a Semaphore,
+        on_wait: impl FnOnce() -> W,
+    ) -> SemaphorePermit<'a> {
+        if let Ok(permit) = permits.try_acquire() {
+            return permit;
+        }
+        on_wait().await;
+        permits.acquire().await.expect("sync semaphore is never closed")
+    }
+
+    /// 验证所有请求使用相同的配置
+    pub fn ensure_same_configs(requests: &[SyncRequest]) -> Result<()> {
+        let first_request = &requests[0];
+        for req in requests {
+            if req.mysql_config_id != first_request.mysql_config_id
+                || req.rw_config_id != first_request.rw_config_id
+                || req.sr_config_id != first_request.sr_config_id
+            {
+                return Err(crate::utils::error::AppError::Validation(
+                    "All tables must use the same database configurations".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 查找同一组请求（连接、表和选项都相同，不区分顺序）等待中或执行中的任务
+    pub async fn find_duplicate(&self, requests: &[SyncRequest]) -> Result<Option<i64>> {
+        let Some(first) = requests.first() else {
+            return Ok(None);
+        };
+        let active = TaskRepository::new(&self.app_db)
+            .find_active_syncs(
+                first.mysql_config_id,
+                first.rw_config_id,
+                first.sr_config_id,
+            )
+            .await?;
+        let expected = Self::request_set(requests)?;
+        for task in active {
+            // 无法还原请求的旧任务不参与比较
+            let Ok(existing) = task.sync_requests() else {
+                continue;
+            };
+            if !existing.is_empty() && Self::request_set(&existing)? == expected {
+                return Ok(Some(task.id));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 请求序列化后排序，用于不区分顺序地比较两组请求
+    fn request_set(requests: &[SyncRequest]) -> Result<Vec<String>> {
+        let mut set = requests
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        set.sort();
+        Ok(set)
+    }
+
+    /// 生成每个表将执行的 DDL，但不执行
+    /// 配置了预发 RisingWave 连接时，先在预发连接上试运行 RisingWave 语句
+    pub async fn preview_ddl(
+        &self,
+        requests: &[SyncRequest],
+        staging_rw_config_id: Option<i64>,
+    ) -> Result<Vec<TableDdlPreview>> {
+        if requests.is_empty() {
+            return Err(crate::utils::error::AppError::Validation(
+                "No tables to sync".to_string(),
+            ));
+        }
+        Self::ensure_same_configs(requests)?;
+
+        let config_repo = ConfigRepository::new(&self.app_db);
+        let mysql_config = config_repo.find_by_id(requests[0].mysql_config_id).await?;
+        let sr_config = config_repo.find_by_id(requests[0].sr_config_id).await?;
+        // SRV 名称解析为实际主机和端口
+        let mysql_config = ConnectionService::resolve_config(mysql_config).await?;
+        let sr_config = ConnectionService::resolve_config(sr_config).await?;
+
+        let suggested_buckets = Self::suggest_buckets(&mysql_config, requests).await;
+        let mut previews = Vec::with_capacity(requests.len());
+        let mut schemas = Vec::with_capacity(requests.len());
+        let mut colocation_members = Vec::new();
+        for (request, suggested_buckets) in requests.iter().zip(suggested_buckets) {
+            let schema = MetadataService::get_mysql_table_schema(
+                &mysql_config,
+                &request.mysql_database,
+                &request.mysql_table,
+            )
+            .await?;
+
+            let mut risingwave =
+                vec![RisingWaveDDLGenerator::generate_create_schema_ddl(&request.target_database)];
+            // 共享已有的 Source 时不再创建新的 Source
+            let source_name = match Self::shared_source(&self.app_db, request).await? {
+                Some(shared) => shared.object_name,
+                None => {
+                    risingwave.push(RisingWaveDDLGenerator::generate_source_ddl(
+                        &mysql_config,
+                        &request.mysql_database,
+                        &request.target_database,
+                        Self::allocate_server_id(&self.app_db, &mysql_config, request).await?,
+                    )?);
+                    RisingWaveDDLGenerator::get_source_name(
+                        &request.mysql_database,
+                        &request.target_database,
+                    )
+                }
+            };
+            risingwave.push(RisingWaveDDLGenerator::generate_table_ddl_from_source(
+                &source_name,
+                &request.mysql_database,
+                &request.mysql_table,
+                &request.target_database,
+                &request.target_table,
+            )?);
+            risingwave.push(RisingWaveDDLGenerator::generate_sink_ddl(&sr_config, request, &schema)?);
+
+            let starrocks = vec![
+                StarRocksDDLGenerator::generate_create_database_ddl(&request.target_database),
+                Self::generate_target_table_ddl(&sr_config, request, &schema, suggested_buckets)?,
+            ];
+
+            if sr_config.db_type != DbType::Doris {
+                colocation_members.extend(Self::colocation_member(request, &schema, suggested_buckets)?);
+                StarRocksDDLGenerator::validate_colocation(&colocation_members)?;
+            }
+
+            previews.push(TableDdlPreview {
+                mysql_database: request.mysql_database.clone(),
+                mysql_table: request.mysql_table.clone(),
+                target_database: request.target_database.clone(),
+                target_table: request.target_table.clone(),
+                risingwave,
+                starrocks,
+                suggested_buckets,
+                staging_validation: None,
+            });
+            schemas.push(schema);
+        }
+
+        if let Some(staging_config_id) = StagingValidator::staging_config_id(staging_rw_config_id) {
+            StagingValidator::validate(
+                &self.app_db,
+                staging_config_id,
+                requests[0].rw_config_id,
+                &mysql_config,
+                &mut previews,
+                &schemas,
+            )
+            .await?;
+        }
+
+        Ok(previews)
+    }
+
+    /// 获取 CDC Source 的 server.id（同一 Source 始终相同）
+    async fn allocate_server_id(
+        app_db: &MySqlPool,
+        mysql_config: &DatabaseConfig,
+        request: &SyncRequest,
+    ) -> Result<u32> {
+        ServerIdRepository::new(app_db)
+            .allocate(
+                &mysql_config.host,
+                mysql_config.port,
+                &request.mysql_database,
+                &RisingWaveDDLGenerator::get_source_name(
+                    &request.mysql_database,
+                    &request.target_database,
+                ),
+                request.options.server_id,
+            )
+            .await
+    }
+
+    /// 请求开启共享 Source 时，查找同一 MySQL 数据库已登记的共享 Source
+    async fn shared_source(
+        app_db: &MySqlPool,
+        request: &SyncRequest,
+    ) -> Result<Option<ManagedObject>> {
+        if !request.options.share_source {
+            return Ok(None);
+        }
+        ManagedObjectRepository::new(app_db)
+            .find_shared(
+                request.rw_config_id,
+                ManagedObjectType::Source,
+                &ManagedObject::source_share_key(request.mysql_config_id, &request.mysql_database),
+            )
+            .await
+    }
+
+    /// 登记共享的 Source 及其 Secret（已登记时不变），返回对象 ID
+    async fn register_shared_source(
+        app_db: &MySqlPool,
+        request: &SyncRequest,
+        source_name: &str,
+    ) -> Result<Vec<i64>> {
+        let repo = ManagedObjectRepository::new(app_db);
+        let share_key =
+            ManagedObject::source_share_key(request.mysql_config_id, &request.mysql_database);
+        let secret_name = RisingWaveDDLGenerator::get_source_secret_name(source_name);
+        let mut ids = Vec::with_capacity(2);
+        for (object_type, object_name) in [
+            (ManagedObjectType::Secret, secret_name.as_str()),
+            (ManagedObjectType::Source, source_name),
+        ] {
+            ids.push(
+                repo.register(request.rw_config_id, object_type, object_name, Some(&share_key))
+                    .await?,
+            );
+        }
+        Ok(ids)
+    }
+
+    /// 目标连接必须是 StarRocks 或 Doris
+    fn ensure_sink_target(config: &DatabaseConfig) -> Result<()> {
+        if !config.db_type.is_sink_target() {
+            return Err(crate::utils::error::AppError::Validation(format!(
+                "Config {} is not a StarRocks or Doris connection",
+                config.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// 为未指定分桶数的请求按源表大小建议分桶数，与 requests 一一对应
+    /// 同一 Colocation Group 的表使用组内最大的建议值；统计信息获取失败时不建议，由 StarRocks 决定
+    async fn suggest_buckets(
+        mysql_config: &DatabaseConfig,
+        requests: &[SyncRequest],
+    ) -> Vec<Option<u32>> {
+        let strategy = CapacityService::bucket_strategy();
+        // Colocation Group 需要固定分桶数，自动分桶模式下也按大小建议
+        let needs_suggestion = |request: &SyncRequest| {
+            let options = request.options.starrocks_table_options();
+            options.buckets.is_none()
+                && !options.auto_bucket
+                && (strategy == BucketStrategy::Size
+                    || (strategy == BucketStrategy::Auto && options.colocate_with.is_some()))
+        };
+        if !requests.iter().any(needs_suggestion) {
+            return vec![None; requests.len()];
+        }
+
+        let tables: Vec<(String, String)> = requests
+            .iter()
+            .map(|r| (r.mysql_database.clone(), r.mysql_table.clone()))
+            .collect();
+        let stats = match MetadataService::get_mysql_table_stats(mysql_config, &tables).await {
+            Ok(stats) => stats,
+            Err(e) => {
+                tracing::warn!("Skipping bucket suggestion, failed to fetch table stats: {}", e);
+                return vec![None; requests.len()];
+            }
+        };
+
+        let mut suggestions: Vec<Option<u32>> = requests
+            .iter()
+            .zip(stats.iter())
+            .map(|(request, stats)| {
+                needs_suggestion(request).then(|| CapacityService::suggest_buckets(stats))
+            })
+            .collect();
+
+        let group_of = |request: &SyncRequest| request.options.starrocks_table_options().colocate_with;
+        for (index, request) in requests.iter().enumerate() {
+            let Some(group) = group_of(request) else {
+                continue;
+            };
+            let group_max = requests
+                .iter()
+                .zip(suggestions.iter())
+                .filter(|(r, _)| group_of(r).as_deref() == Some(group.as_str()))
+                .filter_map(|(_, s)| *s)
+                .max();
+            if suggestions[index].is_some() {
+                suggestions[index] = group_max;
+            }
+        }
+        suggestions
+    }
+
+    /// 本次建表实际使用的表属性
+    /// 没有指定分桶数时使用建议分桶数，SR_BUCKET_SUGGESTION=auto 时使用自动分桶（Colocation Group 除外）
+    fn target_table_options(
+        request: &SyncRequest,
+        suggested_buckets: Option<u32>,
+    ) -> StarRocksTableOptions {
+        let mut options = request.options.starrocks_table_options();
+        if options.buckets.is_none() && !options.auto_bucket {
+            if suggested_buckets.is_some() {
+                options.buckets = suggested_buckets;
+            } else if CapacityService::bucket_strategy() == BucketStrategy::Auto
+                && options.colocate_with.is_none()
+            {
+                options.auto_bucket = true;
+            }
+        }
+        options
+    }
+
+    /// 按目标库类型生成建表语句
+    fn generate_target_table_ddl(
+        target_config: &DatabaseConfig,
+        request: &SyncRequest,
+        schema: &TableSchema,
+        suggested_buckets: Option<u32>,
+    ) -> Result<String> {
+        let target_schema = request.target_schema(schema);
+        let options = Self::target_table_options(request, suggested_buckets);
+        match target_config.db_type {
+            DbType::Doris => DorisDDLGenerator::generate_table_ddl(
+                &target_schema,
+                &request.target_database,
+                &request.target_table,
+                &options,
+                request.partition.as_ref(),
+            ),
+            _ => StarRocksDDLGenerator::generate_table_ddl(
+                &target_schema,
+                &request.target_database,
+                &request.target_table,
+                &options,
+                request.partition.as_ref(),
+            ),
+        }
+    }
+
+    /// 表的 Colocation Group 成员信息（只用于 StarRocks 目标）
+    fn colocation_member(
+        request: &SyncRequest,
+        schema: &TableSchema,
+        suggested_buckets: Option<u32>,
+    ) -> Result<Option<ColocationMember>> {
+        StarRocksDDLGenerator::colocation_member(
+            &request.target_schema(schema),
+            &request.target_table,
+            &Self::target_table_options(request, suggested_buckets),
+        )
+    }
+
+    /// 查询 StarRocks 中已存在的同名 Colocation Group（组名格式为 `<DbId>_<group>`）
+    async fn existing_colocation_group(
+        executor: &mut DdlExecutor<'_>,
+        database: &str,
+        group: &str,
+    ) -> Result<Option<ColocationMember>> {
+        let dbs: Vec<mysql_async::Row> = executor
+            .sr_query("list StarRocks databases", "SHOW PROC '/dbs'")
+            .await?;
+        let db_id = dbs.iter().find_map(|row| {
+            let name: String = row.get("DbName")?;
+            // 旧版本的库名带 default_cluster: 前缀
+            let name = name.rsplit(':').next().unwrap_or_default().to_string();
+            (name == database).then(|| row.get::<String, _>("DbId")).flatten()
+        });
+        let Some(db_id) = db_id else {
+            return Ok(None);
+        };
+
+        let group_name = format!("{}_{}", db_id, group);
+        let groups: Vec<mysql_async::Row> = executor
+            .sr_query("list colocation groups", "SHOW PROC '/colocation_group'")
+            .await?;
+        let Some(row) = groups
+            .iter()
+            .find(|row| row.get::<String, _>("GroupName").as_deref() == Some(group_name.as_str()))
+        else {
+            return Ok(None);
+        };
+
+        let number = |column: &str| {
+            row.get::<String, _>(column)
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .unwrap_or_default()
+        };
+        // DistCols 例如 "int(11)" 或 "int(11), varchar(64)"，取第一个分桶列
+        let dist_cols: String = row.get("DistCols").unwrap_or_default();
+        let distribution_type = match dist_cols.find(')') {
+            Some(end) => dist_cols[..=end].to_string(),
+            None => dist_cols.split(',').next().unwrap_or_default().to_string(),
+        };
+
+        Ok(Some(ColocationMember {
+            group: group.to_string(),
+            table: group_name,
+            buckets: number("BucketsNum"),
+            replication_num: number("ReplicationNum"),
+            distribution_type,
+        }))
+    }
+
+    /// 为已存在的 StarRocks 表补充 MySQL 新增的列，并记录到任务日志，返回是否修改了表
+    async fn reconcile_starrocks_schema(
+        logger: &TaskLogger<'_>,
+        executor: &mut DdlExecutor<'_>,
+        request: &SyncRequest,
+        schema: &TableSchema,
+    ) -> Result<bool> {
+        let sql = format!(
+            "SELECT COLUMN_NAME FROM information_schema.columns WHERE {}",
+            StarRocksDDLGenerator::table_filter(&request.target_database, &request.target_table)
+        );
+        let existing: Vec<String> = executor
+            .sr_query("list StarRocks columns", &sql)
+            .await?;
+        if existing.is_empty() {
+            return Ok(false);
+        }
+
+        let alter_ddl = StarRocksDDLGenerator::generate_add_columns_ddl(
+            &request.target_schema(schema),
+            &existing,
+            &request.target_database,
+            &request.target_table,
+        )?;
+        match alter_ddl {
+            Some(ddl) => {
+                executor.sr("add StarRocks columns", &ddl, true).await?;
+                logger
+                    .step(&format!("Applied StarRocks schema migration: {}", ddl))
+                    .await?;
+                Ok(true)
+            }
+            None => {
+                logger
+                    .info("StarRocks table schema is up to date")
+                    .await?;
+                Ok(false)
+            }
+        }
+    }
+
+    /// 目标表是否已存在
+    async fn target_table_exists(executor: &mut DdlExecutor<'_>, request: &SyncRequest) -> Result<bool> {
+        let check_table_sql = format!(
+            "SELECT 1 FROM information_schema.tables WHERE {} LIMIT 1",
+            StarRocksDDLGenerator::table_filter(&request.target_database, &request.target_table)
+        );
+
+        let table_exists: Option<i32> = executor
+            .sr_query_first("check table existence", &check_table_sql)
+            .await?;
+        Ok(table_exists.is_some())
+    }
+
     /// 第一步：从 MySQL 获取表结构
     async fn fetch_mysql_schema(
-        task_repo: &TaskRepository<'_>,
-        task_id: i64,
+        logger: &TaskLogger<'_>,
         mysql_config: &DatabaseConfig,
         request: &SyncRequest,
     ) -> Result<crate::models::TableSchema> {
-        task_repo
-            .add_log(task_id, "info", "Fetching MySQL table schema...")
-            .await?;
+        logger.info("Fetching MySQL table schema...").await?;
         tracing::info!("Fetching MySQL table schema from db: {}, table {}", &request.mysql_database, &request.mysql_table);
 
         let schema = MetadataService::get_mysql_table_schema(
@@ -177,12 +695,83 @@ impl SyncEngine {
             schema.columns.len(),
             schema.primary_keys.len()
         );
+        logger
+            .debug(&format!(
+                "Fetched schema for {}.{}: {} columns, primary keys: {}",
+                request.mysql_database,
+                request.mysql_table,
+                schema.columns.len(),
+                schema.primary_keys.join(", ")
+            ))
+            .await?;
 
         Ok(schema)
     }
 
+    /// StarRocks 容量预检
+    /// 根据源表数据量估算所需空间，与 BE 可用空间对比；Block 模式下空间不足会中止任务
+    async fn run_capacity_check(
+        logger: &TaskLogger<'_>,
+        mysql_config: &DatabaseConfig,
+        sr_conn: &mut mysql_async::Conn,
+        requests: &[SyncRequest],
+    ) -> Result<()> {
+        let options = &requests[0].options;
+        let mode = options.capacity_check;
+        let min_free_ratio = options
+            .min_free_space_ratio
+            .unwrap_or_else(CapacityService::default_min_free_ratio);
+
+        logger
+            .info("Running StarRocks capacity preflight check...")
+            .await?;
+
+        let tables: Vec<(String, String)> = requests
+            .iter()
+            .map(|r| (r.mysql_database.clone(), r.mysql_table.clone()))
+            .collect();
+
+        let capacity = async {
+            let stats = MetadataService::get_mysql_table_stats(mysql_config, &tables).await?;
+            let capacity = CapacityService::fetch_starrocks_capacity(sr_conn).await?;
+            Ok::<_, crate::utils::error::AppError>((stats, capacity))
+        }
+        .await;
+
+        let (stats, capacity) = match capacity {
+            Ok(v) => v,
+            Err(e) if mode == CapacityCheckMode::Warn => {
+                logger
+                    .warn(&format!("Capacity preflight check skipped: {}", e))
+                    .await?;
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+
+        let replication_num = options.starrocks_table_options().replication_num as i64;
+        let required_bytes = CapacityService::estimate_required_bytes(&stats, replication_num);
+        let result = CapacityService::evaluate(mode, required_bytes, &capacity, min_free_ratio);
+        logger
+            .repo()
+            .update_preflight_result(logger.task_id(), &serde_json::to_string(&result)?)
+            .await?;
+
+        if result.passed {
+            logger.info(&result.message).await?;
+        } else if mode == CapacityCheckMode::Block {
+            logger.error(&result.message).await?;
+            return Err(crate::utils::error::AppError::Validation(result.message));
+        } else {
+            logger.warn(&result.message).await?;
+        }
+
+        Ok(())
+    }
+
     /// 删除 RisingWave 对象
     async fn drop_risingwave_objects(
+        logger: &TaskLogger<'_>,
         pool: &PgPool,
         request: &SyncRequest,
     ) -> Result<()> {
@@ -192,7 +781,8 @@ impl SyncEngine {
             &request.target_table
         );
         tracing::debug!("Drop sink DDL: {}", drop_sink);
-        let _ = sqlx::query(&drop_sink).execute(pool).await; // 忽略错误
+        let error = sqlx::query(&drop_sink).execute(pool).await.err().map(|e| e.to_string()); // 忽略错误
+        AuditService::record_task_ddl(logger, "RisingWave", &drop_sink, error.as_deref()).await;
 
         // 再删除 Table
         let drop_table = RisingWaveDDLGenerator::generate_drop_table_ddl(
@@ -200,7 +790,8 @@ impl SyncEngine {
             &request.target_table
         );
         tracing::debug!("Drop table DDL: {}", drop_table);
-        let _ = sqlx::query(&drop_table).execute(pool).await; // 忽略错误
+        let error = sqlx::query(&drop_table).execute(pool).await.err().map(|e| e.to_string()); // 忽略错误
+        AuditService::record_task_ddl(logger, "RisingWave", &drop_table, error.as_deref()).await;
 
         // 注意：不删除 Source，因为 Source 是数据库级别的，可能被其他表使用
         tracing::info!(
@@ -222,62 +813,82 @@ impl SyncEngine {
         sr_config: DatabaseConfig,
         requests: Vec<SyncRequest>,
     ) -> Result<()> {
-        let task_repo = TaskRepository::new(&app_db);
+        // 批量任务共用第一个请求的日志级别
+        let logger = TaskLogger::new(&app_db, task_id, requests[0].options.log_level);
+        let steps = StepTracker::new(&app_db, task_id);
 
-        task_repo
-            .add_log(
-                task_id,
-                "info",
-                &format!("Starting batch sync for {} tables", requests.len()),
-            )
-            .await?;
-
-        // 连接到 RisingWave
-        task_repo
-            .add_log(task_id, "info", "Connecting to RisingWave...")
-            .await?;
-
-        let rw_opts = ConnectionService::build_postgres_options_from_config(&rw_config);
-        let rw_pool = PgPool::connect_with(rw_opts).await.map_err(|e| {
-            tracing::error!("Failed to connect to RisingWave: {}", e);
-            e
-        })?;
+        let result =
+            Self::run_batch_sync(&logger, &steps, mysql_config, rw_config, sr_config, requests)
+                .await;
+        if let Err(e) = &result
+            && let Err(step_err) = steps.fail(&e.to_string()).await
+        {
+            tracing::warn!("Failed to record failed step for task {}: {}", task_id, step_err);
+        }
+        // 无论成功与否都写入缓冲的日志和超出配额的汇总
+        if let Err(e) = logger.finish().await {
+            tracing::warn!("Failed to flush logs for task {}: {}", task_id, e);
+        }
+        result
+    }
 
-        // 连接到 StarRocks
-        task_repo
-            .add_log(task_id, "info", "Connecting to StarRocks...")
+    async fn run_batch_sync(
+        logger: &TaskLogger<'_>,
+        steps: &StepTracker<'_>,
+        mysql_config: DatabaseConfig,
+        rw_config: DatabaseConfig,
+        sr_config: DatabaseConfig,
+        requests: Vec<SyncRequest>,
+    ) -> Result<()> {
+        logger
+            .step(&format!("Starting batch sync for {} tables", requests.len()))
             .await?;
 
-        let mut sr_opts_builder = mysql_async::OptsBuilder::default()
-            .ip_or_hostname(&sr_config.host)
-            .tcp_port(sr_config.port)
-            .user(Some(&sr_config.username))
-            .pass(Some(&sr_config.password))
-            .prefer_socket(false);
+        // 连接到 RisingWave 和 StarRocks，DDL 执行遇到瞬时错误时按策略重试
+        steps.begin(0).await?;
+        fault::before_step(SyncStep::Connect)?;
+        let mut executor = DdlExecutor::connect(
+            logger,
+            requests[0].options.retry_policy(),
+            &rw_config,
+            &sr_config,
+        )
+        .await?;
+        // 升级前以小写名称创建的对象改为当前名称，重跑时沿用而不是重复创建
+        LegacyNameService::adopt(&executor, logger, &requests).await?;
 
-        if let Some(db) = &sr_config.database_name {
-            sr_opts_builder = sr_opts_builder.db_name(Some(db));
+        // 建表之前先做容量预检
+        if requests[0].options.capacity_check != CapacityCheckMode::Disabled {
+            let sr_conn = executor.sr_conn().await?;
+            Self::run_capacity_check(logger, &mysql_config, sr_conn, &requests).await?;
         }
+        // 集群负载高于阈值时推迟回填
+        BackfillGate::wait(logger, executor.sr_conn().await?).await?;
+        steps.finish(StepStatus::Completed).await?;
 
-        let sr_opts = mysql_async::Opts::from(sr_opts_builder);
-        let mut sr_conn = mysql_async::Conn::new(sr_opts).await.map_err(|e| {
-            tracing::error!("Failed to connect to StarRocks: {}", e);
-            crate::utils::error::AppError::Connection(format!("StarRocks connection failed: {}", e))
-        })?;
-
-        // 收集所有需要的 schema、source 和 database
-        let mut schemas_created = std::collections::HashSet::new();
-        let mut secrets_created = std::collections::HashSet::new();
-        let mut sources_created = std::collections::HashSet::new();
-        let mut databases_created = std::collections::HashSet::new();
+        let suggested_buckets = Self::suggest_buckets(&mysql_config, &requests).await;
+        let mut batch = BatchContext {
+            logger,
+            executor,
+            mysql_config,
+            sr_config,
+            schemas_created: HashSet::new(),
+            secrets_created: HashSet::new(),
+            sources_created: HashSet::new(),
+            databases_created: HashSet::new(),
+        };
 
-        // 顺序处理每个表
+        // 顺序处理每个表，每张表依次执行 SyncStep::TABLE_STEPS
         let total_tables = requests.len();
+        let mut outcomes = Vec::with_capacity(total_tables);
         for (index, request) in requests.iter().enumerate() {
-            task_repo
-                .add_log(
-                    task_id,
-                    "info",
+            let table_started = Instant::now();
+            let table_objects = vec![
+                format!("{}.{}", request.mysql_database, request.mysql_table),
+                format!("{}.{}", request.target_database, request.target_table),
+            ];
+            logger
+                .step_with(
                     &format!(
                         "Processing table {}/{}: {}.{}",
                         index + 1,
@@ -285,230 +896,506 @@ impl SyncEngine {
                         request.mysql_database,
                         request.mysql_table
                     ),
+                    TaskLogPayload {
+                        step: Some("process table".to_string()),
+                        objects: table_objects.clone(),
+                        ..Default::default()
+                    },
                 )
                 .await?;
 
-            // 第一步：获取表结构
-            let schema = Self::fetch_mysql_schema(
-                &task_repo,
-                task_id,
-                &mysql_config,
-                &request,
-            ).await?;
-
-            // 第二步：设置 RisingWave（只创建一次共享资源）
-            // 创建 schema（如果还没创建）
-            if !schemas_created.contains(&request.target_database) {
-                task_repo
-                    .add_log(task_id, "info", &format!("Creating schema {} in RisingWave...", request.target_database))
-                    .await?;
-
-                let schema_ddl = RisingWaveDDLGenerator::generate_create_schema_ddl(&request.target_database);
-                tracing::info!("schema ddl : {}", &schema_ddl);
-                sqlx::query(&schema_ddl).execute(&rw_pool).await.map_err(|e| {
-                    tracing::error!("Failed to create schema: {}", e);
-                    e
-                })?;
-                schemas_created.insert(request.target_database.clone());
+            let mut table = TableContext {
+                request,
+                schema: None,
+                suggested_buckets: suggested_buckets[index],
+                source_name: RisingWaveDDLGenerator::get_source_name(
+                    &request.mysql_database,
+                    &request.target_database,
+                ),
+                action: TableSyncAction::Skipped,
+            };
+            for step in SyncStep::TABLE_STEPS {
+                steps.begin(NewTaskStep::index_of(index, step)).await?;
+                fault::before_step(step)?;
+                let status = Self::run_table_step(step, &mut batch, &mut table).await?;
+                steps.finish(status).await?;
             }
 
-            // 创建 MySQL SECRET（如果还没创建）
-            if !secrets_created.contains(&request.target_database) {
-                task_repo
-                    .add_log(task_id, "info", "Creating secret for MySQL password...")
-                    .await?;
+            logger
+                .step(&format!(
+                    "Successfully synced {}.{} to {}.{} ({}/{}, {})",
+                    request.mysql_database,
+                    request.mysql_table,
+                    request.target_database,
+                    request.target_table,
+                    index + 1,
+                    total_tables,
+                    table.action.as_str()
+                ))
+                .await?;
+            outcomes.push(TableSyncOutcome {
+                source_table: format!("{}.{}", request.mysql_database, request.mysql_table),
+                target_table: format!("{}.{}", request.target_database, request.target_table),
+                action: table.action,
+            });
+            logger
+                .repo()
+                .update_table_outcomes(logger.task_id(), &serde_json::to_string(&outcomes)?)
+                .await?;
+            let table_ms = table_started.elapsed().as_millis() as u64;
+            logger
+                .debug_with(
+                    &format!(
+                        "Table {}.{} took {} ms",
+                        request.mysql_database, request.mysql_table, table_ms
+                    ),
+                    Some(TaskLogPayload {
+                        step: Some("process table".to_string()),
+                        duration_ms: Some(table_ms),
+                        objects: table_objects,
+                        ..Default::default()
+                    }),
+                )
+                .await?;
+            // 每个表结束时写入缓冲的日志
+            logger.flush().await?;
+        }
 
-                let secret_ddl = RisingWaveDDLGenerator::generate_secret_ddl(&mysql_config, &request.target_database)?;
-                sqlx::query(&secret_ddl).execute(&rw_pool).await.map_err(|e| {
-                    tracing::error!("Failed to create secret: {}", e);
-                    e
-                })?;
-                secrets_created.insert(request.target_database.clone());
-            }
+        // 完成日志
+        let count = |action| outcomes.iter().filter(|o| o.action == action).count();
+        logger
+            .step(&format!(
+                "Successfully completed batch sync for {} tables (created {}, skipped {}, updated {})",
+                total_tables,
+                count(TableSyncAction::Created),
+                count(TableSyncAction::Skipped),
+                count(TableSyncAction::Updated)
+            ))
+            .await?;
 
-            // 创建 CDC Source（如果还没创建）
-            let source_key = format!("{}:{}", request.target_database, request.mysql_database);
-            if !sources_created.contains(&source_key) {
-                task_repo
-                    .add_log(
-                        task_id,
-                        "info",
-                        &format!("Creating RisingWave CDC source for database {}...", request.mysql_database),
-                    )
-                    .await?;
+        // 关闭连接
+        batch.executor.close().await;
 
-                let source_ddl = RisingWaveDDLGenerator::generate_source_ddl(
-                    &mysql_config,
-                    &request.mysql_database,
-                    &request.target_database
-                )?;
-                sqlx::query(&source_ddl).execute(&rw_pool).await.map_err(|e| {
-                    tracing::error!("Failed to create RisingWave source: {}", e);
-                    e
-                })?;
-                sources_created.insert(source_key);
-            }
+        Ok(())
+    }
 
-            // 如果需要，删除现有对象
-            if request.options.recreate_rw_source {
-                task_repo
-                    .add_log(task_id, "info", "Dropping existing RisingWave objects...")
-                    .await?;
+    /// 执行单张表的一个步骤，返回步骤结果（无需执行时为 Skipped）
+    async fn run_table_step(
+        step: SyncStep,
+        batch: &mut BatchContext<'_>,
+        table: &mut TableContext<'_>,
+    ) -> Result<StepStatus> {
+        let logger = batch.logger;
+        let request = table.request;
 
-                Self::drop_risingwave_objects(&rw_pool, request).await?;
+        match step {
+            // 任务级步骤，在 run_batch_sync 中执行
+            SyncStep::Connect => Ok(StepStatus::Skipped),
+            SyncStep::FetchSchema => {
+                let schema = Self::fetch_mysql_schema(logger, &batch.mysql_config, request).await?;
+                // 列名映射有误时在创建任何对象之前失败
+                RisingWaveDDLGenerator::validate_column_mapping(request, &schema)?;
+                table.schema = Some(schema);
+                Ok(StepStatus::Completed)
             }
+            SyncStep::SetupSource => {
+                let mut executed = false;
 
-            // 创建 Table
-            task_repo
-                .add_log(
-                    task_id,
-                    "info",
-                    &format!("Creating RisingWave table {}.{}...", request.target_database, request.target_table),
-                )
-                .await?;
+                // 创建 schema（如果还没创建）
+                if !batch.schemas_created.contains(&request.target_database) {
+                    logger
+                        .info(&format!("Creating schema {} in RisingWave...", request.target_database))
+                        .await?;
 
-            let table_ddl = RisingWaveDDLGenerator::generate_table_ddl(
-                &request.mysql_database,
-                &request.mysql_table,
-                &request.target_database,
-                &request.target_table
-            )?;
-            tracing::info!("table ddl: {}", &table_ddl);
-            sqlx::query(&table_ddl).execute(&rw_pool).await.map_err(|e| {
-                tracing::error!("Failed to create RisingWave table: {}", e);
-                e
-            })?;
-
-            // 第三步：设置 StarRocks
-            // 创建数据库（如果还没创建）
-            if !databases_created.contains(&request.target_database) {
-                let create_db_ddl = StarRocksDDLGenerator::generate_create_database_ddl(&request.target_database);
-                sr_conn.query_drop(&create_db_ddl).await.map_err(|e| {
-                    tracing::error!("Failed to create StarRocks database: {}", e);
-                    crate::utils::error::AppError::Unknown(format!("Failed to create database: {}", e))
-                })?;
-                databases_created.insert(request.target_database.clone());
-            }
+                    let schema_ddl = RisingWaveDDLGenerator::generate_create_schema_ddl(&request.target_database);
+                    tracing::info!("schema ddl : {}", &schema_ddl);
+                    batch.executor.rw("create schema", &schema_ddl, true).await?;
+                    batch.schemas_created.insert(request.target_database.clone());
+                    executed = true;
+                }
 
-            // 处理表（删除或清空）
-            if request.options.recreate_sr_table {
-                task_repo
-                    .add_log(task_id, "info", "Dropping existing StarRocks table...")
-                    .await?;
+                // 复用其他目标 schema 中已登记的共享 Source，不再创建 Secret 和 Source
+                if let Some(shared) = Self::shared_source(logger.app_db(), request).await? {
+                    logger
+                        .info(&format!(
+                            "Reusing shared RisingWave source {} ({} references)",
+                            shared.object_name, shared.ref_count
+                        ))
+                        .await?;
+                    table.source_name = shared.object_name;
+                    return Ok(if executed { StepStatus::Completed } else { StepStatus::Skipped });
+                }
 
-                let drop_table_ddl = StarRocksDDLGenerator::generate_drop_table_ddl(
-                    &request.target_database,
-                    &request.target_table,
-                );
-                sr_conn.query_drop(&drop_table_ddl).await.map_err(|e| {
-                    tracing::error!("Failed to drop StarRocks table: {}", e);
-                    crate::utils::error::AppError::Unknown(format!("Failed to drop table: {}", e))
-                })?;
-            } else if request.options.truncate_sr_table {
-                let check_table_sql = format!(
-                    "SELECT 1 FROM information_schema.tables WHERE table_schema = '{}' AND table_name = '{}' LIMIT 1",
-                    request.target_database,
-                    request.target_table
-                );
+                // 创建 MySQL SECRET（如果还没创建）
+                if !batch.secrets_created.contains(&request.target_database) {
+                    logger.info("Creating secret for MySQL password...").await?;
+
+                    let secret_ddl = RisingWaveDDLGenerator::generate_secret_ddl(&batch.mysql_config, &request.target_database)?;
+                    // SECRET 语句包含密码，不记录到日志
+                    batch.executor.rw("create secret", &secret_ddl, false).await?;
+                    batch.secrets_created.insert(request.target_database.clone());
+                    executed = true;
+                }
 
-                let table_exists: Option<i32> = sr_conn.query_first(&check_table_sql).await.map_err(|e| {
-                    tracing::error!("Failed to check if table exists: {}", e);
-                    crate::utils::error::AppError::Unknown(format!("Failed to check table existence: {}", e))
-                })?;
+                // 创建 CDC Source（如果还没创建）
+                let source_key = format!("{}:{}", request.target_database, request.mysql_database);
+                if !batch.sources_created.contains(&source_key) {
+                    logger
+                        .info(&format!("Creating RisingWave CDC source for database {}...", request.mysql_database))
+                        .await?;
 
-                if table_exists.is_some() {
-                    task_repo
-                        .add_log(task_id, "info", "Truncating StarRocks table...")
+                    let server_id = Self::allocate_server_id(logger.app_db(), &batch.mysql_config, request).await?;
+                    logger
+                        .debug(&format!("Using server.id {} for source", server_id))
                         .await?;
+                    let source_ddl = RisingWaveDDLGenerator::generate_source_ddl(
+                        &batch.mysql_config,
+                        &request.mysql_database,
+                        &request.target_database,
+                        server_id,
+                    )?;
+                    batch.executor.rw("create RisingWave source", &source_ddl, true).await?;
+                    batch.sources_created.insert(source_key);
+                    executed = true;
+                }
+
+                // 先登记对象，建表失败时没有引用的对象可以被清理
+                if request.options.share_source {
+                    Self::register_shared_source(logger.app_db(), request, &table.source_name).await?;
+                }
+
+                Ok(if executed { StepStatus::Completed } else { StepStatus::Skipped })
+            }
+            SyncStep::CreateRwTable => {
+                let state = ExistingObjectService::rw_table(batch.executor.rw_pool(), request, &table.source_name).await?;
+
+                // 如果需要，删除现有对象
+                let create = if request.options.recreate_rw_source {
+                    logger.info("Dropping existing RisingWave objects...").await?;
+
+                    Self::drop_risingwave_objects(logger, batch.executor.rw_pool(), request).await?;
+                    if state != ObjectState::Missing {
+                        table.record(TableSyncAction::Updated);
+                    }
+                    true
+                } else {
+                    match state {
+                        ObjectState::Missing => true,
+                        ObjectState::Matching => {
+                            logger
+                                .info(&format!(
+                                    "RisingWave table {}.{} already exists with the same definition, skipping",
+                                    request.target_database, request.target_table
+                                ))
+                                .await?;
+                            false
+                        }
+                        ObjectState::Changed => {
+                            logger
+                                .warn(&format!(
+                                    "RisingWave table {}.{} already exists with a different upstream table, keeping it (set recreate_rw_source to rebuild)",
+                                    request.target_database, request.target_table
+                                ))
+                                .await?;
+                            false
+                        }
+                    }
+                };
+
+                if create {
+                    // 创建 Table
+                    logger
+                        .info(&format!("Creating RisingWave table {}.{}...", request.target_database, request.target_table))
+                        .await?;
+
+                    let table_ddl = RisingWaveDDLGenerator::generate_table_ddl_from_source(
+                        &table.source_name,
+                        &request.mysql_database,
+                        &request.mysql_table,
+                        &request.target_database,
+                        &request.target_table
+                    )?;
+                    tracing::info!("table ddl: {}", &table_ddl);
+                    batch.executor.rw("create RisingWave table", &table_ddl, true).await?;
+                    table.record(TableSyncAction::Created);
+                }
+
+                // Table 创建成功后记录对共享 Source 和 Secret 的引用
+                if request.options.share_source {
+                    let repo = ManagedObjectRepository::new(logger.app_db());
+                    for id in Self::register_shared_source(logger.app_db(), request, &table.source_name).await? {
+                        repo.add_ref(id, &request.target_database, &request.target_table).await?;
+                    }
+                }
+                Ok(if create { StepStatus::Completed } else { StepStatus::Skipped })
+            }
+            SyncStep::PrepareTarget => {
+                // 创建数据库（如果还没创建）
+                if !batch.databases_created.contains(&request.target_database) {
+                    let create_db_ddl = StarRocksDDLGenerator::generate_create_database_ddl(&request.target_database);
+                    batch.executor.sr("create database", &create_db_ddl, true).await?;
+                    batch.databases_created.insert(request.target_database.clone());
+                }
+
+                // 处理表（删除或清空）
+                if request.options.recreate_sr_table {
+                    if Self::target_table_exists(&mut batch.executor, request).await? {
+                        table.record(TableSyncAction::Updated);
+                    }
+                    logger.info("Dropping existing StarRocks table...").await?;
+
+                    let drop_table_ddl = StarRocksDDLGenerator::generate_drop_table_ddl(
+                        &request.target_database,
+                        &request.target_table,
+                    );
+                    batch.executor.sr("drop table", &drop_table_ddl, true).await?;
+                } else if request.options.truncate_sr_table
+                    && Self::target_table_exists(&mut batch.executor, request).await?
+                {
+                    logger.info("Truncating StarRocks table...").await?;
 
                     let truncate_ddl = StarRocksDDLGenerator::generate_truncate_table_ddl(
                         &request.target_database,
                         &request.target_table,
                     );
-                    sr_conn.query_drop(&truncate_ddl).await.map_err(|e| {
-                        tracing::error!("Failed to truncate StarRocks table: {}", e);
-                        crate::utils::error::AppError::Unknown(format!("Failed to truncate table: {}", e))
-                    })?;
+                    batch.executor.sr("truncate table", &truncate_ddl, true).await?;
+                    table.record(TableSyncAction::Updated);
                 }
+                Ok(StepStatus::Completed)
             }
+            SyncStep::CreateTargetTable => {
+                let schema = table.schema()?;
 
-            // 创建表
-            task_repo
-                .add_log(task_id, "info", "Creating StarRocks table...")
-                .await?;
+                let state = ExistingObjectService::target_table(&mut batch.executor, request, schema).await?;
+                if state == ObjectState::Matching {
+                    logger
+                        .info(&format!(
+                            "StarRocks table {}.{} already exists with the same columns, skipping",
+                            request.target_database, request.target_table
+                        ))
+                        .await?;
+                    return Ok(StepStatus::Skipped);
+                }
+                let create = state == ObjectState::Missing;
+                if create {
+                    Self::create_target_table(batch, table).await?;
+                }
 
-            let sr_table_ddl = StarRocksDDLGenerator::generate_table_ddl(
-                &schema,
-                &request.target_database,
-                &request.target_table,
-            )?;
-            tracing::info!("starrocks table ddl: {}", &sr_table_ddl);
-            sr_conn.query_drop(&sr_table_ddl).await.map_err(|e| {
-                tracing::error!("Failed to create StarRocks table: {}", e);
-                crate::utils::error::AppError::Unknown(format!("Failed to create table: {}", e))
-            })?;
-
-            // 第四步：创建 Sink 到 StarRocks
-            // 创建 StarRocks SECRET（如果还没创建）
-            let sr_secret_key = format!("sr_secret:{}", request.target_database);
-            if !secrets_created.contains(&sr_secret_key) {
-                task_repo
-                    .add_log(task_id, "info", "Creating secret for StarRocks password...")
+                let mut updated = false;
+
+                if request.options.reconcile_sr_schema && !request.options.recreate_sr_table {
+                    let workspace = request.options.workspace.as_deref().unwrap_or(DEFAULT_WORKSPACE);
+                    let enabled = FeatureFlagService::is_enabled(
+                        logger.app_db(),
+                        workspace,
+                        Feature::AutoAlterPropagation,
+                    )
                     .await?;
+                    if enabled {
+                        updated = Self::reconcile_starrocks_schema(logger, &mut batch.executor, request, schema).await?;
+                    } else {
+                        logger
+                            .warn(&format!(
+                                "Skipping schema reconcile: feature '{}' is disabled for workspace {}",
+                                Feature::AutoAlterPropagation.as_str(),
+                                workspace
+                            ))
+                            .await?;
+                    }
+                } else if !create {
+                    logger
+                        .warn(&format!(
+                            "StarRocks table {}.{} already exists with different columns, keeping it (set reconcile_sr_schema or recreate_sr_table to update)",
+                            request.target_database, request.target_table
+                        ))
+                        .await?;
+                }
 
-                let sr_secret_ddl = RisingWaveDDLGenerator::generate_starrocks_secret_ddl(&sr_config, &request.target_database)?;
-                sqlx::query(&sr_secret_ddl).execute(&rw_pool).await.map_err(|e| {
-                    tracing::error!("Failed to create StarRocks secret: {}", e);
-                    e
-                })?;
-                secrets_created.insert(sr_secret_key);
+                if updated {
+                    table.record(TableSyncAction::Updated);
+                } else if create {
+                    table.record(TableSyncAction::Created);
+                }
+                Ok(if create || updated { StepStatus::Completed } else { StepStatus::Skipped })
             }
+            SyncStep::CreateSink => {
+                let schema = table.schema()?;
 
-            task_repo
-                .add_log(task_id, "info", "Creating RisingWave sink to StarRocks...")
-                .await?;
+                // 创建 StarRocks SECRET（如果还没创建）
+                let mut executed = false;
+                let sr_secret_key = format!("sr_secret:{}", request.target_database);
+                if !batch.secrets_created.contains(&sr_secret_key) {
+                    logger.info("Creating secret for StarRocks password...").await?;
 
-            let sink_ddl = RisingWaveDDLGenerator::generate_sink_ddl(
-                &sr_config,
-                &request,
-                &schema
-            )?;
-            tracing::info!("sink ddl: {}", &sink_ddl);
-            sqlx::query(&sink_ddl).execute(&rw_pool).await.map_err(|e| {
-                tracing::error!("Failed to create RisingWave sink: {}", e);
-                e
-            })?;
+                    let sr_secret_ddl = RisingWaveDDLGenerator::generate_starrocks_secret_ddl(&batch.sr_config, &request.target_database)?;
+                    batch.executor.rw("create StarRocks secret", &sr_secret_ddl, false).await?;
+                    batch.secrets_created.insert(sr_secret_key);
+                    executed = true;
+                }
 
-            task_repo
-                .add_log(
-                    task_id,
-                    "info",
-                    &format!(
-                        "Successfully synced {}.{} to {}.{} ({}/{})",
-                        request.mysql_database,
-                        request.mysql_table,
-                        request.target_database,
-                        request.target_table,
-                        index + 1,
-                        total_tables
-                    ),
-                )
-                .await?;
+                let sink_ddl = RisingWaveDDLGenerator::generate_sink_ddl(
+                    &batch.sr_config,
+                    request,
+                    schema
+                )?;
+                match ExistingObjectService::sink(batch.executor.rw_pool(), request, &sink_ddl).await? {
+                    ObjectState::Matching => {
+                        logger
+                            .info(&format!(
+                                "RisingWave sink {}.{}_to_sr_sink already exists with the same definition, skipping",
+                                request.target_database, request.target_table
+                            ))
+                            .await?;
+                        return Ok(if executed { StepStatus::Completed } else { StepStatus::Skipped });
+                    }
+                    ObjectState::Changed => {
+                        logger.info("Recreating RisingWave sink with the new definition...").await?;
+
+                        let drop_sink = RisingWaveDDLGenerator::generate_drop_sink_ddl(
+                            &request.target_database,
+                            &request.target_table
+                        );
+                        batch.executor.rw("drop RisingWave sink", &drop_sink, true).await?;
+                        table.record(TableSyncAction::Updated);
+                    }
+                    ObjectState::Missing => table.record(TableSyncAction::Created),
+                }
+
+                logger.info("Creating RisingWave sink to StarRocks...").await?;
+
+                tracing::info!("sink ddl: {}", &sink_ddl);
+                batch.executor.rw("create RisingWave sink", &sink_ddl, true).await?;
+                Ok(StepStatus::Completed)
+            }
         }
+    }
 
-        // 完成日志
-        task_repo
-            .add_log(
-                task_id,
-                "info",
-                &format!("Successfully completed batch sync for {} tables", total_tables),
-            )
-            .await?;
+    /// 创建目标表，按需加入 colocation 组
+    async fn create_target_table(batch: &mut BatchContext<'_>, table: &TableContext<'_>) -> Result<()> {
+        let logger = batch.logger;
+        let request = table.request;
+        let schema = table.schema()?;
 
-        // 关闭连接
-        rw_pool.close().await;
-        let _ = sr_conn.disconnect().await;
+        // 创建表
+        logger.info("Creating StarRocks table...").await?;
+
+        if batch.sr_config.db_type != DbType::Doris
+            && let Some(member) =
+                Self::colocation_member(request, schema, table.suggested_buckets)?
+        {
+            // 同批次先创建的表已加入组，与 StarRocks 中的组比较即可覆盖组内所有成员
+            if let Some(existing) = Self::existing_colocation_group(
+                &mut batch.executor,
+                &request.target_database,
+                &member.group,
+            )
+            .await?
+            {
+                StarRocksDDLGenerator::validate_colocation(&[existing, member.clone()])?;
+            }
+            logger
+                .info(&format!(
+                    "Joining colocation group {} ({} buckets)",
+                    member.group, member.buckets
+                ))
+                .await?;
+        }
 
+        if let Some(buckets) = table.suggested_buckets {
+            logger
+                .info(&format!(
+                    "Using suggested bucket count {} based on source table size",
+                    buckets
+                ))
+                .await?;
+        }
+        let sr_table_ddl = Self::generate_target_table_ddl(
+            &batch.sr_config,
+            request,
+            schema,
+            table.suggested_buckets,
+        )?;
+        tracing::info!("starrocks table ddl: {}", &sr_table_ddl);
+        batch.executor.sr("create table", &sr_table_ddl, true).await?;
         Ok(())
     }
 }
+
+/// 批量同步中各表共享的连接、配置和已创建的共享对象
+struct BatchContext<'a> {
+    logger: &'a TaskLogger<'a>,
+    executor: DdlExecutor<'a>,
+    mysql_config: DatabaseConfig,
+    sr_config: DatabaseConfig,
+    schemas_created: HashSet<String>,
+    secrets_created: HashSet<String>,
+    sources_created: HashSet<String>,
+    databases_created: HashSet<String>,
+}
+
+/// 单张表在步骤之间传递的状态
+struct TableContext<'a> {
+    request: &'a SyncRequest,
+    /// FetchSchema 步骤获取的表结构
+    schema: Option<TableSchema>,
+    suggested_buckets: Option<u32>,
+    /// Table 使用的 CDC Source，共享 Source 时位于其他 schema
+    source_name: String,
+    /// 已执行步骤汇总的结果，对象都已存在时为 Skipped
+    action: TableSyncAction,
+}
+
+impl TableContext<'_> {
+    fn record(&mut self, action: TableSyncAction) {
+        self.action = self.action.max(action);
+    }
+
+    fn schema(&self) -> Result<&TableSchema> {
+        self.schema.as_ref().ok_or_else(|| {
+            crate::utils::error::AppError::Unknown(format!(
+                "Schema of {}.{} has not been fetched",
+                self.request.mysql_database, self.request.mysql_table
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_sync_slots_cap_concurrent_syncs() {
+        let permits = Arc::new(Semaphore::new(2));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let waited = Arc::new(AtomicUsize::new(0));
+
+        // 一次提交 10 个任务，同时执行的不超过许可数，其余排队
+        let handles: Vec<_> = (0..10)
+            .map(|_| {
+                let (permits, running, peak, waited) =
+                    (permits.clone(), running.clone(), peak.clone(), waited.clone());
+                tokio::spawn(async move {
+                    let _permit = SyncEngine::acquire_slot(&permits, || async {
+                        waited.fetch_add(1, Ordering::SeqCst);
+                    })
+                    .await;
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(waited.load(Ordering::SeqCst), 8);
+        assert_eq!(permits.available_permits(), 2);
+    }
+}
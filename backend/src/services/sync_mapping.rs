@@ -0,0 +1,296 @@
+use crate::db::{ConfigRepository, ScheduleRepository, SyncMappingRepository};
+use crate::generators::RisingWaveDDLGenerator;
+use crate::models::{PipelinePauseResult, SyncMappingRequest, SyncRequest};
+use crate::services::{AuditService, ConnectionPoolManager, SchedulerService};
+use crate::utils::error::{AppError, Result};
+use crate::utils::naming::TableNameTemplate;
+use chrono::Utc;
+use sqlx::MySqlPool;
+use std::collections::HashSet;
+
+/// 同步映射：按保存的定义生成同步请求，整体暂停或恢复管道
+pub struct SyncMappingService;
+
+impl SyncMappingService {
+    /// 校验创建或更新同步映射的请求
+    pub fn validate(request: &SyncMappingRequest) -> Result<()> {
+        if request.name.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Sync mapping name is required".to_string(),
+            ));
+        }
+        if request.tables.is_empty() {
+            return Err(AppError::Validation("No tables to sync".to_string()));
+        }
+        let mut targets = HashSet::new();
+        for sync in Self::requests(request)? {
+            if !targets.insert((sync.target_database.clone(), sync.target_table.clone())) {
+                return Err(AppError::Validation(format!(
+                    "Target table {}.{} is used by more than one table",
+                    sync.target_database, sync.target_table
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 展开为同步请求
+    /// 目标表名优先使用表中指定的名称，其次按模板生成，都没有时与 MySQL 表名相同
+    pub fn requests(mapping: &SyncMappingRequest) -> Result<Vec<SyncRequest>> {
+        let template = mapping
+            .target_table_template
+            .as_deref()
+            .filter(|t| !t.trim().is_empty())
+            .map(TableNameTemplate::parse)
+            .transpose()?;
+        let default_database = mapping
+            .target_database
+            .as_deref()
+            .filter(|db| !db.trim().is_empty());
+
+        mapping
+            .tables
+            .iter()
+            .map(|table| {
+                let target_database = table
+                    .target_database
+                    .as_deref()
+                    .filter(|db| !db.trim().is_empty())
+                    .or(default_database)
+                    .unwrap_or(&table.mysql_database)
+                    .to_string();
+                let target_table = match (
+                    table
+                        .target_table
+                        .as_deref()
+                        .filter(|t| !t.trim().is_empty()),
+                    &template,
+                ) {
+                    (Some(target_table), _) => target_table.to_string(),
+                    (None, Some(template)) => template.render(
+                        &table.mysql_database,
+                        &table.mysql_table,
+                        &target_database,
+                    )?,
+                    (None, None) => table.mysql_table.clone(),
+                };
+                Ok(SyncRequest {
+                    mysql_config_id: mapping.mysql_config_id,
+                    rw_config_id: mapping.rw_config_id,
+                    sr_config_id: mapping.sr_config_id,
+                    mysql_database: table.mysql_database.clone(),
+                    mysql_table: table.mysql_table.clone(),
+                    target_database,
+                    target_table,
+                    options: mapping.options.clone(),
+                    row_filter: table.row_filter.clone(),
+                    partition: table.partition.clone(),
+                    column_mapping: table.column_mapping.clone(),
+                    nested_columns: table.nested_columns.clone(),
+                    spatial_columns: table.spatial_columns.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// 暂停管道：sink 限速为 0，停用写入相同目标表的定时同步，并标记映射已暂停
+    /// 已暂停时重新执行，便于重试上次失败的 sink
+    pub async fn pause(
+        app_db: &MySqlPool,
+        id: i64,
+        actor: Option<&str>,
+    ) -> Result<PipelinePauseResult> {
+        Self::set_paused(app_db, id, true, actor).await
+    }
+
+    /// 恢复管道：取消 sink 限速，重新启用暂停时停用的定时同步
+    pub async fn resume(
+        app_db: &MySqlPool,
+        id: i64,
+        actor: Option<&str>,
+    ) -> Result<PipelinePauseResult> {
+        Self::set_paused(app_db, id, false, actor).await
+    }
+
+    async fn set_paused(
+        app_db: &MySqlPool,
+        id: i64,
+        paused: bool,
+        actor: Option<&str>,
+    ) -> Result<PipelinePauseResult> {
+        let repo = SyncMappingRepository::new(app_db);
+        let mapping = repo.find_by_id(id).await?;
+        let requests = Self::requests(&mapping.definition())?;
+        let rw_config = ConfigRepository::new(app_db)
+            .find_by_id(mapping.rw_config_id)
+            .await?;
+        let rw_pool = ConnectionPoolManager::postgres(&rw_config).await?;
+
+        let mut result = PipelinePauseResult {
+            mapping_id: id,
+            paused,
+            sinks: Vec::new(),
+            failed: Vec::new(),
+            schedule_ids: Vec::new(),
+        };
+        for request in &requests {
+            let sink =
+                RisingWaveDDLGenerator::get_sink_name(&request.target_database, &request.target_table);
+            let sql = RisingWaveDDLGenerator::generate_sink_rate_limit_ddl(
+                &request.target_database,
+                &request.target_table,
+                paused,
+            );
+            let error = sqlx::query(&sql)
+                .execute(&rw_pool)
+                .await
+                .err()
+                .map(|e| e.to_string());
+            AuditService::record_ddl(app_db, actor, None, "RisingWave", &sql, error.as_deref())
+                .await;
+            match error {
+                Some(e) => {
+                    tracing::warn!(
+                        "Failed to {} sink {}: {}",
+                        if paused { "pause" } else { "resume" },
+                        sink,
+                        e
+                    );
+                    result.failed.push(format!("{}: {}", sink, e));
+                }
+                None => result.sinks.push(sink),
+            }
+        }
+
+        let schedules = ScheduleRepository::new(app_db);
+        let mut paused_schedule_ids = Vec::new();
+        if paused {
+            paused_schedule_ids = mapping.paused_schedule_ids.clone();
+            for schedule in schedules.find_all().await? {
+                if schedule.enabled && Self::shares_targets(&schedule.requests, &requests) {
+                    schedules.set_enabled(schedule.id, false, None).await?;
+                    result.schedule_ids.push(schedule.id);
+                    paused_schedule_ids.push(schedule.id);
+                }
+            }
+        } else {
+            for &schedule_id in &mapping.paused_schedule_ids {
+                // 暂停期间删除的定时同步不再恢复
+                let Ok(schedule) = schedules.find_by_id(schedule_id).await else {
+                    continue;
+                };
+                let next_run_at = SchedulerService::next_run_at(
+                    &schedule.cron,
+                    &schedule.timezone,
+                    true,
+                    Utc::now(),
+                )?;
+                schedules
+                    .set_enabled(schedule_id, true, next_run_at)
+                    .await?;
+                result.schedule_ids.push(schedule_id);
+            }
+        }
+        repo.set_paused(id, paused, &paused_schedule_ids).await?;
+
+        Ok(result)
+    }
+
+    /// 定时同步是否写入管道中的任一目标表
+    fn shares_targets(schedule: &[SyncRequest], pipeline: &[SyncRequest]) -> bool {
+        schedule.iter().any(|s| {
+            pipeline.iter().any(|p| {
+                s.rw_config_id == p.rw_config_id
+                    && s.target_database == p.target_database
+                    && s.target_table == p.target_table
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MappingTable;
+
+    fn table(db: &str, name: &str, target_table: Option<&str>) -> MappingTable {
+        MappingTable {
+            mysql_database: db.to_string(),
+            mysql_table: name.to_string(),
+            target_database: None,
+            target_table: target_table.map(str::to_string),
+            row_filter: None,
+            partition: None,
+            column_mapping: Default::default(),
+            nested_columns: Default::default(),
+            spatial_columns: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_mapping_requests() {
+        let mut mapping = SyncMappingRequest {
+            name: "nightly".to_string(),
+            description: None,
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            target_database: Some("ods".to_string()),
+            tables: vec![
+                table("shop", "orders", None),
+                table("shop", "users", Some("dim_users")),
+            ],
+            options: Default::default(),
+            target_table_template: Some("{db}_{table}".to_string()),
+            version: None,
+        };
+        let requests = SyncMappingService::requests(&mapping).unwrap();
+        assert_eq!(requests[0].target_database, "ods");
+        assert_eq!(requests[0].target_table, "shop_orders");
+        assert_eq!(requests[1].target_table, "dim_users");
+        assert_eq!(requests[1].sr_config_id, 3);
+        assert!(SyncMappingService::validate(&mapping).is_ok());
+
+        // 没有模板和目标库时与源表相同
+        mapping.target_database = None;
+        mapping.target_table_template = None;
+        let requests = SyncMappingService::requests(&mapping).unwrap();
+        assert_eq!(requests[0].target_database, "shop");
+        assert_eq!(requests[0].target_table, "orders");
+
+        // 两张表写入同一个目标表
+        mapping.tables[1].target_table = Some("orders".to_string());
+        assert!(SyncMappingService::validate(&mapping).is_err());
+    }
+
+    #[test]
+    fn test_schedule_shares_targets() {
+        let mut mapping = SyncMappingRequest {
+            name: "nightly".to_string(),
+            description: None,
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id: 3,
+            target_database: Some("ods".to_string()),
+            tables: vec![table("shop", "orders", None), table("shop", "users", None)],
+            options: Default::default(),
+            target_table_template: None,
+            version: None,
+        };
+        let pipeline = SyncMappingService::requests(&mapping).unwrap();
+
+        mapping.tables = vec![table("shop", "users", None), table("shop", "items", None)];
+        let schedule = SyncMappingService::requests(&mapping).unwrap();
+        assert!(SyncMappingService::shares_targets(&schedule, &pipeline));
+
+        // 目标表相同但写入另一个 RisingWave
+        mapping.rw_config_id = 4;
+        let schedule = SyncMappingService::requests(&mapping).unwrap();
+        assert!(!SyncMappingService::shares_targets(&schedule, &pipeline));
+
+        mapping.rw_config_id = 2;
+        mapping.tables = vec![table("shop", "items", None)];
+        let schedule = SyncMappingService::requests(&mapping).unwrap();
+        assert!(!SyncMappingService::shares_targets(&schedule, &pipeline));
+    }
+}
@@ -0,0 +1,293 @@
+use crate::db::{ConfigRepository, SyncMetricRepository, TaskRepository};
+use crate::generators::{RisingWaveDDLGenerator, StarRocksDDLGenerator};
+use crate::models::{SyncMetric, SyncRequest, SyncTask, TaskFilter, TaskStatus};
+use crate::services::{ConnectionPoolManager, ConnectionService};
+use crate::utils::error::{AppError, Result};
+use crate::utils::sql_validator::{self, SqlDialect};
+use chrono::{Duration, Utc};
+use mysql_async::prelude::*;
+use sqlx::MySqlPool;
+use std::collections::HashSet;
+
+/// 默认采样间隔（秒）
+const DEFAULT_SNAPSHOT_INTERVAL_SECS: u64 = 3600;
+/// 采样保留天数
+const METRIC_RETENTION_DAYS: i64 = 90;
+/// 采样时扫描的最近已完成任务数
+const SNAPSHOT_TASK_WINDOW: i64 = 500;
+
+/// 行数对比服务：统计同步任务各表在 MySQL、RisingWave 和 StarRocks 三端的行数
+pub struct SyncMetricsService;
+
+impl SyncMetricsService {
+    /// 采样间隔，可通过环境变量 ROW_COUNT_SNAPSHOT_INTERVAL_SECS 覆盖，0 表示关闭采样
+    pub fn snapshot_interval_secs() -> u64 {
+        std::env::var("ROW_COUNT_SNAPSHOT_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SNAPSHOT_INTERVAL_SECS)
+    }
+
+    /// 启动后台采样任务
+    pub fn spawn_snapshotter(app_db: MySqlPool) {
+        let interval_secs = Self::snapshot_interval_secs();
+        if interval_secs == 0 {
+            tracing::info!("Row count snapshots disabled");
+            return;
+        }
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::record_snapshots(&app_db).await {
+                    tracing::warn!("Failed to record row count snapshots: {}", e);
+                }
+            }
+        });
+    }
+
+    /// 已完成任务中各表当前的三端行数（不保存）
+    pub async fn count_task(app_db: &MySqlPool, task_id: i64) -> Result<Vec<SyncMetric>> {
+        let task = TaskRepository::new(app_db).find_by_id(task_id).await?;
+        if task.status != TaskStatus::Completed {
+            return Err(AppError::Validation(format!(
+                "Task {} is {}, row counts are only available for completed tasks",
+                task_id,
+                task.status.as_str()
+            )));
+        }
+        let requests = task.sync_requests()?;
+        Self::count_requests(app_db, &task, &requests).await
+    }
+
+    /// 对每个目标表最近一次完成的任务采样一次行数，并清理过期采样，返回采样的表数
+    pub async fn record_snapshots(app_db: &MySqlPool) -> Result<usize> {
+        let filter = TaskFilter {
+            status: Some(TaskStatus::Completed),
+            ..Default::default()
+        };
+        let tasks = TaskRepository::new(app_db)
+            .find_history(&filter, SNAPSHOT_TASK_WINDOW, 0)
+            .await?;
+
+        let mut metrics = Vec::new();
+        for (task, requests) in Self::latest_pipelines(&tasks) {
+            match Self::count_requests(app_db, task, &requests).await {
+                Ok(counted) => metrics.extend(counted),
+                Err(e) => tracing::warn!("Failed to count rows for task {}: {}", task.id, e),
+            }
+        }
+
+        let repo = SyncMetricRepository::new(app_db);
+        repo.save_all(&metrics).await?;
+        let deleted = repo
+            .delete_before(Utc::now() - Duration::days(METRIC_RETENTION_DAYS))
+            .await?;
+        if deleted > 0 {
+            tracing::debug!("Deleted {} expired row count snapshots", deleted);
+        }
+
+        Ok(metrics.len())
+    }
+
+    /// 最近 days 天的行数采样，target 为 `database.table`
+    pub async fn history(
+        app_db: &MySqlPool,
+        days: i64,
+        target: Option<&str>,
+    ) -> Result<Vec<SyncMetric>> {
+        SyncMetricRepository::new(app_db)
+            .find_since(Utc::now() - Duration::days(days.clamp(1, 90)), target)
+            .await
+    }
+
+    /// 每个目标表（按 StarRocks 连接区分）只保留最近一次完成的任务中的请求（tasks 按时间倒序）
+    fn latest_pipelines(tasks: &[SyncTask]) -> Vec<(&SyncTask, Vec<SyncRequest>)> {
+        let mut seen = HashSet::new();
+        tasks
+            .iter()
+            .filter_map(|task| {
+                let requests: Vec<SyncRequest> = task
+                    .sync_requests()
+                    .ok()?
+                    .into_iter()
+                    .filter(|r| {
+                        seen.insert((
+                            task.sr_config_id,
+                            r.target_database.clone(),
+                            r.target_table.clone(),
+                        ))
+                    })
+                    .collect();
+                (!requests.is_empty()).then_some((task, requests))
+            })
+            .collect()
+    }
+
+    /// 统计任务中各表的三端行数，单端查询失败时记录在 error_message 中
+    async fn count_requests(
+        app_db: &MySqlPool,
+        task: &SyncTask,
+        requests: &[SyncRequest],
+    ) -> Result<Vec<SyncMetric>> {
+        let config_repo = ConfigRepository::new(app_db);
+        let mysql_config = config_repo.find_by_id(task.mysql_config_id).await?;
+        let rw_config = config_repo.find_by_id(task.rw_config_id).await?;
+        let sr_config = config_repo.find_by_id(task.sr_config_id).await?;
+
+        let mysql_pool = ConnectionPoolManager::mysql(&mysql_config).await?;
+        let rw_pool = ConnectionPoolManager::postgres(&rw_config).await?;
+        let sr_config = ConnectionService::resolve_config(sr_config).await?;
+        let mut sr_conn = ConnectionService::connect_starrocks(&sr_config).await?;
+
+        let mut metrics = Vec::with_capacity(requests.len());
+        for request in requests {
+            let mut errors = Vec::new();
+
+            let source_count = Self::count_mysql(&mysql_pool, request)
+                .await
+                .map_err(|e| errors.push(format!("MySQL: {}", e)))
+                .ok();
+
+            let rw_sql = format!(
+                "SELECT COUNT(*) FROM {}",
+                RisingWaveDDLGenerator::get_rw_table_name(
+                    &request.target_database,
+                    &request.target_table
+                )
+            );
+            let rw_count = sqlx::query_scalar::<_, i64>(&rw_sql)
+                .fetch_one(&rw_pool)
+                .await
+                .map_err(|e| errors.push(format!("RisingWave: {}", e)))
+                .ok();
+
+            let sr_sql = format!(
+                "SELECT COUNT(*) FROM {}.{}",
+                StarRocksDDLGenerator::quote_identifier(&request.target_database),
+                StarRocksDDLGenerator::quote_identifier(&request.target_table)
+            );
+            let target_count = match sr_conn.query_first::<i64, _>(sr_sql).await {
+                Ok(count) => count,
+                Err(e) => {
+                    errors.push(format!("StarRocks: {}", e));
+                    None
+                }
+            };
+
+            metrics.push(SyncMetric {
+                id: 0,
+                task_id: task.id,
+                mysql_database: request.mysql_database.clone(),
+                mysql_table: request.mysql_table.clone(),
+                target_database: request.target_database.clone(),
+                target_table: request.target_table.clone(),
+                source_count,
+                rw_count,
+                target_count,
+                lag: SyncMetric::lag_of(source_count, target_count),
+                error_message: (!errors.is_empty()).then(|| errors.join("; ")),
+                sampled_at: Utc::now(),
+            });
+        }
+
+        let _ = sr_conn.disconnect().await;
+
+        Ok(metrics)
+    }
+
+    /// MySQL 源表行数，应用同步时的行过滤条件
+    async fn count_mysql(pool: &MySqlPool, request: &SyncRequest) -> Result<i64> {
+        let mut sql = format!(
+            "SELECT COUNT(*) FROM {}.{}",
+            StarRocksDDLGenerator::quote_identifier(&request.mysql_database),
+            StarRocksDDLGenerator::quote_identifier(&request.mysql_table)
+        );
+        if let Some(filter) = request
+            .row_filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+        {
+            sql_validator::validate_expression(filter, SqlDialect::MySql)?;
+            sql.push_str(&format!(" WHERE {}", filter));
+        }
+        Ok(sqlx::query_scalar::<_, i64>(&sql).fetch_one(pool).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: i64, sr_config_id: i64, targets: &[&str]) -> SyncTask {
+        let requests: Vec<SyncRequest> = targets
+            .iter()
+            .map(|table| SyncRequest {
+                mysql_config_id: 1,
+                rw_config_id: 2,
+                sr_config_id,
+                mysql_database: "shop".to_string(),
+                mysql_table: table.to_string(),
+                target_database: "ods".to_string(),
+                target_table: table.to_string(),
+                options: Default::default(),
+                row_filter: None,
+                partition: None,
+                column_mapping: Default::default(),
+                nested_columns: Default::default(),
+                spatial_columns: Default::default(),
+            })
+            .collect();
+        SyncTask {
+            id,
+            task_name: "t".to_string(),
+            mysql_config_id: 1,
+            rw_config_id: 2,
+            sr_config_id,
+            mysql_database: "shop".to_string(),
+            mysql_table: "[Batch]".to_string(),
+            target_database: "ods".to_string(),
+            target_table: "[Batch]".to_string(),
+            status: TaskStatus::Completed,
+            started_at: Utc::now(),
+            completed_at: None,
+            error_message: None,
+            options: "{}".to_string(),
+            preflight_result: None,
+            requests: Some(serde_json::to_string(&requests).unwrap()),
+            sink_requests: None,
+        }
+    }
+
+    #[test]
+    fn test_latest_pipelines() {
+        // 按时间倒序：较早的任务中已被更新任务覆盖的表不再采样
+        let tasks = vec![
+            task(3, 10, &["orders"]),
+            task(2, 10, &["orders", "users"]),
+            task(1, 11, &["orders"]),
+        ];
+        let pipelines: Vec<(i64, Vec<String>)> = SyncMetricsService::latest_pipelines(&tasks)
+            .into_iter()
+            .map(|(task, requests)| {
+                (
+                    task.id,
+                    requests.into_iter().map(|r| r.target_table).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            pipelines,
+            vec![
+                (3, vec!["orders".to_string()]),
+                (2, vec!["users".to_string()]),
+                (1, vec!["orders".to_string()]),
+            ]
+        );
+
+        assert_eq!(SyncMetric::lag_of(Some(10), Some(7)), Some(3));
+        assert_eq!(SyncMetric::lag_of(Some(10), None), None);
+    }
+}
@@ -0,0 +1,320 @@
+use crate::db::TaskRepository;
+use crate::models::{NewTaskLog, Setting, TaskLogLevel, TaskLogPayload};
+use crate::utils::error::Result;
+use crate::utils::redact;
+use sqlx::MySqlPool;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// 单个任务默认最多写入的日志行数
+pub const DEFAULT_MAX_LOGS_PER_TASK: u64 = 5000;
+
+/// 缓冲区达到该行数时立即写入
+const LOG_FLUSH_ROWS: usize = 100;
+
+/// 距上次写入超过该时间时立即写入
+const LOG_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 日志配额判定结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuotaDecision {
+    Write,
+    /// 配额刚好用完：写入本条并提示后续日志将被合并
+    WriteLast,
+    Suppress,
+}
+
+/// 每个任务的日志软配额，超出部分只计数，任务结束时汇总为一行
+#[derive(Debug)]
+struct LogQuota {
+    /// 0 表示不限制
+    max: u64,
+    written: AtomicU64,
+    suppressed: AtomicU64,
+}
+
+impl LogQuota {
+    fn new(max: u64) -> Self {
+        Self {
+            max,
+            written: AtomicU64::new(0),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    fn admit(&self) -> QuotaDecision {
+        if self.max == 0 {
+            return QuotaDecision::Write;
+        }
+        let written = self.written.fetch_add(1, Ordering::Relaxed) + 1;
+        if written < self.max {
+            QuotaDecision::Write
+        } else if written == self.max {
+            QuotaDecision::WriteLast
+        } else {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            QuotaDecision::Suppress
+        }
+    }
+
+    fn take_suppressed(&self) -> u64 {
+        self.suppressed.swap(0, Ordering::Relaxed)
+    }
+}
+
+/// 待写入日志的缓冲区
+#[derive(Debug)]
+struct LogBuffer {
+    entries: Vec<NewTaskLog>,
+    last_flush: Instant,
+}
+
+impl LogBuffer {
+    fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// 加入一条日志，返回是否需要立即写入
+    fn push(&mut self, entry: NewTaskLog) -> bool {
+        let urgent = entry.log_level == "error";
+        self.entries.push(entry);
+        urgent
+            || self.entries.len() >= LOG_FLUSH_ROWS
+            || self.last_flush.elapsed() >= LOG_FLUSH_INTERVAL
+    }
+
+    fn take(&mut self) -> Vec<NewTaskLog> {
+        self.last_flush = Instant::now();
+        std::mem::take(&mut self.entries)
+    }
+}
+
+/// 按日志详细程度写入 task_logs
+///
+/// 日志先进入缓冲区，按行数、时间间隔或调用 `flush` 时批量写入，
+/// 任务结束前必须调用 `flush`。
+pub struct TaskLogger<'a> {
+    app_db: &'a MySqlPool,
+    repo: TaskRepository<'a>,
+    task_id: i64,
+    level: TaskLogLevel,
+    quota: LogQuota,
+    buffer: Mutex<LogBuffer>,
+}
+
+impl<'a> TaskLogger<'a> {
+    pub fn new(app_db: &'a MySqlPool, task_id: i64, level: TaskLogLevel) -> Self {
+        Self {
+            app_db,
+            repo: TaskRepository::new(app_db),
+            task_id,
+            level,
+            quota: LogQuota::new(Self::max_logs_per_task()),
+            buffer: Mutex::new(LogBuffer::new()),
+        }
+    }
+
+    /// 每个任务的日志行数上限，取自设置 task_logs.max_rows（兼容环境变量 TASK_LOG_MAX_ROWS），0 表示不限制
+    pub fn max_logs_per_task() -> u64 {
+        Setting::TaskLogMaxRows
+            .parsed()
+            .unwrap_or(DEFAULT_MAX_LOGS_PER_TASK)
+    }
+
+    async fn write(
+        &self,
+        level: &str,
+        message: &str,
+        payload: Option<TaskLogPayload>,
+    ) -> Result<()> {
+        let flush = match self.quota.admit() {
+            QuotaDecision::Write => self.enqueue(level, message, payload),
+            QuotaDecision::WriteLast => {
+                self.enqueue(level, message, payload);
+                self.enqueue(
+                    "warn",
+                    &format!(
+                        "Task log quota of {} rows reached, further messages will be suppressed",
+                        self.quota.max
+                    ),
+                    None,
+                )
+            }
+            QuotaDecision::Suppress => false,
+        };
+        if flush {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    fn enqueue(&self, level: &str, message: &str, payload: Option<TaskLogPayload>) -> bool {
+        let entry = NewTaskLog {
+            log_level: level.to_string(),
+            message: message.to_string(),
+            created_at: chrono::Utc::now(),
+            payload,
+        };
+        self.buffer.lock().unwrap().push(entry)
+    }
+
+    /// 将缓冲区中的日志批量写入
+    pub async fn flush(&self) -> Result<()> {
+        let entries = self.buffer.lock().unwrap().take();
+        if entries.is_empty() {
+            return Ok(());
+        }
+        self.repo.add_logs(self.task_id, &entries).await
+    }
+
+    /// 写入缓冲区，并将超出配额被丢弃的日志汇总为一行，任务结束前调用
+    pub async fn finish(&self) -> Result<()> {
+        let suppressed = self.quota.take_suppressed();
+        if suppressed > 0 {
+            self.enqueue(
+                "warn",
+                &format!("{} similar messages suppressed", suppressed),
+                None,
+            );
+        }
+        self.flush().await
+    }
+
+    /// 步骤边界，任何级别都记录
+    pub async fn step(&self, message: &str) -> Result<()> {
+        self.step_with(message, TaskLogPayload::step(message)).await
+    }
+
+    /// 带结构化内容的步骤边界
+    pub async fn step_with(&self, message: &str, payload: TaskLogPayload) -> Result<()> {
+        self.write("info", message, Some(payload)).await
+    }
+
+    /// 普通步骤，minimal 级别不记录
+    pub async fn info(&self, message: &str) -> Result<()> {
+        if self.level >= TaskLogLevel::Normal {
+            self.write("info", message, None).await?;
+        }
+        Ok(())
+    }
+
+    /// 调试信息，只有 debug 级别记录
+    pub async fn debug(&self, message: &str) -> Result<()> {
+        self.debug_with(message, None).await
+    }
+
+    /// 带结构化内容的调试信息
+    pub async fn debug_with(&self, message: &str, payload: Option<TaskLogPayload>) -> Result<()> {
+        if self.level >= TaskLogLevel::Debug {
+            self.write("debug", message, payload).await?;
+        }
+        Ok(())
+    }
+
+    /// 记录已执行的 DDL 及耗时（不要传入包含密码的语句）
+    /// normal 级别只记录步骤和耗时，debug 级别同时记录语句，语句中的密码和密钥会被隐藏
+    pub async fn ddl(&self, target: &str, action: &str, ddl: &str, started: Instant) -> Result<()> {
+        let duration_ms = started.elapsed().as_millis() as u64;
+        let mut payload = TaskLogPayload {
+            step: Some(action.to_string()),
+            duration_ms: Some(duration_ms),
+            ..Default::default()
+        };
+
+        if self.level >= TaskLogLevel::Debug {
+            let ddl = redact::scrub_sql(ddl);
+            payload.ddl = Some(ddl.to_string());
+            self.write(
+                "debug",
+                &format!("[{}] {} executed in {} ms:\n{}", target, action, duration_ms, ddl),
+                Some(payload),
+            )
+            .await?;
+        } else if self.level >= TaskLogLevel::Normal {
+            self.write(
+                "info",
+                &format!("[{}] {} completed in {} ms", target, action, duration_ms),
+                Some(payload),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn warn(&self, message: &str) -> Result<()> {
+        self.write("warn", message, None).await
+    }
+
+    pub async fn error(&self, message: &str) -> Result<()> {
+        self.write("error", message, None).await
+    }
+
+    pub fn task_id(&self) -> i64 {
+        self.task_id
+    }
+
+    /// 元数据库连接池
+    pub fn app_db(&self) -> &'a MySqlPool {
+        self.app_db
+    }
+
+    /// 直接访问仓库（更新任务字段等）
+    pub fn repo(&self) -> &TaskRepository<'a> {
+        &self.repo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_suppresses_after_limit() {
+        let quota = LogQuota::new(3);
+        assert_eq!(quota.admit(), QuotaDecision::Write);
+        assert_eq!(quota.admit(), QuotaDecision::Write);
+        assert_eq!(quota.admit(), QuotaDecision::WriteLast);
+        assert_eq!(quota.admit(), QuotaDecision::Suppress);
+        assert_eq!(quota.admit(), QuotaDecision::Suppress);
+        assert_eq!(quota.take_suppressed(), 2);
+        assert_eq!(quota.take_suppressed(), 0);
+    }
+
+    fn entry(level: &str) -> NewTaskLog {
+        NewTaskLog {
+            log_level: level.to_string(),
+            message: "msg".to_string(),
+            created_at: chrono::Utc::now(),
+            payload: None,
+        }
+    }
+
+    #[test]
+    fn test_buffer_flush_triggers() {
+        let mut buffer = LogBuffer::new();
+        for _ in 0..LOG_FLUSH_ROWS - 1 {
+            assert!(!buffer.push(entry("info")));
+        }
+        assert!(buffer.push(entry("info")));
+        assert_eq!(buffer.take().len(), LOG_FLUSH_ROWS);
+
+        assert!(buffer.push(entry("error")));
+
+        buffer.take();
+        buffer.last_flush = Instant::now() - LOG_FLUSH_INTERVAL;
+        assert!(buffer.push(entry("debug")));
+    }
+
+    #[test]
+    fn test_quota_unlimited() {
+        let quota = LogQuota::new(0);
+        for _ in 0..10 {
+            assert_eq!(quota.admit(), QuotaDecision::Write);
+        }
+        assert_eq!(quota.take_suppressed(), 0);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::db::TaskRepository;
+use crate::models::{NewTaskStep, StepStatus, SyncStep, SyncTask, TaskStatus, TaskStep};
+use crate::services::SyncEngine;
+use crate::utils::error::Result;
+use sqlx::MySqlPool;
+
+/// 中断任务的错误信息
+const INTERRUPTED_MESSAGE: &str = "Interrupted by server restart";
+
+/// 任务恢复：启动时把遗留的执行中任务标记为中断，可选地继续执行未完成的表
+pub struct TaskRecoveryService;
+
+impl TaskRecoveryService {
+    /// RESUME_INTERRUPTED_TASKS=true 时为中断的同步任务重新提交未完成的表
+    pub fn resume_enabled() -> bool {
+        std::env::var("RESUME_INTERRUPTED_TASKS")
+            .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false)
+    }
+
+    /// 多个实例共用元数据库时，其他实例正在执行的任务也会被标记为中断
+    pub async fn recover(app_db: &MySqlPool) -> Result<()> {
+        let repo = TaskRepository::new(app_db);
+        let running = repo.find_running().await?;
+        if running.is_empty() {
+            return Ok(());
+        }
+        tracing::warn!(
+            "Marking {} orphaned running tasks as interrupted",
+            running.len()
+        );
+
+        let resume = Self::resume_enabled();
+        for task in running {
+            repo.fail_running_steps(task.id, INTERRUPTED_MESSAGE)
+                .await?;
+            repo.finish_with_log(
+                task.id,
+                TaskStatus::Interrupted,
+                Some(INTERRUPTED_MESSAGE.to_string()),
+                "warn",
+                INTERRUPTED_MESSAGE,
+            )
+            .await?;
+
+            if resume && !task.is_sink_task() && !task.is_cleanup_task() {
+                let message = match Self::resume(app_db, &task).await {
+                    Ok(message) => message,
+                    Err(e) => {
+                        tracing::warn!("Failed to resume task {}: {}", task.id, e);
+                        format!("Failed to resume: {}", e)
+                    }
+                };
+                repo.add_log(task.id, "info", &message, None).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 重新提交未完成的表，返回写入原任务日志的说明
+    async fn resume(app_db: &MySqlPool, task: &SyncTask) -> Result<String> {
+        let requests = task.sync_requests()?;
+        if requests.is_empty() {
+            return Ok("Task does not record its table list, not resumed".to_string());
+        }
+        let repo = TaskRepository::new(app_db);
+        let steps = repo.get_steps(task.id).await?;
+        let completed = Self::completed_tables(&steps, requests.len());
+        let remaining: Vec<_> = requests
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !completed.contains(index))
+            .map(|(_, request)| request)
+            .collect();
+        if remaining.is_empty() {
+            return Ok("All tables were completed, nothing to resume".to_string());
+        }
+
+        let task_id = SyncEngine::new(app_db.clone())
+            .sync_multiple_tables(remaining)
+            .await?;
+        if let Some(created_by) = repo.find_created_by(task.id).await? {
+            repo.set_created_by(task_id, &created_by).await?;
+        }
+        tracing::info!("Resumed interrupted task {} as task {}", task.id, task_id);
+        Ok(format!(
+            "Resumed as task #{}, skipping {} completed tables",
+            task_id,
+            completed.len()
+        ))
+    }
+
+    /// 所有步骤都已完成或跳过的表序号
+    /// 表内的步骤都是可重复执行的 DDL，未完成的表从头执行
+    fn completed_tables(steps: &[TaskStep], table_count: usize) -> Vec<usize> {
+        (0..table_count)
+            .filter(|&table_index| {
+                SyncStep::TABLE_STEPS.iter().all(|step| {
+                    let index = NewTaskStep::index_of(table_index, *step);
+                    steps.iter().any(|s| {
+                        s.step_index == index
+                            && matches!(s.status, StepStatus::Completed | StepStatus::Skipped)
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completed_tables() {
+        let table_steps = SyncStep::TABLE_STEPS.len() as i32;
+        let steps: Vec<TaskStep> = (0..=2 * table_steps)
+            .map(|step_index| TaskStep {
+                id: step_index as i64,
+                task_id: 7,
+                step_index,
+                step: SyncStep::Connect,
+                table_name: None,
+                // 第一张表全部完成，第二张表最后一步执行中
+                status: if step_index == 2 * table_steps {
+                    StepStatus::Running
+                } else if step_index == 1 {
+                    StepStatus::Skipped
+                } else {
+                    StepStatus::Completed
+                },
+                started_at: None,
+                completed_at: None,
+                duration_ms: None,
+                error_message: None,
+            })
+            .collect();
+        assert_eq!(TaskRecoveryService::completed_tables(&steps, 2), vec![0]);
+        assert!(TaskRecoveryService::completed_tables(&[], 2).is_empty());
+    }
+}
@@ -0,0 +1,320 @@
+use crate::db::{ConfigRepository, TaskRepository, ValidationRepository};
+use crate::models::{Column, SyncRequest, ValidationResult};
+use crate::services::{ConnectionPoolManager, ConnectionService, MetadataService};
+use crate::utils::error::{AppError, Result};
+use crate::utils::sql_validator::{self, SqlDialect};
+use mysql_async::prelude::*;
+use sqlx::{MySqlPool, Row};
+
+/// 数据一致性校验服务
+/// 对比 MySQL 源表与 StarRocks 目标表的行数，以及可选的数值列求和校验值
+pub struct ValidationService;
+
+/// 单表两端的统计值
+struct TableSnapshot {
+    count: i64,
+    checksum: Option<String>,
+}
+
+impl ValidationService {
+    /// 校验一个同步任务涉及的所有表，并保存结果
+    pub async fn validate_task(
+        app_db: &MySqlPool,
+        task_id: i64,
+        with_checksum: bool,
+    ) -> Result<Vec<ValidationResult>> {
+        let task = TaskRepository::new(app_db).find_by_id(task_id).await?;
+        let requests = task.sync_requests()?;
+        if requests.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Task {} does not record its table list",
+                task_id
+            )));
+        }
+
+        let config_repo = ConfigRepository::new(app_db);
+        let mysql_config = config_repo.find_by_id(task.mysql_config_id).await?;
+        let sr_config = config_repo.find_by_id(task.sr_config_id).await?;
+        // SRV 名称解析为实际主机和端口
+        let mysql_config = ConnectionService::resolve_config(mysql_config).await?;
+        let sr_config = ConnectionService::resolve_config(sr_config).await?;
+
+        tracing::info!(
+            "Validating task {} ({} tables, checksum: {})",
+            task_id,
+            requests.len(),
+            with_checksum
+        );
+
+        let mysql_pool = ConnectionPoolManager::mysql(&mysql_config).await?;
+
+        let sr_opts = ConnectionService::build_starrocks_opts_from_config(
+            &ConnectionService::via_proxy(&sr_config).await?,
+        )?;
+        let mut sr_conn = mysql_async::Conn::new(sr_opts).await.map_err(|e| {
+            tracing::error!("Failed to connect to StarRocks: {}", e);
+            AppError::Connection(format!("StarRocks connection failed: {}", e))
+        })?;
+
+        let validation_repo = ValidationRepository::new(app_db);
+        let mut results = Vec::with_capacity(requests.len());
+        for request in &requests {
+            let compared =
+                Self::compare_table(&mysql_pool, &mut sr_conn, request, with_checksum).await;
+
+            let mut result = ValidationResult {
+                id: 0,
+                task_id,
+                mysql_database: request.mysql_database.clone(),
+                mysql_table: request.mysql_table.clone(),
+                target_database: request.target_database.clone(),
+                target_table: request.target_table.clone(),
+                source_count: None,
+                target_count: None,
+                source_checksum: None,
+                target_checksum: None,
+                matched: false,
+                error_message: None,
+                created_at: chrono::Utc::now(),
+            };
+
+            match compared {
+                Ok((source, target)) => {
+                    result.matched =
+                        source.count == target.count && source.checksum == target.checksum;
+                    result.source_count = Some(source.count);
+                    result.target_count = Some(target.count);
+                    result.source_checksum = source.checksum;
+                    result.target_checksum = target.checksum;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Validation failed for {}.{}: {}",
+                        request.mysql_database,
+                        request.mysql_table,
+                        e
+                    );
+                    result.error_message = Some(e.to_string());
+                }
+            }
+
+            results.push(result);
+        }
+        validation_repo.save_all(&mut results).await?;
+
+        let _ = sr_conn.disconnect().await;
+
+        Ok(results)
+    }
+
+    /// 查询单表两端的行数（和校验值）
+    async fn compare_table(
+        mysql_pool: &MySqlPool,
+        sr_conn: &mut mysql_async::Conn,
+        request: &SyncRequest,
+        with_checksum: bool,
+    ) -> Result<(TableSnapshot, TableSnapshot)> {
+        let checksum_columns = if with_checksum {
+            let columns = MetadataService::get_columns(
+                mysql_pool,
+                &request.mysql_database,
+                &request.mysql_table,
+            )
+            .await?;
+            Self::checksum_columns(&columns)
+        } else {
+            vec![]
+        };
+
+        let row_filter = request
+            .row_filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty());
+        // 过滤条件会拼接进 MySQL 和 StarRocks 的统计查询
+        if let Some(filter) = row_filter {
+            sql_validator::validate_expression(filter, SqlDialect::MySql)?;
+        }
+
+        // MySQL 源表
+        let source_sql = Self::build_snapshot_query(
+            &request.mysql_database,
+            &request.mysql_table,
+            &checksum_columns,
+            row_filter,
+            "CHAR",
+        );
+        tracing::debug!("Source validation query: {}", source_sql);
+        let row = sqlx::query(&source_sql).fetch_one(mysql_pool).await?;
+        let mut source_values = Vec::with_capacity(checksum_columns.len());
+        for i in 0..checksum_columns.len() {
+            source_values.push(row.try_get::<Option<String>, _>(i + 1)?);
+        }
+        let source = TableSnapshot {
+            count: row.try_get(0)?,
+            checksum: with_checksum.then(|| Self::format_checksum(&source_values)),
+        };
+
+        // StarRocks 目标表（过滤条件已在 sink 中生效，这里不再重复）
+        let target_columns: Vec<String> = checksum_columns
+            .iter()
+            .map(|c| request.target_column_name(c).to_string())
+            .collect();
+        let target_sql = Self::build_snapshot_query(
+            &request.target_database,
+            &request.target_table,
+            &target_columns,
+            None,
+            "VARCHAR",
+        );
+        tracing::debug!("Target validation query: {}", target_sql);
+        let row: mysql_async::Row = sr_conn
+            .query_first(&target_sql)
+            .await
+            .map_err(|e| AppError::Unknown(format!("StarRocks validation query failed: {}", e)))?
+            .ok_or_else(|| AppError::Unknown("StarRocks returned no rows".to_string()))?;
+        let mut target_values = Vec::with_capacity(checksum_columns.len());
+        for i in 0..checksum_columns.len() {
+            target_values.push(row.get::<Option<String>, usize>(i + 1).flatten());
+        }
+        let target = TableSnapshot {
+            count: row.get::<i64, usize>(0).unwrap_or_default(),
+            checksum: with_checksum.then(|| Self::format_checksum(&target_values)),
+        };
+
+        Ok((source, target))
+    }
+
+    /// 参与求和校验的列：整数和定点数列（浮点数求和结果不稳定，不参与）
+    fn checksum_columns(columns: &[Column]) -> Vec<String> {
+        columns
+            .iter()
+            .filter(|c| {
+                let data_type = c.data_type.to_uppercase();
+                let base_type = data_type.split(['(', ' ']).next().unwrap_or(&data_type);
+                matches!(
+                    base_type,
+                    "TINYINT"
+                        | "SMALLINT"
+                        | "MEDIUMINT"
+                        | "INT"
+                        | "INTEGER"
+                        | "BIGINT"
+                        | "DECIMAL"
+                        | "NUMERIC"
+                )
+            })
+            .map(|c| c.name.clone())
+            .collect()
+    }
+
+    /// 生成统计查询：COUNT(*) 加上各校验列的 SUM（转换为字符串避免精度差异）
+    fn build_snapshot_query(
+        database: &str,
+        table: &str,
+        checksum_columns: &[String],
+        row_filter: Option<&str>,
+        string_type: &str,
+    ) -> String {
+        let mut expressions = vec!["COUNT(*)".to_string()];
+        for column in checksum_columns {
+            expressions.push(format!("CAST(SUM(`{}`) AS {})", column, string_type));
+        }
+
+        let mut sql = format!(
+            "SELECT {} FROM `{}`.`{}`",
+            expressions.join(", "),
+            database,
+            table
+        );
+        if let Some(filter) = row_filter {
+            sql.push_str(&format!(" WHERE {}", filter));
+        }
+        sql
+    }
+
+    /// 将各列求和结果拼成校验值，统一去掉小数末尾的 0
+    fn format_checksum(values: &[Option<String>]) -> String {
+        values
+            .iter()
+            .map(|v| match v {
+                Some(v) => {
+                    let v = v.trim();
+                    if v.contains('.') {
+                        v.trim_end_matches('0').trim_end_matches('.').to_string()
+                    } else {
+                        v.to_string()
+                    }
+                }
+                None => "NULL".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, data_type: &str) -> Column {
+        Column {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_nullable: true,
+            default_value: None,
+            comment: None,
+            character_maximum_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+        }
+    }
+
+    #[test]
+    fn test_checksum_columns() {
+        let columns = vec![
+            column("id", "bigint unsigned"),
+            column("amount", "decimal(10,2)"),
+            column("ratio", "double"),
+            column("name", "varchar(64)"),
+        ];
+        assert_eq!(
+            ValidationService::checksum_columns(&columns),
+            vec!["id".to_string(), "amount".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_build_snapshot_query() {
+        let sql = ValidationService::build_snapshot_query(
+            "shop",
+            "orders",
+            &["id".to_string()],
+            Some("deleted_at IS NULL"),
+            "CHAR",
+        );
+        assert_eq!(
+            sql,
+            "SELECT COUNT(*), CAST(SUM(`id`) AS CHAR) FROM `shop`.`orders` WHERE deleted_at IS NULL"
+        );
+
+        let sql = ValidationService::build_snapshot_query("ods", "orders", &[], None, "VARCHAR");
+        assert_eq!(sql, "SELECT COUNT(*) FROM `ods`.`orders`");
+    }
+
+    #[test]
+    fn test_format_checksum_normalizes_decimals() {
+        let mysql = ValidationService::format_checksum(&[
+            Some("120.50".to_string()),
+            Some("42".to_string()),
+            None,
+        ]);
+        let starrocks = ValidationService::format_checksum(&[
+            Some("120.5".to_string()),
+            Some("42".to_string()),
+            None,
+        ]);
+        assert_eq!(mysql, starrocks);
+        assert_eq!(mysql, "120.5,42,NULL");
+    }
+}
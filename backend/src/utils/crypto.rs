@@ -6,23 +6,189 @@ use aes_gcm::{
 use base64::{engine::general_purpose, Engine as _};
 use once_cell::sync::Lazy;
 use rand::Rng;
+use sha2::{Digest, Sha256};
 
-// 在生产环境中，这个密钥应该从环境变量或配置文件中读取
-static ENCRYPTION_KEY: Lazy<[u8; 32]> = Lazy::new(|| {
-    // TODO: 从环境变量或配置文件读取
-    // 这里使用固定密钥仅用于开发，生产环境必须使用安全的密钥管理
-    let key_str = std::env::var("ENCRYPTION_KEY")
-        .unwrap_or_else(|_| "rw_cdc_sr_default_key_32_bytes!".to_string());
-    let mut key = [0u8; 32];
-    let key_bytes = key_str.as_bytes();
-    let len = key_bytes.len().min(32);
-    key[..len].copy_from_slice(&key_bytes[..len]);
-    key
-});
+/// 未配置密钥时使用的开发密钥，启动时输出警告
+const DEFAULT_KEY: &str = "rw_cdc_sr_default_key_32_bytes!";
+
+/// 带版本的密文格式 `v1:<密钥 ID>:<base64>`；没有前缀的旧密文按当前密钥和旧密钥依次尝试
+const CIPHERTEXT_VERSION: &str = "v1";
+
+/// 完整 32 字节密钥的前缀，例如 `base64:...`；其他值按旧方式截断或补零到 32 字节
+const BASE64_KEY_PREFIX: &str = "base64:";
+
+static KEYRING: Lazy<Keyring> =
+    Lazy::new(|| Keyring::from_env().expect("Invalid encryption key configuration"));
+
+/// 存储加密使用的密钥：当前密钥用于加密，旧密钥只用于解密（轮换期间）
+struct Keyring {
+    current: KeyEntry,
+    previous: Vec<KeyEntry>,
+}
+
+struct KeyEntry {
+    /// 密钥 SHA-256 的前 8 个十六进制字符，写入密文用于选择解密密钥
+    id: String,
+    key: [u8; 32],
+}
+
+impl KeyEntry {
+    fn parse(value: &str) -> Result<Self> {
+        let key = if let Some(encoded) = value.strip_prefix(BASE64_KEY_PREFIX) {
+            general_purpose::STANDARD
+                .decode(encoded.trim())
+                .ok()
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                .ok_or_else(|| {
+                    AppError::Encryption("base64 key must decode to 32 bytes".to_string())
+                })?
+        } else {
+            let mut key = [0u8; 32];
+            let key_bytes = value.as_bytes();
+            let len = key_bytes.len().min(32);
+            key[..len].copy_from_slice(&key_bytes[..len]);
+            key
+        };
+        let digest = Sha256::digest(key);
+        let id = digest[..4].iter().map(|b| format!("{:02x}", b)).collect();
+        Ok(Self { id, key })
+    }
+}
+
+impl Keyring {
+    fn new(current: &str, previous: &[&str]) -> Result<Self> {
+        Ok(Self {
+            current: KeyEntry::parse(current)?,
+            previous: previous
+                .iter()
+                .map(|value| KeyEntry::parse(value))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// ENCRYPTION_KEY_FILE 优先于 ENCRYPTION_KEY，ENCRYPTION_KEY_PREVIOUS 为逗号分隔的旧密钥
+    fn from_env() -> Result<Self> {
+        let current = Self::configured_key()?.unwrap_or_else(|| DEFAULT_KEY.to_string());
+        let previous = std::env::var("ENCRYPTION_KEY_PREVIOUS").unwrap_or_default();
+        let previous: Vec<&str> = previous
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .collect();
+        Self::new(&current, &previous)
+    }
+
+    fn configured_key() -> Result<Option<String>> {
+        if let Ok(path) = std::env::var("ENCRYPTION_KEY_FILE") {
+            let value = std::fs::read_to_string(&path).map_err(|e| {
+                AppError::Encryption(format!(
+                    "Failed to read ENCRYPTION_KEY_FILE {}: {}",
+                    path, e
+                ))
+            })?;
+            return Ok(Some(value.trim().to_string()));
+        }
+        Ok(std::env::var("ENCRYPTION_KEY")
+            .ok()
+            .filter(|v| !v.is_empty()))
+    }
+
+    fn encrypt(&self, plaintext: &str) -> Result<String> {
+        Ok(format!(
+            "{}:{}:{}",
+            CIPHERTEXT_VERSION,
+            self.current.id,
+            encrypt_with_key(plaintext, &self.current.key)?
+        ))
+    }
+
+    fn decrypt(&self, encrypted: &str) -> Result<String> {
+        let mut keys = std::iter::once(&self.current).chain(&self.previous);
+        match Self::split_versioned(encrypted) {
+            Some((id, data)) => {
+                let entry = keys.find(|entry| entry.id == id).ok_or_else(|| {
+                    AppError::Encryption(format!(
+                        "Unknown encryption key {}, add it to ENCRYPTION_KEY_PREVIOUS",
+                        id
+                    ))
+                })?;
+                decrypt_with_key(data, &entry.key)
+            }
+            None => {
+                let mut last_error = None;
+                for entry in keys {
+                    match decrypt_with_key(encrypted, &entry.key) {
+                        Ok(plaintext) => return Ok(plaintext),
+                        Err(e) => last_error = Some(e),
+                    }
+                }
+                Err(last_error.expect("keyring has a current key"))
+            }
+        }
+    }
+
+    /// 不是当前密钥加密的带版本密文时，返回用当前密钥重新加密的结果
+    fn reencrypt(&self, encrypted: &str) -> Result<Option<String>> {
+        if Self::split_versioned(encrypted).is_some_and(|(id, _)| id == self.current.id) {
+            return Ok(None);
+        }
+        Ok(Some(self.encrypt(&self.decrypt(encrypted)?)?))
+    }
+
+    /// base64 中没有冒号，有版本前缀时返回 (密钥 ID, 密文)
+    fn split_versioned(encrypted: &str) -> Option<(&str, &str)> {
+        let rest = encrypted
+            .strip_prefix(CIPHERTEXT_VERSION)?
+            .strip_prefix(':')?;
+        rest.split_once(':')
+    }
+}
+
+/// 启动时校验密钥配置，未配置密钥时输出警告
+pub fn init() -> Result<()> {
+    if Keyring::configured_key()?.is_none() {
+        tracing::warn!(
+            "ENCRYPTION_KEY is not set, stored passwords are encrypted with the built-in development key"
+        );
+    }
+    // 先校验配置，避免首次加解密时 panic
+    Keyring::from_env()?;
+    tracing::info!(
+        "Encryption key {} loaded ({} previous keys)",
+        KEYRING.current.id,
+        KEYRING.previous.len()
+    );
+    Ok(())
+}
+
+/// 当前加密密钥的 ID
+pub fn current_key_id() -> &'static str {
+    &KEYRING.current.id
+}
 
 /// 加密字符串
 pub fn encrypt(plaintext: &str) -> Result<String> {
-    let cipher = Aes256Gcm::new_from_slice(&*ENCRYPTION_KEY)
+    KEYRING.encrypt(plaintext)
+}
+
+/// 解密字符串（兼容没有版本前缀的旧密文）
+pub fn decrypt(encrypted: &str) -> Result<String> {
+    KEYRING.decrypt(encrypted)
+}
+
+/// 用当前密钥重新加密，已是当前密钥加密的密文返回 None
+pub fn reencrypt(encrypted: &str) -> Result<Option<String>> {
+    KEYRING.reencrypt(encrypted)
+}
+
+/// 从口令派生密钥（用于导出文件等不依赖本机密钥的场景）
+pub fn derive_key(passphrase: &str) -> [u8; 32] {
+    Sha256::digest(passphrase.as_bytes()).into()
+}
+
+/// 使用指定密钥加密字符串
+pub fn encrypt_with_key(plaintext: &str, key: &[u8; 32]) -> Result<String> {
+    let cipher = Aes256Gcm::new_from_slice(key)
         .map_err(|e| AppError::Encryption(format!("Failed to create cipher: {}", e)))?;
 
     // 生成随机 nonce (12 bytes for AES-GCM)
@@ -42,9 +208,9 @@ pub fn encrypt(plaintext: &str) -> Result<String> {
     Ok(general_purpose::STANDARD.encode(&result))
 }
 
-/// 解密字符串
-pub fn decrypt(encrypted: &str) -> Result<String> {
-    let cipher = Aes256Gcm::new_from_slice(&*ENCRYPTION_KEY)
+/// 使用指定密钥解密字符串
+pub fn decrypt_with_key(encrypted: &str, key: &[u8; 32]) -> Result<String> {
+    let cipher = Aes256Gcm::new_from_slice(key)
         .map_err(|e| AppError::Encryption(format!("Failed to create cipher: {}", e)))?;
 
     // 解码 base64
@@ -85,6 +251,40 @@ mod tests {
         assert_eq!(decrypted, original);
     }
 
+    #[test]
+    fn test_passphrase_key() {
+        let key = derive_key("bundle-passphrase");
+        let encrypted = encrypt_with_key("secret", &key).unwrap();
+        assert_eq!(decrypt_with_key(&encrypted, &key).unwrap(), "secret");
+        assert!(decrypt_with_key(&encrypted, &derive_key("wrong")).is_err());
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let old = Keyring::new("old_key", &[]).unwrap();
+        let legacy = encrypt_with_key("secret", &old.current.key).unwrap();
+        let versioned = old.encrypt("secret").unwrap();
+        assert!(versioned.starts_with(&format!("v1:{}:", old.current.id)));
+        assert_eq!(old.decrypt(&legacy).unwrap(), "secret");
+        assert_eq!(old.reencrypt(&versioned).unwrap(), None);
+
+        let key = general_purpose::STANDARD.encode([7u8; 32]);
+        let rotated = Keyring::new(&format!("base64:{}", key), &["old_key"]).unwrap();
+        assert_eq!(rotated.current.key, [7u8; 32]);
+        assert_eq!(rotated.decrypt(&legacy).unwrap(), "secret");
+        assert_eq!(rotated.decrypt(&versioned).unwrap(), "secret");
+        let reencrypted = rotated.reencrypt(&versioned).unwrap().unwrap();
+        assert!(reencrypted.starts_with(&format!("v1:{}:", rotated.current.id)));
+        assert_eq!(rotated.decrypt(&reencrypted).unwrap(), "secret");
+
+        // 旧密钥移除后无法解密旧密文
+        let new_only = Keyring::new(&format!("base64:{}", key), &[]).unwrap();
+        assert!(new_only.decrypt(&versioned).is_err());
+        assert!(new_only.decrypt(&legacy).is_err());
+        assert_eq!(new_only.decrypt(&reencrypted).unwrap(), "secret");
+        assert!(Keyring::new("base64:c2hvcnQ=", &[]).is_err());
+    }
+
     #[test]
     fn test_decrypt_invalid_data() {
         let result = decrypt("invalid_base64!");
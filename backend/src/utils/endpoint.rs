@@ -0,0 +1,190 @@
+use crate::utils::error::{AppError, Result};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use trust_dns_resolver::TokioAsyncResolver;
+
+/// 连接配置中的主机地址
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Host {
+    Name(String),
+    Ipv4(Ipv4Addr),
+    Ipv6(Ipv6Addr),
+    /// DNS SRV 记录，例如 `_mysql._tcp.db.example.com`，连接前解析为实际主机和端口
+    Srv(String),
+}
+
+impl Host {
+    /// 解析并校验主机字段：主机名、IPv4、IPv6（可带方括号）或 SRV 名称
+    pub fn parse(host: &str) -> Result<Self> {
+        let host = host.trim();
+        if host.is_empty() {
+            return Err(AppError::Validation("Host must not be empty".to_string()));
+        }
+
+        if let Some(inner) = host.strip_prefix('[') {
+            let inner = inner.strip_suffix(']').ok_or_else(|| {
+                AppError::Validation(format!("Unterminated IPv6 literal: {}", host))
+            })?;
+            return inner
+                .parse()
+                .map(Host::Ipv6)
+                .map_err(|_| AppError::Validation(format!("Invalid IPv6 address: {}", host)));
+        }
+        if host.contains(':') {
+            return host.parse().map(Host::Ipv6).map_err(|_| {
+                AppError::Validation(format!(
+                    "Invalid host (use [addr] for IPv6, port goes in the port field): {}",
+                    host
+                ))
+            });
+        }
+        if let Ok(ip) = host.parse() {
+            return Ok(Host::Ipv4(ip));
+        }
+
+        let valid = host.len() <= 253
+            && host.split('.').all(|label| {
+                !label.is_empty()
+                    && label.len() <= 63
+                    && label
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            });
+        if !valid {
+            return Err(AppError::Validation(format!("Invalid hostname: {}", host)));
+        }
+
+        if host.starts_with('_') && (host.contains("._tcp.") || host.contains("._udp.")) {
+            Ok(Host::Srv(host.to_string()))
+        } else {
+            Ok(Host::Name(host.to_string()))
+        }
+    }
+
+    /// 驱动使用的主机（IPv6 不带方括号）
+    pub fn driver_host(&self) -> String {
+        match self {
+            Host::Name(name) | Host::Srv(name) => name.clone(),
+            Host::Ipv4(ip) => ip.to_string(),
+            Host::Ipv6(ip) => ip.to_string(),
+        }
+    }
+
+    /// 拼接 URL 使用的主机（IPv6 带方括号）
+    pub fn url_host(&self) -> String {
+        match self {
+            Host::Ipv6(ip) => format!("[{}]", ip),
+            _ => self.driver_host(),
+        }
+    }
+}
+
+/// 驱动使用的主机，无法解析时原样返回（配置在保存时已校验）
+pub fn driver_host(host: &str) -> String {
+    Host::parse(host)
+        .map(|h| h.driver_host())
+        .unwrap_or_else(|_| host.to_string())
+}
+
+/// 拼接 URL 使用的主机，无法解析时原样返回
+pub fn url_host(host: &str) -> String {
+    Host::parse(host)
+        .map(|h| h.url_host())
+        .unwrap_or_else(|_| host.to_string())
+}
+
+/// 解析实际连接的主机和端口；SRV 名称按优先级（小优先）和权重（大优先）选择一条记录
+pub async fn resolve(host: &str, port: u16) -> Result<(String, u16)> {
+    let name = match Host::parse(host)? {
+        Host::Srv(name) => name,
+        other => return Ok((other.url_host(), port)),
+    };
+
+    let resolver = TokioAsyncResolver::tokio_from_system_conf()
+        .map_err(|e| AppError::Connection(format!("Failed to create DNS resolver: {}", e)))?;
+    let lookup = resolver
+        .srv_lookup(name.as_str())
+        .await
+        .map_err(|e| AppError::Connection(format!("SRV lookup for {} failed: {}", name, e)))?;
+
+    let records: Vec<(u16, u16, String, u16)> = lookup
+        .iter()
+        .map(|srv| {
+            (
+                srv.priority(),
+                srv.weight(),
+                srv.target().to_utf8(),
+                srv.port(),
+            )
+        })
+        .collect();
+
+    pick_srv_record(&records)
+        .ok_or_else(|| AppError::Connection(format!("No SRV records found for {}", name)))
+}
+
+/// 从 (priority, weight, target, port) 中选择优先级最小、权重最大的记录
+fn pick_srv_record(records: &[(u16, u16, String, u16)]) -> Option<(String, u16)> {
+    records
+        .iter()
+        .min_by_key(|(priority, weight, _, _)| (*priority, std::cmp::Reverse(*weight)))
+        .map(|(_, _, target, port)| (target.trim_end_matches('.').to_string(), *port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_host() {
+        assert_eq!(
+            Host::parse("mysql.prod").unwrap(),
+            Host::Name("mysql.prod".to_string())
+        );
+        assert_eq!(
+            Host::parse("10.0.0.1").unwrap(),
+            Host::Ipv4("10.0.0.1".parse().unwrap())
+        );
+        assert_eq!(
+            Host::parse("[fd00::1]").unwrap(),
+            Host::Ipv6("fd00::1".parse().unwrap())
+        );
+        assert_eq!(
+            Host::parse("fd00::1").unwrap(),
+            Host::Ipv6("fd00::1".parse().unwrap())
+        );
+        assert_eq!(
+            Host::parse("_mysql._tcp.db.example.com").unwrap(),
+            Host::Srv("_mysql._tcp.db.example.com".to_string())
+        );
+
+        assert!(Host::parse("").is_err());
+        assert!(Host::parse("[fd00::1").is_err());
+        assert!(Host::parse("mysql.prod:3306").is_err());
+        assert!(Host::parse("bad host").is_err());
+        assert!(Host::parse("a..b").is_err());
+    }
+
+    #[test]
+    fn test_host_forms() {
+        let v6 = Host::parse("[fd00::1]").unwrap();
+        assert_eq!(v6.driver_host(), "fd00::1");
+        assert_eq!(v6.url_host(), "[fd00::1]");
+        assert_eq!(url_host("fd00::1"), "[fd00::1]");
+        assert_eq!(driver_host("[fd00::1]"), "fd00::1");
+        assert_eq!(url_host("mysql.prod"), "mysql.prod");
+    }
+
+    #[test]
+    fn test_pick_srv_record() {
+        let records = vec![
+            (20, 100, "backup.example.com.".to_string(), 3307),
+            (10, 5, "b.example.com.".to_string(), 3306),
+            (10, 50, "a.example.com.".to_string(), 3306),
+        ];
+        assert_eq!(
+            pick_srv_record(&records),
+            Some(("a.example.com".to_string(), 3306))
+        );
+        assert_eq!(pick_srv_record(&[]), None);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::models::SyncStep;
+use crate::utils::error::{AppError, Result};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 开启故障注入的环境变量，仅用于开发和集成测试
+pub const FAULT_INJECTION_ENV: &str = "FAULT_INJECTION";
+
+/// 当前生效的故障注入计划，为空表示未开启
+static PLAN: Lazy<Mutex<Option<FaultPlan>>> = Lazy::new(|| Mutex::new(None));
+
+/// DDL 的执行目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DdlTarget {
+    RisingWave,
+    StarRocks,
+}
+
+/// 单条故障规则，remaining 为剩余触发次数，为空表示每次都触发
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FaultRule {
+    /// 执行到该步骤时失败，用于验证断点续跑和告警
+    FailStep {
+        step: SyncStep,
+        remaining: Option<u32>,
+    },
+    /// 执行 DDL 前等待
+    DelayDdl { delay: Duration },
+    /// DDL 返回瞬时错误，用于验证重试
+    FailDdl {
+        target: Option<DdlTarget>,
+        remaining: Option<u32>,
+    },
+}
+
+/// 故障注入计划
+/// 格式为分号分隔的规则，例如 `fail_step=create_sink:1;delay_ddl=500;fail_ddl=rw:2`
+/// - `fail_step=<step>[:n]`：执行到该步骤时失败（前 n 次）
+/// - `delay_ddl=<ms>`：每条 DDL 执行前等待
+/// - `fail_ddl=<rw|sr|all>[:n]`：DDL 返回瞬时错误（前 n 次）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FaultPlan {
+    pub rules: Vec<FaultRule>,
+}
+
+impl FaultPlan {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut rules = Vec::new();
+        for rule in spec.split(';').map(str::trim).filter(|r| !r.is_empty()) {
+            let invalid = || AppError::Config(format!("Invalid fault injection rule: {}", rule));
+            let (kind, value) = rule.split_once('=').ok_or_else(invalid)?;
+            let (value, count) = match value.split_once(':') {
+                Some((value, count)) => (value, Some(count.parse().map_err(|_| invalid())?)),
+                None => (value, None),
+            };
+            rules.push(match kind.trim() {
+                "fail_step" => FaultRule::FailStep {
+                    step: SyncStep::try_from(value.trim().to_string()).map_err(|_| invalid())?,
+                    remaining: count,
+                },
+                "delay_ddl" if count.is_none() => FaultRule::DelayDdl {
+                    delay: Duration::from_millis(value.trim().parse().map_err(|_| invalid())?),
+                },
+                "fail_ddl" => FaultRule::FailDdl {
+                    target: match value.trim() {
+                        "rw" => Some(DdlTarget::RisingWave),
+                        "sr" => Some(DdlTarget::StarRocks),
+                        "all" => None,
+                        _ => return Err(invalid()),
+                    },
+                    remaining: count,
+                },
+                _ => return Err(invalid()),
+            });
+        }
+        Ok(Self { rules })
+    }
+
+    /// 是否在该步骤注入失败，会消耗一次触发次数
+    fn fail_step(&mut self, current: SyncStep) -> bool {
+        self.rules.iter_mut().any(|rule| match rule {
+            FaultRule::FailStep { step, remaining } if *step == current => consume(remaining),
+            _ => false,
+        })
+    }
+
+    /// DDL 执行前的等待时间
+    fn ddl_delay(&self) -> Duration {
+        self.rules
+            .iter()
+            .map(|rule| match rule {
+                FaultRule::DelayDdl { delay } => *delay,
+                _ => Duration::ZERO,
+            })
+            .sum()
+    }
+
+    /// 是否让该 DDL 失败，会消耗一次触发次数
+    fn fail_ddl(&mut self, current: DdlTarget) -> bool {
+        self.rules.iter_mut().any(|rule| match rule {
+            FaultRule::FailDdl { target, remaining } if target.is_none_or(|t| t == current) => {
+                consume(remaining)
+            }
+            _ => false,
+        })
+    }
+}
+
+fn consume(remaining: &mut Option<u32>) -> bool {
+    match remaining {
+        None => true,
+        Some(0) => false,
+        Some(n) => {
+            *n -= 1;
+            true
+        }
+    }
+}
+
+/// 从环境变量加载故障注入计划，未设置时不开启
+pub fn init_from_env() -> Result<()> {
+    let Some(spec) = std::env::var(FAULT_INJECTION_ENV)
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+    else {
+        return Ok(());
+    };
+    let plan = FaultPlan::parse(&spec)?;
+    tracing::warn!(
+        "Fault injection enabled ({}), do not use in production: {}",
+        FAULT_INJECTION_ENV,
+        spec
+    );
+    install(Some(plan));
+    Ok(())
+}
+
+/// 替换当前的故障注入计划
+pub fn install(plan: Option<FaultPlan>) {
+    *PLAN.lock().unwrap() = plan;
+}
+
+/// 同步步骤开始前调用，命中规则时返回错误
+pub fn before_step(step: SyncStep) -> Result<()> {
+    let fail = PLAN
+        .lock()
+        .unwrap()
+        .as_mut()
+        .is_some_and(|plan| plan.fail_step(step));
+    if !fail {
+        return Ok(());
+    }
+    tracing::warn!("Injecting failure at step {}", step.as_str());
+    Err(AppError::Unknown(format!(
+        "Injected failure at step {}",
+        step.as_str()
+    )))
+}
+
+/// DDL 执行前调用：按计划等待，命中失败规则时返回错误信息（按瞬时错误处理）
+pub async fn before_ddl(target: DdlTarget) -> Option<String> {
+    let (delay, fail) = match PLAN.lock().unwrap().as_mut() {
+        Some(plan) => (plan.ddl_delay(), plan.fail_ddl(target)),
+        None => return None,
+    };
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+    fail.then(|| {
+        tracing::warn!("Injecting transient DDL failure on {:?}", target);
+        "injected fault: connection reset".to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fault_plan_parse_and_counts() {
+        let mut plan =
+            FaultPlan::parse("fail_step=create_sink:1; delay_ddl=250; fail_ddl=rw:2").unwrap();
+        assert_eq!(plan.ddl_delay(), Duration::from_millis(250));
+
+        assert!(!plan.fail_step(SyncStep::CreateRwTable));
+        assert!(plan.fail_step(SyncStep::CreateSink));
+        assert!(!plan.fail_step(SyncStep::CreateSink));
+
+        assert!(!plan.fail_ddl(DdlTarget::StarRocks));
+        assert!(plan.fail_ddl(DdlTarget::RisingWave));
+        assert!(plan.fail_ddl(DdlTarget::RisingWave));
+        assert!(!plan.fail_ddl(DdlTarget::RisingWave));
+
+        let mut always = FaultPlan::parse("fail_ddl=all").unwrap();
+        assert!(always.fail_ddl(DdlTarget::StarRocks));
+        assert!(always.fail_ddl(DdlTarget::StarRocks));
+
+        assert!(FaultPlan::parse("fail_step=unknown").is_err());
+        assert!(FaultPlan::parse("delay_ddl=100:2").is_err());
+        assert!(FaultPlan::parse("explode").is_err());
+        assert_eq!(FaultPlan::parse(" ").unwrap(), FaultPlan::default());
+    }
+}
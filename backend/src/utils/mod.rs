@@ -1,4 +1,14 @@
 pub mod error;
 pub mod type_mapper;
+pub mod connection_url;
 pub mod crypto;
+pub mod endpoint;
+pub mod fault;
+pub mod proxy;
+pub mod redact;
+pub mod service;
+pub mod smtp;
+pub mod sql_validator;
+pub mod tls;
 
+pub mod naming;
@@ -0,0 +1,103 @@
+use crate::models::SyncRequest;
+use crate::utils::error::{AppError, Result};
+
+/// 目标表名模板，例如 `ods_{table}`、`{db}_{table}`
+/// 支持的占位符：{db}（MySQL 库名）、{table}（MySQL 表名）、{target_db}（目标库名）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableNameTemplate(String);
+
+impl TableNameTemplate {
+    /// 解析模板，未知占位符或括号不匹配时报错
+    pub fn parse(template: &str) -> Result<Self> {
+        let template = template.trim();
+        if template.is_empty() {
+            return Err(AppError::Validation(
+                "Table name template must not be empty".to_string(),
+            ));
+        }
+        let template = Self(template.to_string());
+        template.render("db", "table", "target_db")?;
+        Ok(template)
+    }
+
+    /// 按源表渲染目标表名
+    pub fn render(&self, db: &str, table: &str, target_db: &str) -> Result<String> {
+        let mut name = String::with_capacity(self.0.len() + table.len());
+        let mut rest = self.0.as_str();
+        while let Some(start) = rest.find(['{', '}']) {
+            if rest[start..].starts_with('}') {
+                return Err(self.invalid("unmatched '}'"));
+            }
+            name.push_str(&rest[..start]);
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| self.invalid("unmatched '{'"))?;
+            match &rest[start + 1..start + end] {
+                "db" => name.push_str(db),
+                "table" => name.push_str(table),
+                "target_db" => name.push_str(target_db),
+                other => return Err(self.invalid(&format!("unknown placeholder {{{}}}", other))),
+            }
+            rest = &rest[start + end + 1..];
+        }
+        name.push_str(rest);
+
+        if name.is_empty()
+            || name.len() > 256
+            || name
+                .chars()
+                .any(|c| c.is_whitespace() || matches!(c, '`' | '"' | '\'' | '.'))
+        {
+            return Err(AppError::Validation(format!(
+                "Table name template {} produces an invalid table name: {}",
+                self.0, name
+            )));
+        }
+        Ok(name)
+    }
+
+    /// 用模板覆盖同步请求的目标表名
+    pub fn apply(&self, requests: &mut [SyncRequest]) -> Result<()> {
+        for request in requests {
+            request.target_table = self.render(
+                &request.mysql_database,
+                &request.mysql_table,
+                &request.target_database,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn invalid(&self, reason: &str) -> AppError {
+        AppError::Validation(format!(
+            "Invalid table name template {}: {}",
+            self.0, reason
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let template = TableNameTemplate::parse("ods_{table}").unwrap();
+        assert_eq!(
+            template.render("shop", "orders", "dw").unwrap(),
+            "ods_orders"
+        );
+
+        let template = TableNameTemplate::parse("{db}_{table}_v2").unwrap();
+        assert_eq!(
+            template.render("shop", "orders", "dw").unwrap(),
+            "shop_orders_v2"
+        );
+
+        assert!(TableNameTemplate::parse("ods_{name}").is_err());
+        assert!(TableNameTemplate::parse("ods_{table").is_err());
+        assert!(TableNameTemplate::parse("ods_table}").is_err());
+        assert!(TableNameTemplate::parse("ods.{table}").is_err());
+        assert!(TableNameTemplate::parse(" ").is_err());
+    }
+}
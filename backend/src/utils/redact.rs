@@ -0,0 +1,56 @@
+use crate::models::REDACTED_SECRET;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::borrow::Cow;
+
+/// `CREATE SECRET ... AS '...'` 中的密文
+static SECRET_VALUE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(\bAS\s+)'(?:[^']|'')*'").unwrap());
+
+/// WITH 子句中直接写出的密码和密钥，例如 `password = '...'`、`s3.secret.key = '...'`
+static SECRET_OPTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)([\w.]*(?:password|secret|access\.key|secret\.key|token)\s*=\s*)'(?:[^']|'')*'",
+    )
+    .unwrap()
+});
+
+/// 是否允许接口返回明文密码，通过环境变量 ALLOW_SECRET_REVEAL=true 开启
+pub fn allow_secret_reveal() -> bool {
+    std::env::var("ALLOW_SECRET_REVEAL")
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// 隐藏 SQL 中的密码和密钥，用于写日志前处理生成的 DDL
+pub fn scrub_sql(sql: &str) -> Cow<'_, str> {
+    let scrubbed = SECRET_VALUE.replace_all(sql, format!("${{1}}'{}'", REDACTED_SECRET));
+    match SECRET_OPTION.replace_all(&scrubbed, format!("${{1}}'{}'", REDACTED_SECRET)) {
+        Cow::Borrowed(_) => scrubbed,
+        Cow::Owned(s) => Cow::Owned(s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_sql() {
+        assert_eq!(
+            scrub_sql(
+                r#"CREATE SECRET IF NOT EXISTS "ods".mysql_pwd WITH ( backend = 'meta' ) AS 'p''a ss';"#
+            ),
+            r#"CREATE SECRET IF NOT EXISTS "ods".mysql_pwd WITH ( backend = 'meta' ) AS '******';"#
+        );
+        assert_eq!(
+            scrub_sql(
+                "CREATE SOURCE s WITH (connector = 'mysql-cdc', password = 'x''y', s3.secret.key = 'k') FORMAT PLAIN"
+            ),
+            "CREATE SOURCE s WITH (connector = 'mysql-cdc', password = '******', s3.secret.key = '******') FORMAT PLAIN"
+        );
+        // 引用 SECRET 的写法和普通语句不变
+        let sql = "CREATE SINK s AS SELECT * FROM t WITH (starrocks.password = secret \"ods\".starrocks_pwd)";
+        assert!(matches!(scrub_sql(sql), Cow::Borrowed(_)));
+    }
+}
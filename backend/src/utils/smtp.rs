@@ -0,0 +1,187 @@
+use crate::models::SmtpTls;
+use crate::utils::error::{AppError, Result};
+use base64::{Engine as _, engine::general_purpose};
+use openssl::ssl::{SslConnector, SslMethod};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// 一封纯文本邮件
+pub struct Mail<'a> {
+    pub from: &'a str,
+    pub to: &'a [String],
+    pub subject: &'a str,
+    pub body: &'a str,
+}
+
+/// SMTP 服务器
+pub struct SmtpServer<'a> {
+    pub host: &'a str,
+    pub port: u16,
+    pub tls: SmtpTls,
+    /// 设置后使用 AUTH LOGIN 认证
+    pub credentials: Option<(&'a str, &'a str)>,
+    pub timeout: Duration,
+}
+
+trait Stream: Read + Write + std::fmt::Debug {}
+impl<T: Read + Write + std::fmt::Debug> Stream for T {}
+
+/// 逐条发送命令并读取应答的 SMTP 会话
+struct Session {
+    reader: BufReader<Box<dyn Stream>>,
+}
+
+impl Session {
+    /// 读取一条（可能多行的）应答，返回应答码和文本
+    fn reply(&mut self) -> Result<(u16, String)> {
+        let mut text = String::new();
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line).map_err(smtp_error)? == 0 {
+                return Err(AppError::Connection(
+                    "SMTP server closed the connection".to_string(),
+                ));
+            }
+            text.push_str(&line);
+            // 多行应答的中间行为 `250-...`，最后一行为 `250 ...`
+            if line.len() < 4 || line.as_bytes()[3] != b'-' {
+                let code = line.get(..3).and_then(|c| c.parse().ok()).ok_or_else(|| {
+                    AppError::Connection(format!("Invalid SMTP reply: {}", line.trim_end()))
+                })?;
+                return Ok((code, text));
+            }
+        }
+    }
+
+    /// 发送一行命令，应答码不在 expected 中时返回错误
+    fn command(&mut self, line: &str, expected: &[u16]) -> Result<()> {
+        let stream = self.reader.get_mut();
+        stream
+            .write_all(format!("{}\r\n", line).as_bytes())
+            .map_err(smtp_error)?;
+        stream.flush().map_err(smtp_error)?;
+        self.expect(expected)
+    }
+
+    fn expect(&mut self, expected: &[u16]) -> Result<()> {
+        let (code, text) = self.reply()?;
+        if !expected.contains(&code) {
+            return Err(AppError::Connection(format!(
+                "Unexpected SMTP reply: {}",
+                text.trim_end()
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn smtp_error(e: impl std::fmt::Display) -> AppError {
+    AppError::Connection(format!("SMTP error: {}", e))
+}
+
+/// 发送邮件（阻塞调用，在异步上下文中应放到 spawn_blocking 中执行）
+pub fn send(server: &SmtpServer, mail: &Mail) -> Result<()> {
+    let addr = (server.host, server.port)
+        .to_socket_addrs()
+        .map_err(smtp_error)?
+        .next()
+        .ok_or_else(|| AppError::Connection(format!("Cannot resolve {}", server.host)))?;
+    let tcp = TcpStream::connect_timeout(&addr, server.timeout).map_err(smtp_error)?;
+    tcp.set_read_timeout(Some(server.timeout))
+        .map_err(smtp_error)?;
+    tcp.set_write_timeout(Some(server.timeout))
+        .map_err(smtp_error)?;
+
+    let connector = SslConnector::builder(SslMethod::tls())
+        .map_err(smtp_error)?
+        .build();
+    let stream: Box<dyn Stream> = match server.tls {
+        SmtpTls::Tls => Box::new(connector.connect(server.host, tcp).map_err(smtp_error)?),
+        SmtpTls::None | SmtpTls::StartTls => Box::new(tcp),
+    };
+    let mut session = Session {
+        reader: BufReader::new(stream),
+    };
+
+    session.expect(&[220])?;
+    session.command("EHLO rw-cdc-sr", &[250])?;
+    if server.tls == SmtpTls::StartTls {
+        session.command("STARTTLS", &[220])?;
+        let plain = session.reader.into_inner();
+        let tls = connector.connect(server.host, plain).map_err(smtp_error)?;
+        session = Session {
+            reader: BufReader::new(Box::new(tls)),
+        };
+        session.command("EHLO rw-cdc-sr", &[250])?;
+    }
+
+    if let Some((username, password)) = server.credentials {
+        session.command("AUTH LOGIN", &[334])?;
+        session.command(&general_purpose::STANDARD.encode(username), &[334])?;
+        session.command(&general_purpose::STANDARD.encode(password), &[235])?;
+    }
+
+    session.command(&format!("MAIL FROM:<{}>", mail.from), &[250])?;
+    for to in mail.to {
+        session.command(&format!("RCPT TO:<{}>", to), &[250, 251])?;
+    }
+    session.command("DATA", &[354])?;
+    session.command(&format!("{}\r\n.", build_message(mail)), &[250])?;
+    let _ = session.command("QUIT", &[221]);
+
+    Ok(())
+}
+
+/// 生成邮件内容：主题按 RFC 2047 编码，正文为 base64 编码的 UTF-8 文本
+/// （base64 行中不会出现以 `.` 开头的行，不需要点转义）
+fn build_message(mail: &Mail) -> String {
+    let body = general_purpose::STANDARD.encode(mail.body);
+    let body_lines: Vec<&str> = body
+        .as_bytes()
+        .chunks(76)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+
+    [
+        format!("From: {}", mail.from),
+        format!("To: {}", mail.to.join(", ")),
+        format!(
+            "Subject: =?UTF-8?B?{}?=",
+            general_purpose::STANDARD.encode(mail.subject)
+        ),
+        format!("Date: {}", chrono::Utc::now().to_rfc2822()),
+        "MIME-Version: 1.0".to_string(),
+        "Content-Type: text/plain; charset=UTF-8".to_string(),
+        "Content-Transfer-Encoding: base64".to_string(),
+        String::new(),
+        body_lines.join("\r\n"),
+    ]
+    .join("\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_message() {
+        let to = vec!["ops@example.com".to_string(), "dba@example.com".to_string()];
+        let body = "同步任务失败\n".repeat(10);
+        let message = build_message(&Mail {
+            from: "alert@example.com",
+            to: &to,
+            subject: "任务失败",
+            body: &body,
+        });
+
+        assert!(message.contains("To: ops@example.com, dba@example.com\r\n"));
+        assert!(message.contains("Subject: =?UTF-8?B?5Lu75Yqh5aSx6LSl?=\r\n"));
+        let (_, encoded) = message.split_once("\r\n\r\n").unwrap();
+        assert!(encoded.split("\r\n").all(|line| line.len() <= 76));
+        let decoded = general_purpose::STANDARD
+            .decode(encoded.replace("\r\n", ""))
+            .unwrap();
+        assert_eq!(String::from_utf8(decoded).unwrap(), body);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::utils::error::{AppError, Result};
+use sqlparser::ast::Statement;
+use sqlparser::dialect::{Dialect, MySqlDialect, PostgreSqlDialect};
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer, Whitespace};
+
+/// 用户输入的 SQL 片段所在的方言，StarRocks / Doris 使用 MySQL 语法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    RisingWave,
+    MySql,
+}
+
+impl SqlDialect {
+    fn parser_dialect(&self) -> Box<dyn Dialect> {
+        match self {
+            SqlDialect::RisingWave => Box::new(PostgreSqlDialect {}),
+            SqlDialect::MySql => Box::new(MySqlDialect {}),
+        }
+    }
+}
+
+/// 校验拼接进生成语句的表达式（例如行过滤条件）：必须是单个完整的表达式，不能带注释
+pub fn validate_expression(expr: &str, dialect: SqlDialect) -> Result<()> {
+    let invalid =
+        |reason: String| AppError::Validation(format!("Invalid expression {}: {}", expr, reason));
+    let parser_dialect = dialect.parser_dialect();
+    reject_comments(expr, parser_dialect.as_ref()).map_err(invalid)?;
+
+    let mut parser = Parser::new(parser_dialect.as_ref())
+        .try_with_sql(expr)
+        .map_err(|e| invalid(e.to_string()))?;
+    parser.parse_expr().map_err(|e| invalid(e.to_string()))?;
+    let rest = parser.peek_token().token;
+    if rest != Token::EOF {
+        return Err(invalid(format!("unexpected {}", rest)));
+    }
+    Ok(())
+}
+
+/// 校验用户提供的查询（例如物化视图定义）：必须是单条只读查询，不能包含 DDL / DML
+pub fn validate_query(sql: &str, dialect: SqlDialect) -> Result<()> {
+    let invalid = |reason: String| AppError::Validation(format!("Invalid query: {}", reason));
+    let statements = Parser::parse_sql(dialect.parser_dialect().as_ref(), sql)
+        .map_err(|e| invalid(e.to_string()))?;
+    match statements.as_slice() {
+        [Statement::Query(_)] => Ok(()),
+        [] => Err(invalid("empty statement".to_string())),
+        [_] => Err(invalid("only SELECT queries are allowed".to_string())),
+        _ => Err(invalid("only a single statement is allowed".to_string())),
+    }
+}
+
+/// 注释可能截断拼接后的语句，一律拒绝
+fn reject_comments(sql: &str, dialect: &dyn Dialect) -> std::result::Result<(), String> {
+    let tokens = Tokenizer::new(dialect, sql)
+        .tokenize()
+        .map_err(|e| e.to_string())?;
+    let has_comment = tokens.iter().any(|token| {
+        matches!(
+            token,
+            Token::Whitespace(
+                Whitespace::SingleLineComment { .. } | Whitespace::MultiLineComment(_)
+            )
+        )
+    });
+    if has_comment {
+        return Err("comments are not allowed".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_expression_and_query() {
+        for filter in [
+            "deleted_at IS NULL",
+            "status IN ('a', 'b;c') AND amount > 0",
+            "id IN (SELECT id FROM allow_list)",
+        ] {
+            assert!(
+                validate_expression(filter, SqlDialect::RisingWave).is_ok(),
+                "{}",
+                filter
+            );
+        }
+        assert!(validate_expression("`name` = 'x'", SqlDialect::MySql).is_ok());
+
+        for filter in [
+            "",
+            "1 = 1; DROP TABLE x",
+            "1 = 1) UNION SELECT * FROM secrets --",
+            "id > 0 -- trailing",
+            "id > 0 /* comment */",
+            "id >",
+            "DROP TABLE x",
+        ] {
+            assert!(
+                validate_expression(filter, SqlDialect::RisingWave).is_err(),
+                "{}",
+                filter
+            );
+        }
+
+        assert!(
+            validate_query(
+                "SELECT id, count(*) FROM t GROUP BY id",
+                SqlDialect::RisingWave
+            )
+            .is_ok()
+        );
+        assert!(
+            validate_query(
+                "WITH a AS (SELECT 1) SELECT * FROM a",
+                SqlDialect::RisingWave
+            )
+            .is_ok()
+        );
+        assert!(validate_query("DELETE FROM t", SqlDialect::RisingWave).is_err());
+        assert!(validate_query("SELECT 1; SELECT 2", SqlDialect::RisingWave).is_err());
+        assert!(validate_query("", SqlDialect::RisingWave).is_err());
+    }
+}
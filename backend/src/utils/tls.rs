@@ -0,0 +1,148 @@
+use crate::models::TlsOptions;
+use crate::utils::error::{AppError, Result};
+use openssl::pkcs12::Pkcs12;
+use openssl::pkey::PKey;
+use openssl::x509::X509;
+
+/// 去掉空白的证书字段，并校验证书和私钥能按 PEM 解析
+pub fn normalize(tls: TlsOptions) -> Result<TlsOptions> {
+    let blank_to_none = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+    let tls = TlsOptions {
+        ca_cert: blank_to_none(tls.ca_cert),
+        client_cert: blank_to_none(tls.client_cert),
+        client_key: blank_to_none(tls.client_key),
+        verify_hostname: tls.verify_hostname,
+    };
+
+    if let Some(ca) = &tls.ca_cert {
+        let certs = X509::stack_from_pem(ca.as_bytes()).map_err(|e| invalid("ca_cert", e))?;
+        if certs.is_empty() {
+            return Err(AppError::Validation(
+                "ca_cert does not contain any PEM certificate".to_string(),
+            ));
+        }
+    } else if tls.verify_hostname {
+        return Err(AppError::Validation(
+            "verify_hostname requires ca_cert".to_string(),
+        ));
+    }
+
+    match (&tls.client_cert, &tls.client_key) {
+        (Some(cert), Some(key)) => {
+            client_identity(cert, key)?;
+        }
+        (None, None) => {}
+        _ => {
+            return Err(AppError::Validation(
+                "client_cert and client_key must be configured together".to_string(),
+            ));
+        }
+    }
+
+    Ok(tls)
+}
+
+/// 把 PEM 格式的客户端证书和私钥打包为无口令的 PKCS#12
+/// mysql_async（native-tls）只接受 PKCS#12 格式的客户端身份
+pub fn client_identity(cert_pem: &str, key_pem: &str) -> Result<Vec<u8>> {
+    let cert = X509::from_pem(cert_pem.as_bytes()).map_err(|e| invalid("client_cert", e))?;
+    let key =
+        PKey::private_key_from_pem(key_pem.as_bytes()).map_err(|e| invalid("client_key", e))?;
+    if !cert.public_key().is_ok_and(|public| public.public_eq(&key)) {
+        return Err(AppError::Validation(
+            "client_key does not match client_cert".to_string(),
+        ));
+    }
+
+    Pkcs12::builder()
+        .name("client")
+        .pkey(&key)
+        .cert(&cert)
+        .build2("")
+        .and_then(|pkcs12| pkcs12.to_der())
+        .map_err(|e| AppError::Encryption(format!("Failed to build client identity: {}", e)))
+}
+
+fn invalid(field: &str, e: openssl::error::ErrorStack) -> AppError {
+    AppError::Validation(format!("Invalid PEM in {}: {}", field, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::asn1::Asn1Time;
+    use openssl::hash::MessageDigest;
+    use openssl::rsa::Rsa;
+    use openssl::x509::X509NameBuilder;
+
+    fn self_signed() -> (String, String) {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "rw-cdc-sr").unwrap();
+        let name = name.build();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+
+        let cert = String::from_utf8(builder.build().to_pem().unwrap()).unwrap();
+        let key = String::from_utf8(key.private_key_to_pem_pkcs8().unwrap()).unwrap();
+        (cert, key)
+    }
+
+    #[test]
+    fn test_normalize_tls_options() {
+        let (cert, key) = self_signed();
+
+        let tls = normalize(TlsOptions {
+            ca_cert: Some(cert.clone()),
+            client_cert: Some(cert.clone()),
+            client_key: Some(key.clone()),
+            verify_hostname: true,
+        })
+        .unwrap();
+        assert!(!client_identity(&cert, &key).unwrap().is_empty());
+        assert_eq!(tls.ca_cert.as_deref(), Some(cert.as_str()));
+
+        let blank = normalize(TlsOptions {
+            ca_cert: Some("  ".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(blank, TlsOptions::default());
+
+        assert!(
+            normalize(TlsOptions {
+                client_cert: Some(cert.clone()),
+                ..Default::default()
+            })
+            .is_err()
+        );
+        assert!(
+            normalize(TlsOptions {
+                verify_hostname: true,
+                ..Default::default()
+            })
+            .is_err()
+        );
+        assert!(
+            normalize(TlsOptions {
+                ca_cert: Some("not a certificate".to_string()),
+                ..Default::default()
+            })
+            .is_err()
+        );
+
+        let (_, other_key) = self_signed();
+        assert!(client_identity(&cert, &other_key).is_err());
+    }
+}
@@ -1,15 +1,53 @@
+use crate::models::ColumnTypeSource;
 use crate::utils::error::{AppError, Result};
 
+/// StarRocks DECIMAL 的最大精度，更大的 MySQL DECIMAL（最大 65）映射为 STRING
+const STARROCKS_MAX_DECIMAL_PRECISION: u32 = 38;
+
+/// 不带精度的 RisingWave numeric 对应的 StarRocks 类型
+/// RisingWave 的 numeric 最多 28 位有效数字，保留 10 位小数
+const RW_NUMERIC_STARROCKS_TYPE: &str = "DECIMAL(38,10)";
+
+/// StarRocks 列默认值，由 MySQL 默认值映射而来
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnDefault {
+    /// 常量，生成 DDL 时加引号
+    Literal(String),
+    /// DATETIME 列的 CURRENT_TIMESTAMP
+    CurrentTimestamp,
+    /// 字符串列的 (uuid())
+    Uuid,
+}
+
 /// MySQL 类型到 RisingWave (PostgreSQL) 类型的映射
 pub struct TypeMapper;
 
 impl TypeMapper {
+    /// 拆分 MySQL 列类型（COLUMN_TYPE）为大写的基础类型和括号内的参数
+    /// 忽略 UNSIGNED / ZEROFILL 等修饰，例如 `decimal(10,2) unsigned` -> ("DECIMAL", Some("10,2"))
+    fn split_mysql_type(mysql_type: &str) -> (String, Option<String>) {
+        let upper = mysql_type.trim().to_uppercase();
+        let (base, params) = match upper.split_once('(') {
+            Some((base, rest)) => (
+                base.trim().to_string(),
+                rest.split_once(')')
+                    .map(|(params, _)| params.replace(' ', "")),
+            ),
+            None => (upper.clone(), None),
+        };
+
+        let mut base = base.as_str();
+        for modifier in [" ZEROFILL", " UNSIGNED", " SIGNED"] {
+            base = base.strip_suffix(modifier).unwrap_or(base).trim_end();
+        }
+        (base.to_string(), params)
+    }
+
     /// 将 MySQL 类型映射到 RisingWave (PostgreSQL) 类型
     pub fn mysql_to_risingwave(mysql_type: &str) -> Result<String> {
-        let mysql_type_upper = mysql_type.to_uppercase();
-        let base_type = mysql_type_upper.split('(').next().unwrap_or(&mysql_type_upper);
+        let (base_type, params) = Self::split_mysql_type(mysql_type);
 
-        let rw_type = match base_type {
+        let rw_type = match base_type.as_str() {
             // 整数类型
             "TINYINT" => "TINYINT",
             "SMALLINT" => "SMALLINT",
@@ -22,24 +60,18 @@ impl TypeMapper {
             "DOUBLE" | "DOUBLE PRECISION" => "DOUBLE PRECISION",
             "DECIMAL" | "NUMERIC" => {
                 // 保留精度和小数位数
-                if mysql_type.contains('(') {
-                    return Ok(mysql_type.to_uppercase().replace("DECIMAL", "DECIMAL"));
+                if let Some(params) = params {
+                    return Ok(format!("{}({})", base_type, params));
                 }
                 "DECIMAL"
             }
 
             // 字符串类型
-            "CHAR" => {
-                if mysql_type.contains('(') {
-                    return Ok(mysql_type.to_uppercase());
+            "CHAR" | "VARCHAR" => {
+                if let Some(params) = params {
+                    return Ok(format!("{}({})", base_type, params));
                 }
-                "CHAR"
-            }
-            "VARCHAR" => {
-                if mysql_type.contains('(') {
-                    return Ok(mysql_type.to_uppercase());
-                }
-                "VARCHAR"
+                return Ok(base_type);
             }
             "TEXT" | "TINYTEXT" | "MEDIUMTEXT" | "LONGTEXT" => "TEXT",
 
@@ -55,6 +87,10 @@ impl TypeMapper {
             // JSON 类型
             "JSON" => "JSONB",
 
+            // 空间类型，CDC 中为 MySQL 内部格式（4 字节 SRID + WKB）
+            "GEOMETRY" | "POINT" | "LINESTRING" | "POLYGON" | "MULTIPOINT" | "MULTILINESTRING"
+            | "MULTIPOLYGON" | "GEOMETRYCOLLECTION" | "GEOMCOLLECTION" => "BYTEA",
+
             // 其他类型
             "BOOLEAN" | "BOOL" => "BOOLEAN",
             "BIT" => "BOOLEAN",
@@ -72,55 +108,83 @@ impl TypeMapper {
         Ok(rw_type.to_string())
     }
 
-    /// 将 RisingWave (PostgreSQL) 类型映射到 StarRocks 类型
+    /// 按列类型的来源映射到 StarRocks 类型
+    pub fn to_starrocks(data_type: &str, source: ColumnTypeSource) -> Result<String> {
+        match source {
+            ColumnTypeSource::MySQL => Self::mysql_to_starrocks(data_type),
+            ColumnTypeSource::RisingWave => Self::risingwave_to_starrocks(data_type),
+        }
+    }
+
+    /// 将 RisingWave 类型映射到 StarRocks 类型
+    /// 支持 rw_columns.data_type 中的目录类型名（例如 `character varying`、`timestamp with time zone`）
+    /// 和常用别名，数组、STRUCT 和 MAP 映射为 JSON
     pub fn risingwave_to_starrocks(rw_type: &str) -> Result<String> {
-        let rw_type_upper = rw_type.to_uppercase();
-        let base_type = rw_type_upper.split('(').next().unwrap_or(&rw_type_upper);
+        let lower = rw_type.trim().to_lowercase();
+        if lower.ends_with("[]") || lower.starts_with("struct<") || lower.starts_with("map(") {
+            return Ok("JSON".to_string());
+        }
+        let (base_type, params) = match lower.split_once('(') {
+            Some((base, rest)) => (
+                base.trim(),
+                rest.split_once(')').map(|(params, _)| params.replace(' ', "")),
+            ),
+            None => (lower.as_str(), None),
+        };
 
         let sr_type = match base_type {
             // 整数类型
-            "SMALLINT" => "SMALLINT",
-            "INTEGER" | "INT" => "INT",
-            "BIGINT" => "BIGINT",
+            "smallint" | "int2" => "SMALLINT",
+            "integer" | "int" | "int4" => "INT",
+            "bigint" | "int8" | "serial" => "BIGINT",
+            // 超出 LARGEINT 的范围
+            "rw_int256" | "int256" => "STRING",
 
             // 浮点类型
-            "REAL" | "FLOAT4" => "FLOAT",
-            "DOUBLE PRECISION" | "FLOAT8" => "DOUBLE",
-            "DECIMAL" | "NUMERIC" => {
-                if rw_type.contains('(') {
-                    return Ok(rw_type.to_uppercase());
-                }
-                "DECIMAL"
+            "real" | "float4" => "FLOAT",
+            "double precision" | "double" | "float8" | "float" => "DOUBLE",
+            "numeric" | "decimal" => {
+                return Ok(match params {
+                    Some(params) => {
+                        let precision = params.split(',').next().and_then(|p| p.parse::<u32>().ok());
+                        if precision.is_some_and(|p| p > STARROCKS_MAX_DECIMAL_PRECISION) {
+                            "STRING".to_string()
+                        } else {
+                            format!("DECIMAL({})", params)
+                        }
+                    }
+                    // RisingWave 的 numeric 不带精度，StarRocks 的 DECIMAL 默认为 DECIMAL(10,0) 会截断小数
+                    None => RW_NUMERIC_STARROCKS_TYPE.to_string(),
+                });
             }
 
-            // 字符串类型
-            "CHAR" => {
-                if rw_type.contains('(') {
-                    return Ok(rw_type.to_uppercase());
-                }
-                "CHAR"
+            // 字符串类型，StarRocks 的 VARCHAR 不带长度时为 VARCHAR(1)
+            "character varying" | "varchar" => {
+                return Ok(match params {
+                    Some(params) => format!("VARCHAR({})", params),
+                    None => "STRING".to_string(),
+                });
             }
-            "VARCHAR" => {
-                if rw_type.contains('(') {
-                    return Ok(rw_type.to_uppercase());
-                }
-                "VARCHAR"
+            "character" | "char" => {
+                return Ok(format!("CHAR({})", params.as_deref().unwrap_or("1")));
             }
-            "TEXT" => "STRING",
+            "text" | "string" => "STRING",
 
             // 二进制类型
-            "BYTEA" => "VARBINARY",
+            "bytea" => "VARBINARY",
 
-            // 日期时间类型
-            "DATE" => "DATE",
-            "TIME" => "TIME",
-            "TIMESTAMP" | "TIMESTAMPTZ" => "DATETIME",
+            // 日期时间类型，StarRocks 没有 TIME 列类型和时区类型
+            "date" => "DATE",
+            "time" | "time without time zone" => "STRING",
+            "timestamp" | "timestamp without time zone" => "DATETIME",
+            "timestamptz" | "timestamp with time zone" => "DATETIME",
+            "interval" => "STRING",
 
             // JSON 类型
-            "JSON" | "JSONB" => "JSON",
+            "json" | "jsonb" => "JSON",
 
             // 布尔类型
-            "BOOLEAN" | "BOOL" => "BOOLEAN",
+            "boolean" | "bool" => "BOOLEAN",
 
             _ => {
                 return Err(AppError::TypeMapping(format!(
@@ -135,10 +199,9 @@ impl TypeMapper {
 
     /// 直接从 MySQL 类型映射到 StarRocks 类型
     pub fn mysql_to_starrocks(mysql_type: &str) -> Result<String> {
-        let mysql_type_upper = mysql_type.to_uppercase();
-        let base_type = mysql_type_upper.split('(').next().unwrap_or(&mysql_type_upper);
+        let (base_type, params) = Self::split_mysql_type(mysql_type);
 
-        let sr_type = match base_type {
+        let sr_type = match base_type.as_str() {
             // 整数类型 - 为了与 RisingWave 兼容，TINYINT 也映射为 SMALLINT
             "TINYINT" => "TINYINT",
             "SMALLINT" => "SMALLINT",
@@ -150,17 +213,21 @@ impl TypeMapper {
             "FLOAT" => "FLOAT",
             "DOUBLE" | "DOUBLE PRECISION" => "DOUBLE",
             "DECIMAL" | "NUMERIC" => {
-                // 保留精度和小数位数
-                if mysql_type.contains('(') {
-                    return Ok(mysql_type.to_uppercase());
+                // 保留精度和小数位数，超出 StarRocks 精度上限时使用 STRING 避免建表失败
+                if let Some(params) = params {
+                    let precision = params.split(',').next().and_then(|p| p.parse::<u32>().ok());
+                    if precision.is_some_and(|p| p > STARROCKS_MAX_DECIMAL_PRECISION) {
+                        return Ok("STRING".to_string());
+                    }
+                    return Ok(format!("{}({})", base_type, params));
                 }
                 "DECIMAL"
             }
 
             // 字符串类型
             "CHAR" | "VARCHAR" => {
-                if mysql_type.contains('(') {
-                    return Ok(mysql_type.to_uppercase());
+                if let Some(params) = params {
+                    return Ok(format!("{}({})", base_type, params));
                 }
                 "VARCHAR"
             }
@@ -178,6 +245,10 @@ impl TypeMapper {
             // JSON 类型
             "JSON" => "JSON",
 
+            // 空间类型，默认以十六进制 WKB 写入（见 SpatialColumnMode）
+            "GEOMETRY" | "POINT" | "LINESTRING" | "POLYGON" | "MULTIPOINT" | "MULTILINESTRING"
+            | "MULTIPOLYGON" | "GEOMETRYCOLLECTION" | "GEOMCOLLECTION" => "STRING",
+
             // 其他类型
             "BOOLEAN" | "BOOL" => "BOOLEAN",
             "BIT" => "BOOLEAN",
@@ -194,6 +265,87 @@ impl TypeMapper {
 
         Ok(sr_type.to_string())
     }
+
+    /// 将 MySQL 默认值映射为 StarRocks 列的默认值，StarRocks 不支持时返回 None
+    /// `mysql_default` 为 SHOW CREATE TABLE 中的写法（见 MetadataService::default_expression），
+    /// 例如 `'abc'`、`b'1'`、`CURRENT_TIMESTAMP(3)`、`uuid()`；`sr_type` 为映射后的 StarRocks 类型
+    pub fn mysql_default_to_starrocks(mysql_default: &str, sr_type: &str) -> Option<ColumnDefault> {
+        let sr_base = sr_type
+            .split('(')
+            .next()
+            .unwrap_or(sr_type)
+            .trim()
+            .to_uppercase();
+        // JSON、二进制和复杂类型不支持默认值
+        if matches!(sr_base.as_str(), "JSON" | "VARBINARY" | "BITMAP" | "HLL")
+            || sr_base.starts_with("ARRAY")
+            || sr_base.starts_with("MAP")
+            || sr_base.starts_with("STRUCT")
+        {
+            return None;
+        }
+        let is_datetime = matches!(sr_base.as_str(), "DATE" | "DATETIME");
+        let is_string = matches!(sr_base.as_str(), "CHAR" | "VARCHAR" | "STRING");
+
+        let trimmed = mysql_default.trim();
+        if trimmed.eq_ignore_ascii_case("NULL") {
+            return None;
+        }
+
+        // 字符串字面量
+        if let Some(inner) = trimmed
+            .strip_prefix('\'')
+            .and_then(|rest| rest.strip_suffix('\''))
+        {
+            let value = inner.replace("''", "'").replace("\\\\", "\\");
+            // StarRocks 不接受 MySQL 的零日期
+            if is_datetime && value.starts_with("0000-00-00") {
+                return None;
+            }
+            return Some(ColumnDefault::Literal(value));
+        }
+
+        // BIT 字面量，例如 b'1'
+        if let Some(bits) = trimmed
+            .strip_prefix("b'")
+            .and_then(|rest| rest.strip_suffix('\''))
+        {
+            return u64::from_str_radix(bits, 2)
+                .ok()
+                .map(|value| ColumnDefault::Literal(value.to_string()));
+        }
+
+        // 未加引号的数字（表达式默认值或 MariaDB 的写法）
+        if !is_datetime && trimmed.parse::<f64>().is_ok() {
+            return Some(ColumnDefault::Literal(trimmed.to_string()));
+        }
+
+        // 表达式默认值，去掉外层括号后按函数名匹配
+        let expression = trimmed
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .unwrap_or(trimmed)
+            .trim()
+            .to_lowercase();
+        let function = expression.split('(').next().unwrap_or("").trim();
+        match function {
+            "current_timestamp" | "now" | "localtimestamp" | "localtime" if sr_base == "DATETIME" => {
+                Some(ColumnDefault::CurrentTimestamp)
+            }
+            "uuid" if is_string && expression == "uuid()" => Some(ColumnDefault::Uuid),
+            _ => None,
+        }
+    }
+
+    /// 直接从 MySQL 类型映射到 Doris 类型
+    /// 与 StarRocks 基本一致，Doris 没有 TIME 和 VARBINARY 列类型，使用 STRING 存储
+    pub fn mysql_to_doris(mysql_type: &str) -> Result<String> {
+        let doris_type = Self::mysql_to_starrocks(mysql_type)?;
+        match doris_type.as_str() {
+            "TIME" | "VARBINARY" => Ok("STRING".to_string()),
+            _ => Ok(doris_type),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -218,6 +370,10 @@ mod tests {
             TypeMapper::mysql_to_risingwave("JSON").unwrap(),
             "JSONB"
         );
+        assert_eq!(
+            TypeMapper::mysql_to_risingwave("point").unwrap(),
+            "BYTEA"
+        );
     }
 
     #[test]
@@ -238,6 +394,42 @@ mod tests {
             TypeMapper::risingwave_to_starrocks("TEXT").unwrap(),
             "STRING"
         );
+        // rw_columns 中的目录类型名
+        assert_eq!(
+            TypeMapper::risingwave_to_starrocks("character varying").unwrap(),
+            "STRING"
+        );
+        assert_eq!(
+            TypeMapper::risingwave_to_starrocks("numeric").unwrap(),
+            "DECIMAL(38,10)"
+        );
+        assert_eq!(
+            TypeMapper::risingwave_to_starrocks("numeric(12, 2)").unwrap(),
+            "DECIMAL(12,2)"
+        );
+        assert_eq!(
+            TypeMapper::risingwave_to_starrocks("timestamp with time zone").unwrap(),
+            "DATETIME"
+        );
+        assert_eq!(
+            TypeMapper::risingwave_to_starrocks("double precision").unwrap(),
+            "DOUBLE"
+        );
+        assert_eq!(
+            TypeMapper::risingwave_to_starrocks("integer[]").unwrap(),
+            "JSON"
+        );
+        assert_eq!(
+            TypeMapper::risingwave_to_starrocks("struct<a integer, b character varying>").unwrap(),
+            "JSON"
+        );
+        assert!(TypeMapper::risingwave_to_starrocks("geometry").is_err());
+
+        assert_eq!(
+            TypeMapper::to_starrocks("text", ColumnTypeSource::MySQL).unwrap(),
+            "STRING"
+        );
+        assert!(TypeMapper::to_starrocks("character varying", ColumnTypeSource::MySQL).is_err());
     }
 
     #[test]
@@ -262,6 +454,63 @@ mod tests {
             TypeMapper::mysql_to_starrocks("BIGINT").unwrap(),
             "BIGINT"
         );
+        // COLUMN_TYPE 中的修饰和超出上限的精度
+        assert_eq!(
+            TypeMapper::mysql_to_starrocks("bigint unsigned zerofill").unwrap(),
+            "BIGINT"
+        );
+        assert_eq!(
+            TypeMapper::mysql_to_starrocks("decimal(10, 2) unsigned").unwrap(),
+            "DECIMAL(10,2)"
+        );
+        assert_eq!(
+            TypeMapper::mysql_to_starrocks("decimal(65,30)").unwrap(),
+            "STRING"
+        );
+        // 空间类型
+        assert_eq!(
+            TypeMapper::mysql_to_starrocks("geometry").unwrap(),
+            "STRING"
+        );
+        assert_eq!(
+            TypeMapper::mysql_to_starrocks("multipolygon").unwrap(),
+            "STRING"
+        );
+    }
+
+    #[test]
+    fn test_mysql_default_to_starrocks() {
+        let literal = |v: &str| Some(ColumnDefault::Literal(v.to_string()));
+        assert_eq!(TypeMapper::mysql_default_to_starrocks("'0'", "INT"), literal("0"));
+        assert_eq!(
+            TypeMapper::mysql_default_to_starrocks("'it''s'", "VARCHAR(20)"),
+            literal("it's")
+        );
+        assert_eq!(TypeMapper::mysql_default_to_starrocks("''", "STRING"), literal(""));
+        assert_eq!(TypeMapper::mysql_default_to_starrocks("b'1'", "BOOLEAN"), literal("1"));
+        assert_eq!(TypeMapper::mysql_default_to_starrocks("1.5", "DOUBLE"), literal("1.5"));
+        assert_eq!(
+            TypeMapper::mysql_default_to_starrocks("CURRENT_TIMESTAMP(3)", "DATETIME"),
+            Some(ColumnDefault::CurrentTimestamp)
+        );
+        assert_eq!(
+            TypeMapper::mysql_default_to_starrocks("now()", "DATETIME"),
+            Some(ColumnDefault::CurrentTimestamp)
+        );
+        assert_eq!(
+            TypeMapper::mysql_default_to_starrocks("(uuid())", "VARCHAR(36)"),
+            Some(ColumnDefault::Uuid)
+        );
+
+        // 不支持的组合
+        assert_eq!(TypeMapper::mysql_default_to_starrocks("NULL", "INT"), None);
+        assert_eq!(TypeMapper::mysql_default_to_starrocks("CURRENT_TIMESTAMP", "DATE"), None);
+        assert_eq!(
+            TypeMapper::mysql_default_to_starrocks("'0000-00-00 00:00:00'", "DATETIME"),
+            None
+        );
+        assert_eq!(TypeMapper::mysql_default_to_starrocks("'{}'", "JSON"), None);
+        assert_eq!(TypeMapper::mysql_default_to_starrocks("(rand() * 10)", "DOUBLE"), None);
     }
 
     #[test]
@@ -0,0 +1,87 @@
+import React, { useState } from 'react';
+import { AutoComplete, Input, Tag, message } from 'antd';
+import { useNavigate } from 'react-router-dom';
+import type { SearchResult, SearchResultKind } from '../types';
+import * as api from '../services/api';
+
+const KIND_LABELS: Record<SearchResultKind, string> = {
+  connection: '连接',
+  mapping: '同步映射',
+  task: '任务',
+  rw_object: 'RisingWave',
+  sr_table: 'StarRocks',
+};
+
+// 选中结果后跳转的页面
+const KIND_PATHS: Record<SearchResultKind, string> = {
+  connection: '/connections',
+  mapping: '/tasks',
+  task: '/tasks',
+  rw_object: '/risingwave',
+  sr_table: '/starrocks',
+};
+
+const GlobalSearch: React.FC = () => {
+  const navigate = useNavigate();
+  const [results, setResults] = useState<SearchResult[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [text, setText] = useState('');
+
+  const handleSearch = async (value: string) => {
+    const q = value.trim();
+    if (!q) {
+      setResults([]);
+      return;
+    }
+    setLoading(true);
+    try {
+      const response = await api.search(q, 10);
+      setResults(response.results);
+      if (response.errors.length > 0) {
+        message.warning(`部分集群搜索失败: ${response.errors.join('; ')}`);
+      }
+    } catch (error) {
+      message.error('搜索失败: ' + error);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const options = (Object.keys(KIND_LABELS) as SearchResultKind[])
+    .map((kind) => ({
+      label: KIND_LABELS[kind],
+      options: results
+        .map((result, index) => ({ result, index }))
+        .filter(({ result }) => result.kind === kind)
+        .map(({ result, index }) => ({
+          value: String(index),
+          label: (
+            <span>
+              {result.schema && kind !== 'mapping' ? `${result.schema}.` : ''}
+              {result.name}
+              {result.detail && <Tag style={{ marginLeft: 8 }}>{result.detail}</Tag>}
+            </span>
+          ),
+        })),
+    }))
+    .filter((group) => group.options.length > 0);
+
+  return (
+    <AutoComplete
+      options={options}
+      value={text}
+      onChange={setText}
+      onSelect={(value: string) => {
+        const result = results[Number(value)];
+        setText('');
+        if (result) navigate(KIND_PATHS[result.kind]);
+      }}
+      popupMatchSelectWidth={480}
+      style={{ width: 280, marginLeft: 32 }}
+    >
+      <Input.Search placeholder="搜索连接、任务、表..." loading={loading} onSearch={handleSearch} allowClear />
+    </AutoComplete>
+  );
+};
+
+export default GlobalSearch;
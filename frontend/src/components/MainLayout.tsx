@@ -1,13 +1,17 @@
 import React from 'react';
-import { Layout, Menu } from 'antd';
-import { DatabaseOutlined, TableOutlined, HistoryOutlined, ClusterOutlined } from '@ant-design/icons';
+import { Layout, Menu, Space, Button } from 'antd';
+import { DatabaseOutlined, TableOutlined, HistoryOutlined, ClusterOutlined, AppstoreOutlined, BellOutlined, BranchesOutlined, AlertOutlined, UserOutlined, LogoutOutlined, AuditOutlined, ClockCircleOutlined, SaveOutlined } from '@ant-design/icons';
 import { Outlet, useNavigate, useLocation } from 'react-router-dom';
+import * as api from '../services/api';
+import GlobalSearch from './GlobalSearch';
 
 const { Header, Content, Sider } = Layout;
 
 const MainLayout: React.FC = () => {
   const navigate = useNavigate();
   const location = useLocation();
+  const username = localStorage.getItem('rw_cdc_sr_user');
+  const role = api.getRole();
 
   const menuItems = [
     {
@@ -25,11 +29,55 @@ const MainLayout: React.FC = () => {
       icon: <HistoryOutlined />,
       label: '任务管理',
     },
+    {
+      key: '/schedules',
+      icon: <ClockCircleOutlined />,
+      label: '定时同步',
+    },
+    {
+      key: '/mappings',
+      icon: <SaveOutlined />,
+      label: '同步映射',
+    },
     {
       key: '/risingwave',
       icon: <ClusterOutlined />,
       label: 'RisingWave 对象',
     },
+    {
+      key: '/starrocks',
+      icon: <AppstoreOutlined />,
+      label: 'StarRocks 目录',
+    },
+    {
+      key: '/notifications',
+      icon: <BellOutlined />,
+      label: '通知渠道',
+    },
+    {
+      key: '/alerts',
+      icon: <AlertOutlined />,
+      label: '告警',
+    },
+    {
+      key: '/alert-routing',
+      icon: <BranchesOutlined />,
+      label: '告警路由',
+    },
+    ...(role === 'admin' || !api.getToken()
+      ? [
+          {
+            key: '/audit',
+            icon: <AuditOutlined />,
+            label: '审计日志',
+          },
+        ]
+      : []),
+    {
+      key: '/account',
+      icon: <UserOutlined />,
+      label: '账号与 Token',
+    },
   ];
 
   return (
@@ -38,6 +86,20 @@ const MainLayout: React.FC = () => {
         <div style={{ color: 'white', fontSize: '20px', fontWeight: 'bold' }}>
           RisingWave CDC → StarRocks
         </div>
+        <GlobalSearch />
+        <Space style={{ marginLeft: 'auto', color: 'white' }}>
+          {username && (
+            <span>
+              {username}
+              {role && `（${api.ROLE_LABELS[role]}）`}
+            </span>
+          )}
+          {api.getToken() && (
+            <Button type="text" icon={<LogoutOutlined />} style={{ color: 'white' }} onClick={api.logout}>
+              退出
+            </Button>
+          )}
+        </Space>
       </Header>
       <Layout>
         <Sider width={200} theme="light">
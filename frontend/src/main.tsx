@@ -7,7 +7,16 @@ import MainLayout from './components/MainLayout';
 import ConnectionConfig from './pages/ConnectionConfig';
 import TableSelection from './pages/TableSelection';
 import TaskManagement from './pages/TaskManagement';
+import Schedules from './pages/Schedules';
+import SyncMappings from './pages/SyncMappings';
 import RisingWaveManager from './pages/RisingWaveManager';
+import StarRocksBrowser from './pages/StarRocksBrowser';
+import NotificationChannels from './pages/NotificationChannels';
+import AlertRouting from './pages/AlertRouting';
+import Alerts from './pages/Alerts';
+import AuditLogs from './pages/AuditLogs';
+import Account from './pages/Account';
+import Login from './pages/Login';
 import './styles/global.css';
 
 const App: React.FC = () => {
@@ -15,12 +24,21 @@ const App: React.FC = () => {
     <ConfigProvider locale={zhCN}>
       <BrowserRouter>
         <Routes>
+          <Route path="/login" element={<Login />} />
           <Route path="/" element={<MainLayout />}>
             <Route index element={<Navigate to="/connections" replace />} />
             <Route path="connections" element={<ConnectionConfig />} />
             <Route path="sync" element={<TableSelection />} />
             <Route path="tasks" element={<TaskManagement />} />
+            <Route path="schedules" element={<Schedules />} />
+            <Route path="mappings" element={<SyncMappings />} />
             <Route path="risingwave" element={<RisingWaveManager />} />
+            <Route path="starrocks" element={<StarRocksBrowser />} />
+            <Route path="notifications" element={<NotificationChannels />} />
+            <Route path="alerts" element={<Alerts />} />
+            <Route path="alert-routing" element={<AlertRouting />} />
+            <Route path="audit" element={<AuditLogs />} />
+            <Route path="account" element={<Account />} />
           </Route>
         </Routes>
       </BrowserRouter>
@@ -0,0 +1,300 @@
+import React, { useState, useEffect } from 'react';
+import {
+  Card,
+  Button,
+  Table,
+  Modal,
+  Form,
+  Input,
+  InputNumber,
+  message,
+  Space,
+  Popconfirm,
+  Typography,
+  Select,
+  Tag,
+} from 'antd';
+import { PlusOutlined, DeleteOutlined } from '@ant-design/icons';
+import type { ColumnsType } from 'antd/es/table';
+import type { ApiToken, Role, User } from '../types';
+import * as api from '../services/api';
+
+const formatTime = (time?: string | null) => (time ? new Date(time).toLocaleString() : '-');
+
+const roleOptions = (Object.keys(api.ROLE_LABELS) as Role[]).map((role) => ({
+  value: role,
+  label: api.ROLE_LABELS[role],
+}));
+
+const Account: React.FC = () => {
+  const [tokens, setTokens] = useState<ApiToken[]>([]);
+  const [users, setUsers] = useState<User[]>([]);
+  const [me, setMe] = useState<User | null>(null);
+  const [loading, setLoading] = useState(false);
+  const [tokenModalVisible, setTokenModalVisible] = useState(false);
+  const [userModalVisible, setUserModalVisible] = useState(false);
+  const [createdToken, setCreatedToken] = useState<string | null>(null);
+  const [tokenForm] = Form.useForm();
+  const [userForm] = Form.useForm();
+  const [passwordForm] = Form.useForm();
+
+  const loadData = async () => {
+    setLoading(true);
+    try {
+      const [current, tokenList] = await Promise.all([
+        api.getCurrentUser(),
+        api.listApiTokens(),
+      ]);
+      setMe(current);
+      setTokens(tokenList);
+      // 只有管理员可以管理用户
+      setUsers(current.role === 'admin' ? await api.listUsers() : []);
+    } catch (error) {
+      message.error('加载账号信息失败: ' + error);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    loadData();
+  }, []);
+
+  const handleChangePassword = async (values: any) => {
+    if (values.new_password !== values.confirm_password) {
+      message.error('两次输入的新密码不一致');
+      return;
+    }
+    try {
+      await api.changePassword(values.old_password, values.new_password);
+      message.success('密码已修改');
+      passwordForm.resetFields();
+    } catch (error) {
+      message.error('修改密码失败: ' + error);
+    }
+  };
+
+  const handleCreateToken = async () => {
+    try {
+      const values = await tokenForm.validateFields();
+      const created = await api.createApiToken(values.name, values.expires_in_days || undefined);
+      setTokenModalVisible(false);
+      tokenForm.resetFields();
+      setCreatedToken(created.token);
+      loadData();
+    } catch (error) {
+      if (error instanceof Error) {
+        message.error('创建失败: ' + error.message);
+      }
+    }
+  };
+
+  const handleCreateUser = async () => {
+    try {
+      const values = await userForm.validateFields();
+      await api.createUser(values.username, values.password, values.role);
+      message.success('用户已创建');
+      setUserModalVisible(false);
+      userForm.resetFields();
+      loadData();
+    } catch (error) {
+      if (error instanceof Error) {
+        message.error('创建失败: ' + error.message);
+      }
+    }
+  };
+
+  const handleDeleteToken = async (id: number) => {
+    try {
+      await api.deleteApiToken(id);
+      message.success('Token 已吊销');
+      loadData();
+    } catch (error) {
+      message.error('吊销失败: ' + error);
+    }
+  };
+
+  const handleUpdateRole = async (id: number, role: Role) => {
+    try {
+      await api.updateUserRole(id, role);
+      message.success('角色已修改');
+      loadData();
+    } catch (error) {
+      message.error('修改角色失败: ' + error);
+    }
+  };
+
+  const handleDeleteUser = async (id: number) => {
+    try {
+      await api.deleteUser(id);
+      message.success('用户已删除');
+      loadData();
+    } catch (error) {
+      message.error('删除失败: ' + error);
+    }
+  };
+
+  const tokenColumns: ColumnsType<ApiToken> = [
+    { title: '名称', dataIndex: 'name', key: 'name' },
+    {
+      title: 'Token',
+      dataIndex: 'token_prefix',
+      key: 'token_prefix',
+      render: (prefix: string) => <Typography.Text code>{prefix}…</Typography.Text>,
+    },
+    { title: '过期时间', dataIndex: 'expires_at', key: 'expires_at', render: (t) => t ? formatTime(t) : '永不过期' },
+    { title: '最后使用', dataIndex: 'last_used_at', key: 'last_used_at', render: formatTime },
+    { title: '创建时间', dataIndex: 'created_at', key: 'created_at', render: formatTime },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_, record) => (
+        <Popconfirm title="确定吊销这个 Token 吗？" onConfirm={() => handleDeleteToken(record.id)}>
+          <Button type="link" danger icon={<DeleteOutlined />}>
+            吊销
+          </Button>
+        </Popconfirm>
+      ),
+    },
+  ];
+
+  const userColumns: ColumnsType<User> = [
+    { title: '用户名', dataIndex: 'username', key: 'username' },
+    {
+      title: '角色',
+      dataIndex: 'role',
+      key: 'role',
+      render: (role: Role, record) =>
+        record.id === me?.id ? (
+          <Tag>{api.ROLE_LABELS[role]}</Tag>
+        ) : (
+          <Select
+            value={role}
+            style={{ width: 120 }}
+            onChange={(value: Role) => handleUpdateRole(record.id, value)}
+            options={roleOptions}
+          />
+        ),
+    },
+    { title: '最后登录', dataIndex: 'last_login_at', key: 'last_login_at', render: formatTime },
+    { title: '创建时间', dataIndex: 'created_at', key: 'created_at', render: formatTime },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_, record) =>
+        record.id !== me?.id && (
+          <Popconfirm title="确定删除这个用户吗？" onConfirm={() => handleDeleteUser(record.id)}>
+            <Button type="link" danger icon={<DeleteOutlined />}>
+              删除
+            </Button>
+          </Popconfirm>
+        ),
+    },
+  ];
+
+  return (
+    <Space direction="vertical" size="large" style={{ width: '100%' }}>
+      <Card
+        title={`修改密码${me ? `（${me.username} · ${api.ROLE_LABELS[me.role]}）` : ''}`}
+      >
+        <Form form={passwordForm} layout="inline" onFinish={handleChangePassword}>
+          <Form.Item name="old_password" rules={[{ required: true, message: '请输入当前密码' }]}>
+            <Input.Password placeholder="当前密码" autoComplete="current-password" />
+          </Form.Item>
+          <Form.Item
+            name="new_password"
+            rules={[{ required: true, min: 8, message: '新密码至少 8 位，包含小写字母、大写字母、数字、符号中的至少 3 类' }]}
+          >
+            <Input.Password placeholder="新密码" autoComplete="new-password" />
+          </Form.Item>
+          <Form.Item name="confirm_password" rules={[{ required: true, message: '请确认新密码' }]}>
+            <Input.Password placeholder="确认新密码" autoComplete="new-password" />
+          </Form.Item>
+          <Form.Item>
+            <Button type="primary" htmlType="submit">
+              修改
+            </Button>
+          </Form.Item>
+        </Form>
+      </Card>
+
+      <Card
+        title="API Token"
+        extra={
+          <Button type="primary" icon={<PlusOutlined />} onClick={() => setTokenModalVisible(true)}>
+            新建 Token
+          </Button>
+        }
+      >
+        <Table columns={tokenColumns} dataSource={tokens} rowKey="id" loading={loading} pagination={false} />
+      </Card>
+
+      {me?.role === 'admin' && (
+        <Card
+          title="用户"
+          extra={
+            <Button icon={<PlusOutlined />} onClick={() => setUserModalVisible(true)}>
+              新建用户
+            </Button>
+          }
+        >
+          <Table columns={userColumns} dataSource={users} rowKey="id" loading={loading} pagination={false} />
+        </Card>
+      )}
+
+      <Modal
+        title="新建 API Token"
+        open={tokenModalVisible}
+        onOk={handleCreateToken}
+        onCancel={() => setTokenModalVisible(false)}
+      >
+        <Form form={tokenForm} layout="vertical">
+          <Form.Item name="name" label="名称" rules={[{ required: true, message: '请输入名称' }]}>
+            <Input placeholder="例如 alertmanager" />
+          </Form.Item>
+          <Form.Item name="expires_in_days" label="有效天数" extra="留空表示永不过期">
+            <InputNumber min={1} style={{ width: '100%' }} />
+          </Form.Item>
+        </Form>
+      </Modal>
+
+      <Modal
+        title="Token 已创建"
+        open={!!createdToken}
+        onOk={() => setCreatedToken(null)}
+        onCancel={() => setCreatedToken(null)}
+        cancelButtonProps={{ style: { display: 'none' } }}
+      >
+        <p>请立即复制保存，关闭后无法再次查看：</p>
+        <Typography.Paragraph code copyable>
+          {createdToken}
+        </Typography.Paragraph>
+      </Modal>
+
+      <Modal
+        title="新建用户"
+        open={userModalVisible}
+        onOk={handleCreateUser}
+        onCancel={() => setUserModalVisible(false)}
+      >
+        <Form form={userForm} layout="vertical">
+          <Form.Item name="username" label="用户名" rules={[{ required: true, message: '请输入用户名' }]}>
+            <Input />
+          </Form.Item>
+          <Form.Item
+            name="password"
+            label="初始密码"
+            rules={[{ required: true, min: 8, message: '密码至少 8 位，包含小写字母、大写字母、数字、符号中的至少 3 类' }]}
+          >
+            <Input.Password autoComplete="new-password" />
+          </Form.Item>
+          <Form.Item name="role" label="角色" initialValue="viewer">
+            <Select options={roleOptions} />
+          </Form.Item>
+        </Form>
+      </Modal>
+    </Space>
+  );
+};
+
+export default Account;
@@ -0,0 +1,389 @@
+import React, { useState, useEffect } from 'react';
+import {
+  Card,
+  Button,
+  Table,
+  Modal,
+  Form,
+  Input,
+  Select,
+  InputNumber,
+  Switch,
+  message,
+  Space,
+  Tag,
+  Popconfirm,
+} from 'antd';
+import { PlusOutlined, DeleteOutlined, EditOutlined, MinusCircleOutlined } from '@ant-design/icons';
+import type { ColumnsType } from 'antd/es/table';
+import type {
+  AlertRoute,
+  AlertSilence,
+  LabelMatcher,
+  NotificationChannelConfig,
+} from '../types';
+import * as api from '../services/api';
+
+const MATCH_OPS = ['=', '!=', '=~', '!~'];
+
+const renderMatchers = (matchers: LabelMatcher[]) =>
+  matchers.length ? (
+    <Space wrap>
+      {matchers.map((m, i) => (
+        <Tag key={i}>
+          {m.label}
+          {m.op || '='}
+          {JSON.stringify(m.value)}
+        </Tag>
+      ))}
+    </Space>
+  ) : (
+    <Tag color="blue">所有告警</Tag>
+  );
+
+// 匹配器编辑，标签为空时按空字符串匹配
+const MatchersField: React.FC = () => (
+  <Form.List name="matchers">
+    {(fields, { add, remove }) => (
+      <>
+        {fields.map((field) => (
+          <Space key={field.key} align="baseline">
+            <Form.Item name={[field.name, 'label']} rules={[{ required: true, message: '请输入标签' }]}>
+              <Input placeholder="标签，例如 component" style={{ width: 180 }} />
+            </Form.Item>
+            <Form.Item name={[field.name, 'op']} initialValue="=">
+              <Select style={{ width: 80 }}>
+                {MATCH_OPS.map((op) => (
+                  <Select.Option key={op} value={op}>
+                    {op}
+                  </Select.Option>
+                ))}
+              </Select>
+            </Form.Item>
+            <Form.Item name={[field.name, 'value']} initialValue="">
+              <Input placeholder="值或正则" style={{ width: 200 }} />
+            </Form.Item>
+            <MinusCircleOutlined onClick={() => remove(field.name)} />
+          </Space>
+        ))}
+        <Button type="dashed" onClick={() => add()} icon={<PlusOutlined />} block>
+          添加匹配器
+        </Button>
+      </>
+    )}
+  </Form.List>
+);
+
+const AlertRouting: React.FC = () => {
+  const [routes, setRoutes] = useState<AlertRoute[]>([]);
+  const [silences, setSilences] = useState<AlertSilence[]>([]);
+  const [channels, setChannels] = useState<NotificationChannelConfig[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [routeModalVisible, setRouteModalVisible] = useState(false);
+  const [silenceModalVisible, setSilenceModalVisible] = useState(false);
+  const [saving, setSaving] = useState(false);
+  const [editingId, setEditingId] = useState<number | null>(null);
+  const [routeForm] = Form.useForm();
+  const [silenceForm] = Form.useForm();
+
+  const loadData = async () => {
+    setLoading(true);
+    try {
+      const [routes, silences, channels] = await Promise.all([
+        api.listAlertRoutes(),
+        api.listAlertSilences(),
+        api.listNotificationChannels(),
+      ]);
+      setRoutes(routes);
+      setSilences(silences);
+      setChannels(channels);
+    } catch (error) {
+      message.error('加载告警路由失败: ' + error);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    loadData();
+  }, []);
+
+  const channelName = (id: number) => channels.find((c) => c.id === id)?.name ?? `#${id}`;
+
+  const handleOpenCreateRoute = () => {
+    setEditingId(null);
+    routeForm.resetFields();
+    routeForm.setFieldsValue({ matchers: [], priority: 0, continue_matching: false, enabled: true });
+    setRouteModalVisible(true);
+  };
+
+  const handleOpenEditRoute = (record: AlertRoute) => {
+    setEditingId(record.id);
+    routeForm.resetFields();
+    routeForm.setFieldsValue(record);
+    setRouteModalVisible(true);
+  };
+
+  const handleSaveRoute = async () => {
+    try {
+      const values = await routeForm.validateFields();
+      setSaving(true);
+      const request = { ...values, matchers: values.matchers || [] };
+      if (editingId) {
+        await api.updateAlertRoute(editingId, request);
+      } else {
+        await api.createAlertRoute(request);
+      }
+      message.success('保存成功');
+      setRouteModalVisible(false);
+      loadData();
+    } catch (error) {
+      message.error('保存失败: ' + error);
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleDeleteRoute = async (id: number) => {
+    try {
+      await api.deleteAlertRoute(id);
+      message.success('已删除');
+      loadData();
+    } catch (error) {
+      message.error('删除失败: ' + error);
+    }
+  };
+
+  const handleOpenCreateSilence = () => {
+    silenceForm.resetFields();
+    silenceForm.setFieldsValue({ matchers: [{ label: 'alertname', op: '=', value: '' }], hours: 2 });
+    setSilenceModalVisible(true);
+  };
+
+  const handleSaveSilence = async () => {
+    try {
+      const values = await silenceForm.validateFields();
+      setSaving(true);
+      await api.createAlertSilence({
+        matchers: values.matchers || [],
+        ends_at: new Date(Date.now() + values.hours * 3600 * 1000).toISOString(),
+        comment: values.comment || undefined,
+      });
+      message.success('已创建静默');
+      setSilenceModalVisible(false);
+      loadData();
+    } catch (error) {
+      message.error('创建失败: ' + error);
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleDeleteSilence = async (id: number) => {
+    try {
+      await api.deleteAlertSilence(id);
+      message.success('已删除');
+      loadData();
+    } catch (error) {
+      message.error('删除失败: ' + error);
+    }
+  };
+
+  const routeColumns: ColumnsType<AlertRoute> = [
+    { title: '优先级', dataIndex: 'priority', key: 'priority', width: 80 },
+    { title: '名称', dataIndex: 'name', key: 'name' },
+    {
+      title: '匹配器',
+      dataIndex: 'matchers',
+      key: 'matchers',
+      render: renderMatchers,
+    },
+    {
+      title: '渠道',
+      dataIndex: 'channel_ids',
+      key: 'channel_ids',
+      render: (ids: number[]) => (
+        <Space wrap>
+          {ids.map((id) => (
+            <Tag key={id} color="purple">
+              {channelName(id)}
+            </Tag>
+          ))}
+        </Space>
+      ),
+    },
+    {
+      title: '继续匹配',
+      dataIndex: 'continue_matching',
+      key: 'continue_matching',
+      render: (value: boolean) => (value ? '是' : '否'),
+    },
+    {
+      title: '状态',
+      dataIndex: 'enabled',
+      key: 'enabled',
+      render: (enabled: boolean) =>
+        enabled ? <Tag color="green">启用</Tag> : <Tag>停用</Tag>,
+    },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_, record) => (
+        <Space>
+          <Button type="link" icon={<EditOutlined />} onClick={() => handleOpenEditRoute(record)}>
+            编辑
+          </Button>
+          <Popconfirm title="确定删除该规则吗？" onConfirm={() => handleDeleteRoute(record.id)}>
+            <Button type="link" danger icon={<DeleteOutlined />}>
+              删除
+            </Button>
+          </Popconfirm>
+        </Space>
+      ),
+    },
+  ];
+
+  const silenceColumns: ColumnsType<AlertSilence> = [
+    {
+      title: '匹配器',
+      dataIndex: 'matchers',
+      key: 'matchers',
+      render: renderMatchers,
+    },
+    {
+      title: '生效时间',
+      key: 'range',
+      render: (_, record) =>
+        `${new Date(record.starts_at).toLocaleString()} ~ ${new Date(record.ends_at).toLocaleString()}`,
+    },
+    {
+      title: '状态',
+      key: 'status',
+      render: (_, record) => {
+        const now = Date.now();
+        if (new Date(record.ends_at).getTime() <= now) return <Tag>已过期</Tag>;
+        if (new Date(record.starts_at).getTime() > now) return <Tag color="blue">未开始</Tag>;
+        return <Tag color="orange">生效中</Tag>;
+      },
+    },
+    { title: '备注', dataIndex: 'comment', key: 'comment' },
+    { title: '创建人', dataIndex: 'created_by', key: 'created_by' },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_, record) => (
+        <Popconfirm title="确定删除该静默吗？" onConfirm={() => handleDeleteSilence(record.id)}>
+          <Button type="link" danger icon={<DeleteOutlined />}>
+            删除
+          </Button>
+        </Popconfirm>
+      ),
+    },
+  ];
+
+  return (
+    <div>
+      <Card
+        title="告警路由规则"
+        extra={
+          <Button type="primary" icon={<PlusOutlined />} onClick={handleOpenCreateRoute}>
+            新建规则
+          </Button>
+        }
+      >
+        <Table
+          columns={routeColumns}
+          dataSource={routes}
+          rowKey="id"
+          loading={loading}
+          pagination={false}
+        />
+        <div style={{ marginTop: 8, color: '#888' }}>
+          告警按优先级从小到大匹配规则；没有匹配任何规则的告警发送到所有订阅了告警的渠道
+        </div>
+      </Card>
+
+      <Card
+        title="静默"
+        style={{ marginTop: 16 }}
+        extra={
+          <Button icon={<PlusOutlined />} onClick={handleOpenCreateSilence}>
+            新建静默
+          </Button>
+        }
+      >
+        <Table columns={silenceColumns} dataSource={silences} rowKey="id" loading={loading} />
+      </Card>
+
+      <Modal
+        title={editingId ? '编辑告警路由规则' : '新建告警路由规则'}
+        open={routeModalVisible}
+        onCancel={() => setRouteModalVisible(false)}
+        onOk={handleSaveRoute}
+        confirmLoading={saving}
+        width={640}
+      >
+        <Form form={routeForm} layout="vertical">
+          <Form.Item label="名称" name="name" rules={[{ required: true, message: '请输入名称' }]}>
+            <Input placeholder="例如 sink-alerts" />
+          </Form.Item>
+          <Form.Item label="匹配器" extra="所有匹配器都满足时匹配；不添加匹配器时匹配所有告警">
+            <MatchersField />
+          </Form.Item>
+          <Form.Item
+            label="发送到渠道"
+            name="channel_ids"
+            rules={[{ required: true, message: '请选择至少一个渠道' }]}
+          >
+            <Select mode="multiple">
+              {channels.map((c) => (
+                <Select.Option key={c.id} value={c.id}>
+                  {c.name}
+                </Select.Option>
+              ))}
+            </Select>
+          </Form.Item>
+          <Space>
+            <Form.Item label="优先级" name="priority" extra="数字越小越先匹配">
+              <InputNumber />
+            </Form.Item>
+            <Form.Item
+              label="继续匹配"
+              name="continue_matching"
+              valuePropName="checked"
+              extra="匹配后继续匹配后面的规则"
+            >
+              <Switch />
+            </Form.Item>
+            <Form.Item label="启用" name="enabled" valuePropName="checked">
+              <Switch />
+            </Form.Item>
+          </Space>
+        </Form>
+      </Modal>
+
+      <Modal
+        title="新建静默"
+        open={silenceModalVisible}
+        onCancel={() => setSilenceModalVisible(false)}
+        onOk={handleSaveSilence}
+        confirmLoading={saving}
+        width={640}
+      >
+        <Form form={silenceForm} layout="vertical">
+          <Form.Item label="匹配器" extra="静默期间所有匹配器都满足的告警不发送">
+            <MatchersField />
+          </Form.Item>
+          <Form.Item label="持续时间（小时）" name="hours" rules={[{ required: true }]}>
+            <InputNumber min={0.25} step={0.5} />
+          </Form.Item>
+          <Form.Item label="备注" name="comment">
+            <Input.TextArea rows={2} placeholder="例如 计划内维护" />
+          </Form.Item>
+        </Form>
+      </Modal>
+    </div>
+  );
+};
+
+export default AlertRouting;
@@ -0,0 +1,212 @@
+import React, { useState, useEffect } from 'react';
+import { Card, Button, Table, Input, Select, message, Space, Tag, Popconfirm, Descriptions } from 'antd';
+import { CheckOutlined, ReloadOutlined } from '@ant-design/icons';
+import type { ColumnsType } from 'antd/es/table';
+import type { AlertRecord, AlertStatus } from '../types';
+import * as api from '../services/api';
+
+const SEVERITY_COLORS: Record<string, string> = {
+  critical: 'red',
+  warning: 'orange',
+  info: 'blue',
+};
+
+const Alerts: React.FC = () => {
+  const [alerts, setAlerts] = useState<AlertRecord[]>([]);
+  const [total, setTotal] = useState(0);
+  const [loading, setLoading] = useState(false);
+  const [statusFilter, setStatusFilter] = useState<AlertStatus | undefined>('firing');
+  const [severityFilter, setSeverityFilter] = useState<string | undefined>();
+  const [fingerprint, setFingerprint] = useState<string | undefined>();
+  const [currentPage, setCurrentPage] = useState(1);
+  const [pageSize, setPageSize] = useState(20);
+  const [user, setUser] = useState(() => localStorage.getItem('rw_cdc_sr_user') || '');
+
+  const loadAlerts = async () => {
+    setLoading(true);
+    try {
+      const response = await api.listAlerts({
+        status: statusFilter,
+        severity: severityFilter,
+        fingerprint,
+        limit: pageSize,
+        offset: (currentPage - 1) * pageSize,
+      });
+      setAlerts(response.data);
+      setTotal(response.total);
+    } catch (error) {
+      message.error('加载告警失败: ' + error);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    loadAlerts();
+    // 每30秒自动刷新
+    const interval = setInterval(loadAlerts, 30000);
+    return () => clearInterval(interval);
+  }, [statusFilter, severityFilter, fingerprint, currentPage, pageSize]);
+
+  const handleResolve = async (record: AlertRecord) => {
+    if (!user.trim()) {
+      message.warning('请先填写操作人');
+      return;
+    }
+    try {
+      localStorage.setItem('rw_cdc_sr_user', user.trim());
+      await api.resolveAlert(record.id, user.trim());
+      message.success('已标记为解决');
+      loadAlerts();
+    } catch (error) {
+      message.error('操作失败: ' + error);
+    }
+  };
+
+  const columns: ColumnsType<AlertRecord> = [
+    {
+      title: '状态',
+      dataIndex: 'status',
+      key: 'status',
+      width: 100,
+      render: (status: AlertStatus, record) => (
+        <Space direction="vertical" size={0}>
+          {status === 'firing' ? <Tag color="red">告警中</Tag> : <Tag color="green">已解决</Tag>}
+          {record.silenced && <Tag>已静默</Tag>}
+        </Space>
+      ),
+    },
+    { title: '告警', dataIndex: 'alertname', key: 'alertname' },
+    {
+      title: '级别',
+      dataIndex: 'severity',
+      key: 'severity',
+      render: (severity?: string) =>
+        severity ? <Tag color={SEVERITY_COLORS[severity.toLowerCase()]}>{severity}</Tag> : '-',
+    },
+    {
+      title: '摘要',
+      key: 'summary',
+      render: (_, record) => record.annotations.summary || '-',
+    },
+    {
+      title: '开始时间',
+      dataIndex: 'starts_at',
+      key: 'starts_at',
+      render: (time: string) => new Date(time).toLocaleString(),
+    },
+    {
+      title: '最后收到',
+      dataIndex: 'last_received_at',
+      key: 'last_received_at',
+      render: (time: string, record) => `${new Date(time).toLocaleString()}（${record.receive_count} 次）`,
+    },
+    {
+      title: '解决',
+      key: 'resolved',
+      render: (_, record) =>
+        record.resolved_at
+          ? `${new Date(record.resolved_at).toLocaleString()}${record.resolved_by ? ` · ${record.resolved_by}` : ''}`
+          : '-',
+    },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_, record) => (
+        <Space>
+          <Button type="link" onClick={() => setFingerprint(record.fingerprint)}>
+            同类历史
+          </Button>
+          {record.status === 'firing' && (
+            <Popconfirm title="确定标记为已解决吗？" onConfirm={() => handleResolve(record)}>
+              <Button type="link" icon={<CheckOutlined />}>
+                解决
+              </Button>
+            </Popconfirm>
+          )}
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <Card
+      title="告警"
+      extra={
+        <Space>
+          <Input
+            placeholder="操作人"
+            value={user}
+            onChange={(e) => setUser(e.target.value)}
+            style={{ width: 120 }}
+          />
+          <Select
+            allowClear
+            placeholder="状态"
+            value={statusFilter}
+            onChange={(value) => {
+              setStatusFilter(value);
+              setCurrentPage(1);
+            }}
+            style={{ width: 110 }}
+          >
+            <Select.Option value="firing">告警中</Select.Option>
+            <Select.Option value="resolved">已解决</Select.Option>
+          </Select>
+          <Select
+            allowClear
+            placeholder="级别"
+            value={severityFilter}
+            onChange={(value) => {
+              setSeverityFilter(value);
+              setCurrentPage(1);
+            }}
+            style={{ width: 110 }}
+          >
+            <Select.Option value="critical">critical</Select.Option>
+            <Select.Option value="warning">warning</Select.Option>
+            <Select.Option value="info">info</Select.Option>
+          </Select>
+          {fingerprint && (
+            <Tag closable onClose={() => setFingerprint(undefined)}>
+              fingerprint: {fingerprint}
+            </Tag>
+          )}
+          <Button icon={<ReloadOutlined />} onClick={loadAlerts}>
+            刷新
+          </Button>
+        </Space>
+      }
+    >
+      <Table
+        columns={columns}
+        dataSource={alerts}
+        rowKey="id"
+        loading={loading}
+        expandable={{
+          expandedRowRender: (record) => (
+            <Descriptions column={1} size="small" bordered>
+              {Object.entries({ ...record.labels, ...record.annotations }).map(([key, value]) => (
+                <Descriptions.Item key={key} label={key}>
+                  {value}
+                </Descriptions.Item>
+              ))}
+            </Descriptions>
+          ),
+        }}
+        pagination={{
+          current: currentPage,
+          pageSize,
+          total,
+          showSizeChanger: true,
+          onChange: (page, size) => {
+            setCurrentPage(page);
+            setPageSize(size);
+          },
+        }}
+      />
+    </Card>
+  );
+};
+
+export default Alerts;
@@ -0,0 +1,180 @@
+import React, { useState, useEffect } from 'react';
+import { Card, Button, Table, Input, Select, message, Space, Tag, Typography } from 'antd';
+import { ReloadOutlined } from '@ant-design/icons';
+import type { ColumnsType } from 'antd/es/table';
+import type { AuditLog } from '../types';
+import * as api from '../services/api';
+
+const ACTION_LABELS: Record<string, string> = {
+  drop_table: '删除表',
+  drop_sink: '删除 Sink',
+  drop_source: '删除 Source',
+  drop_materialized_view: '删除物化视图',
+  drop_secret: '删除 Secret',
+  alter_secret: '更新 Secret',
+  truncate_table: '清空表',
+  create_sink: '创建 Sink',
+  rename_table: '重命名表',
+  connection_create: '新建连接',
+  connection_update: '修改连接',
+  connection_delete: '删除连接',
+  connection_clone: '克隆连接',
+  connection_rotate_password: '轮换密码',
+  connection_import: '导入连接',
+};
+
+const AuditLogs: React.FC = () => {
+  const [logs, setLogs] = useState<AuditLog[]>([]);
+  const [total, setTotal] = useState(0);
+  const [loading, setLoading] = useState(false);
+  const [actor, setActor] = useState<string | undefined>();
+  const [action, setAction] = useState<string | undefined>();
+  const [target, setTarget] = useState<string | undefined>();
+  const [currentPage, setCurrentPage] = useState(1);
+  const [pageSize, setPageSize] = useState(20);
+
+  const loadLogs = async () => {
+    setLoading(true);
+    try {
+      const response = await api.listAuditLogs({
+        actor,
+        action,
+        target,
+        limit: pageSize,
+        offset: (currentPage - 1) * pageSize,
+      });
+      setLogs(response.data);
+      setTotal(response.total);
+    } catch (error) {
+      message.error('加载审计日志失败: ' + error);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    loadLogs();
+  }, [actor, action, target, currentPage, pageSize]);
+
+  const columns: ColumnsType<AuditLog> = [
+    {
+      title: '时间',
+      dataIndex: 'created_at',
+      key: 'created_at',
+      width: 180,
+      render: (time: string) => new Date(time).toLocaleString(),
+    },
+    {
+      title: '操作人',
+      dataIndex: 'actor',
+      key: 'actor',
+      render: (value?: string | null) => value || '-',
+    },
+    {
+      title: '操作',
+      dataIndex: 'action',
+      key: 'action',
+      render: (value: string) => ACTION_LABELS[value] || value,
+    },
+    { title: '目标对象', dataIndex: 'target', key: 'target' },
+    {
+      title: '系统',
+      dataIndex: 'engine',
+      key: 'engine',
+      render: (value?: string | null) => value || '-',
+    },
+    {
+      title: '任务',
+      dataIndex: 'task_id',
+      key: 'task_id',
+      render: (value?: number | null) => value ?? '-',
+    },
+    {
+      title: '结果',
+      key: 'success',
+      render: (_, record) =>
+        record.success ? <Tag color="green">成功</Tag> : <Tag color="red">失败</Tag>,
+    },
+  ];
+
+  return (
+    <Card
+      title="审计日志"
+      extra={
+        <Space>
+          <Input.Search
+            allowClear
+            placeholder="操作人"
+            onSearch={(value) => {
+              setActor(value.trim() || undefined);
+              setCurrentPage(1);
+            }}
+            style={{ width: 140 }}
+          />
+          <Select
+            allowClear
+            placeholder="操作"
+            value={action}
+            onChange={(value) => {
+              setAction(value);
+              setCurrentPage(1);
+            }}
+            style={{ width: 140 }}
+          >
+            {Object.entries(ACTION_LABELS).map(([value, label]) => (
+              <Select.Option key={value} value={value}>
+                {label}
+              </Select.Option>
+            ))}
+          </Select>
+          <Input.Search
+            allowClear
+            placeholder="目标对象"
+            onSearch={(value) => {
+              setTarget(value.trim() || undefined);
+              setCurrentPage(1);
+            }}
+            style={{ width: 180 }}
+          />
+          <Button icon={<ReloadOutlined />} onClick={loadLogs}>
+            刷新
+          </Button>
+        </Space>
+      }
+    >
+      <Table
+        columns={columns}
+        dataSource={logs}
+        rowKey="id"
+        loading={loading}
+        expandable={{
+          rowExpandable: (record) => !!record.sql_text || !!record.error_message,
+          expandedRowRender: (record) => (
+            <Space direction="vertical" style={{ width: '100%' }}>
+              {record.sql_text && (
+                <Typography.Paragraph code copyable style={{ whiteSpace: 'pre-wrap', marginBottom: 0 }}>
+                  {record.sql_text}
+                </Typography.Paragraph>
+              )}
+              {record.error_message && (
+                <Typography.Text type="danger">{record.error_message}</Typography.Text>
+              )}
+            </Space>
+          ),
+        }}
+        pagination={{
+          current: currentPage,
+          pageSize,
+          total,
+          showSizeChanger: true,
+          onChange: (page, size) => {
+            setCurrentPage(page);
+            setPageSize(size);
+          },
+        }}
+      />
+    </Card>
+  );
+};
+
+export default AuditLogs;
@@ -0,0 +1,60 @@
+import React, { useState } from 'react';
+import { Card, Form, Input, Button, message } from 'antd';
+import { UserOutlined, LockOutlined } from '@ant-design/icons';
+import { useNavigate, useSearchParams } from 'react-router-dom';
+import * as api from '../services/api';
+
+const Login: React.FC = () => {
+  const [loading, setLoading] = useState(false);
+  const navigate = useNavigate();
+  const [searchParams] = useSearchParams();
+
+  const handleLogin = async (values: { username: string; password: string }) => {
+    setLoading(true);
+    try {
+      await api.login(values.username, values.password);
+      const redirect = searchParams.get('redirect');
+      navigate(redirect && redirect.startsWith('/') && redirect !== '/login' ? redirect : '/', {
+        replace: true,
+      });
+    } catch (error) {
+      message.error('登录失败: ' + error);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  return (
+    <div
+      style={{
+        minHeight: '100vh',
+        display: 'flex',
+        alignItems: 'center',
+        justifyContent: 'center',
+        background: '#f0f2f5',
+      }}
+    >
+      <Card title="RisingWave CDC → StarRocks" style={{ width: 360 }}>
+        <Form onFinish={handleLogin} autoComplete="on">
+          <Form.Item name="username" rules={[{ required: true, message: '请输入用户名' }]}>
+            <Input prefix={<UserOutlined />} placeholder="用户名" autoComplete="username" />
+          </Form.Item>
+          <Form.Item name="password" rules={[{ required: true, message: '请输入密码' }]}>
+            <Input.Password
+              prefix={<LockOutlined />}
+              placeholder="密码"
+              autoComplete="current-password"
+            />
+          </Form.Item>
+          <Form.Item style={{ marginBottom: 0 }}>
+            <Button type="primary" htmlType="submit" loading={loading} block>
+              登录
+            </Button>
+          </Form.Item>
+        </Form>
+      </Card>
+    </div>
+  );
+};
+
+export default Login;
@@ -0,0 +1,359 @@
+import React, { useState, useEffect } from 'react';
+import {
+  Card,
+  Button,
+  Table,
+  Modal,
+  Form,
+  Input,
+  Select,
+  InputNumber,
+  Switch,
+  message,
+  Space,
+  Tag,
+  Popconfirm,
+} from 'antd';
+import { PlusOutlined, DeleteOutlined, EditOutlined, SendOutlined } from '@ant-design/icons';
+import type { ColumnsType } from 'antd/es/table';
+import type {
+  ChannelSettings,
+  NotificationChannelConfig,
+  NotificationEvent,
+} from '../types';
+import * as api from '../services/api';
+
+const KIND_LABELS: Record<ChannelSettings['kind'], string> = {
+  lark: '飞书',
+  dingtalk: '钉钉',
+  slack: 'Slack',
+  webhook: '通用 Webhook',
+  email: '邮件 (SMTP)',
+};
+
+const EVENT_LABELS: Record<NotificationEvent, string> = {
+  alert: 'Alertmanager 告警',
+  task_failed: '同步任务失败',
+  task_completed: '同步任务完成',
+};
+
+// 表单字段转换为渠道配置
+const toSettings = (values: any): ChannelSettings => {
+  switch (values.kind) {
+    case 'dingtalk':
+      return { kind: 'dingtalk', webhook_url: values.webhook_url, secret: values.secret || undefined };
+    case 'webhook':
+      return {
+        kind: 'webhook',
+        url: values.webhook_url,
+        headers: values.headers ? JSON.parse(values.headers) : undefined,
+      };
+    case 'email':
+      return {
+        kind: 'email',
+        smtp_host: values.smtp_host,
+        smtp_port: values.smtp_port,
+        tls: values.tls,
+        username: values.username || undefined,
+        password: values.password || undefined,
+        from: values.from,
+        to: values.to,
+      };
+    default:
+      return { kind: values.kind, webhook_url: values.webhook_url };
+  }
+};
+
+// 渠道配置展开为表单字段
+const toFormValues = (channel: NotificationChannelConfig) => {
+  const settings = channel.settings;
+  const base = { name: channel.name, events: channel.events, enabled: channel.enabled, kind: settings.kind };
+  if (settings.kind === 'webhook') {
+    return {
+      ...base,
+      webhook_url: settings.url,
+      headers: settings.headers && Object.keys(settings.headers).length
+        ? JSON.stringify(settings.headers)
+        : undefined,
+    };
+  }
+  return { ...base, ...settings };
+};
+
+const NotificationChannels: React.FC = () => {
+  const [channels, setChannels] = useState<NotificationChannelConfig[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [modalVisible, setModalVisible] = useState(false);
+  const [saving, setSaving] = useState(false);
+  const [editingId, setEditingId] = useState<number | null>(null);
+  const [form] = Form.useForm();
+  const kind: ChannelSettings['kind'] = Form.useWatch('kind', form);
+
+  const loadChannels = async () => {
+    setLoading(true);
+    try {
+      setChannels(await api.listNotificationChannels());
+    } catch (error) {
+      message.error('加载通知渠道失败: ' + error);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    loadChannels();
+  }, []);
+
+  const handleOpenCreate = () => {
+    setEditingId(null);
+    form.resetFields();
+    form.setFieldsValue({
+      kind: 'lark',
+      events: ['alert', 'task_failed'],
+      enabled: true,
+      smtp_port: 465,
+      tls: 'tls',
+    });
+    setModalVisible(true);
+  };
+
+  const handleOpenEdit = (record: NotificationChannelConfig) => {
+    setEditingId(record.id);
+    form.resetFields();
+    form.setFieldsValue(toFormValues(record));
+    setModalVisible(true);
+  };
+
+  const handleSave = async () => {
+    try {
+      const values = await form.validateFields();
+      setSaving(true);
+      const request = {
+        name: values.name,
+        settings: toSettings(values),
+        events: values.events,
+        enabled: values.enabled,
+      };
+      if (editingId) {
+        await api.updateNotificationChannel(editingId, request);
+      } else {
+        await api.createNotificationChannel(request);
+      }
+      message.success('保存成功');
+      setModalVisible(false);
+      loadChannels();
+    } catch (error) {
+      message.error('保存失败: ' + error);
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await api.deleteNotificationChannel(id);
+      message.success('已删除');
+      loadChannels();
+    } catch (error) {
+      message.error('删除失败: ' + error);
+    }
+  };
+
+  const handleTest = async (record: NotificationChannelConfig) => {
+    try {
+      await api.testNotificationChannel(record.id);
+      message.success(`已向 ${record.name} 发送测试消息`);
+    } catch (error) {
+      message.error('发送失败: ' + error);
+    }
+  };
+
+  const columns: ColumnsType<NotificationChannelConfig> = [
+    { title: '名称', dataIndex: 'name', key: 'name' },
+    {
+      title: '类型',
+      key: 'kind',
+      render: (_, record) => <Tag>{KIND_LABELS[record.settings.kind]}</Tag>,
+    },
+    {
+      title: '事件',
+      dataIndex: 'events',
+      key: 'events',
+      render: (events: NotificationEvent[]) => (
+        <Space>
+          {events.map((e) => (
+            <Tag key={e} color={{ alert: 'orange', task_failed: 'red', task_completed: 'green' }[e]}>
+              {EVENT_LABELS[e]}
+            </Tag>
+          ))}
+        </Space>
+      ),
+    },
+    {
+      title: '状态',
+      dataIndex: 'enabled',
+      key: 'enabled',
+      render: (enabled: boolean) =>
+        enabled ? <Tag color="green">启用</Tag> : <Tag>停用</Tag>,
+    },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_, record) => (
+        <Space>
+          <Button type="link" icon={<SendOutlined />} onClick={() => handleTest(record)}>
+            测试
+          </Button>
+          <Button type="link" icon={<EditOutlined />} onClick={() => handleOpenEdit(record)}>
+            编辑
+          </Button>
+          <Popconfirm title="确定删除该渠道吗？" onConfirm={() => handleDelete(record.id)}>
+            <Button type="link" danger icon={<DeleteOutlined />}>
+              删除
+            </Button>
+          </Popconfirm>
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <div>
+      <Card
+        title="通知渠道"
+        extra={
+          <Button type="primary" icon={<PlusOutlined />} onClick={handleOpenCreate}>
+            新建渠道
+          </Button>
+        }
+      >
+        <Table columns={columns} dataSource={channels} rowKey="id" loading={loading} />
+      </Card>
+
+      <Modal
+        title={editingId ? '编辑通知渠道' : '新建通知渠道'}
+        open={modalVisible}
+        onCancel={() => setModalVisible(false)}
+        onOk={handleSave}
+        confirmLoading={saving}
+        width={600}
+      >
+        <Form form={form} layout="vertical">
+          <Form.Item label="名称" name="name" rules={[{ required: true, message: '请输入名称' }]}>
+            <Input placeholder="例如 ops-dingtalk" />
+          </Form.Item>
+
+          <Form.Item label="类型" name="kind" rules={[{ required: true }]}>
+            <Select>
+              {Object.entries(KIND_LABELS).map(([value, label]) => (
+                <Select.Option key={value} value={value}>
+                  {label}
+                </Select.Option>
+              ))}
+            </Select>
+          </Form.Item>
+
+          <Form.Item
+            label="订阅事件"
+            name="events"
+            rules={[{ required: true, message: '请选择至少一个事件' }]}
+          >
+            <Select mode="multiple">
+              {Object.entries(EVENT_LABELS).map(([value, label]) => (
+                <Select.Option key={value} value={value}>
+                  {label}
+                </Select.Option>
+              ))}
+            </Select>
+          </Form.Item>
+
+          {kind !== 'email' && (
+            <Form.Item
+              label="Webhook 地址"
+              name="webhook_url"
+              rules={[{ required: true, message: '请输入 Webhook 地址' }]}
+            >
+              <Input placeholder="https://..." />
+            </Form.Item>
+          )}
+
+          {kind === 'dingtalk' && (
+            <Form.Item label="加签密钥" name="secret" extra="机器人安全设置为加签时填写">
+              <Input.Password placeholder="SEC..." />
+            </Form.Item>
+          )}
+
+          {kind === 'webhook' && (
+            <Form.Item
+              label="请求头 (JSON)"
+              name="headers"
+              extra='例如 {"Authorization": "Bearer xxx"}，请求体为通知的 JSON（event、title、text、fields）'
+              rules={[
+                {
+                  validator: async (_, value) => {
+                    if (value) JSON.parse(value);
+                  },
+                  message: '请输入合法的 JSON 对象',
+                },
+              ]}
+            >
+              <Input.TextArea rows={2} />
+            </Form.Item>
+          )}
+
+          {kind === 'email' && (
+            <>
+              <Space>
+                <Form.Item
+                  label="SMTP 服务器"
+                  name="smtp_host"
+                  rules={[{ required: true, message: '请输入 SMTP 服务器' }]}
+                >
+                  <Input placeholder="smtp.example.com" style={{ width: 260 }} />
+                </Form.Item>
+                <Form.Item label="端口" name="smtp_port" rules={[{ required: true }]}>
+                  <InputNumber min={1} max={65535} />
+                </Form.Item>
+                <Form.Item label="加密" name="tls">
+                  <Select style={{ width: 120 }}>
+                    <Select.Option value="tls">TLS</Select.Option>
+                    <Select.Option value="starttls">STARTTLS</Select.Option>
+                    <Select.Option value="none">不加密</Select.Option>
+                  </Select>
+                </Form.Item>
+              </Space>
+              <Space>
+                <Form.Item label="用户名" name="username">
+                  <Input style={{ width: 260 }} />
+                </Form.Item>
+                <Form.Item label="密码" name="password">
+                  <Input.Password style={{ width: 260 }} />
+                </Form.Item>
+              </Space>
+              <Form.Item
+                label="发件人"
+                name="from"
+                rules={[{ required: true, message: '请输入发件人地址' }]}
+              >
+                <Input placeholder="alert@example.com" />
+              </Form.Item>
+              <Form.Item
+                label="收件人"
+                name="to"
+                rules={[{ required: true, message: '请输入收件人地址' }]}
+              >
+                <Select mode="tags" placeholder="输入地址后回车" />
+              </Form.Item>
+            </>
+          )}
+
+          <Form.Item label="启用" name="enabled" valuePropName="checked">
+            <Switch />
+          </Form.Item>
+        </Form>
+      </Modal>
+    </div>
+  );
+};
+
+export default NotificationChannels;
@@ -25,11 +25,28 @@ import type {
   RwTable,
   RwMaterializedView,
   RwSink,
+  RwView,
+  RwIndex,
+  RwSecret,
+  RwSecretUsage,
+  RwRelationKeys,
+  OrphanObject,
+  OrphanReason,
+  SqlQueryResult,
 } from "../types";
 
 const { Title, Paragraph } = Typography;
 const { TabPane } = Tabs;
 
+const orphanReasonLabels: Record<OrphanReason, { color: string; label: string }> = {
+  no_sync_task: { color: "default", label: "没有同步任务" },
+  pipeline_cleaned_up: { color: "orange", label: "管道已清理" },
+  target_table_missing: { color: "red", label: "目标表已删除" },
+  unreferenced: { color: "blue", label: "未被引用" },
+};
+
+const orphanKey = (o: OrphanObject) => `${o.kind}:${o.schema_name}.${o.name}`;
+
 // 防抖 Hook：延迟更新值
 function useDebounce<T>(value: T, delay: number): T {
   const [debouncedValue, setDebouncedValue] = useState<T>(value);
@@ -100,6 +117,45 @@ const RisingWaveManager: React.FC = () => {
     loading: false,
   });
 
+  const [viewsState, setViewsState] = useState<TabState<RwView>>({
+    data: [],
+    total: 0,
+    currentPage: 1,
+    pageSize: 20,
+    search: "",
+    loading: false,
+  });
+
+  const [indexesState, setIndexesState] = useState<TabState<RwIndex>>({
+    data: [],
+    total: 0,
+    currentPage: 1,
+    pageSize: 20,
+    search: "",
+    loading: false,
+  });
+
+  const [secretsState, setSecretsState] = useState<TabState<RwSecret>>({
+    data: [],
+    total: 0,
+    currentPage: 1,
+    pageSize: 20,
+    search: "",
+    loading: false,
+  });
+  // 同步目标 schema 中 Secret 的引用关系
+  const [secretUsage, setSecretUsage] = useState<RwSecretUsage[]>([]);
+
+  // 遗留对象：同步目标 schema 中没有对应同步任务的对象
+  const [orphans, setOrphans] = useState<OrphanObject[]>([]);
+  const [orphansLoading, setOrphansLoading] = useState(false);
+  const [selectedOrphanKeys, setSelectedOrphanKeys] = useState<React.Key[]>([]);
+
+  // SQL console states
+  const [consoleSql, setConsoleSql] = useState("");
+  const [consoleResult, setConsoleResult] = useState<SqlQueryResult | null>(null);
+  const [consoleLoading, setConsoleLoading] = useState(false);
+
   // Modal states for viewing SQL definitions
   const [sqlModalVisible, setSqlModalVisible] = useState(false);
   const [sqlModalContent, setSqlModalContent] = useState("");
@@ -116,11 +172,20 @@ const RisingWaveManager: React.FC = () => {
   const [createSinkLoading, setCreateSinkLoading] = useState(false);
   const [srConnections, setSrConnections] = useState<DatabaseConfig[]>([]);
   const [sinkForm] = Form.useForm();
+  const [sinkKeys, setSinkKeys] = useState<RwRelationKeys | null>(null);
   const [currentSinkSource, setCurrentSinkSource] = useState<{
     name: string;
     type: 'table' | 'materialized_view';
   } | null>(null);
 
+  // Batch create sink modal states
+  const [batchSinkSource, setBatchSinkSource] = useState<{
+    names: string[];
+    type: 'table' | 'materialized_view';
+  } | null>(null);
+  const [batchSinkLoading, setBatchSinkLoading] = useState(false);
+  const [batchSinkForm] = Form.useForm();
+
   // Load RisingWave connections on mount
   useEffect(() => {
     loadConnections();
@@ -145,6 +210,9 @@ const RisingWaveManager: React.FC = () => {
   const debouncedTablesSearch = useDebounce(tablesState.search, 500);
   const debouncedMvsSearch = useDebounce(mvsState.search, 500);
   const debouncedSinksSearch = useDebounce(sinksState.search, 500);
+  const debouncedViewsSearch = useDebounce(viewsState.search, 500);
+  const debouncedIndexesSearch = useDebounce(indexesState.search, 500);
+  const debouncedSecretsSearch = useDebounce(secretsState.search, 500);
 
   // Load sources when its state changes (using debounced search)
   useEffect(() => {
@@ -174,6 +242,27 @@ const RisingWaveManager: React.FC = () => {
     }
   }, [selectedRwId, selectedSchema, sinksState.currentPage, sinksState.pageSize, debouncedSinksSearch]);
 
+  // Load views when its state changes (using debounced search)
+  useEffect(() => {
+    if (selectedRwId && selectedSchema) {
+      loadViews();
+    }
+  }, [selectedRwId, selectedSchema, viewsState.currentPage, viewsState.pageSize, debouncedViewsSearch]);
+
+  // Load indexes when its state changes (using debounced search)
+  useEffect(() => {
+    if (selectedRwId && selectedSchema) {
+      loadIndexes();
+    }
+  }, [selectedRwId, selectedSchema, indexesState.currentPage, indexesState.pageSize, debouncedIndexesSearch]);
+
+  // Load secrets when its state changes (using debounced search)
+  useEffect(() => {
+    if (selectedRwId && selectedSchema) {
+      loadSecrets();
+    }
+  }, [selectedRwId, selectedSchema, secretsState.currentPage, secretsState.pageSize, debouncedSecretsSearch]);
+
   const loadConnections = async () => {
     try {
       const conns = await api.getAllConnections();
@@ -227,6 +316,21 @@ const RisingWaveManager: React.FC = () => {
       currentPage: 1,
       search: "",
     }));
+    setViewsState(prev => ({
+      ...prev,
+      currentPage: 1,
+      search: "",
+    }));
+    setIndexesState(prev => ({
+      ...prev,
+      currentPage: 1,
+      search: "",
+    }));
+    setSecretsState(prev => ({
+      ...prev,
+      currentPage: 1,
+      search: "",
+    }));
   };
 
   const loadSources = async () => {
@@ -333,6 +437,88 @@ const RisingWaveManager: React.FC = () => {
     }
   };
 
+  const loadViews = async () => {
+    if (!selectedRwId || !selectedSchema) return;
+
+    setViewsState(prev => ({ ...prev, loading: true }));
+    try {
+      const offset = (viewsState.currentPage - 1) * viewsState.pageSize;
+      const response = await api.listRwViews(
+        selectedRwId,
+        selectedSchema,
+        viewsState.search || undefined,
+        viewsState.pageSize,
+        offset
+      );
+
+      setViewsState(prev => ({
+        ...prev,
+        data: response.data,
+        total: response.total,
+        loading: false,
+      }));
+    } catch (error) {
+      message.error("加载 Views 失败: " + error);
+      setViewsState(prev => ({ ...prev, loading: false }));
+    }
+  };
+
+  const loadIndexes = async () => {
+    if (!selectedRwId || !selectedSchema) return;
+
+    setIndexesState(prev => ({ ...prev, loading: true }));
+    try {
+      const offset = (indexesState.currentPage - 1) * indexesState.pageSize;
+      const response = await api.listRwIndexes(
+        selectedRwId,
+        selectedSchema,
+        indexesState.search || undefined,
+        indexesState.pageSize,
+        offset
+      );
+
+      setIndexesState(prev => ({
+        ...prev,
+        data: response.data,
+        total: response.total,
+        loading: false,
+      }));
+    } catch (error) {
+      message.error("加载 Indexes 失败: " + error);
+      setIndexesState(prev => ({ ...prev, loading: false }));
+    }
+  };
+
+  const loadSecrets = async () => {
+    if (!selectedRwId || !selectedSchema) return;
+
+    setSecretsState(prev => ({ ...prev, loading: true }));
+    try {
+      const offset = (secretsState.currentPage - 1) * secretsState.pageSize;
+      const [response, usage] = await Promise.all([
+        api.listRwSecrets(
+          selectedRwId,
+          selectedSchema,
+          secretsState.search || undefined,
+          secretsState.pageSize,
+          offset
+        ),
+        api.listRwSecretUsage(selectedRwId),
+      ]);
+      setSecretUsage(usage);
+
+      setSecretsState(prev => ({
+        ...prev,
+        data: response.data,
+        total: response.total,
+        loading: false,
+      }));
+    } catch (error) {
+      message.error("加载 Secrets 失败: " + error);
+      setSecretsState(prev => ({ ...prev, loading: false }));
+    }
+  };
+
   // Show SQL definition in modal
   const showSqlModal = (title: string, sql: string) => {
     setSqlModalTitle(title);
@@ -352,11 +538,54 @@ const RisingWaveManager: React.FC = () => {
     );
   };
 
+  const runConsoleQuery = async () => {
+    if (!selectedRwId || !consoleSql.trim()) return;
+    setConsoleLoading(true);
+    try {
+      setConsoleResult(await api.queryRisingWave(selectedRwId, consoleSql));
+    } catch (error) {
+      message.error("查询失败: " + error);
+    } finally {
+      setConsoleLoading(false);
+    }
+  };
+
+  // 删除前检查下游对象，存在时确认后用 CASCADE 一起删除；返回 null 表示取消
+  const confirmCascade = async (name: string, schemaName: string): Promise<boolean | null> => {
+    if (!selectedRwId) return null;
+    const dependents = await api.getRwObjectDependencies(selectedRwId, schemaName, name);
+    if (dependents.length === 0) return false;
+    return new Promise((resolve) => {
+      Modal.confirm({
+        title: `${name} 存在 ${dependents.length} 个下游对象`,
+        content: (
+          <div>
+            <Paragraph>删除后以下对象会被一起删除：</Paragraph>
+            <ul>
+              {dependents.map((d) => (
+                <li key={d.id}>
+                  {d.object_type}: {d.schema_name}.{d.name}
+                </li>
+              ))}
+            </ul>
+          </div>
+        ),
+        okText: "一起删除",
+        okButtonProps: { danger: true },
+        cancelText: "取消",
+        onOk: () => resolve(true),
+        onCancel: () => resolve(null),
+      });
+    });
+  };
+
   // Delete handlers
   const handleDeleteSource = async (name: string, schemaName: string) => {
     if (!selectedRwId) return;
     try {
-      await api.deleteRwSource(selectedRwId, schemaName, name);
+      const force = await confirmCascade(name, schemaName);
+      if (force === null) return;
+      await api.deleteRwSource(selectedRwId, schemaName, name, force);
       message.success(`已删除 Source: ${name}`);
       loadSources();
     } catch (error) {
@@ -367,7 +596,9 @@ const RisingWaveManager: React.FC = () => {
   const handleDeleteTable = async (name: string, schemaName: string) => {
     if (!selectedRwId) return;
     try {
-      await api.deleteRwTable(selectedRwId, schemaName, name);
+      const force = await confirmCascade(name, schemaName);
+      if (force === null) return;
+      await api.deleteRwTable(selectedRwId, schemaName, name, force);
       message.success(`已删除 Table: ${name}`);
       loadTables();
     } catch (error) {
@@ -378,7 +609,9 @@ const RisingWaveManager: React.FC = () => {
   const handleDeleteMaterializedView = async (name: string, schemaName: string) => {
     if (!selectedRwId) return;
     try {
-      await api.deleteRwMaterializedView(selectedRwId, schemaName, name);
+      const force = await confirmCascade(name, schemaName);
+      if (force === null) return;
+      await api.deleteRwMaterializedView(selectedRwId, schemaName, name, force);
       message.success(`已删除 Materialized View: ${name}`);
       loadMaterializedViews();
     } catch (error) {
@@ -389,7 +622,9 @@ const RisingWaveManager: React.FC = () => {
   const handleDeleteSink = async (name: string, schemaName: string) => {
     if (!selectedRwId) return;
     try {
-      await api.deleteRwSink(selectedRwId, schemaName, name);
+      const force = await confirmCascade(name, schemaName);
+      if (force === null) return;
+      await api.deleteRwSink(selectedRwId, schemaName, name, force);
       message.success(`已删除 Sink: ${name}`);
       loadSinks();
     } catch (error) {
@@ -397,6 +632,103 @@ const RisingWaveManager: React.FC = () => {
     }
   };
 
+  const handleDeleteSecret = async (name: string, schemaName: string) => {
+    if (!selectedRwId) return;
+    try {
+      await api.deleteRwSecret(selectedRwId, schemaName, name);
+      message.success(`已删除 Secret: ${name}`);
+      loadSecrets();
+    } catch (error) {
+      message.error("删除失败: " + error);
+    }
+  };
+
+  // 先预览未使用的 Secret，确认后删除
+  const handleDeleteUnusedSecrets = async () => {
+    if (!selectedRwId) return;
+    try {
+      const preview = await api.deleteUnusedRwSecrets(selectedRwId, true);
+      if (preview.secrets.length === 0) {
+        message.info("没有未使用的 Secret");
+        return;
+      }
+      Modal.confirm({
+        title: `删除 ${preview.secrets.length} 个未使用的 Secret？`,
+        content: (
+          <div style={{ maxHeight: 240, overflow: "auto" }}>
+            {preview.secrets.map(name => (
+              <div key={name}>{name}</div>
+            ))}
+          </div>
+        ),
+        okText: "删除",
+        okButtonProps: { danger: true },
+        onOk: async () => {
+          const result = await api.deleteUnusedRwSecrets(selectedRwId, false);
+          if (result.failed.length > 0) {
+            message.warning(`已删除 ${result.secrets.length} 个，失败: ${result.failed.join(", ")}`);
+          } else {
+            message.success(`已删除 ${result.secrets.length} 个 Secret`);
+          }
+          loadSecrets();
+        },
+      });
+    } catch (error) {
+      message.error("加载未使用的 Secret 失败: " + error);
+    }
+  };
+
+  const loadOrphans = async () => {
+    if (!selectedRwId) return;
+    setOrphansLoading(true);
+    try {
+      setOrphans(await api.listRwOrphans(selectedRwId));
+      setSelectedOrphanKeys([]);
+    } catch (error) {
+      message.error("扫描遗留对象失败: " + error);
+    } finally {
+      setOrphansLoading(false);
+    }
+  };
+
+  // 先预览语句，确认后删除；未选择时删除列表中的全部遗留对象
+  const handleCleanupOrphans = async () => {
+    if (!selectedRwId) return;
+    const objects = selectedOrphanKeys.length
+      ? orphans.filter(o => selectedOrphanKeys.includes(orphanKey(o)))
+      : orphans;
+    try {
+      const preview = await api.cleanupRwOrphans(selectedRwId, objects, true);
+      if (preview.statements.length === 0) {
+        message.info("没有可删除的遗留对象");
+        loadOrphans();
+        return;
+      }
+      Modal.confirm({
+        title: `删除 ${preview.statements.length} 个遗留对象？`,
+        width: 720,
+        content: (
+          <pre style={{ maxHeight: 320, overflow: "auto", fontSize: 12 }}>
+            {preview.statements.join("\n")}
+          </pre>
+        ),
+        okText: "删除",
+        okButtonProps: { danger: true },
+        onOk: async () => {
+          const result = await api.cleanupRwOrphans(selectedRwId, objects, false);
+          if (result.failed.length > 0) {
+            message.warning(`已删除 ${result.dropped.length} 个，失败: ${result.failed.join(", ")}`);
+          } else {
+            message.success(`已删除 ${result.dropped.length} 个遗留对象`);
+          }
+          loadOrphans();
+        },
+      });
+    } catch (error) {
+      message.error("清理遗留对象失败: " + error);
+    }
+  };
+
   // Batch delete handlers
   const handleBatchDelete = async (
     objectType: 'source' | 'table' | 'materialized_view' | 'sink',
@@ -417,8 +749,13 @@ const RisingWaveManager: React.FC = () => {
         names
       );
 
+      const blocked = Object.keys(result.dependents ?? {});
       if (result.success) {
         message.success(`成功删除 ${result.deleted_count} 个对象`);
+      } else if (blocked.length > 0) {
+        message.warning(
+          `删除完成：成功 ${result.deleted_count} 个，${blocked.length} 个存在下游对象未删除：${blocked.join(", ")}`
+        );
       } else {
         message.warning(
           `删除完成：成功 ${result.deleted_count} 个，失败 ${result.failed.length} 个`
@@ -450,6 +787,18 @@ const RisingWaveManager: React.FC = () => {
         target_table: objectName, // 默认使用相同的表名
       });
     }
+
+    // 加载列和目录中的主键，主键不明确时需要用户选择
+    setSinkKeys(null);
+    if (selectedRwId && selectedSchema) {
+      api
+        .getRwObjectKeys(selectedRwId, selectedSchema, objectName)
+        .then((keys) => {
+          setSinkKeys(keys);
+          sinkForm.setFieldsValue({ primary_keys: keys.primary_keys });
+        })
+        .catch((error) => message.error("加载主键信息失败: " + error));
+    }
   };
 
   // Handle Create Sink
@@ -468,10 +817,15 @@ const RisingWaveManager: React.FC = () => {
         source_type: currentSinkSource.type,
         target_database: values.target_database,
         target_table: values.target_table,
+        primary_keys: values.primary_keys?.length ? values.primary_keys : undefined,
+        nested_columns: Object.fromEntries([
+          ...(values.flatten_columns ?? []).map((c: string) => [c, 'flatten']),
+          ...(values.json_columns ?? []).map((c: string) => [c, 'json']),
+        ]),
       };
 
       const result = await api.createRwSink(request);
-      message.success(result.message || 'Sink 创建成功');
+      message.success(`${result.message || 'Sink 创建成功'}（任务 #${result.task_id}）`);
 
       setCreateSinkModalVisible(false);
       sinkForm.resetFields();
@@ -484,6 +838,66 @@ const RisingWaveManager: React.FC = () => {
     }
   };
 
+  // Show Batch Create Sink modal
+  const showBatchSinkModal = (
+    objectType: 'table' | 'materialized_view',
+    selectedKeys: React.Key[],
+    objects: Array<{ id: number; name: string }>
+  ) => {
+    const names = objects.filter(obj => selectedKeys.includes(obj.id)).map(obj => obj.name);
+    setBatchSinkSource({ names, type: objectType });
+    if (srConnections.length > 0) {
+      batchSinkForm.setFieldsValue({
+        sr_config_id: srConnections[0].id,
+        target_database: srConnections[0].database_name || '',
+      });
+    }
+  };
+
+  // Handle Batch Create Sink：目标表名与来源对象同名
+  const handleBatchCreateSinks = async () => {
+    if (!selectedRwId || !selectedSchema || !batchSinkSource) return;
+
+    try {
+      const values = await batchSinkForm.validateFields();
+      setBatchSinkLoading(true);
+
+      const result = await api.batchCreateRwSinks(
+        selectedRwId,
+        values.sr_config_id,
+        batchSinkSource.names.map((name) => ({
+          schema: selectedSchema,
+          source_object: name,
+          source_type: batchSinkSource.type,
+          target_database: values.target_database,
+          target_table: name,
+        }))
+      );
+
+      const failed = result.results.filter((r) => !r.success);
+      if (failed.length === 0) {
+        message.success(`成功创建 ${result.created_count} 个 Sink（任务 #${result.task_id}）`);
+      } else {
+        message.warning(
+          `创建完成（任务 #${result.task_id}）：成功 ${result.created_count} 个，失败 ${failed.length} 个：${failed
+            .map((r) => `${r.source}（${r.error}）`)
+            .join("；")}`,
+          8
+        );
+      }
+
+      setBatchSinkSource(null);
+      batchSinkForm.resetFields();
+      setSelectedTableKeys([]);
+      setSelectedMvKeys([]);
+      loadSinks();
+    } catch (error) {
+      message.error("批量创建 Sink 失败: " + error);
+    } finally {
+      setBatchSinkLoading(false);
+    }
+  };
+
   // Table columns definitions
   const sourceColumns: ColumnsType<RwSource> = [
     {
@@ -742,6 +1156,163 @@ const RisingWaveManager: React.FC = () => {
     },
   ];
 
+  const viewColumns: ColumnsType<RwView> = [
+    {
+      title: "ID",
+      dataIndex: "id",
+      key: "id",
+      width: 80,
+    },
+    {
+      title: "名称",
+      dataIndex: "name",
+      key: "name",
+    },
+    {
+      title: "Schema",
+      dataIndex: "schema_name",
+      key: "schema_name",
+    },
+    {
+      title: "所有者",
+      dataIndex: "owner",
+      key: "owner",
+    },
+    {
+      title: "操作",
+      key: "actions",
+      width: 120,
+      render: (_: any, record: RwView) =>
+        record.definition && (
+          <Button
+            type="link"
+            size="small"
+            icon={<EyeOutlined />}
+            onClick={() => showSqlModal(`View: ${record.name}`, record.definition!)}
+          >
+            查看
+          </Button>
+        ),
+    },
+  ];
+
+  const indexColumns: ColumnsType<RwIndex> = [
+    {
+      title: "ID",
+      dataIndex: "id",
+      key: "id",
+      width: 80,
+    },
+    {
+      title: "名称",
+      dataIndex: "name",
+      key: "name",
+    },
+    {
+      title: "Schema",
+      dataIndex: "schema_name",
+      key: "schema_name",
+    },
+    {
+      title: "所有者",
+      dataIndex: "owner",
+      key: "owner",
+    },
+    {
+      title: "所属对象",
+      dataIndex: "table_name",
+      key: "table_name",
+    },
+    {
+      title: "操作",
+      key: "actions",
+      width: 120,
+      render: (_: any, record: RwIndex) =>
+        record.definition && (
+          <Button
+            type="link"
+            size="small"
+            icon={<EyeOutlined />}
+            onClick={() => showSqlModal(`Index: ${record.name}`, record.definition!)}
+          >
+            查看
+          </Button>
+        ),
+    },
+  ];
+
+  // Secret 只展示名称，不展示内容
+  const secretColumns: ColumnsType<RwSecret> = [
+    {
+      title: "ID",
+      dataIndex: "id",
+      key: "id",
+      width: 80,
+    },
+    {
+      title: "名称",
+      dataIndex: "name",
+      key: "name",
+    },
+    {
+      title: "Schema",
+      dataIndex: "schema_name",
+      key: "schema_name",
+    },
+    {
+      title: "所有者",
+      dataIndex: "owner",
+      key: "owner",
+    },
+    {
+      title: "引用",
+      key: "references",
+      render: (_, record) => {
+        const usage = secretUsage.find(
+          u => u.schema_name === record.schema_name && u.name === record.name
+        );
+        if (!usage) return "-";
+        if (usage.references.length === 0) return <Tag>未使用</Tag>;
+        return (
+          <Space size={[0, 4]} wrap>
+            {usage.references.map(r => (
+              <Tag key={`${r.kind}:${r.schema_name}.${r.name}`} color={r.kind === "source" ? "blue" : "green"}>
+                {r.kind} {r.schema_name}.{r.name}
+              </Tag>
+            ))}
+          </Space>
+        );
+      },
+    },
+    {
+      title: "操作",
+      key: "action",
+      width: 100,
+      render: (_, record) => {
+        const usage = secretUsage.find(
+          u => u.schema_name === record.schema_name && u.name === record.name
+        );
+        return (
+          <Popconfirm
+            title={`确定要删除 Secret ${record.name} 吗？`}
+            onConfirm={() => handleDeleteSecret(record.name, record.schema_name)}
+            disabled={!!usage && usage.references.length > 0}
+          >
+            <Button
+              type="link"
+              danger
+              size="small"
+              icon={<DeleteOutlined />}
+              disabled={!!usage && usage.references.length > 0}
+            >
+              删除
+            </Button>
+          </Popconfirm>
+        );
+      },
+    },
+  ];
+
   return (
     <div style={{ padding: 24 }}>
       <Title level={2}>RisingWave Object Manager</Title>
@@ -878,6 +1449,12 @@ const RisingWaveManager: React.FC = () => {
                       批量删除 ({selectedTableKeys.length})
                     </Button>
                   </Popconfirm>
+                  <Button
+                    style={{ marginLeft: 8 }}
+                    onClick={() => showBatchSinkModal('table', selectedTableKeys, tablesState.data)}
+                  >
+                    批量创建 Sink ({selectedTableKeys.length})
+                  </Button>
                 </div>
               )}
 
@@ -941,6 +1518,12 @@ const RisingWaveManager: React.FC = () => {
                       批量删除 ({selectedMvKeys.length})
                     </Button>
                   </Popconfirm>
+                  <Button
+                    style={{ marginLeft: 8 }}
+                    onClick={() => showBatchSinkModal('materialized_view', selectedMvKeys, mvsState.data)}
+                  >
+                    批量创建 Sink ({selectedMvKeys.length})
+                  </Button>
                 </div>
               )}
 
@@ -1030,6 +1613,218 @@ const RisingWaveManager: React.FC = () => {
               />
             </Spin>
           </TabPane>
+
+          <TabPane tab={`Views (${viewsState.total})`} key="views">
+            <Spin spinning={viewsState.loading}>
+              <Space direction="vertical" style={{ width: "100%", marginBottom: 16 }}>
+                <Input.Search
+                  placeholder="搜索对象名称..."
+                  value={viewsState.search}
+                  onChange={(e) => setViewsState(prev => ({
+                    ...prev,
+                    search: e.target.value,
+                    currentPage: 1
+                  }))}
+                  onSearch={() => setViewsState(prev => ({ ...prev, currentPage: 1 }))}
+                  allowClear
+                  style={{ width: 300 }}
+                />
+              </Space>
+
+              <Table
+                columns={viewColumns}
+                dataSource={viewsState.data}
+                rowKey="id"
+                pagination={{
+                  current: viewsState.currentPage,
+                  pageSize: viewsState.pageSize,
+                  total: viewsState.total,
+                  showSizeChanger: true,
+                  showTotal: (total) => `共 ${total} 条`,
+                  pageSizeOptions: ['10', '20', '50', '100'],
+                  onChange: (page, size) => {
+                    setViewsState(prev => ({
+                      ...prev,
+                      currentPage: page,
+                      pageSize: size,
+                    }));
+                  },
+                }}
+              />
+            </Spin>
+          </TabPane>
+
+          <TabPane tab={`Indexes (${indexesState.total})`} key="indexes">
+            <Spin spinning={indexesState.loading}>
+              <Space direction="vertical" style={{ width: "100%", marginBottom: 16 }}>
+                <Input.Search
+                  placeholder="搜索对象名称..."
+                  value={indexesState.search}
+                  onChange={(e) => setIndexesState(prev => ({
+                    ...prev,
+                    search: e.target.value,
+                    currentPage: 1
+                  }))}
+                  onSearch={() => setIndexesState(prev => ({ ...prev, currentPage: 1 }))}
+                  allowClear
+                  style={{ width: 300 }}
+                />
+              </Space>
+
+              <Table
+                columns={indexColumns}
+                dataSource={indexesState.data}
+                rowKey="id"
+                pagination={{
+                  current: indexesState.currentPage,
+                  pageSize: indexesState.pageSize,
+                  total: indexesState.total,
+                  showSizeChanger: true,
+                  showTotal: (total) => `共 ${total} 条`,
+                  pageSizeOptions: ['10', '20', '50', '100'],
+                  onChange: (page, size) => {
+                    setIndexesState(prev => ({
+                      ...prev,
+                      currentPage: page,
+                      pageSize: size,
+                    }));
+                  },
+                }}
+              />
+            </Spin>
+          </TabPane>
+
+          <TabPane tab={`Secrets (${secretsState.total})`} key="secrets">
+            <Spin spinning={secretsState.loading}>
+              <Space direction="vertical" style={{ width: "100%", marginBottom: 16 }}>
+                <Input.Search
+                  placeholder="搜索对象名称..."
+                  value={secretsState.search}
+                  onChange={(e) => setSecretsState(prev => ({
+                    ...prev,
+                    search: e.target.value,
+                    currentPage: 1
+                  }))}
+                  onSearch={() => setSecretsState(prev => ({ ...prev, currentPage: 1 }))}
+                  allowClear
+                  style={{ width: 300 }}
+                />
+                <Button danger icon={<DeleteOutlined />} onClick={handleDeleteUnusedSecrets}>
+                  删除未使用的 Secret
+                </Button>
+              </Space>
+
+              <Table
+                columns={secretColumns}
+                dataSource={secretsState.data}
+                rowKey="id"
+                pagination={{
+                  current: secretsState.currentPage,
+                  pageSize: secretsState.pageSize,
+                  total: secretsState.total,
+                  showSizeChanger: true,
+                  showTotal: (total) => `共 ${total} 条`,
+                  pageSizeOptions: ['10', '20', '50', '100'],
+                  onChange: (page, size) => {
+                    setSecretsState(prev => ({
+                      ...prev,
+                      currentPage: page,
+                      pageSize: size,
+                    }));
+                  },
+                }}
+              />
+            </Spin>
+          </TabPane>
+
+          <TabPane tab={`遗留对象 (${orphans.length})`} key="orphans">
+            <Spin spinning={orphansLoading}>
+              <Space style={{ marginBottom: 16 }}>
+                <Button onClick={loadOrphans} disabled={!selectedRwId}>
+                  扫描
+                </Button>
+                <Button
+                  danger
+                  icon={<DeleteOutlined />}
+                  onClick={handleCleanupOrphans}
+                  disabled={orphans.length === 0}
+                >
+                  {selectedOrphanKeys.length ? `删除选中 (${selectedOrphanKeys.length})` : "全部删除"}
+                </Button>
+                <span style={{ color: "#888" }}>
+                  扫描所有同步目标 schema 中按本工具命名规则创建、但没有对应同步任务的 Sink / Table / Source / Secret
+                </span>
+              </Space>
+              <Table
+                size="small"
+                rowKey={orphanKey}
+                dataSource={orphans}
+                rowSelection={{
+                  selectedRowKeys: selectedOrphanKeys,
+                  onChange: setSelectedOrphanKeys,
+                }}
+                columns={[
+                  { title: "类型", dataIndex: "kind", key: "kind", render: (kind: string) => <Tag>{kind}</Tag> },
+                  { title: "Schema", dataIndex: "schema_name", key: "schema_name" },
+                  { title: "名称", dataIndex: "name", key: "name" },
+                  {
+                    title: "原因",
+                    dataIndex: "reason",
+                    key: "reason",
+                    render: (reason: OrphanReason) => (
+                      <Tag color={orphanReasonLabels[reason].color}>{orphanReasonLabels[reason].label}</Tag>
+                    ),
+                  },
+                ]}
+                pagination={{ pageSize: 50, showTotal: (total) => `共 ${total} 条` }}
+              />
+            </Spin>
+          </TabPane>
+
+          <TabPane tab="SQL 控制台" key="console">
+            <Space direction="vertical" style={{ width: "100%" }}>
+              <Input.TextArea
+                value={consoleSql}
+                onChange={(e) => setConsoleSql(e.target.value)}
+                placeholder="只允许单条只读语句：SELECT / WITH / SHOW / DESCRIBE / EXPLAIN / VALUES"
+                autoSize={{ minRows: 4, maxRows: 12 }}
+                style={{ fontFamily: "monospace" }}
+              />
+              <Space>
+                <Button
+                  type="primary"
+                  onClick={runConsoleQuery}
+                  loading={consoleLoading}
+                  disabled={!selectedRwId || !consoleSql.trim()}
+                >
+                  执行
+                </Button>
+                {consoleResult && (
+                  <span>
+                    {consoleResult.rows.length} 行，{consoleResult.elapsed_ms} ms
+                    {consoleResult.truncated && (
+                      <Tag color="orange" style={{ marginLeft: 8 }}>已截断</Tag>
+                    )}
+                  </span>
+                )}
+              </Space>
+              {consoleResult && (
+                <Table
+                  size="small"
+                  scroll={{ x: true }}
+                  columns={consoleResult.columns.map((name, index) => ({
+                    title: name,
+                    key: index,
+                    render: (_: unknown, row: (string | null)[]) =>
+                      row[index] === null ? <Tag>NULL</Tag> : row[index],
+                  }))}
+                  dataSource={consoleResult.rows}
+                  rowKey={(_, index) => String(index)}
+                  pagination={{ pageSize: 50, showTotal: (total) => `共 ${total} 条` }}
+                />
+              )}
+            </Space>
+          </TabPane>
         </Tabs>
       </Card>
 
@@ -1073,6 +1868,44 @@ const RisingWaveManager: React.FC = () => {
         </Paragraph>
       </Modal>
 
+      {/* Batch Create Sink Modal */}
+      <Modal
+        title={`批量创建 Sink 到 StarRocks - ${batchSinkSource?.names.length ?? 0} 个${batchSinkSource?.type === 'table' ? '表' : '物化视图'}`}
+        open={batchSinkSource !== null}
+        onCancel={() => {
+          setBatchSinkSource(null);
+          batchSinkForm.resetFields();
+        }}
+        onOk={handleBatchCreateSinks}
+        confirmLoading={batchSinkLoading}
+        width={600}
+      >
+        <Form form={batchSinkForm} layout="vertical" style={{ marginTop: 24 }}>
+          <Form.Item
+            label="StarRocks 连接"
+            name="sr_config_id"
+            rules={[{ required: true, message: "请选择 StarRocks 连接" }]}
+          >
+            <Select placeholder="选择 StarRocks 连接">
+              {srConnections.map((conn) => (
+                <Select.Option key={conn.id} value={conn.id}>
+                  {conn.name} ({conn.host}:{conn.port})
+                </Select.Option>
+              ))}
+            </Select>
+          </Form.Item>
+
+          <Form.Item
+            label="目标数据库"
+            name="target_database"
+            extra="目标表与 RisingWave 中的对象同名，主键使用目录中的主键"
+            rules={[{ required: true, message: "请输入目标数据库名称" }]}
+          >
+            <Input placeholder="输入目标数据库名称" />
+          </Form.Item>
+        </Form>
+      </Modal>
+
       {/* Create Sink Modal */}
       <Modal
         title={`创建 Sink 到 StarRocks - ${currentSinkSource?.type === 'table' ? '表' : '物化视图'}: ${currentSinkSource?.name}`}
@@ -1120,6 +1953,55 @@ const RisingWaveManager: React.FC = () => {
           >
             <Input placeholder="输入目标表名称" />
           </Form.Item>
+
+          <Form.Item
+            label="主键列"
+            name="primary_keys"
+            extra={
+              sinkKeys?.ambiguous
+                ? "RisingWave 中没有可用的主键（无主键或包含隐藏列），请选择能唯一标识一行的列"
+                : sinkKeys?.distribution_keys.length
+                  ? `分布键：${sinkKeys.distribution_keys.join(", ")}`
+                  : undefined
+            }
+            rules={[{ required: !!sinkKeys?.ambiguous, message: "请选择主键列" }]}
+          >
+            <Select mode="multiple" placeholder="选择主键列" loading={!sinkKeys}>
+              {(sinkKeys?.columns ?? []).map((column) => (
+                <Select.Option key={column} value={column}>
+                  {column}
+                </Select.Option>
+              ))}
+            </Select>
+          </Form.Item>
+
+          <Form.Item
+            label="展开 STRUCT 列"
+            name="flatten_columns"
+            extra="STRUCT 字段写入独立的列，例如 addr.city 写入 addr_city"
+          >
+            <Select mode="multiple" placeholder="不展开" allowClear>
+              {(sinkKeys?.columns ?? []).map((column) => (
+                <Select.Option key={column} value={column}>
+                  {column}
+                </Select.Option>
+              ))}
+            </Select>
+          </Form.Item>
+
+          <Form.Item
+            label="序列化为 JSON 字符串的列"
+            name="json_columns"
+            extra="STRUCT、数组、MAP 或 JSON 列写入 STRING 列，未选择时写入 JSON 列"
+          >
+            <Select mode="multiple" placeholder="不序列化" allowClear>
+              {(sinkKeys?.columns ?? []).map((column) => (
+                <Select.Option key={column} value={column}>
+                  {column}
+                </Select.Option>
+              ))}
+            </Select>
+          </Form.Item>
         </Form>
       </Modal>
     </div>
@@ -0,0 +1,268 @@
+import React, { useState, useEffect } from 'react';
+import {
+  Card,
+  Button,
+  Table,
+  Modal,
+  Form,
+  Input,
+  Switch,
+  message,
+  Space,
+  Tag,
+  Popconfirm,
+  Tooltip,
+} from 'antd';
+import { PlusOutlined, DeleteOutlined, EditOutlined } from '@ant-design/icons';
+import type { ColumnsType } from 'antd/es/table';
+import type { ScheduledRun, SyncSchedule } from '../types';
+import * as api from '../services/api';
+
+// 浏览器所在时区，作为新建定时同步的默认值
+const browserTimezone = () => Intl.DateTimeFormat().resolvedOptions().timeZone || 'UTC';
+
+const renderRuns = (runs: ScheduledRun[]) =>
+  runs.length ? (
+    <Space direction="vertical" size={0}>
+      {runs.map((run) => (
+        <span key={run.utc}>
+          {run.local}
+          <span style={{ color: '#999' }}>（UTC {run.utc}）</span>
+        </span>
+      ))}
+    </Space>
+  ) : (
+    '-'
+  );
+
+const Schedules: React.FC = () => {
+  const [schedules, setSchedules] = useState<SyncSchedule[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [modalVisible, setModalVisible] = useState(false);
+  const [saving, setSaving] = useState(false);
+  const [editingId, setEditingId] = useState<number | null>(null);
+  const [preview, setPreview] = useState<ScheduledRun[]>([]);
+  const [form] = Form.useForm();
+
+  const loadData = async () => {
+    setLoading(true);
+    try {
+      setSchedules(await api.listSchedules());
+    } catch (error) {
+      message.error('加载定时同步失败: ' + error);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    loadData();
+  }, []);
+
+  const handleOpenCreate = () => {
+    setEditingId(null);
+    setPreview([]);
+    form.resetFields();
+    form.setFieldsValue({ cron: '0 2 * * *', timezone: browserTimezone(), requests: '[]', enabled: true });
+    setModalVisible(true);
+  };
+
+  const handleOpenEdit = (record: SyncSchedule) => {
+    setEditingId(record.id);
+    setPreview(record.next_runs);
+    form.resetFields();
+    form.setFieldsValue({ ...record, requests: JSON.stringify(record.requests, null, 2) });
+    setModalVisible(true);
+  };
+
+  const handlePreview = async () => {
+    try {
+      const { cron, timezone } = form.getFieldsValue(['cron', 'timezone']);
+      setPreview(await api.previewSchedule(cron, timezone));
+    } catch (error) {
+      setPreview([]);
+      message.error('预览失败: ' + error);
+    }
+  };
+
+  const handleSave = async () => {
+    try {
+      const values = await form.validateFields();
+      setSaving(true);
+      const request = { ...values, requests: JSON.parse(values.requests) };
+      if (editingId) {
+        await api.updateSchedule(editingId, request);
+      } else {
+        await api.createSchedule(request, localStorage.getItem('rw_cdc_sr_user') ?? undefined);
+      }
+      message.success('保存成功');
+      setModalVisible(false);
+      loadData();
+    } catch (error) {
+      message.error('保存失败: ' + error);
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await api.deleteSchedule(id);
+      message.success('已删除');
+      loadData();
+    } catch (error) {
+      message.error('删除失败: ' + error);
+    }
+  };
+
+  const columns: ColumnsType<SyncSchedule> = [
+    { title: '名称', dataIndex: 'name', key: 'name' },
+    {
+      title: '计划',
+      key: 'cron',
+      render: (_, record) => (
+        <Space direction="vertical" size={0}>
+          <code>{record.cron}</code>
+          <span style={{ color: '#999' }}>{record.timezone}</span>
+        </Space>
+      ),
+    },
+    {
+      title: '表',
+      dataIndex: 'requests',
+      key: 'requests',
+      render: (requests: SyncSchedule['requests']) => (
+        <Tooltip title={requests.map((r) => `${r.mysql_database}.${r.mysql_table}`).join(', ')}>
+          {requests.length} 张
+        </Tooltip>
+      ),
+    },
+    {
+      title: '接下来的运行',
+      dataIndex: 'next_runs',
+      key: 'next_runs',
+      render: (runs: ScheduledRun[]) => renderRuns(runs.slice(0, 3)),
+    },
+    {
+      title: '上次运行',
+      key: 'last_run',
+      render: (_, record) =>
+        record.last_run_at ? (
+          <Space direction="vertical" size={0}>
+            <span>{new Date(record.last_run_at).toLocaleString()}</span>
+            {record.last_task_id && <span>任务 #{record.last_task_id}</span>}
+            {record.last_error && <span style={{ color: 'red' }}>{record.last_error}</span>}
+          </Space>
+        ) : (
+          '-'
+        ),
+    },
+    {
+      title: '状态',
+      dataIndex: 'enabled',
+      key: 'enabled',
+      render: (enabled: boolean) =>
+        enabled ? <Tag color="green">启用</Tag> : <Tag>停用</Tag>,
+    },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_, record) => (
+        <Space>
+          <Button type="link" icon={<EditOutlined />} onClick={() => handleOpenEdit(record)}>
+            编辑
+          </Button>
+          <Popconfirm title="确定删除该定时同步吗？" onConfirm={() => handleDelete(record.id)}>
+            <Button type="link" danger icon={<DeleteOutlined />}>
+              删除
+            </Button>
+          </Popconfirm>
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <div>
+      <Card
+        title="定时同步"
+        extra={
+          <Button type="primary" icon={<PlusOutlined />} onClick={handleOpenCreate}>
+            新建定时同步
+          </Button>
+        }
+      >
+        <Table
+          columns={columns}
+          dataSource={schedules}
+          rowKey="id"
+          loading={loading}
+          pagination={false}
+        />
+        <div style={{ marginTop: 8, color: '#888' }}>
+          按定时同步所在时区的本地时间运行；上一次提交的任务仍在执行时跳过本次运行
+        </div>
+      </Card>
+
+      <Modal
+        title={editingId ? '编辑定时同步' : '新建定时同步'}
+        open={modalVisible}
+        onCancel={() => setModalVisible(false)}
+        onOk={handleSave}
+        confirmLoading={saving}
+        width={720}
+      >
+        <Form form={form} layout="vertical">
+          <Form.Item label="名称" name="name" rules={[{ required: true, message: '请输入名称' }]}>
+            <Input placeholder="例如 nightly-orders" />
+          </Form.Item>
+          <Space align="start">
+            <Form.Item
+              label="Cron 表达式"
+              name="cron"
+              rules={[{ required: true, message: '请输入 cron 表达式' }]}
+              extra="分 时 日 月 周，星期建议使用 MON-FRI 等缩写"
+            >
+              <Input style={{ width: 240 }} />
+            </Form.Item>
+            <Form.Item
+              label="时区"
+              name="timezone"
+              rules={[{ required: true, message: '请输入时区' }]}
+              extra="IANA 时区名，例如 Asia/Shanghai"
+            >
+              <Input style={{ width: 220 }} />
+            </Form.Item>
+            <Form.Item label=" ">
+              <Button onClick={handlePreview}>预览</Button>
+            </Form.Item>
+          </Space>
+          {preview.length > 0 && <div style={{ marginBottom: 16 }}>{renderRuns(preview)}</div>}
+          <Form.Item
+            label="同步请求（JSON）"
+            name="requests"
+            rules={[
+              { required: true, message: '请输入同步请求' },
+              {
+                validator: async (_, value) => {
+                  const parsed = JSON.parse(value);
+                  if (!Array.isArray(parsed) || parsed.length === 0) {
+                    throw new Error('需要至少一个同步请求');
+                  }
+                },
+              },
+            ]}
+            extra="与 /api/sync/multiple 的请求体相同"
+          >
+            <Input.TextArea rows={8} style={{ fontFamily: 'monospace' }} />
+          </Form.Item>
+          <Form.Item label="启用" name="enabled" valuePropName="checked">
+            <Switch />
+          </Form.Item>
+        </Form>
+      </Modal>
+    </div>
+  );
+};
+
+export default Schedules;
@@ -0,0 +1,337 @@
+import React, { useState, useEffect } from "react";
+import {
+  Card,
+  Select,
+  Table,
+  message,
+  Typography,
+  Space,
+  Tag,
+  Button,
+  Drawer,
+  Tabs,
+  Input,
+} from "antd";
+import { EyeOutlined } from "@ant-design/icons";
+import type { ColumnsType } from "antd/es/table";
+import * as api from "../services/api";
+import type {
+  DatabaseConfig,
+  SrTable,
+  SrColumn,
+  SrTableSchema,
+  SrPartition,
+} from "../types";
+
+const { Title, Paragraph } = Typography;
+
+const StarRocksBrowser: React.FC = () => {
+  const [connections, setConnections] = useState<DatabaseConfig[]>([]);
+  const [selectedId, setSelectedId] = useState<number | null>(null);
+  const [databases, setDatabases] = useState<string[]>([]);
+  const [selectedDatabase, setSelectedDatabase] = useState<string | null>(null);
+
+  const [tables, setTables] = useState<SrTable[]>([]);
+  const [total, setTotal] = useState(0);
+  const [currentPage, setCurrentPage] = useState(1);
+  const [pageSize, setPageSize] = useState(20);
+  const [search, setSearch] = useState("");
+  const [loading, setLoading] = useState(false);
+
+  // Table detail drawer
+  const [detailTable, setDetailTable] = useState<string | null>(null);
+  const [detailSchema, setDetailSchema] = useState<SrTableSchema | null>(null);
+  const [detailPartitions, setDetailPartitions] = useState<SrPartition[]>([]);
+  const [detailLoading, setDetailLoading] = useState(false);
+
+  useEffect(() => {
+    loadConnections();
+  }, []);
+
+  useEffect(() => {
+    if (selectedId) {
+      loadDatabases();
+    }
+  }, [selectedId]);
+
+  useEffect(() => {
+    if (selectedId && selectedDatabase) {
+      loadTables();
+    }
+  }, [selectedId, selectedDatabase, currentPage, pageSize, search]);
+
+  const loadConnections = async () => {
+    try {
+      const conns = await api.getAllConnections();
+      const srConns = conns.filter(
+        (c) => c.db_type === "starrocks" || c.db_type === "doris"
+      );
+      setConnections(srConns);
+      if (srConns.length > 0) {
+        setSelectedId(srConns[0].id);
+      }
+    } catch (error) {
+      message.error("加载连接失败: " + error);
+    }
+  };
+
+  const loadDatabases = async () => {
+    if (!selectedId) return;
+
+    try {
+      const list = await api.listSrDatabases(selectedId);
+      setDatabases(list);
+      setCurrentPage(1);
+      setSelectedDatabase(
+        list.find((db) => db !== "information_schema" && db !== "_statistics_") ??
+          list[0] ??
+          null
+      );
+    } catch (error) {
+      message.error("加载数据库失败: " + error);
+    }
+  };
+
+  const loadTables = async () => {
+    if (!selectedId || !selectedDatabase) return;
+
+    setLoading(true);
+    try {
+      const response = await api.listSrTables(
+        selectedId,
+        selectedDatabase,
+        search || undefined,
+        pageSize,
+        (currentPage - 1) * pageSize
+      );
+      setTables(response.data);
+      setTotal(response.total);
+    } catch (error) {
+      message.error("加载表失败: " + error);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const openDetail = async (table: string) => {
+    if (!selectedId || !selectedDatabase) return;
+
+    setDetailTable(table);
+    setDetailSchema(null);
+    setDetailPartitions([]);
+    setDetailLoading(true);
+    try {
+      const [schema, partitions] = await Promise.all([
+        api.getSrTableSchema(selectedId, selectedDatabase, table),
+        api.listSrPartitions(selectedId, selectedDatabase, table),
+      ]);
+      setDetailSchema(schema);
+      setDetailPartitions(partitions);
+    } catch (error) {
+      message.error("加载表结构失败: " + error);
+    } finally {
+      setDetailLoading(false);
+    }
+  };
+
+  const tableColumns: ColumnsType<SrTable> = [
+    { title: "名称", dataIndex: "name", key: "name" },
+    {
+      title: "类型",
+      dataIndex: "table_type",
+      key: "table_type",
+      render: (type?: string) => type && <Tag>{type}</Tag>,
+    },
+    { title: "行数", dataIndex: "table_rows", key: "table_rows" },
+    { title: "创建时间", dataIndex: "create_time", key: "create_time" },
+    { title: "注释", dataIndex: "comment", key: "comment", ellipsis: true },
+    {
+      title: "操作",
+      key: "action",
+      render: (_, record) => (
+        <Button
+          type="link"
+          icon={<EyeOutlined />}
+          onClick={() => openDetail(record.name)}
+        >
+          查看
+        </Button>
+      ),
+    },
+  ];
+
+  const columnColumns: ColumnsType<SrColumn> = [
+    {
+      title: "列名",
+      dataIndex: "name",
+      key: "name",
+      render: (name: string, record) => (
+        <Space>
+          {name}
+          {record.is_key && <Tag color="blue">KEY</Tag>}
+        </Space>
+      ),
+    },
+    { title: "类型", dataIndex: "data_type", key: "data_type" },
+    {
+      title: "可空",
+      dataIndex: "is_nullable",
+      key: "is_nullable",
+      render: (nullable: boolean) => (nullable ? "YES" : "NO"),
+    },
+    { title: "默认值", dataIndex: "default_value", key: "default_value" },
+    { title: "注释", dataIndex: "comment", key: "comment" },
+  ];
+
+  const partitionColumns: ColumnsType<SrPartition> = [
+    { title: "分区", dataIndex: "name", key: "name" },
+    { title: "状态", dataIndex: "state", key: "state" },
+    { title: "分区键", dataIndex: "partition_key", key: "partition_key" },
+    { title: "范围", dataIndex: "range", key: "range", ellipsis: true },
+    { title: "分桶键", dataIndex: "distribution_key", key: "distribution_key" },
+    { title: "分桶数", dataIndex: "buckets", key: "buckets" },
+    { title: "行数", dataIndex: "row_count", key: "row_count" },
+    { title: "数据量", dataIndex: "data_size", key: "data_size" },
+  ];
+
+  return (
+    <div style={{ padding: 24 }}>
+      <Title level={2}>StarRocks 目录</Title>
+
+      <Card style={{ marginBottom: 24 }}>
+        <Space size="large">
+          <div>
+            <label style={{ marginRight: 8 }}>StarRocks 连接:</label>
+            <Select
+              style={{ width: 300 }}
+              value={selectedId}
+              onChange={setSelectedId}
+              placeholder="选择 StarRocks 连接"
+            >
+              {connections.map((conn) => (
+                <Select.Option key={conn.id} value={conn.id}>
+                  {conn.name} ({conn.host}:{conn.port})
+                </Select.Option>
+              ))}
+            </Select>
+          </div>
+
+          <div>
+            <label style={{ marginRight: 8 }}>数据库:</label>
+            <Select
+              style={{ width: 200 }}
+              value={selectedDatabase}
+              onChange={(db) => {
+                setSelectedDatabase(db);
+                setCurrentPage(1);
+              }}
+              placeholder="选择数据库"
+              disabled={!selectedId}
+              showSearch
+            >
+              {databases.map((db) => (
+                <Select.Option key={db} value={db}>
+                  {db}
+                </Select.Option>
+              ))}
+            </Select>
+          </div>
+        </Space>
+      </Card>
+
+      <Card>
+        <Space direction="vertical" style={{ width: "100%", marginBottom: 16 }}>
+          <Input.Search
+            placeholder="搜索表名..."
+            onSearch={(value) => {
+              setSearch(value);
+              setCurrentPage(1);
+            }}
+            allowClear
+            style={{ width: 300 }}
+          />
+        </Space>
+
+        <Table
+          columns={tableColumns}
+          dataSource={tables}
+          rowKey="name"
+          loading={loading}
+          pagination={{
+            current: currentPage,
+            pageSize,
+            total,
+            showSizeChanger: true,
+            showTotal: (total) => `共 ${total} 条`,
+            pageSizeOptions: ["10", "20", "50", "100"],
+            onChange: (page, size) => {
+              setCurrentPage(page);
+              setPageSize(size);
+            },
+          }}
+        />
+      </Card>
+
+      <Drawer
+        title={`${selectedDatabase}.${detailTable}`}
+        open={detailTable !== null}
+        onClose={() => setDetailTable(null)}
+        width={960}
+        loading={detailLoading}
+      >
+        <Tabs
+          defaultActiveKey="columns"
+          items={[
+            {
+              key: "columns",
+              label: "列",
+              children: (
+                <Table
+                  columns={columnColumns}
+                  dataSource={detailSchema?.columns ?? []}
+                  rowKey="name"
+                  pagination={false}
+                  size="small"
+                />
+              ),
+            },
+            {
+              key: "partitions",
+              label: `分区 (${detailPartitions.length})`,
+              children: (
+                <Table
+                  columns={partitionColumns}
+                  dataSource={detailPartitions}
+                  rowKey="name"
+                  pagination={false}
+                  size="small"
+                />
+              ),
+            },
+            {
+              key: "ddl",
+              label: "建表语句",
+              children: (
+                <Paragraph
+                  code
+                  copyable
+                  style={{
+                    background: "#f5f5f5",
+                    padding: "12px",
+                    borderRadius: "4px",
+                    whiteSpace: "pre-wrap",
+                    wordBreak: "break-word",
+                  }}
+                >
+                  {detailSchema?.create_table}
+                </Paragraph>
+              ),
+            },
+          ]}
+        />
+      </Drawer>
+    </div>
+  );
+};
+
+export default StarRocksBrowser;
@@ -0,0 +1,353 @@
+import React, { useState, useEffect } from 'react';
+import {
+  Card,
+  Button,
+  Table,
+  Modal,
+  Form,
+  Input,
+  Select,
+  message,
+  Space,
+  Popconfirm,
+  Tooltip,
+  Tag,
+} from 'antd';
+import {
+  PlusOutlined,
+  DeleteOutlined,
+  EditOutlined,
+  PlayCircleOutlined,
+  PauseCircleOutlined,
+} from '@ant-design/icons';
+import type { ColumnsType } from 'antd/es/table';
+import type { DatabaseConfig, SyncMapping } from '../types';
+import * as api from '../services/api';
+
+const SyncMappings: React.FC = () => {
+  const [mappings, setMappings] = useState<SyncMapping[]>([]);
+  const [connections, setConnections] = useState<DatabaseConfig[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [modalVisible, setModalVisible] = useState(false);
+  const [saving, setSaving] = useState(false);
+  const [editingId, setEditingId] = useState<number | null>(null);
+  // 打开编辑时的版本号，保存时服务端校验，被他人修改过则返回 409
+  const [editingVersion, setEditingVersion] = useState<number | undefined>();
+  const [form] = Form.useForm();
+
+  const loadData = async () => {
+    setLoading(true);
+    try {
+      setMappings(await api.listSyncMappings());
+    } catch (error) {
+      message.error('加载同步映射失败: ' + error);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    loadData();
+    api.getAllConnections().then(setConnections).catch(() => setConnections([]));
+  }, []);
+
+  const connectionOptions = (dbType: string) =>
+    connections
+      .filter((c) => c.db_type === dbType)
+      .map((c) => ({ label: c.name, value: c.id }));
+  const connectionName = (id: number) => connections.find((c) => c.id === id)?.name ?? `#${id}`;
+
+  const handleOpenCreate = () => {
+    setEditingId(null);
+    form.resetFields();
+    form.setFieldsValue({ tables: '[]', options: '{}' });
+    setModalVisible(true);
+  };
+
+  const handleOpenEdit = (record: SyncMapping) => {
+    setEditingId(record.id);
+    setEditingVersion(record.version);
+    form.resetFields();
+    form.setFieldsValue({
+      ...record,
+      tables: JSON.stringify(record.tables, null, 2),
+      options: JSON.stringify(record.options, null, 2),
+    });
+    setModalVisible(true);
+  };
+
+  const handleSave = async () => {
+    try {
+      const values = await form.validateFields();
+      setSaving(true);
+      const request = {
+        ...values,
+        tables: JSON.parse(values.tables),
+        options: JSON.parse(values.options || '{}'),
+      };
+      if (editingId) {
+        await api.updateSyncMapping(editingId, { ...request, version: editingVersion });
+      } else {
+        await api.createSyncMapping(request, localStorage.getItem('rw_cdc_sr_user') ?? undefined);
+      }
+      message.success('保存成功');
+      setModalVisible(false);
+      loadData();
+    } catch (error) {
+      message.error('保存失败: ' + error);
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleRun = async (id: number) => {
+    try {
+      const result = await api.runSyncMapping(id);
+      if (result.approval_id) {
+        message.info(`需要审批，已创建审批单 #${result.approval_id}`);
+      } else if (result.deduplicated) {
+        message.info(`相同的同步正在执行：任务 #${result.task_id}`);
+      } else {
+        message.success(`同步任务已创建：任务 #${result.task_id}`);
+      }
+      loadData();
+    } catch (error) {
+      message.error('运行失败: ' + error);
+    }
+  };
+
+  // 暂停或恢复整个管道：sink、相关定时同步和映射状态一起变更
+  const handleTogglePause = async (record: SyncMapping) => {
+    const user = localStorage.getItem('rw_cdc_sr_user') ?? undefined;
+    try {
+      const result = record.paused
+        ? await api.resumePipeline(record.id, user)
+        : await api.pausePipeline(record.id, user);
+      const action = result.paused ? '暂停' : '恢复';
+      if (result.failed.length > 0) {
+        message.warning(`部分 sink ${action}失败: ${result.failed.join('; ')}`);
+      } else {
+        message.success(
+          `已${action} ${result.sinks.length} 个 sink、${result.schedule_ids.length} 个定时同步`
+        );
+      }
+      loadData();
+    } catch (error) {
+      message.error('操作失败: ' + error);
+    }
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await api.deleteSyncMapping(id);
+      message.success('已删除');
+      loadData();
+    } catch (error) {
+      message.error('删除失败: ' + error);
+    }
+  };
+
+  const columns: ColumnsType<SyncMapping> = [
+    {
+      title: '名称',
+      dataIndex: 'name',
+      key: 'name',
+      render: (name: string, record) => (
+        <Space direction="vertical" size={0}>
+          <Space>
+            <span>{name}</span>
+            {record.paused && (
+              <Tooltip
+                title={
+                  record.paused_at
+                    ? `暂停于 ${new Date(record.paused_at).toLocaleString()}`
+                    : undefined
+                }
+              >
+                <Tag color="orange">已暂停</Tag>
+              </Tooltip>
+            )}
+          </Space>
+          {record.description && <span style={{ color: '#999' }}>{record.description}</span>}
+        </Space>
+      ),
+    },
+    {
+      title: '连接',
+      key: 'connections',
+      render: (_, record) =>
+        [record.mysql_config_id, record.rw_config_id, record.sr_config_id]
+          .map(connectionName)
+          .join(' → '),
+    },
+    {
+      title: '表',
+      dataIndex: 'tables',
+      key: 'tables',
+      render: (tables: SyncMapping['tables']) => (
+        <Tooltip title={tables.map((t) => `${t.mysql_database}.${t.mysql_table}`).join(', ')}>
+          {tables.length} 张
+        </Tooltip>
+      ),
+    },
+    {
+      title: '目标',
+      key: 'target',
+      render: (_, record) => (
+        <Space direction="vertical" size={0}>
+          <span>{record.target_database || '与源库相同'}</span>
+          {record.target_table_template && <code>{record.target_table_template}</code>}
+        </Space>
+      ),
+    },
+    {
+      title: '上次运行',
+      key: 'last_run',
+      render: (_, record) =>
+        record.last_run_at ? (
+          <Space direction="vertical" size={0}>
+            <span>{new Date(record.last_run_at).toLocaleString()}</span>
+            {record.last_task_id && <span>任务 #{record.last_task_id}</span>}
+          </Space>
+        ) : (
+          '-'
+        ),
+    },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_, record) => (
+        <Space>
+          <Popconfirm title="按该映射提交同步？" onConfirm={() => handleRun(record.id)}>
+            <Button type="link" icon={<PlayCircleOutlined />} disabled={record.paused}>
+              运行
+            </Button>
+          </Popconfirm>
+          <Popconfirm
+            title={
+              record.paused
+                ? '恢复 sink 写入并重新启用相关定时同步？'
+                : '暂停 sink 写入并停用相关定时同步？'
+            }
+            onConfirm={() => handleTogglePause(record)}
+          >
+            <Button
+              type="link"
+              icon={record.paused ? <PlayCircleOutlined /> : <PauseCircleOutlined />}
+            >
+              {record.paused ? '恢复' : '暂停'}
+            </Button>
+          </Popconfirm>
+          <Button type="link" icon={<EditOutlined />} onClick={() => handleOpenEdit(record)}>
+            编辑
+          </Button>
+          <Popconfirm title="确定删除该同步映射吗？" onConfirm={() => handleDelete(record.id)}>
+            <Button type="link" danger icon={<DeleteOutlined />}>
+              删除
+            </Button>
+          </Popconfirm>
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <div>
+      <Card
+        title="同步映射"
+        extra={
+          <Button type="primary" icon={<PlusOutlined />} onClick={handleOpenCreate}>
+            新建同步映射
+          </Button>
+        }
+      >
+        <Table
+          columns={columns}
+          dataSource={mappings}
+          rowKey="id"
+          loading={loading}
+          pagination={false}
+        />
+        <div style={{ marginTop: 8, color: '#888' }}>
+          保存连接、表清单、同步选项和目标表名模板，运行时按定义重新提交同步；相同的同步正在执行时返回已有任务
+        </div>
+      </Card>
+
+      <Modal
+        title={editingId ? '编辑同步映射' : '新建同步映射'}
+        open={modalVisible}
+        onCancel={() => setModalVisible(false)}
+        onOk={handleSave}
+        confirmLoading={saving}
+        width={720}
+      >
+        <Form form={form} layout="vertical">
+          <Form.Item label="名称" name="name" rules={[{ required: true, message: '请输入名称' }]}>
+            <Input placeholder="例如 nightly-reprovision" />
+          </Form.Item>
+          <Form.Item label="说明" name="description">
+            <Input />
+          </Form.Item>
+          <Space align="start">
+            <Form.Item
+              label="MySQL"
+              name="mysql_config_id"
+              rules={[{ required: true, message: '请选择连接' }]}
+            >
+              <Select style={{ width: 200 }} options={connectionOptions('mysql')} />
+            </Form.Item>
+            <Form.Item
+              label="RisingWave"
+              name="rw_config_id"
+              rules={[{ required: true, message: '请选择连接' }]}
+            >
+              <Select style={{ width: 200 }} options={connectionOptions('risingwave')} />
+            </Form.Item>
+            <Form.Item
+              label="StarRocks"
+              name="sr_config_id"
+              rules={[{ required: true, message: '请选择连接' }]}
+            >
+              <Select style={{ width: 200 }} options={connectionOptions('starrocks')} />
+            </Form.Item>
+          </Space>
+          <Space align="start">
+            <Form.Item label="目标库" name="target_database" extra="为空时与 MySQL 库名相同">
+              <Input style={{ width: 300 }} />
+            </Form.Item>
+            <Form.Item
+              label="目标表名模板"
+              name="target_table_template"
+              extra="例如 ods_{table}，支持 {db}、{table}、{target_db}"
+            >
+              <Input style={{ width: 300 }} />
+            </Form.Item>
+          </Space>
+          <Form.Item
+            label="表清单（JSON）"
+            name="tables"
+            rules={[
+              { required: true, message: '请输入表清单' },
+              {
+                validator: async (_, value) => {
+                  const parsed = JSON.parse(value);
+                  if (!Array.isArray(parsed) || parsed.length === 0) {
+                    throw new Error('需要至少一张表');
+                  }
+                },
+              },
+            ]}
+            extra='例如 [{"mysql_database": "shop", "mysql_table": "orders"}]，可为单表指定 target_database、target_table、row_filter 等'
+          >
+            <Input.TextArea rows={8} style={{ fontFamily: 'monospace' }} />
+          </Form.Item>
+          <Form.Item label="同步选项（JSON）" name="options" extra="与同步请求的 options 相同">
+            <Input.TextArea rows={4} style={{ fontFamily: 'monospace' }} />
+          </Form.Item>
+        </Form>
+      </Modal>
+    </div>
+  );
+};
+
+export default SyncMappings;
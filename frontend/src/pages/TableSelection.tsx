@@ -7,13 +7,13 @@ import {
   Table,
   Checkbox, Input,
   message,
-  Space, Alert
+  Space, Alert, Tag
 } from "antd";
 import { SyncOutlined, ArrowRightOutlined } from "@ant-design/icons";
 import type { ColumnsType } from "antd/es/table";
 import type {
   DatabaseConfig, SyncRequest,
-  SyncOptions,
+  SyncOptions, StagingStatus, TableDdlPreview,
 } from "../types";
 import * as api from "../services/api";
 
@@ -26,6 +26,28 @@ interface SelectedTable {
 
 const STORAGE_KEY = 'table_selection_batch_target_db';
 
+const STAGING_STATUS: Record<StagingStatus, { color: string; label: string }> = {
+  passed: { color: "green", label: "通过" },
+  failed: { color: "red", label: "失败" },
+  connector_error: { color: "orange", label: "连接失败（预期）" },
+  skipped: { color: "default", label: "跳过" },
+};
+
+// 按模板生成目标表名（与后端 target_table_template 规则一致），模板为空时使用源表名
+const renderTableName = (
+  template: string,
+  db: string,
+  table: string,
+  targetDb: string
+): string => {
+  if (!template.trim()) return table;
+  return template
+    .trim()
+    .replace(/\{db\}/g, db)
+    .replace(/\{table\}/g, table)
+    .replace(/\{target_db\}/g, targetDb);
+};
+
 const TableSelection: React.FC = () => {
   const [currentStep, setCurrentStep] = useState(0);
   const [mysqlConnections, setMysqlConnections] = useState<DatabaseConfig[]>(
@@ -39,6 +61,8 @@ const TableSelection: React.FC = () => {
   const [selectedSrId, setSelectedSrId] = useState<number>();
 
   const [batchTargetDatabase, setBatchTargetDatabase] = useState<string>("");
+  // 目标表名模板，例如 ods_{table}、{db}_{table}
+  const [tableNameTemplate, setTableNameTemplate] = useState<string>("");
 
   const [databases, setDatabases] = useState<string[]>([]);
   const [selectedDatabase, setSelectedDatabase] = useState<string>();
@@ -53,6 +77,10 @@ const TableSelection: React.FC = () => {
   });
 
   const [syncing, setSyncing] = useState(false);
+  // 试运行 DDL 的预发 RisingWave 连接，为空时使用设置 rw.staging_config_id
+  const [stagingRwId, setStagingRwId] = useState<number>();
+  const [previews, setPreviews] = useState<TableDdlPreview[]>([]);
+  const [previewing, setPreviewing] = useState(false);
 
   // 从 localStorage 恢复批量目标数据库
   useEffect(() => {
@@ -144,6 +172,35 @@ const TableSelection: React.FC = () => {
     setCurrentStep(2);
   };
 
+  const buildRequests = (): SyncRequest[] =>
+    selectedTables.map((table) => ({
+      mysql_config_id: selectedMysqlId!,
+      rw_config_id: selectedRwId!,
+      sr_config_id: selectedSrId!,
+      mysql_database: table.database,
+      mysql_table: table.table,
+      target_database: table.targetDatabase,
+      target_table: table.targetTable,
+      options: syncOptions,
+    }));
+
+  // 预览 DDL，配置了预发连接时同时返回试运行结果
+  const handlePreview = async () => {
+    if (!selectedMysqlId || !selectedRwId || !selectedSrId) {
+      message.error("请完成所有配置");
+      return;
+    }
+    setPreviewing(true);
+    try {
+      setPreviews(await api.previewSync(buildRequests(), stagingRwId));
+    } catch (error) {
+      setPreviews([]);
+      message.error("预览 DDL 失败: " + error);
+    } finally {
+      setPreviewing(false);
+    }
+  };
+
   // 执行同步
   const handleSync = async () => {
     if (!selectedMysqlId || !selectedRwId || !selectedSrId) {
@@ -183,7 +240,10 @@ const TableSelection: React.FC = () => {
             options: syncOptions,
           }
         });
-        const taskId = await api.syncMultipleTables(request);
+        const taskId = await api.syncMultipleTables(
+          request,
+          tableNameTemplate.trim() || undefined
+        );
         message.success(`批量同步任务已创建！任务 ID: ${taskId}`);
       }
 
@@ -232,7 +292,12 @@ const TableSelection: React.FC = () => {
                     database: selectedDatabase!,
                     table,
                     targetDatabase: batchTargetDatabase || selectedDatabase!,
-                    targetTable: table,
+                    targetTable: renderTableName(
+                      tableNameTemplate,
+                      selectedDatabase!,
+                      table,
+                      batchTargetDatabase || selectedDatabase!
+                    ),
                   },
                 ]);
               }
@@ -424,6 +489,32 @@ const TableSelection: React.FC = () => {
                         （修改此处将统一应用到下方所有表）
                       </span>
                     </div>
+                    <div>
+                      <span style={{ marginRight: 8 }}>目标表名模板：</span>
+                      <Input
+                        style={{ width: 300 }}
+                        placeholder="例如 ods_{table} 或 {db}_{table}"
+                        value={tableNameTemplate}
+                        onChange={(e) => {
+                          const template = e.target.value;
+                          setTableNameTemplate(template);
+                          setSelectedTables((tables) =>
+                            tables.map((t) => ({
+                              ...t,
+                              targetTable: renderTableName(
+                                template,
+                                t.database,
+                                t.table,
+                                t.targetDatabase
+                              ),
+                            }))
+                          );
+                        }}
+                      />
+                      <span style={{ marginLeft: 8, color: "#999", fontSize: 12 }}>
+                        （支持 {"{db}"}、{"{table}"}、{"{target_db}"}，为空时使用源表名）
+                      </span>
+                    </div>
                   </Space>
                 </div>
                 <Table
@@ -541,6 +632,17 @@ const TableSelection: React.FC = () => {
                 >
                   清空 StarRocks 表数据（仅清空数据）
                 </Checkbox>
+                <Checkbox
+                  checked={syncOptions.disable_notifications ?? false}
+                  onChange={(e) =>
+                    setSyncOptions({
+                      ...syncOptions,
+                      disable_notifications: e.target.checked,
+                    })
+                  }
+                >
+                  任务完成或失败时不发送通知
+                </Checkbox>
               </Space>
             </Card>
 
@@ -557,6 +659,55 @@ const TableSelection: React.FC = () => {
                 }
                 type="info"
               />
+              <Space style={{ marginTop: 12 }}>
+                <Select
+                  allowClear
+                  style={{ width: 280 }}
+                  placeholder="预发 RisingWave（默认使用设置）"
+                  value={stagingRwId}
+                  onChange={setStagingRwId}
+                  options={rwConnections
+                    .filter((c) => c.id !== selectedRwId)
+                    .map((c) => ({ label: c.name, value: c.id }))}
+                />
+                <Button loading={previewing} onClick={handlePreview}>
+                  预览 DDL
+                </Button>
+              </Space>
+              {previews.map((preview) => (
+                <div
+                  key={`${preview.mysql_database}.${preview.mysql_table}`}
+                  style={{ marginTop: 12 }}
+                >
+                  <strong>
+                    {preview.mysql_database}.{preview.mysql_table} →{" "}
+                    {preview.target_database}.{preview.target_table}
+                  </strong>
+                  {preview.risingwave.map((sql, i) => {
+                    const result = preview.staging_validation?.[i];
+                    return (
+                      <div key={i} style={{ marginTop: 4 }}>
+                        {result && (
+                          <Tag color={STAGING_STATUS[result.status].color}>
+                            {STAGING_STATUS[result.status].label}
+                          </Tag>
+                        )}
+                        {result?.error && (
+                          <span style={{ color: result.status === "failed" ? "red" : "#999" }}>
+                            {result.error}
+                          </span>
+                        )}
+                        <pre style={{ margin: 0, whiteSpace: "pre-wrap" }}>{sql}</pre>
+                      </div>
+                    );
+                  })}
+                  {preview.starrocks.map((sql, i) => (
+                    <pre key={i} style={{ margin: "4px 0 0", whiteSpace: "pre-wrap" }}>
+                      {sql}
+                    </pre>
+                  ))}
+                </div>
+              ))}
             </Card>
 
             <div style={{ textAlign: "right" }}>
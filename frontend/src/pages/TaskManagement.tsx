@@ -16,9 +16,11 @@ import {
   Input,
   Checkbox,
   Alert,
+  Radio,
 } from 'antd';
 import {
   ReloadOutlined,
+  DownloadOutlined,
   EyeOutlined,
   CloseCircleOutlined,
   CheckCircleOutlined,
@@ -27,9 +29,24 @@ import {
   MinusCircleOutlined,
   RedoOutlined,
   EditOutlined,
+  DeleteOutlined,
 } from '@ant-design/icons';
 import type { ColumnsType } from 'antd/es/table';
-import type { SyncTask, TaskStatus, TaskLog, SyncProgress, SyncOptions } from '../types';
+import type {
+  SyncTask,
+  TaskStatus,
+  TaskLog,
+  TaskLogLevelFilter,
+  SyncProgress,
+  TableSyncAction,
+  SyncOptions,
+  Comment,
+  SyncMetric,
+  CleanupMode,
+  PipelineCleanupResult,
+  TaskTeardownResult,
+  RetryBatchRequest,
+} from '../types';
 import * as api from '../services/api';
 
 // 辅助函数：解析 options 字符串
@@ -45,6 +62,11 @@ const parseOptions = (optionsStr: string): SyncOptions => {
   }
 };
 
+// 清理任务的 mysql_table 标记
+const CLEANUP_TASK_MARKER = '[Cleanup]';
+// 任务详情中显示的日志条数
+const TASK_LOG_TAIL = 500;
+
 interface EditFormValues {
   target_database: string;
   target_table: string;
@@ -57,10 +79,39 @@ const TaskManagement: React.FC = () => {
   const [tasks, setTasks] = useState<SyncTask[]>([]);
   const [loading, setLoading] = useState(false);
   const [statusFilter, setStatusFilter] = useState<TaskStatus | undefined>();
+  const [selectedTaskIds, setSelectedTaskIds] = useState<React.Key[]>([]);
+  const [retryingBatch, setRetryingBatch] = useState(false);
   const [detailModalVisible, setDetailModalVisible] = useState(false);
   const [selectedTask, setSelectedTask] = useState<SyncTask | null>(null);
   const [taskLogs, setTaskLogs] = useState<TaskLog[]>([]);
+  const [taskLogTotal, setTaskLogTotal] = useState(0);
+  const [taskLogLevel, setTaskLogLevel] = useState<TaskLogLevelFilter | undefined>(undefined);
   const [progress, setProgress] = useState<SyncProgress | null>(null);
+  const [taskComments, setTaskComments] = useState<Comment[]>([]);
+  const [pipelineComments, setPipelineComments] = useState<Comment[]>([]);
+  const [commentUser, setCommentUser] = useState(
+    () => localStorage.getItem('rw_cdc_sr_user') || ''
+  );
+  const [commentBody, setCommentBody] = useState('');
+  const [commentTarget, setCommentTarget] = useState<'task' | 'pipeline'>('task');
+
+  // 行数对比
+  const [rowCountTask, setRowCountTask] = useState<SyncTask | null>(null);
+  const [rowCounts, setRowCounts] = useState<SyncMetric[]>([]);
+  const [rowCountLoading, setRowCountLoading] = useState(false);
+
+  // 清理下线管道的目标表
+  const [cleanupTask, setCleanupTask] = useState<SyncTask | null>(null);
+  const [cleanupMode, setCleanupMode] = useState<CleanupMode>('trash');
+  const [cleanupPlan, setCleanupPlan] = useState<PipelineCleanupResult | null>(null);
+  const [cleanupConfirm, setCleanupConfirm] = useState('');
+  const [cleaningUp, setCleaningUp] = useState(false);
+  const [teardownTask, setTeardownTask] = useState<SyncTask | null>(null);
+  const [teardownMode, setTeardownMode] = useState<CleanupMode>('drop');
+  const [teardownDropSource, setTeardownDropSource] = useState(false);
+  const [teardownPlan, setTeardownPlan] = useState<TaskTeardownResult | null>(null);
+  const [teardownConfirm, setTeardownConfirm] = useState('');
+  const [tearingDown, setTearingDown] = useState(false);
 
   // 编辑并重新执行相关状态
   const [editModalVisible, setEditModalVisible] = useState(false);
@@ -105,10 +156,10 @@ const TaskManagement: React.FC = () => {
     setDetailModalVisible(true);
 
     try {
-      const logs = await api.getTaskLogs(task.id);
-      setTaskLogs(logs);
+      await loadTaskLogs(task.id, taskLogLevel);
+      await loadComments(task);
 
-      if (task.status === 'running') {
+      if (task.status === 'running' || task.status === 'completed') {
         const prog = await api.getSyncProgress(task.id);
         setProgress(prog);
       }
@@ -117,6 +168,47 @@ const TaskManagement: React.FC = () => {
     }
   };
 
+  // 只加载最后 TASK_LOG_TAIL 条日志，避免日志很多时卡住页面
+  const loadTaskLogs = async (taskId: number, level?: TaskLogLevelFilter) => {
+    const page = await api.getTaskLogs(taskId, { tail: TASK_LOG_TAIL, level });
+    setTaskLogs(page.logs);
+    setTaskLogTotal(page.total);
+  };
+
+  // 加载任务和所属管道的评论
+  const loadComments = async (task: SyncTask) => {
+    const [taskList, pipelineList] = await Promise.all([
+      api.getTaskComments(task.id),
+      api.getPipelineComments(`${task.target_database}.${task.target_table}`),
+    ]);
+    setTaskComments(taskList);
+    setPipelineComments(pipelineList);
+  };
+
+  // 添加评论
+  const handleAddComment = async () => {
+    if (!selectedTask || !commentUser.trim() || !commentBody.trim()) {
+      message.warning('请填写署名和评论内容');
+      return;
+    }
+    try {
+      localStorage.setItem('rw_cdc_sr_user', commentUser.trim());
+      if (commentTarget === 'task') {
+        await api.addTaskComment(selectedTask.id, commentUser.trim(), commentBody);
+      } else {
+        await api.addPipelineComment(
+          `${selectedTask.target_database}.${selectedTask.target_table}`,
+          commentUser.trim(),
+          commentBody
+        );
+      }
+      setCommentBody('');
+      await loadComments(selectedTask);
+    } catch (error) {
+      message.error('添加评论失败: ' + error);
+    }
+  };
+
   // 取消任务
   const handleCancelTask = async (taskId: number) => {
     try {
@@ -128,6 +220,107 @@ const TaskManagement: React.FC = () => {
     }
   };
 
+  // 查询三端行数
+  const handleViewRowCounts = async (task: SyncTask) => {
+    setRowCountTask(task);
+    setRowCounts([]);
+    setRowCountLoading(true);
+    try {
+      setRowCounts(await api.getTaskRowCounts(task.id));
+    } catch (error) {
+      message.error('查询行数失败: ' + error);
+    } finally {
+      setRowCountLoading(false);
+    }
+  };
+
+  // 预览清理语句
+  const loadCleanupPlan = async (task: SyncTask, mode: CleanupMode) => {
+    setCleanupPlan(null);
+    try {
+      setCleanupPlan(
+        await api.cleanupPipeline({
+          rw_config_id: task.rw_config_id,
+          target_database: task.target_database,
+          target_table: task.target_table,
+          mode,
+          dry_run: true,
+        })
+      );
+    } catch (error) {
+      message.error('生成清理计划失败: ' + error);
+    }
+  };
+
+  const handleOpenCleanup = (task: SyncTask) => {
+    setCleanupTask(task);
+    setCleanupMode('trash');
+    setCleanupConfirm('');
+    loadCleanupPlan(task, 'trash');
+  };
+
+  // 确认后提交清理任务
+  const handleCleanup = async () => {
+    if (!cleanupTask) return;
+    setCleaningUp(true);
+    try {
+      const result = await api.cleanupPipeline({
+        rw_config_id: cleanupTask.rw_config_id,
+        target_database: cleanupTask.target_database,
+        target_table: cleanupTask.target_table,
+        mode: cleanupMode,
+        confirm: cleanupConfirm,
+      });
+      message.success(`清理任务已提交，任务 ID: ${result.task_id}`);
+      setCleanupTask(null);
+      loadTasks();
+    } catch (error) {
+      message.error('清理失败: ' + error);
+    } finally {
+      setCleaningUp(false);
+    }
+  };
+
+  // 预览拆除语句
+  const loadTeardownPlan = async (task: SyncTask, mode: CleanupMode, dropSource: boolean) => {
+    setTeardownPlan(null);
+    try {
+      setTeardownPlan(
+        await api.teardownTask(task.id, { mode, drop_source: dropSource, dry_run: true })
+      );
+    } catch (error) {
+      message.error('生成拆除计划失败: ' + error);
+    }
+  };
+
+  const handleOpenTeardown = (task: SyncTask) => {
+    setTeardownTask(task);
+    setTeardownMode('drop');
+    setTeardownDropSource(false);
+    setTeardownConfirm('');
+    loadTeardownPlan(task, 'drop', false);
+  };
+
+  // 确认后提交拆除任务
+  const handleTeardown = async () => {
+    if (!teardownTask) return;
+    setTearingDown(true);
+    try {
+      const result = await api.teardownTask(teardownTask.id, {
+        mode: teardownMode,
+        drop_source: teardownDropSource,
+        confirm: teardownConfirm,
+      });
+      message.success(`拆除任务已提交，任务 ID: ${result.task_id}`);
+      setTeardownTask(null);
+      loadTasks();
+    } catch (error) {
+      message.error('拆除失败: ' + error);
+    } finally {
+      setTearingDown(false);
+    }
+  };
+
   // 重试任务
   const handleRetryTask = async (taskId: number) => {
     try {
@@ -139,6 +332,40 @@ const TaskManagement: React.FC = () => {
     }
   };
 
+  // 批量重试失败任务
+  const handleRetryBatch = async (request: RetryBatchRequest) => {
+    setRetryingBatch(true);
+    try {
+      const result = await api.retryTasksBatch(request);
+      setSelectedTaskIds([]);
+      loadTasks();
+      if (result.results.length === 0) {
+        message.info('没有需要重试的失败任务');
+        return;
+      }
+      const items = result.results.map((r) => {
+        if (r.new_task_id) return `#${r.task_id} → 新任务 #${r.new_task_id}`;
+        if (r.approval_id) return `#${r.task_id} → 等待审批 #${r.approval_id}`;
+        return `#${r.task_id}: ${r.error}`;
+      });
+      const failed = result.results.some((r) => r.error);
+      (failed ? Modal.warning : Modal.success)({
+        title: `已重试 ${Object.keys(result.mapping).length} / ${result.results.length} 个任务`,
+        content: (
+          <div>
+            {items.map((item) => (
+              <div key={item}>{item}</div>
+            ))}
+          </div>
+        ),
+      });
+    } catch (error) {
+      message.error('批量重试失败: ' + error);
+    } finally {
+      setRetryingBatch(false);
+    }
+  };
+
   // 快速重新执行成功任务
   const handleQuickReExecute = async (task: SyncTask) => {
     const isBatchTask = task.task_name.toLowerCase().includes('batch');
@@ -246,6 +473,12 @@ const TaskManagement: React.FC = () => {
   };
 
   // 获取状态标签
+  const tableActionColors: Record<TableSyncAction, string> = {
+    created: 'green',
+    updated: 'orange',
+    skipped: 'default',
+  };
+
   const getStatusTag = (status: TaskStatus) => {
     const statusConfig = {
       pending: { color: 'default', icon: <MinusCircleOutlined />, text: '等待中' },
@@ -261,6 +494,11 @@ const TaskManagement: React.FC = () => {
         icon: <ExclamationCircleOutlined />,
         text: '已取消',
       },
+      interrupted: {
+        color: 'orange',
+        icon: <ExclamationCircleOutlined />,
+        text: '已中断',
+      },
     };
 
     const config = statusConfig[status];
@@ -375,6 +613,7 @@ const TaskManagement: React.FC = () => {
       width: 260,
       render: (_, record) => {
         const isBatchTask = record.task_name.toLowerCase().includes('batch');
+        const isCleanupTask = record.mysql_table === CLEANUP_TASK_MARKER;
 
         return (
           <Space size="small" wrap>
@@ -396,7 +635,7 @@ const TaskManagement: React.FC = () => {
                 取消
               </Button>
             )}
-            {record.status === 'failed' && (
+            {(record.status === 'failed' || record.status === 'interrupted') && !isCleanupTask && (
               <Button
                 type="link"
                 size="small"
@@ -406,7 +645,7 @@ const TaskManagement: React.FC = () => {
                 重试
               </Button>
             )}
-            {record.status === 'completed' && (
+            {record.status === 'completed' && !isCleanupTask && (
               <>
                 <Tooltip title={isBatchTask ? "批量任务按原配置重新执行" : "使用相同配置重新执行"}>
                   <Button
@@ -428,6 +667,36 @@ const TaskManagement: React.FC = () => {
                     编辑执行
                   </Button>
                 </Tooltip>
+                <Button
+                  type="link"
+                  size="small"
+                  onClick={() => handleViewRowCounts(record)}
+                >
+                  行数
+                </Button>
+                {!isBatchTask && (
+                  <Tooltip title="删除 sink / RisingWave 表，并删除或重命名 StarRocks 目标表">
+                    <Button
+                      type="link"
+                      size="small"
+                      danger
+                      icon={<DeleteOutlined />}
+                      onClick={() => handleOpenCleanup(record)}
+                    >
+                      清理目标
+                    </Button>
+                  </Tooltip>
+                )}
+                <Tooltip title="按执行顺序删除该任务创建的 sink、RisingWave 表、CDC Source 和 StarRocks 表">
+                  <Button
+                    type="link"
+                    size="small"
+                    danger
+                    onClick={() => handleOpenTeardown(record)}
+                  >
+                    拆除管道
+                  </Button>
+                </Tooltip>
               </>
             )}
           </Space>
@@ -457,10 +726,37 @@ const TaskManagement: React.FC = () => {
               <Select.Option value="completed">已完成</Select.Option>
               <Select.Option value="failed">失败</Select.Option>
               <Select.Option value="cancelled">已取消</Select.Option>
+              <Select.Option value="interrupted">已中断</Select.Option>
             </Select>
+            <Button
+              icon={<RedoOutlined />}
+              disabled={selectedTaskIds.length === 0}
+              loading={retryingBatch}
+              onClick={() => handleRetryBatch({ task_ids: selectedTaskIds as number[] })}
+            >
+              重试所选{selectedTaskIds.length > 0 ? ` (${selectedTaskIds.length})` : ''}
+            </Button>
+            <Button
+              loading={retryingBatch}
+              onClick={() =>
+                Modal.confirm({
+                  title: '重试失败任务',
+                  content: '重新提交最近 24 小时内失败的任务，同一任务多次失败时只重试最近一次。',
+                  onOk: () => handleRetryBatch({ failed_within_hours: 24 }),
+                })
+              }
+            >
+              重试 24 小时内失败
+            </Button>
             <Button icon={<ReloadOutlined />} onClick={loadTasks}>
               刷新
             </Button>
+            <Button
+              icon={<DownloadOutlined />}
+              href={api.getTaskExportUrl({ status: statusFilter })}
+            >
+              导出 CSV
+            </Button>
           </Space>
         }
       >
@@ -469,6 +765,13 @@ const TaskManagement: React.FC = () => {
           dataSource={tasks}
           rowKey="id"
           loading={loading}
+          rowSelection={{
+            selectedRowKeys: selectedTaskIds,
+            onChange: setSelectedTaskIds,
+            getCheckboxProps: (record) => ({
+              disabled: record.status !== 'failed' && record.status !== 'interrupted',
+            }),
+          }}
           pagination={{
             current: currentPage,
             pageSize: pageSize,
@@ -496,6 +799,9 @@ const TaskManagement: React.FC = () => {
           setSelectedTask(null);
           setTaskLogs([]);
           setProgress(null);
+          setTaskComments([]);
+          setPipelineComments([]);
+          setCommentBody('');
         }}
         footer={null}
         width={800}
@@ -537,19 +843,59 @@ const TaskManagement: React.FC = () => {
 
             {progress && selectedTask.status === 'running' && (
               <Card title="同步进度" size="small">
-                <Progress
-                  percent={Math.floor(
-                    (progress.current_step_index / progress.total_steps) * 100
-                  )}
-                  status="active"
-                />
+                <Progress percent={progress.percent} status="active" />
                 <p style={{ marginTop: 16 }}>
-                  当前步骤: {progress.current_step}
+                  当前步骤: {progress.current_step ?? '-'}（{progress.current_step_index}/
+                  {progress.total_steps}）
                 </p>
               </Card>
             )}
 
-            <Card title="执行日志" size="small">
+            {progress && progress.tables.length > 0 && (
+              <Card title="表处理结果" size="small">
+                {progress.tables.map((t) => (
+                  <div key={t.target_table}>
+                    <Tag color={tableActionColors[t.action]}>{t.action}</Tag>
+                    {t.source_table} → {t.target_table}
+                  </div>
+                ))}
+              </Card>
+            )}
+
+            <Card
+              title="执行日志"
+              size="small"
+              extra={
+                <Space>
+                  {taskLogTotal > taskLogs.length && (
+                    <span style={{ color: '#999' }}>
+                      显示最后 {taskLogs.length} 条，共 {taskLogTotal} 条
+                    </span>
+                  )}
+                  <Select
+                    size="small"
+                    allowClear
+                    placeholder="全部级别"
+                    style={{ width: 120 }}
+                    value={taskLogLevel}
+                    onChange={(level?: TaskLogLevelFilter) => {
+                      setTaskLogLevel(level);
+                      if (selectedTask) {
+                        loadTaskLogs(selectedTask.id, level).catch((error) =>
+                          message.error('加载日志失败: ' + error)
+                        );
+                      }
+                    }}
+                    options={[
+                      { value: 'debug', label: 'debug 及以上' },
+                      { value: 'info', label: 'info 及以上' },
+                      { value: 'warn', label: 'warn 及以上' },
+                      { value: 'error', label: 'error' },
+                    ]}
+                  />
+                </Space>
+              }
+            >
               {taskLogs.length > 0 ? (
                 <Timeline
                   items={taskLogs.map((log) => ({
@@ -573,6 +919,57 @@ const TaskManagement: React.FC = () => {
                 <p style={{ textAlign: 'center', color: '#999' }}>暂无日志</p>
               )}
             </Card>
+
+            <Card title="评论" size="small">
+              {[...taskComments, ...pipelineComments].length > 0 ? (
+                <Timeline
+                  items={[...taskComments, ...pipelineComments]
+                    .sort((a, b) => a.created_at.localeCompare(b.created_at))
+                    .map((comment) => ({
+                      color: comment.subject_type === 'task' ? 'blue' : 'gray',
+                      children: (
+                        <div>
+                          <div style={{ fontSize: '12px', color: '#999' }}>
+                            {comment.author} · {formatTime(comment.created_at)}
+                            {comment.subject_type === 'pipeline' && ' · 管道'}
+                          </div>
+                          <div style={{ whiteSpace: 'pre-wrap' }}>{comment.body}</div>
+                        </div>
+                      ),
+                    }))}
+                />
+              ) : (
+                <p style={{ textAlign: 'center', color: '#999' }}>暂无评论</p>
+              )}
+              <Space direction="vertical" style={{ width: '100%' }}>
+                <Space>
+                  <Input
+                    placeholder="署名"
+                    value={commentUser}
+                    onChange={(e) => setCommentUser(e.target.value)}
+                    style={{ width: 160 }}
+                  />
+                  <Select
+                    value={commentTarget}
+                    onChange={setCommentTarget}
+                    style={{ width: 140 }}
+                    options={[
+                      { value: 'task', label: '评论此任务' },
+                      { value: 'pipeline', label: '评论此管道' },
+                    ]}
+                  />
+                </Space>
+                <Input.TextArea
+                  rows={2}
+                  placeholder="例如：SR 升级导致失败，14:00 已重跑"
+                  value={commentBody}
+                  onChange={(e) => setCommentBody(e.target.value)}
+                />
+                <Button type="primary" onClick={handleAddComment}>
+                  添加评论
+                </Button>
+              </Space>
+            </Card>
           </Space>
         )}
       </Modal>
@@ -709,6 +1106,147 @@ const TaskManagement: React.FC = () => {
           </Space>
         )}
       </Modal>
+
+      {/* 三端行数对比 Modal */}
+      <Modal
+        title={`行数对比 - 任务 #${rowCountTask?.id ?? ''}`}
+        open={rowCountTask !== null}
+        onCancel={() => setRowCountTask(null)}
+        footer={null}
+        width={900}
+      >
+        <Table<SyncMetric>
+          rowKey={(m) => `${m.target_database}.${m.target_table}`}
+          loading={rowCountLoading}
+          dataSource={rowCounts}
+          pagination={false}
+          size="small"
+          columns={[
+            {
+              title: '目标表',
+              key: 'target',
+              render: (_, m) => `${m.target_database}.${m.target_table}`,
+            },
+            { title: 'MySQL', dataIndex: 'source_count', key: 'source_count' },
+            { title: 'RisingWave', dataIndex: 'rw_count', key: 'rw_count' },
+            { title: 'StarRocks', dataIndex: 'target_count', key: 'target_count' },
+            {
+              title: '差值',
+              dataIndex: 'lag',
+              key: 'lag',
+              render: (lag?: number) =>
+                lag === undefined || lag === null ? '-' : (
+                  <Tag color={lag === 0 ? 'green' : 'orange'}>{lag}</Tag>
+                ),
+            },
+            {
+              title: '错误',
+              dataIndex: 'error_message',
+              key: 'error_message',
+              ellipsis: true,
+            },
+          ]}
+        />
+      </Modal>
+
+      {/* 清理下线管道 Modal */}
+      <Modal
+        title={`清理目标 - ${cleanupTask?.target_database}.${cleanupTask?.target_table}`}
+        open={cleanupTask !== null}
+        onCancel={() => setCleanupTask(null)}
+        onOk={handleCleanup}
+        okText="执行清理"
+        okButtonProps={{
+          danger: true,
+          disabled:
+            !cleanupPlan ||
+            cleanupConfirm.trim() !== `${cleanupTask?.target_database}.${cleanupTask?.target_table}`,
+        }}
+        confirmLoading={cleaningUp}
+        width={760}
+      >
+        <Space direction="vertical" style={{ width: '100%' }}>
+          <Alert
+            type="warning"
+            showIcon
+            message="将删除该管道的 RisingWave sink 和表，并处理 StarRocks 目标表。RisingWave 表仍被其他对象依赖时任务会失败。"
+          />
+          <Radio.Group
+            value={cleanupMode}
+            onChange={(e) => {
+              setCleanupMode(e.target.value);
+              if (cleanupTask) loadCleanupPlan(cleanupTask, e.target.value);
+            }}
+          >
+            <Radio value="trash">重命名到回收表</Radio>
+            <Radio value="drop">直接删除</Radio>
+          </Radio.Group>
+          <pre style={{ background: '#f5f5f5', padding: 12, margin: 0, whiteSpace: 'pre-wrap' }}>
+            {cleanupPlan
+              ? cleanupPlan.statements.map((s) => `-- ${s.system}\n${s.sql}`).join('\n')
+              : '加载中...'}
+          </pre>
+          <Input
+            placeholder={`输入 ${cleanupTask?.target_database}.${cleanupTask?.target_table} 确认`}
+            value={cleanupConfirm}
+            onChange={(e) => setCleanupConfirm(e.target.value)}
+          />
+        </Space>
+      </Modal>
+
+      {/* 拆除管道 Modal */}
+      <Modal
+        title={`拆除管道 - 任务 ${teardownTask?.id}`}
+        open={teardownTask !== null}
+        onCancel={() => setTeardownTask(null)}
+        onOk={handleTeardown}
+        okText="执行拆除"
+        okButtonProps={{
+          danger: true,
+          disabled: !teardownPlan || teardownConfirm.trim() !== String(teardownTask?.id),
+        }}
+        confirmLoading={tearingDown}
+        width={760}
+      >
+        <Space direction="vertical" style={{ width: '100%' }}>
+          <Alert
+            type="warning"
+            showIcon
+            message="将依次删除该任务所有表的 RisingWave sink 和表，再处理 StarRocks 目标表。RisingWave 表仍被其他对象依赖时任务会失败；CDC Source 仍被其他表使用时跳过。"
+          />
+          <Space>
+            <Radio.Group
+              value={teardownMode}
+              onChange={(e) => {
+                setTeardownMode(e.target.value);
+                if (teardownTask) loadTeardownPlan(teardownTask, e.target.value, teardownDropSource);
+              }}
+            >
+              <Radio value="drop">直接删除</Radio>
+              <Radio value="trash">重命名到回收表</Radio>
+            </Radio.Group>
+            <Checkbox
+              checked={teardownDropSource}
+              onChange={(e) => {
+                setTeardownDropSource(e.target.checked);
+                if (teardownTask) loadTeardownPlan(teardownTask, teardownMode, e.target.checked);
+              }}
+            >
+              删除未使用的 CDC Source
+            </Checkbox>
+          </Space>
+          <pre style={{ background: '#f5f5f5', padding: 12, margin: 0, whiteSpace: 'pre-wrap' }}>
+            {teardownPlan
+              ? teardownPlan.statements.map((s) => `-- ${s.system}\n${s.sql}`).join('\n')
+              : '加载中...'}
+          </pre>
+          <Input
+            placeholder={`输入任务 ID ${teardownTask?.id} 确认`}
+            value={teardownConfirm}
+            onChange={(e) => setTeardownConfirm(e.target.value)}
+          />
+        </Space>
+      </Modal>
     </div>
   );
 };
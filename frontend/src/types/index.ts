@@ -1,5 +1,5 @@
 // 数据库类型
-export type DbType = 'mysql' | 'risingwave' | 'starrocks';
+export type DbType = 'mysql' | 'risingwave' | 'starrocks' | 'doris';
 
 // 数据库配置
 export interface DatabaseConfig {
@@ -10,11 +10,26 @@ export interface DatabaseConfig {
   port: number;
   username: string;
   password: string;
+  // 不使用密码认证（空密码、IAM 或证书认证）
+  no_password?: boolean;
   database_name?: string;
+  proxy?: string;
+  tls?: TlsOptions;
+  version: number;
   created_at: string;
   updated_at: string;
 }
 
+// 连接的 TLS 配置（PEM 格式），配置后强制使用 TLS
+export interface TlsOptions {
+  // 校验服务端证书的 CA 证书，为空时不校验
+  ca_cert?: string;
+  client_cert?: string;
+  client_key?: string;
+  // 同时校验服务端证书中的主机名（需要 ca_cert）
+  verify_hostname?: boolean;
+}
+
 // 创建连接请求
 export interface CreateConnectionRequest {
   name: string;
@@ -22,8 +37,13 @@ export interface CreateConnectionRequest {
   host: string;
   port: number;
   username: string;
-  password: string;
+  password?: string;
+  no_password?: boolean;
   database_name?: string;
+  proxy?: string;
+  tls?: TlsOptions;
+  // 更新时期望的版本号，不一致时服务端返回 409
+  version?: number;
 }
 
 // 测试连接请求
@@ -32,8 +52,13 @@ export interface TestConnectionRequest {
   host: string;
   port: number;
   username: string;
-  password: string;
+  password?: string;
+  no_password?: boolean;
   database_name?: string;
+  proxy?: string;
+  tls?: TlsOptions;
+  // 测试已保存的连接时传入，密码为占位值 ****** 时使用保存的密码
+  config_id?: number;
 }
 
 // 连接测试结果
@@ -43,6 +68,64 @@ export interface ConnectionTestResult {
   error?: string;
 }
 
+// 连接密码轮换结果
+export interface RotatedSecret {
+  rw_config_id: number;
+  secret_name: string;
+  updated: boolean;
+  error?: string | null;
+  // 定义中引用该 SECRET 的 Source / Sink
+  touched: RwSecretReference[];
+}
+
+export interface RotationCheck {
+  rw_config_id: number;
+  kind: 'source' | 'sink' | 'schema';
+  name: string;
+  healthy: boolean;
+  error?: string | null;
+}
+
+export interface RotatePasswordResult {
+  config_id: number;
+  version: number;
+  secrets: RotatedSecret[];
+  checks: RotationCheck[];
+  success: boolean;
+}
+
+// 导出时密码的处理方式：不导出 / 使用口令重新加密
+export type PasswordMode = 'exclude' | 'encrypt';
+
+// 导出请求
+export interface ExportRequest {
+  passwords?: PasswordMode;
+  passphrase?: string;
+}
+
+// 导入结果
+export interface ImportResult {
+  created: string[];
+  skipped: string[];
+  missing_passwords: string[];
+  sync_requests: SyncRequest[];
+}
+
+// 一类监控配置的导入结果
+export interface ImportChanges {
+  created: string[];
+  updated: string[];
+  unchanged: string[];
+  skipped: string[];
+}
+
+// 监控配置（通知渠道、告警路由规则、静默窗口）的导入结果
+export interface MonitoringImportResult {
+  channels: ImportChanges;
+  alert_routes: ImportChanges;
+  silences: ImportChanges;
+}
+
 // 表列信息
 export interface Column {
   name: string;
@@ -70,16 +153,85 @@ export interface TableSchema {
   columns: Column[];
   primary_keys: string[];
   indexes: Index[];
+  // 列类型的来源：MySQL COLUMN_TYPE 或 RisingWave 目录类型名
+  type_source?: 'mysql' | 'risingwave';
+  // 表注释（MySQL TABLE_COMMENT）
+  comment?: string;
+}
+
+// 表数据预览，值为文本格式，非 UTF-8 的二进制值显示为 0x 开头的十六进制
+export interface TablePreview {
+  database: string;
+  table_name: string;
+  columns: string[];
+  rows: (string | null)[][];
+}
+
+export interface PreviewTableRequest {
+  config_id: number;
+  database: string;
+  table: string;
+  limit?: number;
+  // 同时预览 StarRocks 目标表，库名和表名默认与源表相同
+  sr_config_id?: number;
+  target_database?: string;
+  target_table?: string;
 }
 
 // 任务状态
-export type TaskStatus = 'pending' | 'running' | 'completed' | 'failed' | 'cancelled';
+// interrupted：服务重启或关闭时仍在执行的任务
+export type TaskStatus = 'pending' | 'running' | 'completed' | 'failed' | 'cancelled' | 'interrupted';
+
+// StarRocks 容量预检模式
+export type TaskLogLevel = 'minimal' | 'normal' | 'debug';
+
+export type CapacityCheckMode = 'disabled' | 'warn' | 'block';
+
+// StarRocks 建表属性
+export interface StarRocksTableOptions {
+  replication_num?: number;
+  buckets?: number;
+  auto_bucket?: boolean;
+  storage_medium?: 'HDD' | 'SSD';
+  dynamic_partition?: Record<string, string>;
+  // Colocation Group 名称，同组的表需要相同的分桶数、副本数和分桶列类型
+  colocate_with?: string;
+  // 排序键列（ORDER BY），为空时按主键排序
+  sort_keys?: string[];
+  // Bitmap 索引列
+  bitmap_indexes?: string[];
+  // Bloom Filter 索引列
+  bloom_filter_columns?: string[];
+}
 
 // 同步选项
 export interface SyncOptions {
   recreate_rw_source: boolean;
   recreate_sr_table: boolean;
   truncate_sr_table: boolean;
+  // 表已存在时为 MySQL 新增的列执行 ALTER TABLE ADD COLUMN
+  reconcile_sr_schema?: boolean;
+  capacity_check?: CapacityCheckMode;
+  min_free_space_ratio?: number;
+  starrocks_table?: StarRocksTableOptions;
+  // 任务日志详细程度，默认 normal
+  log_level?: TaskLogLevel;
+  // DDL 重试策略，为空时使用服务端默认值
+  retry?: RetryPolicy;
+  // 固定 CDC Source 的 server.id，为空时自动分配
+  server_id?: number;
+  // 与同一 MySQL 数据库的其他映射共享 CDC Source
+  share_source?: boolean;
+  // 所属工作区，用于功能开关，为空时使用 default
+  workspace?: string;
+  // 任务完成或失败时不发送通知
+  disable_notifications?: boolean;
+}
+
+export interface RetryPolicy {
+  max_attempts?: number;
+  initial_backoff_ms?: number;
+  max_backoff_ms?: number;
 }
 
 // 同步请求
@@ -92,6 +244,72 @@ export interface SyncRequest {
   target_database: string;
   target_table: string;
   options: SyncOptions;
+  row_filter?: string;
+  partition?: StarRocksPartition;
+  // 列名映射：源列名 -> 目标列名
+  column_mapping?: Record<string, string>;
+  // 嵌套类型列的写入方式：源列名 -> flatten / json
+  nested_columns?: Record<string, NestedColumnMode>;
+  // 空间类型列的写入方式：源列名 -> hex / binary，未指定时为 hex
+  spatial_columns?: Record<string, SpatialColumnMode>;
+}
+
+// flatten: STRUCT 字段展开为独立列（addr.city -> addr_city）；json: 序列化为 JSON 字符串
+export type NestedColumnMode = 'flatten' | 'json';
+
+// hex: 十六进制 WKB 写入 STRING 列；binary: WKB 写入 VARBINARY 列
+export type SpatialColumnMode = 'hex' | 'binary';
+
+// StarRocks 分区配置
+export type PartitionGranularity = 'hour' | 'day' | 'month' | 'year';
+
+export interface RangePartition {
+  name: string;
+  lower: string;
+  upper: string;
+}
+
+export type StarRocksPartition =
+  | {
+      type: 'expression';
+      column: string;
+      granularity: PartitionGranularity;
+      // 派生分区列名：目标表新增按粒度截断的列并加入主键，column 不必属于主键
+      derived_column?: string;
+    }
+  | { type: 'range'; column: string; ranges?: RangePartition[] };
+
+// 克隆管道请求
+export interface ClonePipelineRequest {
+  mysql_config_id?: number;
+  rw_config_id?: number;
+  sr_config_id?: number;
+  target_database?: string;
+  execute?: boolean;
+  // 预览时先在该 RisingWave 连接上试运行，默认使用设置 rw.staging_config_id
+  staging_rw_config_id?: number;
+}
+
+// 单表 DDL 预览
+export interface TableDdlPreview {
+  mysql_database: string;
+  mysql_table: string;
+  target_database: string;
+  target_table: string;
+  risingwave: string[];
+  starrocks: string[];
+  // 按源表大小建议的分桶数，已指定分桶数或自动分桶时为空
+  suggested_buckets?: number;
+  // RisingWave 语句在预发连接上的试运行结果，与 risingwave 一一对应
+  staging_validation?: StagingStatementResult[];
+}
+
+// connector_error：通过了解析和绑定，连接 MySQL / StarRocks 时失败（预发使用假密码，属于预期）
+export type StagingStatus = 'passed' | 'failed' | 'connector_error' | 'skipped';
+
+export interface StagingStatementResult {
+  status: StagingStatus;
+  error?: string;
 }
 
 // 批量同步请求
@@ -126,6 +344,38 @@ export interface SyncTask {
   completed_at?: string;
   error_message?: string;
   options: string;
+  preflight_result?: string;
+  requests?: string;
+}
+
+// StarRocks 容量预检结果（preflight_result 反序列化后的结构）
+export interface CapacityCheckResult {
+  mode: CapacityCheckMode;
+  required_bytes: number;
+  available_bytes: number;
+  total_bytes: number;
+  min_free_ratio: number;
+  projected_free_ratio: number;
+  passed: boolean;
+  message: string;
+  checked_at: string;
+}
+
+// 数据一致性校验结果
+export interface ValidationResult {
+  id: number;
+  task_id: number;
+  mysql_database: string;
+  mysql_table: string;
+  target_database: string;
+  target_table: string;
+  source_count?: number;
+  target_count?: number;
+  source_checksum?: string;
+  target_checksum?: string;
+  matched: boolean;
+  error_message?: string;
+  created_at: string;
 }
 
 // 任务日志
@@ -135,31 +385,314 @@ export interface TaskLog {
   log_level: string;
   message: string;
   created_at: string;
+  payload?: TaskLogPayload | null;
+}
+
+export type TaskLogLevelFilter = 'debug' | 'info' | 'warn' | 'error';
+
+// 任务日志查询，tail 时只返回最后 N 条并忽略 limit / offset / cursor
+export interface TaskLogQuery {
+  limit?: number;
+  offset?: number;
+  cursor?: string;
+  tail?: number;
+  // 最低日志级别
+  level?: TaskLogLevelFilter;
+}
+
+export interface TaskLogPage {
+  logs: TaskLog[];
+  total: number;
+  limit: number;
+  offset: number;
+  next_cursor?: string | null;
+}
+
+// 任务日志的结构化内容
+export interface TaskLogPayload {
+  step?: string;
+  ddl?: string;
+  duration_ms?: number;
+  objects?: string[];
+}
+
+// 同步任务步骤
+export type SyncStep =
+  | 'connect'
+  | 'fetch_schema'
+  | 'setup_source'
+  | 'create_rw_table'
+  | 'prepare_target'
+  | 'create_target_table'
+  | 'create_sink';
+
+export type StepStatus = 'pending' | 'running' | 'completed' | 'skipped' | 'failed';
+
+export interface TaskStep {
+  id: number;
+  task_id: number;
+  step_index: number;
+  step: SyncStep;
+  table_name?: string | null;
+  status: StepStatus;
+  started_at?: string | null;
+  completed_at?: string | null;
+  duration_ms?: number | null;
+  error_message?: string | null;
 }
 
 // 同步进度
 export interface SyncProgress {
   task_id: number;
   status: TaskStatus;
-  current_step: string;
-  total_steps: number;
+  current_step?: string | null;
+  // 已完成（含跳过）的步骤数
   current_step_index: number;
-  logs: string[];
+  total_steps: number;
+  percent: number;
+  steps: TaskStep[];
+  // 已处理完的表的结果
+  tables: TableSyncOutcome[];
+}
+
+export type TableSyncAction = 'skipped' | 'created' | 'updated';
+
+// 单张表的同步结果
+export interface TableSyncOutcome {
+  source_table: string;
+  target_table: string;
+  action: TableSyncAction;
 }
 
 // 任务历史查询
 export interface TaskHistoryQuery {
   status?: TaskStatus;
+  mysql_database?: string;
+  mysql_table?: string;
+  target_table?: string;
+  // RFC 3339 时间
+  started_after?: string;
+  started_before?: string;
   limit?: number;
   offset?: number;
+  // 上一页的 next_cursor，传入时忽略 offset
+  cursor?: string;
+  // approximate 时返回估算总数
+  count?: 'exact' | 'approximate';
+}
+
+// 批量重试失败任务，task_ids 和 failed_within_hours 至少提供一个
+export interface RetryBatchRequest {
+  task_ids?: number[];
+  failed_within_hours?: number;
+}
+
+// 单个任务的重试结果，需要审批时只返回 approval_id
+export interface RetriedTask {
+  task_id: number;
+  new_task_id?: number | null;
+  approval_id?: number | null;
+  error?: string | null;
+}
+
+export interface RetryBatchResult {
+  // 原任务 id → 新任务 id
+  mapping: Record<string, number>;
+  results: RetriedTask[];
 }
 
 // 分页任务响应
 export interface PaginatedTasksResponse {
   tasks: SyncTask[];
   total: number;
+  // total 是否为估算值
+  total_estimated: boolean;
   limit: number;
   offset: number;
+  // 下一页游标，没有更多数据时为空
+  next_cursor?: string;
+}
+
+// 任务每日统计（按日期、状态和连接聚合）
+export interface TaskDailyStat {
+  stat_date: string;
+  status: TaskStatus;
+  mysql_config_id: number;
+  rw_config_id: number;
+  sr_config_id: number;
+  task_count: number;
+  total_duration_ms: number;
+  max_duration_ms: number;
+}
+
+// 失败原因分类，按错误信息中的关键字识别
+export type ErrorCode =
+  | 'binlog_permission'
+  | 'binlog_config'
+  | 'permission_denied'
+  | 'authentication'
+  | 'timeout'
+  | 'connection'
+  | 'capacity'
+  | 'object_not_found'
+  | 'object_exists'
+  | 'primary_key'
+  | 'unsupported_type'
+  | 'sql_syntax'
+  | 'unknown';
+
+export interface ErrorCodeStat {
+  code: ErrorCode;
+  description: string;
+  count: number;
+  // 每个统计区间的次数，与 ErrorStats.buckets 一一对应
+  trend: number[];
+  last_seen: string;
+  last_task_id: number;
+  sample?: string | null;
+}
+
+// 按错误分类汇总的失败任务
+export interface ErrorStats {
+  since: string;
+  bucket: 'day' | 'week';
+  buckets: string[];
+  total: number;
+  codes: ErrorCodeStat[];
+}
+
+// 单表在 MySQL、RisingWave 和 StarRocks 三端的行数，lag 为 MySQL 与 StarRocks 的差值
+export interface SyncMetric {
+  id: number;
+  task_id: number;
+  mysql_database: string;
+  mysql_table: string;
+  target_database: string;
+  target_table: string;
+  source_count?: number;
+  rw_count?: number;
+  target_count?: number;
+  lag?: number;
+  error_message?: string;
+  sampled_at: string;
+}
+
+// ============ 通知渠道 ============
+
+export type NotificationEvent = 'alert' | 'task_failed' | 'task_completed';
+
+export type SmtpTls = 'none' | 'starttls' | 'tls';
+
+// 渠道类型和配置，kind 决定其余字段
+export type ChannelSettings =
+  | { kind: 'lark'; webhook_url: string }
+  | { kind: 'dingtalk'; webhook_url: string; secret?: string }
+  | { kind: 'slack'; webhook_url: string }
+  | { kind: 'webhook'; url: string; headers?: Record<string, string> }
+  | {
+      kind: 'email';
+      smtp_host: string;
+      smtp_port: number;
+      tls?: SmtpTls;
+      username?: string;
+      password?: string;
+      from: string;
+      to: string[];
+    };
+
+export interface NotificationChannelConfig {
+  id: number;
+  name: string;
+  settings: ChannelSettings;
+  events: NotificationEvent[];
+  enabled: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface NotificationChannelRequest {
+  name: string;
+  settings: ChannelSettings;
+  events?: NotificationEvent[];
+  enabled?: boolean;
+}
+
+// ============ 告警历史 ============
+
+export type AlertStatus = 'firing' | 'resolved';
+
+export interface AlertRecord {
+  id: number;
+  fingerprint: string;
+  alertname: string;
+  status: AlertStatus;
+  severity?: string;
+  labels: Record<string, string>;
+  annotations: Record<string, string>;
+  starts_at: string;
+  ends_at?: string;
+  receive_count: number;
+  last_received_at: string;
+  silenced: boolean;
+  resolved_at?: string;
+  resolved_by?: string;
+  created_at: string;
+}
+
+export interface AlertQuery {
+  status?: AlertStatus;
+  severity?: string;
+  fingerprint?: string;
+  limit?: number;
+  offset?: number;
+}
+
+// ============ 告警路由 ============
+
+export type MatchOp = '=' | '!=' | '=~' | '!~';
+
+export interface LabelMatcher {
+  label: string;
+  op?: MatchOp;
+  value: string;
+}
+
+export interface AlertRoute {
+  id: number;
+  name: string;
+  matchers: LabelMatcher[];
+  channel_ids: number[];
+  priority: number;
+  continue_matching: boolean;
+  enabled: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface AlertRouteRequest {
+  name: string;
+  matchers: LabelMatcher[];
+  channel_ids: number[];
+  priority?: number;
+  continue_matching?: boolean;
+  enabled?: boolean;
+}
+
+export interface AlertSilence {
+  id: number;
+  matchers: LabelMatcher[];
+  starts_at: string;
+  ends_at: string;
+  comment?: string;
+  created_by?: string;
+  created_at: string;
+}
+
+export interface AlertSilenceRequest {
+  matchers: LabelMatcher[];
+  starts_at?: string;
+  ends_at: string;
+  comment?: string;
 }
 
 // 泛型分页响应
@@ -194,6 +727,78 @@ export interface RwSource {
   definition?: string;
 }
 
+// 跨目标 schema 共享的 RisingWave 对象
+export type ManagedObjectType = 'secret' | 'source';
+
+export interface ManagedObject {
+  id: number;
+  rw_config_id: number;
+  object_type: ManagedObjectType;
+  object_name: string;
+  share_key?: string;
+  // 引用该对象的目标表数量
+  ref_count: number;
+  created_at: string;
+}
+
+// RisingWave 升级后检查 / 重建 sink
+export interface SinkPropertyChange {
+  key: string;
+  // 为空表示 RisingWave 上没有该属性
+  current?: string;
+  expected: string;
+}
+
+export interface SinkDrift {
+  target_database: string;
+  target_table: string;
+  sink_name: string;
+  changes: SinkPropertyChange[];
+}
+
+export interface RecreateSinksResult {
+  checked: number;
+  drifts: SinkDrift[];
+  skipped: { sink_name: string; reason: string }[];
+  // 有差异且不是 dry_run 时为重建任务 ID
+  task_id?: number;
+}
+
+export type CleanupMode = 'drop' | 'trash';
+
+export interface PipelineCleanupRequest {
+  rw_config_id: number;
+  target_database: string;
+  target_table: string;
+  mode: CleanupMode;
+  // 必须为 database.table，dry_run 时可以为空
+  confirm?: string;
+  dry_run?: boolean;
+}
+
+export interface PipelineCleanupResult {
+  statements: { system: string; sql: string }[];
+  // trash 模式下目标表的新名称
+  trash_table?: string;
+  task_id?: number;
+}
+
+export interface TaskTeardownRequest {
+  // 默认直接删除 StarRocks 目标表
+  mode?: CleanupMode;
+  // 同时删除不再被其他表使用的 CDC Source
+  drop_source?: boolean;
+  // 必须为任务 ID，dry_run 时可以为空
+  confirm?: string;
+  dry_run?: boolean;
+}
+
+export interface TaskTeardownResult {
+  statements: { system: string; sql: string }[];
+  trash_tables: string[];
+  task_id?: number;
+}
+
 export interface RwTable {
   id: number;
   name: string;
@@ -219,3 +824,424 @@ export interface RwSink {
   target_table?: string;
   definition?: string;
 }
+
+export interface RwView {
+  id: number;
+  name: string;
+  schema_name: string;
+  owner: number;
+  definition?: string;
+}
+
+export interface RwIndex {
+  id: number;
+  name: string;
+  schema_name: string;
+  owner: number;
+  // 索引所在的表或物化视图
+  table_name?: string;
+  definition?: string;
+}
+
+// 只有名称，不包含内容
+export interface RwSecret {
+  id: number;
+  name: string;
+  schema_name: string;
+  owner: number;
+}
+
+export interface RwSecretReference {
+  kind: 'source' | 'sink';
+  schema_name: string;
+  name: string;
+}
+
+// 同步目标 schema 中的 SECRET 及引用它的 Source / Sink
+export interface RwSecretUsage {
+  id: number;
+  name: string;
+  schema_name: string;
+  managed: boolean;
+  references: RwSecretReference[];
+}
+
+export interface DeleteUnusedSecretsResult {
+  secrets: string[];
+  failed: string[];
+}
+
+export type OrphanObjectKind = 'sink' | 'table' | 'source' | 'secret';
+
+export type OrphanReason = 'no_sync_task' | 'pipeline_cleaned_up' | 'target_table_missing' | 'unreferenced';
+
+// 按本工具命名规则识别、没有对应同步任务的 RisingWave 对象
+export interface OrphanObject {
+  kind: OrphanObjectKind;
+  schema_name: string;
+  name: string;
+  reason: OrphanReason;
+}
+
+export interface OrphanCleanupResult {
+  statements: string[];
+  dropped: string[];
+  failed: string[];
+  skipped: string[];
+}
+
+// SQL 控制台的查询结果，值为 RisingWave 的文本格式
+export interface SqlQueryResult {
+  columns: string[];
+  rows: (string | null)[][];
+  truncated: boolean;
+  elapsed_ms: number;
+}
+
+// 表或物化视图的列和主键，ambiguous 时创建 sink 需要选择主键列
+export interface RwRelationKeys {
+  columns: string[];
+  primary_keys: string[];
+  distribution_keys: string[];
+  ambiguous: boolean;
+}
+
+// 删除对象时会被 CASCADE 一起删除的下游对象
+export interface RwDependentObject {
+  id: number;
+  name: string;
+  schema_name: string;
+  object_type: string;
+}
+
+// StarRocks 目录浏览
+export interface SrTable {
+  name: string;
+  table_type?: string;
+  engine?: string;
+  table_rows?: string;
+  create_time?: string;
+  comment?: string;
+}
+
+export interface SrColumn {
+  name: string;
+  data_type: string;
+  is_nullable: boolean;
+  is_key: boolean;
+  default_value?: string;
+  comment?: string;
+}
+
+export interface SrTableSchema {
+  database: string;
+  table: string;
+  columns: SrColumn[];
+  create_table: string;
+}
+
+// SHOW PARTITIONS 的一行，未分区的表只有一个与表同名的分区
+export interface SrPartition {
+  name: string;
+  state?: string;
+  partition_key?: string;
+  range?: string;
+  distribution_key?: string;
+  buckets?: string;
+  data_size?: string;
+  row_count?: string;
+}
+
+export interface RwSinkError {
+  timestamp: string;
+  event_type: string;
+  info: string;
+}
+
+export interface RwSinkStatus {
+  id: number;
+  name: string;
+  schema_name: string;
+  connector: string;
+  state: 'running' | 'creating' | 'error';
+  managed: boolean;
+  is_decoupled?: boolean;
+  creating_progress?: string;
+  recent_error_count: number;
+  recent_errors: RwSinkError[];
+}
+
+export type PipelineHealth = 'green' | 'yellow' | 'red';
+
+export interface PipelineStatus {
+  source: string;
+  target: string;
+  health: PipelineHealth;
+  last_status: string;
+  last_task_at: string;
+  last_sync_at?: string;
+}
+
+export interface StatusSummary {
+  overall: PipelineHealth;
+  generated_at: string;
+  pipelines: PipelineStatus[];
+}
+
+// 管道详情：状态、最近一次任务和仍在触发的关联告警
+export interface PipelineDetail {
+  target: string;
+  status?: PipelineStatus;
+  last_task_id?: number;
+  active_incidents: AlertRecord[];
+}
+
+export interface PipelineSla {
+  source: string;
+  target: string;
+  uptime_7d: number;
+  sla_7d: number;
+  samples_7d: number;
+  uptime_30d: number;
+  sla_30d: number;
+  samples_30d: number;
+}
+
+export interface SlaBreach {
+  source: string;
+  target: string;
+  worst_health: PipelineHealth;
+  started_at: string;
+  ended_at?: string;
+  samples: number;
+}
+
+// 功能开关
+export type FeatureName = 'controller_mode' | 'auto_alter_propagation' | 'auto_recovery';
+
+export interface FeatureFlag {
+  workspace: string;
+  name: FeatureName;
+  enabled: boolean;
+  description?: string;
+  updated_at?: string;
+}
+
+// 运行时设置
+export type SettingName =
+  | 'sr.default_buckets'
+  | 'sr.default_replication_num'
+  | 'sr.default_storage_medium'
+  | 'rw.default_schema'
+  | 'ddl.retry_max_attempts'
+  | 'ddl.retry_backoff_ms'
+  | 'task_logs.max_rows'
+  | 'task_logs.retention_days'
+  | 'pool.idle_ttl_secs'
+  | 'sql_console.max_rows'
+  | 'sql_console.timeout_secs';
+
+export type SettingSource = 'database' | 'env' | 'default';
+
+export interface SettingValue {
+  name: SettingName;
+  value?: string | null;
+  source: SettingSource;
+  description: string;
+  env_var: string;
+  updated_at?: string | null;
+}
+
+// 同步审批
+export type ApprovalStatus = 'pending' | 'approved' | 'rejected';
+
+export interface ApprovalRequest {
+  id: number;
+  status: ApprovalStatus;
+  reason: string;
+  requests: string; // JSON 序列化的 SyncRequest[]
+  requested_by: string;
+  requested_at: string;
+  decided_by?: string;
+  decided_at?: string;
+  comment?: string;
+  task_id?: number;
+}
+
+// 任务 / 管道评论
+export type CommentSubject = 'task' | 'pipeline';
+
+export interface Comment {
+  id: number;
+  subject_type: CommentSubject;
+  subject_id: string;
+  author: string;
+  body: string;
+  created_at: string;
+}
+
+// 登录认证
+// 用户角色：viewer 只读，operator 可执行同步和删除对象，admin 另外可管理用户和设置
+export type Role = 'viewer' | 'operator' | 'admin';
+
+export interface User {
+  id: number;
+  username: string;
+  role: Role;
+  enabled: boolean;
+  last_login_at?: string | null;
+  created_at: string;
+}
+
+export interface LoginResponse {
+  token: string;
+  expires_at: string;
+  user: User;
+}
+
+export interface ApiToken {
+  id: number;
+  user_id: number;
+  name: string;
+  token_prefix: string;
+  expires_at?: string | null;
+  last_used_at?: string | null;
+  created_at: string;
+}
+
+// 新建的 API Token，token 明文只返回一次
+export interface CreatedApiToken extends ApiToken {
+  token: string;
+}
+
+// ============ 审计日志 ============
+
+export interface AuditLog {
+  id: number;
+  actor?: string | null;
+  action: string;
+  target: string;
+  engine?: string | null;
+  sql_text?: string | null;
+  task_id?: number | null;
+  success: boolean;
+  error_message?: string | null;
+  created_at: string;
+}
+
+export interface AuditQuery {
+  actor?: string;
+  action?: string;
+  target?: string;
+  task_id?: number;
+  limit?: number;
+  offset?: number;
+}
+
+// ============ 全局搜索 ============
+
+export type SearchResultKind = 'connection' | 'mapping' | 'task' | 'rw_object' | 'sr_table';
+
+export interface SearchResult {
+  kind: SearchResultKind;
+  name: string;
+  detail?: string | null;
+  id?: number | null;
+  config_id?: number | null;
+  schema?: string | null;
+}
+
+// 无法访问的集群记录在 errors 中
+export interface SearchResponse {
+  query: string;
+  results: SearchResult[];
+  errors: string[];
+}
+
+// ============ 定时同步 ============
+
+// 一次计划运行时间，local 为定时同步时区中带偏移量的本地时间
+export interface ScheduledRun {
+  local: string;
+  utc: string;
+}
+
+export interface SyncSchedule {
+  id: number;
+  name: string;
+  // 5 段（分 时 日 月 周）或带秒的 6 段 cron 表达式
+  cron: string;
+  // IANA 时区名，例如 Asia/Shanghai
+  timezone: string;
+  requests: SyncRequest[];
+  enabled: boolean;
+  next_run_at?: string | null;
+  last_run_at?: string | null;
+  last_task_id?: number | null;
+  last_error?: string | null;
+  created_by?: string | null;
+  created_at: string;
+  updated_at: string;
+  next_runs: ScheduledRun[];
+}
+
+export interface ScheduleRequest {
+  name: string;
+  cron: string;
+  timezone: string;
+  requests: SyncRequest[];
+  enabled?: boolean;
+}
+
+// 同步映射中的一张表，目标库/表为空时使用映射的默认值或模板
+export interface MappingTable {
+  mysql_database: string;
+  mysql_table: string;
+  target_database?: string | null;
+  target_table?: string | null;
+  row_filter?: string | null;
+  partition?: SyncRequest['partition'];
+  column_mapping?: Record<string, string>;
+  nested_columns?: SyncRequest['nested_columns'];
+  spatial_columns?: SyncRequest['spatial_columns'];
+}
+
+export interface SyncMappingRequest {
+  name: string;
+  description?: string | null;
+  mysql_config_id: number;
+  rw_config_id: number;
+  sr_config_id: number;
+  target_database?: string | null;
+  tables: MappingTable[];
+  options?: Partial<SyncOptions>;
+  // 目标表名模板，例如 ods_{table}
+  target_table_template?: string | null;
+  // 更新时期望的版本号，不一致时服务端返回 409
+  version?: number;
+}
+
+// 同步映射：保存的同步定义，可一键重新提交
+export interface SyncMapping extends SyncMappingRequest {
+  id: number;
+  options: SyncOptions;
+  last_task_id?: number | null;
+  last_run_at?: string | null;
+  // 暂停后 sink 停止写入，相关定时同步停用
+  paused: boolean;
+  paused_at?: string | null;
+  paused_schedule_ids: number[];
+  version: number;
+  created_by?: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+// 暂停或恢复管道的结果
+export interface PipelinePauseResult {
+  mapping_id: number;
+  paused: boolean;
+  sinks: string[];
+  failed: string[];
+  schedule_ids: number[];
+}